@@ -34,7 +34,7 @@ echo.
 
 :: Step 3: Build VST plugin
 echo [3/3] Building VST plugin (noobsynth_vst.dll)...
-cargo build -p noobsynth_vst --release
+cargo build -p noobsynth_vst --profile plugin
 if errorlevel 1 (
     echo ERROR: VST build failed
     exit /b 1
@@ -49,7 +49,7 @@ echo ============================================
 echo.
 echo Output files:
 echo   - target\release\noobsynth3.exe
-echo   - target\release\noobsynth_vst.dll
+echo   - target\plugin\noobsynth_vst.dll
 echo.
 echo To use the VST:
 echo   1. Copy both files to the same folder
@@ -61,7 +61,7 @@ if "%1"=="--dist" (
     echo Creating dist folder...
     if not exist "dist-vst" mkdir dist-vst
     copy /Y "target\release\noobsynth3.exe" "dist-vst\" >nul
-    copy /Y "target\release\noobsynth_vst.dll" "dist-vst\" >nul
+    copy /Y "target\plugin\noobsynth_vst.dll" "dist-vst\" >nul
     echo Files copied to dist-vst\
     echo.
 )
@@ -0,0 +1,127 @@
+//! Criterion benchmarks for the hot DSP paths. Whole-graph rendering is
+//! benchmarked in dsp-graph (`benches/graph_bench.rs`).
+//!
+//! Run with `cargo bench -p dsp-core`. HTML reports are written to
+//! `target/criterion/report/index.html`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use dsp_core::{Reverb, ReverbInputs, ReverbParams, Sample, Vcf, VcfInputs, VcfParams, Vco, VcoInputs, VcoParams};
+
+const SAMPLE_RATE: f32 = 48_000.0;
+const FRAME_SIZES: [usize; 3] = [128, 512, 4096];
+
+/// Deterministic saw-ish test signal used as filter/reverb input.
+fn test_signal(frames: usize) -> Vec<Sample> {
+    (0..frames)
+        .map(|i| ((i % 100) as Sample / 50.0) - 1.0)
+        .collect()
+}
+
+fn bench_vco(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vco");
+    for &frames in &FRAME_SIZES {
+        group.throughput(Throughput::Elements(frames as u64));
+        group.bench_with_input(BenchmarkId::new("saw_unison4", frames), &frames, |b, &frames| {
+            let mut vco = Vco::new(SAMPLE_RATE);
+            let mut out = vec![0.0; frames];
+            let mut sub = vec![0.0; frames];
+            b.iter(|| {
+                let params = VcoParams {
+                    base_freq: &[220.0],
+                    waveform: &[2.0],
+                    wave_morph: &[0.0],
+                    pwm: &[0.5],
+                    fm_lin_depth: &[0.0],
+                    fm_exp_depth: &[0.0],
+                    unison: &[4.0],
+                    detune: &[7.0],
+                    sub_mix: &[0.3],
+                    sub_oct: &[1.0],
+                    stereo_spread: &[0.0],
+                    oversample: &[0.0],
+                };
+                let inputs = VcoInputs {
+                    pitch: None,
+                    fm_lin: None,
+                    fm_audio: None,
+                    fm_exp: None,
+                    pwm: None,
+                    sync: None,
+                };
+                vco.process_block(&mut out, Some(&mut sub), None, inputs, params);
+                black_box(&out);
+            });
+        });
+    }
+    group.finish();
+}
+
+fn bench_vcf(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vcf");
+    for (name, model, slope) in [("svf_12db", 0.0, 0.0), ("ladder_24db", 1.0, 1.0)] {
+        for &frames in &FRAME_SIZES {
+            group.throughput(Throughput::Elements(frames as u64));
+            group.bench_with_input(BenchmarkId::new(name, frames), &frames, |b, &frames| {
+                let mut vcf = Vcf::new(SAMPLE_RATE);
+                let input = test_signal(frames);
+                let mut out = vec![0.0; frames];
+                let model = [model];
+                let slope = [slope];
+                b.iter(|| {
+                    let params = VcfParams {
+                        cutoff: &[1200.0],
+                        resonance: &[0.4],
+                        drive: &[0.2],
+                        env_amount: &[0.0],
+                        mod_amount: &[0.0],
+                        key_track: &[0.0],
+                        model: &model,
+                        mode: &[0.0],
+                        slope: &slope,
+                    };
+                    let inputs = VcfInputs {
+                        audio: Some(&input),
+                        mod_in: None,
+                        env: None,
+                        key: None,
+                    };
+                    vcf.process_block(&mut out, inputs, params);
+                    black_box(&out);
+                });
+            });
+        }
+    }
+    group.finish();
+}
+
+fn bench_reverb(c: &mut Criterion) {
+    let mut group = c.benchmark_group("reverb");
+    for &frames in &FRAME_SIZES {
+        group.throughput(Throughput::Elements(frames as u64));
+        group.bench_with_input(BenchmarkId::new("stereo", frames), &frames, |b, &frames| {
+            let mut reverb = Reverb::new(SAMPLE_RATE);
+            let input = test_signal(frames);
+            let mut out_l = vec![0.0; frames];
+            let mut out_r = vec![0.0; frames];
+            b.iter(|| {
+                let params = ReverbParams {
+                    time: &[0.7],
+                    damp: &[0.4],
+                    pre_delay: &[20.0],
+                    mix: &[0.5],
+                    wet_only: &[0.0],
+                };
+                let inputs = ReverbInputs {
+                    input_l: Some(&input),
+                    input_r: Some(&input),
+                };
+                reverb.process_block(&mut out_l, &mut out_r, inputs, params);
+                black_box(&out_l);
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_vco, bench_vcf, bench_reverb);
+criterion_main!(benches);
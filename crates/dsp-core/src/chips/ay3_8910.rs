@@ -103,6 +103,11 @@ impl Ay3_8910 {
         chip
     }
 
+    /// Update the output sample rate (chip clock is unchanged).
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+    }
+
     /// Reset the chip to initial state.
     pub fn reset(&mut self) {
         self.regs = [0; 16];
@@ -149,6 +149,16 @@ pub fn saturate(value: f32) -> f32 {
     value.tanh()
 }
 
+/// Dry gain of an effect's dry/wet mix.
+///
+/// `1 - mix`, or 0 when `wet_only` is set (>= 0.5): an effect on a send
+/// bus must not add the dry signal again, whatever its `mix`. The wet gain
+/// stays `mix`, so `mix = 0` with `wet_only` is silent.
+#[inline]
+pub fn dry_gain(mix: f32, wet_only: f32) -> f32 {
+    if wet_only >= 0.5 { 0.0 } else { 1.0 - mix }
+}
+
 /// Polynomial Band-Limited Step (polyBLEP) anti-aliasing.
 ///
 /// Reduces aliasing artifacts in oscillators by smoothing discontinuities
@@ -222,6 +232,66 @@ pub fn freq_to_midi(freq: f32) -> f32 {
     A4_MIDI as f32 + SEMITONES_PER_OCTAVE * (freq / A4_FREQ).log2()
 }
 
+/// MIDI note number at 0 V on the V/Oct scale (C4).
+pub const C4_MIDI: u8 = 60;
+
+/// Convert a MIDI note number to V/Oct CV (C4 = 0 V).
+///
+/// # Example
+///
+/// ```
+/// use dsp_core::common::midi_note_to_cv;
+///
+/// assert_eq!(midi_note_to_cv(60), 0.0);
+/// assert_eq!(midi_note_to_cv(72), 1.0);
+/// assert_eq!(midi_note_to_cv(57), -0.25);
+/// ```
+#[inline]
+pub fn midi_note_to_cv(note: u8) -> f32 {
+    semitones_to_cv(note as f32 - C4_MIDI as f32)
+}
+
+/// Convert a semitone offset to a V/Oct CV offset.
+#[inline]
+pub fn semitones_to_cv(semitones: f32) -> f32 {
+    semitones / SEMITONES_PER_OCTAVE
+}
+
+/// Convert V/Oct CV to frequency in Hz (0 V = C4, 261.63 Hz).
+///
+/// # Example
+///
+/// ```
+/// use dsp_core::common::cv_to_frequency;
+///
+/// assert!((cv_to_frequency(0.0) - 261.63).abs() < 0.01);
+/// assert!((cv_to_frequency(1.0) - 523.25).abs() < 0.01);
+/// ```
+#[inline]
+pub fn cv_to_frequency(cv: f32) -> f32 {
+    midi_to_freq(C4_MIDI as i32) * cv.exp2()
+}
+
+/// Convert frequency in Hz to V/Oct CV (C4 = 0 V).
+///
+/// Non-positive frequencies map to -5 V, far below the audible range.
+///
+/// # Example
+///
+/// ```
+/// use dsp_core::common::{cv_to_frequency, frequency_to_cv};
+///
+/// assert!((frequency_to_cv(440.0) - 0.75).abs() < 1e-4);
+/// assert!((frequency_to_cv(cv_to_frequency(-1.5)) + 1.5).abs() < 1e-4);
+/// ```
+#[inline]
+pub fn frequency_to_cv(hz: f32) -> f32 {
+    if hz <= 0.0 {
+        return -5.0;
+    }
+    (hz / midi_to_freq(C4_MIDI as i32)).log2()
+}
+
 /// Get musical scale intervals (semitones from root).
 /// 0=Off, 1=Chrom, 2=Maj, 3=Min, 4=Dor, 5=Lyd, 6=Mix, 7=MajPent, 8=MinPent
 pub fn get_scale_notes(scale_idx: i32) -> &'static [i32] {
@@ -83,6 +83,11 @@ impl Clap909 {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Silence the voice and clear its filters, as after `new`.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.sample_rate);
+    }
+
     fn white_noise(&mut self) -> f32 {
         self.noise_state ^= self.noise_state << 13;
         self.noise_state ^= self.noise_state >> 17;
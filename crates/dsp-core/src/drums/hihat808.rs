@@ -91,6 +91,11 @@ impl HiHat808 {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Silence the voice and clear its filters, as after `new`.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.sample_rate);
+    }
+
     /// Process a block of samples.
     pub fn process_block(
         &mut self,
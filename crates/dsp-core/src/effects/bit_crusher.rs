@@ -0,0 +1,160 @@
+//! Bit crusher / sample rate reducer.
+//!
+//! Lo-fi effect: the signal is held for `downsample` samples (sample rate
+//! reduction) and quantized to `bits` of resolution.
+
+use crate::common::{input_at, sample_at, Sample};
+
+/// Bit crusher effect.
+///
+/// # Example
+///
+/// ```ignore
+/// use dsp_core::effects::{BitCrusher, BitCrusherParams};
+///
+/// let mut crusher = BitCrusher::new();
+/// let mut output = [0.0f32; 128];
+/// crusher.process_block(&mut output, input, params);
+/// ```
+pub struct BitCrusher {
+    /// Samples left before the next sample is captured.
+    countdown: f32,
+    /// Quantized sample being held.
+    held: f32,
+    /// Anti-alias lowpass state.
+    lowpass: f32,
+    seed: u32,
+}
+
+/// Parameters for BitCrusher.
+pub struct BitCrusherParams<'a> {
+    /// Resolution in bits (1-16, fractional values allowed)
+    pub bits: &'a [Sample],
+    /// Sample rate reduction factor (1-64)
+    pub downsample: &'a [Sample],
+    /// Dry/wet mix (0-1)
+    pub mix: &'a [Sample],
+    /// Lowpass the input before decimating (0 = off, 1 = on)
+    pub antialias: &'a [Sample],
+    /// Add dither before quantizing (0 = off, 1 = on)
+    pub dither: &'a [Sample],
+}
+
+impl Default for BitCrusher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BitCrusher {
+    pub fn new() -> Self {
+        Self {
+            countdown: 0.0,
+            held: 0.0,
+            lowpass: 0.0,
+            seed: 0x2468_ace1,
+        }
+    }
+
+    /// Drop the held sample and the lowpass memory.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Generate next random value using LCG (-1 to 1).
+    fn next_random(&mut self) -> f32 {
+        self.seed = self
+            .seed
+            .wrapping_mul(1664525)
+            .wrapping_add(1013904223);
+        let raw = (self.seed >> 9) as f32 / 8_388_608.0;
+        raw * 2.0 - 1.0
+    }
+
+    /// Quantize to `levels - 1` steps over -1..1.
+    fn quantize(&mut self, value: f32, levels: f32, dither: bool) -> f32 {
+        let steps = levels - 1.0;
+        let mut scaled = (value.clamp(-1.0, 1.0) * 0.5 + 0.5) * steps;
+        if dither {
+            // Triangular dither, +/- 1 step peak.
+            scaled += (self.next_random() + self.next_random()) * 0.5;
+        }
+        (scaled.round().clamp(0.0, steps) / steps) * 2.0 - 1.0
+    }
+
+    /// Process a block of audio.
+    pub fn process_block(
+        &mut self,
+        output: &mut [Sample],
+        input: Option<&[Sample]>,
+        params: BitCrusherParams<'_>,
+    ) {
+        for (i, out) in output.iter_mut().enumerate() {
+            let bits = sample_at(params.bits, i, 8.0).clamp(1.0, 16.0);
+            let factor = sample_at(params.downsample, i, 1.0).clamp(1.0, 64.0);
+            let mix = sample_at(params.mix, i, 1.0).clamp(0.0, 1.0);
+            let antialias = sample_at(params.antialias, i, 0.0) >= 0.5;
+            let dither = sample_at(params.dither, i, 0.0) >= 0.5;
+
+            let dry = input_at(input, i);
+            let source = if antialias {
+                // One-pole lowpass at the reduced Nyquist (sr / 2 / factor).
+                let coeff = 1.0 - (-std::f32::consts::PI / factor).exp();
+                self.lowpass += coeff * (dry - self.lowpass);
+                self.lowpass
+            } else {
+                self.lowpass = dry;
+                dry
+            };
+
+            if self.countdown <= 0.0 {
+                self.held = self.quantize(source, bits.exp2(), dither);
+                self.countdown += factor;
+            }
+            self.countdown -= 1.0;
+
+            *out = dry * (1.0 - mix) + self.held * mix;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crush(input: &[Sample], bits: f32, downsample: f32) -> Vec<Sample> {
+        let mut crusher = BitCrusher::new();
+        let mut output = vec![0.0; input.len()];
+        let params = BitCrusherParams {
+            bits: &[bits],
+            downsample: &[downsample],
+            mix: &[1.0],
+            antialias: &[0.0],
+            dither: &[0.0],
+        };
+        crusher.process_block(&mut output, Some(input), params);
+        output
+    }
+
+    fn ramp(len: usize) -> Vec<Sample> {
+        (0..len).map(|i| i as f32 / (len - 1) as f32 * 2.0 - 1.0).collect()
+    }
+
+    #[test]
+    fn test_four_bits_gives_at_most_16_levels() {
+        let mut levels: Vec<Sample> = crush(&ramp(4096), 4.0, 1.0);
+        levels.sort_by(f32::total_cmp);
+        levels.dedup();
+        assert!(levels.len() <= 16, "{} levels", levels.len());
+        assert!(levels.len() > 8);
+    }
+
+    #[test]
+    fn test_downsample_holds_samples() {
+        let output = crush(&ramp(64), 16.0, 8.0);
+        for block in output.chunks(8) {
+            assert!(block.iter().all(|&sample| sample == block[0]));
+        }
+        assert_ne!(output[0], output[8]);
+    }
+}
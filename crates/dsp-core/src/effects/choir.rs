@@ -105,6 +105,13 @@ impl Choir {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Clear the formant filters and restart the LFO.
+    pub fn reset(&mut self) {
+        self.phase = 0.0;
+        self.filters_l = [FormantFilter::default(); 3];
+        self.filters_r = [FormantFilter::default(); 3];
+    }
+
     /// Process a block of stereo audio.
     pub fn process_block(
         &mut self,
@@ -3,7 +3,7 @@
 //! Creates a thickening effect by mixing the dry signal with
 //! a modulated delayed version.
 
-use crate::common::{clamp, input_at, sample_at, Sample};
+use crate::common::{clamp, dry_gain, input_at, sample_at, Sample};
 
 /// Stereo chorus effect.
 ///
@@ -51,6 +51,8 @@ pub struct ChorusParams<'a> {
     pub feedback: &'a [Sample],
     /// Stereo spread (0-1)
     pub spread: &'a [Sample],
+    /// Wet only (>= 0.5 = no dry signal, for send buses)
+    pub wet_only: &'a [Sample],
 }
 
 impl Chorus {
@@ -73,6 +75,14 @@ impl Chorus {
         self.allocate_buffers();
     }
 
+    /// Empty the delay lines and restart the LFO.
+    pub fn reset(&mut self) {
+        self.buffer_l.fill(0.0);
+        self.buffer_r.fill(0.0);
+        self.write_index = 0;
+        self.phase = 0.0;
+    }
+
     fn allocate_buffers(&mut self) {
         let max_delay_ms = 50.0;
         let max_samples = ((max_delay_ms / 1000.0) * self.sample_rate).ceil() as usize + 2;
@@ -142,7 +152,7 @@ impl Chorus {
             self.buffer_r[self.write_index] = input_r + delayed_r * feedback;
 
             let wet = clamp(mix, 0.0, 1.0);
-            let dry = 1.0 - wet;
+            let dry = dry_gain(wet, sample_at(params.wet_only, i, 0.0));
 
             out_l[i] = input_l * dry + delayed_l * wet;
             out_r[i] = input_r * dry + delayed_r * wet;
@@ -1,13 +1,16 @@
 //! Dynamics compressor effect.
 //!
-//! Standard audio compressor with threshold, ratio, attack, release,
-//! makeup gain, and dry/wet mix controls. Supports stereo with linked
-//! detection (both channels share the same envelope for consistent imaging).
+//! Feed-forward compressor with threshold, ratio, soft knee, attack, release,
+//! makeup gain, and dry/wet mix controls. The detector follows either the
+//! peak or the RMS level. Supports stereo with linked detection (both
+//! channels share the same envelope for consistent imaging), or detection
+//! from an external sidechain signal.
 
 use crate::common::{input_at, sample_at, Sample};
 
 /// Compressor state for envelope follower.
 pub struct Compressor {
+    /// Smoothed detector value: level for peak detection, squared level for RMS.
     envelope: f32,
     sample_rate: f32,
 }
@@ -20,6 +23,24 @@ impl Compressor {
             sample_rate,
         }
     }
+
+    /// Update the sample rate.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+    }
+
+    /// Release any gain reduction in progress.
+    pub fn reset(&mut self) {
+        self.envelope = 0.0;
+    }
+}
+
+/// Input signals for the Compressor effect.
+pub struct CompressorInputs<'a> {
+    pub input_l: Option<&'a [Sample]>,
+    pub input_r: Option<&'a [Sample]>,
+    /// Sidechain: drives the detector instead of the input when connected
+    pub sidechain: Option<&'a [Sample]>,
 }
 
 /// Parameters for the Compressor effect.
@@ -32,70 +53,75 @@ pub struct CompressorParams<'a> {
     pub attack: &'a [Sample],
     /// Release time in ms (10 to 2000)
     pub release: &'a [Sample],
+    /// Soft knee width in dB (0 = hard knee, up to 24)
+    pub knee: &'a [Sample],
     /// Makeup gain in dB (-24 to +24)
     pub makeup: &'a [Sample],
     /// Dry/wet mix (0 to 1)
     pub mix: &'a [Sample],
+    /// Level detector (0 = peak, 1 = RMS)
+    pub detector: &'a [Sample],
 }
 
 impl Compressor {
+    /// Run the detector on `key` and return the compressor gain (linear,
+    /// without makeup) for sample `i`.
+    fn next_gain(&mut self, key: Sample, i: usize, params: &CompressorParams<'_>) -> f32 {
+        let threshold_db = sample_at(params.threshold, i, -20.0).clamp(-60.0, 0.0);
+        let ratio = sample_at(params.ratio, i, 4.0).clamp(1.0, 20.0);
+        let attack_ms = sample_at(params.attack, i, 10.0).clamp(0.5, 200.0);
+        let release_ms = sample_at(params.release, i, 100.0).clamp(10.0, 2000.0);
+        let knee_db = sample_at(params.knee, i, 0.0).clamp(0.0, 24.0);
+        let rms = sample_at(params.detector, i, 0.0) >= 0.5;
+
+        // Calculate attack and release coefficients
+        let attack_coeff = (-2.0 * std::f32::consts::PI * 1000.0 / (attack_ms * self.sample_rate)).exp();
+        let release_coeff = (-2.0 * std::f32::consts::PI * 1000.0 / (release_ms * self.sample_rate)).exp();
+
+        // Envelope follower on the peak, or on the power for RMS detection
+        let detected = if rms { key * key } else { key.abs() };
+        let coeff = if detected > self.envelope { attack_coeff } else { release_coeff };
+        self.envelope = coeff * self.envelope + (1.0 - coeff) * detected;
+
+        let level = if rms { self.envelope.sqrt() } else { self.envelope };
+        let level_db = linear_to_db(level);
+        db_to_linear(static_curve(level_db, threshold_db, ratio, knee_db) - level_db)
+    }
+
     /// Process a stereo block of audio through the compressor.
-    /// Uses linked detection (max of both channels) to preserve stereo image.
+    /// Uses linked detection (max of both channels) to preserve stereo image,
+    /// or the sidechain signal when one is given (e.g. a kick ducking a pad).
+    ///
+    /// `gain_reduction` receives the reduction applied per sample as
+    /// `1 - gain` (0 = none, towards 1 = heavily compressed), for metering
+    /// or reuse as a modulation signal.
     pub fn process_block_stereo(
         &mut self,
         out_l: &mut [Sample],
         out_r: &mut [Sample],
-        in_l: Option<&[Sample]>,
-        in_r: Option<&[Sample]>,
+        mut gain_reduction: Option<&mut [Sample]>,
+        inputs: CompressorInputs<'_>,
         params: CompressorParams<'_>,
     ) {
         let frames = out_l.len().min(out_r.len());
-        if frames == 0 {
-            return;
-        }
 
         for i in 0..frames {
-            let threshold_db = sample_at(params.threshold, i, -20.0).clamp(-60.0, 0.0);
-            let ratio = sample_at(params.ratio, i, 4.0).clamp(1.0, 20.0);
-            let attack_ms = sample_at(params.attack, i, 10.0).clamp(0.5, 200.0);
-            let release_ms = sample_at(params.release, i, 100.0).clamp(10.0, 2000.0);
-            let makeup_db = sample_at(params.makeup, i, 0.0).clamp(-24.0, 24.0);
+            let makeup_lin = db_to_linear(sample_at(params.makeup, i, 0.0).clamp(-24.0, 24.0));
             let mix = sample_at(params.mix, i, 1.0).clamp(0.0, 1.0);
 
-            let sample_l = input_at(in_l, i);
-            let sample_r = input_at(in_r, i);
-
-            // Convert threshold and makeup from dB to linear
-            let threshold_lin = db_to_linear(threshold_db);
-            let makeup_lin = db_to_linear(makeup_db);
+            let sample_l = input_at(inputs.input_l, i);
+            let sample_r = input_at(inputs.input_r, i);
 
-            // Calculate attack and release coefficients
-            let attack_coeff = (-2.0 * std::f32::consts::PI * 1000.0 / (attack_ms * self.sample_rate)).exp();
-            let release_coeff = (-2.0 * std::f32::consts::PI * 1000.0 / (release_ms * self.sample_rate)).exp();
-
-            // Linked stereo detection - use max of both channels
-            let input_peak = sample_l.abs().max(sample_r.abs());
-
-            // Envelope follower (peak detection)
-            if input_peak > self.envelope {
-                // Attack phase
-                self.envelope = attack_coeff * self.envelope + (1.0 - attack_coeff) * input_peak;
-            } else {
-                // Release phase
-                self.envelope = release_coeff * self.envelope + (1.0 - release_coeff) * input_peak;
-            }
-
-            // Calculate gain reduction
-            let gain = if self.envelope > threshold_lin {
-                // Over threshold - apply compression
-                let over_db = linear_to_db(self.envelope / threshold_lin);
-                let reduced_db = over_db / ratio;
-                let target_level = threshold_lin * db_to_linear(reduced_db);
-                target_level / self.envelope.max(1e-10)
-            } else {
-                // Under threshold - no compression
-                1.0
+            // Linked stereo detection - use the louder channel
+            let key = match inputs.sidechain {
+                Some(key) => input_at(Some(key), i),
+                None if sample_l.abs() >= sample_r.abs() => sample_l,
+                None => sample_r,
             };
+            let gain = self.next_gain(key, i, &params);
+            if let Some(reduction) = gain_reduction.as_deref_mut().and_then(|gr| gr.get_mut(i)) {
+                *reduction = 1.0 - gain;
+            }
 
             // Apply gain and makeup
             let compressed_l = sample_l * gain * makeup_lin;
@@ -115,60 +141,33 @@ impl Compressor {
         input: Option<&[Sample]>,
         params: CompressorParams<'_>,
     ) {
-        if output.is_empty() {
-            return;
-        }
-
-        for i in 0..output.len() {
-            let threshold_db = sample_at(params.threshold, i, -20.0).clamp(-60.0, 0.0);
-            let ratio = sample_at(params.ratio, i, 4.0).clamp(1.0, 20.0);
-            let attack_ms = sample_at(params.attack, i, 10.0).clamp(0.5, 200.0);
-            let release_ms = sample_at(params.release, i, 100.0).clamp(10.0, 2000.0);
-            let makeup_db = sample_at(params.makeup, i, 0.0).clamp(-24.0, 24.0);
+        for (i, out) in output.iter_mut().enumerate() {
+            let makeup_lin = db_to_linear(sample_at(params.makeup, i, 0.0).clamp(-24.0, 24.0));
             let mix = sample_at(params.mix, i, 1.0).clamp(0.0, 1.0);
 
             let in_sample = input_at(input, i);
+            let gain = self.next_gain(in_sample, i, &params);
 
-            // Convert threshold and makeup from dB to linear
-            let threshold_lin = db_to_linear(threshold_db);
-            let makeup_lin = db_to_linear(makeup_db);
-
-            // Calculate attack and release coefficients
-            let attack_coeff = (-2.0 * std::f32::consts::PI * 1000.0 / (attack_ms * self.sample_rate)).exp();
-            let release_coeff = (-2.0 * std::f32::consts::PI * 1000.0 / (release_ms * self.sample_rate)).exp();
-
-            // Envelope follower (peak detection)
-            let input_abs = in_sample.abs();
-            if input_abs > self.envelope {
-                // Attack phase
-                self.envelope = attack_coeff * self.envelope + (1.0 - attack_coeff) * input_abs;
-            } else {
-                // Release phase
-                self.envelope = release_coeff * self.envelope + (1.0 - release_coeff) * input_abs;
-            }
-
-            // Calculate gain reduction
-            let gain = if self.envelope > threshold_lin {
-                // Over threshold - apply compression
-                let over_db = linear_to_db(self.envelope / threshold_lin);
-                let reduced_db = over_db / ratio;
-                let target_level = threshold_lin * db_to_linear(reduced_db);
-                target_level / self.envelope.max(1e-10)
-            } else {
-                // Under threshold - no compression
-                1.0
-            };
-
-            // Apply gain and makeup
             let compressed = in_sample * gain * makeup_lin;
-
-            // Mix dry/wet
-            let dry = 1.0 - mix;
-            output[i] = in_sample * dry + compressed * mix;
+            *out = in_sample * (1.0 - mix) + compressed * mix;
         }
     }
 }
 
+/// Output level in dB for a detected level, with a quadratic soft knee of
+/// `knee_db` centred on the threshold.
+fn static_curve(level_db: f32, threshold_db: f32, ratio: f32, knee_db: f32) -> f32 {
+    let over = level_db - threshold_db;
+    if 2.0 * over <= -knee_db {
+        level_db
+    } else if 2.0 * over < knee_db {
+        let x = over + knee_db * 0.5;
+        level_db + (1.0 / ratio - 1.0) * x * x / (2.0 * knee_db)
+    } else {
+        threshold_db + over / ratio
+    }
+}
+
 /// Convert decibels to linear amplitude.
 #[inline]
 fn db_to_linear(db: f32) -> f32 {
@@ -180,3 +179,93 @@ fn db_to_linear(db: f32) -> f32 {
 fn linear_to_db(linear: f32) -> f32 {
     20.0 * linear.max(1e-10).log10()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f32 = 48000.0;
+
+    fn params<'a>() -> CompressorParams<'a> {
+        CompressorParams {
+            threshold: &[-20.0],
+            ratio: &[4.0],
+            attack: &[1.0],
+            release: &[100.0],
+            knee: &[0.0],
+            makeup: &[0.0],
+            mix: &[1.0],
+            detector: &[0.0],
+        }
+    }
+
+    /// Run `input` (dual mono) through a fresh compressor; returns the left output.
+    fn compress(input: &[Sample], sidechain: Option<&[Sample]>) -> Vec<Sample> {
+        let mut compressor = Compressor::new(SAMPLE_RATE);
+        let mut out_l = vec![0.0; input.len()];
+        let mut out_r = vec![0.0; input.len()];
+        let inputs = CompressorInputs { input_l: Some(input), input_r: Some(input), sidechain };
+        compressor.process_block_stereo(&mut out_l, &mut out_r, None, inputs, params());
+        out_l
+    }
+
+    #[test]
+    fn test_transient_engages_and_releases_gain_reduction() {
+        let burst = (0.1 * SAMPLE_RATE) as usize;
+        let release = (0.1 * SAMPLE_RATE) as usize;
+        // Full-scale burst, then a steady level 6 dB under the threshold.
+        let input: Vec<Sample> = (0..burst + 2 * release)
+            .map(|i| if i < burst { if i % 2 == 0 { 1.0 } else { -1.0 } } else { 0.05 })
+            .collect();
+        let output = compress(&input, None);
+        let gain = |i: usize| output[i] / input[i];
+
+        // 20 dB over at 4:1 leaves 5 dB over: about 0.18 of the input.
+        assert!((gain(burst - 1).abs() - 0.178).abs() < 0.01, "gain {}", gain(burst - 1));
+        // Still ducked just after the burst...
+        assert!(gain(burst + (0.005 * SAMPLE_RATE) as usize) < 0.9);
+        // ...and back to unity by the end of the release time.
+        assert!((gain(burst + release) - 1.0).abs() < 1e-3, "gain {}", gain(burst + release));
+    }
+
+    #[test]
+    fn test_sidechain_drives_detection() {
+        let input = vec![0.05; 4800];
+        let key: Vec<Sample> = (0..4800).map(|i| if i < 2400 { 1.0 } else { 0.0 }).collect();
+
+        let output = compress(&input, Some(&key));
+        assert!(output[2399] < 0.02, "quiet input should duck under the key");
+        assert!((output[4799] - 0.05).abs() < 1e-4);
+        // Without the key the same input is under the threshold.
+        assert!(compress(&input, None).iter().all(|&s| (s - 0.05).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_static_curve_points() {
+        // 0 dBFS into a -12 dB threshold at 4:1: 12 dB over becomes 3 dB over.
+        assert!((static_curve(0.0, -12.0, 4.0, 0.0) + 9.0).abs() < 1e-4);
+        assert_eq!(static_curve(-20.0, -12.0, 4.0, 0.0), -20.0);
+        // A 6 dB knee starts 3 dB under the threshold and meets the line 3 dB over.
+        assert_eq!(static_curve(-15.0, -12.0, 4.0, 6.0), -15.0);
+        assert!((static_curve(-9.0, -12.0, 4.0, 6.0) - static_curve(-9.0, -12.0, 4.0, 0.0)).abs() < 1e-4);
+        let at_threshold = static_curve(-12.0, -12.0, 4.0, 6.0);
+        assert!(at_threshold < -12.0 && at_threshold > -12.6, "knee {at_threshold}");
+    }
+
+    #[test]
+    fn test_steady_level_settles_on_the_curve() {
+        let mut compressor = Compressor::new(SAMPLE_RATE);
+        let frames = 4800;
+        let input = vec![1.0; frames];
+        let mut out_l = vec![0.0; frames];
+        let mut out_r = vec![0.0; frames];
+        let mut reduction = vec![0.0; frames];
+        let params = CompressorParams { threshold: &[-12.0], detector: &[1.0], ..params() };
+        let inputs = CompressorInputs { input_l: Some(&input), input_r: Some(&input), sidechain: None };
+        compressor.process_block_stereo(&mut out_l, &mut out_r, Some(&mut reduction), inputs, params);
+
+        let out_db = linear_to_db(out_l[frames - 1]);
+        assert!((out_db + 9.0).abs() < 0.1, "output {out_db} dB");
+        assert!((reduction[frames - 1] - (1.0 - db_to_linear(-9.0))).abs() < 0.01);
+    }
+}
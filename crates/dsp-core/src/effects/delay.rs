@@ -3,7 +3,7 @@
 //! A versatile delay effect with feedback, tone control, and optional
 //! ping-pong stereo bouncing.
 
-use crate::common::{input_at, sample_at, Sample};
+use crate::common::{dry_gain, input_at, sample_at, Sample};
 
 /// Stereo delay effect.
 ///
@@ -53,6 +53,8 @@ pub struct DelayParams<'a> {
     pub tone: &'a [Sample],
     /// Ping-pong mode (>= 0.5 = enabled)
     pub ping_pong: &'a [Sample],
+    /// Wet only (>= 0.5 = no dry signal, for send buses)
+    pub wet_only: &'a [Sample],
 }
 
 impl Delay {
@@ -76,6 +78,15 @@ impl Delay {
         self.allocate_buffers();
     }
 
+    /// Empty the delay lines, cutting the echo tail.
+    pub fn reset(&mut self) {
+        self.buffer_l.fill(0.0);
+        self.buffer_r.fill(0.0);
+        self.write_index = 0;
+        self.damp_state_l = 0.0;
+        self.damp_state_r = 0.0;
+    }
+
     fn allocate_buffers(&mut self) {
         let max_delay_ms = 2000.0;
         let max_samples = ((max_delay_ms / 1000.0) * self.sample_rate).ceil() as usize + 2;
@@ -145,7 +156,8 @@ impl Delay {
             self.buffer_l[self.write_index] = in_l + self.damp_state_l;
             self.buffer_r[self.write_index] = in_r + self.damp_state_r;
 
-            let dry = 1.0 - mix;
+            // The dry path is not delayed on purpose: the echo is the delayed copy.
+            let dry = dry_gain(mix, sample_at(params.wet_only, i, 0.0));
             out_l[i] = in_l * dry + delayed_l * mix;
             out_r[i] = in_r * dry + delayed_r * mix;
 
@@ -1,6 +1,7 @@
 //! Distortion effect with multiple modes.
 //!
-//! Provides soft clip, hard clip, and foldback distortion types.
+//! Provides soft clip, hard clip, foldback, asymmetric clip and tube
+//! saturation types.
 
 use crate::common::{input_at, sample_at, Sample};
 
@@ -11,6 +12,8 @@ use crate::common::{input_at, sample_at, Sample};
 /// - 0: Soft clip (tanh-style)
 /// - 1: Hard clip
 /// - 2: Foldback distortion
+/// - 3: Asymmetric clip (+0.7 / -0.5, shifted by `bias`)
+/// - 4: Tube: `tanh(x + bias) - tanh(bias)`, normalized to ±1
 ///
 /// # Example
 ///
@@ -30,10 +33,17 @@ pub struct DistortionParams<'a> {
     pub tone: &'a [Sample],
     /// Dry/wet mix (0-1)
     pub mix: &'a [Sample],
-    /// Distortion mode (0=soft, 1=hard, 2=foldback)
+    /// Distortion mode (0=soft, 1=hard, 2=foldback, 3=asymmetric, 4=tube)
     pub mode: &'a [Sample],
+    /// Operating point (-1 to 1), used by the asymmetric and tube modes
+    pub bias: &'a [Sample],
 }
 
+/// Positive clip level of the asymmetric mode.
+const ASYM_POSITIVE_CLIP: Sample = 0.7;
+/// Negative clip level of the asymmetric mode.
+const ASYM_NEGATIVE_CLIP: Sample = -0.5;
+
 impl Distortion {
     /// Process a block of audio.
     pub fn process_block(
@@ -50,12 +60,14 @@ impl Distortion {
             let tone = sample_at(params.tone, i, 0.5).clamp(0.0, 1.0);
             let mix = sample_at(params.mix, i, 1.0).clamp(0.0, 1.0);
             let mode = sample_at(params.mode, i, 0.0);
+            let bias = sample_at(params.bias, i, 0.0).clamp(-1.0, 1.0);
 
             let in_sample = input_at(input, i);
             let gain = 1.0 + drive * 20.0;
             let driven = in_sample * gain;
 
-            // Mode: 0 = soft clip (tanh), 1 = hard clip, 2 = foldback
+            // Mode: 0 = soft clip (tanh), 1 = hard clip, 2 = foldback,
+            // 3 = asymmetric clip, 4 = tube
             let shaped = if mode < 0.5 {
                 // Soft clip (tanh approximation)
                 let x = driven.clamp(-3.0, 3.0);
@@ -63,6 +75,15 @@ impl Distortion {
             } else if mode < 1.5 {
                 // Hard clip
                 driven.clamp(-1.0, 1.0)
+            } else if mode >= 3.5 {
+                // Tube: bias-shifted tanh, with the static offset removed
+                let offset = bias.tanh();
+                ((driven + bias).tanh() - offset) / (1.0 + offset.abs())
+            } else if mode >= 2.5 {
+                // Asymmetric: the bias moves the signal against uneven clip
+                // levels; the bias itself is removed so silence stays silent
+                let clip = |x: Sample| x.clamp(ASYM_NEGATIVE_CLIP, ASYM_POSITIVE_CLIP);
+                clip(driven + bias) - clip(bias)
             } else {
                 // Foldback
                 let mut x = driven;
@@ -84,3 +105,47 @@ impl Distortion {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shape(input: &[Sample], mode: Sample, bias: Sample) -> Vec<Sample> {
+        let mut output = vec![0.0; input.len()];
+        Distortion::process_block(
+            &mut output,
+            Some(input),
+            DistortionParams {
+                drive: &[1.0],
+                tone: &[1.0],
+                mix: &[1.0],
+                mode: &[mode],
+                bias: &[bias],
+            },
+        );
+        output
+    }
+
+    #[test]
+    fn test_asymmetric_clips_halves_at_different_levels() {
+        let output = shape(&[1.0, -1.0, 0.0], 3.0, 0.0);
+        assert_eq!(output, vec![ASYM_POSITIVE_CLIP, ASYM_NEGATIVE_CLIP, 0.0]);
+    }
+
+    #[test]
+    fn test_tube_bias_adds_even_harmonics_without_dc_at_rest() {
+        let sine: Vec<Sample> = (0..480)
+            .map(|n| 0.05 * (std::f32::consts::TAU * n as f32 / 48.0).sin())
+            .collect();
+        for bias in [-0.6, 0.0, 0.6] {
+            let output = shape(&[0.0; 8], 4.0, bias);
+            assert!(output.iter().all(|s| s.abs() < 1e-6), "offset at rest with bias {bias}");
+        }
+        // A symmetric curve averages to zero over a cycle; a biased one
+        // rectifies a little (second harmonic).
+        let mean = |bias: Sample| shape(&sine, 4.0, bias).iter().sum::<Sample>() / sine.len() as Sample;
+        assert!(mean(0.0).abs() < 1e-4);
+        assert!(mean(0.6).abs() > 0.01, "{}", mean(0.6));
+        assert!(shape(&sine, 4.0, 0.6).iter().all(|s| s.abs() <= 1.0));
+    }
+}
@@ -3,7 +3,7 @@
 //! Creates a rich string-ensemble type sound using three
 //! delay lines with different LFO rates.
 
-use crate::common::{input_at, sample_at, Sample};
+use crate::common::{dry_gain, input_at, sample_at, Sample};
 
 /// Ensemble effect (tri-chorus).
 ///
@@ -49,6 +49,8 @@ pub struct EnsembleParams<'a> {
     pub mix: &'a [Sample],
     /// Stereo spread (0-1)
     pub spread: &'a [Sample],
+    /// Wet only (>= 0.5 = no dry signal, for send buses)
+    pub wet_only: &'a [Sample],
 }
 
 impl Ensemble {
@@ -75,6 +77,18 @@ impl Ensemble {
         self.allocate_buffers();
     }
 
+    /// Empty the delay lines and restart the LFOs.
+    pub fn reset(&mut self) {
+        self.buffer_l.fill(0.0);
+        self.buffer_r.fill(0.0);
+        self.write_index = 0;
+        self.phases = [
+            0.0,
+            std::f32::consts::TAU / 3.0,
+            (2.0 * std::f32::consts::TAU) / 3.0,
+        ];
+    }
+
     fn allocate_buffers(&mut self) {
         let max_delay_ms = 60.0;
         let max_samples = ((max_delay_ms / 1000.0) * self.sample_rate).ceil() as usize + 2;
@@ -165,7 +179,7 @@ impl Ensemble {
 
             let wet_l = sum_l / 3.0;
             let wet_r = sum_r / 3.0;
-            let dry = 1.0 - mix;
+            let dry = dry_gain(mix, sample_at(params.wet_only, i, 0.0));
             out_l[i] = input_l * dry + wet_l * mix;
             out_r[i] = input_r * dry + wet_r * mix;
 
@@ -175,3 +189,41 @@ impl Ensemble {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::TAU;
+
+    #[test]
+    fn test_mono_input_spreads_to_stereo() {
+        let sample_rate = 48000.0;
+        let frames = 9600;
+        let input: Vec<f32> = (0..frames)
+            .map(|n| (TAU * 220.0 * n as f32 / sample_rate).sin())
+            .collect();
+        let mut out_l = vec![0.0; frames];
+        let mut out_r = vec![0.0; frames];
+        let mut ensemble = Ensemble::new(sample_rate);
+        ensemble.process_block(
+            &mut out_l,
+            &mut out_r,
+            EnsembleInputs { input_l: Some(&input), input_r: None },
+            EnsembleParams {
+                rate: &[0.5],
+                depth_ms: &[8.0],
+                delay_ms: &[12.0],
+                mix: &[1.0],
+                spread: &[1.0],
+                wet_only: &[0.0],
+            },
+        );
+
+        // Skip the initial fill of the delay lines.
+        let (l, r) = (&out_l[2400..], &out_r[2400..]);
+        let energy = l.iter().map(|s| s * s).sum::<f32>() / l.len() as f32;
+        let side = l.iter().zip(r).map(|(a, b)| (a - b) * (a - b)).sum::<f32>() / l.len() as f32;
+        assert!(energy > 0.05, "wet signal too quiet: {energy}");
+        assert!(side > 0.01 * energy, "left and right should differ: {side}");
+    }
+}
@@ -105,6 +105,18 @@ impl GranularDelay {
         self.allocate_buffers();
     }
 
+    /// Empty the delay lines and stop every grain.
+    pub fn reset(&mut self) {
+        self.buffer_l.fill(0.0);
+        self.buffer_r.fill(0.0);
+        self.write_index = 0;
+        for grain in &mut self.grains {
+            grain.active = false;
+        }
+        self.spawn_phase = 0.0;
+        self.seed = 0x9876_5432;
+    }
+
     fn allocate_buffers(&mut self) {
         let max_delay_ms = 2500.0;
         let max_samples = ((max_delay_ms / 1000.0) * self.sample_rate).ceil() as usize + 2;
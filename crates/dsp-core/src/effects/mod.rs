@@ -19,6 +19,7 @@
 //! ## Distortion
 //! - [`Distortion`] - Multi-mode distortion (soft, hard, foldback)
 //! - [`Wavefolder`] - Wavefolder for complex harmonics
+//! - [`BitCrusher`] - Bit depth and sample rate reduction
 //!
 //! ## Spectral
 //! - [`Choir`] - Formant filter for vowel sounds
@@ -26,6 +27,13 @@
 //! - [`RingMod`] - Ring modulator
 //! - [`PitchShifter`] - Granular pitch shifter
 //!
+//! ## Dynamics
+//! - [`Compressor`] - Compressor with sidechain
+//! - [`NoiseGate`] - Noise gate with hold and key input
+//!
+//! ## Stereo
+//! - [`StereoWidth`] - Mid/side width with mono bass crossover
+//!
 //! # Shared Components
 //!
 //! Some effects share internal components:
@@ -42,11 +50,14 @@ pub mod spring_reverb;
 pub mod phaser;
 pub mod distortion;
 pub mod wavefolder;
+pub mod bit_crusher;
 pub mod ring_mod;
 pub mod choir;
 pub mod vocoder;
 pub mod pitch_shifter;
 pub mod compressor;
+pub mod noise_gate;
+pub mod stereo_width;
 
 // Re-export all public types
 pub use delay::{Delay, DelayInputs, DelayParams};
@@ -59,8 +70,11 @@ pub use spring_reverb::{SpringReverb, SpringReverbInputs, SpringReverbParams};
 pub use phaser::{Phaser, PhaserInputs, PhaserParams};
 pub use distortion::{Distortion, DistortionParams};
 pub use wavefolder::{Wavefolder, WavefolderParams};
+pub use bit_crusher::{BitCrusher, BitCrusherParams};
 pub use ring_mod::{RingMod, RingModParams};
 pub use choir::{Choir, ChoirInputs, ChoirParams, FormantFilter};
 pub use vocoder::{Vocoder, VocoderInputs, VocoderParams};
 pub use pitch_shifter::{PitchShifter, PitchShifterInputs, PitchShifterParams};
-pub use compressor::{Compressor, CompressorParams};
+pub use compressor::{Compressor, CompressorInputs, CompressorParams};
+pub use noise_gate::{NoiseGate, NoiseGateInputs, NoiseGateParams};
+pub use stereo_width::{StereoWidth, StereoWidthParams};
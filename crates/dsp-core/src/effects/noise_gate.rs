@@ -0,0 +1,174 @@
+//! Noise gate effect.
+//!
+//! Mutes the signal (down to a floor level) while it stays under a
+//! threshold. Stereo detection is linked; an external key signal can drive
+//! the detector instead (e.g. a kick opening a pad).
+
+use crate::common::{input_at, sample_at, Sample};
+
+/// Release time of the level detector, short enough to follow the signal
+/// but long enough to ride over the zero crossings of low notes.
+const DETECTOR_RELEASE_MS: f32 = 10.0;
+
+/// Noise gate state.
+pub struct NoiseGate {
+    sample_rate: f32,
+    /// Peak level seen by the detector.
+    level: f32,
+    /// Current gain applied to the signal (floor..1).
+    gain: f32,
+    /// Samples left before the gate starts closing.
+    hold_left: usize,
+}
+
+/// Parameters for the NoiseGate effect.
+pub struct NoiseGateParams<'a> {
+    /// Threshold in dB (-80 to 0)
+    pub threshold: &'a [Sample],
+    /// Opening time in ms (0.1 to 50)
+    pub attack: &'a [Sample],
+    /// Time held open after the level drops, in ms (0 to 500)
+    pub hold: &'a [Sample],
+    /// Closing time in ms (5 to 2000)
+    pub release: &'a [Sample],
+    /// Attenuation when closed, in dB (-80 to 0)
+    pub floor: &'a [Sample],
+}
+
+/// Input signals for NoiseGate.
+pub struct NoiseGateInputs<'a> {
+    pub input_l: Option<&'a [Sample]>,
+    pub input_r: Option<&'a [Sample]>,
+    /// Key signal: drives the detector when connected
+    pub key: Option<&'a [Sample]>,
+}
+
+impl NoiseGate {
+    /// Create a closed gate.
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate: sample_rate.max(1.0),
+            level: 0.0,
+            gain: 0.0,
+            hold_left: 0,
+        }
+    }
+
+    /// Update the sample rate.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate.max(1.0);
+    }
+
+    /// Close the gate.
+    pub fn reset(&mut self) {
+        self.level = 0.0;
+        self.gain = 0.0;
+        self.hold_left = 0;
+    }
+
+    /// Process a stereo block.
+    pub fn process_block(
+        &mut self,
+        out_l: &mut [Sample],
+        out_r: &mut [Sample],
+        inputs: NoiseGateInputs<'_>,
+        params: NoiseGateParams<'_>,
+    ) {
+        let ms_coeff = |ms: f32| (-1000.0 / (ms * self.sample_rate)).exp();
+        let detector_coeff = ms_coeff(DETECTOR_RELEASE_MS);
+
+        for (i, (left, right)) in out_l.iter_mut().zip(out_r.iter_mut()).enumerate() {
+            let threshold = db_to_linear(sample_at(params.threshold, i, -40.0).clamp(-80.0, 0.0));
+            let attack_ms = sample_at(params.attack, i, 1.0).clamp(0.1, 50.0);
+            let hold_ms = sample_at(params.hold, i, 50.0).clamp(0.0, 500.0);
+            let release_ms = sample_at(params.release, i, 100.0).clamp(5.0, 2000.0);
+            let floor = db_to_linear(sample_at(params.floor, i, -80.0).clamp(-80.0, 0.0));
+
+            let sample_l = input_at(inputs.input_l, i);
+            let sample_r = input_at(inputs.input_r, i);
+            let peak = match inputs.key {
+                Some(key) => input_at(Some(key), i).abs(),
+                None => sample_l.abs().max(sample_r.abs()),
+            };
+            self.level = peak.max(self.level * detector_coeff);
+
+            let target = if self.level >= threshold {
+                self.hold_left = (hold_ms * 0.001 * self.sample_rate) as usize;
+                1.0
+            } else if self.hold_left > 0 {
+                self.hold_left -= 1;
+                1.0
+            } else {
+                floor
+            };
+            let coeff = if target > self.gain { ms_coeff(attack_ms) } else { ms_coeff(release_ms) };
+            self.gain = target + (self.gain - target) * coeff;
+
+            *left = sample_l * self.gain;
+            *right = sample_r * self.gain;
+        }
+    }
+}
+
+/// Convert decibels to linear amplitude.
+#[inline]
+fn db_to_linear(db: f32) -> f32 {
+    10.0_f32.powf(db / 20.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f32 = 48000.0;
+
+    fn params<'a>() -> NoiseGateParams<'a> {
+        NoiseGateParams {
+            threshold: &[-40.0],
+            attack: &[1.0],
+            hold: &[50.0],
+            release: &[20.0],
+            floor: &[-80.0],
+        }
+    }
+
+    fn gate(input: &[Sample], key: Option<&[Sample]>) -> Vec<Sample> {
+        let mut gate = NoiseGate::new(SAMPLE_RATE);
+        let mut out_l = vec![0.0; input.len()];
+        let mut out_r = vec![0.0; input.len()];
+        let inputs = NoiseGateInputs { input_l: Some(input), input_r: Some(input), key };
+        gate.process_block(&mut out_l, &mut out_r, inputs, params());
+        out_l
+    }
+
+    #[test]
+    fn test_decaying_tone_is_muted_after_hold() {
+        // 220 Hz tone decaying by 60 dB per second: crosses -40 dB at 0.667 s.
+        let frames = SAMPLE_RATE as usize;
+        let input: Vec<Sample> = (0..frames)
+            .map(|n| {
+                let t = n as f32 / SAMPLE_RATE;
+                10.0_f32.powf(-3.0 * t) * (std::f32::consts::TAU * 220.0 * t).sin()
+            })
+            .collect();
+        let output = gate(&input, None);
+
+        // Open (after the attack) while the tone is loud.
+        let at = |seconds: f32| (seconds * SAMPLE_RATE) as usize;
+        assert!((output[at(0.3)] - input[at(0.3)]).abs() < 1e-4);
+        // Still open through the hold time right after the crossing.
+        let peak = |range: std::ops::Range<usize>| output[range].iter().fold(0.0, |m: f32, s| m.max(s.abs()));
+        assert!(peak(at(0.68)..at(0.7)) > 0.005);
+        // Muted once hold and release have passed.
+        assert!(peak(at(0.85)..frames) < 1e-5, "leak {}", peak(at(0.85)..frames));
+    }
+
+    #[test]
+    fn test_key_opens_the_gate() {
+        let input = vec![0.001; 4800];
+        let key: Vec<Sample> = (0..4800).map(|i| if i < 2400 { 1.0 } else { 0.0 }).collect();
+        let output = gate(&input, Some(&key));
+        assert!((output[2000] - 0.001).abs() < 1e-6);
+        assert!(gate(&input, None)[2000] < 1e-6);
+    }
+}
@@ -1,14 +1,22 @@
-//! 4-stage phaser effect.
+//! 2- to 12-stage phaser effect.
 //!
 //! Creates sweeping, jet-like sounds using cascaded
-//! allpass filters modulated by an LFO.
+//! allpass filters modulated by an LFO or an external CV.
 
-use crate::common::{input_at, sample_at, Sample};
+use crate::common::{dry_gain, input_at, sample_at, Sample};
 
-/// 4-stage stereo phaser.
+/// 2- to 12-stage stereo phaser.
 ///
-/// Uses four cascaded first-order allpass filters with
-/// LFO modulation and feedback for classic phaser sound.
+/// Uses cascaded first-order allpass filters with LFO
+/// modulation and feedback for classic phaser sound. Every
+/// two stages add a notch; stages are laid out in banks of
+/// four spread around the center frequency, so 8 stages run
+/// a second bank after the first for a deeper, more
+/// flanger-like sweep.
+///
+/// The right channel's LFO can be shifted in phase for a
+/// wider stereo sweep. A connected modulation input replaces
+/// the internal LFO.
 ///
 /// # Example
 ///
@@ -23,17 +31,28 @@ use crate::common::{input_at, sample_at, Sample};
 /// ```
 pub struct Phaser {
     sample_rate: f32,
-    allpass_l: [f32; 4],
-    allpass_r: [f32; 4],
+    /// One allpass state per active stage; capacity is
+    /// [`MAX_STAGES`], so stage changes never reallocate.
+    allpass_l: Vec<f32>,
+    allpass_r: Vec<f32>,
     lfo_phase: f32,
 }
 
+/// Stage count range; the count is always even.
+const MIN_STAGES: usize = 2;
+const MAX_STAGES: usize = 12;
+/// Corner frequencies of one bank of four allpass stages, relative to
+/// the center frequency.
+const BANK_RATIOS: [f32; 4] = [0.25, 0.5, 1.0, 2.0];
+
 /// Input signals for Phaser.
 pub struct PhaserInputs<'a> {
     /// Left audio input
     pub input_l: Option<&'a [Sample]>,
     /// Right audio input
     pub input_r: Option<&'a [Sample]>,
+    /// External sweep (-1 to 1), replaces the LFO when connected
+    pub modulation: Option<&'a [Sample]>,
 }
 
 /// Parameters for Phaser.
@@ -46,17 +65,49 @@ pub struct PhaserParams<'a> {
     pub feedback: &'a [Sample],
     /// Dry/wet mix (0-1)
     pub mix: &'a [Sample],
+    /// Allpass stages (2-12, rounded to an even count)
+    pub stages: &'a [Sample],
+    /// Right LFO phase offset in cycles (0-0.5)
+    pub stereo: &'a [Sample],
+    /// Center frequency in Hz (100-4000)
+    pub center: &'a [Sample],
+    /// Wet only (>= 0.5 = no dry signal, for send buses)
+    pub wet_only: &'a [Sample],
 }
 
 impl Phaser {
-    /// Create a new phaser.
+    /// Create a new 4-stage phaser.
     pub fn new(sample_rate: f32) -> Self {
-        Self {
+        Self::new_with_stages(sample_rate, 4)
+    }
+
+    /// Create a phaser with 2 to 12 allpass stages (odd counts round up).
+    pub fn new_with_stages(sample_rate: f32, stages: usize) -> Self {
+        let mut phaser = Self {
             sample_rate: sample_rate.max(1.0),
-            allpass_l: [0.0; 4],
-            allpass_r: [0.0; 4],
+            allpass_l: Vec::with_capacity(MAX_STAGES),
+            allpass_r: Vec::with_capacity(MAX_STAGES),
             lfo_phase: 0.0,
-        }
+        };
+        phaser.set_stages(stages);
+        phaser
+    }
+
+    fn clamp_stages(stages: usize) -> usize {
+        (stages + stages % 2).clamp(MIN_STAGES, MAX_STAGES)
+    }
+
+    /// Number of active allpass stages.
+    pub fn stages(&self) -> usize {
+        self.allpass_l.len()
+    }
+
+    /// Change the stage count. Kept stages keep their state; added
+    /// stages start from silence.
+    pub fn set_stages(&mut self, stages: usize) {
+        let stages = Self::clamp_stages(stages);
+        self.allpass_l.resize(stages, 0.0);
+        self.allpass_r.resize(stages, 0.0);
     }
 
     /// Update the sample rate.
@@ -64,6 +115,13 @@ impl Phaser {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Clear the allpass stages and restart the LFO.
+    pub fn reset(&mut self) {
+        self.allpass_l.fill(0.0);
+        self.allpass_r.fill(0.0);
+        self.lfo_phase = 0.0;
+    }
+
     fn allpass(input: f32, coeff: f32, state: &mut f32) -> f32 {
         let output = *state - input * coeff;
         *state = input + output * coeff;
@@ -82,21 +140,35 @@ impl Phaser {
             return;
         }
 
-        let base_freqs: [f32; 4] = [200.0, 400.0, 800.0, 1600.0];
+        let stages = sample_at(params.stages, 0, self.stages() as f32);
+        self.set_stages(stages.round().max(0.0) as usize);
+        let last = self.stages() - 1;
 
         for i in 0..out_l.len() {
             let rate = sample_at(params.rate, i, 0.5).clamp(0.05, 5.0);
             let depth = sample_at(params.depth, i, 0.7).clamp(0.0, 1.0);
             let feedback = sample_at(params.feedback, i, 0.3).clamp(0.0, 0.9);
             let mix = sample_at(params.mix, i, 0.5).clamp(0.0, 1.0);
+            let stereo = sample_at(params.stereo, i, 0.0).clamp(0.0, 0.5);
+            let center = sample_at(params.center, i, 800.0).clamp(100.0, 4000.0);
 
-            // LFO
+            // LFO, or the external sweep when connected
             self.lfo_phase += rate / self.sample_rate;
             if self.lfo_phase >= 1.0 {
                 self.lfo_phase -= 1.0;
             }
-            let lfo = (self.lfo_phase * std::f32::consts::TAU).sin();
-            let mod_amount = 0.5 + lfo * 0.5 * depth;
+            let (sweep_l, sweep_r) = match inputs.modulation {
+                Some(values) => {
+                    let value = input_at(Some(values), i).clamp(-1.0, 1.0);
+                    (value, value)
+                }
+                None => (
+                    (self.lfo_phase * std::f32::consts::TAU).sin(),
+                    ((self.lfo_phase + stereo) * std::f32::consts::TAU).sin(),
+                ),
+            };
+            let mod_l = 0.5 + sweep_l * 0.5 * depth;
+            let mod_r = 0.5 + sweep_r * 0.5 * depth;
 
             let in_l = input_at(inputs.input_l, i);
             let in_r = match inputs.input_r {
@@ -105,19 +177,151 @@ impl Phaser {
             };
 
             // Process allpass chain
-            let mut proc_l = in_l + self.allpass_l[3] * feedback;
-            let mut proc_r = in_r + self.allpass_r[3] * feedback;
-
-            for stage in 0..4 {
-                let freq = base_freqs[stage] * mod_amount;
-                let coeff = (1.0 - freq / self.sample_rate).clamp(-0.99, 0.99);
-                proc_l = Self::allpass(proc_l, coeff, &mut self.allpass_l[stage]);
-                proc_r = Self::allpass(proc_r, coeff, &mut self.allpass_r[stage]);
+            let mut proc_l = in_l + self.allpass_l[last] * feedback;
+            let mut proc_r = in_r + self.allpass_r[last] * feedback;
+
+            for stage in 0..self.allpass_l.len() {
+                let freq = center * BANK_RATIOS[stage % 4];
+                // 0.999 keeps stages down to ~50 Hz apart (0.99 merged
+                // everything under ~500 Hz, so `center` had no effect there).
+                let coeff_l = (1.0 - freq * mod_l / self.sample_rate).clamp(-0.999, 0.999);
+                let coeff_r = (1.0 - freq * mod_r / self.sample_rate).clamp(-0.999, 0.999);
+                proc_l = Self::allpass(proc_l, coeff_l, &mut self.allpass_l[stage]);
+                proc_r = Self::allpass(proc_r, coeff_r, &mut self.allpass_r[stage]);
             }
 
-            let dry = 1.0 - mix;
+            let dry = dry_gain(mix, sample_at(params.wet_only, i, 0.0));
             out_l[i] = in_l * dry + proc_l * mix;
             out_r[i] = in_r * dry + proc_r * mix;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Static (depth 0), fully wet, no feedback: the output is the bare
+    /// allpass chain.
+    fn run(phaser: &mut Phaser, input: &[f32], stages: f32) -> Vec<f32> {
+        let mut out_l = vec![0.0; input.len()];
+        let mut out_r = vec![0.0; input.len()];
+        phaser.process_block(
+            &mut out_l,
+            &mut out_r,
+            PhaserInputs { input_l: Some(input), input_r: None, modulation: None },
+            PhaserParams {
+                rate: &[0.5],
+                depth: &[0.0],
+                feedback: &[0.0],
+                mix: &[1.0],
+                stages: &[stages],
+                stereo: &[0.0],
+                center: &[800.0],
+                wet_only: &[0.0],
+            },
+        );
+        out_l
+    }
+
+    /// Local minima of the 50/50 mix's magnitude response that dip below
+    /// -20 dB, on a log grid from 2 Hz to just under Nyquist.
+    fn count_notches(stages: usize) -> usize {
+        let mut impulse = vec![0.0; 16384];
+        impulse[0] = 1.0;
+        let mut response = vec![0.0; impulse.len()];
+        let mut right = vec![0.0; impulse.len()];
+        Phaser::new_with_stages(48000.0, stages).process_block(
+            &mut response,
+            &mut right,
+            PhaserInputs { input_l: Some(&impulse), input_r: None, modulation: None },
+            PhaserParams {
+                rate: &[0.5],
+                depth: &[0.0],
+                feedback: &[0.0],
+                mix: &[0.5],
+                stages: &[stages as f32],
+                stereo: &[0.0],
+                center: &[800.0],
+                wet_only: &[0.0],
+            },
+        );
+        let magnitude: Vec<f32> = (0..1500)
+            .map(|k| {
+                let freq = 2.0 * 11950.0f32.powf(k as f32 / 1499.0);
+                // DFT at `freq`, rotating the phasor instead of calling sin/cos.
+                let w = std::f64::consts::TAU * freq as f64 / 48000.0;
+                let (step_re, step_im) = (w.cos(), -w.sin());
+                let (mut re, mut im, mut p_re, mut p_im) = (0.0, 0.0, 1.0, 0.0);
+                for &x in &response {
+                    re += x as f64 * p_re;
+                    im += x as f64 * p_im;
+                    (p_re, p_im) = (p_re * step_re - p_im * step_im, p_re * step_im + p_im * step_re);
+                }
+                (re * re + im * im).sqrt() as f32
+            })
+            .collect();
+        magnitude.windows(3).filter(|m| m[1] < m[0] && m[1] <= m[2] && m[1] < 0.1).count()
+    }
+
+    #[test]
+    fn test_eight_stages_cascade_two_banks() {
+        let input: Vec<f32> = (0..512).map(|n| ((n * 7919) % 61) as f32 / 30.0 - 1.0).collect();
+        let twice = {
+            let first = run(&mut Phaser::new(48000.0), &input, 4.0);
+            run(&mut Phaser::new(48000.0), &first, 4.0)
+        };
+        let eight = run(&mut Phaser::new_with_stages(48000.0, 8), &input, 8.0);
+        assert_eq!(eight, twice);
+    }
+
+    #[test]
+    fn test_notch_count_grows_with_stages() {
+        let notches: Vec<usize> = [2, 4, 6, 8, 12].iter().map(|&stages| count_notches(stages)).collect();
+        assert_eq!(notches, [1, 2, 3, 4, 6]);
+    }
+
+    #[test]
+    fn test_stage_count_is_even_and_bounded() {
+        let stages = |count| Phaser::new_with_stages(48000.0, count).stages();
+        assert_eq!([0, 3, 4, 7, 20].map(stages), [2, 4, 4, 8, 12]);
+    }
+
+    #[test]
+    fn test_modulation_input_replaces_the_lfo() {
+        // A fast LFO with full depth would move the notches; a constant
+        // external sweep of 0 pins them where depth 0 puts them.
+        let input: Vec<f32> = (0..2048).map(|n| ((n * 7919) % 61) as f32 / 30.0 - 1.0).collect();
+        let mut swept = vec![0.0; input.len()];
+        let mut right = vec![0.0; input.len()];
+        Phaser::new(48000.0).process_block(
+            &mut swept,
+            &mut right,
+            PhaserInputs { input_l: Some(&input), input_r: None, modulation: Some(&[0.0]) },
+            PhaserParams {
+                rate: &[5.0],
+                depth: &[1.0],
+                feedback: &[0.0],
+                mix: &[1.0],
+                stages: &[4.0],
+                stereo: &[0.25],
+                center: &[800.0],
+                wet_only: &[0.0],
+            },
+        );
+        assert_eq!(swept, run(&mut Phaser::new(48000.0), &input, 4.0));
+        assert_eq!(swept, right);
+    }
+
+    #[test]
+    fn test_set_stages_keeps_first_bank() {
+        let input = vec![0.5; 64];
+        let mut phaser = Phaser::new(48000.0);
+        run(&mut phaser, &input, 4.0);
+        let first_bank = phaser.allpass_l[..4].to_vec();
+        phaser.set_stages(8);
+        assert_eq!(phaser.stages(), 8);
+        assert_eq!(&phaser.allpass_l[..4], &first_bank[..]);
+        assert!(phaser.allpass_l[4..].iter().all(|&s| s == 0.0));
+    }
+}
@@ -102,6 +102,24 @@ impl PitchShifter {
         }
     }
 
+    /// Empty the buffer and stop every grain.
+    pub fn reset(&mut self) {
+        self.buffer.fill(0.0);
+        self.write_index = 0;
+        for grain in &mut self.grains {
+            grain.active = false;
+        }
+        self.next_grain = 0;
+        self.spawn_phase = 0.0;
+    }
+
+    /// Delay the grains add to the input: each one starts reading half a
+    /// grain behind the write position.
+    pub fn latency_samples(&self, grain_ms: f32) -> usize {
+        let grain_length = (grain_ms.clamp(10.0, 100.0) * self.sample_rate / 1000.0).max(1.0) as usize;
+        grain_length / 2
+    }
+
     fn read_interpolated(buffer: &[Sample], pos: f32) -> f32 {
         let size = buffer.len() as i32;
         let base = pos.floor();
@@ -3,7 +3,19 @@
 //! Algorithmic reverb using parallel comb filters followed
 //! by series allpass filters with pre-delay.
 
-use crate::common::{clamp, input_at, sample_at, Sample};
+use crate::common::{clamp, dry_gain, input_at, sample_at, Sample};
+
+/// Sample rate the Freeverb tuning constants were designed for.
+const TUNING_SAMPLE_RATE: f32 = 44100.0;
+/// Comb delay lengths (samples at 44.1 kHz).
+const COMB_TUNING: [usize; 4] = [1116, 1188, 1277, 1356];
+/// Allpass delay lengths (samples at 44.1 kHz).
+const ALLPASS_TUNING: [usize; 2] = [556, 441];
+/// Right-channel offset (samples at 44.1 kHz), as in the JOS/Freeverb tuning.
+const STEREO_SPREAD: usize = 23;
+/// Shortest comb allowed at any sample rate; shorter combs ring as audible
+/// resonant peaks instead of diffuse reverb.
+const MIN_COMB_SIZE: usize = 20;
 
 /// Comb filter for reverb.
 pub struct CombFilter {
@@ -39,6 +51,12 @@ impl CombFilter {
         self.damp2 = 1.0 - self.damp1;
     }
 
+    /// Silence the delay line.
+    pub fn clear(&mut self) {
+        self.buffer.fill(0.0);
+        self.filter_store = 0.0;
+    }
+
     /// Process a single sample.
     pub fn process(&mut self, input: f32) -> f32 {
         let output = self.buffer[self.index];
@@ -66,6 +84,16 @@ impl AllpassFilter {
         }
     }
 
+    /// Set the feedback coefficient.
+    pub fn set_feedback(&mut self, value: f32) {
+        self.feedback = value;
+    }
+
+    /// Silence the delay line.
+    pub fn clear(&mut self) {
+        self.buffer.fill(0.0);
+    }
+
     /// Process a single sample.
     pub fn process(&mut self, input: f32) -> f32 {
         let buffer_out = self.buffer[self.index];
@@ -121,6 +149,8 @@ pub struct ReverbParams<'a> {
     pub pre_delay: &'a [Sample],
     /// Dry/wet mix (0-1)
     pub mix: &'a [Sample],
+    /// Wet only (>= 0.5 = no dry signal, for send buses)
+    pub wet_only: &'a [Sample],
 }
 
 impl Reverb {
@@ -146,36 +176,38 @@ impl Reverb {
         self.allocate_buffers();
     }
 
+    /// Empty the combs, allpasses and pre-delay, cutting the tail.
+    pub fn reset(&mut self) {
+        for comb in self.combs_l.iter_mut().chain(self.combs_r.iter_mut()) {
+            comb.clear();
+        }
+        for allpass in self.allpass_l.iter_mut().chain(self.allpass_r.iter_mut()) {
+            allpass.clear();
+        }
+        self.pre_buffer_l.fill(0.0);
+        self.pre_buffer_r.fill(0.0);
+        self.pre_write_index = 0;
+    }
+
     fn allocate_buffers(&mut self) {
-        let scale = self.sample_rate / 44100.0;
-        let comb_tuning = [1116, 1188, 1277, 1356];
-        let allpass_tuning = [556, 441];
-        let stereo_spread = 23;
+        let scale = self.sample_rate / TUNING_SAMPLE_RATE;
+        let scaled = |length: usize| (length as f32 * scale).round() as usize;
 
-        self.combs_l = comb_tuning
+        self.combs_l = COMB_TUNING
             .iter()
-            .map(|length| CombFilter::new(((*length as f32 * scale).round() as usize).max(1)))
+            .map(|&length| CombFilter::new(scaled(length).max(MIN_COMB_SIZE)))
             .collect();
-        self.combs_r = comb_tuning
+        self.combs_r = COMB_TUNING
             .iter()
-            .map(|length| {
-                CombFilter::new((((length + stereo_spread) as f32 * scale).round() as usize).max(1))
-            })
+            .map(|&length| CombFilter::new(scaled(length + STEREO_SPREAD).max(MIN_COMB_SIZE)))
             .collect();
-        self.allpass_l = allpass_tuning
+        self.allpass_l = ALLPASS_TUNING
             .iter()
-            .map(|length| {
-                AllpassFilter::new(((*length as f32 * scale).round() as usize).max(1), 0.5)
-            })
+            .map(|&length| AllpassFilter::new(scaled(length).max(1), 0.5))
             .collect();
-        self.allpass_r = allpass_tuning
+        self.allpass_r = ALLPASS_TUNING
             .iter()
-            .map(|length| {
-                AllpassFilter::new(
-                    (((length + stereo_spread) as f32 * scale).round() as usize).max(1),
-                    0.5,
-                )
-            })
+            .map(|&length| AllpassFilter::new(scaled(length + STEREO_SPREAD).max(1), 0.5))
             .collect();
 
         let max_pre_delay_ms = 120.0;
@@ -271,9 +303,33 @@ impl Reverb {
             wet_l *= wet_scale;
             wet_r *= wet_scale;
 
-            let dry = 1.0 - mix;
+            let dry = dry_gain(mix, sample_at(params.wet_only, i, 0.0));
             out_l[i] = in_l * dry + wet_l * mix;
             out_r[i] = in_r * dry + wet_r * mix;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comb_sizes_at_low_sample_rate() {
+        let reverb = Reverb::new(22050.0);
+        for comb in reverb.combs_l.iter().chain(reverb.combs_r.iter()) {
+            assert!(comb.buffer.len() >= MIN_COMB_SIZE);
+        }
+        // Right channel keeps the (scaled) stereo spread.
+        assert_eq!(reverb.combs_l[0].buffer.len(), 558);
+        assert_eq!(reverb.combs_r[0].buffer.len(), 570);
+    }
+
+    #[test]
+    fn test_comb_sizes_clamped_at_tiny_sample_rate() {
+        let reverb = Reverb::new(100.0);
+        for comb in reverb.combs_l.iter().chain(reverb.combs_r.iter()) {
+            assert_eq!(comb.buffer.len(), MIN_COMB_SIZE);
+        }
+    }
+}
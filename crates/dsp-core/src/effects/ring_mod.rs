@@ -4,41 +4,100 @@
 //! creating sum and difference frequencies.
 
 use crate::common::{sample_at, input_at, Sample};
+use std::f32::consts::TAU;
 
 /// Ring modulator effect.
 ///
-/// A simple ring modulator that multiplies two signals together.
-/// When used with a sine wave carrier, creates inharmonic sidebands.
+/// Multiplies input A with a carrier. The carrier is input B when patched,
+/// otherwise (or when `internal_carrier` is on) a built-in oscillator (sine,
+/// triangle, saw or square) running at `carrier_freq`. With a sine carrier
+/// this creates inharmonic sidebands at the sum and difference frequencies.
 ///
 /// # Example
 ///
 /// ```ignore
 /// use dsp_core::effects::{RingMod, RingModParams};
 ///
+/// let mut ring = RingMod::new(44100.0);
 /// let mut output = [0.0f32; 128];
-/// let carrier = [0.5f32; 128];  // e.g., sine wave
 /// let input = [0.3f32; 128];    // audio input
 ///
-/// RingMod::process_block(
+/// ring.process_block(
 ///     &mut output,
-///     Some(&carrier),
 ///     Some(&input),
-///     RingModParams { level: &[1.0] },
+///     None, // use the internal carrier
+///     RingModParams {
+///         level: &[1.0],
+///         carrier_freq: &[100.0],
+///         carrier_shape: &[0.0],
+///         internal_carrier: &[0.0],
+///         mix: &[1.0],
+///     },
 /// );
 /// ```
-pub struct RingMod;
+pub struct RingMod {
+    sample_rate: f32,
+    phase: f32,
+}
 
 /// Parameters for RingMod.
 pub struct RingModParams<'a> {
-    /// Output level/mix (0-1)
+    /// Output level (0-1)
     pub level: &'a [Sample],
+    /// Internal carrier frequency in Hz (used when input B is unpatched)
+    pub carrier_freq: &'a [Sample],
+    /// Internal carrier shape: 0=sine, 1=triangle, 2=saw, 3=square
+    pub carrier_shape: &'a [Sample],
+    /// Use the internal carrier even when input B is patched (0/1)
+    pub internal_carrier: &'a [Sample],
+    /// Dry/wet mix (0 = input A only, 1 = fully ring modulated)
+    pub mix: &'a [Sample],
 }
 
 impl RingMod {
+    /// Create a new ring modulator.
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate: sample_rate.max(1.0),
+            phase: 0.0,
+        }
+    }
+
+    /// Update the sample rate.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate.max(1.0);
+    }
+
+    /// Restart the internal carrier at phase 0.
+    pub fn reset(&mut self) {
+        self.phase = 0.0;
+    }
+
+    /// Next sample of the internal carrier.
+    fn next_carrier(&mut self, freq: f32, shape: f32) -> f32 {
+        let value = if shape < 0.5 {
+            (self.phase * TAU).sin()
+        } else if shape < 1.5 {
+            1.0 - 4.0 * (self.phase - 0.5).abs()
+        } else if shape < 2.5 {
+            2.0 * self.phase - 1.0
+        } else if self.phase < 0.5 {
+            1.0
+        } else {
+            -1.0
+        };
+        self.phase += freq / self.sample_rate;
+        self.phase -= self.phase.floor();
+        value
+    }
+
     /// Process a block of samples.
     ///
-    /// Multiplies input_a (carrier) with input_b (modulator) and scales by level.
+    /// Multiplies input_a with the carrier (input_b, or the internal
+    /// oscillator when input_b is `None` or `internal_carrier` is on), blends with the dry input_a by
+    /// `mix` and scales by level.
     pub fn process_block(
+        &mut self,
         output: &mut [Sample],
         input_a: Option<&[Sample]>,
         input_b: Option<&[Sample]>,
@@ -49,10 +108,96 @@ impl RingMod {
         }
 
         for i in 0..output.len() {
-            let carrier = input_at(input_a, i);
-            let modulator = input_at(input_b, i);
+            let dry = input_at(input_a, i);
+            let external = input_b.filter(|_| sample_at(params.internal_carrier, i, 0.0) < 0.5);
+            let carrier = match external {
+                Some(_) => input_at(external, i),
+                None => {
+                    let freq = sample_at(params.carrier_freq, i, 440.0).clamp(0.0, 20000.0);
+                    let shape = sample_at(params.carrier_shape, i, 0.0);
+                    self.next_carrier(freq, shape)
+                }
+            };
             let level = sample_at(params.level, i, 1.0);
-            output[i] = carrier * modulator * level;
+            let mix = sample_at(params.mix, i, 1.0).clamp(0.0, 1.0);
+            let wet = dry * carrier;
+            output[i] = (dry * (1.0 - mix) + wet * mix) * level;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Magnitude of one frequency component (single-bin DFT).
+    fn magnitude(signal: &[f32], freq: f32, sample_rate: f32) -> f32 {
+        let (mut re, mut im) = (0.0f32, 0.0f32);
+        for (n, &s) in signal.iter().enumerate() {
+            let angle = TAU * freq * n as f32 / sample_rate;
+            re += s * angle.cos();
+            im -= s * angle.sin();
+        }
+        (re * re + im * im).sqrt() / signal.len() as f32
+    }
+
+    #[test]
+    fn test_internal_carrier_sidebands() {
+        let sample_rate = 48000.0;
+        let frames = 4800;
+        let input: Vec<f32> = (0..frames)
+            .map(|n| (TAU * 440.0 * n as f32 / sample_rate).sin())
+            .collect();
+        let mut output = vec![0.0; frames];
+        let mut ring = RingMod::new(sample_rate);
+        ring.process_block(
+            &mut output,
+            Some(&input),
+            None,
+            RingModParams {
+                level: &[1.0],
+                carrier_freq: &[100.0],
+                carrier_shape: &[0.0],
+                internal_carrier: &[0.0],
+                mix: &[1.0],
+            },
+        );
+
+        let lower = magnitude(&output, 340.0, sample_rate);
+        let upper = magnitude(&output, 540.0, sample_rate);
+        let original = magnitude(&output, 440.0, sample_rate);
+        assert!(lower > 0.2, "340 Hz sideband missing: {lower}");
+        assert!(upper > 0.2, "540 Hz sideband missing: {upper}");
+        assert!(original < 0.01, "440 Hz should be suppressed: {original}");
+    }
+
+    #[test]
+    fn test_internal_carrier_overrides_input_b() {
+        let input = [0.5f32; 64];
+        let silence = [0.0f32; 64];
+        let render = |internal: f32| {
+            let mut output = [0.0f32; 64];
+            let mut ring = RingMod::new(48000.0);
+            ring.process_block(
+                &mut output,
+                Some(&input),
+                Some(&silence),
+                RingModParams {
+                    level: &[1.0],
+                    carrier_freq: &[1000.0],
+                    carrier_shape: &[2.0],
+                    internal_carrier: &[internal],
+                    mix: &[1.0],
+                },
+            );
+            output
+        };
+
+        // The patched (silent) carrier mutes the output...
+        assert!(render(0.0).iter().all(|&s| s == 0.0));
+        // ...unless the internal saw takes over: it ramps from -1 upwards.
+        let forced = render(1.0);
+        assert_eq!(forced[0], -0.5);
+        assert!(forced[1] > forced[0] && forced[2] > forced[1]);
+    }
+}
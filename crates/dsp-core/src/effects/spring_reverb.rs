@@ -1,11 +1,80 @@
 //! Spring reverb emulation.
 //!
-//! Simulates a spring reverb tank with comb and allpass filters
-//! and optional saturation for vintage character.
+//! Simulates up to four spring tanks in parallel: input drive saturation,
+//! allpass dispersion sections tuned to the spring's transit times, a damped
+//! feedback tank per spring, then a high-pass to remove the boom.
 
 use super::reverb::{AllpassFilter, CombFilter};
 use crate::common::{clamp, input_at, sample_at, saturate, Sample};
 
+/// Sample rate the tuning constants were designed for.
+const TUNING_SAMPLE_RATE: f32 = 44100.0;
+/// Maximum number of springs running in parallel.
+pub const MAX_SPRINGS: usize = 4;
+/// Tank delay length of each spring (samples at 44.1 kHz).
+const TANK_TUNING: [usize; MAX_SPRINGS] = [1687, 2053, 2389, 1871];
+/// Dispersion allpass lengths (samples at 44.1 kHz): ~2.9 ms and ~11.5 ms,
+/// the transit times of a typical two-spring tank.
+const DISPERSION_TUNING: [usize; 2] = [128, 507];
+/// Per-spring stretch of the dispersion sections, so springs chirp apart.
+const SPRING_DETUNE: [f32; MAX_SPRINGS] = [1.0, 1.07, 0.93, 1.13];
+/// Right-channel offset (samples at 44.1 kHz).
+const STEREO_SPREAD: usize = 17;
+/// Corner of the output high-pass.
+const HIGHPASS_HZ: f32 = 120.0;
+
+/// One spring: dispersion allpasses feeding a damped tank.
+struct Spring {
+    dispersion: Vec<AllpassFilter>,
+    tank: CombFilter,
+}
+
+impl Spring {
+    fn new(index: usize, scale: f32, spread: usize) -> Self {
+        let detune = SPRING_DETUNE[index];
+        let length = |samples: usize, stretch: f32| {
+            (((samples + spread) as f32 * scale * stretch).round() as usize).max(1)
+        };
+        Self {
+            dispersion: DISPERSION_TUNING
+                .iter()
+                .map(|&samples| AllpassFilter::new(length(samples, detune), 0.5))
+                .collect(),
+            tank: CombFilter::new(length(TANK_TUNING[index], 1.0)),
+        }
+    }
+
+    fn process(&mut self, input: f32) -> f32 {
+        let mut value = input;
+        for allpass in &mut self.dispersion {
+            value = allpass.process(value);
+        }
+        self.tank.process(value)
+    }
+
+    fn clear(&mut self) {
+        for allpass in &mut self.dispersion {
+            allpass.clear();
+        }
+        self.tank.clear();
+    }
+}
+
+/// One-pole high-pass.
+#[derive(Default)]
+struct HighPass {
+    last_in: f32,
+    last_out: f32,
+}
+
+impl HighPass {
+    fn process(&mut self, input: f32, coeff: f32) -> f32 {
+        self.last_out = coeff * (self.last_out + input - self.last_in);
+        self.last_in = input;
+        self.last_out
+    }
+}
+
 /// Spring reverb effect.
 ///
 /// Emulates the sound of vintage spring reverb tanks with
@@ -24,10 +93,13 @@ use crate::common::{clamp, input_at, sample_at, saturate, Sample};
 /// ```
 pub struct SpringReverb {
     sample_rate: f32,
-    combs_l: Vec<CombFilter>,
-    combs_r: Vec<CombFilter>,
-    allpass_l: Vec<AllpassFilter>,
-    allpass_r: Vec<AllpassFilter>,
+    springs_l: Vec<Spring>,
+    springs_r: Vec<Spring>,
+    highpass_l: HighPass,
+    highpass_r: HighPass,
+    highpass_coeff: f32,
+    /// Springs processed in the previous block.
+    active_springs: usize,
 }
 
 /// Input signals for SpringReverb.
@@ -48,6 +120,10 @@ pub struct SpringReverbParams<'a> {
     pub mix: &'a [Sample],
     /// Input drive/saturation (0-1)
     pub drive: &'a [Sample],
+    /// Dispersion allpass amount (0-1)
+    pub diffusion: &'a [Sample],
+    /// Springs running in parallel (1-4)
+    pub spring_count: &'a [Sample],
 }
 
 impl SpringReverb {
@@ -55,10 +131,12 @@ impl SpringReverb {
     pub fn new(sample_rate: f32) -> Self {
         let mut spring = Self {
             sample_rate: sample_rate.max(1.0),
-            combs_l: Vec::new(),
-            combs_r: Vec::new(),
-            allpass_l: Vec::new(),
-            allpass_r: Vec::new(),
+            springs_l: Vec::new(),
+            springs_r: Vec::new(),
+            highpass_l: HighPass::default(),
+            highpass_r: HighPass::default(),
+            highpass_coeff: 1.0,
+            active_springs: MAX_SPRINGS,
         };
         spring.allocate_buffers();
         spring
@@ -70,37 +148,26 @@ impl SpringReverb {
         self.allocate_buffers();
     }
 
+    /// Empty every spring, cutting the tail.
+    pub fn reset(&mut self) {
+        for spring in self.springs_l.iter_mut().chain(self.springs_r.iter_mut()) {
+            spring.clear();
+        }
+        self.highpass_l = HighPass::default();
+        self.highpass_r = HighPass::default();
+    }
+
     fn allocate_buffers(&mut self) {
-        let scale = self.sample_rate / 44100.0;
-        let comb_tuning = [1687, 2053, 2389];
-        let allpass_tuning = [347, 113];
-        let stereo_spread = 17;
-
-        self.combs_l = comb_tuning
-            .iter()
-            .map(|length| CombFilter::new(((*length as f32 * scale).round() as usize).max(1)))
-            .collect();
-        self.combs_r = comb_tuning
-            .iter()
-            .map(|length| {
-                CombFilter::new((((length + stereo_spread) as f32 * scale).round() as usize).max(1))
-            })
-            .collect();
-        self.allpass_l = allpass_tuning
-            .iter()
-            .map(|length| {
-                AllpassFilter::new(((*length as f32 * scale).round() as usize).max(1), 0.5)
-            })
-            .collect();
-        self.allpass_r = allpass_tuning
-            .iter()
-            .map(|length| {
-                AllpassFilter::new(
-                    (((length + stereo_spread) as f32 * scale).round() as usize).max(1),
-                    0.5,
-                )
-            })
+        let scale = self.sample_rate / TUNING_SAMPLE_RATE;
+        self.springs_l = (0..MAX_SPRINGS).map(|index| Spring::new(index, scale, 0)).collect();
+        self.springs_r = (0..MAX_SPRINGS)
+            .map(|index| Spring::new(index, scale, STEREO_SPREAD))
             .collect();
+        self.highpass_l = HighPass::default();
+        self.highpass_r = HighPass::default();
+        let rc = 1.0 / (std::f32::consts::TAU * HIGHPASS_HZ);
+        let dt = 1.0 / self.sample_rate;
+        self.highpass_coeff = rc / (rc + dt);
     }
 
     /// Process a block of stereo audio.
@@ -117,18 +184,29 @@ impl SpringReverb {
 
         let decay = clamp(sample_at(params.decay, 0, 0.6), 0.0, 0.98);
         let tone = clamp(sample_at(params.tone, 0, 0.4), 0.0, 1.0);
+        let diffusion = clamp(sample_at(params.diffusion, 0, 0.5), 0.0, 1.0);
+        let count = clamp(sample_at(params.spring_count, 0, 3.0).round(), 1.0, MAX_SPRINGS as f32) as usize;
         let feedback = clamp(0.35 + decay * 0.6, 0.2, 0.98);
         let damp = 0.08 + (1.0 - tone) * 0.82;
+        let dispersion = 0.3 + diffusion * 0.45;
 
-        for comb in &mut self.combs_l {
-            comb.set_feedback(feedback);
-            comb.set_damp(damp);
+        // Springs switched back on start from silence, not a stale tail.
+        for index in self.active_springs..count {
+            self.springs_l[index].clear();
+            self.springs_r[index].clear();
         }
-        for comb in &mut self.combs_r {
-            comb.set_feedback(feedback);
-            comb.set_damp(damp);
+        self.active_springs = count;
+
+        for spring in self.springs_l.iter_mut().chain(self.springs_r.iter_mut()) {
+            spring.tank.set_feedback(feedback);
+            spring.tank.set_damp(damp);
+            for allpass in &mut spring.dispersion {
+                allpass.set_feedback(dispersion);
+            }
         }
 
+        let wet_scale = 1.2 / count as f32;
+        let coeff = self.highpass_coeff;
         for i in 0..out_l.len() {
             let mix = clamp(sample_at(params.mix, i, 0.4), 0.0, 1.0);
             let drive = clamp(sample_at(params.drive, i, 0.2), 0.0, 1.0);
@@ -145,22 +223,14 @@ impl SpringReverb {
 
             let mut wet_l = 0.0;
             let mut wet_r = 0.0;
-            for comb in &mut self.combs_l {
-                wet_l += comb.process(spring_in_l);
-            }
-            for comb in &mut self.combs_r {
-                wet_r += comb.process(spring_in_r);
+            for spring in &mut self.springs_l[..count] {
+                wet_l += spring.process(spring_in_l);
             }
-            for allpass in &mut self.allpass_l {
-                wet_l = allpass.process(wet_l);
+            for spring in &mut self.springs_r[..count] {
+                wet_r += spring.process(spring_in_r);
             }
-            for allpass in &mut self.allpass_r {
-                wet_r = allpass.process(wet_r);
-            }
-
-            let wet_scale = 0.4;
-            wet_l *= wet_scale;
-            wet_r *= wet_scale;
+            let wet_l = self.highpass_l.process(wet_l * wet_scale, coeff);
+            let wet_r = self.highpass_r.process(wet_r * wet_scale, coeff);
 
             let dry = 1.0 - mix;
             out_l[i] = input_l * dry + wet_l * mix;
@@ -168,3 +238,53 @@ impl SpringReverb {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(spring: &mut SpringReverb, input: &[Sample], count: f32) -> (Vec<Sample>, Vec<Sample>) {
+        let mut out_l = vec![0.0; input.len()];
+        let mut out_r = vec![0.0; input.len()];
+        spring.process_block(
+            &mut out_l,
+            &mut out_r,
+            SpringReverbInputs { input_l: Some(input), input_r: None },
+            SpringReverbParams {
+                decay: &[0.7],
+                tone: &[0.5],
+                mix: &[1.0],
+                drive: &[0.0],
+                diffusion: &[0.5],
+                spring_count: &[count],
+            },
+        );
+        (out_l, out_r)
+    }
+
+    #[test]
+    fn test_highpass_removes_dc_boom() {
+        let mut spring = SpringReverb::new(44100.0);
+        let input = vec![0.5; 44100];
+        let (out_l, _) = render(&mut spring, &input, 3.0);
+        let tail = &out_l[out_l.len() - 4410..];
+        let mean = tail.iter().sum::<Sample>() / tail.len() as Sample;
+        assert!(mean.abs() < 0.01, "DC leaked through: {mean}");
+    }
+
+    #[test]
+    fn test_spring_count_changes_the_tail() {
+        let mut impulse = vec![0.0; 8192];
+        impulse[0] = 1.0;
+        let mut one = SpringReverb::new(48000.0);
+        let mut four = SpringReverb::new(48000.0);
+        let (one_l, one_r) = render(&mut one, &impulse, 1.0);
+        let (four_l, _) = render(&mut four, &impulse, 4.0);
+
+        assert!(one_l.iter().any(|s| s.abs() > 1e-3), "no reverb tail");
+        assert!(one_l.iter().all(|s| s.is_finite()));
+        assert_ne!(one_l, four_l);
+        // Mono input still comes out as two decorrelated sides.
+        assert_ne!(one_l, one_r);
+    }
+}
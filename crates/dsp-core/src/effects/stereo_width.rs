@@ -0,0 +1,128 @@
+//! Stereo width (mid/side) utility.
+//!
+//! Scales the side signal to narrow or widen the stereo image, with an
+//! optional crossover that keeps the low end mono.
+
+use crate::common::{input_at, sample_at, Sample};
+
+/// Mid/side stereo width.
+///
+/// width = 0: mono, width = 1: unchanged, width = 2: side doubled.
+/// Below `mono_below` Hz the side signal is removed (0 = off).
+pub struct StereoWidth {
+    sample_rate: f32,
+    /// Lowpass states of the two one-pole highpass stages on the side
+    /// signal (12 dB/oct).
+    side_low: [f32; 2],
+}
+
+/// Parameters for StereoWidth.
+pub struct StereoWidthParams<'a> {
+    /// Side gain (0-2)
+    pub width: &'a [Sample],
+    /// Crossover frequency in Hz under which the signal is mono (0 = off, up to 500)
+    pub mono_below: &'a [Sample],
+}
+
+impl StereoWidth {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate: sample_rate.max(1.0),
+            side_low: [0.0; 2],
+        }
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate.max(1.0);
+    }
+
+    /// Clear the side highpass memory.
+    pub fn reset(&mut self) {
+        self.side_low = [0.0; 2];
+    }
+
+    /// Process a stereo block. A missing right input reuses the left one.
+    pub fn process_block(
+        &mut self,
+        out_l: &mut [Sample],
+        out_r: &mut [Sample],
+        input_l: Option<&[Sample]>,
+        input_r: Option<&[Sample]>,
+        params: StereoWidthParams<'_>,
+    ) {
+        let input_r = input_r.or(input_l);
+        for (i, (left, right)) in out_l.iter_mut().zip(out_r.iter_mut()).enumerate() {
+            let width = sample_at(params.width, i, 1.0).clamp(0.0, 2.0);
+            let mono_below = sample_at(params.mono_below, i, 0.0).clamp(0.0, 500.0);
+
+            let in_l = input_at(input_l, i);
+            let in_r = input_at(input_r, i);
+            let mid = (in_l + in_r) * 0.5;
+            let mut side = (in_l - in_r) * 0.5;
+
+            if mono_below > 0.0 {
+                let coeff = 1.0 - (-std::f32::consts::TAU * mono_below / self.sample_rate).exp();
+                for low in &mut self.side_low {
+                    *low += coeff * (side - *low);
+                    side -= *low;
+                }
+            } else {
+                self.side_low = [0.0; 2];
+            }
+
+            side *= width;
+            *left = mid + side;
+            *right = mid - side;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f32 = 48000.0;
+
+    fn process(width: f32, mono_below: f32, in_l: &[Sample], in_r: &[Sample]) -> (Vec<Sample>, Vec<Sample>) {
+        let mut stage = StereoWidth::new(SAMPLE_RATE);
+        let mut out_l = vec![0.0; in_l.len()];
+        let mut out_r = vec![0.0; in_l.len()];
+        let params = StereoWidthParams { width: &[width], mono_below: &[mono_below] };
+        stage.process_block(&mut out_l, &mut out_r, Some(in_l), Some(in_r), params);
+        (out_l, out_r)
+    }
+
+    fn stereo_test_signal() -> (Vec<Sample>, Vec<Sample>) {
+        let left = (0..512).map(|i| (i as f32 * 0.05).sin()).collect();
+        let right = (0..512).map(|i| (i as f32 * 0.13).cos() * 0.5).collect();
+        (left, right)
+    }
+
+    #[test]
+    fn test_zero_width_is_mono() {
+        let (in_l, in_r) = stereo_test_signal();
+        let (out_l, out_r) = process(0.0, 0.0, &in_l, &in_r);
+        assert_eq!(out_l, out_r);
+        assert!((out_l[100] - (in_l[100] + in_r[100]) * 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_unit_width_is_unchanged() {
+        let (in_l, in_r) = stereo_test_signal();
+        let (out_l, out_r) = process(1.0, 0.0, &in_l, &in_r);
+        let unchanged = |out: &[Sample], input: &[Sample]| out.iter().zip(input).all(|(o, i)| (o - i).abs() < 1e-6);
+        assert!(unchanged(&out_l, &in_l));
+        assert!(unchanged(&out_r, &in_r));
+    }
+
+    #[test]
+    fn test_mono_below_removes_low_side() {
+        // 40 Hz, fully out of phase: pure side signal.
+        let low: Vec<Sample> =
+            (0..9600).map(|i| (std::f32::consts::TAU * 40.0 * i as f32 / SAMPLE_RATE).sin()).collect();
+        let inverted: Vec<Sample> = low.iter().map(|s| -s).collect();
+        let (out_l, _) = process(1.0, 300.0, &low, &inverted);
+        let peak = out_l[4800..].iter().fold(0.0_f32, |m, s| m.max(s.abs()));
+        assert!(peak < 0.2, "low side leaked: {peak}");
+    }
+}
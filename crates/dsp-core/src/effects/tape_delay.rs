@@ -84,6 +84,17 @@ impl TapeDelay {
         self.allocate_buffers();
     }
 
+    /// Empty the tape, cutting the echo tail.
+    pub fn reset(&mut self) {
+        self.buffer_l.fill(0.0);
+        self.buffer_r.fill(0.0);
+        self.write_index = 0;
+        self.wow_phase = 0.0;
+        self.flutter_phase = 0.0;
+        self.damp_state_l = 0.0;
+        self.damp_state_r = 0.0;
+    }
+
     fn allocate_buffers(&mut self) {
         let max_delay_ms = 2000.0;
         let max_samples = ((max_delay_ms / 1000.0) * self.sample_rate).ceil() as usize + 2;
@@ -96,6 +96,17 @@ impl Vocoder {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Clear the band filters and envelopes.
+    pub fn reset(&mut self) {
+        self.mod_filters = [FormantFilter::default(); VOCODER_BANDS];
+        self.car_filters = [FormantFilter::default(); VOCODER_BANDS];
+        self.envelopes = [0.0; VOCODER_BANDS];
+        self.unvoiced_env = 0.0;
+        self.hp_state = 0.0;
+        self.hp_prev = 0.0;
+        self.rng = 0x1234_5678;
+    }
+
     /// Process a block of audio.
     pub fn process_block(
         &mut self,
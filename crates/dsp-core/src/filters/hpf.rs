@@ -58,6 +58,11 @@ impl Hpf {
         self.vcf.set_sample_rate(sample_rate);
     }
 
+    /// Clear the filter memory.
+    pub fn reset(&mut self) {
+        self.vcf.reset();
+    }
+
     /// Process a block of samples.
     pub fn process_block(
         &mut self,
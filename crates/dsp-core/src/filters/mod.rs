@@ -4,6 +4,7 @@
 //!
 //! - [`Vcf`]: Voltage Controlled Filter with SVF and Ladder models
 //! - [`Hpf`]: Simple High-Pass Filter (Vcf wrapper)
+//! - [`DcBlocker`]: One-pole DC blocker
 //!
 //! # Filter Models
 //!
@@ -38,6 +39,8 @@
 
 pub mod vcf;
 pub mod hpf;
+pub mod dc_blocker;
 
 pub use vcf::{LadderState, SvfState, Vcf, VcfInputs, VcfParams};
 pub use hpf::{Hpf, HpfParams, HpfInputs};
+pub use dc_blocker::{DcBlocker, DcBlockerParams};
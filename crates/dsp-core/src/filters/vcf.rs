@@ -143,6 +143,15 @@ impl Vcf {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Clear the filter memory.
+    pub fn reset(&mut self) {
+        self.stage_a = SvfState::default();
+        self.stage_b = SvfState::default();
+        self.ladder = LadderState::default();
+        self.cutoff_smooth = 800.0;
+        self.res_smooth = 0.4;
+    }
+
     /// Process a single SVF stage.
     ///
     /// Returns (lowpass, bandpass, highpass) outputs.
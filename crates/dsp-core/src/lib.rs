@@ -12,9 +12,11 @@
 // - `oscillators` - Vco, Supersaw, Karplus, NesOsc, SnesOsc, Tb303, FmOperator
 // - `filters` - Vcf (SVF/Ladder)
 // - `effects` - Delay, Reverb, Chorus, Ensemble, Phaser, Distortion, etc.
-// - `modulators` - Lfo, Adsr, SampleHold, SlewLimiter, Quantizer
+// - `modulators` - Lfo, Adsr, SampleHold, SlewLimiter, EnvFollower, Quantizer
 // - `sequencers` - StepSequencer, DrumSequencer, Arpeggiator, Euclidean, Clock
 // - `drums` - TR-909 emulations (Kick, Snare, HiHat, Clap, Tom, Rimshot)
+// - `voice_allocator` - VoiceAllocator (note-to-voice mapping, voice stealing)
+// - `prelude` - `use dsp_core::prelude::*` for the module types above
 
 pub mod common;
 pub mod oscillators;
@@ -24,12 +26,14 @@ pub mod modulators;
 pub mod sequencers;
 pub mod drums;
 pub mod chips;
+pub mod voice_allocator;
 
 // Re-export common types at crate root for convenience
 pub use common::{
     clamp, input_at, midi_to_freq, poly_blep, sample_at, saturate, freq_to_midi,
+    midi_note_to_cv, semitones_to_cv, cv_to_frequency, frequency_to_cv,
     Node, ProcessContext, Sample,
-    A4_FREQ, A4_MIDI, SEMITONES_PER_OCTAVE,
+    A4_FREQ, A4_MIDI, C4_MIDI, SEMITONES_PER_OCTAVE,
 };
 
 // Re-export oscillators
@@ -51,6 +55,7 @@ pub use oscillators::{
     Wavetable, WavetableParams, WavetableInputs,
     Granular, GranularParams, GranularInputs, GrainShape,
     ParticleCloud, ParticleCloudParams, ParticleCloudInputs, ParticleMode, OscShape,
+    SamplePlayer, SamplePlayerParams, SamplePlayerInputs, MAX_SAMPLE_FRAMES,
 };
 
 // Re-export filters
@@ -58,6 +63,7 @@ pub use filters::{
     Vcf, VcfParams, VcfInputs,
     SvfState, LadderState,
     Hpf, HpfParams, HpfInputs,
+    DcBlocker, DcBlockerParams,
 };
 
 // Re-export effects
@@ -73,11 +79,14 @@ pub use effects::{
     Phaser, PhaserParams, PhaserInputs,
     Distortion, DistortionParams,
     Wavefolder, WavefolderParams,
+    BitCrusher, BitCrusherParams,
     RingMod, RingModParams,
     Choir, ChoirParams, ChoirInputs, FormantFilter,
     Vocoder, VocoderParams, VocoderInputs,
     PitchShifter, PitchShifterParams, PitchShifterInputs,
-    Compressor, CompressorParams,
+    Compressor, CompressorParams, CompressorInputs,
+    NoiseGate, NoiseGateParams, NoiseGateInputs,
+    StereoWidth, StereoWidthParams,
 };
 
 // Re-export modulators
@@ -86,13 +95,17 @@ pub use modulators::{
     Adsr, AdsrParams, AdsrInputs,
     SampleHold, SampleHoldParams, SampleHoldInputs,
     SlewLimiter, SlewParams, SlewInputs,
+    EnvFollower, EnvFollowerParams, EnvFollowerInputs,
     Quantizer, QuantizerParams, QuantizerInputs,
     Chaos, ChaosParams, ChaosInputs,
+    Logic, LogicParams, LogicInputs, LogicOutputs,
+    PeakMeter, PeakMeterParams, PeakMeterInputs, PeakMeterOutputs,
 };
 
 // Re-export sequencers
 pub use sequencers::{
     MasterClock, MasterClockParams, MasterClockInputs, MasterClockOutputs,
+    ClockDivider, ClockDividerParams, ClockDividerInputs, CLOCK_DIV_LABELS, CLOCK_DIV_RATIOS,
     Arpeggiator, ArpeggiatorParams, ArpeggiatorInputs, ArpeggiatorOutputs,
     ArpMode, RATE_DIVISIONS,
     StepSequencer, StepSequencerParams, StepSequencerInputs, StepSequencerOutputs,
@@ -101,9 +114,10 @@ pub use sequencers::{
     DrumStep, DRUM_TRACKS, DRUM_STEPS, DRUM_TRACK_NAMES,
     EuclideanSequencer, EuclideanParams, EuclideanInputs,
     EUCLIDEAN_MAX_STEPS,
-    Mario, MarioOutputs, MARIO_CHANNELS,
+    CvBank, MAX_BANK_CHANNELS, MARIO_CHANNELS,
     MidiFileSequencer, MidiFileSequencerParams, MidiFileSequencerInputs, MidiFileSequencerOutputs,
     MidiNote, MidiTrack, MIDI_TRACKS, MAX_NOTES_PER_TRACK,
+    parse_smf, SmfFile, SmfTrack,
     TuringMachine, TuringParams, TuringInputs,
     SidPlayer, SidPlayerParams, SidPlayerInputs, SidPlayerOutputs, SidHeader,
     AyPlayer, AyPlayerParams, AyPlayerInputs, AyPlayerOutputs, YmHeader,
@@ -112,6 +126,9 @@ pub use sequencers::{
 // Re-export chips
 pub use chips::Ay3_8910;
 
+// Re-export voice allocation
+pub use voice_allocator::{StealPolicy, VoiceAllocator};
+
 // Re-export drums (TR-909)
 pub use drums::{
     Kick909, Kick909Params, Kick909Inputs,
@@ -132,13 +149,124 @@ pub use drums::{
     Tom808, Tom808Params, Tom808Inputs,
 };
 
+/// Everything typical DSP code needs in one import: the shared types, and
+/// every module with its params, inputs and outputs.
+///
+/// Helpers such as `sample_at` or `saturate` are left out; import them
+/// from the crate root when needed.
+///
+/// ```
+/// use dsp_core::prelude::*;
+///
+/// let ctx = ProcessContext::new(48000.0, 128);
+/// let mut osc = SineOsc::new(440.0);
+/// osc.reset(ctx.sample_rate);
+/// let mut out: Vec<Sample> = vec![0.0; ctx.block_size];
+/// osc.process(&mut out);
+/// ```
+pub mod prelude {
+    pub use crate::common::{Node, ProcessContext, Sample};
+
+    pub use crate::oscillators::{
+        Vco, VcoParams, VcoInputs,
+        Supersaw, SupersawParams, SupersawInputs,
+        Noise, NoiseParams,
+        SineOsc,
+        NesOsc, NesOscParams, NesOscInputs,
+        SnesOsc, SnesOscParams, SnesOscInputs,
+        Tb303, Tb303Params, Tb303Inputs, Tb303Outputs,
+        KarplusStrong, KarplusParams, KarplusInputs,
+        FmOperator, FmOperatorParams, FmOperatorInputs,
+        FmMatrix, FmMatrixParams, OpParams,
+        Shepard, ShepardParams, ShepardInputs,
+        PipeOrgan, PipeOrganParams, PipeOrganInputs, OrganVoicing,
+        SpectralSwarm, SpectralSwarmParams, SpectralSwarmInputs,
+        Resonator, ResonatorParams, ResonatorInputs,
+        Wavetable, WavetableParams, WavetableInputs,
+        Granular, GranularParams, GranularInputs, GrainShape,
+        ParticleCloud, ParticleCloudParams, ParticleCloudInputs, ParticleMode, OscShape,
+        SamplePlayer, SamplePlayerParams, SamplePlayerInputs,
+    };
+
+    pub use crate::filters::{
+        Vcf, VcfParams, VcfInputs,
+        Hpf, HpfParams, HpfInputs,
+        DcBlocker, DcBlockerParams,
+    };
+
+    pub use crate::effects::{
+        Delay, DelayParams, DelayInputs,
+        TapeDelay, TapeDelayParams, TapeDelayInputs,
+        GranularDelay, GranularDelayParams, GranularDelayInputs,
+        Chorus, ChorusParams, ChorusInputs,
+        Ensemble, EnsembleParams, EnsembleInputs,
+        SpringReverb, SpringReverbParams, SpringReverbInputs,
+        Reverb, ReverbParams, ReverbInputs,
+        Phaser, PhaserParams, PhaserInputs,
+        Distortion, DistortionParams,
+        Wavefolder, WavefolderParams,
+        BitCrusher, BitCrusherParams,
+        RingMod, RingModParams,
+        Choir, ChoirParams, ChoirInputs,
+        Vocoder, VocoderParams, VocoderInputs,
+        PitchShifter, PitchShifterParams, PitchShifterInputs,
+        Compressor, CompressorParams, CompressorInputs,
+        NoiseGate, NoiseGateParams, NoiseGateInputs,
+        StereoWidth, StereoWidthParams,
+    };
+
+    pub use crate::modulators::{
+        Lfo, LfoParams, LfoInputs,
+        Adsr, AdsrParams, AdsrInputs,
+        SampleHold, SampleHoldParams, SampleHoldInputs,
+        SlewLimiter, SlewParams, SlewInputs,
+        EnvFollower, EnvFollowerParams, EnvFollowerInputs,
+        Quantizer, QuantizerParams, QuantizerInputs,
+        Chaos, ChaosParams, ChaosInputs,
+        Logic, LogicParams, LogicInputs, LogicOutputs,
+        PeakMeter, PeakMeterParams, PeakMeterInputs, PeakMeterOutputs,
+    };
+
+    pub use crate::sequencers::{
+        MasterClock, MasterClockParams, MasterClockInputs, MasterClockOutputs,
+        ClockDivider, ClockDividerParams, ClockDividerInputs,
+        Arpeggiator, ArpeggiatorParams, ArpeggiatorInputs, ArpeggiatorOutputs, ArpMode,
+        StepSequencer, StepSequencerParams, StepSequencerInputs, StepSequencerOutputs, SeqStep,
+        DrumSequencer, DrumSequencerParams, DrumSequencerInputs, DrumSequencerOutputs, DrumStep,
+        EuclideanSequencer, EuclideanParams, EuclideanInputs,
+        CvBank,
+        MidiFileSequencer, MidiFileSequencerParams, MidiFileSequencerInputs, MidiFileSequencerOutputs,
+        TuringMachine, TuringParams, TuringInputs,
+        SidPlayer, SidPlayerParams, SidPlayerInputs, SidPlayerOutputs,
+        AyPlayer, AyPlayerParams, AyPlayerInputs, AyPlayerOutputs,
+    };
+
+    pub use crate::drums::{
+        Kick909, Kick909Params, Kick909Inputs,
+        Snare909, Snare909Params, Snare909Inputs,
+        HiHat909, HiHat909Params, HiHat909Inputs,
+        Clap909, Clap909Params, Clap909Inputs,
+        Tom909, Tom909Params, Tom909Inputs,
+        Rimshot909, Rimshot909Params, Rimshot909Inputs,
+        Kick808, Kick808Params, Kick808Inputs,
+        Snare808, Snare808Params, Snare808Inputs,
+        HiHat808, HiHat808Params, HiHat808Inputs,
+        Cowbell808, Cowbell808Params, Cowbell808Inputs,
+        Clap808, Clap808Params, Clap808Inputs,
+        Tom808, Tom808Params, Tom808Inputs,
+    };
+
+    pub use crate::voice_allocator::{StealPolicy, VoiceAllocator};
+    pub use crate::{Crossfader, CrossfaderParams, Mixer, Pan, Vca};
+}
+
 // =============================================================================
 // Amplifiers / Utilities (not extracted to separate modules)
 // =============================================================================
 
 /// Simple VCA (Voltage Controlled Amplifier).
 ///
-/// Multiplies input signal by gain and optional CV.
+/// Multiplies input signal by gain and optional CV (`1 - CV` when inverted).
 pub struct Vca;
 
 impl Vca {
@@ -147,6 +275,7 @@ impl Vca {
         input: Option<&[Sample]>,
         cv: Option<&[Sample]>,
         gain: &[Sample],
+        invert: &[Sample],
     ) {
         if output.is_empty() {
             return;
@@ -155,7 +284,15 @@ impl Vca {
         for i in 0..output.len() {
             let source = input_at(input, i);
             let cv_value = match cv {
-                Some(values) => sample_at(values, i, 1.0).max(0.0),
+                Some(values) => {
+                    let value = sample_at(values, i, 1.0);
+                    // Inverted, a high CV closes the VCA: sidechain ducking.
+                    if sample_at(invert, i, 0.0) >= 0.5 {
+                        (1.0 - value).clamp(0.0, 1.0)
+                    } else {
+                        value.max(0.0)
+                    }
+                }
                 None => 1.0,
             };
             let gain_value = sample_at(gain, i, 1.0);
@@ -296,16 +433,37 @@ impl Mixer {
 ///
 /// mix = 0: 100% input A
 /// mix = 1: 100% input B
-/// mix = 0.5: 50% A + 50% B
+/// mix = 0.5: 50% A + 50% B (linear), or 71% of each (equal power)
 pub struct Crossfader;
 
+/// Fade position and curve for the crossfader.
+pub struct CrossfaderParams<'a> {
+    /// Base position (0 = A, 1 = B).
+    pub mix: &'a [Sample],
+    /// Position CV, added to `mix` before clamping.
+    pub mix_cv: Option<&'a [Sample]>,
+    /// Equal-power curve instead of the linear one.
+    pub equal_power: bool,
+}
+
 impl Crossfader {
+    /// Gains of A and B at position `m` (0..1). The equal-power law keeps
+    /// the level of uncorrelated sources constant across the fade.
+    #[inline]
+    fn gains(m: f32, equal_power: bool) -> (f32, f32) {
+        if equal_power {
+            let angle = m * std::f32::consts::FRAC_PI_2;
+            (angle.cos(), angle.sin())
+        } else {
+            (1.0 - m, m)
+        }
+    }
+
     pub fn process_block(
         output: &mut [Sample],
         input_a: Option<&[Sample]>,
         input_b: Option<&[Sample]>,
-        mix: &[Sample],
-        mix_cv: Option<&[Sample]>,
+        params: CrossfaderParams<'_>,
     ) {
         // Early exit: if no inputs connected, output silence
         if input_a.is_none() && input_b.is_none() {
@@ -314,12 +472,12 @@ impl Crossfader {
         }
 
         for i in 0..output.len() {
-            let base = sample_at(mix, i, 0.5);
-            let cv = input_at(mix_cv, i);
-            let m = (base + cv).clamp(0.0, 1.0);
+            let base = sample_at(params.mix, i, 0.5);
+            let cv = input_at(params.mix_cv, i);
+            let (gain_a, gain_b) = Self::gains((base + cv).clamp(0.0, 1.0), params.equal_power);
             let a = input_at(input_a, i);
             let b = input_at(input_b, i);
-            output[i] = a * (1.0 - m) + b * m;
+            output[i] = a * gain_a + b * gain_b;
         }
     }
 
@@ -331,8 +489,7 @@ impl Crossfader {
         input_a_r: Option<&[Sample]>,
         input_b_l: Option<&[Sample]>,
         input_b_r: Option<&[Sample]>,
-        mix: &[Sample],
-        mix_cv: Option<&[Sample]>,
+        params: CrossfaderParams<'_>,
     ) {
         // Early exit: if no inputs connected, output silence
         if input_a_l.is_none() && input_b_l.is_none() {
@@ -342,15 +499,50 @@ impl Crossfader {
         }
 
         for i in 0..output_l.len() {
-            let base = sample_at(mix, i, 0.5);
-            let cv = input_at(mix_cv, i);
-            let m = (base + cv).clamp(0.0, 1.0);
+            let base = sample_at(params.mix, i, 0.5);
+            let cv = input_at(params.mix_cv, i);
+            let (gain_a, gain_b) = Self::gains((base + cv).clamp(0.0, 1.0), params.equal_power);
             let a_l = input_at(input_a_l, i);
             let a_r = input_at(input_a_r, i);
             let b_l = input_at(input_b_l, i);
             let b_r = input_at(input_b_r, i);
-            output_l[i] = a_l * (1.0 - m) + b_l * m;
-            output_r[i] = a_r * (1.0 - m) + b_r * m;
+            output_l[i] = a_l * gain_a + b_l * gain_b;
+            output_r[i] = a_r * gain_a + b_r * gain_b;
+        }
+    }
+}
+
+/// Stereo panner with an equal-power law.
+///
+/// pan = -1: hard left, pan = 0: center (-3 dB each side), pan = 1: hard right.
+/// A stereo input is balanced with the same gains per side.
+pub struct Pan;
+
+impl Pan {
+    /// Left and right gains for a pan position (clamped to -1..1).
+    pub fn gains(pan: Sample) -> (Sample, Sample) {
+        let angle = (pan.clamp(-1.0, 1.0) + 1.0) * std::f32::consts::FRAC_PI_4;
+        (angle.cos(), angle.sin())
+    }
+
+    pub fn process_block(
+        output_l: &mut [Sample],
+        output_r: &mut [Sample],
+        input_l: Option<&[Sample]>,
+        input_r: Option<&[Sample]>,
+        pan: &[Sample],
+        pan_cv: Option<&[Sample]>,
+    ) {
+        if output_l.is_empty() {
+            return;
+        }
+
+        for (i, (out_l, out_r)) in output_l.iter_mut().zip(output_r.iter_mut()).enumerate() {
+            let base = sample_at(pan, i, 0.0);
+            let cv = input_at(pan_cv, i);
+            let (gain_l, gain_r) = Self::gains(base + cv);
+            *out_l = input_at(input_l, i) * gain_l;
+            *out_r = input_at(input_r, i) * gain_r;
         }
     }
 }
@@ -67,6 +67,88 @@ impl Adsr {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Return to idle with the envelope at zero.
+    pub fn reset(&mut self) {
+        self.stage = 0;
+        self.env = 0.0;
+        self.last_gate = 0.0;
+        self.release_step = 0.0;
+    }
+
+    /// Advance the envelope by one sample. Times are in seconds.
+    ///
+    /// Shared with envelopes embedded in other modules (e.g. the FM Matrix
+    /// operators) so every envelope in a patch responds to a gate the same
+    /// way.
+    pub fn next_sample(
+        &mut self,
+        gate: f32,
+        attack: f32,
+        decay: f32,
+        sustain: f32,
+        release: f32,
+    ) -> f32 {
+        let sustain_level = sustain.clamp(0.0, 1.0);
+
+        // Gate rising edge -> start attack
+        if gate > 0.5 && self.last_gate <= 0.5 {
+            self.stage = 1;
+            self.release_step = 0.0;
+        }
+        // Gate falling edge -> start release
+        else if gate <= 0.5 && self.last_gate > 0.5 {
+            if self.env > 0.0 {
+                let release_time = release.max(0.001);
+                self.release_step = self.env / (release_time * self.sample_rate);
+                self.stage = 4;
+            } else {
+                self.stage = 0;
+            }
+        }
+        self.last_gate = gate;
+
+        // Process current stage
+        if self.stage == 1 {
+            // Attack
+            let attack_time = attack.max(0.001);
+            let attack_step = (1.0 - self.env) / (attack_time * self.sample_rate);
+            self.env += attack_step;
+            if self.env >= 1.0 {
+                self.env = 1.0;
+                self.stage = 2;
+            }
+        } else if self.stage == 2 {
+            // Decay
+            let decay_time = decay.max(0.001);
+            let decay_step = (1.0 - sustain_level) / (decay_time * self.sample_rate);
+            self.env -= decay_step;
+            if self.env <= sustain_level {
+                self.env = sustain_level;
+                self.stage = 3;
+            }
+        } else if self.stage == 3 {
+            // Sustain
+            self.env = sustain_level;
+        } else if self.stage == 4 {
+            // Release
+            if self.release_step <= 0.0 {
+                self.env = 0.0;
+                self.stage = 0;
+            } else {
+                self.env -= self.release_step;
+                if self.env <= 0.0 {
+                    self.env = 0.0;
+                    self.stage = 0;
+                }
+            }
+        } else {
+            // Idle
+            self.env = 0.0;
+        }
+
+        self.env
+    }
+
     /// Process a block of samples.
     pub fn process_block(
         &mut self,
@@ -84,66 +166,7 @@ impl Adsr {
             let decay = sample_at(params.decay, i, 0.2);
             let sustain = sample_at(params.sustain, i, 0.65);
             let release = sample_at(params.release, i, 0.4);
-
-            let sustain_level = sustain.clamp(0.0, 1.0);
-
-            // Gate rising edge -> start attack
-            if gate > 0.5 && self.last_gate <= 0.5 {
-                self.stage = 1;
-                self.release_step = 0.0;
-            }
-            // Gate falling edge -> start release
-            else if gate <= 0.5 && self.last_gate > 0.5 {
-                if self.env > 0.0 {
-                    let release_time = release.max(0.001);
-                    self.release_step = self.env / (release_time * self.sample_rate);
-                    self.stage = 4;
-                } else {
-                    self.stage = 0;
-                }
-            }
-            self.last_gate = gate;
-
-            // Process current stage
-            if self.stage == 1 {
-                // Attack
-                let attack_time = attack.max(0.001);
-                let attack_step = (1.0 - self.env) / (attack_time * self.sample_rate);
-                self.env += attack_step;
-                if self.env >= 1.0 {
-                    self.env = 1.0;
-                    self.stage = 2;
-                }
-            } else if self.stage == 2 {
-                // Decay
-                let decay_time = decay.max(0.001);
-                let decay_step = (1.0 - sustain_level) / (decay_time * self.sample_rate);
-                self.env -= decay_step;
-                if self.env <= sustain_level {
-                    self.env = sustain_level;
-                    self.stage = 3;
-                }
-            } else if self.stage == 3 {
-                // Sustain
-                self.env = sustain_level;
-            } else if self.stage == 4 {
-                // Release
-                if self.release_step <= 0.0 {
-                    self.env = 0.0;
-                    self.stage = 0;
-                } else {
-                    self.env -= self.release_step;
-                    if self.env <= 0.0 {
-                        self.env = 0.0;
-                        self.stage = 0;
-                    }
-                }
-            } else {
-                // Idle
-                self.env = 0.0;
-            }
-
-            output[i] = self.env;
+            output[i] = self.next_sample(gate, attack, decay, sustain, release);
         }
     }
 }
@@ -49,6 +49,20 @@ impl Chaos {
         }
     }
 
+    /// Update the sample rate.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+    }
+
+    /// Restart the attractor from its initial point.
+    pub fn reset(&mut self) {
+        self.x = 0.1;
+        self.y = 0.0;
+        self.z = 0.0;
+        self.last_z = 0.0;
+        self.trigger_timer = 0;
+    }
+
     pub fn process_block(
         &mut self,
         out_x: &mut [f32],
@@ -0,0 +1,156 @@
+//! Envelope Follower module.
+//!
+//! Turns the level of an audio signal into a CV, e.g. to duck
+//! pads under a kick drum.
+
+use crate::common::{input_at, sample_at, Sample};
+
+/// Envelope Follower.
+///
+/// Peak-rectifies the input, then smooths it with separate one-pole
+/// attack and release time constants. The output is clamped to 0..1.
+///
+/// # Parameters
+///
+/// - Attack: Time constant while the level rises
+/// - Release: Time constant while the level falls
+/// - Gain: Input gain applied before rectification
+///
+/// # Example
+///
+/// ```ignore
+/// use dsp_core::modulators::{EnvFollower, EnvFollowerParams, EnvFollowerInputs};
+///
+/// let mut follower = EnvFollower::new(44100.0);
+/// let mut output = [0.0f32; 128];
+///
+/// follower.process_block(&mut output, inputs, params);
+/// ```
+pub struct EnvFollower {
+    sample_rate: f32,
+    envelope: f32,
+}
+
+/// Input signals for EnvFollower.
+pub struct EnvFollowerInputs<'a> {
+    /// Audio signal to follow
+    pub input: Option<&'a [Sample]>,
+}
+
+/// Parameters for EnvFollower.
+pub struct EnvFollowerParams<'a> {
+    /// Attack time in seconds (0-1)
+    pub attack: &'a [Sample],
+    /// Release time in seconds (0-2)
+    pub release: &'a [Sample],
+    /// Input gain (0-4)
+    pub gain: &'a [Sample],
+}
+
+impl EnvFollower {
+    /// Create a new envelope follower.
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate: sample_rate.max(1.0),
+            envelope: 0.0,
+        }
+    }
+
+    /// Update the sample rate.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate.max(1.0);
+    }
+
+    /// Drop the envelope to zero.
+    pub fn reset(&mut self) {
+        self.envelope = 0.0;
+    }
+
+    fn coeff(&self, time: f32) -> f32 {
+        if time <= 0.0001 {
+            1.0
+        } else {
+            1.0 - (-1.0 / (time * self.sample_rate)).exp()
+        }
+    }
+
+    /// Process a block of samples.
+    pub fn process_block(
+        &mut self,
+        output: &mut [Sample],
+        inputs: EnvFollowerInputs<'_>,
+        params: EnvFollowerParams<'_>,
+    ) {
+        if output.is_empty() {
+            return;
+        }
+
+        for (i, out) in output.iter_mut().enumerate() {
+            let attack = sample_at(params.attack, i, 0.01).max(0.0);
+            let release = sample_at(params.release, i, 0.2).max(0.0);
+            let gain = sample_at(params.gain, i, 1.0).max(0.0);
+
+            // Clamping the target keeps the envelope itself in 0..1, so a hot
+            // input doesn't stretch the release.
+            let level = (input_at(inputs.input, i).abs() * gain).min(1.0);
+            let time = if level > self.envelope { attack } else { release };
+            self.envelope += (level - self.envelope) * self.coeff(time);
+            *out = self.envelope.clamp(0.0, 1.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f32 = 48000.0;
+
+    /// Sample index where `output` first crosses `threshold` in the given direction.
+    fn crossing(output: &[Sample], threshold: f32, rising: bool) -> usize {
+        output
+            .iter()
+            .position(|&value| if rising { value >= threshold } else { value <= threshold })
+            .expect("envelope never crossed the threshold")
+    }
+
+    #[test]
+    fn test_burst_follows_attack_and_release_times() {
+        let attack = 0.01;
+        let release = 0.1;
+        let burst = (0.2 * SAMPLE_RATE) as usize;
+        let total = burst + (0.5 * SAMPLE_RATE) as usize;
+        // Full-scale square burst: every sample peaks at 1.
+        let input: Vec<Sample> = (0..total)
+            .map(|i| if i >= burst { 0.0 } else if i % 2 == 0 { 1.0 } else { -1.0 })
+            .collect();
+
+        let mut follower = EnvFollower::new(SAMPLE_RATE);
+        let mut output = vec![0.0; total];
+        follower.process_block(
+            &mut output,
+            EnvFollowerInputs { input: Some(&input) },
+            EnvFollowerParams { attack: &[attack], release: &[release], gain: &[1.0] },
+        );
+
+        // One time constant: 63% of the way up, then down to 37%.
+        let rise = crossing(&output, 1.0 - (-1.0f32).exp(), true) as f32 / SAMPLE_RATE;
+        assert!((rise - attack).abs() <= attack * 0.1, "attack took {rise}s");
+        let fall = crossing(&output[burst..], (-1.0f32).exp(), false) as f32 / SAMPLE_RATE;
+        assert!((fall - release).abs() <= release * 0.1, "release took {fall}s");
+        assert!(output.iter().all(|&value| (0.0..=1.0).contains(&value)));
+    }
+
+    #[test]
+    fn test_hot_input_is_clamped() {
+        let input = vec![3.0; 512];
+        let mut follower = EnvFollower::new(SAMPLE_RATE);
+        let mut output = vec![0.0; 512];
+        follower.process_block(
+            &mut output,
+            EnvFollowerInputs { input: Some(&input) },
+            EnvFollowerParams { attack: &[0.0], release: &[0.1], gain: &[4.0] },
+        );
+        assert!(output.iter().all(|&value| value == 1.0));
+    }
+}
@@ -75,6 +75,12 @@ impl Lfo {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Restart the cycle at phase 0.
+    pub fn reset(&mut self) {
+        self.phase = 0.0;
+        self.last_sync = 0.0;
+    }
+
     /// Process a block of samples.
     pub fn process_block(
         &mut self,
@@ -0,0 +1,88 @@
+//! Gate logic module.
+//!
+//! Combines two gate signals with AND, OR and XOR, and turns a CV into a
+//! gate with a comparator.
+
+use crate::common::{input_at, sample_at, Sample};
+
+/// Level above which a gate input counts as high.
+const GATE_THRESHOLD: f32 = 0.5;
+
+/// Gate logic and comparator.
+///
+/// Useful for rhythmic combinations of several sequencers: AND keeps the
+/// steps two patterns share, OR merges them, XOR keeps the steps only one
+/// of them plays. The comparator outputs a gate while input A is above
+/// the threshold.
+///
+/// All outputs are 0.0 or 1.0.
+///
+/// # Example
+///
+/// ```ignore
+/// use dsp_core::modulators::{Logic, LogicParams, LogicInputs, LogicOutputs};
+///
+/// Logic::process_block(outputs, LogicInputs { a: Some(&gate_a), b: Some(&gate_b) }, LogicParams { threshold: &[0.0] });
+/// ```
+pub struct Logic;
+
+/// Input signals for Logic.
+pub struct LogicInputs<'a> {
+    /// Gate A, also the comparator input
+    pub a: Option<&'a [Sample]>,
+    /// Gate B
+    pub b: Option<&'a [Sample]>,
+}
+
+/// Parameters for Logic.
+pub struct LogicParams<'a> {
+    /// Comparator threshold (-1 to 1)
+    pub threshold: &'a [Sample],
+}
+
+/// Output buffers for Logic.
+pub struct LogicOutputs<'a> {
+    pub and: &'a mut [Sample],
+    pub or: &'a mut [Sample],
+    pub xor: &'a mut [Sample],
+    /// High while input A is above the threshold
+    pub compare: &'a mut [Sample],
+}
+
+impl Logic {
+    /// Process a block of samples.
+    pub fn process_block(outputs: LogicOutputs<'_>, inputs: LogicInputs<'_>, params: LogicParams<'_>) {
+        let gate = |high: bool| if high { 1.0 } else { 0.0 };
+        for i in 0..outputs.and.len() {
+            let a = input_at(inputs.a, i);
+            let b = input_at(inputs.b, i);
+            let (a_high, b_high) = (a > GATE_THRESHOLD, b > GATE_THRESHOLD);
+            outputs.and[i] = gate(a_high && b_high);
+            outputs.or[i] = gate(a_high || b_high);
+            outputs.xor[i] = gate(a_high != b_high);
+            outputs.compare[i] = gate(a > sample_at(params.threshold, i, 0.0));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gates_follow_the_truth_tables() {
+        // Every combination of A and B, plus a CV ramp for the comparator.
+        let a = [0.0, 1.0, 0.0, 1.0, 0.3, 0.8];
+        let b = [0.0, 0.0, 1.0, 1.0, 0.0, 0.0];
+        let (mut and, mut or, mut xor, mut compare) = ([9.0; 6], [9.0; 6], [9.0; 6], [9.0; 6]);
+        Logic::process_block(
+            LogicOutputs { and: &mut and, or: &mut or, xor: &mut xor, compare: &mut compare },
+            LogicInputs { a: Some(&a), b: Some(&b) },
+            LogicParams { threshold: &[0.5] },
+        );
+        assert_eq!(and, [0.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+        assert_eq!(or, [0.0, 1.0, 1.0, 1.0, 0.0, 1.0]);
+        assert_eq!(xor, [0.0, 1.0, 1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(compare, [0.0, 1.0, 0.0, 1.0, 0.0, 1.0]);
+    }
+}
@@ -9,7 +9,10 @@
 //! ## Processors
 //! - [`SampleHold`] - Sample and hold with random mode
 //! - [`SlewLimiter`] - Slew rate limiter / portamento
+//! - [`EnvFollower`] - Audio envelope follower (audio to CV)
 //! - [`Quantizer`] - Pitch quantizer with multiple scales
+//! - [`Logic`] - Gate AND/OR/XOR and comparator
+//! - [`PeakMeter`] - Peak and RMS level as CV
 //!
 //! # Typical Usage
 //!
@@ -37,12 +40,18 @@ pub mod lfo;
 pub mod adsr;
 pub mod sample_hold;
 pub mod slew;
+pub mod env_follower;
 pub mod quantizer;
 pub mod chaos;
+pub mod logic;
+pub mod peak_meter;
 
 pub use lfo::{Lfo, LfoInputs, LfoParams};
 pub use adsr::{Adsr, AdsrInputs, AdsrParams};
 pub use sample_hold::{SampleHold, SampleHoldInputs, SampleHoldParams};
 pub use slew::{SlewLimiter, SlewInputs, SlewParams};
+pub use env_follower::{EnvFollower, EnvFollowerInputs, EnvFollowerParams};
 pub use quantizer::{Quantizer, QuantizerInputs, QuantizerParams};
 pub use chaos::{Chaos, ChaosInputs, ChaosParams};
+pub use logic::{Logic, LogicInputs, LogicOutputs, LogicParams};
+pub use peak_meter::{PeakMeter, PeakMeterInputs, PeakMeterOutputs, PeakMeterParams};
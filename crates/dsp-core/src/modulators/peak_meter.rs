@@ -0,0 +1,133 @@
+//! Peak / RMS meter module.
+//!
+//! Measures the level of a signal as two CVs, for patching or for driving
+//! meters in the UI. The signal itself passes through untouched.
+
+use crate::common::{input_at, sample_at, Sample};
+
+/// Peak / RMS meter.
+///
+/// The peak follows `|x|` with attack and release ballistics; the RMS is a
+/// running mean square with the window as time constant. Both outputs are
+/// clamped to 0..1.
+///
+/// # Example
+///
+/// ```ignore
+/// use dsp_core::modulators::{PeakMeter, PeakMeterInputs, PeakMeterOutputs, PeakMeterParams};
+///
+/// let mut meter = PeakMeter::new(44100.0);
+/// meter.process_block(
+///     PeakMeterOutputs { out: &mut out, peak: &mut peak, rms: &mut rms },
+///     PeakMeterInputs { input: Some(&audio) },
+///     params,
+/// );
+/// ```
+pub struct PeakMeter {
+    sample_rate: f32,
+    peak: f32,
+    mean_square: f32,
+}
+
+/// Input signals for PeakMeter.
+pub struct PeakMeterInputs<'a> {
+    /// Signal to measure
+    pub input: Option<&'a [Sample]>,
+}
+
+/// Parameters for PeakMeter.
+pub struct PeakMeterParams<'a> {
+    /// Peak attack time in seconds (0.001-0.1)
+    pub attack: &'a [Sample],
+    /// Peak release time in seconds (0.01-5)
+    pub release: &'a [Sample],
+    /// RMS window in milliseconds (10-1000)
+    pub rms_window: &'a [Sample],
+}
+
+/// Output buffers for PeakMeter.
+pub struct PeakMeterOutputs<'a> {
+    /// Input, unchanged
+    pub out: &'a mut [Sample],
+    /// Peak level (0-1)
+    pub peak: &'a mut [Sample],
+    /// RMS level (0-1)
+    pub rms: &'a mut [Sample],
+}
+
+impl PeakMeter {
+    /// Create a new meter.
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate: sample_rate.max(1.0),
+            peak: 0.0,
+            mean_square: 0.0,
+        }
+    }
+
+    /// Update the sample rate.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate.max(1.0);
+    }
+
+    /// Drop both levels to zero.
+    pub fn reset(&mut self) {
+        self.peak = 0.0;
+        self.mean_square = 0.0;
+    }
+
+    fn coeff(&self, time: f32) -> f32 {
+        1.0 - (-1.0 / (time.max(0.0001) * self.sample_rate)).exp()
+    }
+
+    /// Process a block of samples.
+    pub fn process_block(
+        &mut self,
+        outputs: PeakMeterOutputs<'_>,
+        inputs: PeakMeterInputs<'_>,
+        params: PeakMeterParams<'_>,
+    ) {
+        for i in 0..outputs.out.len() {
+            let attack = sample_at(params.attack, i, 0.005);
+            let release = sample_at(params.release, i, 0.3);
+            let window = sample_at(params.rms_window, i, 300.0) * 0.001;
+
+            let input = input_at(inputs.input, i);
+            let level = input.abs();
+            let time = if level > self.peak { attack } else { release };
+            self.peak += (level - self.peak) * self.coeff(time);
+            self.mean_square += (input * input - self.mean_square) * self.coeff(window);
+
+            outputs.out[i] = input;
+            outputs.peak[i] = self.peak.clamp(0.0, 1.0);
+            outputs.rms[i] = self.mean_square.max(0.0).sqrt().min(1.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f32 = 48000.0;
+
+    #[test]
+    fn test_sine_reads_its_peak_and_rms() {
+        let frames = SAMPLE_RATE as usize;
+        let input: Vec<Sample> = (0..frames)
+            .map(|i| 0.5 * (std::f32::consts::TAU * 440.0 * i as f32 / SAMPLE_RATE).sin())
+            .collect();
+        let (mut out, mut peak, mut rms) = (vec![0.0; frames], vec![0.0; frames], vec![0.0; frames]);
+        let mut meter = PeakMeter::new(SAMPLE_RATE);
+        meter.process_block(
+            PeakMeterOutputs { out: &mut out, peak: &mut peak, rms: &mut rms },
+            PeakMeterInputs { input: Some(&input) },
+            PeakMeterParams { attack: &[0.001], release: &[1.0], rms_window: &[100.0] },
+        );
+
+        assert_eq!(out, input);
+        let (peak, rms) = (peak[frames - 1], rms[frames - 1]);
+        assert!((peak - 0.5).abs() < 0.01, "peak {peak}");
+        assert!((rms - 0.5 / 2.0f32.sqrt()).abs() < 0.01, "rms {rms}");
+    }
+}
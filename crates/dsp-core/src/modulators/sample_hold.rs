@@ -9,9 +9,12 @@ use crate::common::{input_at, sample_at, Sample};
 ///
 /// On each trigger rising edge, captures either:
 /// - The current input value (track mode)
-/// - A random value (random mode)
+/// - A noise sample (random mode, or track mode with nothing on the input):
+///   the noise input if connected, otherwise internal white noise scaled by
+///   the noise level
 ///
-/// The captured value is held until the next trigger.
+/// The captured value is held until the next trigger. With a glide time the
+/// output slews towards each new value instead of jumping.
 ///
 /// # Modes
 ///
@@ -23,15 +26,20 @@ use crate::common::{input_at, sample_at, Sample};
 /// ```ignore
 /// use dsp_core::modulators::{SampleHold, SampleHoldParams, SampleHoldInputs};
 ///
-/// let mut sh = SampleHold::new();
+/// let mut sh = SampleHold::new(44100.0);
 /// let mut output = [0.0f32; 128];
 ///
 /// sh.process_block(&mut output, inputs, params);
 /// ```
 pub struct SampleHold {
+    sample_rate: f32,
     last_trigger: f32,
     held: f32,
+    /// Output value, gliding towards `held`.
+    value: f32,
     seed: u32,
+    /// Seed the random mode restarts from on `reset`
+    start_seed: u32,
 }
 
 /// Input signals for SampleHold.
@@ -40,24 +48,51 @@ pub struct SampleHoldInputs<'a> {
     pub input: Option<&'a [Sample]>,
     /// Trigger input (samples on rising edge)
     pub trigger: Option<&'a [Sample]>,
+    /// External noise source, sampled instead of the internal noise
+    pub noise: Option<&'a [Sample]>,
 }
 
 /// Parameters for SampleHold.
 pub struct SampleHoldParams<'a> {
     /// Mode (0 = track input, 1 = random)
     pub mode: &'a [Sample],
+    /// Glide time constant in seconds (0 = step, up to 10)
+    pub glide: &'a [Sample],
+    /// Level of the internal noise (0-1)
+    pub noise_level: &'a [Sample],
 }
 
 impl SampleHold {
     /// Create a new Sample and Hold module.
-    pub fn new() -> Self {
+    pub fn new(sample_rate: f32) -> Self {
+        Self::new_with_seed(sample_rate, 0x1234_5678)
+    }
+
+    /// Create a Sample and Hold whose random mode starts from `seed`.
+    pub fn new_with_seed(sample_rate: f32, seed: u32) -> Self {
         Self {
+            sample_rate: sample_rate.max(1.0),
             last_trigger: 0.0,
             held: 0.0,
-            seed: 0x1234_5678,
+            value: 0.0,
+            seed,
+            start_seed: seed,
         }
     }
 
+    /// Update the sample rate.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate.max(1.0);
+    }
+
+    /// Drop the held value to zero and restart the random mode.
+    pub fn reset(&mut self) {
+        self.last_trigger = 0.0;
+        self.held = 0.0;
+        self.value = 0.0;
+        self.seed = self.start_seed;
+    }
+
     /// Generate next random value using LCG.
     fn next_random(&mut self) -> f32 {
         self.seed = self
@@ -83,22 +118,26 @@ impl SampleHold {
             // On trigger rising edge
             if trigger > 0.5 && self.last_trigger <= 0.5 {
                 let mode = sample_at(params.mode, i, 0.0);
-                if mode < 0.5 {
+                self.held = match (inputs.input, inputs.noise) {
                     // Track mode: sample the input
-                    self.held = input_at(inputs.input, i);
-                } else {
-                    // Random mode: generate random value
-                    self.held = self.next_random();
-                }
+                    (Some(input), _) if mode < 0.5 => input_at(Some(input), i),
+                    (_, Some(noise)) => input_at(Some(noise), i),
+                    _ => {
+                        let level = sample_at(params.noise_level, i, 1.0).clamp(0.0, 1.0);
+                        self.next_random() * level
+                    }
+                };
             }
             self.last_trigger = trigger;
-            output[i] = self.held;
-        }
-    }
-}
 
-impl Default for SampleHold {
-    fn default() -> Self {
-        Self::new()
+            let glide = sample_at(params.glide, i, 0.0).clamp(0.0, 10.0);
+            let coeff = if glide <= 0.0001 {
+                1.0
+            } else {
+                1.0 - (-1.0 / (glide * self.sample_rate)).exp()
+            };
+            self.value += (self.held - self.value) * coeff;
+            output[i] = self.value;
+        }
     }
 }
@@ -64,6 +64,11 @@ impl SlewLimiter {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Drop the output to zero.
+    pub fn reset(&mut self) {
+        self.value = 0.0;
+    }
+
     /// Process a block of samples.
     pub fn process_block(
         &mut self,
@@ -1,50 +1,40 @@
 //! FM Matrix 4-operator synthesizer module.
 //!
 //! A professional 4-operator FM synthesizer inspired by the Yamaha DX7,
-//! with 8 algorithm presets and independent ADSR envelopes per operator.
+//! with 8 algorithm presets, a free 4x4 modulation matrix and independent
+//! ADSR envelopes per operator.
 
 use std::f64::consts::TAU;
 
-/// Envelope stage for FM operator.
-#[derive(Clone, Copy, PartialEq)]
-enum EnvStage {
-    Idle,
-    Attack,
-    Decay,
-    Sustain,
-    Release,
-}
+use crate::modulators::Adsr;
 
 /// Single FM operator state.
-#[derive(Clone)]
 struct Operator {
     phase: f64,
-    env_stage: EnvStage,
+    /// Same envelope as the standalone ADSR module, times converted from ms.
+    env: Adsr,
     env_level: f32,
     feedback_buf: [f32; 2],
     feedback_idx: usize,
-    prev_gate: f32,
 }
 
 impl Operator {
-    fn new() -> Self {
+    fn new(sample_rate: f32) -> Self {
         Self {
             phase: 0.0,
-            env_stage: EnvStage::Idle,
+            env: Adsr::new(sample_rate),
             env_level: 0.0,
             feedback_buf: [0.0; 2],
             feedback_idx: 0,
-            prev_gate: 0.0,
         }
     }
 
     fn reset(&mut self) {
         self.phase = 0.0;
-        self.env_stage = EnvStage::Idle;
+        self.env.reset();
         self.env_level = 0.0;
         self.feedback_buf = [0.0; 2];
         self.feedback_idx = 0;
-        self.prev_gate = 0.0;
     }
 }
 
@@ -52,6 +42,9 @@ impl Operator {
 pub struct FmMatrix {
     sample_rate: f32,
     operators: [Operator; 4],
+    /// Operator outputs from the previous sample, used by matrix routes that
+    /// point "backwards" (an operator computed later in the sample).
+    last_outputs: [f32; 4],
 }
 
 /// Parameters for a single operator.
@@ -87,6 +80,9 @@ pub struct FmMatrixParams {
     pub brightness: f32,
     pub master: f32,
     pub ops: [OpParams; 4],
+    /// Extra modulation on top of the algorithm: `matrix[src][dst]` is how
+    /// much operator `src` modulates operator `dst` (0 = none, 1 = full).
+    pub matrix: [[f32; 4]; 4],
 }
 
 impl Default for FmMatrixParams {
@@ -102,6 +98,7 @@ impl Default for FmMatrixParams {
                 OpParams { ratio: 3.0, level: 0.3, ..Default::default() },
                 OpParams { ratio: 4.0, level: 0.2, ..Default::default() },
             ],
+            matrix: [[0.0; 4]; 4],
         }
     }
 }
@@ -165,20 +162,20 @@ const ALGORITHMS: [Algorithm; 8] = [
 impl FmMatrix {
     /// Create a new FM Matrix synthesizer.
     pub fn new(sample_rate: f32) -> Self {
+        let sample_rate = sample_rate.max(1.0);
         Self {
-            sample_rate: sample_rate.max(1.0),
-            operators: [
-                Operator::new(),
-                Operator::new(),
-                Operator::new(),
-                Operator::new(),
-            ],
+            sample_rate,
+            operators: std::array::from_fn(|_| Operator::new(sample_rate)),
+            last_outputs: [0.0; 4],
         }
     }
 
     /// Update the sample rate.
     pub fn set_sample_rate(&mut self, sample_rate: f32) {
         self.sample_rate = sample_rate.max(1.0);
+        for op in &mut self.operators {
+            op.env.set_sample_rate(self.sample_rate);
+        }
     }
 
     /// Reset all operators.
@@ -186,6 +183,7 @@ impl FmMatrix {
         for op in &mut self.operators {
             op.reset();
         }
+        self.last_outputs = [0.0; 4];
     }
 
     /// Process a single sample.
@@ -199,68 +197,19 @@ impl FmMatrix {
         params: &FmMatrixParams,
     ) -> f32 {
         let algo = &ALGORITHMS[params.algorithm.min(7)];
-        let dt_ms = 1000.0 / self.sample_rate;
-
-        // Detect gate edges
-        let gate_on = gate > 0.5;
-        let gate_rising = gate > 0.5 && self.operators[0].prev_gate <= 0.5;
-        let gate_falling = gate <= 0.5 && self.operators[0].prev_gate > 0.5;
 
-        // Process envelopes for all operators
+        // Envelopes: every operator follows the module gate exactly like a
+        // standalone ADSR would.
         let mut env_levels = [0.0f32; 4];
         for (i, op) in self.operators.iter_mut().enumerate() {
             let op_params = &params.ops[i];
-
-            // Update gate tracking (use op 0's prev_gate for all)
-            if i == 0 {
-                op.prev_gate = gate;
-            }
-
-            // Envelope state machine
-            if gate_rising {
-                op.env_stage = EnvStage::Attack;
-            } else if gate_falling && op.env_stage != EnvStage::Idle {
-                op.env_stage = EnvStage::Release;
-            }
-
-            // Calculate envelope
-            match op.env_stage {
-                EnvStage::Idle => {
-                    op.env_level = 0.0;
-                }
-                EnvStage::Attack => {
-                    let attack_rate = 1.0 / op_params.attack_ms.max(0.1);
-                    op.env_level += attack_rate * dt_ms;
-                    if op.env_level >= 1.0 {
-                        op.env_level = 1.0;
-                        op.env_stage = EnvStage::Decay;
-                    }
-                }
-                EnvStage::Decay => {
-                    let target = op_params.sustain;
-                    let decay_rate = (1.0 - target) / op_params.decay_ms.max(0.1);
-                    op.env_level -= decay_rate * dt_ms;
-                    if op.env_level <= target {
-                        op.env_level = target;
-                        op.env_stage = EnvStage::Sustain;
-                    }
-                }
-                EnvStage::Sustain => {
-                    op.env_level = op_params.sustain;
-                    if !gate_on {
-                        op.env_stage = EnvStage::Release;
-                    }
-                }
-                EnvStage::Release => {
-                    let release_rate = op.env_level / op_params.release_ms.max(0.1);
-                    op.env_level -= release_rate * dt_ms;
-                    if op.env_level <= 0.001 {
-                        op.env_level = 0.0;
-                        op.env_stage = EnvStage::Idle;
-                    }
-                }
-            }
-
+            op.env_level = op.env.next_sample(
+                gate,
+                op_params.attack_ms * 0.001,
+                op_params.decay_ms * 0.001,
+                op_params.sustain,
+                op_params.release_ms * 0.001,
+            );
             env_levels[i] = op.env_level;
         }
 
@@ -277,13 +226,17 @@ impl FmMatrix {
             let detune_factor = (2.0_f32).powf(op_params.detune / 1200.0);
             let op_freq = freq_hz * op_params.ratio * detune_factor;
 
-            // Gather modulation from other operators based on algorithm
+            // Gather modulation from other operators based on algorithm,
+            // plus the free matrix. Operators above `i` already ran this
+            // sample; the others contribute their previous output.
             let mut fm_mod = 0.0f32;
             for j in 0..4 {
+                let source = if j > i { op_outputs[j] } else { self.last_outputs[j] };
                 let bit_pos = i * 4 + j;
                 if (algo.mod_matrix >> bit_pos) & 1 == 1 {
-                    fm_mod += op_outputs[j];
+                    fm_mod += source;
                 }
+                fm_mod += source * params.matrix[j][i];
             }
 
             // Add external FM to Op4 only
@@ -325,6 +278,8 @@ impl FmMatrix {
             op_outputs[i] = out * env_levels[i] * op_params.level * level_scale;
         }
 
+        self.last_outputs = op_outputs;
+
         // Sum carriers
         let mut output = 0.0f32;
         for i in 0..4 {
@@ -383,4 +338,17 @@ mod tests {
         // Should produce some output
         assert!(max_output > 0.0);
     }
+
+    fn render(params: &FmMatrixParams) -> Vec<f32> {
+        let mut fm = FmMatrix::new(48000.0);
+        (0..2000).map(|_| fm.process_sample(220.0, 1.0, 1.0, 0.0, params)).collect()
+    }
+
+    #[test]
+    fn test_matrix_adds_modulation() {
+        let mut params = FmMatrixParams { algorithm: 7, ..Default::default() };
+        let dry = render(&params);
+        params.matrix[0][1] = 1.0;
+        assert_ne!(dry, render(&params));
+    }
 }
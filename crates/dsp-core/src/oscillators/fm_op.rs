@@ -110,6 +110,11 @@ impl FmOperator {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Return to idle with the phase and feedback cleared.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.sample_rate);
+    }
+
     /// Process a block of audio.
     pub fn process_block(
         &mut self,
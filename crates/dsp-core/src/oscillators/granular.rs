@@ -200,6 +200,18 @@ impl Granular {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Stop every grain and any recording in progress; the loaded buffer
+    /// is kept.
+    pub fn reset(&mut self) {
+        if self.recording {
+            self.stop_recording();
+        }
+        self.grains = [Grain::default(); MAX_GRAINS];
+        self.spawn_phase = 0.0;
+        self.seed = 0xDEAD_BEEF;
+        self.last_trigger = 0.0;
+    }
+
     /// Load sample data into the buffer.
     /// Data should be normalized f32 samples.
     pub fn load_buffer(&mut self, data: &[Sample]) {
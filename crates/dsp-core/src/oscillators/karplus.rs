@@ -93,6 +93,16 @@ impl KarplusStrong {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Empty the delay line, silencing the string.
+    pub fn reset(&mut self) {
+        self.delay_line.fill(0.0);
+        self.write_pos = 0;
+        self.last_output = 0.0;
+        self.prev_gate = 0.0;
+        self.is_active = false;
+        self.frac_delay = 0.0;
+    }
+
     /// Generate noise using LCG.
     fn next_noise(&mut self) -> f32 {
         self.noise_state = self.noise_state.wrapping_mul(1103515245).wrapping_add(12345);
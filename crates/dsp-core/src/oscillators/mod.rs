@@ -18,6 +18,7 @@
 //! - [`Wavetable`] - Wavetable oscillator with morphing
 //! - [`Granular`] - Sample-based granular synthesizer
 //! - [`FmMatrix`] - 4-operator FM synthesizer (DX7 style)
+//! - [`SamplePlayer`] - Audio file playback with loop and V/oct rate
 
 mod vco;
 mod supersaw;
@@ -36,6 +37,7 @@ mod resonator;
 mod wavetable;
 mod granular;
 mod particle_cloud;
+mod sample_player;
 
 pub use vco::{Vco, VcoParams, VcoInputs};
 pub use supersaw::{Supersaw, SupersawParams, SupersawInputs};
@@ -54,3 +56,4 @@ pub use resonator::{Resonator, ResonatorParams, ResonatorInputs};
 pub use wavetable::{Wavetable, WavetableParams, WavetableInputs};
 pub use granular::{Granular, GranularParams, GranularInputs, GrainShape};
 pub use particle_cloud::{ParticleCloud, ParticleCloudParams, ParticleCloudInputs, ParticleMode, OscShape};
+pub use sample_player::{SamplePlayer, SamplePlayerParams, SamplePlayerInputs, MAX_SAMPLE_FRAMES};
@@ -76,6 +76,15 @@ impl NesOsc {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Restart the phases and noise shift registers.
+    pub fn reset(&mut self) {
+        for (i, phase) in self.phases.iter_mut().enumerate() {
+            *phase = i as f32 / 8.0;
+        }
+        self.lfsrs = [1; 8];
+        self.noise_timers = [0.0; 8];
+    }
+
     /// Generate NES pulse wave with specified duty cycle.
     fn nes_pulse(phase: f32, duty: u8) -> f32 {
         let threshold = match duty {
@@ -1,6 +1,7 @@
 //! Noise generator module.
 //!
-//! Generates white, pink, brown, blue, and violet noise with stereo support.
+//! Generates white, pink, brown, blue, violet and LFSR "digital" noise with
+//! stereo support and an optional sample-and-hold rate divider.
 
 use crate::common::{sample_at, Sample};
 
@@ -23,6 +24,20 @@ use crate::common::{sample_at, Sample};
 /// - **Violet noise** (type=4): +6dB/octave slope. Energy increases rapidly with frequency.
 ///   Generated by differentiating white noise.
 ///
+/// - **Digital noise** (type=5 long, type=6 short): 1-bit noise from a 15-bit
+///   linear feedback shift register, modelled on the NES APU noise channel.
+///   Each step computes `feedback = bit0 ^ bitN`, shifts the register right
+///   by one and stores `feedback` in bit 14, with `N = 1` in long mode
+///   (polynomial x^15 + x^14 + 1, period 32767) and `N = 6` in short mode
+///   (x^15 + x^9 + 1, period 93 from the power-on state), which gives the
+///   short mode its metallic, pitched character.
+///
+/// # Rate Divide
+///
+/// `rate_divide` (1-64) holds each generated value for N samples, acting as a
+/// built-in sample-rate reducer. Heavily divided noise doubles as stepped
+/// random CV.
+///
 /// # Stereo
 ///
 /// The stereo parameter controls the width of the stereo field:
@@ -41,14 +56,36 @@ pub struct Noise {
     brown_r: f32,
     prev_white_r: f32,
     prev_pink_r: f32,
+    // Digital (LFSR) state
+    lfsr_l: u16,
+    lfsr_r: u16,
+    // Rate divider (sample-and-hold) state
+    hold_counter: u32,
+    held_l: f32,
+    held_r: f32,
+    /// Seed the sequence restarts from on `reset`
+    start_seed: u32,
 }
 
+/// Power-on value of the NES noise shift register.
+const LFSR_SEED: u16 = 1;
+/// Seed used by [`Noise::new`].
+const DEFAULT_SEED: u32 = 0x1234_5678;
+/// XORed into the seed for the right channel (keeps the historical
+/// 0x8765_4321 right seed for the default seed).
+const RIGHT_SEED_MASK: u32 = 0x9551_1559;
+/// Output scale for 1-bit digital noise, roughly matching white noise RMS.
+const DIGITAL_LEVEL: f32 = 0.6;
+
 /// Parameters for noise generation.
 pub struct NoiseParams<'a> {
     /// Output level (0.0 to 1.0)
     pub level: &'a [Sample],
-    /// Noise type: 0=white, 1=pink, 2=brown, 3=blue, 4=violet
+    /// Noise type: 0=white, 1=pink, 2=brown, 3=blue, 4=violet,
+    /// 5=digital (long LFSR), 6=digital (short LFSR)
     pub noise_type: &'a [Sample],
+    /// Hold each value for N samples (1 = no reduction, up to 64)
+    pub rate_divide: &'a [Sample],
     /// Stereo width (0.0 = mono, 1.0 = full stereo)
     pub stereo: &'a [Sample],
     /// Pan position (-1.0 = left, 0.0 = center, 1.0 = right)
@@ -58,17 +95,30 @@ pub struct NoiseParams<'a> {
 impl Noise {
     /// Create a new noise generator.
     pub fn new() -> Self {
+        Self::new_with_seed(DEFAULT_SEED)
+    }
+
+    /// Create a noise generator whose random sequence starts from `seed`.
+    /// Generators with different seeds are uncorrelated, e.g. one per voice.
+    pub fn new_with_seed(seed: u32) -> Self {
         Self {
-            seed_l: 0x1234_5678,
+            seed_l: seed,
             pink_l: [0.0; 7],
             brown_l: 0.0,
             prev_white_l: 0.0,
             prev_pink_l: 0.0,
-            seed_r: 0x8765_4321, // Different seed for right channel
+            seed_r: seed ^ RIGHT_SEED_MASK, // Different seed for right channel
             pink_r: [0.0; 7],
             brown_r: 0.0,
             prev_white_r: 0.0,
             prev_pink_r: 0.0,
+            lfsr_l: LFSR_SEED,
+            // Different start state for the right channel
+            lfsr_r: 0x2A5B,
+            hold_counter: 0,
+            held_l: 0.0,
+            held_r: 0.0,
+            start_seed: seed,
         }
     }
 
@@ -180,6 +230,25 @@ impl Noise {
         violet * 0.5
     }
 
+    /// Advance a 15-bit NES-style LFSR by one step.
+    fn lfsr_step(state: u16, short: bool) -> u16 {
+        let tap = if short { 6 } else { 1 };
+        let feedback = (state ^ (state >> tap)) & 1;
+        (state >> 1) | (feedback << 14)
+    }
+
+    /// Generate next digital noise sample (left channel).
+    fn next_digital_l(&mut self, short: bool) -> f32 {
+        self.lfsr_l = Self::lfsr_step(self.lfsr_l, short);
+        if self.lfsr_l & 1 == 0 { DIGITAL_LEVEL } else { -DIGITAL_LEVEL }
+    }
+
+    /// Generate next digital noise sample (right channel).
+    fn next_digital_r(&mut self, short: bool) -> f32 {
+        self.lfsr_r = Self::lfsr_step(self.lfsr_r, short);
+        if self.lfsr_r & 1 == 0 { DIGITAL_LEVEL } else { -DIGITAL_LEVEL }
+    }
+
     /// Generate a sample for the given color (left channel).
     fn sample_l(&mut self, color: f32) -> f32 {
         if color < 0.5 {
@@ -190,8 +259,10 @@ impl Noise {
             self.next_brown_l()
         } else if color < 3.5 {
             self.next_blue_l()
-        } else {
+        } else if color < 4.5 {
             self.next_violet_l()
+        } else {
+            self.next_digital_l(color >= 5.5)
         }
     }
 
@@ -205,11 +276,24 @@ impl Noise {
             self.next_brown_r()
         } else if color < 3.5 {
             self.next_blue_r()
-        } else {
+        } else if color < 4.5 {
             self.next_violet_r()
+        } else {
+            self.next_digital_r(color >= 5.5)
         }
     }
 
+    /// Advance the rate divider; returns true when a new value is due.
+    fn tick_hold(&mut self, rate_divide: &[Sample], i: usize) -> bool {
+        if self.hold_counter > 0 {
+            self.hold_counter -= 1;
+            return false;
+        }
+        let divide = sample_at(rate_divide, i, 1.0).round().clamp(1.0, 64.0) as u32;
+        self.hold_counter = divide - 1;
+        true
+    }
+
     /// Process a block of noise (mono output, backwards compatible).
     ///
     /// # Arguments
@@ -223,8 +307,10 @@ impl Noise {
         for i in 0..output.len() {
             let level = sample_at(params.level, i, 0.4).clamp(0.0, 1.0);
             let color = sample_at(params.noise_type, i, 0.0);
-            let noise = self.sample_l(color);
-            output[i] = noise * level;
+            if self.tick_hold(params.rate_divide, i) {
+                self.held_l = self.sample_l(color);
+            }
+            output[i] = self.held_l * level;
         }
     }
 
@@ -252,8 +338,12 @@ impl Noise {
             let stereo = sample_at(params.stereo, i, 0.0).clamp(0.0, 1.0);
             let pan = sample_at(params.pan, i, 0.0).clamp(-1.0, 1.0);
 
-            let noise_l = self.sample_l(color);
-            let noise_r = self.sample_r(color);
+            if self.tick_hold(params.rate_divide, i) {
+                self.held_l = self.sample_l(color);
+                self.held_r = self.sample_r(color);
+            }
+            let noise_l = self.held_l;
+            let noise_r = self.held_r;
 
             // Blend between mono (L=R) and full stereo based on stereo param
             let mono = (noise_l + noise_r) * 0.5;
@@ -272,8 +362,105 @@ impl Noise {
     }
 }
 
+impl Noise {
+    /// Restart the random sequence from `seed` (filter memories are kept).
+    pub fn set_seed(&mut self, seed: u32) {
+        self.seed_l = seed;
+        self.seed_r = seed ^ RIGHT_SEED_MASK;
+        self.start_seed = seed;
+    }
+
+    /// Clear the filter memories and restart the random sequence from the
+    /// last seed set.
+    pub fn reset(&mut self) {
+        *self = Self::new_with_seed(self.start_seed);
+    }
+}
+
 impl Default for Noise {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lfsr_short_mode_period() {
+        let mut state = LFSR_SEED;
+        for step in 1..=93 {
+            state = Noise::lfsr_step(state, true);
+            if step < 93 {
+                assert_ne!(state, LFSR_SEED, "short mode repeated early at step {}", step);
+            }
+        }
+        assert_eq!(state, LFSR_SEED);
+    }
+
+    #[test]
+    fn test_digital_short_output_repeats() {
+        let mut noise = Noise::new();
+        let mut out = vec![0.0; 93 * 4];
+        noise.process_block(
+            &mut out,
+            NoiseParams {
+                level: &[1.0],
+                noise_type: &[6.0],
+                rate_divide: &[1.0],
+                stereo: &[0.0],
+                pan: &[0.0],
+            },
+        );
+        for i in 0..out.len() - 93 {
+            assert_eq!(out[i], out[i + 93]);
+        }
+    }
+
+    fn white(noise: &mut Noise) -> Vec<f32> {
+        let mut out = vec![0.0; 64];
+        noise.process_block(
+            &mut out,
+            NoiseParams {
+                level: &[1.0],
+                noise_type: &[0.0],
+                rate_divide: &[1.0],
+                stereo: &[0.0],
+                pan: &[0.0],
+            },
+        );
+        out
+    }
+
+    #[test]
+    fn test_seeds_give_distinct_sequences() {
+        let first = white(&mut Noise::new_with_seed(1));
+        assert_ne!(first, white(&mut Noise::new_with_seed(2)));
+        assert_eq!(first, white(&mut Noise::new_with_seed(1)));
+
+        let mut reseeded = Noise::new_with_seed(7);
+        reseeded.set_seed(1);
+        assert_eq!(first, white(&mut reseeded));
+    }
+
+    #[test]
+    fn test_rate_divide_holds_values() {
+        let mut noise = Noise::new();
+        let mut out = vec![0.0; 64];
+        noise.process_block(
+            &mut out,
+            NoiseParams {
+                level: &[1.0],
+                noise_type: &[0.0],
+                rate_divide: &[8.0],
+                stereo: &[0.0],
+                pan: &[0.0],
+            },
+        );
+        for chunk in out.chunks(8) {
+            assert!(chunk.iter().all(|&v| v == chunk[0]));
+        }
+        assert_ne!(out[0], out[8]);
+    }
+}
@@ -207,8 +207,15 @@ impl ParticleCloud {
             active_count_cache: 0,
         };
 
-        // Initialize particles with random positions
-        for (i, p) in cloud.particles.iter_mut().enumerate() {
+        cloud.scatter_particles();
+        cloud
+    }
+
+    /// Give every particle a fresh random position and lifetime, with the
+    /// first `target_count` active.
+    fn scatter_particles(&mut self) {
+        for (i, p) in self.particles.iter_mut().enumerate() {
+            *p = Particle::default();
             p.noise_state = 0xDEADBEEF_u32.wrapping_add(i as u32 * 12345);
             p.x = p.next_random_01();
             p.y = p.next_random_01();
@@ -216,21 +223,19 @@ impl ParticleCloud {
             p.vy = p.next_random() * 0.1;
             p.lifetime = 2.0 + p.next_random_01() * 4.0;
             p.age = p.next_random_01() * p.lifetime; // Stagger initial ages
-            p.active = i < 16; // Default 16 active
+            p.active = i < self.target_count;
         }
 
         // Initialize positions cache for UI polling before first process_block
         let mut active = 0;
-        for (idx, p) in cloud.particles.iter().enumerate() {
-            cloud.positions_cache[idx * 2] = p.x;
-            cloud.positions_cache[idx * 2 + 1] = p.y;
+        for (idx, p) in self.particles.iter().enumerate() {
+            self.positions_cache[idx * 2] = p.x;
+            self.positions_cache[idx * 2 + 1] = p.y;
             if p.active {
                 active += 1;
             }
         }
-        cloud.active_count_cache = active;
-
-        cloud
+        self.active_count_cache = active;
     }
 
     /// Update the sample rate.
@@ -238,6 +243,14 @@ impl ParticleCloud {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Restart the particles and clear the input capture; a loaded sample
+    /// is kept.
+    pub fn reset(&mut self) {
+        self.scatter_particles();
+        self.input_buffer.fill(0.0);
+        self.input_write_pos = 0;
+    }
+
     /// Load sample data into the buffer (for Sample mode).
     pub fn load_buffer(&mut self, data: &[Sample]) {
         let len = data.len().min(MAX_BUFFER_SAMPLES);
@@ -79,6 +79,11 @@ impl Resonator {
         }
     }
 
+    /// Update the sample rate.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+    }
+
     fn noise(&mut self) -> f32 {
         self.noise_state = self.noise_state.wrapping_mul(1664525).wrapping_add(1013904223);
         (self.noise_state as f32 / u32::MAX as f32) * 2.0 - 1.0
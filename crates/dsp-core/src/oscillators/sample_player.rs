@@ -0,0 +1,301 @@
+//! Sample Player module.
+//!
+//! Plays back an audio file decoded by the host (mono or stereo f32),
+//! with V/oct playback rate, start/end points and looping.
+
+use crate::common::{input_at, sample_at, Sample};
+
+/// Longest sample a player accepts: 60 s of stereo at 48 kHz.
+pub const MAX_SAMPLE_FRAMES: usize = 60 * 48_000;
+
+/// Length of the crossfades at the loop point, on retrigger and at the
+/// one-shot end.
+const FADE_SECONDS: f32 = 0.005;
+
+/// Sample Player.
+///
+/// Loading a sample starts playback at the start point; a rising gate
+/// restarts it. Without loop the player stops at the end point, fading
+/// out over the last few milliseconds. With loop the end crossfades into
+/// the material just before the start point (silence before the file
+/// start), so the wrap has no click.
+///
+/// Playback uses 4-point Hermite interpolation, so any rate reads cleanly.
+///
+/// # Example
+///
+/// ```ignore
+/// use dsp_core::oscillators::{SamplePlayer, SamplePlayerParams, SamplePlayerInputs};
+///
+/// let mut player = SamplePlayer::new(48000.0);
+/// player.load(&interleaved, 2, 44100.0)?;
+/// player.process_block(&mut out_l, &mut out_r, inputs, params);
+/// ```
+pub struct SamplePlayer {
+    sample_rate: f32,
+    /// Sample rate of the loaded file.
+    source_rate: f32,
+    left: Vec<Sample>,
+    right: Vec<Sample>,
+    /// Playhead in source frames.
+    position: f64,
+    playing: bool,
+    /// Jump to the start point on the next sample.
+    restart: bool,
+    /// Previous playhead, faded out after a retrigger.
+    tail: Option<f64>,
+    /// Output samples left in the retrigger fade.
+    tail_fade: usize,
+    last_gate: f32,
+}
+
+/// Input signals for SamplePlayer.
+pub struct SamplePlayerInputs<'a> {
+    /// Gate/trigger: a rising edge restarts playback
+    pub gate: Option<&'a [Sample]>,
+    /// Playback rate in V/oct (0 = original speed)
+    pub pitch: Option<&'a [Sample]>,
+}
+
+/// Parameters for SamplePlayer.
+pub struct SamplePlayerParams<'a> {
+    /// Loop between start and end (0 = one-shot, 1 = loop)
+    pub looping: &'a [Sample],
+    /// Start point as a fraction of the sample (0-1)
+    pub start: &'a [Sample],
+    /// End point as a fraction of the sample (0-1)
+    pub end: &'a [Sample],
+    /// Output gain (0-2)
+    pub gain: &'a [Sample],
+}
+
+/// 4-point Hermite interpolation between `x0` and `x1`.
+fn hermite(xm1: Sample, x0: Sample, x1: Sample, x2: Sample, t: Sample) -> Sample {
+    let c1 = 0.5 * (x1 - xm1);
+    let c2 = xm1 - 2.5 * x0 + 2.0 * x1 - 0.5 * x2;
+    let c3 = 0.5 * (x2 - xm1) + 1.5 * (x0 - x1);
+    ((c3 * t + c2) * t + c1) * t + x0
+}
+
+impl SamplePlayer {
+    /// Create an empty player.
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate: sample_rate.max(1.0),
+            source_rate: sample_rate.max(1.0),
+            left: Vec::new(),
+            right: Vec::new(),
+            position: 0.0,
+            playing: false,
+            restart: false,
+            tail: None,
+            tail_fade: 0,
+            last_gate: 0.0,
+        }
+    }
+
+    /// Update the output sample rate; playback speed follows.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate.max(1.0);
+    }
+
+    /// Load interleaved samples (1 or 2 channels; extra channels are
+    /// ignored) recorded at `source_rate`, and start playing.
+    ///
+    /// Returns the number of frames, or an error when the sample is longer
+    /// than [`MAX_SAMPLE_FRAMES`].
+    pub fn load(&mut self, data: &[Sample], channels: usize, source_rate: f32) -> Result<usize, String> {
+        if channels == 0 {
+            return Err("Sample has no channels".to_string());
+        }
+        let frames = data.len() / channels;
+        if frames > MAX_SAMPLE_FRAMES {
+            return Err(format!(
+                "Sample too long: {frames} frames (max {MAX_SAMPLE_FRAMES}, 60 s at 48 kHz)"
+            ));
+        }
+        let right_channel = if channels >= 2 { 1 } else { 0 };
+        self.left = data.chunks_exact(channels).map(|frame| frame[0]).collect();
+        self.right = data.chunks_exact(channels).map(|frame| frame[right_channel]).collect();
+        self.source_rate = source_rate.max(1.0);
+        self.tail = None;
+        self.reset();
+        Ok(frames)
+    }
+
+    /// Number of frames loaded.
+    pub fn frames(&self) -> usize {
+        self.left.len()
+    }
+
+    /// Rewind to the start point and play, keeping the loaded sample.
+    pub fn reset(&mut self) {
+        self.playing = !self.left.is_empty();
+        self.restart = true;
+        self.tail = None;
+        self.tail_fade = 0;
+        self.last_gate = 0.0;
+    }
+
+    fn read(&self, position: f64) -> (Sample, Sample) {
+        let base = position.floor();
+        let t = (position - base) as Sample;
+        let base = base as i64;
+        let at = |data: &[Sample], offset: i64| -> Sample {
+            usize::try_from(base + offset)
+                .ok()
+                .and_then(|index| data.get(index).copied())
+                .unwrap_or(0.0)
+        };
+        let channel = |data: &[Sample]| hermite(at(data, -1), at(data, 0), at(data, 1), at(data, 2), t);
+        (channel(&self.left), channel(&self.right))
+    }
+
+    /// Process a block of stereo samples.
+    pub fn process_block(
+        &mut self,
+        out_l: &mut [Sample],
+        out_r: &mut [Sample],
+        inputs: SamplePlayerInputs<'_>,
+        params: SamplePlayerParams<'_>,
+    ) {
+        let len = self.left.len();
+        if len == 0 {
+            out_l.fill(0.0);
+            out_r.fill(0.0);
+            return;
+        }
+
+        let looping = sample_at(params.looping, 0, 0.0) >= 0.5;
+        let start = (sample_at(params.start, 0, 0.0).clamp(0.0, 1.0) * len as f32).floor() as f64;
+        let mut end = (sample_at(params.end, 0, 1.0).clamp(0.0, 1.0) * len as f32).floor() as f64;
+        if end <= start {
+            end = len as f64;
+        }
+        let region = end - start;
+        let fade = (FADE_SECONDS as f64 * self.source_rate as f64).min(region / 2.0).max(1.0);
+        let tail_total = ((FADE_SECONDS * self.sample_rate) as usize).max(1);
+        let base_rate = self.source_rate as f64 / self.sample_rate as f64;
+
+        for (i, (left, right)) in out_l.iter_mut().zip(out_r.iter_mut()).enumerate() {
+            let gate = input_at(inputs.gate, i);
+            if gate > 0.5 && self.last_gate <= 0.5 {
+                if self.playing {
+                    self.tail = Some(self.position);
+                    self.tail_fade = tail_total;
+                }
+                self.playing = true;
+                self.restart = true;
+            }
+            self.last_gate = gate;
+            if self.restart {
+                self.position = start;
+                self.restart = false;
+            }
+
+            let pitch = input_at(inputs.pitch, i).clamp(-4.0, 4.0);
+            let rate = base_rate * 2f64.powf(pitch as f64);
+
+            let (mut l, mut r) = (0.0, 0.0);
+            if self.playing {
+                (l, r) = self.read(self.position);
+                let into_fade = self.position - (end - fade);
+                if into_fade > 0.0 {
+                    let t = (into_fade / fade).min(1.0) as Sample;
+                    if looping {
+                        // Blend toward what precedes the start point, which is
+                        // exactly where the playhead lands after the wrap.
+                        let (wrap_l, wrap_r) = self.read(self.position - region);
+                        l = l * (1.0 - t) + wrap_l * t;
+                        r = r * (1.0 - t) + wrap_r * t;
+                    } else {
+                        l *= 1.0 - t;
+                        r *= 1.0 - t;
+                    }
+                }
+                self.position += rate;
+                if self.position >= end {
+                    if looping {
+                        self.position -= region * ((self.position - start) / region).floor();
+                    } else {
+                        self.playing = false;
+                    }
+                }
+            }
+
+            if let Some(tail) = self.tail {
+                let g = self.tail_fade as Sample / tail_total as Sample;
+                let (tail_l, tail_r) = self.read(tail);
+                l = l * (1.0 - g) + tail_l * g;
+                r = r * (1.0 - g) + tail_r * g;
+                self.tail_fade -= 1;
+                self.tail = (self.tail_fade > 0).then_some(tail + rate);
+            }
+
+            let gain = sample_at(params.gain, i, 1.0).clamp(0.0, 2.0);
+            *left = l * gain;
+            *right = r * gain;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params<'a>(looping: &'a [Sample], start: &'a [Sample], end: &'a [Sample]) -> SamplePlayerParams<'a> {
+        SamplePlayerParams { looping, start, end, gain: &[1.0] }
+    }
+
+    fn render(player: &mut SamplePlayer, frames: usize, gate: Option<&[Sample]>, params: SamplePlayerParams<'_>) -> Vec<Sample> {
+        let mut out_l = vec![0.0; frames];
+        let mut out_r = vec![0.0; frames];
+        player.process_block(&mut out_l, &mut out_r, SamplePlayerInputs { gate, pitch: None }, params);
+        out_l
+    }
+
+    #[test]
+    fn test_unity_rate_plays_the_sample_verbatim_then_stops() {
+        let data: Vec<Sample> = (0..1000).map(|n| (n as Sample * 0.01).sin()).collect();
+        let mut player = SamplePlayer::new(48000.0);
+        assert_eq!(player.load(&data, 1, 48000.0), Ok(1000));
+
+        let out = render(&mut player, 1200, None, params(&[0.0], &[0.0], &[1.0]));
+        // Untouched until the end fade (5 ms = 240 frames).
+        for (played, source) in out[..700].iter().zip(&data) {
+            assert!((played - source).abs() < 1e-5);
+        }
+        assert!(out[1000..].iter().all(|&s| s == 0.0));
+    }
+
+    #[test]
+    fn test_loop_wrap_has_no_jump() {
+        // A ramp makes the wrap a full-scale step without the crossfade.
+        let data: Vec<Sample> = (0..4800).map(|n| (n % 4800) as Sample / 4800.0).collect();
+        let mut player = SamplePlayer::new(48000.0);
+        player.load(&data, 1, 48000.0).unwrap();
+
+        let out = render(&mut player, 12000, None, params(&[1.0], &[0.5], &[1.0]));
+        let biggest_step = out.windows(2).map(|pair| (pair[1] - pair[0]).abs()).fold(0.0, Sample::max);
+        assert!(biggest_step < 0.01, "loop wrap jumps by {biggest_step}");
+        assert!(out[11999] > 0.0);
+    }
+
+    #[test]
+    fn test_gate_restarts_and_oversized_samples_are_rejected() {
+        let data = vec![0.5; 96000];
+        let mut player = SamplePlayer::new(48000.0);
+        player.load(&data, 2, 48000.0).unwrap();
+        render(&mut player, 48000, None, params(&[0.0], &[0.0], &[1.0]));
+        assert!(render(&mut player, 16, None, params(&[0.0], &[0.0], &[1.0])).iter().all(|&s| s == 0.0));
+
+        let gate = vec![1.0; 512];
+        let out = render(&mut player, 512, Some(&gate), params(&[0.0], &[0.0], &[1.0]));
+        assert!((out[511] - 0.5).abs() < 1e-5);
+
+        let too_long = vec![0.0; (MAX_SAMPLE_FRAMES + 1) * 2];
+        assert!(player.load(&too_long, 2, 48000.0).is_err());
+        // The previous sample is kept.
+        assert_eq!(player.frames(), 48000);
+    }
+}
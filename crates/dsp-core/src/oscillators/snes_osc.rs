@@ -135,6 +135,15 @@ impl SnesOsc {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Restart the phases and the decimation.
+    pub fn reset(&mut self) {
+        for (i, phase) in self.phases.iter_mut().enumerate() {
+            *phase = i as f32 / 8.0;
+        }
+        self.decim_counters = [0.0; 8];
+        self.last_samples = [0.0; 8];
+    }
+
     /// Gaussian/cubic interpolation for smooth wavetable playback.
     fn gaussian_interpolate(samples: &[f32; 4], frac: f32) -> f32 {
         let t = frac;
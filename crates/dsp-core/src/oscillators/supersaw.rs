@@ -17,6 +17,8 @@ use crate::common::{input_at, poly_blep, sample_at, Sample};
 /// - Adjustable detune spread (0-100 cents)
 /// - Symmetric voice distribution
 /// - Level weighting (center louder than sides)
+/// - Randomized voice phases, re-scattered on each gate (like hardware
+///   supersaws) so the voices never start comb-filtered
 ///
 /// # Example
 ///
@@ -31,6 +33,9 @@ use crate::common::{input_at, poly_blep, sample_at, Sample};
 pub struct Supersaw {
     sample_rate: f32,
     phases: [f32; 7],
+    /// LCG state for phase scattering (same generator as `Noise`)
+    phase_seed: u32,
+    last_gate: f32,
 }
 
 /// Parameters for Supersaw processing.
@@ -41,12 +46,17 @@ pub struct SupersawParams<'a> {
     pub detune: &'a [Sample],
     /// Output mix level (0.0 to 1.0)
     pub mix: &'a [Sample],
+    /// How far voice phases are scattered on a gate (0 = all voices restart
+    /// in phase, 1 = uniformly random over the cycle)
+    pub phase_spread: &'a [Sample],
 }
 
 /// Input signals for Supersaw modulation.
 pub struct SupersawInputs<'a> {
     /// Pitch CV (1V/octave)
     pub pitch: Option<&'a [Sample]>,
+    /// Gate; each rising edge re-scatters the voice phases
+    pub gate: Option<&'a [Sample]>,
 }
 
 impl Supersaw {
@@ -55,15 +65,40 @@ impl Supersaw {
     /// Voice mix levels (center louder)
     const LEVELS: [f32; 7] = [0.7, 0.8, 0.9, 1.0, 0.9, 0.8, 0.7];
 
+    /// Seed used by `new`, so a fresh Supersaw always sounds the same.
+    const DEFAULT_SEED: u32 = 0x5EED_0007;
+
     /// Create a new Supersaw at the given sample rate.
     pub fn new(sample_rate: f32) -> Self {
-        let mut phases = [0.0; 7];
-        for (i, phase) in phases.iter_mut().enumerate() {
-            *phase = i as f32 / 7.0;
-        }
-        Self {
+        let mut supersaw = Self {
             sample_rate: sample_rate.max(1.0),
-            phases,
+            phases: [0.0; 7],
+            phase_seed: 0,
+            last_gate: 0.0,
+        };
+        supersaw.randomize_phases(Self::DEFAULT_SEED);
+        supersaw
+    }
+
+    /// Reseed the phase generator and spread all voice phases uniformly
+    /// over the cycle.
+    pub fn randomize_phases(&mut self, seed: u32) {
+        self.phase_seed = seed;
+        self.scatter_phases(1.0);
+    }
+
+    /// Next value of the LCG in [0, 1).
+    fn next_random(&mut self) -> f32 {
+        self.phase_seed = self
+            .phase_seed
+            .wrapping_mul(1664525)
+            .wrapping_add(1013904223);
+        (self.phase_seed >> 9) as f32 / 8_388_608.0
+    }
+
+    fn scatter_phases(&mut self, spread: f32) {
+        for v in 0..self.phases.len() {
+            self.phases[v] = self.next_random() * spread;
         }
     }
 
@@ -72,6 +107,12 @@ impl Supersaw {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Scatter the phases again as `new` does.
+    pub fn reset(&mut self) {
+        self.last_gate = 0.0;
+        self.randomize_phases(Self::DEFAULT_SEED);
+    }
+
     /// Process a block of audio.
     ///
     /// # Arguments
@@ -95,6 +136,13 @@ impl Supersaw {
             let detune_cents = sample_at(params.detune, i, 25.0).clamp(0.0, 100.0);
             let mix = sample_at(params.mix, i, 1.0).clamp(0.0, 1.0);
 
+            let gate = input_at(inputs.gate, i);
+            if gate > 0.5 && self.last_gate <= 0.5 {
+                let spread = sample_at(params.phase_spread, i, 1.0).clamp(0.0, 1.0);
+                self.scatter_phases(spread);
+            }
+            self.last_gate = gate;
+
             let frequency = base * 2.0_f32.powf(pitch);
             let mut sample = 0.0;
             let mut total_level = 0.0;
@@ -122,3 +170,36 @@ impl Supersaw {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phases_are_scattered_and_deterministic() {
+        let a = Supersaw::new(48000.0);
+        let b = Supersaw::new(48000.0);
+        assert_eq!(a.phases, b.phases);
+        assert!(a.phases.iter().all(|p| (0.0..1.0).contains(p)));
+        let spread = a.phases.iter().fold(0.0f32, |acc, &p| acc.max(p))
+            - a.phases.iter().fold(1.0f32, |acc, &p| acc.min(p));
+        assert!(spread > 0.3, "phases bunched together: {:?}", a.phases);
+    }
+
+    #[test]
+    fn test_gate_with_zero_spread_aligns_phases() {
+        let mut saw = Supersaw::new(48000.0);
+        let mut output = [0.0; 4];
+        saw.process_block(
+            &mut output,
+            SupersawInputs { pitch: None, gate: Some(&[1.0]) },
+            SupersawParams {
+                base_freq: &[0.0],
+                detune: &[0.0],
+                mix: &[1.0],
+                phase_spread: &[0.0],
+            },
+        );
+        assert!(saw.phases.iter().all(|&p| p == 0.0), "{:?}", saw.phases);
+    }
+}
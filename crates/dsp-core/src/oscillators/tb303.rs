@@ -20,11 +20,19 @@ use crate::common::{input_at, poly_blep, sample_at, Sample};
 ///
 /// # Accent Behavior
 ///
-/// When velocity > 0.7, the accent is triggered, which:
+/// When velocity > 0.7 or the accent input is high at the gate, the accent
+/// is triggered, which:
 /// - Boosts the filter envelope
 /// - Increases output amplitude
 /// - Creates the characteristic "squelch"
 ///
+/// # Slide Behavior
+///
+/// While the slide input is high the note is legato: pitch follows the
+/// pitch input through the glide without waiting for a new gate, and a gate
+/// that rises while the previous note is still sounding does not retrigger
+/// the envelopes.
+///
 /// # Example
 ///
 /// ```ignore
@@ -61,7 +69,7 @@ pub struct Tb303 {
     // Gate state
     gate_on: bool,
     last_gate: f32,
-    last_velocity: f32,
+    accented: bool,
 }
 
 /// Parameters for TB-303.
@@ -90,6 +98,10 @@ pub struct Tb303Inputs<'a> {
     pub gate: Option<&'a [Sample]>,
     /// Velocity (>0.7 triggers accent)
     pub velocity: Option<&'a [Sample]>,
+    /// Accent gate (>0.5 at the gate triggers accent)
+    pub accent: Option<&'a [Sample]>,
+    /// Slide gate (>0.5 ties the note to the previous one)
+    pub slide: Option<&'a [Sample]>,
     /// External cutoff CV modulation
     pub cutoff_cv: Option<&'a [Sample]>,
 }
@@ -118,7 +130,7 @@ impl Tb303 {
             amp_env: 0.0,
             gate_on: false,
             last_gate: 0.0,
-            last_velocity: 0.0,
+            accented: false,
         }
     }
 
@@ -127,6 +139,11 @@ impl Tb303 {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Close the gate and clear the filter and envelopes.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.sample_rate);
+    }
+
     /// Process 3-pole diode ladder filter (18dB/oct).
     ///
     /// This is the characteristic TB-303 filter sound.
@@ -187,6 +204,8 @@ impl Tb303 {
             let pitch_cv = input_at(inputs.pitch, i);
             let gate = input_at(inputs.gate, i);
             let velocity = input_at(inputs.velocity, i).clamp(0.0, 1.0);
+            let accent = velocity > 0.7 || input_at(inputs.accent, i) > 0.5;
+            let slide = input_at(inputs.slide, i) > 0.5;
             let cutoff_cv = input_at(inputs.cutoff_cv, i);
 
             // Gate edge detection
@@ -194,20 +213,27 @@ impl Tb303 {
             let gate_falling = gate <= 0.5 && self.last_gate > 0.5;
             self.last_gate = gate;
 
-            // On gate rising: set target frequency and trigger envelopes
+            // On gate rising: set target frequency and trigger envelopes,
+            // unless sliding from a note that is still sounding
             if gate_rising {
                 // Convert V/oct to Hz (A2 = 110Hz at 0V)
                 self.target_freq = 110.0 * 2.0_f32.powf(pitch_cv);
                 self.gate_on = true;
-                self.last_velocity = velocity;
 
-                // Trigger filter envelope
-                self.filter_env = 1.0;
+                if !(slide && self.amp_env > 0.01) {
+                    self.accented = accent;
+
+                    // Trigger filter envelope
+                    self.filter_env = 1.0;
 
-                // Trigger accent envelope if velocity > 0.7
-                if velocity > 0.7 {
-                    self.accent_env = 1.0;
+                    // Trigger accent envelope
+                    if accent {
+                        self.accent_env = 1.0;
+                    }
                 }
+            } else if self.gate_on && slide {
+                // Tied note: follow the pitch through the glide
+                self.target_freq = 110.0 * 2.0_f32.powf(pitch_cv);
             }
 
             if gate_falling {
@@ -260,7 +286,7 @@ impl Tb303 {
             let filtered = self.process_diode_ladder(osc_out, final_cutoff, resonance);
 
             // Apply VCA with accent amplitude boost
-            let accent_amp_boost = if self.last_velocity > 0.7 { 1.0 + accent_amount * 0.5 } else { 1.0 };
+            let accent_amp_boost = if self.accented { 1.0 + accent_amount * 0.5 } else { 1.0 };
             let audio_out = filtered * self.amp_env * accent_amp_boost;
 
             outputs.audio[i] = audio_out.clamp(-1.0, 1.0);
@@ -268,3 +294,52 @@ impl Tb303 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params() -> Tb303Params<'static> {
+        Tb303Params {
+            waveform: &[0.0],
+            cutoff: &[800.0],
+            resonance: &[0.3],
+            decay: &[0.3],
+            envmod: &[0.5],
+            accent: &[0.6],
+            glide: &[0.02],
+        }
+    }
+
+    #[test]
+    fn test_slide_glides_without_retrigger() {
+        let sample_rate = 48000.0;
+        let mut tb = Tb303::new(sample_rate);
+        let mut audio = vec![0.0; 4800];
+        let mut env = vec![0.0; 4800];
+        let gate = vec![1.0; 4800];
+        let slide = vec![1.0; 4800];
+        let mut pitch = vec![0.0; 4800];
+        pitch[2400..].fill(1.0);
+
+        tb.process_block(
+            Tb303Outputs { audio: &mut audio, env_out: &mut env },
+            Tb303Inputs {
+                pitch: Some(&pitch),
+                gate: Some(&gate),
+                velocity: None,
+                accent: None,
+                slide: Some(&slide),
+                cutoff_cv: None,
+            },
+            params(),
+        );
+
+        // One trigger at the start, then the envelope only decays.
+        assert!(env.windows(2).all(|w| w[1] <= w[0]));
+        // The pitch change is picked up without a new gate and glides
+        // toward A3 (20 ms glide, 50 ms later).
+        assert!(tb.current_freq > 200.0 && tb.current_freq < 220.0, "{}", tb.current_freq);
+        assert!(audio[2400..].iter().any(|s| s.abs() > 0.01));
+    }
+}
@@ -8,12 +8,13 @@ use crate::common::{input_at, poly_blep, sample_at, Sample};
 /// Main VCO (Voltage Controlled Oscillator).
 ///
 /// Features:
-/// - 4 waveforms: sine, triangle, sawtooth, pulse (with PWM)
-/// - Up to 4 unison voices with detune
+/// - 4 waveforms: sine, triangle, sawtooth, pulse (with PWM), or a
+///   continuous morph between neighbouring waveforms
+/// - Up to 4 unison voices with detune and stereo spread
 /// - Linear and exponential FM
 /// - Hard sync input
 /// - Sub-oscillator (1 or 2 octaves down)
-/// - Anti-aliased using polyBLEP
+/// - Anti-aliased using polyBLEP, with optional 2x oversampling for high notes
 ///
 /// # Example
 ///
@@ -34,10 +35,56 @@ pub struct Vco {
     last_sync: f32,
     pwm_smooth: f32,
     phases: [f32; 4],
+    /// Undetuned master phase driving the sync output
+    sync_phase: f32,
     sub_phases: [f32; 4],
     tri_states: [f32; 4],
     voice_count: usize,
     voice_offsets: [f32; 4],
+    /// Decimators of the main (or left), right and sub signals in
+    /// oversampling mode
+    decimators: [HalfBand; 3],
+}
+
+/// Taps of the half-band decimation filter (odd, so the center tap is 0.5).
+const HALF_BAND_TAPS: usize = 31;
+
+/// Half-band lowpass that turns a 2x stream back into one sample per pair.
+///
+/// Windowed-sinc (Blackman) with a cutoff at a quarter of the oversampled
+/// rate; every other tap is zero. Delays the signal by
+/// `(HALF_BAND_TAPS - 1) / 4` output samples.
+struct HalfBand {
+    taps: [f32; HALF_BAND_TAPS],
+    history: [f32; HALF_BAND_TAPS],
+}
+
+impl HalfBand {
+    fn new() -> Self {
+        let center = (HALF_BAND_TAPS / 2) as f32;
+        let mut taps = [0.0; HALF_BAND_TAPS];
+        for (n, tap) in taps.iter_mut().enumerate() {
+            let x = n as f32 - center;
+            let sinc = if x == 0.0 {
+                0.5
+            } else {
+                (std::f32::consts::FRAC_PI_2 * x).sin() / (std::f32::consts::PI * x)
+            };
+            let w = std::f32::consts::TAU * n as f32 / (HALF_BAND_TAPS - 1) as f32;
+            *tap = sinc * (0.42 - 0.5 * w.cos() + 0.08 * (2.0 * w).cos());
+        }
+        let sum: f32 = taps.iter().sum();
+        taps.iter_mut().for_each(|tap| *tap /= sum);
+        Self { taps, history: [0.0; HALF_BAND_TAPS] }
+    }
+
+    /// Push two oversampled samples and return one filtered sample.
+    fn process(&mut self, first: f32, second: f32) -> f32 {
+        self.history.copy_within(2.., 0);
+        self.history[HALF_BAND_TAPS - 2] = first;
+        self.history[HALF_BAND_TAPS - 1] = second;
+        self.history.iter().zip(&self.taps).map(|(x, tap)| x * tap).sum()
+    }
 }
 
 /// Parameters for VCO processing.
@@ -48,6 +95,10 @@ pub struct VcoParams<'a> {
     pub base_freq: &'a [Sample],
     /// Waveform select: 0=sine, 1=triangle, 2=saw, 3=pulse
     pub waveform: &'a [Sample],
+    /// Morph mode (>= 0.5): a fractional `waveform` crossfades the two
+    /// neighbouring waveforms (1.5 = half triangle, half saw) instead of
+    /// switching at the nearest one
+    pub wave_morph: &'a [Sample],
     /// Pulse width (0.05 to 0.95, only for pulse wave)
     pub pwm: &'a [Sample],
     /// Linear FM depth (Hz per unit input)
@@ -62,6 +113,12 @@ pub struct VcoParams<'a> {
     pub sub_mix: &'a [Sample],
     /// Sub-oscillator octave (1.0 = -1 oct, 2.0 = -2 oct)
     pub sub_oct: &'a [Sample],
+    /// Stereo width of the unison voices (0 = mono, 1 = full spread).
+    /// Only used by [`Vco::process_block_stereo`].
+    pub stereo_spread: &'a [Sample],
+    /// 2x oversampling (>= 0.5): the waveforms are rendered at twice the
+    /// sample rate and decimated, for less aliasing on high notes
+    pub oversample: &'a [Sample],
 }
 
 /// Input signals for VCO modulation.
@@ -70,7 +127,7 @@ pub struct VcoInputs<'a> {
     pub pitch: Option<&'a [Sample]>,
     /// Linear FM input
     pub fm_lin: Option<&'a [Sample]>,
-    /// Audio-rate FM input
+    /// Audio-rate FM input, summed with `fm_lin` and scaled by `fm_lin_depth`
     pub fm_audio: Option<&'a [Sample]>,
     /// Exponential FM input
     pub fm_exp: Option<&'a [Sample]>,
@@ -95,10 +152,12 @@ impl Vco {
             last_sync: 0.0,
             pwm_smooth: 0.5,
             phases,
+            sync_phase: 0.0,
             sub_phases,
             tri_states: [0.0; 4],
             voice_count: 1,
             voice_offsets: [0.0; 4],
+            decimators: [HalfBand::new(), HalfBand::new(), HalfBand::new()],
         };
         vco.update_voice_offsets(1.0);
         vco
@@ -109,6 +168,23 @@ impl Vco {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Restart the phases and clear the filter memories; the unison voice
+    /// layout is kept.
+    pub fn reset(&mut self) {
+        let len = self.phases.len() as f32;
+        for (index, phase) in self.phases.iter_mut().enumerate() {
+            *phase = index as f32 / len;
+            self.sub_phases[index] = *phase;
+        }
+        self.last_sync = 0.0;
+        self.pwm_smooth = 0.5;
+        self.sync_phase = 0.0;
+        self.tri_states = [0.0; 4];
+        for decimator in &mut self.decimators {
+            decimator.history = [0.0; HALF_BAND_TAPS];
+        }
+    }
+
     fn update_voice_offsets(&mut self, voices: f32) {
         let count = voices.round().clamp(1.0, 4.0) as usize;
         self.voice_count = count;
@@ -122,18 +198,59 @@ impl Vco {
         }
     }
 
+    /// Pan position (-1 left .. 1 right) of a unison voice at full spread.
+    ///
+    /// Voice 0 stays centered; the others alternate left/right, moving
+    /// further out in pairs (voices 1/2, then 3).
+    fn voice_pan(&self, voice: usize) -> f32 {
+        if voice == 0 {
+            return 0.0;
+        }
+        let pairs = (self.voice_count / 2).max(1) as f32;
+        let distance = voice.div_ceil(2) as f32 / pairs;
+        if voice % 2 == 1 { -distance } else { distance }
+    }
+
     /// Process a block of audio.
     ///
     /// # Arguments
     ///
     /// * `output` - Main audio output buffer
     /// * `sub_output` - Optional sub-oscillator output
-    /// * `sync_output` - Optional sync pulse output
+    /// * `sync_output` - Optional sync pulse output (1.0 for one sample each time the
+    ///   undetuned fundamental wraps or is hard-synced, 0.0 otherwise)
     /// * `inputs` - Modulation inputs
     /// * `params` - Processing parameters
     pub fn process_block(
         &mut self,
         output: &mut [Sample],
+        sub_output: Option<&mut [Sample]>,
+        sync_output: Option<&mut [Sample]>,
+        inputs: VcoInputs<'_>,
+        params: VcoParams<'_>,
+    ) {
+        self.render(output, None, sub_output, sync_output, inputs, params);
+    }
+
+    /// Process a block of audio into a stereo pair, panning the unison
+    /// voices by `stereo_spread` (balance law, so a spread of 0 writes the
+    /// mono signal to both sides). The sub-oscillator stays centered.
+    pub fn process_block_stereo(
+        &mut self,
+        output_l: &mut [Sample],
+        output_r: &mut [Sample],
+        sub_output: Option<&mut [Sample]>,
+        sync_output: Option<&mut [Sample]>,
+        inputs: VcoInputs<'_>,
+        params: VcoParams<'_>,
+    ) {
+        self.render(output_l, Some(output_r), sub_output, sync_output, inputs, params);
+    }
+
+    fn render(
+        &mut self,
+        output: &mut [Sample],
+        mut output_r: Option<&mut [Sample]>,
         mut sub_output: Option<&mut [Sample]>,
         mut sync_output: Option<&mut [Sample]>,
         inputs: VcoInputs<'_>,
@@ -143,7 +260,19 @@ impl Vco {
             return;
         }
 
-        let wave_index = params.waveform.get(0).copied().unwrap_or(2.0);
+        let wave_index = params.waveform.first().copied().unwrap_or(2.0).clamp(0.0, 3.0);
+        let morph = params.wave_morph.first().copied().unwrap_or(0.0) >= 0.5;
+        // Waveforms to blend and the weight of the second one.
+        let (wave_a, wave_b, blend) = if morph {
+            let lower = wave_index.floor().min(2.0);
+            (lower as usize, lower as usize + 1, wave_index - lower)
+        } else {
+            let nearest = wave_index.round() as usize;
+            (nearest, nearest, 0.0)
+        };
+        let oversample = params.oversample.first().copied().unwrap_or(0.0) >= 0.5;
+        let factor = if oversample { 2 } else { 1 };
+        let rate = self.sample_rate * factor as f32;
         let requested_voices = params.unison.get(0).copied().unwrap_or(1.0);
         if requested_voices.round() as usize != self.voice_count {
             self.update_voice_offsets(requested_voices);
@@ -166,9 +295,14 @@ impl Vco {
             let detune_cents = sample_at(params.detune, i, 0.0);
             let sub_mix = sample_at(params.sub_mix, i, 0.0).clamp(0.0, 1.0);
             let sub_oct = sample_at(params.sub_oct, i, 1.0).clamp(1.0, 2.0);
+            let spread = sample_at(params.stereo_spread, i, 0.0).clamp(0.0, 1.0);
+
+            let mut sync_pulse = 0.0;
 
             // Hard sync detection
             if sync > 0.5 && self.last_sync <= 0.5 {
+                self.sync_phase = 0.0;
+                sync_pulse = 1.0;
                 for phase in self.phases.iter_mut().take(self.voice_count) {
                     *phase = 0.0;
                 }
@@ -192,70 +326,86 @@ impl Vco {
             self.pwm_smooth += (pwm_target - self.pwm_smooth) * pwm_coeff;
 
             let sub_div = if sub_oct >= 1.5 { 4.0 } else { 2.0 };
-            let mut sample = 0.0;
-            let mut sub_sample = 0.0;
-            let mut sync_pulse = 0.0;
 
-            // Process each unison voice
-            for v in 0..self.voice_count {
-                let offset = self.voice_offsets[v];
-                let detune_factor = 2.0_f32.powf((detune_cents * offset) / 1200.0);
-                let voice_freq = frequency * detune_factor;
-                let dt = (voice_freq / self.sample_rate).min(1.0);
-
-                let mut next_phase = self.phases[v] + voice_freq / self.sample_rate;
-                if next_phase >= 1.0 {
-                    next_phase -= next_phase.floor();
-                    sync_pulse = 1.0;
-                }
-                self.phases[v] = next_phase;
-                let phase = next_phase;
-
-                // Waveform generation with polyBLEP anti-aliasing
-                let voice_sample = if wave_index < 0.5 {
-                    // Sine
-                    (std::f32::consts::TAU * phase).sin()
-                } else if wave_index < 1.5 {
-                    // Triangle (integrated square)
-                    let mut square = if phase < 0.5 { 1.0 } else { -1.0 };
-                    square += poly_blep(phase, dt);
-                    square -= poly_blep((phase - 0.5).rem_euclid(1.0), dt);
-                    let tri = &mut self.tri_states[v];
-                    *tri += square * (2.0 * voice_freq / self.sample_rate);
-                    *tri = tri.clamp(-1.0, 1.0);
-                    *tri
-                } else if wave_index < 2.5 {
-                    // Sawtooth
-                    let mut saw = 2.0 * phase - 1.0;
-                    saw -= poly_blep(phase, dt);
-                    saw
-                } else {
-                    // Pulse with PWM
-                    let mut pulse = if phase < self.pwm_smooth { 1.0 } else { -1.0 };
-                    pulse += poly_blep(phase, dt);
-                    pulse -= poly_blep((phase - self.pwm_smooth).rem_euclid(1.0), dt);
-                    pulse
-                };
-                sample += voice_sample;
-
-                // Sub-oscillator (square wave)
-                let sub_freq = voice_freq / sub_div;
-                let sub_dt = (sub_freq / self.sample_rate).min(1.0);
-                self.sub_phases[v] += sub_freq / self.sample_rate;
-                if self.sub_phases[v] >= 1.0 {
-                    self.sub_phases[v] -= self.sub_phases[v].floor();
+            // Sync out follows the fundamental, not the detuned unison voices,
+            // so a slave VCO gets exactly one reset per cycle.
+            self.sync_phase += frequency / self.sample_rate;
+            if self.sync_phase >= 1.0 {
+                self.sync_phase -= self.sync_phase.floor();
+                sync_pulse = 1.0;
+            }
+
+            // Main (or left), right and sub signals of each oversampled step
+            let mut steps = [[0.0; 3]; 2];
+            for step in steps.iter_mut().take(factor) {
+                let mut sample = 0.0;
+                let mut sample_l = 0.0;
+                let mut sample_r = 0.0;
+                let mut sub_sample = 0.0;
+
+                // Process each unison voice
+                for v in 0..self.voice_count {
+                    let offset = self.voice_offsets[v];
+                    let detune_factor = 2.0_f32.powf((detune_cents * offset) / 1200.0);
+                    let voice_freq = frequency * detune_factor;
+                    let dt = (voice_freq / rate).min(1.0);
+
+                    let mut next_phase = self.phases[v] + voice_freq / rate;
+                    if next_phase >= 1.0 {
+                        next_phase -= next_phase.floor();
+                    }
+                    self.phases[v] = next_phase;
+                    let phase = next_phase;
+
+                    let mut voice_sample = self.wave_sample(wave_a, v, phase, dt, voice_freq / rate);
+                    if wave_b != wave_a {
+                        let next = self.wave_sample(wave_b, v, phase, dt, voice_freq / rate);
+                        voice_sample += (next - voice_sample) * blend;
+                    }
+                    sample += voice_sample;
+                    if output_r.is_some() {
+                        let pan = self.voice_pan(v) * spread;
+                        sample_l += voice_sample * (1.0 - pan.max(0.0));
+                        sample_r += voice_sample * (1.0 + pan.min(0.0));
+                    }
+
+                    // Sub-oscillator (square wave)
+                    let sub_freq = voice_freq / sub_div;
+                    let sub_dt = (sub_freq / rate).min(1.0);
+                    self.sub_phases[v] += sub_freq / rate;
+                    if self.sub_phases[v] >= 1.0 {
+                        self.sub_phases[v] -= self.sub_phases[v].floor();
+                    }
+                    let sub_phase = self.sub_phases[v];
+                    let mut sub_wave = if sub_phase < 0.5 { 1.0 } else { -1.0 };
+                    sub_wave += poly_blep(sub_phase, sub_dt);
+                    sub_wave -= poly_blep((sub_phase - 0.5).rem_euclid(1.0), sub_dt);
+                    sub_sample += sub_wave;
                 }
-                let sub_phase = self.sub_phases[v];
-                let mut sub_wave = if sub_phase < 0.5 { 1.0 } else { -1.0 };
-                sub_wave += poly_blep(sub_phase, sub_dt);
-                sub_wave -= poly_blep((sub_phase - 0.5).rem_euclid(1.0), sub_dt);
-                sub_sample += sub_wave;
+
+                // Average voices
+                let count = self.voice_count as f32;
+                let main = if output_r.is_some() { sample_l } else { sample };
+                *step = [main / count, sample_r / count, sub_sample / count];
             }
 
-            // Average voices and write outputs
-            sample /= self.voice_count as f32;
-            sub_sample /= self.voice_count as f32;
-            output[i] = sample + sub_sample * sub_mix;
+            let [main, right, sub_sample] = if oversample {
+                let [first, second] = steps;
+                let [main, right, sub] = &mut self.decimators;
+                [
+                    main.process(first[0], second[0]),
+                    right.process(first[1], second[1]),
+                    sub.process(first[2], second[2]),
+                ]
+            } else {
+                steps[0]
+            };
+
+            // Write outputs
+            output[i] = main + sub_sample * sub_mix;
+            if let Some(ref mut out_r) = output_r {
+                out_r[i] = right + sub_sample * sub_mix;
+            }
             if let Some(ref mut sub_buf) = sub_buffer {
                 sub_buf[i] = sub_sample;
             }
@@ -264,4 +414,130 @@ impl Vco {
             }
         }
     }
+
+    /// One sample of waveform `wave` (0=sine .. 3=pulse) for unison voice
+    /// `voice`, anti-aliased with polyBLEP. `increment` is the unclamped
+    /// phase increment per rendered sample.
+    fn wave_sample(&mut self, wave: usize, voice: usize, phase: f32, dt: f32, increment: f32) -> f32 {
+        match wave {
+            // Sine
+            0 => (std::f32::consts::TAU * phase).sin(),
+            // Triangle (integrated square)
+            1 => {
+                let mut square = if phase < 0.5 { 1.0 } else { -1.0 };
+                square += poly_blep(phase, dt);
+                square -= poly_blep((phase - 0.5).rem_euclid(1.0), dt);
+                let tri = &mut self.tri_states[voice];
+                *tri += square * (2.0 * increment);
+                *tri = tri.clamp(-1.0, 1.0);
+                *tri
+            }
+            // Sawtooth
+            2 => {
+                let mut saw = 2.0 * phase - 1.0;
+                saw -= poly_blep(phase, dt);
+                saw
+            }
+            // Pulse with PWM
+            _ => {
+                let mut pulse = if phase < self.pwm_smooth { 1.0 } else { -1.0 };
+                pulse += poly_blep(phase, dt);
+                pulse -= poly_blep((phase - self.pwm_smooth).rem_euclid(1.0), dt);
+                pulse
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f32 = 48000.0;
+
+    fn render(freq: f32, waveform: f32, morph: f32, oversample: f32, frames: usize) -> Vec<Sample> {
+        let mut vco = Vco::new(SAMPLE_RATE);
+        let mut output = vec![0.0; frames];
+        let params = VcoParams {
+            base_freq: &[freq],
+            waveform: &[waveform],
+            wave_morph: &[morph],
+            pwm: &[0.5],
+            fm_lin_depth: &[0.0],
+            fm_exp_depth: &[0.0],
+            unison: &[1.0],
+            detune: &[0.0],
+            sub_mix: &[0.0],
+            sub_oct: &[1.0],
+            stereo_spread: &[0.0],
+            oversample: &[oversample],
+        };
+        let inputs = VcoInputs { pitch: None, fm_lin: None, fm_audio: None, fm_exp: None, pwm: None, sync: None };
+        vco.process_block(&mut output, None, None, inputs, params);
+        output
+    }
+
+    /// Magnitude of DFT bin `bin` of `window`.
+    fn bin_magnitude(window: &[Sample], bin: usize) -> f32 {
+        let (mut re, mut im) = (0.0f64, 0.0f64);
+        for (i, &x) in window.iter().enumerate() {
+            let angle = std::f64::consts::TAU * ((bin * i) % window.len()) as f64 / window.len() as f64;
+            re += x as f64 * angle.cos();
+            im += x as f64 * angle.sin();
+        }
+        re.hypot(im) as f32
+    }
+
+    /// Harmonics 2-16 relative to the fundamental of a 375 Hz tone (128
+    /// samples per cycle, so the analysis window holds whole cycles).
+    fn brightness(waveform: f32, morph: f32) -> f32 {
+        let output = render(375.0, waveform, morph, 0.0, 8192);
+        let window = &output[4096..];
+        let harmonic = |n: usize| {
+            let (mut re, mut im) = (0.0f32, 0.0f32);
+            for (i, &x) in window.iter().enumerate() {
+                let angle = std::f32::consts::TAU * (n * i) as f32 / 128.0;
+                re += x * angle.cos();
+                im += x * angle.sin();
+            }
+            re.hypot(im)
+        };
+        (2..=16).map(harmonic).sum::<f32>() / harmonic(1)
+    }
+
+    #[test]
+    fn test_morph_blends_neighbouring_waveforms() {
+        let (triangle, saw) = (brightness(1.0, 1.0), brightness(2.0, 1.0));
+        let blend = brightness(1.5, 1.0);
+        assert!(triangle < blend && blend < saw, "tri {triangle}, blend {blend}, saw {saw}");
+        // Without morph, 1.5 rounds to the saw.
+        assert!((brightness(1.5, 0.0) - saw).abs() < 1e-3 * saw);
+    }
+
+    /// Energy outside the harmonics of a high saw, relative to the energy
+    /// of the harmonics. The saw does 437 cycles per 4096 samples, so every
+    /// harmonic and every alias falls on an exact DFT bin.
+    fn saw_aliasing(oversample: f32) -> f32 {
+        const WINDOW: usize = 4096;
+        const CYCLES: usize = 437;
+        let freq = CYCLES as f32 * SAMPLE_RATE / WINDOW as f32;
+        let output = render(freq, 2.0, 0.0, oversample, 2 * WINDOW);
+        let window = &output[WINDOW..];
+
+        let mean = window.iter().sum::<f32>() / WINDOW as f32;
+        let total: f32 = window.iter().map(|x| (x - mean) * (x - mean)).sum();
+        // Parseval: a real tone at bin k holds 2|X_k|^2 / N of the energy.
+        let harmonics: f32 = (1..)
+            .map(|n| n * CYCLES)
+            .take_while(|&bin| bin < WINDOW / 2)
+            .map(|bin| 2.0 * bin_magnitude(window, bin).powi(2) / WINDOW as f32)
+            .sum();
+        (total - harmonics).max(0.0) / harmonics
+    }
+
+    #[test]
+    fn test_oversampling_reduces_aliasing_of_a_high_saw() {
+        let (plain, oversampled) = (saw_aliasing(0.0), saw_aliasing(1.0));
+        assert!(oversampled < plain * 0.25, "aliasing {plain} without, {oversampled} with oversampling");
+    }
 }
@@ -68,6 +68,11 @@ impl Wavetable {
         wt
     }
 
+    /// Update the sample rate.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+    }
+
     fn init_tables(&mut self) {
         // Bank 0: Basic - Classic waveforms
         self.generate_basic_bank();
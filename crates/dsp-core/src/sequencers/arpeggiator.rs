@@ -325,6 +325,23 @@ impl Arpeggiator {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Release every held note and rewind the pattern.
+    pub fn reset(&mut self) {
+        self.clear_notes();
+        self.random_pattern.clear();
+        self.gate_samples = 0;
+        self.ratchet_count = 1;
+        self.ratchet_current = 0;
+        self.current_cv = 0.0;
+        self.current_accent = 0.0;
+        self.prev_gate_in = 0.0;
+        self.prev_clock = 0.0;
+        self.gate_low_samples = 0;
+        self.euclid_step = 0;
+        self.swing_ratchet_count = 1;
+        self.rng = Xorshift32::new(12345);
+    }
+
     fn build_pattern(&mut self, mode: ArpMode, octaves: usize) {
         self.pattern.clear();
 
@@ -142,6 +142,11 @@ impl MasterClock {
         }
     }
 
+    /// Rewind to the first beat of a bar with every output low.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.sample_rate);
+    }
+
 
     /// Process a block of samples.
     pub fn process_block(
@@ -0,0 +1,201 @@
+//! Clock divider / multiplier.
+//!
+//! Turns an incoming clock into a slower or faster one. Division counts
+//! rising edges; multiplication measures the input period and spreads the
+//! extra pulses evenly across it.
+
+use crate::common::{input_at, sample_at, Sample};
+
+/// Labels of the selectable ratios, indexed by the `ratio` param.
+pub const CLOCK_DIV_LABELS: [&str; 10] = ["/16", "/8", "/4", "/3", "/2", "x1", "x2", "x3", "x4", "x8"];
+
+/// `(divide, multiply)` of each ratio in [`CLOCK_DIV_LABELS`].
+pub const CLOCK_DIV_RATIOS: [(u32, u32); 10] =
+    [(16, 1), (8, 1), (4, 1), (3, 1), (2, 1), (1, 1), (1, 2), (1, 3), (1, 4), (1, 8)];
+
+/// Output pulse length in seconds (shortened for fast multiplied clocks).
+const PULSE_SECONDS: f32 = 0.005;
+
+/// Clock divider / multiplier.
+///
+/// A rising edge on `reset` restarts the count, so the next input pulse
+/// produces an output pulse. Multiplication needs two input pulses to
+/// learn the period; until then only the input pulses pass.
+///
+/// # Example
+///
+/// ```ignore
+/// use dsp_core::sequencers::{ClockDivider, ClockDividerInputs, ClockDividerParams};
+///
+/// let mut div = ClockDivider::new(44100.0);
+/// let mut out = [0.0f32; 128];
+/// div.process_block(&mut out, ClockDividerInputs { clock: Some(&clock), reset: None }, ClockDividerParams { ratio: &[2.0] });
+/// ```
+pub struct ClockDivider {
+    sample_rate: f32,
+    last_clock: f32,
+    last_reset: f32,
+    /// Input pulses since the last divided output pulse.
+    count: u32,
+    /// Samples since the last input pulse.
+    since_edge: u32,
+    /// Measured input period in samples (0 until two pulses were seen).
+    period: u32,
+    seen_edge: bool,
+    /// Next multiplied pulse due in the current input period.
+    sub_pulse: u32,
+    /// Samples left in the current output pulse.
+    pulse_remaining: u32,
+}
+
+/// Input signals for ClockDivider.
+pub struct ClockDividerInputs<'a> {
+    /// Clock to divide or multiply (rising edges)
+    pub clock: Option<&'a [Sample]>,
+    /// Realign the count (rising edge)
+    pub reset: Option<&'a [Sample]>,
+}
+
+/// Parameters for ClockDivider.
+pub struct ClockDividerParams<'a> {
+    /// Index into [`CLOCK_DIV_RATIOS`] (0-9, default 4 = /2)
+    pub ratio: &'a [Sample],
+}
+
+impl ClockDivider {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate: sample_rate.max(1.0),
+            last_clock: 0.0,
+            last_reset: 0.0,
+            count: 0,
+            since_edge: 0,
+            period: 0,
+            seen_edge: false,
+            sub_pulse: 0,
+            pulse_remaining: 0,
+        }
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate.max(1.0);
+    }
+
+    /// Forget the count and the measured period.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.sample_rate);
+    }
+
+    /// Start an output pulse, no longer than half the output period.
+    fn fire(&mut self, multiply: u32) {
+        let mut length = (PULSE_SECONDS * self.sample_rate).max(1.0) as u32;
+        if self.period > 0 {
+            length = length.min((self.period / (2 * multiply)).max(1));
+        }
+        self.pulse_remaining = length;
+    }
+
+    /// Process a block of samples.
+    pub fn process_block(
+        &mut self,
+        output: &mut [Sample],
+        inputs: ClockDividerInputs<'_>,
+        params: ClockDividerParams<'_>,
+    ) {
+        for (i, out) in output.iter_mut().enumerate() {
+            let index = sample_at(params.ratio, i, 4.0).round().clamp(0.0, 9.0) as usize;
+            let (divide, multiply) = CLOCK_DIV_RATIOS[index];
+
+            let reset = input_at(inputs.reset, i);
+            if reset > 0.5 && self.last_reset <= 0.5 {
+                self.count = 0;
+                self.sub_pulse = multiply;
+            }
+            self.last_reset = reset;
+
+            self.since_edge = self.since_edge.saturating_add(1);
+            let clock = input_at(inputs.clock, i);
+            if clock > 0.5 && self.last_clock <= 0.5 {
+                if self.seen_edge {
+                    self.period = self.since_edge;
+                }
+                self.seen_edge = true;
+                self.since_edge = 0;
+                if self.count.is_multiple_of(divide) {
+                    self.fire(multiply);
+                }
+                self.count = (self.count + 1) % divide;
+                self.sub_pulse = 1;
+            } else if self.period > 0
+                && self.sub_pulse < multiply
+                && self.since_edge as u64 * multiply as u64 >= self.period as u64 * self.sub_pulse as u64
+            {
+                self.sub_pulse += 1;
+                self.fire(multiply);
+            }
+            self.last_clock = clock;
+
+            *out = if self.pulse_remaining > 0 {
+                self.pulse_remaining -= 1;
+                1.0
+            } else {
+                0.0
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f32 = 48000.0;
+
+    /// A 2 ms pulse every `period` samples, `pulses` times.
+    fn clock(period: usize, pulses: usize) -> Vec<Sample> {
+        (0..period * pulses).map(|i| if i % period < 96 { 1.0 } else { 0.0 }).collect()
+    }
+
+    fn rising_edges(signal: &[Sample]) -> Vec<usize> {
+        let mut last = 0.0;
+        let mut edges = Vec::new();
+        for (i, &s) in signal.iter().enumerate() {
+            if s > 0.5 && last <= 0.5 {
+                edges.push(i);
+            }
+            last = s;
+        }
+        edges
+    }
+
+    fn run(ratio: &str, input: &[Sample]) -> Vec<Sample> {
+        let index = CLOCK_DIV_LABELS.iter().position(|&label| label == ratio).unwrap();
+        let mut div = ClockDivider::new(SAMPLE_RATE);
+        let mut out = vec![0.0; input.len()];
+        let params = ClockDividerParams { ratio: &[index as f32] };
+        div.process_block(&mut out, ClockDividerInputs { clock: Some(input), reset: None }, params);
+        out
+    }
+
+    #[test]
+    fn test_multiply_spreads_pulses_over_the_period() {
+        let out = run("x4", &clock(4800, 4));
+        // Input pulses pass from the start; the extra ones once the period is known.
+        let edges = rising_edges(&out);
+        assert_eq!(edges[..2], [0, 4800]);
+        assert_eq!(edges[2..], [6000, 7200, 8400, 9600, 10800, 12000, 13200, 14400, 15600, 16800, 18000]);
+    }
+
+    #[test]
+    fn test_reset_realigns_the_division() {
+        let input = clock(1000, 8);
+        let mut reset = vec![0.0; input.len()];
+        reset[2500..2600].fill(1.0);
+        let mut div = ClockDivider::new(SAMPLE_RATE);
+        let mut out = vec![0.0; input.len()];
+        let params = ClockDividerParams { ratio: &[2.0] };
+        div.process_block(&mut out, ClockDividerInputs { clock: Some(&input), reset: Some(&reset) }, params);
+        // /4: pulse 0, then the reset makes pulse 3 the new downbeat.
+        assert_eq!(rising_edges(&out), [0, 3000, 7000]);
+    }
+}
@@ -0,0 +1,126 @@
+//! CV/Gate Bank.
+//!
+//! A CV/gate holder module controlled by the host (JavaScript, Tauri or
+//! any external sequencer). The Mario song player is a 5-channel bank.
+
+use crate::common::Sample;
+
+/// Maximum number of channels in a bank.
+pub const MAX_BANK_CHANNELS: usize = 16;
+
+/// Number of channels in the Mario module.
+pub const MARIO_CHANNELS: usize = 5;
+
+/// CV/Gate Bank.
+///
+/// Holds CV and gate values for 1-16 channels. The values are set
+/// externally by the host and output as constant CV/gate signals.
+///
+/// This module has no internal sequencing logic - it's purely
+/// a bridge between a host-side sequencer and the audio graph.
+///
+/// # Outputs
+///
+/// - One CV output per channel (pitch as V/oct)
+/// - One gate output per channel (0 or 1)
+///
+/// # Example
+///
+/// ```ignore
+/// use dsp_core::sequencers::CvBank;
+///
+/// let mut bank = CvBank::new(4);
+///
+/// // Set channel 0 to play middle C with gate on
+/// bank.set_cv(0, 0.0);  // Middle C = 0V in V/oct
+/// bank.set_gate(0, 1.0);
+///
+/// // Fill output buffers
+/// let mut cv_out = [0.0f32; 128];
+/// let mut gate_out = [0.0f32; 128];
+/// bank.fill_channel(0, &mut cv_out, &mut gate_out);
+/// ```
+pub struct CvBank {
+    cv: Vec<f32>,
+    gate: Vec<f32>,
+}
+
+impl CvBank {
+    /// Create a bank of `channels` channels (clamped to 1-16).
+    pub fn new(channels: usize) -> Self {
+        let channels = channels.clamp(1, MAX_BANK_CHANNELS);
+        Self {
+            cv: vec![0.0; channels],
+            gate: vec![0.0; channels],
+        }
+    }
+
+    /// Number of channels in the bank.
+    pub fn channels(&self) -> usize {
+        self.cv.len()
+    }
+
+    /// Close every gate and return the CVs to 0 V.
+    pub fn reset(&mut self) {
+        self.cv.fill(0.0);
+        self.gate.fill(0.0);
+    }
+
+    /// Set the CV value for a channel (0-based).
+    ///
+    /// CV is in V/oct format where 0V = middle C (MIDI 60).
+    pub fn set_cv(&mut self, channel: usize, value: f32) {
+        if let Some(cv) = self.cv.get_mut(channel) {
+            *cv = value;
+        }
+    }
+
+    /// Set the gate value for a channel (0-based).
+    ///
+    /// Gate should be 0.0 (off) or 1.0 (on).
+    pub fn set_gate(&mut self, channel: usize, value: f32) {
+        if let Some(gate) = self.gate.get_mut(channel) {
+            *gate = value;
+        }
+    }
+
+    /// Get the current CV value for a channel.
+    pub fn cv(&self, channel: usize) -> f32 {
+        self.cv.get(channel).copied().unwrap_or(0.0)
+    }
+
+    /// Get the current gate value for a channel.
+    pub fn gate(&self, channel: usize) -> f32 {
+        self.gate.get(channel).copied().unwrap_or(0.0)
+    }
+
+    /// Fill one channel's CV and gate output buffers with the held values.
+    pub fn fill_channel(&self, channel: usize, cv_out: &mut [Sample], gate_out: &mut [Sample]) {
+        cv_out.fill(self.cv(channel));
+        gate_out.fill(self.gate(channel));
+    }
+}
+
+impl Default for CvBank {
+    fn default() -> Self {
+        Self::new(MARIO_CHANNELS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_out_of_range_channels_are_ignored() {
+        let mut bank = CvBank::new(3);
+        bank.set_cv(2, 1.5);
+        bank.set_cv(3, 2.0);
+        bank.set_gate(3, 1.0);
+        assert_eq!(bank.cv(2), 1.5);
+        assert_eq!(bank.cv(3), 0.0);
+        assert_eq!(bank.gate(3), 0.0);
+        assert_eq!(CvBank::new(0).channels(), 1);
+        assert_eq!(CvBank::new(64).channels(), MAX_BANK_CHANNELS);
+    }
+}
@@ -177,6 +177,22 @@ impl DrumSequencer {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Rewind to the first step with every gate low; the pattern is kept.
+    pub fn reset(&mut self) {
+        self.current_step = 0;
+        self.phase = 0.0;
+        self.gate_on = [false; DRUM_TRACKS];
+        self.gate_samples = [0; DRUM_TRACKS];
+        self.swing_pending = false;
+        self.swing_delay_remaining = 0;
+        self.swing_gates = [false; DRUM_TRACKS];
+        self.swing_accents = [false; DRUM_TRACKS];
+        self.prev_clock = 0.0;
+        self.prev_reset = 0.0;
+        self.current_gates = [0.0; DRUM_TRACKS];
+        self.current_accents = [0.0; DRUM_TRACKS];
+    }
+
     /// Get current step position (0-15).
     pub fn current_step(&self) -> usize {
         self.current_step
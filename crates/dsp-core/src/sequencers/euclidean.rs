@@ -139,6 +139,19 @@ impl EuclideanSequencer {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Rewind to the first step with the gate low.
+    pub fn reset(&mut self) {
+        self.current_step = 0;
+        self.phase = 0.0;
+        self.gate_on = false;
+        self.gate_samples = 0;
+        self.swing_pending = false;
+        self.swing_delay_remaining = 0;
+        self.prev_clock = 0.0;
+        self.prev_reset = 0.0;
+        self.current_gate = 0.0;
+    }
+
     /// Compute Euclidean rhythm using Bresenham-style distribution.
     fn compute_pattern(&mut self, steps: usize, pulses: usize, rotation: usize) {
         let steps = steps.clamp(2, EUCLIDEAN_MAX_STEPS);
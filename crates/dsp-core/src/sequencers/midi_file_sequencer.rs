@@ -4,6 +4,8 @@
 
 use crate::common::{sample_at, Sample, A4_MIDI};
 
+use super::smf::SmfFile;
+
 /// Number of output tracks.
 pub const MIDI_TRACKS: usize = 8;
 
@@ -26,7 +28,7 @@ pub struct MidiEvent {
 pub const MAX_NOTES_PER_TRACK: usize = 8192;
 
 /// A single MIDI note event.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct MidiNote {
     /// Position in ticks from start
     pub tick: u32,
@@ -137,6 +139,8 @@ pub struct MidiFileSequencerParams<'a> {
     pub enabled: &'a [Sample],
     /// Tempo in BPM (40-300)
     pub tempo: &'a [Sample],
+    /// Playback speed relative to `tempo` (0.25-4)
+    pub tempo_scale: &'a [Sample],
     /// Gate length percentage (10-100%)
     pub gate_length: &'a [Sample],
     /// Loop playback (0 = off, 1 = on)
@@ -311,6 +315,16 @@ impl MidiFileSequencer {
         self.total_ticks
     }
 
+    /// Ticks per quarter note of the loaded data.
+    pub fn ticks_per_beat(&self) -> u32 {
+        self.ticks_per_beat
+    }
+
+    /// Loaded notes of a track, sorted by tick (empty past [`MIDI_TRACKS`]).
+    pub fn track_notes(&self, track: usize) -> &[MidiNote] {
+        self.tracks.get(track).map_or(&[], |track| track.notes.as_slice())
+    }
+
     /// Seek to a specific tick position.
     pub fn seek_to_tick(&mut self, tick: u32) {
         self.current_tick = tick as f64;
@@ -340,6 +354,16 @@ impl MidiFileSequencer {
         self.reset_voices();
     }
 
+    /// Rewind to the start with every gate low; the loaded notes are kept.
+    pub fn reset(&mut self) {
+        self.seek_to_tick(0);
+        self.current_cv = [0.0; MIDI_TRACKS];
+        self.current_velocity = [0.0; MIDI_TRACKS];
+        self.prev_reset = 0.0;
+        self.playing = false;
+        self.event_tail = self.event_head;
+    }
+
     /// Parse JSON MIDI data and load into tracks.
     ///
     /// Expected format:
@@ -359,16 +383,7 @@ impl MidiFileSequencer {
     /// }
     /// ```
     pub fn parse_midi_data(&mut self, json: &str) {
-        // Reset all tracks
-        for track in &mut self.tracks {
-            track.notes.clear();
-            track.note_index = 0;
-            track.active_note = None;
-            track.note_remaining = 0;
-        }
-        self.current_tick = 0.0;
-        self.total_ticks = 0;
-        self.ticks_per_beat = 480;
+        self.clear_tracks();
 
         // Parse ticksPerBeat
         if let Some(start) = json.find("\"ticksPerBeat\"") {
@@ -447,6 +462,38 @@ impl MidiFileSequencer {
             }
         }
 
+        self.finish_load();
+    }
+
+    /// Load the notes of a parsed `.mid` file, as [`parse_midi_data`](Self::parse_midi_data)
+    /// does for its JSON form. Tracks past [`MIDI_TRACKS`] and notes past
+    /// [`MAX_NOTES_PER_TRACK`] are ignored.
+    pub fn load_file(&mut self, file: &SmfFile) {
+        self.clear_tracks();
+        self.ticks_per_beat = file.ticks_per_beat.max(1);
+        self.total_ticks = file.total_ticks;
+        for (track, source) in self.tracks.iter_mut().zip(&file.tracks) {
+            let len = source.notes.len().min(MAX_NOTES_PER_TRACK);
+            track.notes.extend_from_slice(&source.notes[..len]);
+        }
+        self.finish_load();
+    }
+
+    /// Empty all tracks and rewind, before loading new data.
+    fn clear_tracks(&mut self) {
+        for track in &mut self.tracks {
+            track.notes.clear();
+            track.note_index = 0;
+            track.active_note = None;
+            track.note_remaining = 0;
+        }
+        self.current_tick = 0.0;
+        self.total_ticks = 0;
+        self.ticks_per_beat = 480;
+    }
+
+    /// Sort the loaded notes, assign their voices and start playback.
+    fn finish_load(&mut self) {
         // Pre-allocate voices PER TRACK (not globally)
         // This ensures each track has independent polyphony
         let vc = self.voice_count.max(1).min(MAX_POLY_VOICES);
@@ -622,7 +669,8 @@ impl MidiFileSequencer {
 
         // Read params
         let enabled = sample_at(params.enabled, 0, 1.0) > 0.5;
-        let tempo = sample_at(params.tempo, 0, 120.0).clamp(40.0, 300.0);
+        let tempo = sample_at(params.tempo, 0, 120.0).clamp(40.0, 300.0)
+            * sample_at(params.tempo_scale, 0, 1.0).clamp(0.25, 4.0);
         let gate_pct = sample_at(params.gate_length, 0, 90.0).clamp(10.0, 100.0) / 100.0;
         let loop_enabled = sample_at(params.loop_enabled, 0, 1.0) > 0.5;
 
@@ -10,18 +10,20 @@
 //!
 //! ## Clock
 //! - [`MasterClock`] - Global transport/clock generator
+//! - [`ClockDivider`] - Clock divider/multiplier
 //!
 //! ## Note Sequencers
 //! - [`Arpeggiator`] - Arpeggiator with multiple modes and patterns
 //! - [`StepSequencer`] - 16-step CV/gate sequencer with slide
-//! - [`MidiFileSequencer`] - MIDI file playback with 8 tracks
+//! - [`MidiFileSequencer`] - MIDI file playback with 8 tracks, fed by
+//!   [`parse_smf`] from `.mid` files
 //!
 //! ## Rhythm Sequencers
 //! - [`DrumSequencer`] - 8-track, 16-step drum pattern sequencer
 //! - [`EuclideanSequencer`] - Euclidean rhythm generator
 //!
 //! ## Special
-//! - [`Mario`] - Mario song player (host-controlled CV/gate holder)
+//! - [`CvBank`] - Host-controlled CV/gate bank (also drives the Mario song player)
 //!
 //! # Typical Usage
 //!
@@ -58,12 +60,14 @@
 //! ```
 
 pub mod clock;
+pub mod clock_div;
 pub mod arpeggiator;
 pub mod step_sequencer;
 pub mod drum_sequencer;
 pub mod euclidean;
-pub mod mario;
+pub mod cv_bank;
 pub mod midi_file_sequencer;
+pub mod smf;
 pub mod turing;
 pub mod sid_player;
 pub mod ay_player;
@@ -139,6 +143,7 @@ pub fn rate_to_beats(rate_index: usize) -> f64 {
 // ============================================================================
 
 pub use clock::{MasterClock, MasterClockInputs, MasterClockParams, MasterClockOutputs};
+pub use clock_div::{ClockDivider, ClockDividerInputs, ClockDividerParams, CLOCK_DIV_LABELS, CLOCK_DIV_RATIOS};
 pub use arpeggiator::{
     Arpeggiator, ArpeggiatorInputs, ArpeggiatorParams, ArpeggiatorOutputs,
     ArpMode,
@@ -155,11 +160,12 @@ pub use euclidean::{
     EuclideanSequencer, EuclideanInputs, EuclideanParams,
     EUCLIDEAN_MAX_STEPS,
 };
-pub use mario::{Mario, MarioOutputs, MARIO_CHANNELS};
+pub use cv_bank::{CvBank, MAX_BANK_CHANNELS, MARIO_CHANNELS};
 pub use midi_file_sequencer::{
     MidiFileSequencer, MidiFileSequencerInputs, MidiFileSequencerParams, MidiFileSequencerOutputs,
     MidiNote, MidiTrack, MIDI_TRACKS, MAX_NOTES_PER_TRACK,
 };
+pub use smf::{parse_smf, SmfFile, SmfTrack};
 pub use turing::{TuringMachine, TuringInputs, TuringParams};
 pub use sid_player::{SidPlayer, SidPlayerParams, SidPlayerInputs, SidPlayerOutputs, SidHeader};
 pub use ay_player::{AyPlayer, AyPlayerParams, AyPlayerInputs, AyPlayerOutputs, YmHeader};
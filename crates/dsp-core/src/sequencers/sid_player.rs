@@ -3,7 +3,7 @@
 //! Plays .sid files from Commodore 64 using a 6502 CPU emulator and reSID.
 //! This is a full SID player that executes the original 6502 code.
 
-use crate::common::{sample_at, Sample};
+use crate::common::{frequency_to_cv, sample_at, Sample};
 use mos6502::cpu::CPU;
 use mos6502::memory::Bus;
 use mos6502::instruction::Nmos6502;
@@ -708,7 +708,7 @@ fn sid_freq_to_cv(freq_reg: u16, clock_freq: f64) -> Sample {
         return -5.0;
     }
     let freq_hz = (freq_reg as f64) * clock_freq / 16777216.0;
-    (freq_hz / 261.63).log2() as f32
+    frequency_to_cv(freq_hz as f32)
 }
 
 /// Convert SID waveform bits (bits 4-7 of control register) to CV.
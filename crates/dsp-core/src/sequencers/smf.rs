@@ -0,0 +1,311 @@
+//! Standard MIDI File reader.
+//!
+//! Turns a `.mid` file (format 0 or 1) into note lists for the
+//! [`MidiFileSequencer`](super::MidiFileSequencer): note on/off pairs become
+//! [`MidiNote`]s with a duration, tempo events make up the tempo map and
+//! track names are kept for display. Everything else (controllers, sysex,
+//! other meta events) is skipped.
+
+use super::midi_file_sequencer::{MidiNote, MAX_NOTES_PER_TRACK, MIDI_TRACKS};
+
+/// Tempo of a file without tempo events, as the SMF spec defines.
+const DEFAULT_TEMPO: f32 = 120.0;
+
+/// A parsed MIDI file, trimmed to what the sequencer can hold.
+#[derive(Clone, Debug)]
+pub struct SmfFile {
+    /// Pulses per quarter note
+    pub ticks_per_beat: u32,
+    /// End of the last note, in ticks
+    pub total_ticks: u32,
+    /// Tempo of the first tempo event in BPM (120 without one)
+    pub tempo: f32,
+    /// Every tempo change as `(tick, bpm)`, sorted by tick. The sequencer
+    /// plays at a single tempo; the map is kept for display.
+    pub tempo_map: Vec<(u32, f32)>,
+    /// Tracks that contain notes, at most [`MIDI_TRACKS`]
+    pub tracks: Vec<SmfTrack>,
+    /// What was dropped to fit the sequencer, for the caller to report
+    pub warnings: Vec<String>,
+}
+
+/// One track of a [`SmfFile`].
+#[derive(Clone, Debug)]
+pub struct SmfTrack {
+    /// Track name meta event, or "Track N"
+    pub name: String,
+    /// Channel of the first note (0-15)
+    pub channel: u8,
+    /// Notes sorted by tick, at most [`MAX_NOTES_PER_TRACK`]
+    pub notes: Vec<MidiNote>,
+}
+
+/// Byte cursor over a chunk.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn byte(&mut self) -> Result<u8, &'static str> {
+        let byte = *self.data.get(self.pos).ok_or("truncated MIDI file")?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], &'static str> {
+        let end = self.pos.checked_add(len).filter(|&end| end <= self.data.len());
+        let bytes = &self.data[self.pos..end.ok_or("truncated MIDI file")?];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn be_u32(&mut self) -> Result<u32, &'static str> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Variable-length quantity (at most 4 bytes).
+    fn vlq(&mut self) -> Result<u32, &'static str> {
+        let mut value = 0u32;
+        for _ in 0..4 {
+            let byte = self.byte()?;
+            value = (value << 7) | (byte & 0x7F) as u32;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("invalid variable-length value in MIDI track")
+    }
+}
+
+/// Notes, name and tempo events read from one `MTrk` chunk.
+#[derive(Default)]
+struct RawTrack {
+    name: Option<String>,
+    channel: Option<u8>,
+    notes: Vec<MidiNote>,
+    tempos: Vec<(u32, f32)>,
+}
+
+fn read_track(data: &[u8]) -> Result<RawTrack, &'static str> {
+    let mut reader = Reader { data, pos: 0 };
+    let mut track = RawTrack::default();
+    // Notes sounding per (channel, key) as (start tick, velocity); a
+    // repeated note on stacks up and note offs close the oldest first.
+    let mut open: Vec<(u8, u8, u32, u8)> = Vec::new();
+    let mut running_status = None;
+    let mut tick = 0u32;
+
+    while reader.pos < data.len() {
+        tick = tick.saturating_add(reader.vlq()?);
+        let mut status = reader.byte()?;
+        let first_data = if status & 0x80 == 0 {
+            let data_byte = status;
+            status = running_status.ok_or("MIDI data byte without a status")?;
+            Some(data_byte)
+        } else {
+            None
+        };
+
+        match status {
+            0xFF => {
+                running_status = None;
+                let kind = reader.byte()?;
+                let len = reader.vlq()? as usize;
+                let payload = reader.bytes(len)?;
+                match kind {
+                    0x03 if track.name.is_none() => {
+                        track.name = Some(String::from_utf8_lossy(payload).trim().to_string());
+                    }
+                    0x51 if len == 3 => {
+                        let micros = u32::from_be_bytes([0, payload[0], payload[1], payload[2]]);
+                        if micros > 0 {
+                            track.tempos.push((tick, 60_000_000.0 / micros as f32));
+                        }
+                    }
+                    0x2F => break,
+                    _ => {}
+                }
+            }
+            0xF0 | 0xF7 => {
+                running_status = None;
+                let len = reader.vlq()? as usize;
+                reader.bytes(len)?;
+            }
+            0x80..=0xEF => {
+                running_status = Some(status);
+                let first = match first_data {
+                    Some(byte) => byte,
+                    None => reader.byte()?,
+                };
+                let kind = status & 0xF0;
+                let second = if kind == 0xC0 || kind == 0xD0 { 0 } else { reader.byte()? };
+                let channel = status & 0x0F;
+                let key = first & 0x7F;
+                match kind {
+                    0x90 if second > 0 => {
+                        track.channel.get_or_insert(channel);
+                        open.push((channel, key, tick, second & 0x7F));
+                    }
+                    0x80 | 0x90 => {
+                        if let Some(index) = open.iter().position(|&(c, k, _, _)| c == channel && k == key) {
+                            let (_, _, start, velocity) = open.remove(index);
+                            track.notes.push(MidiNote { tick: start, note: key, velocity, duration: tick - start, voice: 0 });
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            _ => return Err("invalid MIDI status byte"),
+        }
+    }
+
+    // Notes still held at the end of the track stop there.
+    for (_, key, start, velocity) in open {
+        track.notes.push(MidiNote { tick: start, note: key, velocity, duration: tick - start, voice: 0 });
+    }
+    Ok(track)
+}
+
+/// Parse a Standard MIDI File.
+///
+/// Tracks without notes are skipped. Past [`MIDI_TRACKS`] tracks and
+/// [`MAX_NOTES_PER_TRACK`] notes per track the rest is dropped, with a
+/// line in [`SmfFile::warnings`] for each cut.
+pub fn parse_smf(data: &[u8]) -> Result<SmfFile, &'static str> {
+    let mut reader = Reader { data, pos: 0 };
+    if reader.bytes(4).ok() != Some(b"MThd".as_slice()) {
+        return Err("not a MIDI file");
+    }
+    let header_len = reader.be_u32()? as usize;
+    if header_len < 6 {
+        return Err("invalid MIDI header");
+    }
+    let header = reader.bytes(header_len)?;
+    let format = u16::from_be_bytes([header[0], header[1]]);
+    let division = u16::from_be_bytes([header[4], header[5]]);
+    if format > 1 {
+        return Err("only format 0 and 1 MIDI files are supported");
+    }
+    if division & 0x8000 != 0 || division == 0 {
+        return Err("SMPTE time division is not supported");
+    }
+
+    let mut raw_tracks = Vec::new();
+    while reader.pos + 8 <= data.len() {
+        let id = reader.bytes(4)?;
+        let len = reader.be_u32()? as usize;
+        // A last chunk cut short still yields the notes it holds.
+        let len = len.min(data.len() - reader.pos);
+        let chunk = reader.bytes(len)?;
+        if id == b"MTrk" {
+            raw_tracks.push(read_track(chunk)?);
+        }
+    }
+
+    let mut tempo_map: Vec<(u32, f32)> = raw_tracks.iter().flat_map(|track| track.tempos.iter().copied()).collect();
+    tempo_map.sort_by_key(|&(tick, _)| tick);
+
+    let mut warnings = Vec::new();
+    let with_notes: Vec<RawTrack> = raw_tracks.into_iter().filter(|track| !track.notes.is_empty()).collect();
+    if with_notes.len() > MIDI_TRACKS {
+        warnings.push(format!(
+            "{} tracks contain notes; only the first {MIDI_TRACKS} are loaded",
+            with_notes.len()
+        ));
+    }
+
+    let mut tracks = Vec::new();
+    for raw in with_notes.into_iter().take(MIDI_TRACKS) {
+        let name = raw.name.filter(|name| !name.is_empty()).unwrap_or_else(|| format!("Track {}", tracks.len() + 1));
+        let mut notes = raw.notes;
+        notes.sort_by(|a, b| a.tick.cmp(&b.tick).then_with(|| a.note.cmp(&b.note)));
+        if notes.len() > MAX_NOTES_PER_TRACK {
+            warnings.push(format!(
+                "{name}: {} notes; only the first {MAX_NOTES_PER_TRACK} are loaded",
+                notes.len()
+            ));
+            notes.truncate(MAX_NOTES_PER_TRACK);
+        }
+        tracks.push(SmfTrack { name, channel: raw.channel.unwrap_or(0), notes });
+    }
+
+    let total_ticks = tracks
+        .iter()
+        .flat_map(|track| track.notes.iter())
+        .map(|note| note.tick.saturating_add(note.duration))
+        .max()
+        .unwrap_or(0);
+
+    Ok(SmfFile {
+        ticks_per_beat: division as u32,
+        total_ticks,
+        tempo: tempo_map.first().map_or(DEFAULT_TEMPO, |&(_, bpm)| bpm),
+        tempo_map,
+        tracks,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn smf(format: u16, tracks: &[Vec<u8>]) -> Vec<u8> {
+        let mut data = b"MThd".to_vec();
+        data.extend_from_slice(&6u32.to_be_bytes());
+        data.extend_from_slice(&format.to_be_bytes());
+        data.extend_from_slice(&(tracks.len() as u16).to_be_bytes());
+        data.extend_from_slice(&96u16.to_be_bytes());
+        for track in tracks {
+            data.extend_from_slice(b"MTrk");
+            data.extend_from_slice(&(track.len() as u32).to_be_bytes());
+            data.extend_from_slice(track);
+        }
+        data
+    }
+
+    fn notes(track: &SmfTrack) -> Vec<(u32, u8, u8, u32)> {
+        track.notes.iter().map(|n| (n.tick, n.note, n.velocity, n.duration)).collect()
+    }
+
+    #[test]
+    fn test_running_status_and_zero_velocity_note_offs() {
+        // Tempo 100 BPM, then C4 and E4 overlapping, written with running
+        // status and note-on velocity 0 as note off. The delta of 200 is a
+        // two-byte variable-length value.
+        let track = vec![
+            0x00, 0xFF, 0x51, 0x03, 0x09, 0x27, 0xC0, //
+            0x00, 0x91, 60, 100, //
+            0x30, 64, 80, //
+            0x30, 60, 0, //
+            0x81, 0x48, 0x81, 64, 0, //
+            0x00, 0xFF, 0x2F, 0x00,
+        ];
+        let file = parse_smf(&smf(0, &[track])).unwrap();
+        assert_eq!(file.ticks_per_beat, 96);
+        assert_eq!(file.tempo_map, [(0, 100.0)]);
+        assert_eq!(file.tracks.len(), 1);
+        assert_eq!(file.tracks[0].channel, 1);
+        assert_eq!(notes(&file.tracks[0]), [(0, 60, 100, 96), (48, 64, 80, 248)]);
+        assert_eq!(file.total_ticks, 296);
+        assert!(file.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_notes_past_the_track_limit_are_dropped_with_a_warning() {
+        let mut track = Vec::new();
+        for _ in 0..MAX_NOTES_PER_TRACK + 5 {
+            track.extend_from_slice(&[0x00, 0x90, 60, 100, 0x01, 0x80, 60, 0]);
+        }
+        let file = parse_smf(&smf(1, &[track])).unwrap();
+        assert_eq!(file.tracks[0].notes.len(), MAX_NOTES_PER_TRACK);
+        assert_eq!(file.tempo, DEFAULT_TEMPO);
+        assert_eq!(file.warnings.len(), 1);
+        assert!(file.warnings[0].contains("8197 notes"), "{:?}", file.warnings);
+
+        assert!(parse_smf(b"RIFF").is_err());
+        assert!(parse_smf(&smf(2, &[])).is_err());
+    }
+}
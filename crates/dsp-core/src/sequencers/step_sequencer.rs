@@ -76,6 +76,7 @@ impl Xorshift32 {
 /// let mut gate_out = [0.0f32; 128];
 /// let mut vel_out = [0.0f32; 128];
 /// let mut step_out = [0.0f32; 128];
+/// let mut slide_out = [0.0f32; 128];
 ///
 /// seq.process_block(
 ///     StepSequencerOutputs {
@@ -83,6 +84,7 @@ impl Xorshift32 {
 ///         gate_out: &mut gate_out,
 ///         velocity_out: &mut vel_out,
 ///         step_out: &mut step_out,
+///         slide_out: &mut slide_out,
 ///     },
 ///     StepSequencerInputs { clock: None, reset: None, cv_offset: None },
 ///     StepSequencerParams {
@@ -117,6 +119,8 @@ pub struct StepSequencer {
     slide_target_cv: f32,
     slide_samples: usize,
     slide_total_samples: usize,
+    /// The current step was reached by sliding from the previous one.
+    slid_in: bool,
 
     // Swing state
     swing_pending: bool,
@@ -178,6 +182,8 @@ pub struct StepSequencerOutputs<'a> {
     pub velocity_out: &'a mut [Sample],
     /// Current step position (0-15)
     pub step_out: &'a mut [Sample],
+    /// High while the current step is tied to the next one or was slid into
+    pub slide_out: &'a mut [Sample],
 }
 
 impl StepSequencer {
@@ -199,6 +205,7 @@ impl StepSequencer {
             slide_target_cv: 0.0,
             slide_samples: 0,
             slide_total_samples: 0,
+            slid_in: false,
             swing_pending: false,
             swing_delay_remaining: 0,
             swing_cv: 0.0,
@@ -218,6 +225,26 @@ impl StepSequencer {
         self.sample_rate = sample_rate.max(1.0);
     }
 
+    /// Rewind to the first step with the gate low; the steps are kept.
+    pub fn reset(&mut self) {
+        self.current_step = 0;
+        self.phase = 0.0;
+        self.ping_pong_forward = true;
+        self.gate_on = false;
+        self.gate_samples = 0;
+        self.slide_active = false;
+        self.slide_samples = 0;
+        self.slid_in = false;
+        self.swing_pending = false;
+        self.swing_delay_remaining = 0;
+        self.current_cv = 0.0;
+        self.current_gate = 0.0;
+        self.current_velocity = 1.0;
+        self.prev_clock = 0.0;
+        self.prev_reset = 0.0;
+        self.rng = Xorshift32::new(42);
+    }
+
     /// Get current step position (0-15).
     pub fn current_step(&self) -> usize {
         self.current_step
@@ -384,6 +411,7 @@ impl StepSequencer {
                 outputs.gate_out[i] = 0.0;
                 outputs.velocity_out[i] = 0.0;
                 outputs.step_out[i] = 0.0;
+                outputs.slide_out[i] = 0.0;
                 continue;
             }
 
@@ -399,6 +427,7 @@ impl StepSequencer {
                 self.phase = 0.0;
                 self.ping_pong_forward = true;
                 self.gate_on = false;
+                self.slid_in = false;
                 self.swing_pending = false;
             }
 
@@ -483,6 +512,7 @@ impl StepSequencer {
                 } else {
                     self.slide_active = false;
                 }
+                self.slid_in = self.slide_active;
 
                 // Check for swing (apply to odd steps)
                 let is_odd_step = next_step % 2 == 1;
@@ -501,7 +531,7 @@ impl StepSequencer {
                         self.swing_delay_remaining = swing_delay;
                         self.swing_cv = step_cv;
                         self.swing_velocity = step.velocity;
-                        self.swing_gate_length = gate_length_samples;
+                        self.swing_gate_length = Self::tied_length(step, gate_length_samples);
                     } else {
                         // Immediate step
                         if !self.slide_active {
@@ -510,7 +540,7 @@ impl StepSequencer {
                         self.current_velocity = step.velocity;
                         self.gate_on = true;
                         self.gate_samples = 0;
-                        self.gate_length_samples = gate_length_samples;
+                        self.gate_length_samples = Self::tied_length(step, gate_length_samples);
                     }
                 } else {
                     // Step is off
@@ -549,6 +579,13 @@ impl StepSequencer {
             outputs.gate_out[i] = self.current_gate;
             outputs.velocity_out[i] = self.current_velocity;
             outputs.step_out[i] = self.current_step as f32;
+            let tied = self.steps[self.current_step].slide && self.gate_on;
+            outputs.slide_out[i] = if tied || self.slid_in { 1.0 } else { 0.0 };
         }
     }
+
+    /// A slide step holds its gate until the next step, so the note is tied.
+    fn tied_length(step: &SeqStep, gate_length_samples: usize) -> usize {
+        if step.slide { usize::MAX } else { gate_length_samples }
+    }
 }
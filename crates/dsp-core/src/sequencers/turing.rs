@@ -10,7 +10,7 @@ use crate::common::{sample_at, get_scale_notes};
 pub struct TuringParams<'a> {
     /// Probability of flipping the feedback bit (0 = locked, 0.5 = evolving, 1 = random)
     pub probability: &'a [f32],
-    /// Loop length in bits (2-16)
+    /// Loop length in bits (2-32)
     pub length: &'a [f32],
     /// Output voltage range in octaves (1-5)
     pub range: &'a [f32],
@@ -18,6 +18,8 @@ pub struct TuringParams<'a> {
     pub scale: &'a [f32],
     /// Root note for quantization (0-11)
     pub root: &'a [f32],
+    /// Write switch: while high, every step shifts in a fresh random bit
+    pub write: &'a [f32],
 }
 
 impl<'a> Default for TuringParams<'a> {
@@ -28,6 +30,7 @@ impl<'a> Default for TuringParams<'a> {
             range: &[2.0],
             scale: &[0.0],
             root: &[0.0],
+            write: &[0.0],
         }
     }
 }
@@ -36,13 +39,15 @@ impl<'a> Default for TuringParams<'a> {
 pub struct TuringInputs<'a> {
     pub clock: Option<&'a [f32]>,
     pub reset: Option<&'a [f32]>,
+    /// Added to the probability param
+    pub probability_cv: Option<&'a [f32]>,
 }
 
 /// Turing Machine shift register sequencer
 #[derive(Debug, Clone)]
 pub struct TuringMachine {
-    /// 16-bit shift register
-    register: u16,
+    /// 32-bit shift register (the loop uses the lowest `length` bits)
+    register: u32,
     /// Sample rate
     sample_rate: f32,
     /// Previous clock value for edge detection
@@ -59,23 +64,55 @@ pub struct TuringMachine {
     current_cv: f32,
     /// Gate state
     gate_state: f32,
+    /// Seed the random bits restart from on `reset`
+    start_seed: u32,
 }
 
+/// Register contents after creation and reset.
+const INITIAL_PATTERN: u32 = 0b0101_1010_0110_1001_1010_0110_1001_0101;
+
 impl TuringMachine {
+    /// RNG seed used by [`new`](Self::new).
+    pub const DEFAULT_SEED: u32 = 12345;
+
     pub fn new(sample_rate: f32) -> Self {
+        Self::new_with_seed(sample_rate, Self::DEFAULT_SEED)
+    }
+
+    /// Create a Turing Machine whose random bits come from `seed`, so two
+    /// instances with the same seed and clock produce the same sequence.
+    pub fn new_with_seed(sample_rate: f32, seed: u32) -> Self {
         Self {
-            register: 0b1010_0110_1001_0101, // Initial pattern
+            register: INITIAL_PATTERN,
             sample_rate,
             last_clock: 0.0,
             last_reset: 0.0,
             step: 0,
-            rng_state: 12345,
+            rng_state: seed,
+            start_seed: seed,
             trigger_timer: 0,
             current_cv: 0.0,
             gate_state: 0.0,
         }
     }
 
+    /// Update the sample rate.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+    }
+
+    /// Restart the random bit stream from `seed` (the register is kept).
+    pub fn set_seed(&mut self, seed: u32) {
+        self.rng_state = seed;
+        self.start_seed = seed;
+    }
+
+    /// Reload the initial register and restart the random bits from the
+    /// last seed set.
+    pub fn reset(&mut self) {
+        *self = Self::new_with_seed(self.sample_rate, self.start_seed);
+    }
+
     /// Simple LCG random number generator
     fn next_random(&mut self) -> f32 {
         self.rng_state = self.rng_state.wrapping_mul(1664525).wrapping_add(1013904223);
@@ -92,13 +129,19 @@ impl TuringMachine {
     ) {
         let clock_in = inputs.clock.unwrap_or(&[]);
         let reset_in = inputs.reset.unwrap_or(&[]);
+        let prob_cv_in = inputs.probability_cv.unwrap_or(&[]);
         let pulse_samples = (0.005 * self.sample_rate) as i32; // 5ms pulse
 
         for i in 0..out_cv.len() {
             let clock = sample_at(clock_in, i, 0.0);
             let reset = sample_at(reset_in, i, 0.0);
-            let prob = sample_at(params.probability, i, 0.5).clamp(0.0, 1.0);
-            let length = sample_at(params.length, i, 8.0).clamp(2.0, 16.0) as usize;
+            let write = sample_at(params.write, i, 0.0) > 0.5;
+            let prob = if write {
+                1.0
+            } else {
+                (sample_at(params.probability, i, 0.5) + sample_at(prob_cv_in, i, 0.0)).clamp(0.0, 1.0)
+            };
+            let length = sample_at(params.length, i, 8.0).clamp(2.0, 32.0) as usize;
             let range = sample_at(params.range, i, 2.0).clamp(1.0, 5.0);
             let scale_idx = sample_at(params.scale, i, 0.0) as i32;
             let root = sample_at(params.root, i, 0.0) as i32;
@@ -106,7 +149,7 @@ impl TuringMachine {
             // Reset detection (rising edge)
             if reset > 0.5 && self.last_reset <= 0.5 {
                 self.step = 0;
-                self.register = 0b1010_0110_1001_0101; // Reset pattern
+                self.register = INITIAL_PATTERN;
             }
             self.last_reset = reset;
 
@@ -125,15 +168,15 @@ impl TuringMachine {
                 };
 
                 // Shift register left and insert new bit at position 0
-                self.register = ((self.register << 1) | (new_bit as u16)) & 0xFFFF;
+                self.register = (self.register << 1) | new_bit;
 
                 // Advance step
                 self.step = (self.step + 1) % length;
 
                 // Calculate CV from register
                 // Use lower 'length' bits, normalize to 0-1
-                let mask = (1u16 << length) - 1;
-                let value = (self.register & mask) as f32 / mask as f32;
+                let mask = u32::MAX >> (32 - length);
+                let value = ((self.register & mask) as f64 / mask as f64) as f32;
 
                 // Scale to voltage range (in octaves, centered around 0)
                 let cv_raw = (value - 0.5) * range;
@@ -203,7 +246,38 @@ impl TuringMachine {
     }
 
     /// Get register value (for UI visualization)
-    pub fn register_value(&self) -> u16 {
+    pub fn register_value(&self) -> u32 {
         self.register
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locked_32_bit_loop_repeats() {
+        let sample_rate = 48000.0;
+        let period = 100;
+        let steps = 96;
+        let clock: Vec<f32> = (0..period * steps).map(|i| if i % period < 10 { 1.0 } else { 0.0 }).collect();
+        let mut cv = vec![0.0; clock.len()];
+        let (mut gate, mut pulse) = (cv.clone(), cv.clone());
+        let mut turing = TuringMachine::new(sample_rate);
+        turing.process_block(
+            &mut cv,
+            &mut gate,
+            &mut pulse,
+            TuringInputs { clock: Some(&clock), reset: None, probability_cv: None },
+            TuringParams { probability: &[0.0], length: &[32.0], ..TuringParams::default() },
+        );
+
+        let values: Vec<f32> = (0..steps).map(|step| cv[step * period + 1]).collect();
+        assert_eq!(values[..64], values[32..]);
+        // The high bits take part: more than 16 distinct steps in the loop.
+        let mut distinct = values[..32].to_vec();
+        distinct.sort_by(f32::total_cmp);
+        distinct.dedup();
+        assert!(distinct.len() > 16, "{} distinct values", distinct.len());
+    }
+}
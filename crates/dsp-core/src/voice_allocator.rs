@@ -0,0 +1,219 @@
+//! Polyphonic voice allocation.
+//!
+//! Maps held notes to voice indices with a configurable stealing policy.
+//! Host-agnostic: the plugin, the Tauri MIDI thread and the WASM engine all
+//! allocate through the same code (via `dsp_graph::NoteRouter`).
+
+use std::cmp::Ordering;
+
+/// Which voice to take over when every voice is busy.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StealPolicy {
+    /// Cycle through the voices, one steal after another.
+    #[default]
+    RoundRobin,
+    /// Take the voice whose note started first.
+    OldestFirst,
+    /// Take the quietest voice (oldest on ties).
+    LowestVelocity,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct HeldNote<K> {
+    key: K,
+    velocity: f32,
+    /// Allocation counter at note-on, for `OldestFirst`.
+    age: u64,
+}
+
+/// Voice allocator.
+///
+/// A repeated note-on for a held key returns the voice it already plays on;
+/// otherwise the lowest free voice is used, and only when none is free does
+/// the [`StealPolicy`] pick a voice to steal.
+///
+/// Notes are keyed by `K`: a plain MIDI note (`u8`, the default) or any
+/// richer key such as `(channel, note)`.
+///
+/// # Example
+///
+/// ```ignore
+/// use dsp_core::{StealPolicy, VoiceAllocator};
+///
+/// let mut voices = VoiceAllocator::new(4, StealPolicy::OldestFirst);
+/// let (voice, stolen) = voices.allocate(60, 0.8);
+/// assert_eq!((voice, stolen), (0, None));
+/// assert_eq!(voices.release(60), Some(0));
+/// ```
+#[derive(Clone, Debug)]
+pub struct VoiceAllocator<K = u8> {
+    /// Held note per voice. May be longer than `max_voices` after a shrink,
+    /// so notes on voices above the limit can still be released.
+    voices: Vec<Option<HeldNote<K>>>,
+    max_voices: usize,
+    policy: StealPolicy,
+    next_steal: usize,
+    clock: u64,
+}
+
+impl<K: Copy + PartialEq> VoiceAllocator<K> {
+    /// Create an allocator for `max_voices` voices (at least 1).
+    pub fn new(max_voices: usize, policy: StealPolicy) -> Self {
+        let max_voices = max_voices.max(1);
+        Self {
+            voices: vec![None; max_voices],
+            max_voices,
+            policy,
+            next_steal: 0,
+            clock: 0,
+        }
+    }
+
+    pub fn max_voices(&self) -> usize {
+        self.max_voices
+    }
+
+    /// Change the voice count. Held notes are kept; voices above a smaller
+    /// limit are no longer allocated but still release normally.
+    pub fn set_max_voices(&mut self, max_voices: usize) {
+        self.max_voices = max_voices.max(1);
+        if self.voices.len() < self.max_voices {
+            self.voices.resize(self.max_voices, None);
+        }
+        self.next_steal %= self.max_voices;
+    }
+
+    pub fn policy(&self) -> StealPolicy {
+        self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: StealPolicy) {
+        self.policy = policy;
+    }
+
+    /// Pick a voice for `key`. Returns the voice index and, when a voice
+    /// was stolen, the key it was playing.
+    pub fn allocate(&mut self, key: K, velocity: f32) -> (usize, Option<K>) {
+        self.clock += 1;
+        let held = HeldNote { key, velocity, age: self.clock };
+        if let Some(voice) = self.find(key) {
+            self.voices[voice] = Some(held);
+            return (voice, None);
+        }
+        let active = &mut self.voices[..self.max_voices];
+        if let Some(voice) = active.iter().position(Option::is_none) {
+            active[voice] = Some(held);
+            return (voice, None);
+        }
+        let voice = match self.policy {
+            StealPolicy::RoundRobin => {
+                let voice = self.next_steal % self.max_voices;
+                self.next_steal = (voice + 1) % self.max_voices;
+                voice
+            }
+            StealPolicy::OldestFirst => Self::steal_candidate(active, |a, b| a.age.cmp(&b.age)),
+            StealPolicy::LowestVelocity => Self::steal_candidate(active, |a, b| {
+                a.velocity.total_cmp(&b.velocity).then(a.age.cmp(&b.age))
+            }),
+        };
+        let stolen = active[voice].replace(held).map(|note| note.key);
+        (voice, stolen)
+    }
+
+    /// First voice in `order`; only called when every voice is held.
+    fn steal_candidate(
+        active: &[Option<HeldNote<K>>],
+        order: impl Fn(&HeldNote<K>, &HeldNote<K>) -> Ordering,
+    ) -> usize {
+        active
+            .iter()
+            .enumerate()
+            .filter_map(|(voice, note)| note.as_ref().map(|note| (voice, note)))
+            .min_by(|a, b| order(a.1, b.1))
+            .map_or(0, |(voice, _)| voice)
+    }
+
+    /// Voice currently playing `key`.
+    pub fn find(&self, key: K) -> Option<usize> {
+        self.voices
+            .iter()
+            .position(|note| note.is_some_and(|note| note.key == key))
+    }
+
+    /// Free the voice playing `key` and return it.
+    pub fn release(&mut self, key: K) -> Option<usize> {
+        let voice = self.find(key)?;
+        self.voices[voice] = None;
+        Some(voice)
+    }
+
+    /// Every held `(voice, key)`, in voice order.
+    pub fn active_voices(&self) -> impl Iterator<Item = (usize, K)> + '_ {
+        self.voices
+            .iter()
+            .enumerate()
+            .filter_map(|(voice, note)| note.map(|note| (voice, note.key)))
+    }
+
+    /// Forget every held note.
+    pub fn reset(&mut self) {
+        self.voices.iter_mut().for_each(|note| *note = None);
+        self.next_steal = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(voices: &mut VoiceAllocator, notes: &[(u8, f32)]) {
+        for &(note, velocity) in notes {
+            voices.allocate(note, velocity);
+        }
+    }
+
+    #[test]
+    fn test_free_voices_first_then_steal_by_policy() {
+        let held = [(60, 0.9), (62, 0.2), (64, 0.5)];
+
+        let mut round_robin = VoiceAllocator::new(3, StealPolicy::RoundRobin);
+        fill(&mut round_robin, &held);
+        assert_eq!(round_robin.allocate(65, 1.0), (0, Some(60)));
+        assert_eq!(round_robin.allocate(67, 1.0), (1, Some(62)));
+
+        let mut oldest = VoiceAllocator::new(3, StealPolicy::OldestFirst);
+        fill(&mut oldest, &held);
+        oldest.release(60);
+        assert_eq!(oldest.allocate(65, 1.0), (0, None));
+        assert_eq!(oldest.allocate(67, 1.0), (1, Some(62)));
+        assert_eq!(oldest.allocate(69, 1.0), (2, Some(64)));
+        assert_eq!(oldest.allocate(71, 1.0), (0, Some(65)));
+
+        let mut quietest = VoiceAllocator::new(3, StealPolicy::LowestVelocity);
+        fill(&mut quietest, &held);
+        assert_eq!(quietest.allocate(65, 0.7), (1, Some(62)));
+        assert_eq!(quietest.allocate(67, 0.7), (2, Some(64)));
+    }
+
+    #[test]
+    fn test_retrigger_and_release() {
+        let mut voices = VoiceAllocator::new(4, StealPolicy::RoundRobin);
+        fill(&mut voices, &[(60, 1.0), (64, 1.0)]);
+        assert_eq!(voices.allocate(60, 0.5), (0, None));
+        assert_eq!(voices.active_voices().collect::<Vec<_>>(), vec![(0, 60), (1, 64)]);
+
+        assert_eq!(voices.release(64), Some(1));
+        assert_eq!(voices.release(64), None);
+        voices.reset();
+        assert_eq!(voices.active_voices().count(), 0);
+    }
+
+    #[test]
+    fn test_shrinking_keeps_high_voices_releasable() {
+        let mut voices = VoiceAllocator::new(4, StealPolicy::RoundRobin);
+        fill(&mut voices, &[(60, 1.0), (62, 1.0), (64, 1.0), (65, 1.0)]);
+        voices.set_max_voices(2);
+        assert_eq!(voices.allocate(67, 1.0), (0, Some(60)));
+        assert_eq!(voices.release(65), Some(3));
+    }
+}
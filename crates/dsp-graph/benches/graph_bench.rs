@@ -0,0 +1,37 @@
+//! Criterion benchmark for `GraphEngine::render`.
+//!
+//! Run with `cargo bench -p dsp-graph`. HTML reports are written to
+//! `target/criterion/report/index.html`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use dsp_graph::GraphEngine;
+
+const SAMPLE_RATE: f32 = 48_000.0;
+
+/// Default 8-voice plugin patch (VCO → VCF → VCA → Chorus → Output).
+const DEFAULT_GRAPH_JSON: &str = include_str!("../../dsp-plugin/src/default_graph.json");
+
+fn bench_graph_render(c: &mut Criterion) {
+    let mut group = c.benchmark_group("graph_render");
+    for &frames in &[128usize, 512] {
+        group.throughput(Throughput::Elements(frames as u64));
+        group.bench_with_input(BenchmarkId::new("default_patch_8_voices", frames), &frames, |b, &frames| {
+            let mut engine = GraphEngine::new(SAMPLE_RATE);
+            engine
+                .set_graph_json(DEFAULT_GRAPH_JSON)
+                .expect("default graph should parse");
+            // Hold a chord across all voices so every module is doing real work.
+            for voice in 0..8 {
+                engine.set_control_voice_cv("ctrl-1", voice, voice as f32 / 12.0);
+                engine.set_control_voice_gate("ctrl-1", voice, 1.0);
+            }
+            b.iter(|| {
+                black_box(engine.render(frames));
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_graph_render);
+criterion_main!(benches);
@@ -0,0 +1,20 @@
+//! Fuzz `GraphEngine::set_graph_json` with arbitrary bytes, then render a block.
+//!
+//! `cargo fuzz run fuzz_set_graph` (from `crates/dsp-graph`). The corpus is seeded
+//! with the plugin's default patch and an empty graph.
+
+#![no_main]
+
+use dsp_graph::GraphEngine;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(json) = std::str::from_utf8(data) else {
+        return;
+    };
+    let mut engine = GraphEngine::new(48000.0);
+    // Invalid graphs must be rejected with an error, never a panic.
+    let _ = engine.set_graph_json(json);
+    let output = engine.render(128);
+    assert!(output.len() >= 256);
+});
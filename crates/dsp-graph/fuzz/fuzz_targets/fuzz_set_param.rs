@@ -0,0 +1,21 @@
+//! Fuzz `set_param` / `set_param_string` with arbitrary ids against a known-good patch.
+//!
+//! `cargo fuzz run fuzz_set_param` (from `crates/dsp-graph`).
+
+#![no_main]
+
+use dsp_graph::GraphEngine;
+use libfuzzer_sys::fuzz_target;
+
+const DEFAULT_GRAPH_JSON: &str = include_str!("../../../dsp-plugin/src/default_graph.json");
+
+fuzz_target!(|input: (String, String, f32, String)| {
+    let (module_id, param, value, text) = input;
+    let mut engine = GraphEngine::new(48000.0);
+    engine
+        .set_graph_json(DEFAULT_GRAPH_JSON)
+        .expect("default graph should load");
+    engine.set_param(&module_id, &param, value);
+    engine.set_param_string(&module_id, &param, &text);
+    engine.render(128);
+});
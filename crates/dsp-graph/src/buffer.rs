@@ -59,6 +59,25 @@ impl Buffer {
     }
 }
 
+/// Mix source buffer into a stereo target with gain, placed at `pan`
+/// (-1 = left, 1 = right) with an equal-power law. The center keeps unity
+/// gain on both sides, like [`mix_buffers`]; a stereo source is balanced.
+/// Non-stereo targets ignore `pan`.
+pub fn mix_buffers_panned(target: &mut Buffer, source: &Buffer, gain: f32, pan: f32) {
+    if target.channel_count() != 2 || !(1..=2).contains(&source.channel_count()) {
+        mix_buffers(target, source, gain);
+        return;
+    }
+    let angle = (pan.clamp(-1.0, 1.0) + 1.0) * std::f32::consts::FRAC_PI_4;
+    let side_gains = [angle.cos(), angle.sin()].map(|side| side * std::f32::consts::SQRT_2 * gain);
+    for (channel, side_gain) in side_gains.into_iter().enumerate() {
+        let src = source.channel(channel.min(source.channel_count() - 1));
+        for (t, s) in target.channel_mut(channel).iter_mut().zip(src) {
+            *t += s * side_gain;
+        }
+    }
+}
+
 /// Mix source buffer into target buffer with gain.
 ///
 /// Handles mono/stereo combinations:
@@ -66,6 +85,12 @@ impl Buffer {
 /// - stereo -> mono: downmix to mono
 /// - mono -> stereo: copy to both channels
 /// - stereo -> stereo: direct mix
+///
+/// No port is wider than stereo, so any other combination is unexpected
+/// (debug builds report the first one). It is handled generically: source
+/// channel `i` lands on target `i % target_channels`, averaged with the
+/// other channels landing there, and narrower sources are repeated across
+/// the target channels.
 pub fn mix_buffers(target: &mut Buffer, source: &Buffer, gain: f32) {
     if target.channel_count() == 0 {
         return;
@@ -106,11 +131,46 @@ pub fn mix_buffers(target: &mut Buffer, source: &Buffer, gain: f32) {
                 tgt_r[i] += src_r[i] * gain;
             }
         }
-        _ => {}
+        (target_channels, source_channels) => {
+            debug_assert!(
+                source_channels > 0
+                    && source.channels.iter().all(|channel| channel.len() == target.channel(0).len()),
+                "mix_buffers: unexpected source layout ({source_channels} -> {target_channels} channels)"
+            );
+            #[cfg(debug_assertions)]
+            {
+                static REPORTED: std::sync::Once = std::sync::Once::new();
+                REPORTED.call_once(|| {
+                    eprintln!("mix_buffers: unexpected {source_channels} -> {target_channels} channel mix");
+                });
+            }
+            if source_channels == 0 {
+                return;
+            }
+            if source_channels > target_channels {
+                for (index, src) in source.channels.iter().enumerate() {
+                    let channel = index % target_channels;
+                    // Source channels folded onto this target channel
+                    let contributors = (source_channels - channel).div_ceil(target_channels);
+                    let weight = gain / contributors as f32;
+                    let tgt = target.channel_mut(channel);
+                    for (t, s) in tgt.iter_mut().zip(src) {
+                        *t += s * weight;
+                    }
+                }
+            } else {
+                for (index, tgt) in target.channels.iter_mut().enumerate() {
+                    let src = source.channel(index % source_channels);
+                    for (t, s) in tgt.iter_mut().zip(src) {
+                        *t += s * gain;
+                    }
+                }
+            }
+        }
     }
 }
 
-/// Downmix a buffer to mono.
+/// Downmix a buffer to mono (average of all channels).
 pub fn downmix_to_mono(source: &Buffer, dest: &mut [Sample]) {
     if dest.is_empty() {
         return;
@@ -126,8 +186,84 @@ pub fn downmix_to_mono(source: &Buffer, dest: &mut [Sample]) {
                 dest[i] = 0.5 * (left[i] + right[i]);
             }
         }
-        _ => {
+        0 => {
             dest.fill(0.0);
         }
+        channels => {
+            let weight = 1.0 / channels as Sample;
+            dest.fill(0.0);
+            for src in &source.channels {
+                for (d, s) in dest.iter_mut().zip(src) {
+                    *d += s * weight;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filled(values: &[Sample], frames: usize) -> Buffer {
+        Buffer {
+            channels: values.iter().map(|&v| vec![v; frames]).collect(),
+        }
+    }
+
+    #[test]
+    fn mix_mono_into_stereo_copies_to_both_sides() {
+        let mut target = Buffer::new(2, 4);
+        let source = filled(&[0.25], 4);
+        mix_buffers(&mut target, &source, 1.0);
+        assert!(target.channel(0).iter().all(|&s| s == 0.25));
+        assert!(target.channel(1).iter().all(|&s| s == 0.25));
+    }
+
+    #[test]
+    fn mix_stereo_into_mono_keeps_level() {
+        let mut target = Buffer::new(1, 4);
+        let source = filled(&[0.2, 0.6], 4);
+        mix_buffers(&mut target, &source, 1.0);
+        // Average of the sides: a centred signal keeps its level.
+        assert!(target.channel(0).iter().all(|&s| (s - 0.4).abs() < 1e-6));
+    }
+
+    #[test]
+    fn downmix_averages_every_channel() {
+        let source = filled(&[1.0, 2.0, 3.0, 6.0], 4);
+        let mut dest = [0.0; 4];
+        downmix_to_mono(&source, &mut dest);
+        assert!(dest.iter().all(|&s| s == 3.0));
+    }
+
+    #[test]
+    fn mix_four_into_stereo_folds_down() {
+        let mut target = Buffer::new(2, 4);
+        let source = filled(&[1.0, 2.0, 3.0, 4.0], 4);
+        mix_buffers(&mut target, &source, 1.0);
+        // L = (1 + 3) / 2, R = (2 + 4) / 2
+        assert!(target.channel(0).iter().all(|&s| s == 2.0));
+        assert!(target.channel(1).iter().all(|&s| s == 3.0));
+    }
+
+    #[test]
+    fn mix_three_into_stereo_averages_per_side() {
+        let mut target = Buffer::new(2, 4);
+        let source = filled(&[1.0, 2.0, 3.0], 4);
+        mix_buffers(&mut target, &source, 1.0);
+        // L = (1 + 3) / 2, R = 2 alone
+        assert!(target.channel(0).iter().all(|&s| s == 2.0));
+        assert!(target.channel(1).iter().all(|&s| s == 2.0));
+    }
+
+    #[test]
+    fn mix_mono_into_four_repeats() {
+        let mut target = Buffer::new(4, 4);
+        let source = filled(&[0.5], 4);
+        mix_buffers(&mut target, &source, 2.0);
+        for channel in 0..4 {
+            assert!(target.channel(channel).iter().all(|&s| s == 1.0));
+        }
     }
 }
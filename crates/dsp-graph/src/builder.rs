@@ -0,0 +1,218 @@
+//! Fluent construction of graph payloads from Rust code.
+//!
+//! [`GraphBuilder`] produces the same JSON that
+//! [`GraphEngine::set_graph_json`](crate::GraphEngine::set_graph_json)
+//! accepts, for tests and preset generators that would otherwise build the
+//! string by hand.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::{ConnectionJson, GraphPayload, ModuleSpecJson, PortRefJson, TapJson, CURRENT_SCHEMA_VERSION};
+
+/// Builder for a graph payload.
+///
+/// ```
+/// use dsp_graph::{GraphBuilder, GraphEngine};
+/// use serde_json::json;
+///
+/// let graph = GraphBuilder::new()
+///   .add_module("osc-1", "oscillator", json!({ "type": "sawtooth" }))
+///   .param("frequency", 220.0)
+///   .add_module("out-1", "output", json!({}))
+///   .connect("osc-1", "out", "out-1", "in", "audio")
+///   .tap("out-1", "in")
+///   .build();
+///
+/// let mut engine = GraphEngine::new(48000.0);
+/// engine.set_graph_json(&graph).unwrap();
+/// ```
+#[derive(Clone, Default)]
+pub struct GraphBuilder {
+  modules: Vec<ModuleSpecJson>,
+  connections: Vec<ConnectionJson>,
+  taps: Vec<TapJson>,
+  seed: Option<u64>,
+}
+
+impl GraphBuilder {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Add a module. `params` is a JSON object (anything else adds no params);
+  /// further values can follow with [`param`](Self::param).
+  pub fn add_module(mut self, id: &str, kind: &str, params: Value) -> Self {
+    let params = match params {
+      Value::Object(map) => map.into_iter().collect(),
+      _ => HashMap::new(),
+    };
+    self.modules.push(ModuleSpecJson {
+      id: id.to_string(),
+      kind: kind.to_string(),
+      params: Some(params),
+      bypass: None,
+    });
+    self
+  }
+
+  /// Set a param on the module added last. Ignored before any module.
+  pub fn param(mut self, key: &str, value: impl Into<Value>) -> Self {
+    if let Some(module) = self.modules.last_mut() {
+      module.params.get_or_insert_with(HashMap::new).insert(key.to_string(), value.into());
+    }
+    self
+  }
+
+  /// Bypass the module added last.
+  pub fn bypass(mut self) -> Self {
+    if let Some(module) = self.modules.last_mut() {
+      module.bypass = Some(true);
+    }
+    self
+  }
+
+  /// Cable `from_module.from_port` to `to_module.to_port`; `kind` is the
+  /// cable kind (`audio`, `cv`, `gate`, `sync`).
+  pub fn connect(mut self, from_module: &str, from_port: &str, to_module: &str, to_port: &str, kind: &str) -> Self {
+    self.connections.push(ConnectionJson {
+      from: port_ref(from_module, from_port),
+      to: port_ref(to_module, to_port),
+      kind: kind.to_string(),
+    });
+    self
+  }
+
+  /// Monitor an input port of a module in the render output.
+  pub fn tap(mut self, module_id: &str, port_id: &str) -> Self {
+    self.taps.push(TapJson {
+      module_id: module_id.to_string(),
+      port_id: port_id.to_string(),
+    });
+    self
+  }
+
+  /// Seed the graph's random sources instead of using the engine's.
+  pub fn seed(mut self, seed: u64) -> Self {
+    self.seed = Some(seed);
+    self
+  }
+
+  /// Serialize to graph JSON at [`CURRENT_SCHEMA_VERSION`].
+  pub fn build(self) -> String {
+    let payload = GraphPayload {
+      schema_version: Some(CURRENT_SCHEMA_VERSION),
+      modules: self.modules,
+      connections: self.connections,
+      taps: if self.taps.is_empty() { None } else { Some(self.taps) },
+      seed: self.seed,
+    };
+    serde_json::to_string(&payload).unwrap_or_default()
+  }
+}
+
+fn port_ref(module_id: &str, port_id: &str) -> PortRefJson {
+  PortRefJson {
+    module_id: module_id.to_string(),
+    port_id: port_id.to_string(),
+    voice: None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::GraphEngine;
+  use dsp_core::Sample;
+  use serde_json::json;
+
+  const DEFAULT_GRAPH_JSON: &str = include_str!("../../dsp-plugin/src/default_graph.json");
+
+  /// The plugin's default patch, rebuilt with the builder.
+  fn default_graph() -> String {
+    GraphBuilder::new()
+      .add_module(
+        "osc-1",
+        "oscillator",
+        json!({ "type": "sawtooth", "pwm": 0.5, "unison": 2, "detune": 7, "fmLin": 0, "fmExp": 0, "subMix": 0, "subOct": 1 }),
+      )
+      .param("frequency", 110)
+      .add_module(
+        "vcf-1",
+        "vcf",
+        json!({
+          "cutoff": 1200, "resonance": 0.2, "drive": 0.1, "envAmount": 0.4, "modAmount": 0, "keyTrack": 0.5,
+          "model": "svf", "mode": "lp", "slope": 12
+        }),
+      )
+      .add_module("gain-1", "gain", json!({ "gain": 0.8 }))
+      .add_module(
+        "chorus-1",
+        "chorus",
+        json!({ "rate": 0.3, "depth": 12, "delay": 18, "mix": 0.4, "spread": 0.7, "feedback": 0.1 }),
+      )
+      .add_module("out-1", "output", json!({ "level": 0.7 }))
+      .add_module("adsr-1", "adsr", json!({ "attack": 0.01, "decay": 0.3, "sustain": 0.7, "release": 0.5 }))
+      .add_module("adsr-2", "adsr", json!({ "attack": 0.01, "decay": 0.5, "sustain": 0.3, "release": 0.4 }))
+      .add_module(
+        "ctrl-1",
+        "control",
+        json!({
+          "cv": 0, "cvMode": "unipolar", "velocity": 1, "midiVelocity": true, "gate": 0, "glide": 0.02,
+          "midiEnabled": false, "midiChannel": 0, "midiRoot": 60, "midiInputId": "", "midiVelSlew": 0.005,
+          "voices": 8, "seqOn": false, "seqTempo": 120, "seqGate": 0.5
+        }),
+      )
+      .connect("ctrl-1", "cv-out", "osc-1", "pitch", "cv")
+      .connect("ctrl-1", "cv-out", "vcf-1", "key", "cv")
+      .connect("ctrl-1", "gate-out", "adsr-1", "gate", "gate")
+      .connect("ctrl-1", "gate-out", "adsr-2", "gate", "gate")
+      .connect("osc-1", "out", "vcf-1", "in", "audio")
+      .connect("adsr-2", "env", "vcf-1", "env", "cv")
+      .connect("vcf-1", "out", "gain-1", "in", "audio")
+      .connect("adsr-1", "env", "gain-1", "cv", "cv")
+      .connect("gain-1", "out", "chorus-1", "in", "audio")
+      .connect("chorus-1", "out", "out-1", "in", "audio")
+      .build()
+  }
+
+  fn render_with_notes(graph: &str) -> Vec<Sample> {
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(graph).unwrap();
+    for (voice, cv) in [(0, 0.0), (1, 0.25)] {
+      engine.set_control_voice_cv("ctrl-1", voice, cv);
+      engine.trigger_control_voice_gate("ctrl-1", voice);
+    }
+    let mut rendered = Vec::new();
+    for _ in 0..8 {
+      rendered.extend_from_slice(engine.render(256));
+    }
+    rendered
+  }
+
+  #[test]
+  fn builder_matches_the_default_graph_json() {
+    let built = render_with_notes(&default_graph());
+    assert!(built.iter().any(|&sample| sample.abs() > 1e-3), "default patch should sound");
+    assert_eq!(built, render_with_notes(DEFAULT_GRAPH_JSON));
+  }
+
+  #[test]
+  fn build_writes_the_set_graph_json_shape() {
+    let graph: Value = serde_json::from_str(
+      &GraphBuilder::new()
+        .add_module("lfo-1", "lfo", Value::Null)
+        .param("rate", 2.5)
+        .bypass()
+        .connect("lfo-1", "cv-out", "lfo-1", "rate", "cv")
+        .tap("lfo-1", "rate")
+        .build(),
+    )
+    .unwrap();
+    assert_eq!(graph["schemaVersion"], json!(CURRENT_SCHEMA_VERSION));
+    assert_eq!(graph["modules"][0], json!({ "id": "lfo-1", "type": "lfo", "params": { "rate": 2.5 }, "bypass": true }));
+    assert_eq!(graph["connections"][0]["from"], json!({ "moduleId": "lfo-1", "portId": "cv-out" }));
+    assert_eq!(graph["taps"][0], json!({ "moduleId": "lfo-1", "portId": "rate" }));
+  }
+}
@@ -0,0 +1,53 @@
+//! Errors returned by graph loading and editing.
+
+use std::fmt;
+
+/// Why a graph could not be loaded, validated or edited.
+#[derive(Debug)]
+pub enum GraphError {
+  /// The payload is not valid graph JSON.
+  JsonParse(serde_json::Error),
+  /// A module `type` this engine does not know.
+  UnknownModuleType(String),
+  /// A module panicked while being built (also listed in
+  /// [`GraphEngine::faults`](crate::GraphEngine::faults)).
+  ModulePanicked { module_id: String, message: String },
+  /// Everything else wrong with the graph or the edit (duplicate ids,
+  /// cables to missing modules, unsupported schema version…), one message
+  /// per problem.
+  ValidationFailed(Vec<String>),
+}
+
+impl GraphError {
+  pub(crate) fn invalid(message: impl Into<String>) -> Self {
+    Self::ValidationFailed(vec![message.into()])
+  }
+}
+
+impl fmt::Display for GraphError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::JsonParse(err) => write!(f, "Invalid graph JSON: {err}"),
+      Self::UnknownModuleType(kind) => write!(f, "Unknown module type {kind}"),
+      Self::ModulePanicked { module_id, message } => {
+        write!(f, "Module {module_id} panicked while loading: {message}")
+      }
+      Self::ValidationFailed(problems) => write!(f, "{}", problems.join("; ")),
+    }
+  }
+}
+
+impl std::error::Error for GraphError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      Self::JsonParse(err) => Some(err),
+      _ => None,
+    }
+  }
+}
+
+impl From<serde_json::Error> for GraphError {
+  fn from(err: serde_json::Error) -> Self {
+    Self::JsonParse(err)
+  }
+}
@@ -0,0 +1,108 @@
+//! Engine events for UI animation.
+//!
+//! While rendering, the engine notes gate edges of Control modules, step
+//! changes of the sequencers and bar pulses of Clock modules, so the UI can
+//! light LEDs in time with the audio instead of polling state. Events of one
+//! `render` call are kept in a fixed-capacity queue (the oldest are dropped
+//! when it is full) and read with
+//! [`GraphEngine::drain_events`](crate::GraphEngine::drain_events).
+
+use std::collections::VecDeque;
+
+use serde::Serialize;
+
+/// Events kept per `render` call; older ones are dropped past this.
+pub const MAX_EVENTS_PER_BLOCK: usize = 256;
+
+/// What a [`GraphEvent`] reports. The discriminants are the codes used on
+/// the IPC event ring.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[repr(u32)]
+pub enum GraphEventKind {
+  /// A Control gate went high; `value` is the voice.
+  GateOn = 0,
+  /// A Control gate went low; `value` is the voice.
+  GateOff = 1,
+  /// A sequencer moved; `value` is the new step.
+  Step = 2,
+  /// A Clock bar pulse; `value` counts bars since the graph was loaded.
+  Bar = 3,
+}
+
+impl GraphEventKind {
+  /// Inverse of `kind as u32`.
+  pub fn from_code(code: u32) -> Option<Self> {
+    match code {
+      0 => Some(Self::GateOn),
+      1 => Some(Self::GateOff),
+      2 => Some(Self::Step),
+      3 => Some(Self::Bar),
+      _ => None,
+    }
+  }
+}
+
+/// One event, stamped with the frame of the `render` call it happened in.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphEvent {
+  /// [`hash_module_id`] of the module id.
+  pub module_hash: u32,
+  pub kind: GraphEventKind,
+  pub value: f32,
+  /// Offset in frames from the start of the `render` call. Step changes are
+  /// stamped at the start of the internal chunk they were seen in.
+  pub frame: u32,
+}
+
+/// Hash of a module id as carried by [`GraphEvent::module_hash`]. Same
+/// function as `dsp_ipc::hash_id`, so hashes match on both sides of the
+/// plugin bridge.
+pub fn hash_module_id(id: &str) -> u32 {
+  id.bytes().fold(5381_u32, |hash, byte| hash.wrapping_mul(33).wrapping_add(byte as u32))
+}
+
+/// Bounded event queue, allocated once.
+pub(crate) struct EventQueue {
+  events: VecDeque<GraphEvent>,
+}
+
+impl EventQueue {
+  pub(crate) fn new() -> Self {
+    Self { events: VecDeque::with_capacity(MAX_EVENTS_PER_BLOCK) }
+  }
+
+  pub(crate) fn push(&mut self, event: GraphEvent) {
+    if self.events.len() == MAX_EVENTS_PER_BLOCK {
+      self.events.pop_front();
+    }
+    self.events.push_back(event);
+  }
+
+  pub(crate) fn clear(&mut self) {
+    self.events.clear();
+  }
+
+  pub(crate) fn drain(&mut self) -> std::collections::vec_deque::Drain<'_, GraphEvent> {
+    self.events.drain(..)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn full_queue_drops_the_oldest_events() {
+    let mut queue = EventQueue::new();
+    for frame in 0..MAX_EVENTS_PER_BLOCK as u32 + 10 {
+      queue.push(GraphEvent { module_hash: 1, kind: GraphEventKind::Step, value: 0.0, frame });
+    }
+    let frames: Vec<u32> = queue.drain().map(|event| event.frame).collect();
+    assert_eq!(frames.len(), MAX_EVENTS_PER_BLOCK);
+    assert_eq!(frames[0], 10);
+    assert!(queue.drain().next().is_none());
+    assert_eq!(GraphEventKind::from_code(GraphEventKind::Bar as u32), Some(GraphEventKind::Bar));
+  }
+}
@@ -3,296 +3,486 @@
 use std::collections::HashMap;
 
 use dsp_core::{
-  Adsr, Arpeggiator, AyPlayer, Chaos, Choir, Chorus, Clap808, Clap909, Compressor, Cowbell808, Delay, DrumSequencer, Ensemble,
+  Adsr, Arpeggiator, AyPlayer, BitCrusher, Chaos, Choir, Chorus, Clap808, Clap909, ClockDivider, Compressor, Cowbell808, CvBank, DcBlocker, Delay, DrumSequencer, Ensemble, EnvFollower,
   EuclideanSequencer, FmMatrix, FmOperator, Granular, GranularDelay, HiHat808, HiHat909, Hpf, KarplusStrong,
-  Kick808, Kick909, Lfo, Mario, MasterClock, MidiFileSequencer, NesOsc, Noise, ParticleCloud, Phaser, PipeOrgan, PitchShifter,
-  Resonator, Reverb, Rimshot909, SampleHold, Shepard, SidPlayer, SlewLimiter, Snare808, Snare909, SnesOsc, SpectralSwarm, SpringReverb,
-  StepSequencer, Supersaw, TapeDelay, Tb303, Tom808, Tom909, TuringMachine, Vcf, Vco, Vocoder, Wavetable,
+  Kick808, Kick909, Lfo, MasterClock, MidiFileSequencer, NesOsc, Noise, NoiseGate, ParticleCloud, PeakMeter, Phaser, PipeOrgan, PitchShifter,
+  Resonator, Reverb, RingMod, Rimshot909, SampleHold, SamplePlayer, Shepard, SidPlayer, SlewLimiter, SmfFile, Snare808, Snare909, SnesOsc, SpectralSwarm, SpringReverb, StereoWidth,
+  StepSequencer, Supersaw, TapeDelay, Tb303, Tom808, Tom909, TuringMachine, Vcf, Vco, Vocoder, Wavetable, parse_smf,
 };
 
+use crate::params::param_meta;
 use crate::state::*;
 use crate::types::{ModuleType, ParamBuffer};
-use crate::param_number;
+use crate::{bank_channels, map_string_param, param_number, param_value, MAX_OUTPUT_BUSES};
+
+/// Per-voice seed offset, so poly voices of random modules are uncorrelated.
+const VOICE_SEED_STEP: u32 = 0x1234_5679;
+
+/// Ramp time of the VCF cutoff and envelope amount, which channel pressure
+/// sweeps through `modulate_param` many times a second.
+const VCF_GLIDE_SECONDS: f32 = 0.005;
+
+pub(crate) fn vcf_glide_samples(sample_rate: f32) -> usize {
+  (VCF_GLIDE_SECONDS * sample_rate).round() as usize
+}
+
+/// Seed of the per-voice Control offsets (`voiceDetune`, `voiceSpread`).
+const CONTROL_VOICE_SEED: u32 = 0x9E37_79B9;
+
+/// Deterministic detune and pan offsets in [-1, 1] for a Control voice.
+pub(crate) fn control_voice_offsets(voice_index: usize) -> [f32; 2] {
+  let mut hash = (voice_index as u32).wrapping_add(1).wrapping_mul(CONTROL_VOICE_SEED);
+  [0, 1].map(|_| {
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x7FEB_352D);
+    hash ^= hash >> 15;
+    hash = hash.wrapping_mul(0x846C_A68B);
+    hash ^= hash >> 16;
+    (hash >> 8) as f32 / (1 << 23) as f32 - 1.0
+  })
+}
+
+/// Params older graphs spell differently: every key a param is read from,
+/// the preferred one first.
+const PARAM_KEYS: &[&[&str]] = &[
+  &["carrierFreq", "carrier-freq"],
+  &["carrierShape", "carrier-shape"],
+  &["internalCarrier", "internal-carrier"],
+  &["phaseSpread", "phase-spread"],
+  &["envMod", "envmod"],
+  &["accentAmount", "accent"],
+  &["tempoScale", "tempo-scale"],
+];
+
+/// Value a graph stores for a numeric param, under its name or one of its
+/// older spellings (FM Matrix params also in their nested form).
+pub(crate) fn stored_param(
+  module_type: ModuleType,
+  params: &HashMap<String, serde_json::Value>,
+  param: &str,
+) -> Option<f32> {
+  if module_type == ModuleType::FmMatrix {
+    return stored_fm_matrix_param(params, fm_matrix_key(param)?);
+  }
+  let keys = param_spellings(param).unwrap_or(std::slice::from_ref(&param));
+  keys.iter().find_map(|key| param_value(params, key))
+}
+
+/// Every key a param with older spellings may be stored under.
+fn param_spellings(param: &str) -> Option<&'static [&'static str]> {
+  PARAM_KEYS.iter().find(|keys| keys.contains(&param)).copied()
+}
+
+/// Drop the other spellings of `param` from `params`, so the value about to
+/// be stored under `param` is the one read back and rebuilt from.
+pub(crate) fn forget_param_spellings(
+  module_type: ModuleType,
+  params: &mut HashMap<String, serde_json::Value>,
+  param: &str,
+) {
+  if module_type == ModuleType::FmMatrix {
+    let key = fm_matrix_key(param);
+    params.retain(|name, _| name == param || key.is_none() || fm_matrix_key(name) != key);
+  } else {
+    for key in param_spellings(param).unwrap_or_default() {
+      if *key != param {
+        params.remove(*key);
+      }
+    }
+  }
+}
+
+/// Load value of a parameter described in [`param_table`](crate::param_table).
+/// Missing values take the table's default and loaded ones are clamped and
+/// snapped like `set_param` does, so the table is the one place a
+/// parameter's default and range live.
+fn table_param(module_type: ModuleType, params: &HashMap<String, serde_json::Value>, id: &str) -> f32 {
+  let meta = param_meta(module_type, id).unwrap_or_else(|| panic!("{module_type:?} has no `{id}` in param_table"));
+  meta.constrain(stored_param(module_type, params, id).unwrap_or(meta.default))
+}
 
 /// Create the initial state for a module based on its type and parameters.
+/// `seed` is the module's base random seed; poly voices offset it.
 pub(crate) fn create_state(
   module_type: ModuleType,
   params: &HashMap<String, serde_json::Value>,
   sample_rate: f32,
   voice_index: Option<usize>,
+  seed: u32,
 ) -> ModuleState {
+  let voice_seed_offset = (voice_index.unwrap_or(0) as u32).wrapping_mul(VOICE_SEED_STEP);
+  let param = |id: &str| table_param(module_type, params, id);
   match module_type {
     ModuleType::Oscillator => ModuleState::Vco(VcoState {
       vco: Vco::new(sample_rate),
-      base_freq: ParamBuffer::new(param_number(params, "frequency", 220.0)),
+      base_freq: ParamBuffer::new(param("frequency")),
       waveform: ParamBuffer::new(param_number(params, "type", 2.0)),
-      pwm: ParamBuffer::new(param_number(params, "pwm", 0.5)),
-      fm_lin_depth: ParamBuffer::new(param_number(params, "fmLin", 0.0)),
-      fm_exp_depth: ParamBuffer::new(param_number(params, "fmExp", 0.0)),
-      unison: ParamBuffer::new(param_number(params, "unison", 1.0)),
-      detune: ParamBuffer::new(param_number(params, "detune", 0.0)),
-      sub_mix: ParamBuffer::new(param_number(params, "subMix", 0.0)),
+      pwm: ParamBuffer::new(param("pwm")),
+      fm_lin_depth: ParamBuffer::new(param("fmLin")),
+      fm_exp_depth: ParamBuffer::new(param("fmExp")),
+      unison: ParamBuffer::new(param("unison")),
+      detune: ParamBuffer::new(param("detune")),
+      sub_mix: ParamBuffer::new(param("subMix")),
       sub_oct: ParamBuffer::new(param_number(params, "subOct", 1.0)),
-    }),
-    ModuleType::Noise => ModuleState::Noise(NoiseState {
-      noise: Noise::new(),
-      level: ParamBuffer::new(param_number(params, "level", 0.4)),
-      noise_type: ParamBuffer::new(param_number(params, "noiseType", 0.0)),
-      stereo: ParamBuffer::new(param_number(params, "stereo", 0.0)),
-      pan: ParamBuffer::new(param_number(params, "pan", 0.0)),
-    }),
+      stereo_spread: ParamBuffer::new(param("stereoSpread")),
+      wave_morph: ParamBuffer::new(param("waveMorph")),
+      oversample: ParamBuffer::new(param("oversample")),
+    }),
+    ModuleType::Noise => {
+      let seed = match params.get("seed") {
+        Some(_) => param_number(params, "seed", 0.0) as u32,
+        None => seed,
+      };
+      ModuleState::Noise(NoiseState {
+        noise: Noise::new_with_seed(seed.wrapping_add(voice_seed_offset)),
+        seed: ParamBuffer::new(seed as f32),
+        voice_offset: voice_seed_offset,
+        level: ParamBuffer::new(param("level")),
+        noise_type: ParamBuffer::new(param_number(params, "noiseType", 0.0)),
+        rate_divide: ParamBuffer::new(param("rateDivide")),
+        stereo: ParamBuffer::new(param("stereo")),
+        pan: ParamBuffer::new(param("pan")),
+      })
+    }
     ModuleType::ModRouter => ModuleState::ModRouter(ModRouterState {
-      depth_pitch: ParamBuffer::new(param_number(params, "depthPitch", 0.0)),
-      depth_pwm: ParamBuffer::new(param_number(params, "depthPwm", 0.0)),
-      depth_vcf: ParamBuffer::new(param_number(params, "depthVcf", 0.0)),
-      depth_vca: ParamBuffer::new(param_number(params, "depthVca", 0.0)),
+      depth_pitch: ParamBuffer::new(param("depthPitch")),
+      depth_pwm: ParamBuffer::new(param("depthPwm")),
+      depth_vcf: ParamBuffer::new(param("depthVcf")),
+      depth_vca: ParamBuffer::new(param("depthVca")),
+      depth_aux: ParamBuffer::new(param("depthAux")),
+      offset_pitch: ParamBuffer::new(param("offsetPitch")),
+      offset_pwm: ParamBuffer::new(param("offsetPwm")),
+      offset_vcf: ParamBuffer::new(param("offsetVcf")),
+      offset_vca: ParamBuffer::new(param("offsetVca")),
+      offset_aux: ParamBuffer::new(param("offsetAux")),
+    }),
+    ModuleType::Attenuverter => ModuleState::Attenuverter(AttenuverterState {
+      scale: ParamBuffer::new(param("scale")),
+      offset: ParamBuffer::new(param("offset")),
     }),
     ModuleType::SampleHold => ModuleState::SampleHold(SampleHoldState {
-      sample_hold: SampleHold::new(),
-      mode: ParamBuffer::new(param_number(params, "mode", 0.0)),
+      sample_hold: SampleHold::new_with_seed(sample_rate, seed.wrapping_add(voice_seed_offset)),
+      mode: ParamBuffer::new(param("mode")),
+      glide: ParamBuffer::new(param("glide")),
+      noise_level: ParamBuffer::new(param("noiseLevel")),
     }),
     ModuleType::Slew => ModuleState::Slew(SlewState {
       slew: SlewLimiter::new(sample_rate),
-      rise: ParamBuffer::new(param_number(params, "rise", 0.05)),
-      fall: ParamBuffer::new(param_number(params, "fall", 0.05)),
+      rise: ParamBuffer::new(param("rise")),
+      fall: ParamBuffer::new(param("fall")),
+    }),
+    ModuleType::EnvFollower => ModuleState::EnvFollower(EnvFollowerState {
+      follower: EnvFollower::new(sample_rate),
+      attack: ParamBuffer::new(param("attack")),
+      release: ParamBuffer::new(param("release")),
+      gain: ParamBuffer::new(param("gain")),
     }),
     ModuleType::Quantizer => ModuleState::Quantizer(QuantizerState {
-      root: ParamBuffer::new(param_number(params, "root", 0.0)),
+      root: ParamBuffer::new(param("root")),
       scale: ParamBuffer::new(param_number(params, "scale", 0.0)),
     }),
     ModuleType::Chaos => ModuleState::Chaos(ChaosState {
       chaos: Chaos::new(sample_rate),
-      speed: ParamBuffer::new(param_number(params, "speed", 0.5)),
-      rho: ParamBuffer::new(param_number(params, "rho", 28.0)),
-      sigma: ParamBuffer::new(param_number(params, "sigma", 10.0)),
-      beta: ParamBuffer::new(param_number(params, "beta", 8.0 / 3.0)),
+      speed: ParamBuffer::new(param("speed")),
+      rho: ParamBuffer::new(param("rho")),
+      sigma: ParamBuffer::new(param("sigma")),
+      beta: ParamBuffer::new(param("beta")),
       scale: ParamBuffer::new(param_number(params, "scale", 0.0)),
-      root: ParamBuffer::new(param_number(params, "root", 0.0)),
+      root: ParamBuffer::new(param("root")),
+    }),
+    ModuleType::Logic => ModuleState::Logic(LogicState {
+      threshold: ParamBuffer::new(param("threshold")),
+    }),
+    ModuleType::PeakMeter => ModuleState::PeakMeter(PeakMeterState {
+      meter: PeakMeter::new(sample_rate),
+      attack: ParamBuffer::new(param("attack")),
+      release: ParamBuffer::new(param("release")),
+      rms_window: ParamBuffer::new(param("rmsWindow")),
     }),
     ModuleType::RingMod => ModuleState::RingMod(RingModState {
-      level: ParamBuffer::new(param_number(params, "level", 0.9)),
+      ring: RingMod::new(sample_rate),
+      level: ParamBuffer::new(param("level")),
+      carrier_freq: ParamBuffer::new(param("carrierFreq")),
+      carrier_shape: ParamBuffer::new(param("carrierShape")),
+      internal_carrier: ParamBuffer::new(param("internalCarrier")),
+      mix: ParamBuffer::new(param("mix")),
     }),
     ModuleType::Gain => ModuleState::Gain(GainState {
-      gain: ParamBuffer::new(param_number(params, "gain", 0.2)),
+      gain: ParamBuffer::new(param("gain")),
+      invert: ParamBuffer::new(param("invert")),
     }),
     ModuleType::CvVca => ModuleState::CvVca(GainState {
-      gain: ParamBuffer::new(param_number(params, "gain", 1.0)),
+      gain: ParamBuffer::new(param("gain")),
+      invert: ParamBuffer::new(param("invert")),
     }),
     ModuleType::Output => ModuleState::Output(OutputState {
-      level: ParamBuffer::new(param_number(params, "level", 0.8)),
+      level: ParamBuffer::new(param("level")),
+      bus: param_number(params, "bus", 0.0).round().clamp(0.0, (MAX_OUTPUT_BUSES - 1) as f32) as usize,
+      voice_spread: param("voiceSpread").clamp(0.0, 1.0),
     }),
     ModuleType::Lab => ModuleState::Lab(LabState {
       level: ParamBuffer::new(param_number(params, "level", 0.8)),
     }),
     ModuleType::Lfo => ModuleState::Lfo(LfoState {
       lfo: Lfo::new(sample_rate),
-      rate: ParamBuffer::new(param_number(params, "rate", 2.0)),
-      shape: ParamBuffer::new(param_number(params, "shape", 0.0)),
-      depth: ParamBuffer::new(param_number(params, "depth", 0.7)),
-      offset: ParamBuffer::new(param_number(params, "offset", 0.0)),
+      rate: ParamBuffer::new(param("rate")),
+      shape: ParamBuffer::new(param("shape")),
+      depth: ParamBuffer::new(param("depth")),
+      offset: ParamBuffer::new(param("offset")),
       bipolar: ParamBuffer::new(param_number(params, "bipolar", 1.0)),
     }),
     ModuleType::Adsr => ModuleState::Adsr(AdsrState {
       adsr: Adsr::new(sample_rate),
-      attack: ParamBuffer::new(param_number(params, "attack", 0.02)),
-      decay: ParamBuffer::new(param_number(params, "decay", 0.2)),
-      sustain: ParamBuffer::new(param_number(params, "sustain", 0.65)),
-      release: ParamBuffer::new(param_number(params, "release", 0.4)),
+      attack: ParamBuffer::new(param("attack")),
+      decay: ParamBuffer::new(param("decay")),
+      sustain: ParamBuffer::new(param("sustain")),
+      release: ParamBuffer::new(param("release")),
     }),
     ModuleType::Vcf => ModuleState::Vcf(VcfState {
       vcf: Vcf::new(sample_rate),
-      cutoff: ParamBuffer::new(param_number(params, "cutoff", 800.0)),
-      resonance: ParamBuffer::new(param_number(params, "resonance", 0.4)),
-      drive: ParamBuffer::new(param_number(params, "drive", 0.2)),
-      env_amount: ParamBuffer::new(param_number(params, "envAmount", 0.0)),
-      mod_amount: ParamBuffer::new(param_number(params, "modAmount", 0.0)),
-      key_track: ParamBuffer::new(param_number(params, "keyTrack", 0.0)),
+      cutoff: ParamBuffer::new(param("cutoff")).with_glide(vcf_glide_samples(sample_rate)),
+      resonance: ParamBuffer::new(param("resonance")),
+      drive: ParamBuffer::new(param("drive")),
+      env_amount: ParamBuffer::new(param("envAmount")).with_glide(vcf_glide_samples(sample_rate)),
+      mod_amount: ParamBuffer::new(param("modAmount")),
+      key_track: ParamBuffer::new(param("keyTrack")),
       model: ParamBuffer::new(param_number(params, "model", 0.0)),
       mode: ParamBuffer::new(param_number(params, "mode", 0.0)),
       slope: ParamBuffer::new(param_number(params, "slope", 1.0)),
     }),
     ModuleType::Hpf => ModuleState::Hpf(HpfState {
       hpf: Hpf::new(sample_rate),
-      cutoff: ParamBuffer::new(param_number(params, "cutoff", 280.0)),
+      cutoff: ParamBuffer::new(param("cutoff")),
+    }),
+    ModuleType::DcBlock => ModuleState::DcBlock(DcBlockState {
+      blocker: DcBlocker::new(sample_rate),
+      cutoff: ParamBuffer::new(param("cutoff")),
     }),
     ModuleType::Mixer => ModuleState::Mixer(MixerState {
-      level_a: ParamBuffer::new(param_number(params, "levelA", 0.6)),
-      level_b: ParamBuffer::new(param_number(params, "levelB", 0.6)),
+      level_a: ParamBuffer::new(param("levelA")),
+      level_b: ParamBuffer::new(param("levelB")),
     }),
     ModuleType::MixerWide => ModuleState::MixerWide(MixerWideState {
-      level_a: ParamBuffer::new(param_number(params, "levelA", 0.6)),
-      level_b: ParamBuffer::new(param_number(params, "levelB", 0.6)),
-      level_c: ParamBuffer::new(param_number(params, "levelC", 0.6)),
-      level_d: ParamBuffer::new(param_number(params, "levelD", 0.6)),
-      level_e: ParamBuffer::new(param_number(params, "levelE", 0.6)),
-      level_f: ParamBuffer::new(param_number(params, "levelF", 0.6)),
+      level_a: ParamBuffer::new(param("levelA")),
+      level_b: ParamBuffer::new(param("levelB")),
+      level_c: ParamBuffer::new(param("levelC")),
+      level_d: ParamBuffer::new(param("levelD")),
+      level_e: ParamBuffer::new(param("levelE")),
+      level_f: ParamBuffer::new(param("levelF")),
     }),
     ModuleType::Mixer8 => ModuleState::Mixer8(Mixer8State {
-      level1: ParamBuffer::new(param_number(params, "level1", 0.6)),
-      level2: ParamBuffer::new(param_number(params, "level2", 0.6)),
-      level3: ParamBuffer::new(param_number(params, "level3", 0.6)),
-      level4: ParamBuffer::new(param_number(params, "level4", 0.6)),
-      level5: ParamBuffer::new(param_number(params, "level5", 0.6)),
-      level6: ParamBuffer::new(param_number(params, "level6", 0.6)),
-      level7: ParamBuffer::new(param_number(params, "level7", 0.6)),
-      level8: ParamBuffer::new(param_number(params, "level8", 0.6)),
+      level1: ParamBuffer::new(param("level1")),
+      level2: ParamBuffer::new(param("level2")),
+      level3: ParamBuffer::new(param("level3")),
+      level4: ParamBuffer::new(param("level4")),
+      level5: ParamBuffer::new(param("level5")),
+      level6: ParamBuffer::new(param("level6")),
+      level7: ParamBuffer::new(param("level7")),
+      level8: ParamBuffer::new(param("level8")),
     }),
     ModuleType::Crossfader => ModuleState::Crossfader(CrossfaderState {
-      mix: ParamBuffer::new(param_number(params, "mix", 0.5)),
+      mix: ParamBuffer::new(param("mix")),
+      equal_power: param("curve") >= 0.5,
+    }),
+    ModuleType::Multiple => ModuleState::Multiple,
+    ModuleType::Pan => ModuleState::Pan(PanState {
+      pan: ParamBuffer::new(param("pan")),
+    }),
+    ModuleType::Width => ModuleState::Width(WidthState {
+      width: StereoWidth::new(sample_rate),
+      amount: ParamBuffer::new(param("width")),
+      mono_below: ParamBuffer::new(param("monoBelow")),
     }),
     ModuleType::Chorus => ModuleState::Chorus(ChorusState {
       chorus: Chorus::new(sample_rate),
-      rate: ParamBuffer::new(param_number(params, "rate", 0.3)),
-      depth: ParamBuffer::new(param_number(params, "depth", 8.0)),
-      delay: ParamBuffer::new(param_number(params, "delay", 18.0)),
-      mix: ParamBuffer::new(param_number(params, "mix", 0.45)),
-      feedback: ParamBuffer::new(param_number(params, "feedback", 0.15)),
-      spread: ParamBuffer::new(param_number(params, "spread", 0.6)),
+      rate: ParamBuffer::new(param("rate")),
+      depth: ParamBuffer::new(param("depth")),
+      delay: ParamBuffer::new(param("delay")),
+      mix: ParamBuffer::new(param("mix")),
+      feedback: ParamBuffer::new(param("feedback")),
+      spread: ParamBuffer::new(param("spread")),
+      wet_only: ParamBuffer::new(param("wetOnly")),
     }),
     ModuleType::Ensemble => ModuleState::Ensemble(EnsembleState {
       ensemble: Ensemble::new(sample_rate),
-      rate: ParamBuffer::new(param_number(params, "rate", 0.25)),
-      depth: ParamBuffer::new(param_number(params, "depth", 12.0)),
-      delay: ParamBuffer::new(param_number(params, "delay", 12.0)),
-      mix: ParamBuffer::new(param_number(params, "mix", 0.6)),
-      spread: ParamBuffer::new(param_number(params, "spread", 0.7)),
+      rate: ParamBuffer::new(param("rate")),
+      depth: ParamBuffer::new(param("depth")),
+      delay: ParamBuffer::new(param("delay")),
+      mix: ParamBuffer::new(param("mix")),
+      spread: ParamBuffer::new(param("spread")),
+      wet_only: ParamBuffer::new(param("wetOnly")),
     }),
     ModuleType::Choir => ModuleState::Choir(ChoirState {
       choir: Choir::new(sample_rate),
       vowel: ParamBuffer::new(param_number(params, "vowel", 0.0)),
-      rate: ParamBuffer::new(param_number(params, "rate", 0.25)),
-      depth: ParamBuffer::new(param_number(params, "depth", 0.35)),
-      mix: ParamBuffer::new(param_number(params, "mix", 0.5)),
+      rate: ParamBuffer::new(param("rate")),
+      depth: ParamBuffer::new(param("depth")),
+      mix: ParamBuffer::new(param("mix")),
     }),
     ModuleType::Vocoder => ModuleState::Vocoder(VocoderState {
       vocoder: Vocoder::new(sample_rate),
-      attack: ParamBuffer::new(param_number(params, "attack", 25.0)),
-      release: ParamBuffer::new(param_number(params, "release", 140.0)),
-      low: ParamBuffer::new(param_number(params, "low", 120.0)),
-      high: ParamBuffer::new(param_number(params, "high", 5000.0)),
-      q: ParamBuffer::new(param_number(params, "q", 2.5)),
-      formant: ParamBuffer::new(param_number(params, "formant", 0.0)),
-      emphasis: ParamBuffer::new(param_number(params, "emphasis", 0.4)),
-      unvoiced: ParamBuffer::new(param_number(params, "unvoiced", 0.0)),
-      mix: ParamBuffer::new(param_number(params, "mix", 0.8)),
-      mod_gain: ParamBuffer::new(param_number(params, "modGain", 1.0)),
-      car_gain: ParamBuffer::new(param_number(params, "carGain", 1.0)),
+      attack: ParamBuffer::new(param("attack")),
+      release: ParamBuffer::new(param("release")),
+      low: ParamBuffer::new(param("low")),
+      high: ParamBuffer::new(param("high")),
+      q: ParamBuffer::new(param("q")),
+      formant: ParamBuffer::new(param("formant")),
+      emphasis: ParamBuffer::new(param("emphasis")),
+      unvoiced: ParamBuffer::new(param("unvoiced")),
+      mix: ParamBuffer::new(param("mix")),
+      mod_gain: ParamBuffer::new(param("modGain")),
+      car_gain: ParamBuffer::new(param("carGain")),
     }),
     ModuleType::AudioIn => ModuleState::AudioIn(AudioInState {
-      gain: ParamBuffer::new(param_number(params, "gain", 1.0)),
+      gain: ParamBuffer::new(param("gain")),
     }),
     ModuleType::Delay => ModuleState::Delay(DelayState {
       delay: Delay::new(sample_rate),
-      time: ParamBuffer::new(param_number(params, "time", 360.0)),
-      feedback: ParamBuffer::new(param_number(params, "feedback", 0.35)),
-      mix: ParamBuffer::new(param_number(params, "mix", 0.25)),
-      tone: ParamBuffer::new(param_number(params, "tone", 0.55)),
+      time: ParamBuffer::new(param("time")),
+      feedback: ParamBuffer::new(param("feedback")),
+      mix: ParamBuffer::new(param("mix")),
+      tone: ParamBuffer::new(param("tone")),
       ping_pong: ParamBuffer::new(param_number(params, "pingPong", 0.0)),
+      wet_only: ParamBuffer::new(param("wetOnly")),
     }),
     ModuleType::GranularDelay => ModuleState::GranularDelay(GranularDelayState {
       delay: GranularDelay::new(sample_rate),
-      time: ParamBuffer::new(param_number(params, "time", 420.0)),
-      size: ParamBuffer::new(param_number(params, "size", 120.0)),
-      density: ParamBuffer::new(param_number(params, "density", 6.0)),
-      pitch: ParamBuffer::new(param_number(params, "pitch", 1.0)),
-      feedback: ParamBuffer::new(param_number(params, "feedback", 0.35)),
-      mix: ParamBuffer::new(param_number(params, "mix", 0.5)),
+      time: ParamBuffer::new(param("time")),
+      size: ParamBuffer::new(param("size")),
+      density: ParamBuffer::new(param("density")),
+      pitch: ParamBuffer::new(param("pitch")),
+      feedback: ParamBuffer::new(param("feedback")),
+      mix: ParamBuffer::new(param("mix")),
     }),
     ModuleType::TapeDelay => ModuleState::TapeDelay(TapeDelayState {
       delay: TapeDelay::new(sample_rate),
-      time: ParamBuffer::new(param_number(params, "time", 420.0)),
-      feedback: ParamBuffer::new(param_number(params, "feedback", 0.35)),
-      mix: ParamBuffer::new(param_number(params, "mix", 0.35)),
-      tone: ParamBuffer::new(param_number(params, "tone", 0.55)),
-      wow: ParamBuffer::new(param_number(params, "wow", 0.2)),
-      flutter: ParamBuffer::new(param_number(params, "flutter", 0.2)),
-      drive: ParamBuffer::new(param_number(params, "drive", 0.2)),
+      time: ParamBuffer::new(param("time")),
+      feedback: ParamBuffer::new(param("feedback")),
+      mix: ParamBuffer::new(param("mix")),
+      tone: ParamBuffer::new(param("tone")),
+      wow: ParamBuffer::new(param("wow")),
+      flutter: ParamBuffer::new(param("flutter")),
+      drive: ParamBuffer::new(param("drive")),
     }),
     ModuleType::SpringReverb => ModuleState::SpringReverb(SpringReverbState {
       reverb: SpringReverb::new(sample_rate),
-      decay: ParamBuffer::new(param_number(params, "decay", 0.6)),
-      tone: ParamBuffer::new(param_number(params, "tone", 0.4)),
-      mix: ParamBuffer::new(param_number(params, "mix", 0.4)),
-      drive: ParamBuffer::new(param_number(params, "drive", 0.2)),
+      decay: ParamBuffer::new(param("decay")),
+      tone: ParamBuffer::new(param("tone")),
+      mix: ParamBuffer::new(param("mix")),
+      drive: ParamBuffer::new(param("drive")),
+      diffusion: ParamBuffer::new(param("diffusion")),
+      spring_count: ParamBuffer::new(param("spring-count")),
     }),
     ModuleType::Reverb => ModuleState::Reverb(ReverbState {
       reverb: Reverb::new(sample_rate),
-      time: ParamBuffer::new(param_number(params, "time", 0.62)),
-      damp: ParamBuffer::new(param_number(params, "damp", 0.4)),
-      pre_delay: ParamBuffer::new(param_number(params, "preDelay", 18.0)),
-      mix: ParamBuffer::new(param_number(params, "mix", 0.25)),
-    }),
-    ModuleType::Phaser => ModuleState::Phaser(PhaserState {
-      phaser: Phaser::new(sample_rate),
-      rate: ParamBuffer::new(param_number(params, "rate", 0.5)),
-      depth: ParamBuffer::new(param_number(params, "depth", 0.7)),
-      feedback: ParamBuffer::new(param_number(params, "feedback", 0.3)),
-      mix: ParamBuffer::new(param_number(params, "mix", 0.5)),
-    }),
+      time: ParamBuffer::new(param("time")),
+      damp: ParamBuffer::new(param("damp")),
+      pre_delay: ParamBuffer::new(param("preDelay")),
+      mix: ParamBuffer::new(param("mix")),
+      wet_only: ParamBuffer::new(param("wetOnly")),
+    }),
+    ModuleType::Phaser => {
+      let stages = param("stages");
+      ModuleState::Phaser(PhaserState {
+        phaser: Phaser::new_with_stages(sample_rate, stages.round().max(0.0) as usize),
+        rate: ParamBuffer::new(param("rate")),
+        depth: ParamBuffer::new(param("depth")),
+        feedback: ParamBuffer::new(param("feedback")),
+        mix: ParamBuffer::new(param("mix")),
+        stages: ParamBuffer::new(stages),
+        stereo: ParamBuffer::new(param("stereo")),
+        center: ParamBuffer::new(param("center")),
+        wet_only: ParamBuffer::new(param("wetOnly")),
+      })
+    }
     ModuleType::Distortion => ModuleState::Distortion(DistortionState {
-      drive: ParamBuffer::new(param_number(params, "drive", 0.5)),
-      tone: ParamBuffer::new(param_number(params, "tone", 0.5)),
-      mix: ParamBuffer::new(param_number(params, "mix", 1.0)),
-      mode: ParamBuffer::new(param_number(params, "mode", 0.0)),
+      drive: ParamBuffer::new(param("drive")),
+      tone: ParamBuffer::new(param("tone")),
+      mix: ParamBuffer::new(param("mix")),
+      mode: ParamBuffer::new(match params.get("mode").and_then(|value| value.as_str()) {
+        Some(text) => distortion_mode(text).unwrap_or(0.0),
+        None => param("mode"),
+      }),
+      bias: ParamBuffer::new(param("bias")),
     }),
     ModuleType::Wavefolder => ModuleState::Wavefolder(WavefolderState {
-      drive: ParamBuffer::new(param_number(params, "drive", 0.4)),
-      fold: ParamBuffer::new(param_number(params, "fold", 0.5)),
-      bias: ParamBuffer::new(param_number(params, "bias", 0.0)),
-      mix: ParamBuffer::new(param_number(params, "mix", 0.8)),
+      drive: ParamBuffer::new(param("drive")),
+      fold: ParamBuffer::new(param("fold")),
+      bias: ParamBuffer::new(param("bias")),
+      mix: ParamBuffer::new(param("mix")),
+    }),
+    ModuleType::BitCrusher => ModuleState::BitCrusher(BitCrusherState {
+      crusher: BitCrusher::new(),
+      bits: ParamBuffer::new(param("bits")),
+      downsample: ParamBuffer::new(param("downsample")),
+      mix: ParamBuffer::new(param("mix")),
+      antialias: ParamBuffer::new(param("antialias")),
+      dither: ParamBuffer::new(param("dither")),
     }),
     ModuleType::Supersaw => ModuleState::Supersaw(SupersawState {
       supersaw: Supersaw::new(sample_rate),
-      base_freq: ParamBuffer::new(param_number(params, "frequency", 220.0)),
-      detune: ParamBuffer::new(param_number(params, "detune", 25.0)),
-      mix: ParamBuffer::new(param_number(params, "mix", 1.0)),
+      base_freq: ParamBuffer::new(param("frequency")),
+      detune: ParamBuffer::new(param("detune")),
+      mix: ParamBuffer::new(param("mix")),
+      phase_spread: ParamBuffer::new(param("phaseSpread")),
     }),
     ModuleType::Karplus => ModuleState::Karplus(KarplusState {
       karplus: KarplusStrong::new(sample_rate),
-      frequency: ParamBuffer::new(param_number(params, "frequency", 220.0)),
-      damping: ParamBuffer::new(param_number(params, "damping", 0.3)),
-      decay: ParamBuffer::new(param_number(params, "decay", 0.995)),
-      brightness: ParamBuffer::new(param_number(params, "brightness", 0.5)),
-      pluck_pos: ParamBuffer::new(param_number(params, "pluckPos", 0.5)),
+      frequency: ParamBuffer::new(param("frequency")),
+      damping: ParamBuffer::new(param("damping")),
+      decay: ParamBuffer::new(param("decay")),
+      brightness: ParamBuffer::new(param("brightness")),
+      pluck_pos: ParamBuffer::new(param("pluckPos")),
     }),
     ModuleType::NesOsc => ModuleState::NesOsc(NesOscState {
       nes_osc: NesOsc::new(sample_rate),
-      base_freq: ParamBuffer::new(param_number(params, "frequency", 220.0)),
-      fine: ParamBuffer::new(param_number(params, "fine", 0.0)),
-      volume: ParamBuffer::new(param_number(params, "volume", 1.0)),
+      base_freq: ParamBuffer::new(param("frequency")),
+      fine: ParamBuffer::new(param("fine")),
+      volume: ParamBuffer::new(param("volume")),
       mode: ParamBuffer::new(param_number(params, "mode", 0.0)),
       duty: ParamBuffer::new(param_number(params, "duty", 1.0)),
       noise_mode: ParamBuffer::new(param_number(params, "noiseMode", 0.0)),
-      bitcrush: ParamBuffer::new(param_number(params, "bitcrush", 1.0)),
+      bitcrush: ParamBuffer::new(param("bitcrush")),
     }),
     ModuleType::SnesOsc => ModuleState::SnesOsc(SnesOscState {
       snes_osc: SnesOsc::new(sample_rate),
-      base_freq: ParamBuffer::new(param_number(params, "frequency", 220.0)),
-      fine: ParamBuffer::new(param_number(params, "fine", 0.0)),
-      volume: ParamBuffer::new(param_number(params, "volume", 1.0)),
+      base_freq: ParamBuffer::new(param("frequency")),
+      fine: ParamBuffer::new(param("fine")),
+      volume: ParamBuffer::new(param("volume")),
       wave: ParamBuffer::new(param_number(params, "wave", 0.0)),
-      gauss: ParamBuffer::new(param_number(params, "gauss", 0.7)),
-      color: ParamBuffer::new(param_number(params, "color", 0.5)),
-      lofi: ParamBuffer::new(param_number(params, "lofi", 0.5)),
+      gauss: ParamBuffer::new(param("gauss")),
+      color: ParamBuffer::new(param("color")),
+      lofi: ParamBuffer::new(param("lofi")),
     }),
     ModuleType::Control => ModuleState::Control(ControlState {
       cv: param_number(params, "cv", 0.0),
       cv_target: param_number(params, "cv", 0.0),
       cv_step: 0.0,
       cv_remaining: 0,
-      velocity: param_number(params, "velocity", 1.0).clamp(0.0, 1.0),
-      velocity_target: param_number(params, "velocity", 1.0).clamp(0.0, 1.0),
+      cv_exponential: false,
+      velocity: param("velocity").clamp(0.0, 1.0),
+      velocity_target: param("velocity").clamp(0.0, 1.0),
       velocity_step: 0.0,
       velocity_remaining: 0,
       gate: param_number(params, "gate", 0.0),
       retrigger_samples: 0,
       sync_remaining: 0,
-      glide_seconds: param_number(params, "glide", 0.0).max(0.0),
+      glide_seconds: param("glide").max(0.0),
+      glide_legato: param("glideMode") >= 0.5,
+      glide_exponential: param("glideCurve") >= 0.5,
+      voice_detune: ParamBuffer::new(param("voiceDetune")),
+      voice_spread: ParamBuffer::new(param("voiceSpread")),
+      voice_offsets: control_voice_offsets(voice_index.unwrap_or(0)),
+      tuning: Tuning {
+        ref_freq: param("refFreq").max(1.0),
+        edo: param("edo").round().max(1.0),
+        table: params.get("tuning").map(tuning_table).unwrap_or_default(),
+      },
       sample_rate,
     }),
     ModuleType::Scope => ModuleState::Scope,
-    ModuleType::Mario => ModuleState::Mario(MarioState {
-      mario: Mario::new(),
+    ModuleType::CvBank => ModuleState::CvBank(CvBankState {
+      bank: CvBank::new(bank_channels(params)),
     }),
     ModuleType::Arpeggiator => ModuleState::Arpeggiator(ArpeggiatorState {
       arp: Arpeggiator::new(sample_rate),
@@ -301,15 +491,15 @@ pub(crate) fn create_state(
       mode: ParamBuffer::new(param_number(params, "mode", 0.0)),
       octaves: ParamBuffer::new(param_number(params, "octaves", 1.0)),
       rate: ParamBuffer::new(param_number(params, "rate", 7.0)),
-      gate_len: ParamBuffer::new(param_number(params, "gate", 75.0)),
-      swing: ParamBuffer::new(param_number(params, "swing", 0.0)),
-      tempo: ParamBuffer::new(param_number(params, "tempo", 120.0)),
+      gate_len: ParamBuffer::new(param("gate")),
+      swing: ParamBuffer::new(param("swing")),
+      tempo: ParamBuffer::new(param("tempo")),
       ratchet: ParamBuffer::new(param_number(params, "ratchet", 1.0)),
       ratchet_decay: ParamBuffer::new(param_number(params, "ratchetDecay", 0.0)),
-      probability: ParamBuffer::new(param_number(params, "probability", 100.0)),
+      probability: ParamBuffer::new(param("probability")),
       velocity_mode: ParamBuffer::new(param_number(params, "velocityMode", 0.0)),
       accent_pattern: ParamBuffer::new(param_number(params, "accentPattern", 0.0)),
-      euclid_steps: ParamBuffer::new(param_number(params, "euclidSteps", 8.0)),
+      euclid_steps: ParamBuffer::new(param("euclidSteps")),
       euclid_fill: ParamBuffer::new(param_number(params, "euclidFill", 4.0)),
       euclid_rotate: ParamBuffer::new(param_number(params, "euclidRotate", 0.0)),
       euclid_enabled: ParamBuffer::new(param_number(params, "euclidEnabled", 0.0)),
@@ -326,101 +516,104 @@ pub(crate) fn create_state(
       ModuleState::StepSequencer(StepSequencerState {
         seq,
         enabled: ParamBuffer::new(param_number(params, "enabled", 1.0)),
-        tempo: ParamBuffer::new(param_number(params, "tempo", 120.0)),
+        tempo: ParamBuffer::new(param("tempo")),
         rate: ParamBuffer::new(param_number(params, "rate", 3.0)), // Default 1/8
-        gate_length: ParamBuffer::new(param_number(params, "gateLength", 50.0)),
-        swing: ParamBuffer::new(param_number(params, "swing", 0.0)),
-        slide_time: ParamBuffer::new(param_number(params, "slideTime", 50.0)),
+        gate_length: ParamBuffer::new(param("gateLength")),
+        swing: ParamBuffer::new(param("swing")),
+        slide_time: ParamBuffer::new(param("slideTime")),
         length: ParamBuffer::new(param_number(params, "length", 16.0)),
         direction: ParamBuffer::new(param_number(params, "direction", 0.0)),
       })
     }
     ModuleType::Tb303 => ModuleState::Tb303(Tb303State {
       tb303: Tb303::new(sample_rate),
-      waveform: ParamBuffer::new(param_number(params, "waveform", 0.0)),
-      cutoff: ParamBuffer::new(param_number(params, "cutoff", 800.0)),
-      resonance: ParamBuffer::new(param_number(params, "resonance", 0.3)),
-      decay: ParamBuffer::new(param_number(params, "decay", 0.3)),
-      envmod: ParamBuffer::new(param_number(params, "envmod", 0.5)),
-      accent: ParamBuffer::new(param_number(params, "accent", 0.6)),
-      glide: ParamBuffer::new(param_number(params, "glide", 0.02)),
+      waveform: ParamBuffer::new(match params.get("waveform").and_then(|value| value.as_str()) {
+        Some(text) => tb303_waveform(text).unwrap_or(0.0),
+        None => param_number(params, "waveform", 0.0),
+      }),
+      cutoff: ParamBuffer::new(param("cutoff")),
+      resonance: ParamBuffer::new(param("resonance")),
+      decay: ParamBuffer::new(param("decay")),
+      envmod: ParamBuffer::new(param("envmod")),
+      accent: ParamBuffer::new(param("accent")),
+      glide: ParamBuffer::new(param("glide")),
     }),
     // TR-909 Drums
     ModuleType::Kick909 => ModuleState::Kick909(Kick909State {
       kick: Kick909::new(sample_rate),
-      tune: ParamBuffer::new(param_number(params, "tune", 55.0)),
-      attack: ParamBuffer::new(param_number(params, "attack", 0.5)),
-      decay: ParamBuffer::new(param_number(params, "decay", 0.5)),
-      drive: ParamBuffer::new(param_number(params, "drive", 0.3)),
+      tune: ParamBuffer::new(param("tune")),
+      attack: ParamBuffer::new(param("attack")),
+      decay: ParamBuffer::new(param("decay")),
+      drive: ParamBuffer::new(param("drive")),
     }),
     ModuleType::Snare909 => ModuleState::Snare909(Snare909State {
       snare: Snare909::new(sample_rate),
-      tune: ParamBuffer::new(param_number(params, "tune", 200.0)),
-      tone: ParamBuffer::new(param_number(params, "tone", 0.5)),
-      snappy: ParamBuffer::new(param_number(params, "snappy", 0.5)),
-      decay: ParamBuffer::new(param_number(params, "decay", 0.3)),
+      tune: ParamBuffer::new(param("tune")),
+      tone: ParamBuffer::new(param("tone")),
+      snappy: ParamBuffer::new(param("snappy")),
+      decay: ParamBuffer::new(param("decay")),
     }),
     ModuleType::HiHat909 => ModuleState::HiHat909(HiHat909State {
       hihat: HiHat909::new(sample_rate),
       tune: ParamBuffer::new(param_number(params, "tune", 1.0)),
       decay: ParamBuffer::new(param_number(params, "decay", 0.2)),
-      tone: ParamBuffer::new(param_number(params, "tone", 0.5)),
+      tone: ParamBuffer::new(param("tone")),
       open: ParamBuffer::new(param_number(params, "open", 0.0)),
     }),
     ModuleType::Clap909 => ModuleState::Clap909(Clap909State {
       clap: Clap909::new(sample_rate),
-      tone: ParamBuffer::new(param_number(params, "tone", 0.5)),
-      decay: ParamBuffer::new(param_number(params, "decay", 0.4)),
+      tone: ParamBuffer::new(param("tone")),
+      decay: ParamBuffer::new(param("decay")),
     }),
     ModuleType::Tom909 => ModuleState::Tom909(Tom909State {
       tom: Tom909::new(sample_rate),
-      tune: ParamBuffer::new(param_number(params, "tune", 120.0)),
-      decay: ParamBuffer::new(param_number(params, "decay", 0.4)),
+      tune: ParamBuffer::new(param("tune")),
+      decay: ParamBuffer::new(param("decay")),
     }),
     ModuleType::Rimshot909 => ModuleState::Rimshot909(Rimshot909State {
       rimshot: Rimshot909::new(sample_rate),
-      tune: ParamBuffer::new(param_number(params, "tune", 400.0)),
+      tune: ParamBuffer::new(param("tune")),
     }),
     // TR-808 Drums
     ModuleType::Kick808 => ModuleState::Kick808(Kick808State {
       kick: Kick808::new(sample_rate),
-      tune: ParamBuffer::new(param_number(params, "tune", 45.0)),
-      decay: ParamBuffer::new(param_number(params, "decay", 1.5)),
-      tone: ParamBuffer::new(param_number(params, "tone", 0.3)),
-      click: ParamBuffer::new(param_number(params, "click", 0.2)),
+      tune: ParamBuffer::new(param("tune")),
+      decay: ParamBuffer::new(param("decay")),
+      tone: ParamBuffer::new(param("tone")),
+      click: ParamBuffer::new(param("click")),
     }),
     ModuleType::Snare808 => ModuleState::Snare808(Snare808State {
       snare: Snare808::new(sample_rate),
-      tune: ParamBuffer::new(param_number(params, "tune", 180.0)),
-      tone: ParamBuffer::new(param_number(params, "tone", 0.5)),
-      snappy: ParamBuffer::new(param_number(params, "snappy", 0.6)),
-      decay: ParamBuffer::new(param_number(params, "decay", 0.3)),
+      tune: ParamBuffer::new(param("tune")),
+      tone: ParamBuffer::new(param("tone")),
+      snappy: ParamBuffer::new(param("snappy")),
+      decay: ParamBuffer::new(param("decay")),
     }),
     ModuleType::HiHat808 => ModuleState::HiHat808(HiHat808State {
       hihat: HiHat808::new(sample_rate),
-      tune: ParamBuffer::new(param_number(params, "tune", 1.0)),
-      decay: ParamBuffer::new(param_number(params, "decay", 0.15)),
-      tone: ParamBuffer::new(param_number(params, "tone", 0.6)),
-      snap: ParamBuffer::new(param_number(params, "snap", 0.5)),
+      tune: ParamBuffer::new(param("tune")),
+      decay: ParamBuffer::new(param("decay")),
+      tone: ParamBuffer::new(param("tone")),
+      snap: ParamBuffer::new(param("snap")),
     }),
     ModuleType::Cowbell808 => ModuleState::Cowbell808(Cowbell808State {
       cowbell: Cowbell808::new(sample_rate),
-      tune: ParamBuffer::new(param_number(params, "tune", 1.0)),
-      decay: ParamBuffer::new(param_number(params, "decay", 0.1)),
-      tone: ParamBuffer::new(param_number(params, "tone", 0.6)),
+      tune: ParamBuffer::new(param("tune")),
+      decay: ParamBuffer::new(param("decay")),
+      tone: ParamBuffer::new(param("tone")),
     }),
     ModuleType::Clap808 => ModuleState::Clap808(Clap808State {
       clap: Clap808::new(sample_rate),
-      tone: ParamBuffer::new(param_number(params, "tone", 0.5)),
-      decay: ParamBuffer::new(param_number(params, "decay", 0.3)),
-      spread: ParamBuffer::new(param_number(params, "spread", 0.5)),
+      tone: ParamBuffer::new(param("tone")),
+      decay: ParamBuffer::new(param("decay")),
+      spread: ParamBuffer::new(param("spread")),
     }),
     ModuleType::Tom808 => ModuleState::Tom808(Tom808State {
       tom: Tom808::new(sample_rate),
-      tune: ParamBuffer::new(param_number(params, "tune", 150.0)),
-      decay: ParamBuffer::new(param_number(params, "decay", 0.3)),
-      pitch: ParamBuffer::new(param_number(params, "pitch", 0.5)),
-      tone: ParamBuffer::new(param_number(params, "tone", 0.4)),
+      tune: ParamBuffer::new(param("tune")),
+      decay: ParamBuffer::new(param("decay")),
+      pitch: ParamBuffer::new(param("pitch")),
+      tone: ParamBuffer::new(param("tone")),
     }),
     ModuleType::DrumSequencer => {
       let mut seq = DrumSequencer::new(sample_rate);
@@ -436,7 +629,7 @@ pub(crate) fn create_state(
         tempo: ParamBuffer::new(param_number(params, "tempo", 120.0)),
         rate: ParamBuffer::new(param_number(params, "rate", 4.0)),
         gate_length: ParamBuffer::new(param_number(params, "gateLength", 50.0)),
-        swing: ParamBuffer::new(param_number(params, "swing", 0.0)),
+        swing: ParamBuffer::new(param("swing")),
         length: ParamBuffer::new(param_number(params, "length", 16.0)),
       })
     }
@@ -447,8 +640,10 @@ pub(crate) fn create_state(
       seq.set_voice_count(voice_count);
       // Set voice index for this instance
       seq.set_voice_index(voice_index.unwrap_or(0));
-      // Parse initial MIDI data if provided
-      if let Some(midi_data) = params.get("midiData") {
+      // Parse initial MIDI data if provided, the embedded file first
+      if let Some(file) = params.get("midiFile").and_then(|value| value.as_str()).and_then(decode_midi_file) {
+        seq.load_file(&file);
+      } else if let Some(midi_data) = params.get("midiData") {
         if let Some(s) = midi_data.as_str() {
           seq.parse_midi_data(s);
         }
@@ -457,8 +652,9 @@ pub(crate) fn create_state(
         seq,
         voice_index: voice_index.unwrap_or(0),
         enabled: ParamBuffer::new(param_number(params, "enabled", 1.0)),
-        tempo: ParamBuffer::new(param_number(params, "tempo", 120.0)),
-        gate_length: ParamBuffer::new(param_number(params, "gateLength", 90.0)),
+        tempo: ParamBuffer::new(param("tempo")),
+        tempo_scale: ParamBuffer::new(param("tempoScale")),
+        gate_length: ParamBuffer::new(param("gateLength")),
         loop_enabled: ParamBuffer::new(param_number(params, "loop", 1.0)),
         mute1: ParamBuffer::new(param_number(params, "mute1", 0.0)),
         mute2: ParamBuffer::new(param_number(params, "mute2", 0.0)),
@@ -472,202 +668,189 @@ pub(crate) fn create_state(
     }
     ModuleType::PitchShifter => ModuleState::PitchShifter(PitchShifterState {
       shifter: PitchShifter::new(sample_rate),
-      pitch: ParamBuffer::new(param_number(params, "pitch", 0.0)),
-      fine: ParamBuffer::new(param_number(params, "fine", 0.0)),
-      grain: ParamBuffer::new(param_number(params, "grain", 50.0)),
-      mix: ParamBuffer::new(param_number(params, "mix", 1.0)),
+      pitch: ParamBuffer::new(param("pitch")),
+      fine: ParamBuffer::new(param("fine")),
+      grain: ParamBuffer::new(param("grain")),
+      mix: ParamBuffer::new(param("mix")),
     }),
     ModuleType::Clock => ModuleState::Clock(ClockState {
       clock: MasterClock::new(sample_rate),
       running: ParamBuffer::new(param_number(params, "running", 1.0)),
-      tempo: ParamBuffer::new(param_number(params, "tempo", 120.0)),
+      tempo: ParamBuffer::new(param("tempo")),
       rate: ParamBuffer::new(param_number(params, "rate", 4.0)),
-      swing: ParamBuffer::new(param_number(params, "swing", 0.0)),
+      swing: ParamBuffer::new(param("swing")),
+    }),
+    ModuleType::ClockDiv => ModuleState::ClockDiv(ClockDivState {
+      divider: ClockDivider::new(sample_rate),
+      ratio: ParamBuffer::new(param("ratio")),
     }),
     ModuleType::Euclidean => ModuleState::Euclidean(EuclideanState {
       euclidean: EuclideanSequencer::new(sample_rate),
       enabled: ParamBuffer::new(param_number(params, "enabled", 1.0)),
-      tempo: ParamBuffer::new(param_number(params, "tempo", 120.0)),
+      tempo: ParamBuffer::new(param("tempo")),
       rate: ParamBuffer::new(param_number(params, "rate", 7.0)), // 1/16
-      steps: ParamBuffer::new(param_number(params, "steps", 16.0)),
+      steps: ParamBuffer::new(param("steps")),
       pulses: ParamBuffer::new(param_number(params, "pulses", 4.0)),
       rotation: ParamBuffer::new(param_number(params, "rotation", 0.0)),
-      gate_length: ParamBuffer::new(param_number(params, "gateLength", 50.0)),
-      swing: ParamBuffer::new(param_number(params, "swing", 0.0)),
+      gate_length: ParamBuffer::new(param("gateLength")),
+      swing: ParamBuffer::new(param("swing")),
     }),
     ModuleType::FmOp => ModuleState::FmOp(FmOpState {
       op: FmOperator::new(sample_rate),
-      frequency: ParamBuffer::new(param_number(params, "frequency", 440.0)),
-      ratio: ParamBuffer::new(param_number(params, "ratio", 1.0)),
-      level: ParamBuffer::new(param_number(params, "level", 1.0)),
-      feedback: ParamBuffer::new(param_number(params, "feedback", 0.0)),
-      attack: ParamBuffer::new(param_number(params, "attack", 10.0)),
-      decay: ParamBuffer::new(param_number(params, "decay", 200.0)),
-      sustain: ParamBuffer::new(param_number(params, "sustain", 0.7)),
-      release: ParamBuffer::new(param_number(params, "release", 300.0)),
-    }),
-    ModuleType::FmMatrix => ModuleState::FmMatrix(FmMatrixState {
-      matrix: FmMatrix::new(sample_rate),
-      algorithm: ParamBuffer::new(param_number(params, "algorithm", 0.0)),
-      feedback: ParamBuffer::new(param_number(params, "feedback", 0.5)),
-      brightness: ParamBuffer::new(param_number(params, "brightness", 0.7)),
-      master: ParamBuffer::new(param_number(params, "master", 0.8)),
-      // Operator 1
-      op1_ratio: ParamBuffer::new(param_number(params, "op1_ratio", 1.0)),
-      op1_level: ParamBuffer::new(param_number(params, "op1_level", 1.0)),
-      op1_detune: ParamBuffer::new(param_number(params, "op1_detune", 0.0)),
-      op1_attack: ParamBuffer::new(param_number(params, "op1_attack", 10.0)),
-      op1_decay: ParamBuffer::new(param_number(params, "op1_decay", 300.0)),
-      op1_sustain: ParamBuffer::new(param_number(params, "op1_sustain", 0.7)),
-      op1_release: ParamBuffer::new(param_number(params, "op1_release", 500.0)),
-      // Operator 2
-      op2_ratio: ParamBuffer::new(param_number(params, "op2_ratio", 2.0)),
-      op2_level: ParamBuffer::new(param_number(params, "op2_level", 0.5)),
-      op2_detune: ParamBuffer::new(param_number(params, "op2_detune", 0.0)),
-      op2_attack: ParamBuffer::new(param_number(params, "op2_attack", 10.0)),
-      op2_decay: ParamBuffer::new(param_number(params, "op2_decay", 200.0)),
-      op2_sustain: ParamBuffer::new(param_number(params, "op2_sustain", 0.3)),
-      op2_release: ParamBuffer::new(param_number(params, "op2_release", 300.0)),
-      // Operator 3
-      op3_ratio: ParamBuffer::new(param_number(params, "op3_ratio", 3.0)),
-      op3_level: ParamBuffer::new(param_number(params, "op3_level", 0.3)),
-      op3_detune: ParamBuffer::new(param_number(params, "op3_detune", 0.0)),
-      op3_attack: ParamBuffer::new(param_number(params, "op3_attack", 10.0)),
-      op3_decay: ParamBuffer::new(param_number(params, "op3_decay", 150.0)),
-      op3_sustain: ParamBuffer::new(param_number(params, "op3_sustain", 0.2)),
-      op3_release: ParamBuffer::new(param_number(params, "op3_release", 200.0)),
-      // Operator 4
-      op4_ratio: ParamBuffer::new(param_number(params, "op4_ratio", 4.0)),
-      op4_level: ParamBuffer::new(param_number(params, "op4_level", 0.2)),
-      op4_detune: ParamBuffer::new(param_number(params, "op4_detune", 0.0)),
-      op4_attack: ParamBuffer::new(param_number(params, "op4_attack", 10.0)),
-      op4_decay: ParamBuffer::new(param_number(params, "op4_decay", 100.0)),
-      op4_sustain: ParamBuffer::new(param_number(params, "op4_sustain", 0.1)),
-      op4_release: ParamBuffer::new(param_number(params, "op4_release", 150.0)),
-    }),
+      frequency: ParamBuffer::new(param("frequency")),
+      ratio: ParamBuffer::new(param("ratio")),
+      level: ParamBuffer::new(param("level")),
+      feedback: ParamBuffer::new(param("feedback")),
+      attack: ParamBuffer::new(param("attack")),
+      decay: ParamBuffer::new(param("decay")),
+      sustain: ParamBuffer::new(param("sustain")),
+      release: ParamBuffer::new(param("release")),
+    }),
+    ModuleType::FmMatrix => ModuleState::FmMatrix(create_fm_matrix_state(params, sample_rate)),
     ModuleType::Shepard => ModuleState::Shepard(ShepardState {
       shepard: Shepard::new(sample_rate),
-      voices: ParamBuffer::new(param_number(params, "voices", 8.0)),
-      rate: ParamBuffer::new(param_number(params, "rate", 0.1)),
-      base_freq: ParamBuffer::new(param_number(params, "baseFreq", 220.0)),
-      spread: ParamBuffer::new(param_number(params, "spread", 1.0)),
-      mix: ParamBuffer::new(param_number(params, "mix", 1.0)),
-      waveform: ParamBuffer::new(param_number(params, "waveform", 0.0)),
-      stereo: ParamBuffer::new(param_number(params, "stereo", 0.5)),
-      detune: ParamBuffer::new(param_number(params, "detune", 0.0)),
-      direction: ParamBuffer::new(param_number(params, "direction", 0.0)),
+      voices: ParamBuffer::new(param("voices")),
+      rate: ParamBuffer::new(param("rate")),
+      base_freq: ParamBuffer::new(param("baseFreq")),
+      spread: ParamBuffer::new(param("spread")),
+      mix: ParamBuffer::new(param("mix")),
+      waveform: ParamBuffer::new(param("waveform")),
+      stereo: ParamBuffer::new(param("stereo")),
+      detune: ParamBuffer::new(param("detune")),
+      direction: ParamBuffer::new(param("direction")),
       risset: ParamBuffer::new(param_number(params, "risset", 0.0)),
-      phase_spread: ParamBuffer::new(param_number(params, "phaseSpread", 0.0)),
-      interval: ParamBuffer::new(param_number(params, "interval", 0.0)),
-      tilt: ParamBuffer::new(param_number(params, "tilt", 0.0)),
-      feedback: ParamBuffer::new(param_number(params, "feedback", 0.0)),
-      vibrato: ParamBuffer::new(param_number(params, "vibrato", 0.0)),
-      shimmer: ParamBuffer::new(param_number(params, "shimmer", 0.0)),
+      phase_spread: ParamBuffer::new(param("phaseSpread")),
+      interval: ParamBuffer::new(param("interval")),
+      tilt: ParamBuffer::new(param("tilt")),
+      feedback: ParamBuffer::new(param("feedback")),
+      vibrato: ParamBuffer::new(param("vibrato")),
+      shimmer: ParamBuffer::new(param("shimmer")),
     }),
     ModuleType::PipeOrgan => ModuleState::PipeOrgan(PipeOrganState {
       organ: PipeOrgan::new(sample_rate),
-      frequency: ParamBuffer::new(param_number(params, "frequency", 220.0)),
-      drawbar_16: ParamBuffer::new(param_number(params, "drawbar16", 0.5)),
-      drawbar_8: ParamBuffer::new(param_number(params, "drawbar8", 0.8)),
-      drawbar_4: ParamBuffer::new(param_number(params, "drawbar4", 0.6)),
-      drawbar_223: ParamBuffer::new(param_number(params, "drawbar223", 0.0)),
-      drawbar_2: ParamBuffer::new(param_number(params, "drawbar2", 0.4)),
-      drawbar_135: ParamBuffer::new(param_number(params, "drawbar135", 0.0)),
-      drawbar_113: ParamBuffer::new(param_number(params, "drawbar113", 0.0)),
-      drawbar_1: ParamBuffer::new(param_number(params, "drawbar1", 0.2)),
-      voicing: ParamBuffer::new(param_number(params, "voicing", 0.0)),
-      chiff: ParamBuffer::new(param_number(params, "chiff", 0.3)),
-      tremulant: ParamBuffer::new(param_number(params, "tremulant", 0.0)),
-      trem_rate: ParamBuffer::new(param_number(params, "tremRate", 6.0)),
-      wind: ParamBuffer::new(param_number(params, "wind", 0.1)),
-      brightness: ParamBuffer::new(param_number(params, "brightness", 0.7)),
+      frequency: ParamBuffer::new(param("frequency")),
+      drawbar_16: ParamBuffer::new(param("drawbar16")),
+      drawbar_8: ParamBuffer::new(param("drawbar8")),
+      drawbar_4: ParamBuffer::new(param("drawbar4")),
+      drawbar_223: ParamBuffer::new(param("drawbar223")),
+      drawbar_2: ParamBuffer::new(param("drawbar2")),
+      drawbar_135: ParamBuffer::new(param("drawbar135")),
+      drawbar_113: ParamBuffer::new(param("drawbar113")),
+      drawbar_1: ParamBuffer::new(param("drawbar1")),
+      voicing: ParamBuffer::new(param("voicing")),
+      chiff: ParamBuffer::new(param("chiff")),
+      tremulant: ParamBuffer::new(param("tremulant")),
+      trem_rate: ParamBuffer::new(param("tremRate")),
+      wind: ParamBuffer::new(param("wind")),
+      brightness: ParamBuffer::new(param("brightness")),
     }),
     ModuleType::SpectralSwarm => ModuleState::SpectralSwarm(SpectralSwarmState {
       swarm: SpectralSwarm::new(sample_rate),
-      frequency: ParamBuffer::new(param_number(params, "frequency", 110.0)),
-      partials: ParamBuffer::new(param_number(params, "partials", 16.0)),
-      detune: ParamBuffer::new(param_number(params, "detune", 15.0)),
-      drift: ParamBuffer::new(param_number(params, "drift", 0.3)),
-      density: ParamBuffer::new(param_number(params, "density", 0.8)),
-      evolution: ParamBuffer::new(param_number(params, "evolution", 4.0)),
-      inharmonic: ParamBuffer::new(param_number(params, "inharmonic", 0.0)),
-      tilt: ParamBuffer::new(param_number(params, "tilt", -3.0)),
-      spread: ParamBuffer::new(param_number(params, "spread", 0.7)),
-      shimmer: ParamBuffer::new(param_number(params, "shimmer", 0.0)),
-      attack: ParamBuffer::new(param_number(params, "attack", 2.0)),
-      release: ParamBuffer::new(param_number(params, "release", 3.0)),
+      frequency: ParamBuffer::new(param("frequency")),
+      partials: ParamBuffer::new(param("partials")),
+      detune: ParamBuffer::new(param("detune")),
+      drift: ParamBuffer::new(param("drift")),
+      density: ParamBuffer::new(param("density")),
+      evolution: ParamBuffer::new(param("evolution")),
+      inharmonic: ParamBuffer::new(param("inharmonic")),
+      tilt: ParamBuffer::new(param("tilt")),
+      spread: ParamBuffer::new(param("spread")),
+      shimmer: ParamBuffer::new(param("shimmer")),
+      attack: ParamBuffer::new(param("attack")),
+      release: ParamBuffer::new(param("release")),
       // New parameters
-      waveform: ParamBuffer::new(param_number(params, "waveform", 0.0)),
-      odd_even: ParamBuffer::new(param_number(params, "oddEven", 0.0)),
-      fundamental_mix: ParamBuffer::new(param_number(params, "fundamentalMix", 0.5)),
-      formant_freq: ParamBuffer::new(param_number(params, "formantFreq", 0.0)),
-      formant_q: ParamBuffer::new(param_number(params, "formantQ", 2.0)),
+      waveform: ParamBuffer::new(param("waveform")),
+      odd_even: ParamBuffer::new(param("oddEven")),
+      fundamental_mix: ParamBuffer::new(param("fundamentalMix")),
+      formant_freq: ParamBuffer::new(param("formantFreq")),
+      formant_q: ParamBuffer::new(param("formantQ")),
       freeze: ParamBuffer::new(param_number(params, "freeze", 0.0)),
-      chorus: ParamBuffer::new(param_number(params, "chorus", 0.0)),
-      attack_low: ParamBuffer::new(param_number(params, "attackLow", 1.0)),
-      attack_high: ParamBuffer::new(param_number(params, "attackHigh", 1.0)),
-      release_low: ParamBuffer::new(param_number(params, "releaseLow", 1.0)),
-      release_high: ParamBuffer::new(param_number(params, "releaseHigh", 1.0)),
+      chorus: ParamBuffer::new(param("chorus")),
+      attack_low: ParamBuffer::new(param("attackLow")),
+      attack_high: ParamBuffer::new(param("attackHigh")),
+      release_low: ParamBuffer::new(param("releaseLow")),
+      release_high: ParamBuffer::new(param("releaseHigh")),
     }),
     ModuleType::Resonator => ModuleState::Resonator(ResonatorState {
       resonator: Resonator::new(sample_rate),
-      frequency: ParamBuffer::new(param_number(params, "frequency", 220.0)),
-      structure: ParamBuffer::new(param_number(params, "structure", 0.5)),
-      brightness: ParamBuffer::new(param_number(params, "brightness", 0.7)),
-      damping: ParamBuffer::new(param_number(params, "damping", 0.7)),
-      position: ParamBuffer::new(param_number(params, "position", 0.5)),
-      mode: ParamBuffer::new(param_number(params, "mode", 0.0)),
-      polyphony: ParamBuffer::new(param_number(params, "polyphony", 1.0)),
-      internal_exc: ParamBuffer::new(param_number(params, "internalExc", 0.8)),
-      chorus: ParamBuffer::new(param_number(params, "chorus", 0.0)),
+      frequency: ParamBuffer::new(param("frequency")),
+      structure: ParamBuffer::new(param("structure")),
+      brightness: ParamBuffer::new(param("brightness")),
+      damping: ParamBuffer::new(param("damping")),
+      position: ParamBuffer::new(param("position")),
+      mode: ParamBuffer::new(param("mode")),
+      polyphony: ParamBuffer::new(param("polyphony")),
+      internal_exc: ParamBuffer::new(param("internalExc")),
+      chorus: ParamBuffer::new(param("chorus")),
     }),
     ModuleType::Wavetable => ModuleState::Wavetable(WavetableState {
       wavetable: Wavetable::new(sample_rate),
-      frequency: ParamBuffer::new(param_number(params, "frequency", 220.0)),
-      bank: ParamBuffer::new(param_number(params, "bank", 0.0)),
-      position: ParamBuffer::new(param_number(params, "position", 0.0)),
-      unison: ParamBuffer::new(param_number(params, "unison", 1.0)),
-      detune: ParamBuffer::new(param_number(params, "detune", 15.0)),
-      spread: ParamBuffer::new(param_number(params, "spread", 0.5)),
-      morph_speed: ParamBuffer::new(param_number(params, "morphSpeed", 0.0)),
-      sub_mix: ParamBuffer::new(param_number(params, "subMix", 0.0)),
-      attack: ParamBuffer::new(param_number(params, "attack", 0.01)),
-      release: ParamBuffer::new(param_number(params, "release", 0.3)),
+      frequency: ParamBuffer::new(param("frequency")),
+      bank: ParamBuffer::new(param("bank")),
+      position: ParamBuffer::new(param("position")),
+      unison: ParamBuffer::new(param("unison")),
+      detune: ParamBuffer::new(param("detune")),
+      spread: ParamBuffer::new(param("spread")),
+      morph_speed: ParamBuffer::new(param("morphSpeed")),
+      sub_mix: ParamBuffer::new(param("subMix")),
+      attack: ParamBuffer::new(param("attack")),
+      release: ParamBuffer::new(param("release")),
     }),
     ModuleType::Granular => ModuleState::Granular(GranularState {
       granular: Granular::new(sample_rate),
-      position: ParamBuffer::new(param_number(params, "position", 0.5)),
-      size: ParamBuffer::new(param_number(params, "size", 100.0)),
-      density: ParamBuffer::new(param_number(params, "density", 8.0)),
-      pitch: ParamBuffer::new(param_number(params, "pitch", 1.0)),
-      spray: ParamBuffer::new(param_number(params, "spray", 0.1)),
-      scatter: ParamBuffer::new(param_number(params, "scatter", 0.0)),
-      pan_spread: ParamBuffer::new(param_number(params, "panSpread", 0.5)),
-      shape: ParamBuffer::new(param_number(params, "shape", 1.0)),
-      level: ParamBuffer::new(param_number(params, "level", 0.8)),
+      position: ParamBuffer::new(param("position")),
+      size: ParamBuffer::new(param("size")),
+      density: ParamBuffer::new(param("density")),
+      pitch: ParamBuffer::new(param("pitch")),
+      spray: ParamBuffer::new(param("spray")),
+      scatter: ParamBuffer::new(param("scatter")),
+      pan_spread: ParamBuffer::new(param("panSpread")),
+      shape: ParamBuffer::new(param("shape")),
+      level: ParamBuffer::new(param("level")),
     }),
     ModuleType::ParticleCloud => ModuleState::ParticleCloud(ParticleCloudState {
       cloud: ParticleCloud::new(sample_rate),
-      count: ParamBuffer::new(param_number(params, "count", 16.0)),
-      gravity: ParamBuffer::new(param_number(params, "gravity", 0.0)),
-      turbulence: ParamBuffer::new(param_number(params, "turbulence", 0.3)),
-      friction: ParamBuffer::new(param_number(params, "friction", 0.1)),
-      grain_size: ParamBuffer::new(param_number(params, "grainSize", 100.0)),
-      pitch: ParamBuffer::new(param_number(params, "pitch", 1.0)),
-      spread: ParamBuffer::new(param_number(params, "spread", 0.8)),
-      level: ParamBuffer::new(param_number(params, "level", 0.8)),
-      mode: ParamBuffer::new(param_number(params, "mode", 0.0)),
-      osc_shape: ParamBuffer::new(param_number(params, "oscShape", 0.0)),
+      count: ParamBuffer::new(param("count")),
+      gravity: ParamBuffer::new(param("gravity")),
+      turbulence: ParamBuffer::new(param("turbulence")),
+      friction: ParamBuffer::new(param("friction")),
+      grain_size: ParamBuffer::new(param("grainSize")),
+      pitch: ParamBuffer::new(param("pitch")),
+      spread: ParamBuffer::new(param("spread")),
+      level: ParamBuffer::new(param("level")),
+      mode: ParamBuffer::new(param("mode")),
+      osc_shape: ParamBuffer::new(param("oscShape")),
+    }),
+    ModuleType::SamplePlayer => ModuleState::SamplePlayer(SamplePlayerState {
+      player: SamplePlayer::new(sample_rate),
+      looping: ParamBuffer::new(param("loop")),
+      start: ParamBuffer::new(param("start")),
+      end: ParamBuffer::new(param("end")),
+      gain: ParamBuffer::new(param("gain")),
     }),
     ModuleType::Notes => ModuleState::Notes,  // UI-only, no DSP
-    ModuleType::TuringMachine => ModuleState::TuringMachine(TuringState {
-      turing: TuringMachine::new(sample_rate),
-      probability: ParamBuffer::new(param_number(params, "probability", 0.5)),
-      length: ParamBuffer::new(param_number(params, "length", 8.0)),
-      range: ParamBuffer::new(param_number(params, "range", 2.0)),
-      scale: ParamBuffer::new(param_number(params, "scale", 0.0)),
-      root: ParamBuffer::new(param_number(params, "root", 0.0)),
+    ModuleType::Send => ModuleState::Send(SendState {
+      level: ParamBuffer::new(param("level")),
+    }),
+    ModuleType::Return => ModuleState::Return(ReturnState {
+      level: ParamBuffer::new(param_number(params, "level", 1.0)),
     }),
+    ModuleType::TuringMachine => {
+      let seed = match params.get("seed") {
+        Some(_) => param_number(params, "seed", 0.0) as u32,
+        None => TuringMachine::DEFAULT_SEED,
+      };
+      ModuleState::TuringMachine(TuringState {
+        turing: TuringMachine::new_with_seed(sample_rate, seed),
+        probability: ParamBuffer::new(param("probability")),
+        length: ParamBuffer::new(param("length")),
+        range: ParamBuffer::new(param("range")),
+        scale: ParamBuffer::new(param("scale")),
+        root: ParamBuffer::new(param("root")),
+        write: ParamBuffer::new(param("write")),
+        seed: ParamBuffer::new(seed as f32),
+      })
+    }
     ModuleType::SidPlayer => ModuleState::SidPlayer(SidPlayerState {
       sid_player: SidPlayer::new(sample_rate),
       playing: ParamBuffer::new(param_number(params, "playing", 0.0)),
@@ -681,16 +864,172 @@ pub(crate) fn create_state(
     }),
     ModuleType::Compressor => ModuleState::Compressor(CompressorState {
       compressor: Compressor::new(sample_rate),
-      threshold: ParamBuffer::new(param_number(params, "threshold", -20.0)),
-      ratio: ParamBuffer::new(param_number(params, "ratio", 4.0)),
-      attack: ParamBuffer::new(param_number(params, "attack", 10.0)),
-      release: ParamBuffer::new(param_number(params, "release", 100.0)),
-      makeup: ParamBuffer::new(param_number(params, "makeup", 0.0)),
-      mix: ParamBuffer::new(param_number(params, "mix", 1.0)),
+      threshold: ParamBuffer::new(param("threshold")),
+      ratio: ParamBuffer::new(param("ratio")),
+      attack: ParamBuffer::new(param("attack")),
+      release: ParamBuffer::new(param("release")),
+      knee: ParamBuffer::new(param("knee")),
+      makeup: ParamBuffer::new(param("makeup")),
+      mix: ParamBuffer::new(param("mix")),
+      detector: ParamBuffer::new(param("detector")),
+      sidechain: ParamBuffer::new(param("sidechain")),
+    }),
+    ModuleType::Gate => ModuleState::Gate(GateState {
+      gate: NoiseGate::new(sample_rate),
+      threshold: ParamBuffer::new(param("threshold")),
+      attack: ParamBuffer::new(param("attack")),
+      hold: ParamBuffer::new(param("hold")),
+      release: ParamBuffer::new(param("release")),
+      floor: ParamBuffer::new(param("floor")),
     }),
   }
 }
 
+/// FM Matrix operator fields, in `FmMatrixOpState` order.
+const FM_OP_FIELDS: [&str; 7] = ["ratio", "level", "detune", "attack", "decay", "sustain", "release"];
+
+/// FM Matrix operator defaults, in `FM_OP_FIELDS` order.
+const FM_OP_DEFAULTS: [[f32; 7]; 4] = [
+  [1.0, 1.0, 0.0, 10.0, 300.0, 0.7, 500.0],
+  [2.0, 0.5, 0.0, 10.0, 200.0, 0.3, 300.0],
+  [3.0, 0.3, 0.0, 10.0, 150.0, 0.2, 200.0],
+  [4.0, 0.2, 0.0, 10.0, 100.0, 0.1, 150.0],
+];
+
+/// Address of an FM Matrix param (all indices zero-based).
+#[derive(Clone, Copy, PartialEq)]
+enum FmMatrixKey {
+  Algorithm,
+  Feedback,
+  Brightness,
+  Master,
+  Op(usize, usize),
+  Route(usize, usize),
+}
+
+/// Parse an FM Matrix param name: the globals, `op<N><Field>` (also the
+/// older `op<N>_<field>` spelling) and `matrix<Src><Dst>`, with N in 1-4.
+fn fm_matrix_key(param: &str) -> Option<FmMatrixKey> {
+  let op_index = |digit: u8| (b'1'..=b'4').contains(&digit).then(|| (digit - b'1') as usize);
+  if let Some(rest) = param.strip_prefix("op") {
+    let (&digit, field) = rest.as_bytes().split_first()?;
+    let field = std::str::from_utf8(field).ok()?;
+    let field = field.strip_prefix('_').unwrap_or(field);
+    let field = FM_OP_FIELDS.iter().position(|name| name.eq_ignore_ascii_case(field))?;
+    return Some(FmMatrixKey::Op(op_index(digit)?, field));
+  }
+  if let Some(rest) = param.strip_prefix("matrix") {
+    let &[src, dst] = rest.as_bytes() else {
+      return None;
+    };
+    return Some(FmMatrixKey::Route(op_index(src)?, op_index(dst)?));
+  }
+  match param {
+    "algorithm" => Some(FmMatrixKey::Algorithm),
+    "feedback" => Some(FmMatrixKey::Feedback),
+    "brightness" => Some(FmMatrixKey::Brightness),
+    "master" => Some(FmMatrixKey::Master),
+    _ => None,
+  }
+}
+
+fn fm_op_field_mut(op: &mut FmMatrixOpState, field: usize) -> &mut ParamBuffer {
+  match field {
+    0 => &mut op.ratio,
+    1 => &mut op.level,
+    2 => &mut op.detune,
+    3 => &mut op.attack,
+    4 => &mut op.decay,
+    5 => &mut op.sustain,
+    _ => &mut op.release,
+  }
+}
+
+fn fm_matrix_param_mut(state: &mut FmMatrixState, key: FmMatrixKey) -> &mut ParamBuffer {
+  match key {
+    FmMatrixKey::Algorithm => &mut state.algorithm,
+    FmMatrixKey::Feedback => &mut state.feedback,
+    FmMatrixKey::Brightness => &mut state.brightness,
+    FmMatrixKey::Master => &mut state.master,
+    FmMatrixKey::Op(op, field) => fm_op_field_mut(&mut state.ops[op], field),
+    FmMatrixKey::Route(src, dst) => &mut state.routes[src][dst],
+  }
+}
+
+/// Build an FM Matrix from its params. Besides the flat names accepted by
+/// `apply_param`, a graph may carry the nested form
+/// `"operators": [{ "ratio": 1, "attack": 10, ... }, ...]` and
+/// `"matrix": [[...4], ...4]` (rows are sources); flat params win.
+fn create_fm_matrix_state(params: &HashMap<String, serde_json::Value>, sample_rate: f32) -> FmMatrixState {
+  let default = |id| param_meta(ModuleType::FmMatrix, id).map_or(0.0, |meta| meta.default);
+  let mut state = FmMatrixState {
+    matrix: FmMatrix::new(sample_rate),
+    algorithm: ParamBuffer::new(0.0),
+    feedback: ParamBuffer::new(default("feedback")),
+    brightness: ParamBuffer::new(default("brightness")),
+    master: ParamBuffer::new(default("master")),
+    ops: FM_OP_DEFAULTS.map(|[ratio, level, detune, attack, decay, sustain, release]| FmMatrixOpState {
+      ratio: ParamBuffer::new(ratio),
+      level: ParamBuffer::new(level),
+      detune: ParamBuffer::new(detune),
+      attack: ParamBuffer::new(attack),
+      decay: ParamBuffer::new(decay),
+      sustain: ParamBuffer::new(sustain),
+      release: ParamBuffer::new(release),
+    }),
+    routes: std::array::from_fn(|_| std::array::from_fn(|_| ParamBuffer::new(0.0))),
+  };
+
+  if let Some(operators) = params.get("operators").and_then(|value| value.as_array()) {
+    for (op, fields) in operators.iter().take(4).enumerate() {
+      for (field, name) in FM_OP_FIELDS.iter().enumerate() {
+        if let Some(value) = fields.get(name).and_then(|value| value.as_f64()) {
+          fm_op_field_mut(&mut state.ops[op], field).set(value as f32);
+        }
+      }
+    }
+  }
+  if let Some(rows) = params.get("matrix").and_then(|value| value.as_array()) {
+    for (src, row) in rows.iter().take(4).enumerate() {
+      for (dst, value) in row.as_array().into_iter().flatten().take(4).enumerate() {
+        if let Some(value) = value.as_f64() {
+          state.routes[src][dst].set(value as f32);
+        }
+      }
+    }
+  }
+  for (name, value) in params {
+    if let (Some(key), Some(value)) = (fm_matrix_key(name), value.as_f64()) {
+      let value = param_meta(ModuleType::FmMatrix, name).map_or(value as f32, |meta| meta.constrain(value as f32));
+      fm_matrix_param_mut(&mut state, key).set(value);
+    }
+  }
+  state
+}
+
+/// Stored value of an FM Matrix param: a flat name wins over the nested
+/// form, as in `create_fm_matrix_state`, and missing values are defaults.
+fn stored_fm_matrix_param(params: &HashMap<String, serde_json::Value>, key: FmMatrixKey) -> Option<f32> {
+  let flat = params
+    .iter()
+    .find(|(name, _)| fm_matrix_key(name) == Some(key))
+    .and_then(|(_, value)| value.as_f64());
+  let nested = match key {
+    FmMatrixKey::Op(op, field) => params.get("operators").and_then(|ops| ops.get(op)?.get(FM_OP_FIELDS[field])),
+    FmMatrixKey::Route(src, dst) => params.get("matrix").and_then(|rows| rows.get(src)?.get(dst)),
+    _ => None,
+  };
+  let default = match key {
+    FmMatrixKey::Algorithm | FmMatrixKey::Route(..) => 0.0,
+    FmMatrixKey::Feedback => param_meta(ModuleType::FmMatrix, "feedback")?.default,
+    FmMatrixKey::Brightness => param_meta(ModuleType::FmMatrix, "brightness")?.default,
+    FmMatrixKey::Master => param_meta(ModuleType::FmMatrix, "master")?.default,
+    FmMatrixKey::Op(op, field) => FM_OP_DEFAULTS[op][field],
+  };
+  let value = flat.or_else(|| nested?.as_f64()).map_or(default, |value| value as f32);
+  Some(value)
+}
+
 /// Apply a numeric parameter to a module state.
 pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
   match state {
@@ -704,11 +1043,19 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
       "detune" => state.detune.set(value),
       "subMix" => state.sub_mix.set(value),
       "subOct" => state.sub_oct.set(value),
+      "stereoSpread" => state.stereo_spread.set(value),
+      "waveMorph" => state.wave_morph.set(value),
+      "oversample" => state.oversample.set(value),
       _ => {}
     },
     ModuleState::Noise(state) => match param {
+      "seed" => {
+        state.seed.set(value);
+        state.noise.set_seed((value as u32).wrapping_add(state.voice_offset));
+      }
       "level" => state.level.set(value),
       "noiseType" => state.noise_type.set(value),
+      "rateDivide" => state.rate_divide.set(value),
       "stereo" => state.stereo.set(value),
       "pan" => state.pan.set(value),
       _ => {}
@@ -718,18 +1065,36 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
       "depthPwm" => state.depth_pwm.set(value),
       "depthVcf" => state.depth_vcf.set(value),
       "depthVca" => state.depth_vca.set(value),
+      "depthAux" => state.depth_aux.set(value),
+      "offsetPitch" => state.offset_pitch.set(value),
+      "offsetPwm" => state.offset_pwm.set(value),
+      "offsetVcf" => state.offset_vcf.set(value),
+      "offsetVca" => state.offset_vca.set(value),
+      "offsetAux" => state.offset_aux.set(value),
+      _ => {}
+    },
+    ModuleState::Attenuverter(state) => match param {
+      "scale" => state.scale.set(value),
+      "offset" => state.offset.set(value),
+      _ => {}
+    },
+    ModuleState::SampleHold(state) => match param {
+      "mode" => state.mode.set(value),
+      "glide" => state.glide.set(value),
+      "noiseLevel" => state.noise_level.set(value),
       _ => {}
     },
-    ModuleState::SampleHold(state) => {
-      if param == "mode" {
-        state.mode.set(value);
-      }
-    }
     ModuleState::Slew(state) => match param {
       "rise" => state.rise.set(value),
       "fall" => state.fall.set(value),
       _ => {}
     },
+    ModuleState::EnvFollower(state) => match param {
+      "attack" => state.attack.set(value),
+      "release" => state.release.set(value),
+      "gain" => state.gain.set(value),
+      _ => {}
+    },
     ModuleState::Quantizer(state) => match param {
       "root" => state.root.set(value),
       "scale" => state.scale.set(value),
@@ -744,21 +1109,32 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
       "root" => state.root.set(value),
       _ => {}
     },
-    ModuleState::RingMod(state) => {
-      if param == "level" {
-        state.level.set(value);
-      }
-    }
-    ModuleState::Gain(state) | ModuleState::CvVca(state) => {
-      if param == "gain" {
-        state.gain.set(value);
-      }
-    }
-    ModuleState::Output(state) => {
-      if param == "level" {
-        state.level.set(value);
-      }
-    }
+    ModuleState::Logic(state) if param == "threshold" => state.threshold.set(value),
+    ModuleState::PeakMeter(state) => match param {
+      "attack" => state.attack.set(value),
+      "release" => state.release.set(value),
+      "rmsWindow" | "rms-window" => state.rms_window.set(value),
+      _ => {}
+    },
+    ModuleState::RingMod(state) => match param {
+      "level" => state.level.set(value),
+      "carrierFreq" | "carrier-freq" => state.carrier_freq.set(value),
+      "carrierShape" | "carrier-shape" => state.carrier_shape.set(value),
+      "internalCarrier" | "internal-carrier" => state.internal_carrier.set(value),
+      "mix" => state.mix.set(value),
+      _ => {}
+    },
+    ModuleState::Gain(state) | ModuleState::CvVca(state) => match param {
+      "gain" => state.gain.set(value),
+      "invert" => state.invert.set(value),
+      _ => {}
+    },
+    ModuleState::Output(state) => match param {
+      "level" => state.level.set(value),
+      "bus" => state.bus = value.round().clamp(0.0, (MAX_OUTPUT_BUSES - 1) as f32) as usize,
+      "voiceSpread" => state.voice_spread = value.clamp(0.0, 1.0),
+      _ => {}
+    },
     ModuleState::Lab(state) => {
       if param == "level" {
         state.level.set(value);
@@ -796,6 +1172,7 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
         state.cutoff.set(value);
       }
     }
+    ModuleState::DcBlock(state) if param == "cutoff" => state.cutoff.set(value),
     ModuleState::Mixer(state) => match param {
       "levelA" => state.level_a.set(value),
       "levelB" => state.level_b.set(value),
@@ -823,6 +1200,13 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
     },
     ModuleState::Crossfader(state) => match param {
       "mix" => state.mix.set(value),
+      "curve" => state.equal_power = value >= 0.5,
+      _ => {}
+    },
+    ModuleState::Pan(state) if param == "pan" => state.pan.set(value),
+    ModuleState::Width(state) => match param {
+      "width" => state.amount.set(value),
+      "monoBelow" | "mono-below" => state.mono_below.set(value),
       _ => {}
     },
     ModuleState::Chorus(state) => match param {
@@ -832,6 +1216,7 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
       "mix" => state.mix.set(value),
       "feedback" => state.feedback.set(value),
       "spread" => state.spread.set(value),
+      "wetOnly" => state.wet_only.set(value),
       _ => {}
     },
     ModuleState::Ensemble(state) => match param {
@@ -840,6 +1225,7 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
       "delay" => state.delay.set(value),
       "mix" => state.mix.set(value),
       "spread" => state.spread.set(value),
+      "wetOnly" => state.wet_only.set(value),
       _ => {}
     },
     ModuleState::Choir(state) => match param {
@@ -874,6 +1260,7 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
       "mix" => state.mix.set(value),
       "tone" => state.tone.set(value),
       "pingPong" => state.ping_pong.set(value),
+      "wetOnly" => state.wet_only.set(value),
       _ => {}
     },
     ModuleState::GranularDelay(state) => match param {
@@ -900,6 +1287,8 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
       "tone" => state.tone.set(value),
       "mix" => state.mix.set(value),
       "drive" => state.drive.set(value),
+      "diffusion" => state.diffusion.set(value),
+      "spring-count" => state.spring_count.set(value),
       _ => {}
     },
     ModuleState::Reverb(state) => match param {
@@ -907,6 +1296,7 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
       "damp" => state.damp.set(value),
       "preDelay" => state.pre_delay.set(value),
       "mix" => state.mix.set(value),
+      "wetOnly" => state.wet_only.set(value),
       _ => {}
     },
     ModuleState::Phaser(state) => match param {
@@ -914,6 +1304,13 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
       "depth" => state.depth.set(value),
       "feedback" => state.feedback.set(value),
       "mix" => state.mix.set(value),
+      "stages" => {
+        state.stages.set(value);
+        state.phaser.set_stages(value.round().max(0.0) as usize);
+      }
+      "stereo" => state.stereo.set(value),
+      "center" => state.center.set(value),
+      "wetOnly" => state.wet_only.set(value),
       _ => {}
     },
     ModuleState::Distortion(state) => match param {
@@ -921,6 +1318,7 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
       "tone" => state.tone.set(value),
       "mix" => state.mix.set(value),
       "mode" => state.mode.set(value),
+      "bias" => state.bias.set(value),
       _ => {}
     },
     ModuleState::Wavefolder(state) => match param {
@@ -930,10 +1328,19 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
       "mix" => state.mix.set(value),
       _ => {}
     },
+    ModuleState::BitCrusher(state) => match param {
+      "bits" => state.bits.set(value),
+      "downsample" => state.downsample.set(value),
+      "mix" => state.mix.set(value),
+      "antialias" => state.antialias.set(value),
+      "dither" => state.dither.set(value),
+      _ => {}
+    },
     ModuleState::Supersaw(state) => match param {
       "frequency" => state.base_freq.set(value),
       "detune" => state.detune.set(value),
       "mix" => state.mix.set(value),
+      "phaseSpread" | "phase-spread" => state.phase_spread.set(value),
       _ => {}
     },
     ModuleState::Karplus(state) => match param {
@@ -969,18 +1376,13 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
         "glide" => {
           state.glide_seconds = value.max(0.0);
         }
-        "cv" => {
-          if state.glide_seconds > 0.0 {
-            let total = (state.glide_seconds * state.sample_rate).max(1.0);
-            state.cv_target = value;
-            state.cv_remaining = total as usize;
-            state.cv_step = (state.cv_target - state.cv) / total;
-          } else {
-            state.cv = value;
-            state.cv_target = value;
-            state.cv_remaining = 0;
-          }
+        "glideMode" => {
+          state.glide_legato = value >= 0.5;
+        }
+        "glideCurve" => {
+          state.glide_exponential = value >= 0.5;
         }
+        "cv" => state.set_cv(value),
         "velocity" => {
           let clamped = value.clamp(0.0, 1.0);
           state.velocity = clamped;
@@ -990,6 +1392,10 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
         "gate" => {
           state.gate = value;
         }
+        "voiceDetune" => state.voice_detune.set(value),
+        "voiceSpread" => state.voice_spread.set(value),
+        "refFreq" => state.tuning.ref_freq = value.max(1.0),
+        "edo" => state.tuning.edo = value.round().max(1.0),
         _ => {}
       }
     }
@@ -1030,8 +1436,8 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
       "cutoff" => state.cutoff.set(value),
       "resonance" => state.resonance.set(value),
       "decay" => state.decay.set(value),
-      "envmod" => state.envmod.set(value),
-      "accent" => state.accent.set(value),
+      "envmod" | "envMod" => state.envmod.set(value),
+      "accent" | "accentAmount" => state.accent.set(value),
       "glide" => state.glide.set(value),
       _ => {}
     },
@@ -1124,6 +1530,7 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
     ModuleState::MidiFileSequencer(state) => match param {
       "enabled" => state.enabled.set(value),
       "tempo" => state.tempo.set(value),
+      "tempoScale" | "tempo-scale" => state.tempo_scale.set(value),
       "gateLength" => state.gate_length.set(value),
       "loop" => state.loop_enabled.set(value),
       "mute1" => state.mute1.set(value),
@@ -1150,6 +1557,7 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
       "swing" => state.swing.set(value),
       _ => {}
     },
+    ModuleState::ClockDiv(state) if param == "ratio" => state.ratio.set(value),
     ModuleState::Euclidean(state) => match param {
       "enabled" => state.enabled.set(value),
       "tempo" => state.tempo.set(value),
@@ -1172,45 +1580,11 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
       "release" => state.release.set(value),
       _ => {}
     },
-    ModuleState::FmMatrix(state) => match param {
-      "algorithm" => state.algorithm.set(value),
-      "feedback" => state.feedback.set(value),
-      "brightness" => state.brightness.set(value),
-      "master" => state.master.set(value),
-      // Operator 1
-      "op1_ratio" => state.op1_ratio.set(value),
-      "op1_level" => state.op1_level.set(value),
-      "op1_detune" => state.op1_detune.set(value),
-      "op1_attack" => state.op1_attack.set(value),
-      "op1_decay" => state.op1_decay.set(value),
-      "op1_sustain" => state.op1_sustain.set(value),
-      "op1_release" => state.op1_release.set(value),
-      // Operator 2
-      "op2_ratio" => state.op2_ratio.set(value),
-      "op2_level" => state.op2_level.set(value),
-      "op2_detune" => state.op2_detune.set(value),
-      "op2_attack" => state.op2_attack.set(value),
-      "op2_decay" => state.op2_decay.set(value),
-      "op2_sustain" => state.op2_sustain.set(value),
-      "op2_release" => state.op2_release.set(value),
-      // Operator 3
-      "op3_ratio" => state.op3_ratio.set(value),
-      "op3_level" => state.op3_level.set(value),
-      "op3_detune" => state.op3_detune.set(value),
-      "op3_attack" => state.op3_attack.set(value),
-      "op3_decay" => state.op3_decay.set(value),
-      "op3_sustain" => state.op3_sustain.set(value),
-      "op3_release" => state.op3_release.set(value),
-      // Operator 4
-      "op4_ratio" => state.op4_ratio.set(value),
-      "op4_level" => state.op4_level.set(value),
-      "op4_detune" => state.op4_detune.set(value),
-      "op4_attack" => state.op4_attack.set(value),
-      "op4_decay" => state.op4_decay.set(value),
-      "op4_sustain" => state.op4_sustain.set(value),
-      "op4_release" => state.op4_release.set(value),
-      _ => {}
-    },
+    ModuleState::FmMatrix(state) => {
+      if let Some(key) = fm_matrix_key(param) {
+        fm_matrix_param_mut(state, key).set(value);
+      }
+    }
     ModuleState::Shepard(state) => match param {
       "voices" => state.voices.set(value),
       "rate" => state.rate.set(value),
@@ -1326,12 +1700,24 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
       "oscShape" => state.osc_shape.set(value),
       _ => {}
     },
+    ModuleState::SamplePlayer(state) => match param {
+      "loop" => state.looping.set(value),
+      "start" => state.start.set(value),
+      "end" => state.end.set(value),
+      "gain" => state.gain.set(value),
+      _ => {}
+    },
     ModuleState::TuringMachine(state) => match param {
       "probability" => state.probability.set(value),
       "length" => state.length.set(value),
       "range" => state.range.set(value),
       "scale" => state.scale.set(value),
       "root" => state.root.set(value),
+      "write" => state.write.set(value),
+      "seed" => {
+        state.seed.set(value);
+        state.turing.set_seed(value as u32);
+      }
       _ => {}
     },
     ModuleState::SidPlayer(state) => match param {
@@ -1350,15 +1736,77 @@ pub(crate) fn apply_param(state: &mut ModuleState, param: &str, value: f32) {
       "ratio" => state.ratio.set(value),
       "attack" => state.attack.set(value),
       "release" => state.release.set(value),
+      "knee" => state.knee.set(value),
       "makeup" => state.makeup.set(value),
       "mix" => state.mix.set(value),
+      "detector" => state.detector.set(value),
+      "sidechain" => state.sidechain.set(value),
       _ => {}
     },
+    ModuleState::Gate(state) => match param {
+      "threshold" => state.threshold.set(value),
+      "attack" => state.attack.set(value),
+      "hold" => state.hold.set(value),
+      "release" => state.release.set(value),
+      "floor" => state.floor.set(value),
+      _ => {}
+    },
+    ModuleState::Send(SendState { level }) | ModuleState::Return(ReturnState { level })
+      if param == "level" =>
+    {
+      level.set(value)
+    }
     _ => {}
   }
 }
 
-/// Apply a string parameter to a module state (for sequencer data).
+/// TB-303 waveform index (0 = saw, 1 = square). The generic `waveform`
+/// strings map to oscillator shape indices, which don't fit here.
+fn tb303_waveform(text: &str) -> Option<f32> {
+  match text {
+    "saw" | "sawtooth" => Some(0.0),
+    "square" => Some(1.0),
+    _ => None,
+  }
+}
+
+fn distortion_mode(text: &str) -> Option<f32> {
+  match text {
+    "soft" => Some(0.0),
+    "hard" => Some(1.0),
+    "fold" | "foldback" => Some(2.0),
+    "asymmetric" | "asym" => Some(3.0),
+    "tube" => Some(4.0),
+    _ => None,
+  }
+}
+
+/// Parse the `midiFile` param of a MidiFileSequencer: a whole `.mid` file
+/// in base64, several times smaller in a graph than the `midiData` note list.
+fn decode_midi_file(base64: &str) -> Option<SmfFile> {
+  let mut bytes = Vec::with_capacity(base64.len() / 4 * 3);
+  let (mut bits, mut count) = (0u32, 0);
+  for byte in base64.bytes().filter(|byte| !byte.is_ascii_whitespace() && *byte != b'=') {
+    let value = match byte {
+      b'A'..=b'Z' => byte - b'A',
+      b'a'..=b'z' => byte - b'a' + 26,
+      b'0'..=b'9' => byte - b'0' + 52,
+      b'+' => 62,
+      b'/' => 63,
+      _ => return None,
+    };
+    bits = (bits << 6) | value as u32;
+    count += 6;
+    if count >= 8 {
+      count -= 8;
+      bytes.push((bits >> count) as u8);
+    }
+  }
+  parse_smf(&bytes).ok()
+}
+
+/// Apply a string parameter to a module state (for sequencer data and
+/// named choices).
 pub(crate) fn apply_param_str(state: &mut ModuleState, param: &str, value: &str) {
   match state {
     ModuleState::StepSequencer(state) => {
@@ -1374,8 +1822,69 @@ pub(crate) fn apply_param_str(state: &mut ModuleState, param: &str, value: &str)
     ModuleState::MidiFileSequencer(state) => {
       if param == "midiData" {
         state.seq.parse_midi_data(value);
+      } else if param == "midiFile" {
+        if let Some(file) = decode_midi_file(value) {
+          state.seq.load_file(&file);
+        }
+      }
+    }
+    ModuleState::Tb303(state) if param == "waveform" => {
+      if let Some(waveform) = tb303_waveform(value) {
+        state.waveform.set(waveform);
+      }
+    }
+    ModuleState::RingMod(state) if param == "carrierShape" || param == "carrier-shape" => {
+      let shape = map_string_param(param, value, -1.0);
+      if shape >= 0.0 {
+        state.carrier_shape.set(shape);
+      }
+    }
+    ModuleState::Control(state) if param == "glideMode" => {
+      let mode = map_string_param(param, value, -1.0);
+      if mode >= 0.0 {
+        state.glide_legato = mode >= 0.5;
+      }
+    }
+    ModuleState::Crossfader(state) if param == "curve" => {
+      let curve = map_string_param(param, value, -1.0);
+      if curve >= 0.0 {
+        state.equal_power = curve >= 0.5;
+      }
+    }
+    ModuleState::Control(state) if param == "glideCurve" => {
+      let curve = map_string_param(param, value, -1.0);
+      if curve >= 0.0 {
+        state.glide_exponential = curve >= 0.5;
+      }
+    }
+    ModuleState::Distortion(state) if param == "mode" => {
+      if let Some(mode) = distortion_mode(value) {
+        state.mode.set(mode);
       }
     }
     _ => {}
   }
 }
+
+/// Apply a structured (array/object) parameter. Only the Control `tuning`
+/// table takes one for now.
+pub(crate) fn apply_param_json(state: &mut ModuleState, param: &str, value: &serde_json::Value) {
+  match state {
+    ModuleState::Control(state) if param == "tuning" => state.tuning.table = tuning_table(value),
+    _ => {}
+  }
+}
+
+/// Cents of a `tuning` table; anything but an array of positive numbers
+/// clears it (back to `edo`).
+fn tuning_table(value: &serde_json::Value) -> Vec<f32> {
+  let cents: Vec<f32> = value
+    .as_array()
+    .map(|degrees| degrees.iter().filter_map(|cents| cents.as_f64()).map(|cents| cents as f32).collect())
+    .unwrap_or_default();
+  if cents.last().is_some_and(|&period| period > 0.0) {
+    cents
+  } else {
+    Vec::new()
+  }
+}
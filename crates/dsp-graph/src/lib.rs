@@ -1,51 +1,102 @@
 mod types;
 mod buffer;
+mod builder;
+mod error;
+mod events;
 mod state;
 mod ports;
 mod process;
 mod instantiate;
+mod migrate;
+mod midi_routing;
+mod params;
+mod preset;
+mod snapshot;
 
-use dsp_core::{Sample, MARIO_CHANNELS};
+use dsp_core::{
+  MidiNote, Sample, SmfFile, SmfTrack, CLOCK_DIV_LABELS, MARIO_CHANNELS, MAX_BANK_CHANNELS, MAX_NOTES_PER_TRACK, MIDI_TRACKS,
+};
 
 // Re-export types from our modules
 pub use types::{ModuleType, PortInfo, ConnectionEdge, TapSource, ParamBuffer};
-pub use buffer::{Buffer, mix_buffers, downmix_to_mono};
+pub use buffer::{Buffer, mix_buffers, mix_buffers_panned, downmix_to_mono};
+pub use builder::GraphBuilder;
+pub use error::GraphError;
+pub use events::{hash_module_id, GraphEvent, GraphEventKind, MAX_EVENTS_PER_BLOCK};
+use events::EventQueue;
 pub use state::*;
-pub use ports::{input_ports, output_ports, input_port_index, output_port_index};
-use serde::Deserialize;
-use std::collections::{HashMap, VecDeque};
+pub use ports::{input_ports, output_ports, output_ports_for, input_port_index, output_port_index};
+pub use migrate::{migrate_v1_to_v2, CURRENT_SCHEMA_VERSION};
+pub use midi_routing::{
+  ChannelMap, ChannelRoute, NoteRouter, RoutedVoice, DEFAULT_CONTROL_MODULE, ROUTED_VOICES_CAPACITY,
+};
+pub use params::{param_meta, param_table, ParamKind, ParamMeta};
+pub use preset::{Preset, PRESET_EXTENSION};
+pub use snapshot::{ParamSnapshot, SNAPSHOT_SLOTS};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::panic::{self, AssertUnwindSafe};
 
-#[derive(Deserialize)]
+#[derive(Clone, Default, Deserialize, Serialize)]
 struct GraphPayload {
+  /// Absent in pre-versioning patches (schema 1); set by `migrate`.
+  #[serde(rename = "schemaVersion", skip_serializing_if = "Option::is_none")]
+  schema_version: Option<u32>,
   modules: Vec<ModuleSpecJson>,
   connections: Vec<ConnectionJson>,
+  #[serde(skip_serializing_if = "Option::is_none")]
   taps: Option<Vec<TapJson>>,
+  /// Random seed of the graph; absent uses the engine's
+  /// ([`GraphEngine::set_random_seed`]).
+  #[serde(skip_serializing_if = "Option::is_none")]
+  seed: Option<u64>,
 }
 
-#[derive(Deserialize)]
-struct ModuleSpecJson {
+/// Random seed of an engine until [`GraphEngine::set_random_seed`].
+pub const DEFAULT_RANDOM_SEED: u64 = 0x5678_1234;
+
+/// Base seed of a module's random sources: the graph seed mixed with the
+/// module id, so two Noise modules differ but a patch renders the same
+/// every time. Poly voices add their own offset on top.
+fn module_seed(graph_seed: u64, id_hash: u32) -> u32 {
+  let mut x = graph_seed ^ (id_hash as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+  x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+  x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+  ((x ^ (x >> 31)) >> 32) as u32
+}
+
+/// One module of a graph payload, in the `set_graph_json` shape.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ModuleSpecJson {
   id: String,
   #[serde(rename = "type")]
   kind: String,
   params: Option<HashMap<String, serde_json::Value>>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  bypass: Option<bool>,
 }
 
-#[derive(Deserialize)]
-struct ConnectionJson {
+/// One cable of a graph payload, in the `set_graph_json` shape.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ConnectionJson {
   from: PortRefJson,
   to: PortRefJson,
   kind: String,
 }
 
-#[derive(Deserialize)]
-struct PortRefJson {
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+pub struct PortRefJson {
   #[serde(rename = "moduleId")]
   module_id: String,
   #[serde(rename = "portId")]
   port_id: String,
+  /// On the `from` side of a poly source: bind to this voice instead of the
+  /// default reduction (voice 0 for CV, average for audio).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  voice: Option<usize>,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 struct TapJson {
   #[serde(rename = "moduleId")]
   module_id: String,
@@ -60,8 +111,80 @@ struct ModuleNode {
   outputs: Vec<PortInfo>,
   connections: Vec<Vec<ConnectionEdge>>,
   state: ModuleState,
+  /// Current param values (graph params + later updates), used to rebuild state on reset.
+  params: HashMap<String, serde_json::Value>,
+  /// Skip processing: effects pass input 0 to output 0, everything else is silent.
+  bypass: bool,
+  /// Panicked during render; handled like `bypass` from then on.
+  faulted: bool,
+  /// [`hash_module_id`] of the graph id, for [`GraphEvent`]s.
+  id_hash: u32,
+  /// Last gate level (Control, Clock bar output) or step (sequencers)
+  /// seen by `collect_events`.
+  event_prev: Sample,
+  /// Clock bar pulses counted so far.
+  event_count: u32,
+}
+
+/// When a [`ModuleFault`] happened.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FaultStage {
+  /// Building the module in `set_graph_json`.
+  Load,
+  Render,
+}
+
+/// A module that panicked while the graph was built or rendered.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleFault {
+  /// Empty when the panic could not be traced to one module.
+  pub module_id: String,
+  pub stage: FaultStage,
+  pub message: String,
+}
+
+/// A graph built by [`GraphEngine::compile_graph_json`] away from the live
+/// engine, ready for [`GraphEngine::install_compiled`].
+pub struct CompiledGraph {
+  /// Staging engine holding the built modules, buffers and render order.
+  engine: GraphEngine,
+}
+
+impl CompiledGraph {
+  pub fn sample_rate(&self) -> f32 {
+    self.engine.sample_rate
+  }
+
+  /// Schema migrations applied to the payload, see
+  /// [`GraphEngine::migrations`].
+  pub fn migrations(&self) -> &[&'static str] {
+    &self.engine.migrations
+  }
+
+  /// Connections dropped while wiring the graph, see
+  /// [`GraphEngine::diagnostics`].
+  pub fn diagnostics(&self) -> &[String] {
+    &self.engine.diagnostics
+  }
 }
 
+/// Highest number of stereo output buses (Output `bus` param 0..=7).
+pub const MAX_OUTPUT_BUSES: usize = 8;
+
+/// Output RMS treated as silence by the idle detector (-90 dBFS).
+const SILENCE_THRESHOLD: Sample = 3.162_277_7e-5;
+
+/// Default silence hold before idling; longer than the 2 s max delay time.
+const DEFAULT_IDLE_HOLD_SECONDS: f32 = 3.0;
+
+/// Share of the output ceiling below which the limiter leaves samples alone.
+const LIMITER_KNEE: Sample = 0.8;
+
+/// Largest block processed in one pass; longer renders are chunked.
+pub const MAX_BLOCK_FRAMES: usize = 1024;
+
 pub struct GraphEngine {
   sample_rate: f32,
   voice_count: usize,
@@ -72,11 +195,40 @@ pub struct GraphEngine {
   order: Vec<usize>,
   output_indices: Vec<usize>,
   taps: Vec<TapSource>,
-  main_buffer: Buffer,
+  /// Keep stereo tap ports as L/R channel pairs instead of downmixing.
+  stereo_taps: bool,
+  /// One stereo mix per output bus; bus 0 is the main L/R pair.
+  bus_buffers: Vec<Buffer>,
   output_data: Vec<Sample>,
+  /// Frame-interleaved copy of `output_data`, filled by `render_interleaved`.
+  interleaved_data: Vec<Sample>,
   output_channels: usize,
   external_input: Vec<Sample>,
   external_input_frames: usize,
+  /// Graph as last loaded (ids, kinds, connections, taps) for `to_graph_json`.
+  spec: GraphPayload,
+  /// False when a module that makes sound on its own schedule is patched
+  /// through to an Output ([`self_driven_reaches_output`]).
+  can_idle: bool,
+  idle: bool,
+  silent_frames: usize,
+  idle_hold_seconds: f32,
+  /// A/B parameter snapshots; cleared when a new graph is loaded.
+  snapshots: Vec<Option<ParamSnapshot>>,
+  /// Soft-clip ceiling of the output buses; `None` leaves them unlimited.
+  output_ceiling: Option<Sample>,
+  /// Longest module latency along any path to an Output, in samples.
+  latency: usize,
+  /// Panics caught since the graph was loaded.
+  faults: Vec<ModuleFault>,
+  /// Descriptions of the schema migrations the loaded graph needed.
+  migrations: Vec<&'static str>,
+  /// Connections the last graph build had to drop.
+  diagnostics: Vec<String>,
+  /// UI events of the last `render` call.
+  events: EventQueue,
+  /// Seed of graphs that don't set their own.
+  random_seed: u64,
 }
 
 impl GraphEngine {
@@ -91,32 +243,269 @@ impl GraphEngine {
       order: Vec::new(),
       output_indices: Vec::new(),
       taps: Vec::new(),
-      main_buffer: Buffer::new(2, 0),
+      stereo_taps: false,
+      bus_buffers: vec![Buffer::new(2, 0)],
       output_data: Vec::new(),
+      interleaved_data: Vec::new(),
       output_channels: 2,
       external_input: Vec::new(),
       external_input_frames: 0,
+      spec: GraphPayload::default(),
+      can_idle: true,
+      idle: false,
+      silent_frames: 0,
+      idle_hold_seconds: DEFAULT_IDLE_HOLD_SECONDS,
+      snapshots: vec![None; SNAPSHOT_SLOTS],
+      output_ceiling: None,
+      latency: 0,
+      faults: Vec::new(),
+      migrations: Vec::new(),
+      diagnostics: Vec::new(),
+      events: EventQueue::new(),
+      random_seed: DEFAULT_RANDOM_SEED,
     }
   }
 
-  pub fn set_graph_json(&mut self, payload: &str) -> Result<(), String> {
-    let graph: GraphPayload =
-      serde_json::from_str(payload).map_err(|err| format!("Invalid graph JSON: {err}"))?;
-    self.set_graph(graph);
-    Ok(())
+  /// Schema version this engine writes and migrates older graphs to.
+  pub const CURRENT_SCHEMA_VERSION: u32 = migrate::CURRENT_SCHEMA_VERSION;
+
+  /// Load a graph. A module that panics while being built leaves the
+  /// engine with an empty graph and returns an error naming it (also kept
+  /// in [`faults`](Self::faults)) instead of unwinding into the host.
+  pub fn set_graph_json(&mut self, payload: &str) -> Result<(), GraphError> {
+    let (graph, migrations) = parse_graph_payload(payload)?;
+    self.migrations = migrations;
+    self.faults.clear();
+    self.snapshots.fill(None);
+    let modules = graph.modules.clone();
+    let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| self.set_graph(graph))) else {
+      return Ok(());
+    };
+    let message = panic_message(payload.as_ref());
+    let sample_rate = self.sample_rate;
+    let module_id = modules
+      .iter()
+      .find(|module| {
+        let params = module.params.clone().unwrap_or_default();
+        let module_type = normalize_module_type(&module.kind);
+        panic::catch_unwind(|| ModuleNode::new(module_type, None, &params, sample_rate, 0)).is_err()
+      })
+      .map(|module| module.id.clone())
+      .unwrap_or_default();
+    self.set_graph(GraphPayload::default());
+    self.faults.push(ModuleFault { module_id: module_id.clone(), stage: FaultStage::Load, message: message.clone() });
+    Err(GraphError::ModulePanicked { module_id, message })
+  }
+
+  /// Build a graph without touching a live engine: the expensive half of
+  /// [`set_graph_json`](Self::set_graph_json) (parsing, allocating modules
+  /// and buffers, ordering), meant for a control or background thread.
+  /// `sample_rate` and `stereo_taps` should match the engine it goes to.
+  pub fn compile_graph_json(payload: &str, sample_rate: f32, stereo_taps: bool) -> Result<CompiledGraph, GraphError> {
+    let mut engine = GraphEngine::new(sample_rate);
+    engine.stereo_taps = stereo_taps;
+    engine.set_graph_json(payload)?;
+    Ok(CompiledGraph { engine })
+  }
+
+  /// Swap in a graph from [`compile_graph_json`](Self::compile_graph_json).
+  /// Cheap enough for the audio thread between blocks: nothing is built or
+  /// freed here. Returns the previous graph so the caller can drop it
+  /// elsewhere. MIDI file sequencers keep their position, as with
+  /// `set_graph_json`; a graph compiled for another sample rate or tap
+  /// layout is adapted in place, which does allocate.
+  pub fn install_compiled(&mut self, mut graph: CompiledGraph) -> CompiledGraph {
+    let staged = &mut graph.engine;
+    self.carry_sequencer_ticks(staged);
+    std::mem::swap(&mut self.voice_count, &mut staged.voice_count);
+    std::mem::swap(&mut self.modules, &mut staged.modules);
+    std::mem::swap(&mut self.input_buffers, &mut staged.input_buffers);
+    std::mem::swap(&mut self.output_buffers, &mut staged.output_buffers);
+    std::mem::swap(&mut self.module_map, &mut staged.module_map);
+    std::mem::swap(&mut self.order, &mut staged.order);
+    std::mem::swap(&mut self.output_indices, &mut staged.output_indices);
+    std::mem::swap(&mut self.taps, &mut staged.taps);
+    std::mem::swap(&mut self.bus_buffers, &mut staged.bus_buffers);
+    std::mem::swap(&mut self.output_channels, &mut staged.output_channels);
+    std::mem::swap(&mut self.spec, &mut staged.spec);
+    std::mem::swap(&mut self.can_idle, &mut staged.can_idle);
+    std::mem::swap(&mut self.latency, &mut staged.latency);
+    std::mem::swap(&mut self.faults, &mut staged.faults);
+    std::mem::swap(&mut self.migrations, &mut staged.migrations);
+    std::mem::swap(&mut self.diagnostics, &mut staged.diagnostics);
+    std::mem::swap(&mut self.snapshots, &mut staged.snapshots);
+    self.wake();
+
+    if staged.sample_rate != self.sample_rate {
+      for module in &mut self.modules {
+        module.set_sample_rate(self.sample_rate);
+      }
+      self.update_latency();
+    }
+    if staged.stereo_taps != self.stereo_taps {
+      self.set_stereo_taps(self.stereo_taps);
+    }
+    graph
+  }
+
+  /// Copy the playback position of this engine's MIDI file sequencers to
+  /// the matching ones (same id and voice) of `staged`.
+  fn carry_sequencer_ticks(&self, staged: &mut GraphEngine) {
+    for (module_id, indices) in &staged.module_map {
+      let Some(old_indices) = self.module_map.get(module_id) else { continue };
+      for &index in indices {
+        let voice = staged.modules[index].voice_index;
+        let ModuleState::MidiFileSequencer(state) = &mut staged.modules[index].state else { continue };
+        let old = old_indices.iter().map(|&old| &self.modules[old]).find(|old| old.voice_index == voice);
+        if let Some(ModuleState::MidiFileSequencer(old_state)) = old.map(|old| &old.state) {
+          state.seq.set_current_tick_precise(old_state.seq.current_tick_precise());
+        }
+      }
+    }
+  }
+
+  /// Take the [`GraphEvent`]s of the last `render` call, in order. Each
+  /// render starts a new list, so call this after every render; at most
+  /// [`MAX_EVENTS_PER_BLOCK`] are kept (the newest). Does not allocate.
+  pub fn drain_events(&mut self) -> impl Iterator<Item = GraphEvent> + '_ {
+    self.events.drain()
+  }
+
+  /// Modules that panicked since the graph was loaded. Each is listed once;
+  /// a module that panicked in render stays silent (effects pass their
+  /// input through) until the next `set_graph_json`.
+  pub fn faults(&self) -> &[ModuleFault] {
+    &self.faults
+  }
+
+  /// Descriptions of the schema migrations the last loaded graph went
+  /// through, empty for a graph saved at [`CURRENT_SCHEMA_VERSION`]. The
+  /// engine doesn't log them; callers off the audio thread may.
+  pub fn migrations(&self) -> &[&'static str] {
+    &self.migrations
+  }
+
+  /// Connections dropped by the last graph build (`set_graph_json`,
+  /// `set_graph_diff`, `set_voice_count`), such as a cable bound to a voice
+  /// the source no longer has. Like [`migrations`](Self::migrations), these
+  /// are left to the caller to report.
+  pub fn diagnostics(&self) -> &[String] {
+    &self.diagnostics
   }
 
+  /// Check a graph payload without loading it: the JSON must parse (after
+  /// migration), module ids must be unique with known types, and every
+  /// connection and tap must name existing modules.
+  ///
+  /// `set_graph_json` is lenient (unknown types fall back to an oscillator);
+  /// this is the stricter check for files. Unknown port ids stay allowed,
+  /// as the engine already skips them. An unknown type is reported as
+  /// [`GraphError::UnknownModuleType`]; the other problems are gathered in
+  /// one [`GraphError::ValidationFailed`].
+  pub fn validate_graph_json(payload: &str) -> Result<(), GraphError> {
+    let (graph, _) = parse_graph_payload(payload)?;
+    if let Some(module) = graph.modules.iter().find(|module| parse_module_type(&module.kind).is_none()) {
+      return Err(GraphError::UnknownModuleType(module.kind.clone()));
+    }
+    let mut ids = HashSet::new();
+    let mut problems = Vec::new();
+    for module in &graph.modules {
+      if !ids.insert(module.id.as_str()) {
+        problems.push(format!("Duplicate module id {}", module.id));
+      }
+    }
+    let endpoints = graph.connections.iter().flat_map(|cable| [&cable.from.module_id, &cable.to.module_id]);
+    let taps = graph.taps.iter().flatten().map(|tap| &tap.module_id);
+    for id in endpoints.chain(taps).filter(|id| !ids.contains(id.as_str())) {
+      problems.push(format!("Unknown module {id}"));
+    }
+    if problems.is_empty() {
+      Ok(())
+    } else {
+      Err(GraphError::ValidationFailed(problems))
+    }
+  }
+
+  /// Parameter metadata (range, default, step, kind) for a module type
+  /// string, as a JSON array. Unknown types give an empty array.
+  pub fn param_metadata(module_type: &str) -> serde_json::Value {
+    let table = parse_module_type(module_type).map_or(&[][..], param_table);
+    serde_json::to_value(table).unwrap_or_default()
+  }
+
+  /// Set a numeric parameter. Values are clamped and quantized against
+  /// [`param_table`] when the parameter is described there.
   pub fn set_param(&mut self, module_id: &str, param: &str, value: f32) {
+    self.wake();
+    let mut latency_changed = false;
     if let Some(indices) = self.module_map.get(module_id) {
       for &index in indices {
         if let Some(module) = self.modules.get_mut(index) {
+          let before = module.latency();
           module.apply_param(param, value);
+          latency_changed |= module.latency() != before;
         }
       }
     }
+    if latency_changed {
+      self.update_latency();
+    }
+    if param == "bus" && self.module_kind(module_id) == Some(ModuleType::Output) {
+      self.update_bus_layout();
+    }
+  }
+
+  /// Move a numeric param for live modulation, such as channel pressure on a
+  /// filter, without storing it: the graph JSON and snapshots keep the value
+  /// last given to [`set_param`](Self::set_param). Values are constrained the
+  /// same way; params that glide (VCF cutoff and envelope amount) ramp to it.
+  pub fn modulate_param(&mut self, module_id: &str, param: &str, value: f32) {
+    self.wake();
+    if let Some(indices) = self.module_map.get(module_id) {
+      for &index in indices {
+        self.modules[index].modulate_param(param, value);
+      }
+    }
+  }
+
+  /// Delay between the graph's inputs and its Output modules, in samples:
+  /// the sum of module latencies along the slowest path (parallel paths
+  /// report their maximum; feedback edges are ignored). Hosts use it for
+  /// plugin delay compensation.
+  pub fn latency_samples(&self) -> usize {
+    self.latency
+  }
+
+  fn update_latency(&mut self) {
+    self.latency = compute_latency(&self.modules, &self.order, &self.output_indices);
+  }
+
+  /// Rebuild the graph with a new polyphony. Voice-bound connections are
+  /// re-resolved; ones pointing past the new voice count are dropped with a
+  /// warning.
+  pub fn set_voice_count(&mut self, voices: usize) {
+    let voices = voices.clamp(1, 8);
+    if voices == self.voice_count {
+      return;
+    }
+    let mut graph = self.live_spec();
+    for module in &mut graph.modules {
+      if module.kind == "control" || module.kind == "midi-file-sequencer" {
+        module
+          .params
+          .get_or_insert_with(HashMap::new)
+          .insert("voices".to_string(), serde_json::Value::from(voices));
+      }
+    }
+    self.set_graph(graph);
+  }
+
+  pub fn voice_count(&self) -> usize {
+    self.voice_count
   }
 
   pub fn set_param_string(&mut self, module_id: &str, param: &str, value: &str) {
+    self.wake();
     if let Some(indices) = self.module_map.get(module_id) {
       for &index in indices {
         if let Some(module) = self.modules.get_mut(index) {
@@ -124,9 +513,304 @@ impl GraphEngine {
         }
       }
     }
+    if param == "bus" && matches!(self.module_kind(module_id), Some(ModuleType::Send | ModuleType::Return)) {
+      self.rewire_buses();
+    }
+  }
+
+  /// Set a param from any JSON value: numbers go through [`set_param`](Self::set_param),
+  /// strings through [`set_param_string`](Self::set_param_string); arrays and
+  /// objects are for structured params such as the Control `tuning` table.
+  pub fn set_param_json(&mut self, module_id: &str, param: &str, value: &serde_json::Value) {
+    match value {
+      serde_json::Value::Number(number) => {
+        self.set_param(module_id, param, number.as_f64().unwrap_or_default() as f32)
+      }
+      serde_json::Value::String(text) => self.set_param_string(module_id, param, text),
+      _ => {
+        self.wake();
+        if let Some(indices) = self.module_map.get(module_id) {
+          for &index in indices {
+            self.modules[index].apply_param_json(param, value);
+          }
+        }
+      }
+    }
+  }
+
+  /// Size the output buses for the highest Output `bus` param, so moving an
+  /// Output to a new bus at runtime adds (or drops) its channels.
+  fn update_bus_layout(&mut self) {
+    let bus_count = self
+      .output_indices
+      .iter()
+      .filter_map(|&index| match &self.modules[index].state {
+        ModuleState::Output(state) => Some(state.bus + 1),
+        _ => None,
+      })
+      .max()
+      .unwrap_or(1);
+    self.bus_buffers.resize_with(bus_count, || Buffer::new(2, 0));
+    self.output_channels = 2 * bus_count + self.tap_channel_total();
+  }
+
+  /// Re-resolve Send -> Return feeds after a bus rename.
+  fn rewire_buses(&mut self) {
+    for module in &mut self.modules {
+      if module.module_type == ModuleType::Return {
+        module.connections[0].clear();
+      }
+    }
+    connect_buses(&mut self.modules, &self.module_map);
+    self.order = compute_order(&self.modules);
+    self.can_idle = !self_driven_reaches_output(&self.modules, &self.module_map, &self.spec.connections);
+  }
+
+  /// Bypass a module (all voices) without touching its cables.
+  pub fn set_bypass(&mut self, module_id: &str, bypass: bool) {
+    self.wake();
+    if let Some(indices) = self.module_map.get(module_id) {
+      for &index in indices {
+        self.modules[index].bypass = bypass;
+      }
+    }
+    self.update_latency();
+  }
+
+  /// Export the live graph as JSON: the loaded modules, connections and taps
+  /// with every param at its current value. Feeding the result back to
+  /// `set_graph_json` rebuilds an equivalent engine.
+  pub fn to_graph_json(&self) -> String {
+    let mut spec = self.live_spec();
+    spec.schema_version = Some(Self::CURRENT_SCHEMA_VERSION);
+    serde_json::to_string(&spec).unwrap_or_default()
+  }
+
+  /// Loaded graph with params and bypass taken from the live modules.
+  fn live_spec(&self) -> GraphPayload {
+    let mut spec = self.spec.clone();
+    for module in &mut spec.modules {
+      if let Some(&index) = self.module_map.get(&module.id).and_then(|indices| indices.first()) {
+        module.params = Some(self.modules[index].params.clone());
+        module.bypass = self.modules[index].bypass.then_some(true);
+      }
+    }
+    spec
+  }
+
+  /// Stored value of a numeric module param (its default if the graph left
+  /// it out), or `None` if the module or param doesn't exist. Values set
+  /// through `modulate_param` are not reported.
+  pub fn get_param(&self, module_id: &str, param: &str) -> Option<f32> {
+    let index = *self.module_map.get(module_id)?.first()?;
+    self.modules[index].param(param)
+  }
+
+  /// Whether the current graph contains a module with this id.
+  pub fn has_module(&self, module_id: &str) -> bool {
+    self.module_map.contains_key(module_id)
+  }
+
+  /// Ids of all modules in the current graph, sorted.
+  pub fn module_ids(&self) -> Vec<&str> {
+    let mut ids: Vec<&str> = self.module_map.keys().map(String::as_str).collect();
+    ids.sort_unstable();
+    ids
+  }
+
+  /// Type of a module in the current graph.
+  pub fn module_kind(&self, module_id: &str) -> Option<ModuleType> {
+    let index = *self.module_map.get(module_id)?.first()?;
+    Some(self.modules[index].module_type)
+  }
+
+  /// Current numeric param values of every module (first voice of poly
+  /// modules).
+  pub fn snapshot_params(&self) -> ParamSnapshot {
+    let mut snapshot = ParamSnapshot::default();
+    for (module_id, indices) in &self.module_map {
+      let Some(module) = indices.first().map(|&index| &self.modules[index]) else {
+        continue;
+      };
+      for (param, value) in &module.params {
+        if let Some(value) = value.as_f64() {
+          snapshot.insert(module_id, param, value as f32);
+        }
+      }
+    }
+    snapshot
+  }
+
+  /// Apply the values of `snapshot` that differ from the current ones, via
+  /// `set_param` so clamping and smoothing apply. Modules that no longer
+  /// exist are skipped.
+  pub fn apply_param_snapshot(&mut self, snapshot: &ParamSnapshot) {
+    for (module_id, param, value) in snapshot.iter() {
+      let current = self
+        .module_map
+        .get(module_id)
+        .and_then(|indices| indices.first())
+        .and_then(|&index| self.modules[index].params.get(param))
+        .and_then(serde_json::Value::as_f64);
+      if current != Some(value as f64) {
+        self.set_param(module_id, param, value);
+      }
+    }
+  }
+
+  /// Store the current params in snapshot `slot`.
+  pub fn store_snapshot(&mut self, slot: usize) -> Result<(), String> {
+    let snapshot = self.snapshot_params();
+    *self.snapshot_slot_mut(slot)? = Some(snapshot);
+    Ok(())
+  }
+
+  /// Apply the params stored in snapshot `slot`.
+  pub fn recall_snapshot(&mut self, slot: usize) -> Result<(), String> {
+    let snapshot = self.snapshot_slot_mut(slot)?.take().ok_or_else(|| format!("Snapshot slot {slot} is empty"))?;
+    self.apply_param_snapshot(&snapshot);
+    self.snapshots[slot] = Some(snapshot);
+    Ok(())
+  }
+
+  /// Set every param found in both slots to `a + (b - a) * t`. Enum params
+  /// (per [`param_table`]) switch from `a` to `b` at `t = 0.5` instead.
+  pub fn morph_snapshots(&mut self, slot_a: usize, slot_b: usize, t: f32) -> Result<(), String> {
+    let t = t.clamp(0.0, 1.0);
+    let stored = |slot: usize| {
+      self
+        .snapshots
+        .get(slot)
+        .ok_or_else(|| format!("Snapshot slot {slot} out of range"))?
+        .as_ref()
+        .ok_or_else(|| format!("Snapshot slot {slot} is empty"))
+    };
+    let (from, to) = (stored(slot_a)?, stored(slot_b)?);
+    let mut target = ParamSnapshot::default();
+    for (module_id, param, start) in from.iter() {
+      let Some(end) = to.get(module_id, param) else {
+        continue;
+      };
+      let discrete = self
+        .module_kind(module_id)
+        .and_then(|module_type| param_meta(module_type, param))
+        .is_some_and(|meta| meta.kind == ParamKind::Enum);
+      let value = if !discrete {
+        start + (end - start) * t
+      } else if t < 0.5 {
+        start
+      } else {
+        end
+      };
+      target.insert(module_id, param, value);
+    }
+    self.apply_param_snapshot(&target);
+    Ok(())
+  }
+
+  fn snapshot_slot_mut(&mut self, slot: usize) -> Result<&mut Option<ParamSnapshot>, String> {
+    self
+      .snapshots
+      .get_mut(slot)
+      .ok_or_else(|| format!("Snapshot slot {slot} out of range"))
+  }
+
+  /// Seed the random sources (Noise, Sample & Hold) of graphs that don't
+  /// set a `seed` of their own, for reproducible offline renders. Rebuilds
+  /// the loaded graph, so its DSP state starts over as after [`reset`](Self::reset).
+  pub fn set_random_seed(&mut self, seed: u64) {
+    self.random_seed = seed;
+    self.set_graph(self.live_spec());
+  }
+
+  /// Clear all DSP state (delay lines, reverb tails, filter memory, envelopes,
+  /// oscillator phases) while keeping the graph topology and current params.
+  /// Works in place without allocating, so it is safe on the audio thread.
+  /// Loaded samples, SID/YM tunes and sequencer patterns are kept; players
+  /// and sequencers are rewound.
+  pub fn reset(&mut self) {
+    for module in &mut self.modules {
+      module.reset();
+    }
+    for buffers in self.input_buffers.iter_mut().chain(self.output_buffers.iter_mut()) {
+      for buffer in buffers {
+        buffer.clear();
+      }
+    }
+    for bus in &mut self.bus_buffers {
+      bus.clear();
+    }
+    self.output_data.fill(0.0);
+    self.wake();
+  }
+
+  /// Switch every module to a new sample rate without rebuilding the graph.
+  /// Connections and params are kept; delay lines and other time-based
+  /// buffers are reallocated for the new rate.
+  pub fn set_sample_rate(&mut self, sample_rate: f32) {
+    let sample_rate = sample_rate.max(1.0);
+    if sample_rate == self.sample_rate {
+      return;
+    }
+    self.sample_rate = sample_rate;
+    for module in &mut self.modules {
+      module.set_sample_rate(sample_rate);
+    }
+    self.update_latency();
+    self.wake();
+  }
+
+  /// How long every Output must stay below -90 dBFS, with no gate held,
+  /// before `render` idles. Keep it longer than the longest delay time.
+  pub fn set_idle_hold(&mut self, seconds: f32) {
+    self.idle_hold_seconds = seconds.max(0.0);
+  }
+
+  /// Soft-clip every output bus so no sample exceeds `ceiling` (linear,
+  /// e.g. 1.0 for 0 dBFS); `None` turns the limiter off. NaN and infinite
+  /// samples are replaced with silence either way.
+  pub fn set_output_ceiling(&mut self, ceiling: Option<Sample>) {
+    self.output_ceiling = ceiling.filter(|c| c.is_finite() && *c > 0.0);
+  }
+
+  pub fn output_ceiling(&self) -> Option<Sample> {
+    self.output_ceiling
+  }
+
+  /// True while `render` is skipping module processing and emitting silence.
+  /// Any note, gate, param change or audible external input wakes it.
+  pub fn is_idle(&self) -> bool {
+    self.idle
+  }
+
+  fn wake(&mut self) {
+    self.idle = false;
+    self.silent_frames = 0;
+  }
+
+  fn gates_active(&self) -> bool {
+    self.modules.iter().any(|module| match &module.state {
+      ModuleState::Control(state) => state.gate > 0.0,
+      _ => false,
+    })
+  }
+
+  /// Update the silence counter after a rendered block.
+  fn track_activity(&mut self, output_rms: f32, frames: usize) {
+    if !self.can_idle || output_rms > SILENCE_THRESHOLD || self.gates_active() {
+      self.silent_frames = 0;
+      return;
+    }
+    self.silent_frames += frames;
+    if self.silent_frames as f32 >= self.idle_hold_seconds * self.sample_rate {
+      self.idle = true;
+    }
   }
 
   pub fn set_external_input(&mut self, input: &[Sample]) {
+    if input.iter().any(|s| s.abs() > SILENCE_THRESHOLD) {
+      self.wake();
+    }
     self.external_input.clear();
     self.external_input.extend_from_slice(input);
     self.external_input_frames = input.len();
@@ -137,24 +821,38 @@ impl GraphEngine {
     self.external_input_frames = 0;
   }
 
+  /// V/Oct CV of a MIDI note under the tuning of a Control module
+  /// (`refFreq`, `edo`, `tuning`). Unknown modules use 12-EDO with note 60
+  /// at 0 V. Hosts call this instead of converting notes themselves.
+  pub fn note_to_cv(&self, module_id: &str, note: u8) -> f32 {
+    let state = self.module_map.get(module_id).and_then(|indices| indices.first()).map(|&index| &self.modules[index].state);
+    match state {
+      Some(ModuleState::Control(state)) => state.tuning.note_to_cv(note),
+      _ => dsp_core::midi_note_to_cv(note),
+    }
+  }
+
   pub fn set_control_voice_cv(&mut self, module_id: &str, voice: usize, value: f32) {
+    self.wake();
     if let Some(index) = self.find_voice_instance(module_id, voice) {
       if let Some(ModuleState::Control(state)) = self.modules.get_mut(index).map(|m| &mut m.state) {
-        if state.glide_seconds > 0.0 {
-          let total = (state.glide_seconds * self.sample_rate).max(1.0);
-          state.cv_target = value;
-          state.cv_remaining = total as usize;
-          state.cv_step = (state.cv_target - state.cv) / total;
-        } else {
-          state.cv = value;
-          state.cv_target = value;
-          state.cv_remaining = 0;
-        }
+        state.set_cv(value);
       }
     }
   }
 
+  /// Set the glide time of one voice in seconds. Like the `glide` param, it
+  /// applies to the next CV change, not to a glide in flight.
+  pub fn set_control_voice_glide(&mut self, module_id: &str, voice: usize, seconds: f32) {
+    self.wake();
+    let index = self.find_voice_instance(module_id, voice);
+    if let Some(ModuleState::Control(state)) = index.and_then(|index| self.modules.get_mut(index)).map(|m| &mut m.state) {
+      state.glide_seconds = seconds.max(0.0);
+    }
+  }
+
   pub fn set_control_voice_gate(&mut self, module_id: &str, voice: usize, value: f32) {
+    self.wake();
     if let Some(index) = self.find_voice_instance(module_id, voice) {
       if let Some(ModuleState::Control(state)) = self.modules.get_mut(index).map(|m| &mut m.state) {
         state.gate = value;
@@ -163,6 +861,7 @@ impl GraphEngine {
   }
 
   pub fn trigger_control_voice_gate(&mut self, module_id: &str, voice: usize) {
+    self.wake();
     if let Some(index) = self.find_voice_instance(module_id, voice) {
       if let Some(ModuleState::Control(state)) = self.modules.get_mut(index).map(|m| &mut m.state) {
         // Force a brief gate=0 period to guarantee rising edge for ADSR retrigger
@@ -174,6 +873,7 @@ impl GraphEngine {
   }
 
   pub fn trigger_control_voice_sync(&mut self, module_id: &str, voice: usize) {
+    self.wake();
     if let Some(index) = self.find_voice_instance(module_id, voice) {
       if let Some(ModuleState::Control(state)) = self.modules.get_mut(index).map(|m| &mut m.state) {
         let samples = (0.02 * self.sample_rate).max(1.0);
@@ -189,11 +889,14 @@ impl GraphEngine {
     value: f32,
     slew_seconds: f32,
   ) {
+    self.wake();
     if let Some(index) = self.find_voice_instance(module_id, voice) {
       if let Some(ModuleState::Control(state)) = self.modules.get_mut(index).map(|m| &mut m.state) {
         let clamped = value.clamp(0.0, 1.0);
         if slew_seconds > 0.0 {
-          let total = (slew_seconds * self.sample_rate).max(1.0);
+          // A new value mid-slew restarts the ramp from the current output,
+          // so vel-out never jumps.
+          let total = (slew_seconds * self.sample_rate).round().max(1.0);
           state.velocity_target = clamped;
           state.velocity_remaining = total as usize;
           state.velocity_step = (state.velocity_target - state.velocity) / total;
@@ -206,25 +909,40 @@ impl GraphEngine {
     }
   }
 
-  pub fn set_mario_channel_cv(&mut self, module_id: &str, channel: usize, value: f32) {
-    if channel == 0 || channel > MARIO_CHANNELS {
-      return;
+  /// Set the CV of a `cv-bank` (or `mario`) channel. Channels are 1-based;
+  /// 0 and channels past the bank's `channels` are ignored.
+  pub fn set_bank_channel_cv(&mut self, module_id: &str, channel: usize, value: f32) {
+    self.wake();
+    if let Some((bank, index)) = self.bank_channel_mut(module_id, channel) {
+      bank.set_cv(index, value);
     }
-    if let Some(index) = self.module_map.get(module_id).and_then(|list| list.first()) {
-      if let Some(ModuleState::Mario(state)) = self.modules.get_mut(*index).map(|m| &mut m.state) {
-        state.mario.set_cv(channel - 1, value);
-      }
+  }
+
+  /// Set the gate of a `cv-bank` (or `mario`) channel, 1-based like
+  /// `set_bank_channel_cv`.
+  pub fn set_bank_channel_gate(&mut self, module_id: &str, channel: usize, value: f32) {
+    self.wake();
+    if let Some((bank, index)) = self.bank_channel_mut(module_id, channel) {
+      bank.set_gate(index, value);
     }
   }
 
+  pub fn set_mario_channel_cv(&mut self, module_id: &str, channel: usize, value: f32) {
+    self.set_bank_channel_cv(module_id, channel, value);
+  }
+
   pub fn set_mario_channel_gate(&mut self, module_id: &str, channel: usize, value: f32) {
-    if channel == 0 || channel > MARIO_CHANNELS {
-      return;
-    }
-    if let Some(index) = self.module_map.get(module_id).and_then(|list| list.first()) {
-      if let Some(ModuleState::Mario(state)) = self.modules.get_mut(*index).map(|m| &mut m.state) {
-        state.mario.set_gate(channel - 1, value);
+    self.set_bank_channel_gate(module_id, channel, value);
+  }
+
+  /// The bank of `module_id` and the 0-based index of a 1-based channel.
+  fn bank_channel_mut(&mut self, module_id: &str, channel: usize) -> Option<(&mut dsp_core::CvBank, usize)> {
+    let index = *self.module_map.get(module_id)?.first()?;
+    match &mut self.modules.get_mut(index)?.state {
+      ModuleState::CvBank(state) if (1..=state.bank.channels()).contains(&channel) => {
+        Some((&mut state.bank, channel - 1))
       }
+      _ => None,
     }
   }
 
@@ -291,6 +1009,7 @@ impl GraphEngine {
 
   /// Load sample buffer into a ParticleCloud module
   pub fn load_particle_buffer(&mut self, module_id: &str, data: &[f32]) {
+    self.wake();
     if let Some(index) = self.module_map.get(module_id).and_then(|list| list.first().copied()) {
       if let Some(module) = self.modules.get_mut(index) {
         if let ModuleState::ParticleCloud(ref mut state) = module.state {
@@ -300,6 +1019,50 @@ impl GraphEngine {
     }
   }
 
+  /// Load decoded audio (interleaved, 1 or 2 channels at `sample_rate`) into
+  /// a SamplePlayer module. Returns the number of frames loaded.
+  pub fn load_sample(
+    &mut self,
+    module_id: &str,
+    data: &[f32],
+    channels: usize,
+    sample_rate: f32,
+  ) -> Result<usize, String> {
+    self.wake();
+    let index = self
+      .module_map
+      .get(module_id)
+      .and_then(|list| list.first().copied())
+      .ok_or_else(|| format!("Unknown module: {module_id}"))?;
+    match self.modules.get_mut(index).map(|module| &mut module.state) {
+      Some(ModuleState::SamplePlayer(state)) => state.player.load(data, channels, sample_rate),
+      _ => Err(format!("Module {module_id} is not a sample player")),
+    }
+  }
+
+  /// Load MIDI notes into a MidiFileSequencer (all voices). The module's
+  /// `midiData` param is set to `data`'s JSON, so presets and graph
+  /// rebuilds keep them. Nothing is parsed or serialized here: `data` is
+  /// prepared by the caller, off the audio thread.
+  pub fn set_module_midi_data(&mut self, module_id: &str, data: MidiData) -> Result<(), String> {
+    if self.module_kind(module_id) != Some(ModuleType::MidiFileSequencer) {
+      return Err(format!("Module {module_id} is not a MIDI file sequencer"));
+    }
+    self.wake();
+    let indices = &self.module_map[module_id];
+    let mut json = Some(serde_json::Value::String(data.json));
+    for (position, &index) in indices.iter().enumerate() {
+      let module = &mut self.modules[index];
+      if let ModuleState::MidiFileSequencer(state) = &mut module.state {
+        state.seq.load_file(&data.file);
+      }
+      let value = if position + 1 == indices.len() { json.take() } else { json.clone() };
+      module.params.remove("midiFile");
+      module.params.insert("midiData".to_string(), value.unwrap_or_default());
+    }
+    Ok(())
+  }
+
   /// Seek MIDI file sequencer to a specific tick position
   pub fn seek_midi_sequencer(&mut self, module_id: &str, tick: u32) {
     if let Some(index) = self.module_map.get(module_id).and_then(|list| list.first().copied()) {
@@ -351,6 +1114,7 @@ impl GraphEngine {
 
   /// Load sample data into a Granular module's buffer
   pub fn load_granular_buffer(&mut self, module_id: &str, data: &[Sample]) {
+    self.wake();
     if let Some(index) = self.module_map.get(module_id).and_then(|list| list.first().copied()) {
       if let Some(module) = self.modules.get_mut(index) {
         if let ModuleState::Granular(ref mut state) = module.state {
@@ -405,6 +1169,7 @@ impl GraphEngine {
 
   /// Load a SID file into a SidPlayer module
   pub fn load_sid_file(&mut self, module_id: &str, data: &[u8]) {
+    self.wake();
     if let Some(index) = self.module_map.get(module_id).and_then(|list| list.first().copied()) {
       if let Some(module) = self.modules.get_mut(index) {
         if let ModuleState::SidPlayer(ref mut state) = module.state {
@@ -434,6 +1199,7 @@ impl GraphEngine {
 
   /// Load a YM file into an AyPlayer module
   pub fn load_ym_file(&mut self, module_id: &str, data: &[u8]) {
+    self.wake();
     if let Some(index) = self.module_map.get(module_id).and_then(|list| list.first().copied()) {
       if let Some(module) = self.modules.get_mut(index) {
         if let ModuleState::AyPlayer(ref mut state) = module.state {
@@ -471,86 +1237,300 @@ impl GraphEngine {
     0.0
   }
 
+  /// Number of planar channels in the render output: the main stereo mix,
+  /// one per tap, then a stereo pair per extra output bus.
+  pub fn output_channels(&self) -> usize {
+    self.output_channels
+  }
+
+  /// Number of taps. Tap `n` is planar channel `2 + n` unless stereo taps
+  /// are enabled; see [`tap_channels`](Self::tap_channels).
+  pub fn tap_count(&self) -> usize {
+    self.taps.len()
+  }
+
+  /// Planar channels of tap `tap`: 2 for a stereo port with stereo taps
+  /// enabled, otherwise 1. Taps are laid out back to back from channel 2.
+  pub fn tap_channels(&self, tap: usize) -> usize {
+    self.taps.get(tap).map_or(0, |tap| tap.channels)
+  }
+
+  /// Planar channels used by all taps together.
+  pub fn tap_channel_total(&self) -> usize {
+    self.taps.iter().map(|tap| tap.channels).sum()
+  }
+
+  /// Keep stereo tap ports as separate L/R planar channels instead of a mono
+  /// downmix. Off by default, so every tap is one channel; hosts that turn
+  /// it on must lay out taps with [`tap_channels`](Self::tap_channels).
+  pub fn set_stereo_taps(&mut self, enabled: bool) {
+    self.stereo_taps = enabled;
+    for tap in &mut self.taps {
+      tap.channels = tap_channel_count(&self.modules, tap, enabled);
+    }
+    self.output_channels = 2 * self.bus_buffers.len() + self.tap_channel_total();
+  }
+
+  /// Number of stereo output buses (highest Output `bus` param + 1). Bus 0
+  /// is channels 0-1; bus `b > 0` starts at channel `2 + tap_channel_total() + 2 * (b - 1)`.
+  pub fn bus_count(&self) -> usize {
+    self.bus_buffers.len()
+  }
+
+  /// Same as [`render`](Self::render): `[L][R][tap 0]...`, each `frames` long.
+  pub fn render_planar(&mut self, frames: usize) -> &[Sample] {
+    self.render(frames)
+  }
+
+  /// Render a block and return it frame-interleaved: `[L0, R0, L1, R1, ...]`,
+  /// or `[L0, R0, tap0_0, ..., L1, ...]` when the graph has taps.
+  pub fn render_interleaved(&mut self, frames: usize) -> &[Sample] {
+    if frames == 0 {
+      return &[];
+    }
+    self.render(frames);
+    let channels = self.output_channels;
+    let planar = &self.output_data;
+    self.interleaved_data.resize(planar.len(), 0.0);
+    for (frame, chunk) in self.interleaved_data.chunks_exact_mut(channels).enumerate() {
+      for (channel, sample) in chunk.iter_mut().enumerate() {
+        *sample = planar[channel * frames + frame];
+      }
+    }
+    &self.interleaved_data
+  }
+
+  /// Render `frames` samples. Requests larger than [`MAX_BLOCK_FRAMES`] are
+  /// processed in chunks and written into one contiguous planar result, so
+  /// hosts may pass any block size.
   pub fn render(&mut self, frames: usize) -> &[Sample] {
     if frames == 0 {
       return &[];
     }
-    if self.modules.is_empty() {
-      self.ensure_output(frames);
+    self.ensure_output(frames);
+    self.events.clear();
+    if self.modules.is_empty() || self.idle {
       self.output_data.fill(0.0);
       return &self.output_data;
     }
 
-    for &module_index in &self.order {
-      {
-        let module = &self.modules[module_index];
-        for (input_index, info) in module.inputs.iter().enumerate() {
-          let buffer = &mut self.input_buffers[module_index][input_index];
-          buffer.resize(info.channels, frames);
-          buffer.clear();
-          for edge in &module.connections[input_index] {
-            let source = &self.output_buffers[edge.source_module][edge.source_port];
-            mix_buffers(buffer, source, edge.gain);
-          }
-        }
-        for (output_index, info) in module.outputs.iter().enumerate() {
-          let buffer = &mut self.output_buffers[module_index][output_index];
-          buffer.resize(info.channels, frames);
-          buffer.clear();
+    let mut offset = 0;
+    let mut output_rms: Sample = 0.0;
+    while offset < frames {
+      let chunk = (frames - offset).min(MAX_BLOCK_FRAMES);
+      output_rms = output_rms.max(self.render_chunk(offset, chunk, frames));
+      offset += chunk;
+    }
+    self.track_activity(output_rms, frames);
+
+    &self.output_data
+  }
+
+  /// Run every module in render order. A module that panics is marked
+  /// faulted and recorded in [`faults`](Self::faults), its outputs for this
+  /// block are replaced as for bypass, and the loop resumes with the next
+  /// module. The unwind guard wraps the whole loop, not each module.
+  fn process_modules(&mut self, offset: usize, frames: usize) {
+    let mut position = 0;
+    while position < self.order.len() {
+      let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        while position < self.order.len() {
+          let module_index = self.order[position];
+          self.process_module(module_index, offset, frames);
+          position += 1;
         }
+      }));
+      if let Err(payload) = result {
+        let module_index = self.order[position];
+        self.fault_module(module_index, panic_message(payload.as_ref()));
+        position += 1;
       }
+    }
+  }
 
-      let inputs = &self.input_buffers[module_index];
-      let outputs = &mut self.output_buffers[module_index];
-      let module = &mut self.modules[module_index];
-        if let ModuleState::AudioIn(state) = &mut module.state {
-          let output = outputs[0].channel_mut(0);
-          if self.external_input_frames == 0 {
-            output.fill(0.0);
+  fn process_module(&mut self, module_index: usize, offset: usize, frames: usize) {
+    {
+      let module = &self.modules[module_index];
+      let voice_spread = match &module.state {
+        ModuleState::Output(state) => state.voice_spread,
+        _ => 0.0,
+      };
+      for (input_index, info) in module.inputs.iter().enumerate() {
+        let buffer = &mut self.input_buffers[module_index][input_index];
+        buffer.resize(info.channels, frames);
+        buffer.clear();
+        for edge in &module.connections[input_index] {
+          let source = &self.output_buffers[edge.source_module][edge.source_port];
+          let pan = edge.pan * voice_spread;
+          if pan == 0.0 {
+            mix_buffers(buffer, source, edge.gain);
           } else {
-            let gain = state.gain.slice(frames);
-            let available = self.external_input_frames.min(frames);
-            for i in 0..available {
-              output[i] = self.external_input[i] * gain[i];
-            }
-            if available < frames {
-              output[available..frames].fill(0.0);
-            }
+            mix_buffers_panned(buffer, source, edge.gain, pan);
           }
-          continue;
         }
-      module.process(inputs, outputs, frames, self.sample_rate);
+      }
+      for (output_index, info) in module.outputs.iter().enumerate() {
+        let buffer = &mut self.output_buffers[module_index][output_index];
+        buffer.resize(info.channels, frames);
+        buffer.clear();
+      }
     }
 
-    self.main_buffer.resize(2, frames);
-    self.main_buffer.clear();
-    for &index in &self.output_indices {
-      let outputs = &self.output_buffers[index];
-      if let Some(out_port) = outputs.get(0) {
-        mix_buffers(&mut self.main_buffer, out_port, 1.0);
+    let inputs = &self.input_buffers[module_index];
+    let outputs = &mut self.output_buffers[module_index];
+    let module = &mut self.modules[module_index];
+    if let ModuleState::AudioIn(state) = &mut module.state {
+      let output = outputs[0].channel_mut(0);
+      let available = self.external_input_frames.saturating_sub(offset).min(frames);
+      if available == 0 {
+        output.fill(0.0);
+      } else {
+        let gain = state.gain.slice(frames);
+        for i in 0..available {
+          output[i] = self.external_input[offset + i] * gain[i];
+        }
+        if available < frames {
+          output[available..frames].fill(0.0);
+        }
       }
+      return;
     }
+    if module.bypass || module.faulted {
+      // Outputs were cleared above, so non-effects are already silent.
+      if is_bypass_passthrough(module.module_type) {
+        mix_buffers(&mut outputs[0], &inputs[0], 1.0);
+      }
+      return;
+    }
+    module.process(inputs, outputs, frames, self.sample_rate);
+    self.collect_events(module_index, offset, frames);
+  }
 
-    self.ensure_output(frames);
-    let channel_span = frames;
-    let main_left = self.main_buffer.channel(0);
-    let main_right = self.main_buffer.channel(1);
-    self.output_data[0..channel_span].copy_from_slice(main_left);
-    self.output_data[channel_span..(2 * channel_span)].copy_from_slice(main_right);
-
-    for (tap_index, tap) in self.taps.iter().enumerate() {
-      let offset = (2 + tap_index) * channel_span;
-      let dest = &mut self.output_data[offset..offset + channel_span];
-      let source = &self.input_buffers[tap.module_index][tap.input_port];
-      downmix_to_mono(source, dest);
+  /// Queue the [`GraphEvent`]s of a module that just rendered `frames`
+  /// samples at `offset`: gate edges of Control modules, bar pulses of
+  /// Clock modules and step changes of the sequencers.
+  fn collect_events(&mut self, module_index: usize, offset: usize, frames: usize) {
+    const GATE_THRESHOLD: Sample = 0.5;
+    let module = &mut self.modules[module_index];
+    let module_hash = module.id_hash;
+    let (port, step) = match &module.state {
+      ModuleState::Control(_) => (Some(2), None),
+      ModuleState::Clock(_) => (Some(3), None),
+      ModuleState::StepSequencer(state) => (None, Some(state.seq.current_step())),
+      ModuleState::DrumSequencer(state) => (None, Some(state.seq.current_step())),
+      ModuleState::Euclidean(state) => (None, Some(state.euclidean.current_step())),
+      ModuleState::TuringMachine(state) => (None, Some(state.turing.current_step())),
+      _ => return,
+    };
+    if let Some(step) = step {
+      let step = step as Sample;
+      if step != module.event_prev {
+        module.event_prev = step;
+        self.events.push(GraphEvent { module_hash, kind: GraphEventKind::Step, value: step, frame: offset as u32 });
+      }
+      return;
+    }
+    let Some(output) = port.and_then(|port| self.output_buffers[module_index].get(port)) else { return };
+    let is_clock = module.module_type == ModuleType::Clock;
+    for (i, &level) in output.channel(0)[..frames].iter().enumerate() {
+      let high = level >= GATE_THRESHOLD;
+      let was_high = module.event_prev >= GATE_THRESHOLD;
+      module.event_prev = level;
+      let (kind, value) = match (high, was_high, is_clock) {
+        (true, false, true) => {
+          module.event_count += 1;
+          (GraphEventKind::Bar, module.event_count as Sample)
+        }
+        (true, false, false) => (GraphEventKind::GateOn, module.voice_index.unwrap_or(0) as Sample),
+        (false, true, false) => (GraphEventKind::GateOff, module.voice_index.unwrap_or(0) as Sample),
+        _ => continue,
+      };
+      self.events.push(GraphEvent { module_hash, kind, value, frame: (offset + i) as u32 });
     }
+  }
 
-    &self.output_data
+  /// Silence a module that panicked mid-block and record it once.
+  fn fault_module(&mut self, module_index: usize, message: String) {
+    let module = &mut self.modules[module_index];
+    module.faulted = true;
+    let outputs = &mut self.output_buffers[module_index];
+    for buffer in outputs.iter_mut() {
+      buffer.clear();
+    }
+    if is_bypass_passthrough(module.module_type) {
+      mix_buffers(&mut outputs[0], &self.input_buffers[module_index][0], 1.0);
+    }
+    let module_id = self
+      .module_map
+      .iter()
+      .find(|(_, indices)| indices.contains(&module_index))
+      .map(|(id, _)| id.clone())
+      .unwrap_or_default();
+    if !self.faults.iter().any(|fault| fault.module_id == module_id && fault.stage == FaultStage::Render) {
+      self.faults.push(ModuleFault { module_id, stage: FaultStage::Render, message });
+    }
+  }
+
+  /// Process `frames` samples and write them at `offset` into each planar
+  /// channel of `output_data` (whose channels are `total` frames long).
+  /// Returns the loudest Output module's RMS for the chunk.
+  fn render_chunk(&mut self, offset: usize, frames: usize, total: usize) -> Sample {
+    self.process_modules(offset, frames);
+
+    for bus in &mut self.bus_buffers {
+      bus.resize(2, frames);
+      bus.clear();
+    }
+    let mut output_rms: Sample = 0.0;
+    for &index in &self.output_indices {
+      let bus = match &self.modules[index].state {
+        ModuleState::Output(state) => state.bus,
+        _ => 0,
+      };
+      if let Some(out_port) = self.output_buffers[index].get(0) {
+        mix_buffers(&mut self.bus_buffers[bus], out_port, 1.0);
+        output_rms = output_rms.max(block_rms(out_port));
+      }
+    }
+
+    for bus in &mut self.bus_buffers {
+      for side in 0..2 {
+        limit_output(bus.channel_mut(side), self.output_ceiling);
+      }
+    }
+
+    // Planar layout: [bus 0 L][bus 0 R][tap 0]...[tap n][bus 1 L][bus 1 R]...
+    let tap_channels = self.tap_channel_total();
+    for (bus_index, bus) in self.bus_buffers.iter().enumerate() {
+      let first_channel = if bus_index == 0 { 0 } else { tap_channels + 2 * bus_index };
+      for side in 0..2 {
+        let start = (first_channel + side) * total + offset;
+        self.output_data[start..start + frames].copy_from_slice(bus.channel(side));
+      }
+    }
+
+    let mut channel = 2;
+    for tap in &self.taps {
+      let buffers = if tap.output { &self.output_buffers } else { &self.input_buffers };
+      let source = &buffers[tap.module_index][tap.port];
+      for side in 0..tap.channels {
+        let start = (channel + side) * total + offset;
+        let dest = &mut self.output_data[start..start + frames];
+        if tap.channels == 1 {
+          downmix_to_mono(source, dest);
+        } else {
+          dest.copy_from_slice(source.channel(side));
+        }
+      }
+      channel += tap.channels;
+    }
+    output_rms
   }
 
   fn set_graph(&mut self, graph: GraphPayload) {
     let voice_count = resolve_voice_count(&graph.modules);
     self.voice_count = voice_count;
+    let graph_seed = graph.seed.unwrap_or(self.random_seed);
 
     // Preserve sequencer state before clearing (keyed by module_id + voice_index)
     let mut saved_sequencer_ticks: HashMap<(String, Option<usize>), f64> = HashMap::new();
@@ -576,15 +1556,19 @@ impl GraphEngine {
     for module in &graph.modules {
       let module_type = normalize_module_type(&module.kind);
       let params = module.params.clone().unwrap_or_default();
-      let is_poly = is_poly_type(module_type);
+      let is_poly = is_poly_module(module_type, &params);
       let instance_count = if is_poly { voice_count } else { 1 };
       for voice_index in 0..instance_count {
+        let id_hash = hash_module_id(&module.id);
         let mut node = ModuleNode::new(
           module_type,
           if is_poly { Some(voice_index) } else { None },
           &params,
           self.sample_rate,
+          module_seed(graph_seed, id_hash),
         );
+        node.bypass = module.bypass.unwrap_or(false);
+        node.id_hash = id_hash;
 
         // Restore sequencer state if we have saved state for this module
         if let ModuleState::MidiFileSequencer(ref mut state) = node.state {
@@ -601,22 +1585,132 @@ impl GraphEngine {
     }
 
 
+    let (input_buffers, output_buffers) = modules.iter().map(node_buffers).unzip();
+    self.install_graph(modules, module_map, input_buffers, output_buffers, graph);
+  }
+
+  /// Apply an edit to the loaded graph without rebuilding the modules that
+  /// stay: their DSP state (delay lines, envelopes, phases) and live params
+  /// carry on. Removed modules take their cables with them; added modules
+  /// are appended in the order given. A diff that changes the polyphony
+  /// falls back to a full rebuild.
+  pub fn set_graph_diff(
+    &mut self,
+    added_modules: &[ModuleSpecJson],
+    removed_module_ids: &[String],
+    added_connections: &[ConnectionJson],
+    removed_connections: &[ConnectionJson],
+  ) -> Result<(), GraphError> {
+    for id in removed_module_ids {
+      if !self.module_map.contains_key(id) {
+        return Err(GraphError::invalid(format!("Cannot remove unknown module {id}")));
+      }
+    }
+    for module in added_modules {
+      let taken = self.module_map.contains_key(&module.id) && !removed_module_ids.contains(&module.id);
+      if taken || added_modules.iter().filter(|other| other.id == module.id).count() > 1 {
+        return Err(GraphError::invalid(format!("Duplicate module id {}", module.id)));
+      }
+    }
+    for cable in removed_connections {
+      if !self.spec.connections.iter().any(|existing| same_cable(existing, cable)) {
+        return Err(GraphError::invalid(format!(
+          "Cannot remove unknown connection {}:{} -> {}:{}",
+          cable.from.module_id, cable.from.port_id, cable.to.module_id, cable.to.port_id,
+        )));
+      }
+    }
+
+    let mut graph = self.live_spec();
+    let removed = |id: &String| removed_module_ids.contains(id);
+    graph.modules.retain(|module| !removed(&module.id));
+    graph.modules.extend_from_slice(added_modules);
+    graph.connections.retain(|existing| {
+      !removed(&existing.from.module_id)
+        && !removed(&existing.to.module_id)
+        && !removed_connections.iter().any(|cable| same_cable(existing, cable))
+    });
+    graph.connections.extend_from_slice(added_connections);
+    if let Some(taps) = &mut graph.taps {
+      taps.retain(|tap| !removed(&tap.module_id));
+    }
+
+    if resolve_voice_count(&graph.modules) != self.voice_count {
+      self.set_graph(graph);
+      return Ok(());
+    }
+    let graph_seed = graph.seed.unwrap_or(self.random_seed);
+
+    // Lift the surviving nodes (and their buffers) out by index, then lay
+    // the merged graph out in spec order.
+    let mut old_nodes: Vec<Option<ModuleNode>> = std::mem::take(&mut self.modules).into_iter().map(Some).collect();
+    let mut old_inputs: Vec<Option<Vec<Buffer>>> =
+      std::mem::take(&mut self.input_buffers).into_iter().map(Some).collect();
+    let mut old_outputs: Vec<Option<Vec<Buffer>>> =
+      std::mem::take(&mut self.output_buffers).into_iter().map(Some).collect();
+    let old_map = std::mem::take(&mut self.module_map);
+
+    let mut modules = Vec::new();
+    let mut module_map: HashMap<String, Vec<usize>> = HashMap::new();
     let mut input_buffers = Vec::new();
     let mut output_buffers = Vec::new();
-
-    for node in &modules {
-      let mut inputs = Vec::new();
-      let mut outputs = Vec::new();
-      for port in &node.inputs {
-        inputs.push(Buffer::new(port.channels, 0));
+    for module in &graph.modules {
+      let kept = old_map.get(&module.id).filter(|_| !added_modules.iter().any(|added| added.id == module.id));
+      if let Some(indices) = kept {
+        for &old in indices {
+          let (Some(mut node), Some(inputs), Some(outputs)) =
+            (old_nodes[old].take(), old_inputs[old].take(), old_outputs[old].take())
+          else {
+            continue;
+          };
+          for edges in &mut node.connections {
+            edges.clear();
+          }
+          module_map.entry(module.id.clone()).or_default().push(modules.len());
+          modules.push(node);
+          input_buffers.push(inputs);
+          output_buffers.push(outputs);
+        }
+        continue;
       }
-      for port in &node.outputs {
-        outputs.push(Buffer::new(port.channels, 0));
+      let module_type = normalize_module_type(&module.kind);
+      let params = module.params.clone().unwrap_or_default();
+      let is_poly = is_poly_module(module_type, &params);
+      let instance_count = if is_poly { self.voice_count } else { 1 };
+      for voice_index in 0..instance_count {
+        let id_hash = hash_module_id(&module.id);
+        let mut node = ModuleNode::new(
+          module_type,
+          if is_poly { Some(voice_index) } else { None },
+          &params,
+          self.sample_rate,
+          module_seed(graph_seed, id_hash),
+        );
+        node.bypass = module.bypass.unwrap_or(false);
+        node.id_hash = id_hash;
+        let (inputs, outputs) = node_buffers(&node);
+        module_map.entry(module.id.clone()).or_default().push(modules.len());
+        modules.push(node);
+        input_buffers.push(inputs);
+        output_buffers.push(outputs);
       }
-      input_buffers.push(inputs);
-      output_buffers.push(outputs);
     }
 
+    self.install_graph(modules, module_map, input_buffers, output_buffers, graph);
+    Ok(())
+  }
+
+  /// Wire `graph`'s connections between freshly laid-out `modules` and
+  /// derive the render state (order, outputs, taps, buses) from them.
+  fn install_graph(
+    &mut self,
+    mut modules: Vec<ModuleNode>,
+    module_map: HashMap<String, Vec<usize>>,
+    input_buffers: Vec<Vec<Buffer>>,
+    output_buffers: Vec<Vec<Buffer>>,
+    graph: GraphPayload,
+  ) {
+    self.diagnostics.clear();
     for connection in &graph.connections {
       let from_indices = module_map.get(&connection.from.module_id);
       let to_indices = module_map.get(&connection.to.module_id);
@@ -624,20 +1718,45 @@ impl GraphEngine {
       let Some(to_list) = to_indices else { continue };
       let from_type = modules[from_list[0]].module_type;
       let to_type = modules[to_list[0]].module_type;
+      // Index-mapped ports can still be past a `cv-bank`'s channel count.
       let source_port = match output_port_index(from_type, &connection.from.port_id) {
-        Some(index) => index,
-        None => continue,
+        Some(index) if index < modules[from_list[0]].outputs.len() => index,
+        _ => continue,
       };
       let target_port = match input_port_index(to_type, &connection.to.port_id) {
         Some(index) => index,
         None => continue,
       };
 
-      let source_is_poly = is_poly_type(from_type);
-      let target_is_poly = is_poly_type(to_type);
-      let is_audio = connection.kind == "audio";
+      let source_is_poly = modules[from_list[0]].voice_index.is_some();
+      let target_is_poly = modules[to_list[0]].voice_index.is_some();
+      // `stereo` cables are audio cables; the port channel counts decide
+      // whether the pair survives.
+      let is_audio = matches!(connection.kind.as_str(), "audio" | "stereo");
 
-      if source_is_poly && target_is_poly {
+      if let (Some(voice), true) = (connection.from.voice, source_is_poly) {
+        let Some(&source) = from_list.get(voice) else {
+          self.diagnostics.push(format!(
+            "Dropped connection {}:{} -> {}:{}: voice {} out of range ({} voices)",
+            connection.from.module_id,
+            connection.from.port_id,
+            connection.to.module_id,
+            connection.to.port_id,
+            voice,
+            from_list.len(),
+          ));
+          continue;
+        };
+        let targets = if target_is_poly { &to_list[..] } else { &to_list[..1] };
+        for &target in targets {
+          modules[target].connections[target_port].push(ConnectionEdge {
+            source_module: source,
+            source_port,
+            gain: 1.0,
+            pan: 0.0,
+          });
+        }
+      } else if source_is_poly && target_is_poly {
         let count = from_list.len().min(to_list.len());
         for i in 0..count {
           let target = to_list[i];
@@ -645,6 +1764,7 @@ impl GraphEngine {
             source_module: from_list[i],
             source_port,
             gain: 1.0,
+            pan: 0.0,
           };
           modules[target].connections[target_port].push(edge);
         }
@@ -652,11 +1772,16 @@ impl GraphEngine {
         if is_audio {
           let gain = 1.0 / from_list.len().max(1) as f32;
           let target = to_list[0];
-          for &source in from_list {
+          // Voices are spread evenly from left to right; the Output's
+          // `voiceSpread` scales the positions at mix time.
+          let spread_voices = to_type == ModuleType::Output && from_list.len() > 1;
+          for (voice, &source) in from_list.iter().enumerate() {
+            let pan = if spread_voices { 2.0 * voice as f32 / (from_list.len() - 1) as f32 - 1.0 } else { 0.0 };
             modules[target].connections[target_port].push(ConnectionEdge {
               source_module: source,
               source_port,
               gain,
+              pan,
             });
           }
         } else {
@@ -665,6 +1790,7 @@ impl GraphEngine {
             source_module: from_list[0],
             source_port,
             gain: 1.0,
+            pan: 0.0,
           });
         }
       } else if !source_is_poly && target_is_poly {
@@ -673,6 +1799,7 @@ impl GraphEngine {
             source_module: from_list[0],
             source_port,
             gain: 1.0,
+            pan: 0.0,
           });
         }
       } else {
@@ -681,10 +1808,13 @@ impl GraphEngine {
           source_module: from_list[0],
           source_port,
           gain: 1.0,
+          pan: 0.0,
         });
       }
     }
 
+    connect_buses(&mut modules, &module_map);
+
     let order = compute_order(&modules);
     let output_indices = modules
       .iter()
@@ -692,7 +1822,7 @@ impl GraphEngine {
       .filter_map(|(idx, node)| if node.module_type == ModuleType::Output { Some(idx) } else { None })
       .collect::<Vec<_>>();
 
-    let taps = build_taps(&graph.taps, &modules, &module_map);
+    let taps = build_taps(&graph.taps, &modules, &module_map, self.stereo_taps);
 
     self.modules = modules;
     self.input_buffers = input_buffers;
@@ -701,7 +1831,11 @@ impl GraphEngine {
     self.order = order;
     self.output_indices = output_indices;
     self.taps = taps;
-    self.output_channels = 2 + self.taps.len();
+    self.update_bus_layout();
+    self.can_idle = !self_driven_reaches_output(&self.modules, &self.module_map, &graph.connections);
+    self.update_latency();
+    self.wake();
+    self.spec = graph;
   }
 
   fn ensure_output(&mut self, frames: usize) {
@@ -725,11 +1859,17 @@ impl ModuleNode {
     voice_index: Option<usize>,
     params: &HashMap<String, serde_json::Value>,
     sample_rate: f32,
+    seed: u32,
   ) -> Self {
+    // Lets the tests reach the load-stage fallback of set_graph_json.
+    #[cfg(test)]
+    if params.contains_key("testPanic") {
+      panic!("test panic in {module_type:?}");
+    }
     let inputs = input_ports(module_type);
-    let outputs = output_ports(module_type);
+    let outputs = ports::output_ports_for(module_type, params);
     let connections = (0..inputs.len()).map(|_| Vec::new()).collect();
-    let state = instantiate::create_state(module_type, params, sample_rate, voice_index);
+    let state = instantiate::create_state(module_type, params, sample_rate, voice_index, seed);
 
     Self {
       voice_index,
@@ -738,23 +1878,406 @@ impl ModuleNode {
       outputs,
       connections,
       state,
+      params: params.clone(),
+      bypass: false,
+      faulted: false,
+      id_hash: 0,
+      event_prev: -1.0,
+      event_count: 0,
     }
   }
 
-  fn apply_param(&mut self, param: &str, value: f32) {
+  /// Latency this node adds; a bypassed node passes its input straight on.
+  fn latency(&self) -> usize {
+    if self.bypass {
+      0
+    } else {
+      self.state.latency()
+    }
+  }
+
+  /// Constrain `value` and apply it to the DSP state only; returns what was
+  /// applied.
+  /// Stored value of a numeric param, or its table default when the graph
+  /// left it out.
+  fn param(&self, param: &str) -> Option<f32> {
+    let meta = param_meta(self.module_type, param);
+    match instantiate::stored_param(self.module_type, &self.params, param) {
+      Some(value) => Some(meta.map_or(value, |meta| meta.constrain(value))),
+      None => meta.map(|meta| meta.default),
+    }
+  }
+
+  fn modulate_param(&mut self, param: &str, value: f32) -> f32 {
+    let value = param_meta(self.module_type, param).map_or(value, |meta| meta.constrain(value));
     instantiate::apply_param(&mut self.state, param, value);
+    value
+  }
+
+  fn apply_param(&mut self, param: &str, value: f32) {
+    let value = self.modulate_param(param, value);
+    instantiate::forget_param_spellings(self.module_type, &mut self.params, param);
+    match self.params.get_mut(param) {
+      Some(stored) => *stored = serde_json::Value::from(value),
+      None => {
+        self.params.insert(param.to_string(), serde_json::Value::from(value));
+      }
+    }
   }
 
   fn apply_param_str(&mut self, param: &str, value: &str) {
     instantiate::apply_param_str(&mut self.state, param, value);
+    // Two forms of the same notes: a rebuild must load the one set last.
+    match param {
+      "midiData" => self.params.remove("midiFile"),
+      "midiFile" => self.params.remove("midiData"),
+      _ => None,
+    };
+    self.params.insert(param.to_string(), serde_json::Value::from(value));
+  }
+
+  fn apply_param_json(&mut self, param: &str, value: &serde_json::Value) {
+    instantiate::apply_param_json(&mut self.state, param, value);
+    self.params.insert(param.to_string(), value.clone());
+  }
+
+  /// Clear the DSP state in place: no allocation, and loaded samples,
+  /// tunes and sequencer patterns are kept.
+  fn reset(&mut self) {
+    match &mut self.state {
+      ModuleState::Vco(state) => state.vco.reset(),
+      ModuleState::Supersaw(state) => state.supersaw.reset(),
+      ModuleState::Karplus(state) => state.karplus.reset(),
+      ModuleState::NesOsc(state) => state.nes_osc.reset(),
+      ModuleState::SnesOsc(state) => state.snes_osc.reset(),
+      ModuleState::Noise(state) => state.noise.reset(),
+      ModuleState::Tb303(state) => state.tb303.reset(),
+      ModuleState::FmOp(state) => state.op.reset(),
+      ModuleState::FmMatrix(state) => state.matrix.reset(),
+      ModuleState::Shepard(state) => state.shepard.reset(),
+      ModuleState::PipeOrgan(state) => state.organ.reset(),
+      ModuleState::SpectralSwarm(state) => state.swarm.reset(),
+      ModuleState::Resonator(state) => state.resonator.reset(),
+      ModuleState::Wavetable(state) => state.wavetable.reset(),
+      ModuleState::Granular(state) => state.granular.reset(),
+      ModuleState::ParticleCloud(state) => state.cloud.reset(),
+      ModuleState::SamplePlayer(state) => state.player.reset(),
+      ModuleState::Vcf(state) => state.vcf.reset(),
+      ModuleState::Hpf(state) => state.hpf.reset(),
+      ModuleState::DcBlock(state) => state.blocker.reset(),
+      ModuleState::Width(state) => state.width.reset(),
+      ModuleState::RingMod(state) => state.ring.reset(),
+      ModuleState::Lfo(state) => state.lfo.reset(),
+      ModuleState::Adsr(state) => state.adsr.reset(),
+      ModuleState::SampleHold(state) => state.sample_hold.reset(),
+      ModuleState::Slew(state) => state.slew.reset(),
+      ModuleState::EnvFollower(state) => state.follower.reset(),
+      ModuleState::PeakMeter(state) => state.meter.reset(),
+      ModuleState::Chaos(state) => state.chaos.reset(),
+      ModuleState::Chorus(state) => state.chorus.reset(),
+      ModuleState::Ensemble(state) => state.ensemble.reset(),
+      ModuleState::Choir(state) => state.choir.reset(),
+      ModuleState::Vocoder(state) => state.vocoder.reset(),
+      ModuleState::Delay(state) => state.delay.reset(),
+      ModuleState::GranularDelay(state) => state.delay.reset(),
+      ModuleState::TapeDelay(state) => state.delay.reset(),
+      ModuleState::SpringReverb(state) => state.reverb.reset(),
+      ModuleState::Reverb(state) => state.reverb.reset(),
+      ModuleState::Phaser(state) => state.phaser.reset(),
+      ModuleState::BitCrusher(state) => state.crusher.reset(),
+      ModuleState::PitchShifter(state) => state.shifter.reset(),
+      ModuleState::Compressor(state) => state.compressor.reset(),
+      ModuleState::Gate(state) => state.gate.reset(),
+      ModuleState::Clock(state) => state.clock.reset(),
+      ModuleState::ClockDiv(state) => state.divider.reset(),
+      ModuleState::Arpeggiator(state) => state.arp.reset(),
+      ModuleState::StepSequencer(state) => state.seq.reset(),
+      ModuleState::DrumSequencer(state) => state.seq.reset(),
+      ModuleState::Euclidean(state) => state.euclidean.reset(),
+      ModuleState::CvBank(state) => state.bank.reset(),
+      ModuleState::MidiFileSequencer(state) => state.seq.reset(),
+      ModuleState::TuringMachine(state) => state.turing.reset(),
+      ModuleState::SidPlayer(state) => state.sid_player.reset(),
+      ModuleState::AyPlayer(state) => state.ay_player.reset(),
+      ModuleState::Kick909(state) => state.kick.reset(),
+      ModuleState::Snare909(state) => state.snare.reset(),
+      ModuleState::HiHat909(state) => state.hihat.reset(),
+      ModuleState::Clap909(state) => state.clap.reset(),
+      ModuleState::Tom909(state) => state.tom.reset(),
+      ModuleState::Rimshot909(state) => state.rimshot.reset(),
+      ModuleState::Kick808(state) => state.kick.reset(),
+      ModuleState::Snare808(state) => state.snare.reset(),
+      ModuleState::HiHat808(state) => state.hihat.reset(),
+      ModuleState::Cowbell808(state) => state.cowbell.reset(),
+      ModuleState::Clap808(state) => state.clap.reset(),
+      ModuleState::Tom808(state) => state.tom.reset(),
+      // Land glides in flight; the held note and gate are input, not state.
+      ModuleState::Control(state) => {
+        state.cv = state.cv_target;
+        state.cv_remaining = 0;
+        state.velocity = state.velocity_target;
+        state.velocity_remaining = 0;
+        state.retrigger_samples = 0;
+        state.sync_remaining = 0;
+      }
+      // Stateless modules.
+      ModuleState::Gain(_)
+      | ModuleState::CvVca(_)
+      | ModuleState::Mixer(_)
+      | ModuleState::MixerWide(_)
+      | ModuleState::Mixer8(_)
+      | ModuleState::Crossfader(_)
+      | ModuleState::Multiple
+      | ModuleState::Pan(_)
+      | ModuleState::ModRouter(_)
+      | ModuleState::Attenuverter(_)
+      | ModuleState::Quantizer(_)
+      | ModuleState::Logic(_)
+      | ModuleState::Distortion(_)
+      | ModuleState::Wavefolder(_)
+      | ModuleState::Output(_)
+      | ModuleState::Lab(_)
+      | ModuleState::AudioIn(_)
+      | ModuleState::Send(_)
+      | ModuleState::Return(_)
+      | ModuleState::Scope
+      | ModuleState::Notes => {}
+    }
+    self.event_prev = -1.0;
+    self.event_count = 0;
+  }
+
+  fn set_sample_rate(&mut self, sample_rate: f32) {
+    match &mut self.state {
+      ModuleState::Vco(state) => state.vco.set_sample_rate(sample_rate),
+      ModuleState::Supersaw(state) => state.supersaw.set_sample_rate(sample_rate),
+      ModuleState::Karplus(state) => state.karplus.set_sample_rate(sample_rate),
+      ModuleState::NesOsc(state) => state.nes_osc.set_sample_rate(sample_rate),
+      ModuleState::SnesOsc(state) => state.snes_osc.set_sample_rate(sample_rate),
+      ModuleState::Tb303(state) => state.tb303.set_sample_rate(sample_rate),
+      ModuleState::FmOp(state) => state.op.set_sample_rate(sample_rate),
+      ModuleState::FmMatrix(state) => state.matrix.set_sample_rate(sample_rate),
+      ModuleState::Shepard(state) => state.shepard.set_sample_rate(sample_rate),
+      ModuleState::PipeOrgan(state) => state.organ.set_sample_rate(sample_rate),
+      ModuleState::SpectralSwarm(state) => state.swarm.set_sample_rate(sample_rate),
+      ModuleState::Resonator(state) => state.resonator.set_sample_rate(sample_rate),
+      ModuleState::Wavetable(state) => state.wavetable.set_sample_rate(sample_rate),
+      ModuleState::Granular(state) => state.granular.set_sample_rate(sample_rate),
+      ModuleState::ParticleCloud(state) => state.cloud.set_sample_rate(sample_rate),
+      ModuleState::SamplePlayer(state) => state.player.set_sample_rate(sample_rate),
+      ModuleState::Vcf(state) => {
+        state.vcf.set_sample_rate(sample_rate);
+        state.cutoff.set_glide(instantiate::vcf_glide_samples(sample_rate));
+        state.env_amount.set_glide(instantiate::vcf_glide_samples(sample_rate));
+      }
+      ModuleState::Hpf(state) => state.hpf.set_sample_rate(sample_rate),
+      ModuleState::Lfo(state) => state.lfo.set_sample_rate(sample_rate),
+      ModuleState::Adsr(state) => state.adsr.set_sample_rate(sample_rate),
+      ModuleState::SampleHold(state) => state.sample_hold.set_sample_rate(sample_rate),
+      ModuleState::Slew(state) => state.slew.set_sample_rate(sample_rate),
+      ModuleState::EnvFollower(state) => state.follower.set_sample_rate(sample_rate),
+      ModuleState::PeakMeter(state) => state.meter.set_sample_rate(sample_rate),
+      ModuleState::Chaos(state) => state.chaos.set_sample_rate(sample_rate),
+      ModuleState::Chorus(state) => state.chorus.set_sample_rate(sample_rate),
+      ModuleState::Ensemble(state) => state.ensemble.set_sample_rate(sample_rate),
+      ModuleState::Choir(state) => state.choir.set_sample_rate(sample_rate),
+      ModuleState::Vocoder(state) => state.vocoder.set_sample_rate(sample_rate),
+      ModuleState::Delay(state) => state.delay.set_sample_rate(sample_rate),
+      ModuleState::GranularDelay(state) => state.delay.set_sample_rate(sample_rate),
+      ModuleState::TapeDelay(state) => state.delay.set_sample_rate(sample_rate),
+      ModuleState::SpringReverb(state) => state.reverb.set_sample_rate(sample_rate),
+      ModuleState::Reverb(state) => state.reverb.set_sample_rate(sample_rate),
+      ModuleState::Phaser(state) => state.phaser.set_sample_rate(sample_rate),
+      ModuleState::PitchShifter(state) => state.shifter.set_sample_rate(sample_rate),
+      ModuleState::Compressor(state) => state.compressor.set_sample_rate(sample_rate),
+      ModuleState::Gate(state) => state.gate.set_sample_rate(sample_rate),
+      ModuleState::Width(state) => state.width.set_sample_rate(sample_rate),
+      ModuleState::DcBlock(state) => state.blocker.set_sample_rate(sample_rate),
+      ModuleState::RingMod(state) => state.ring.set_sample_rate(sample_rate),
+      ModuleState::Clock(state) => state.clock.set_sample_rate(sample_rate),
+      ModuleState::ClockDiv(state) => state.divider.set_sample_rate(sample_rate),
+      ModuleState::Arpeggiator(state) => state.arp.set_sample_rate(sample_rate),
+      ModuleState::StepSequencer(state) => state.seq.set_sample_rate(sample_rate),
+      ModuleState::DrumSequencer(state) => state.seq.set_sample_rate(sample_rate),
+      ModuleState::Euclidean(state) => state.euclidean.set_sample_rate(sample_rate),
+      ModuleState::MidiFileSequencer(state) => state.seq.set_sample_rate(sample_rate),
+      ModuleState::TuringMachine(state) => state.turing.set_sample_rate(sample_rate),
+      ModuleState::SidPlayer(state) => state.sid_player.set_sample_rate(sample_rate),
+      ModuleState::AyPlayer(state) => state.ay_player.set_sample_rate(sample_rate),
+      ModuleState::Kick909(state) => state.kick.set_sample_rate(sample_rate),
+      ModuleState::Snare909(state) => state.snare.set_sample_rate(sample_rate),
+      ModuleState::HiHat909(state) => state.hihat.set_sample_rate(sample_rate),
+      ModuleState::Clap909(state) => state.clap.set_sample_rate(sample_rate),
+      ModuleState::Tom909(state) => state.tom.set_sample_rate(sample_rate),
+      ModuleState::Rimshot909(state) => state.rimshot.set_sample_rate(sample_rate),
+      ModuleState::Kick808(state) => state.kick.set_sample_rate(sample_rate),
+      ModuleState::Snare808(state) => state.snare.set_sample_rate(sample_rate),
+      ModuleState::HiHat808(state) => state.hihat.set_sample_rate(sample_rate),
+      ModuleState::Cowbell808(state) => state.cowbell.set_sample_rate(sample_rate),
+      ModuleState::Clap808(state) => state.clap.set_sample_rate(sample_rate),
+      ModuleState::Tom808(state) => state.tom.set_sample_rate(sample_rate),
+      ModuleState::Control(state) => state.sample_rate = sample_rate,
+      // Rate-independent modules.
+      ModuleState::Noise(_)
+      | ModuleState::Gain(_)
+      | ModuleState::CvVca(_)
+      | ModuleState::Mixer(_)
+      | ModuleState::MixerWide(_)
+      | ModuleState::Mixer8(_)
+      | ModuleState::Crossfader(_)
+      | ModuleState::Multiple
+      | ModuleState::Pan(_)
+      | ModuleState::ModRouter(_)
+      | ModuleState::Attenuverter(_)
+      | ModuleState::Quantizer(_)
+      | ModuleState::Logic(_)
+      | ModuleState::Distortion(_)
+      | ModuleState::Wavefolder(_)
+      | ModuleState::BitCrusher(_)
+      | ModuleState::CvBank(_)
+      | ModuleState::Output(_)
+      | ModuleState::Lab(_)
+      | ModuleState::AudioIn(_)
+      | ModuleState::Send(_)
+      | ModuleState::Return(_)
+      | ModuleState::Scope
+      | ModuleState::Notes => {}
+    }
   }
 
   fn process(&mut self, inputs: &[Buffer], outputs: &mut [Buffer], frames: usize, _sample_rate: f32) {
     process::process_module(&mut self.state, &self.connections, inputs, outputs, frames);
   }
 }
+/// MIDI notes for [`GraphEngine::set_module_midi_data`], parsed and in
+/// their `midiData` param form.
+pub struct MidiData {
+  file: SmfFile,
+  json: String,
+}
+
+impl MidiData {
+  /// From a parsed `.mid` file; its JSON is built with [`midi_data_json`].
+  pub fn from_file(file: SmfFile) -> Self {
+    let json = midi_data_json(&file);
+    Self { file, json }
+  }
+
+  /// From a `midiData` param value, with the defaults of the sequencer's
+  /// own parser (100 velocity, sixteenth-note duration). `None` if `json`
+  /// is not a JSON object.
+  pub fn from_json(json: String) -> Option<Self> {
+    let value: serde_json::Value = serde_json::from_str(&json).ok()?;
+    value.as_object()?;
+    let number = |value: &serde_json::Value, key: &str| value.get(key).and_then(serde_json::Value::as_f64);
+    let ticks_per_beat = number(&value, "ticksPerBeat").map_or(480, |ticks| (ticks as u32).max(1));
+    let parse_note = |note: &serde_json::Value| {
+      Some(MidiNote {
+        tick: number(note, "tick")? as u32,
+        note: (number(note, "note")? as u8).min(127),
+        velocity: number(note, "velocity").map_or(100, |velocity| (velocity as u8).min(127)),
+        duration: number(note, "duration").map_or(ticks_per_beat / 4, |duration| duration as u32),
+        ..MidiNote::default()
+      })
+    };
+    let tracks = value
+      .get("tracks")
+      .and_then(serde_json::Value::as_array)
+      .into_iter()
+      .flatten()
+      .take(MIDI_TRACKS)
+      .map(|track| SmfTrack {
+        name: track.get("name").and_then(serde_json::Value::as_str).unwrap_or_default().to_string(),
+        channel: number(track, "channel").map_or(0, |channel| channel as u8),
+        notes: track
+          .get("notes")
+          .and_then(serde_json::Value::as_array)
+          .into_iter()
+          .flatten()
+          .filter_map(parse_note)
+          .take(MAX_NOTES_PER_TRACK)
+          .collect(),
+      })
+      .collect();
+    let file = SmfFile {
+      ticks_per_beat,
+      total_ticks: number(&value, "totalTicks").map_or(0, |ticks| ticks as u32),
+      tempo: number(&value, "tempo").map_or(120.0, |tempo| tempo as f32),
+      tempo_map: Vec::new(),
+      tracks,
+      warnings: Vec::new(),
+    };
+    Some(Self { file, json })
+  }
+
+  pub fn file(&self) -> &SmfFile {
+    &self.file
+  }
+
+  /// The `midiData` param value.
+  pub fn json(&self) -> &str {
+    &self.json
+  }
+}
+
+/// The `midiData` param form of a parsed `.mid` file, as the UI's own
+/// MIDI parser writes it: `{ticksPerBeat, totalTicks, tempo, tracks: [{name,
+/// channel, notes: [{tick, note, velocity, duration}]}]}`.
+pub fn midi_data_json(file: &SmfFile) -> String {
+  let tracks: Vec<serde_json::Value> = file
+    .tracks
+    .iter()
+    .map(|track| {
+      let notes: Vec<serde_json::Value> = track
+        .notes
+        .iter()
+        .map(|note| {
+          serde_json::json!({
+            "tick": note.tick,
+            "note": note.note,
+            "velocity": note.velocity,
+            "duration": note.duration,
+          })
+        })
+        .collect();
+      serde_json::json!({ "name": track.name, "channel": track.channel, "notes": notes })
+    })
+    .collect();
+  serde_json::json!({
+    "ticksPerBeat": file.ticks_per_beat,
+    "totalTicks": file.total_ticks,
+    "tempo": file.tempo,
+    "tracks": tracks,
+  })
+  .to_string()
+}
+
+/// Text of a caught panic payload.
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+  match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+    (Some(message), _) => message.to_string(),
+    (_, Some(message)) => message.clone(),
+    _ => "unknown panic".to_string(),
+  }
+}
+
+/// Parse a graph payload, migrating it to the current schema. Also returns
+/// the descriptions of the migrations that ran.
+fn parse_graph_payload(payload: &str) -> Result<(GraphPayload, Vec<&'static str>), GraphError> {
+  let raw: serde_json::Value = serde_json::from_str(payload)?;
+  let (raw, applied) = migrate::migrate(raw).map_err(GraphError::invalid)?;
+  let graph: GraphPayload = serde_json::from_value(raw)?;
+  debug_assert_eq!(graph.schema_version, Some(CURRENT_SCHEMA_VERSION));
+  Ok((graph, applied))
+}
+
 fn normalize_module_type(raw: &str) -> ModuleType {
-  match raw {
+  parse_module_type(raw).unwrap_or(ModuleType::Oscillator)
+}
+
+/// Module type for a graph `type` string, `None` when unknown.
+fn parse_module_type(raw: &str) -> Option<ModuleType> {
+  let module_type = match raw {
     "oscillator" => ModuleType::Oscillator,
     "supersaw" => ModuleType::Supersaw,
     "karplus" => ModuleType::Karplus,
@@ -762,8 +2285,10 @@ fn normalize_module_type(raw: &str) -> ModuleType {
     "snes-osc" => ModuleType::SnesOsc,
     "noise" => ModuleType::Noise,
     "mod-router" => ModuleType::ModRouter,
+    "atten" => ModuleType::Attenuverter,
     "sample-hold" => ModuleType::SampleHold,
     "slew" => ModuleType::Slew,
+    "env-follower" => ModuleType::EnvFollower,
     "quantizer" => ModuleType::Quantizer,
     "ring-mod" => ModuleType::RingMod,
     "gain" => ModuleType::Gain,
@@ -774,10 +2299,14 @@ fn normalize_module_type(raw: &str) -> ModuleType {
     "adsr" => ModuleType::Adsr,
     "vcf" => ModuleType::Vcf,
     "hpf" => ModuleType::Hpf,
+    "dc-block" => ModuleType::DcBlock,
     "mixer" => ModuleType::Mixer,
-    "mixer-1x2" => ModuleType::MixerWide,
+    "mixer-wide" | "mixer-1x2" => ModuleType::MixerWide,
     "mixer-8" => ModuleType::Mixer8,
-    "crossfader" => ModuleType::Crossfader,
+    "crossfader" | "xfade" => ModuleType::Crossfader,
+    "mult" => ModuleType::Multiple,
+    "pan" => ModuleType::Pan,
+    "stereo-width" => ModuleType::Width,
     "chorus" => ModuleType::Chorus,
     "ensemble" => ModuleType::Ensemble,
     "choir" => ModuleType::Choir,
@@ -791,13 +2320,15 @@ fn normalize_module_type(raw: &str) -> ModuleType {
     "phaser" => ModuleType::Phaser,
     "distortion" => ModuleType::Distortion,
     "wavefolder" => ModuleType::Wavefolder,
+    "bitcrush" => ModuleType::BitCrusher,
     "compressor" => ModuleType::Compressor,
+    "noise-gate" => ModuleType::Gate,
     "control" => ModuleType::Control,
     "scope" => ModuleType::Scope,
-    "mario" => ModuleType::Mario,
+    "cv-bank" | "mario" => ModuleType::CvBank,
     "arpeggiator" => ModuleType::Arpeggiator,
     "step-sequencer" => ModuleType::StepSequencer,
-    "tb-303" => ModuleType::Tb303,
+    "tb-303" | "tb303" => ModuleType::Tb303,
     // TR-909 Drums
     "909-kick" => ModuleType::Kick909,
     "909-snare" => ModuleType::Snare909,
@@ -827,19 +2358,26 @@ fn normalize_module_type(raw: &str) -> ModuleType {
     "wavetable" => ModuleType::Wavetable,
     "granular" => ModuleType::Granular,
     "particle-cloud" => ModuleType::ParticleCloud,
+    "sample-player" => ModuleType::SamplePlayer,
     // Documentation
     "notes" => ModuleType::Notes,
+    "send" => ModuleType::Send,
+    "return" => ModuleType::Return,
     // Effects
     "pitch-shifter" => ModuleType::PitchShifter,
     "clock" => ModuleType::Clock,
+    "clock-div" => ModuleType::ClockDiv,
     "chaos" => ModuleType::Chaos,
+    "logic" => ModuleType::Logic,
+    "peak-meter" => ModuleType::PeakMeter,
     "turing-machine" | "turing" => ModuleType::TuringMachine,
     // SID Player
     "sid-player" => ModuleType::SidPlayer,
     // AY Player
     "ay-player" => ModuleType::AyPlayer,
-    _ => ModuleType::Oscillator,
-  }
+    _ => return None,
+  };
+  Some(module_type)
 }
 
 fn is_poly_type(module_type: ModuleType) -> bool {
@@ -853,28 +2391,231 @@ fn is_poly_type(module_type: ModuleType) -> bool {
       | ModuleType::Noise
       | ModuleType::PipeOrgan
       | ModuleType::ModRouter
+      | ModuleType::Attenuverter
       | ModuleType::SampleHold
       | ModuleType::Slew
+      | ModuleType::EnvFollower
       | ModuleType::Quantizer
       | ModuleType::Chaos
-      | ModuleType::TuringMachine
+      | ModuleType::Logic
+      | ModuleType::PeakMeter
       | ModuleType::RingMod
       | ModuleType::Gain
       | ModuleType::CvVca
+      | ModuleType::Crossfader
+      | ModuleType::Multiple
+      | ModuleType::Pan
       | ModuleType::Lfo
       | ModuleType::Adsr
       | ModuleType::Vcf
       | ModuleType::Hpf
+      | ModuleType::DcBlock
       | ModuleType::Mixer
       | ModuleType::MixerWide
       | ModuleType::Distortion
       | ModuleType::Wavefolder
+      | ModuleType::BitCrusher
       | ModuleType::FmMatrix
+      | ModuleType::Send
       | ModuleType::Control
       | ModuleType::MidiFileSequencer
   )
 }
 
+/// Poly types, plus modules that are mono by default but opt in with a
+/// `poly` param (acid lines want a single TB-303).
+fn is_poly_module(module_type: ModuleType, params: &HashMap<String, serde_json::Value>) -> bool {
+  is_poly_type(module_type) || (module_type == ModuleType::Tb303 && param_number(params, "poly", 0.0) > 0.5)
+}
+
+/// Wire every Send's bus feed (output 1) into each Return on the same `bus`
+/// name. Poly Sends are averaged like any poly-to-mono audio cable.
+fn connect_buses(modules: &mut [ModuleNode], module_map: &HashMap<String, Vec<usize>>) {
+  let bus_name = |node: &ModuleNode| -> String {
+    node.params.get("bus").and_then(|value| value.as_str()).unwrap_or("").to_string()
+  };
+  let mut sends: HashMap<String, Vec<(usize, f32)>> = HashMap::new();
+  for indices in module_map.values() {
+    let Some(&first) = indices.first() else { continue };
+    if modules[first].module_type == ModuleType::Send {
+      let gain = 1.0 / indices.len() as f32;
+      let feeds = sends.entry(bus_name(&modules[first])).or_default();
+      feeds.extend(indices.iter().map(|&index| (index, gain)));
+    }
+  }
+  for indices in module_map.values() {
+    for &index in indices {
+      if modules[index].module_type != ModuleType::Return {
+        continue;
+      }
+      let Some(feeds) = sends.get(&bus_name(&modules[index])) else { continue };
+      for &(source_module, gain) in feeds {
+        modules[index].connections[0].push(ConnectionEdge {
+          source_module,
+          source_port: 1,
+          gain,
+          pan: 0.0,
+        });
+      }
+    }
+  }
+}
+
+/// True when a self-driven module can bring a silent graph back: one of its
+/// audio, gate or sync cables, or a CV cable into a VCA, leads on to an Output
+/// through any cables or bus sends. Other CV cables only bend sounds that are
+/// already playing, so an LFO on a filter lets the engine idle once the
+/// voices have died out, while an LFO swelling a VCA keeps a drone awake.
+fn self_driven_reaches_output(
+  modules: &[ModuleNode],
+  module_map: &HashMap<String, Vec<usize>>,
+  connections: &[ConnectionJson],
+) -> bool {
+  let mut targets = vec![Vec::new(); modules.len()];
+  for (index, module) in modules.iter().enumerate() {
+    for edge in module.connections.iter().flatten() {
+      targets[edge.source_module].push(index);
+    }
+  }
+  let module_type = |id: &str| {
+    module_map
+      .get(id)
+      .and_then(|indices| indices.first())
+      .map(|&index| modules[index].module_type)
+  };
+  let wakes = |cable: &ConnectionJson| {
+    module_type(&cable.from.module_id).is_some_and(is_self_driven)
+      && (cable.kind != "cv"
+        || module_type(&cable.to.module_id).is_some_and(|target| is_amplitude_input(target, &cable.to.port_id)))
+  };
+  let mut stack: Vec<usize> = connections
+    .iter()
+    .filter(|cable| wakes(cable))
+    .filter_map(|cable| module_map.get(&cable.to.module_id))
+    .flatten()
+    .copied()
+    .collect();
+  let mut reached = vec![false; modules.len()];
+  while let Some(index) = stack.pop() {
+    if modules[index].module_type == ModuleType::Output {
+      return true;
+    }
+    for &target in &targets[index] {
+      if !reached[target] {
+        reached[target] = true;
+        stack.push(target);
+      }
+    }
+  }
+  false
+}
+
+/// CV inputs that scale a signal's level, so modulating them can bring a
+/// silent output back.
+fn is_amplitude_input(module_type: ModuleType, port_id: &str) -> bool {
+  match module_type {
+    ModuleType::Gain => port_id == "cv",
+    ModuleType::CvVca => matches!(port_id, "in" | "cv"),
+    _ => false,
+  }
+}
+
+/// Modules that emit notes or triggers on their own schedule, so the graph may
+/// be silent between events without being finished.
+fn is_self_driven(module_type: ModuleType) -> bool {
+  matches!(
+    module_type,
+    ModuleType::Lfo
+      | ModuleType::Chaos
+      | ModuleType::Clock
+      | ModuleType::Arpeggiator
+      | ModuleType::StepSequencer
+      | ModuleType::DrumSequencer
+      | ModuleType::Euclidean
+      | ModuleType::CvBank
+      | ModuleType::MidiFileSequencer
+      | ModuleType::TuringMachine
+      | ModuleType::Tb303
+      | ModuleType::SidPlayer
+      | ModuleType::AyPlayer
+      | ModuleType::SamplePlayer
+  )
+}
+
+/// RMS over all channels of a buffer.
+fn block_rms(buffer: &Buffer) -> Sample {
+  let mut sum = 0.0;
+  let mut count = 0;
+  for channel in 0..buffer.channel_count() {
+    for &s in buffer.channel(channel) {
+      sum += s * s;
+      count += 1;
+    }
+  }
+  if count == 0 { 0.0 } else { (sum / count as Sample).sqrt() }
+}
+
+/// Replace NaN/Inf with silence and, with a ceiling, soft-clip: samples below
+/// `LIMITER_KNEE * ceiling` pass unchanged, louder ones bend towards the
+/// ceiling without reaching it.
+fn limit_output(samples: &mut [Sample], ceiling: Option<Sample>) {
+  for sample in samples {
+    if !sample.is_finite() {
+      *sample = 0.0;
+      continue;
+    }
+    let Some(ceiling) = ceiling else { continue };
+    let knee = ceiling * LIMITER_KNEE;
+    let level = sample.abs();
+    if level > knee {
+      let range = ceiling - knee;
+      *sample = sample.signum() * (knee + range * ((level - knee) / range).tanh());
+    }
+  }
+}
+
+/// Effects and processors whose bypass passes input 0 straight to output 0.
+fn is_bypass_passthrough(module_type: ModuleType) -> bool {
+  matches!(
+    module_type,
+    ModuleType::Vcf
+      | ModuleType::Hpf
+      | ModuleType::DcBlock
+      | ModuleType::Gain
+      | ModuleType::CvVca
+      | ModuleType::Attenuverter
+      | ModuleType::Pan
+      | ModuleType::Width
+      | ModuleType::RingMod
+      | ModuleType::Chorus
+      | ModuleType::Ensemble
+      | ModuleType::Choir
+      | ModuleType::Delay
+      | ModuleType::GranularDelay
+      | ModuleType::TapeDelay
+      | ModuleType::SpringReverb
+      | ModuleType::Reverb
+      | ModuleType::Phaser
+      | ModuleType::Distortion
+      | ModuleType::Wavefolder
+      | ModuleType::BitCrusher
+      | ModuleType::PitchShifter
+      | ModuleType::Compressor
+      | ModuleType::Gate
+  )
+}
+
+/// Empty input/output buffers shaped after a node's ports.
+fn node_buffers(node: &ModuleNode) -> (Vec<Buffer>, Vec<Buffer>) {
+  let inputs = node.inputs.iter().map(|port| Buffer::new(port.channels, 0)).collect();
+  let outputs = node.outputs.iter().map(|port| Buffer::new(port.channels, 0)).collect();
+  (inputs, outputs)
+}
+
+/// Same endpoints (and source voice); the cable `kind` is not compared.
+fn same_cable(a: &ConnectionJson, b: &ConnectionJson) -> bool {
+  a.from == b.from && a.to == b.to
+}
 
 fn resolve_voice_count(modules: &[ModuleSpecJson]) -> usize {
   let mut voice_count = 1.0;
@@ -892,36 +2633,43 @@ fn resolve_voice_count(modules: &[ModuleSpecJson]) -> usize {
   rounded.max(1)
 }
 
+/// Channel count of a `cv-bank`; `mario` patches carry no `channels` param
+/// and get the original 5.
+pub(crate) fn bank_channels(params: &HashMap<String, serde_json::Value>) -> usize {
+  param_number(params, "channels", MARIO_CHANNELS as f32)
+    .round()
+    .clamp(1.0, MAX_BANK_CHANNELS as f32) as usize
+}
+
 pub(crate) fn param_number(
   params: &HashMap<String, serde_json::Value>,
   key: &str,
   default: f32,
 ) -> f32 {
-  let value = params.get(key);
-  match value {
-    Some(serde_json::Value::Number(number)) => {
-      let raw = number.as_f64().unwrap_or(default as f64) as f32;
+  param_value(params, key).unwrap_or(default)
+}
+
+/// Numeric value of a param as a graph stores it: a number, a boolean (0 or
+/// 1) or an option name `map_string_param` knows. `None` if missing.
+pub(crate) fn param_value(params: &HashMap<String, serde_json::Value>, key: &str) -> Option<f32> {
+  match params.get(key)? {
+    serde_json::Value::Number(number) => {
+      let raw = number.as_f64()? as f32;
       if key == "slope" && raw > 1.0 {
-        if raw >= 24.0 { 1.0 } else { 0.0 }
-      } else {
-        raw
-      }
-    }
-    Some(serde_json::Value::Bool(flag)) => {
-      if *flag {
-        1.0
+        Some(if raw >= 24.0 { 1.0 } else { 0.0 })
       } else {
-        0.0
+        Some(raw)
       }
     }
-    Some(serde_json::Value::String(text)) => map_string_param(key, text, default),
-    _ => default,
+    serde_json::Value::Bool(flag) => Some(if *flag { 1.0 } else { 0.0 }),
+    serde_json::Value::String(text) => Some(map_string_param(key, text, f32::NAN)).filter(|value| !value.is_nan()),
+    _ => None,
   }
 }
 
-fn map_string_param(key: &str, text: &str, default: f32) -> f32 {
+pub(crate) fn map_string_param(key: &str, text: &str, default: f32) -> f32 {
   match key {
-    "type" | "waveform" | "shape" => match text {
+    "type" | "waveform" | "shape" | "carrierShape" | "carrier-shape" => match text {
       "sine" => 0.0,
       "triangle" => 1.0,
       "saw" | "sawtooth" => 2.0,
@@ -935,6 +2683,22 @@ fn map_string_param(key: &str, text: &str, default: f32) -> f32 {
       "notch" => 3.0,
       _ => default,
     },
+    "glideMode" | "glide-mode" => match text {
+      "always" => 0.0,
+      "legato" => 1.0,
+      _ => default,
+    },
+    "ratio" => CLOCK_DIV_LABELS.iter().position(|&label| label == text).map_or(default, |index| index as f32),
+    "curve" => match text {
+      "linear" => 0.0,
+      "equal-power" | "equal" => 1.0,
+      _ => default,
+    },
+    "glideCurve" | "glide-curve" => match text {
+      "linear" => 0.0,
+      "exponential" | "exp" => 1.0,
+      _ => default,
+    },
     "model" => match text {
       "svf" => 0.0,
       "ladder" => 1.0,
@@ -944,6 +2708,10 @@ fn map_string_param(key: &str, text: &str, default: f32) -> f32 {
       "white" => 0.0,
       "pink" => 1.0,
       "brown" | "red" => 2.0,
+      "blue" => 3.0,
+      "violet" => 4.0,
+      "digital" => 5.0,
+      "digital-short" => 6.0,
       _ => default,
     },
     _ => default,
@@ -997,10 +2765,36 @@ fn compute_order(modules: &[ModuleNode]) -> Vec<usize> {
   order
 }
 
+/// Longest latency from any source to an Output. An edge whose source runs
+/// at or after its target in `order` is a feedback edge (it carries the
+/// previous block) and does not extend the path.
+fn compute_latency(modules: &[ModuleNode], order: &[usize], output_indices: &[usize]) -> usize {
+  let mut position = vec![0usize; modules.len()];
+  for (slot, &index) in order.iter().enumerate() {
+    position[index] = slot;
+  }
+  // Latency at each module's output.
+  let mut arrival = vec![0usize; modules.len()];
+  for &index in order {
+    let module = &modules[index];
+    let input_latency = module
+      .connections
+      .iter()
+      .flatten()
+      .filter(|edge| position[edge.source_module] < position[index])
+      .map(|edge| arrival[edge.source_module])
+      .max()
+      .unwrap_or(0);
+    arrival[index] = input_latency + module.latency();
+  }
+  output_indices.iter().map(|&index| arrival[index]).max().unwrap_or(0)
+}
+
 fn build_taps(
   taps: &Option<Vec<TapJson>>,
   modules: &[ModuleNode],
   map: &HashMap<String, Vec<usize>>,
+  stereo: bool,
 ) -> Vec<TapSource> {
   let mut results = Vec::new();
   let Some(tap_list) = taps else {
@@ -1012,10 +2806,2217 @@ fn build_taps(
     };
     let index = indices.first().copied().unwrap_or(0);
     let module_type = modules[index].module_type;
-    let Some(input_port) = input_port_index(module_type, &tap.port_id) else {
-      continue;
+    // Input ports first, as before; output ports let meters expose their CVs.
+    let (port, output) = match input_port_index(module_type, &tap.port_id) {
+      Some(port) => (port, false),
+      None => match output_port_index(module_type, &tap.port_id) {
+        Some(port) => (port, true),
+        None => continue,
+      },
     };
-    results.push(TapSource { module_index: index, input_port });
+    let mut source = TapSource { module_index: index, port, output, channels: 1 };
+    source.channels = tap_channel_count(modules, &source, stereo);
+    results.push(source);
   }
   results
 }
+
+fn tap_channel_count(modules: &[ModuleNode], tap: &TapSource, stereo: bool) -> usize {
+  let node = &modules[tap.module_index];
+  let ports = if tap.output { &node.outputs } else { &node.inputs };
+  if stereo && ports[tap.port].channels == 2 { 2 } else { 1 }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  const REVERB_GRAPH: &str = r#"{
+    "modules": [
+      { "id": "in-1", "type": "audio-in", "params": { "gain": 1 } },
+      { "id": "verb-1", "type": "reverb", "params": { "time": 0.9, "mix": 1 } },
+      { "id": "out-1", "type": "output", "params": { "level": 1 } }
+    ],
+    "connections": [
+      { "from": { "moduleId": "in-1", "portId": "out" }, "to": { "moduleId": "verb-1", "portId": "in" }, "kind": "audio" },
+      { "from": { "moduleId": "verb-1", "portId": "out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "audio" }
+    ]
+  }"#;
+
+  fn peak(samples: &[Sample]) -> f32 {
+    samples.iter().fold(0.0, |acc, s| acc.max(s.abs()))
+  }
+
+  #[test]
+  fn output_limiter_sanitizes_and_clamps() {
+    const GRAPH: &str = r#"{
+      "modules": [
+        { "id": "in-1", "type": "audio-in", "params": { "gain": 1 } },
+        { "id": "out-1", "type": "output", "params": { "level": 1 } }
+      ],
+      "connections": [
+        { "from": { "moduleId": "in-1", "portId": "out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "audio" }
+      ]
+    }"#;
+    let input = [10.0, Sample::NAN, -10.0, Sample::INFINITY, 0.5, -0.3];
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(GRAPH).unwrap();
+
+    // Without a ceiling only non-finite samples are touched.
+    engine.set_external_input(&input);
+    let output = engine.render(input.len()).to_vec();
+    assert_eq!(&output[..input.len()], &[10.0, 0.0, -10.0, 0.0, 0.5, -0.3]);
+
+    engine.set_output_ceiling(Some(0.9));
+    engine.set_external_input(&input);
+    let output = engine.render(input.len()).to_vec();
+    assert!(output.iter().all(|s| s.is_finite() && s.abs() <= 0.9), "{output:?}");
+    assert!(output[0] > 0.85 && output[2] < -0.85);
+    // Below the knee the signal is untouched.
+    assert_eq!(output[4], 0.5);
+    assert_eq!(output[5], -0.3);
+
+    engine.set_output_ceiling(None);
+    assert_eq!(engine.output_ceiling(), None);
+  }
+
+  #[test]
+  fn panicking_module_is_isolated() {
+    let graph = GraphBuilder::new()
+      .add_module("in-1", "audio-in", json!({ "gain": 1 }))
+      .add_module("hpf-1", "hpf", json!({ "cutoff": 280 }))
+      .add_module("out-1", "output", json!({ "level": 1 }))
+      .connect("in-1", "out", "hpf-1", "in", "audio")
+      .connect("hpf-1", "out", "out-1", "in", "audio")
+      .build();
+    let input = [0.25, -0.5, 0.75, 1.0];
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(&graph).unwrap();
+    // A stereo state on a mono node indexes a missing channel.
+    let hpf = engine.module_map["hpf-1"][0];
+    engine.modules[hpf].state = ModuleState::Width(WidthState {
+      width: dsp_core::StereoWidth::new(48000.0),
+      amount: ParamBuffer::new(1.0),
+      mono_below: ParamBuffer::new(0.0),
+    });
+
+    for _ in 0..2 {
+      engine.set_external_input(&input);
+      // The faulted filter passes its input through, like a bypassed one.
+      assert_eq!(&engine.render(input.len())[..input.len()], &input);
+    }
+    assert_eq!(engine.faults().len(), 1);
+    assert_eq!((engine.faults()[0].module_id.as_str(), engine.faults()[0].stage), ("hpf-1", FaultStage::Render));
+
+    engine.set_graph_json(&graph).unwrap();
+    assert!(engine.faults().is_empty());
+  }
+
+  #[test]
+  fn module_panicking_on_load_is_named() {
+    let graph = |gain_params| {
+      GraphBuilder::new()
+        .add_module("osc-1", "oscillator", json!({ "frequency": 220 }))
+        .add_module("gain-1", "gain", gain_params)
+        .add_module("out-1", "output", json!({ "level": 1 }))
+        .connect("osc-1", "out", "gain-1", "in", "audio")
+        .connect("gain-1", "out", "out-1", "in", "audio")
+        .build()
+    };
+    let mut engine = GraphEngine::new(48000.0);
+    let err = engine.set_graph_json(&graph(json!({ "gain": 1, "testPanic": true }))).unwrap_err();
+    let GraphError::ModulePanicked { module_id, message } = &err else { panic!("unexpected error {err}") };
+    assert_eq!(module_id, "gain-1");
+    assert!(message.contains("test panic"), "{message}");
+    assert_eq!(engine.faults().len(), 1);
+    assert_eq!((engine.faults()[0].module_id.as_str(), engine.faults()[0].stage), ("gain-1", FaultStage::Load));
+
+    // The engine fell back to an empty graph and keeps rendering silence.
+    assert!(engine.module_ids().is_empty());
+    assert!(engine.render(128).iter().all(|&sample| sample == 0.0));
+
+    engine.set_graph_json(&graph(json!({ "gain": 1 }))).unwrap();
+    assert!(engine.faults().is_empty());
+    assert!(peak(&engine.render(256)[..256]) > 0.0);
+  }
+
+  #[test]
+  fn attenuverter_scales_and_offsets_cv() {
+    let graph = GraphBuilder::new()
+      .add_module("in-1", "audio-in", json!({ "gain": 1 }))
+      .add_module("atten-1", "atten", json!({ "scale": -1, "offset": 0.5 }))
+      .add_module("out-1", "output", json!({ "level": 1 }))
+      .connect("in-1", "out", "atten-1", "cv-in", "cv")
+      .connect("atten-1", "cv-out", "out-1", "in", "cv")
+      .build();
+    let input = [0.0, 0.25, -0.25, 0.5];
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(&graph).unwrap();
+    engine.set_external_input(&input);
+    assert_eq!(&engine.render(input.len())[..input.len()], &[0.5, 0.25, 0.75, 0.0]);
+  }
+
+  #[test]
+  fn compiled_graph_installs_in_microseconds() {
+    // 50 modules: 12 oscillator -> filter -> delay -> gain chains into a mixer.
+    let mut builder = GraphBuilder::new();
+    for chain in 0..12 {
+      let ids = ["osc", "vcf", "delay", "gain"].map(|kind| format!("{kind}-{chain}"));
+      builder = builder
+        .add_module(&ids[0], "oscillator", json!({ "frequency": 110 + 20 * chain }))
+        .add_module(&ids[1], "vcf", json!({}))
+        .add_module(&ids[2], "delay", json!({}))
+        .add_module(&ids[3], "gain", json!({}))
+        .connect(&ids[0], "out", &ids[1], "in", "audio")
+        .connect(&ids[1], "out", &ids[2], "in", "audio")
+        .connect(&ids[2], "out", &ids[3], "in", "audio")
+        .connect(&ids[3], "out", "mix-1", "in-1", "audio");
+    }
+    let graph = builder
+      .add_module("mix-1", "mixer-8", json!({}))
+      .add_module("out-1", "output", json!({}))
+      .connect("mix-1", "out", "out-1", "in", "audio")
+      .build();
+
+    let mut reference = GraphEngine::new(48000.0);
+    reference.set_graph_json(&graph).unwrap();
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(SINE_GRAPH).unwrap();
+    engine.render(128);
+
+    let mut fastest = std::time::Duration::MAX;
+    for _ in 0..5 {
+      let compiled = GraphEngine::compile_graph_json(&graph, 48000.0, false).unwrap();
+      let start = std::time::Instant::now();
+      let retired = engine.install_compiled(compiled);
+      fastest = fastest.min(start.elapsed());
+      drop(retired);
+    }
+    assert!(fastest < std::time::Duration::from_micros(100), "install took {fastest:?}");
+    assert_eq!(engine.module_map.len(), 50);
+    assert_eq!(engine.render(256), reference.render(256));
+  }
+
+  #[test]
+  fn clock_div_passes_every_fourth_pulse() {
+    let graph = GraphBuilder::new()
+      .add_module("clock-1", "clock", json!({ "tempo": 300, "rate": 4 }))
+      .add_module("div-1", "clock-div", json!({ "ratio": "/4" }))
+      .connect("clock-1", "clock", "div-1", "clock", "sync")
+      .build();
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(&graph).unwrap();
+    let (clock, div) = (engine.module_map["clock-1"][0], engine.module_map["div-1"][0]);
+    // Sixteenths at 300 BPM: a pulse every 2400 frames, 16 in 38400.
+    let (mut input, mut output) = (Vec::new(), Vec::new());
+    for _ in 0..38400 / 256 {
+      engine.render(256);
+      input.extend_from_slice(engine.output_buffers[clock][0].channel(0));
+      output.extend_from_slice(engine.output_buffers[div][0].channel(0));
+    }
+    let rising = |signal: &[Sample]| {
+      (0..signal.len()).filter(|&i| signal[i] > 0.5 && (i == 0 || signal[i - 1] <= 0.5)).collect::<Vec<_>>()
+    };
+    let pulses = rising(&input);
+    assert_eq!(pulses.len(), 16);
+    assert_eq!(rising(&output), pulses.iter().step_by(4).copied().collect::<Vec<_>>());
+  }
+
+  #[test]
+  fn logic_combines_two_gate_patterns_sample_by_sample() {
+    // A: sixteenths; B: the same clock halved, with its own pulse length.
+    let graph = GraphBuilder::new()
+      .add_module("clock-1", "clock", json!({ "tempo": 300, "rate": 4 }))
+      .add_module("div-1", "clock-div", json!({ "ratio": "/2" }))
+      .add_module("logic-1", "logic", json!({ "threshold": 0.5 }))
+      .connect("clock-1", "clock", "div-1", "clock", "sync")
+      .connect("clock-1", "clock", "logic-1", "in-a", "gate")
+      .connect("div-1", "clock-out", "logic-1", "in-b", "gate")
+      .build();
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(&graph).unwrap();
+    let (a, b, logic) = (engine.module_map["clock-1"][0], engine.module_map["div-1"][0], engine.module_map["logic-1"][0]);
+    let mut outputs = [0, 1, 2, 3].map(|_| Vec::new());
+    let (mut gate_a, mut gate_b) = (Vec::new(), Vec::new());
+    for _ in 0..9600 / 256 {
+      engine.render(256);
+      gate_a.extend_from_slice(engine.output_buffers[a][0].channel(0));
+      gate_b.extend_from_slice(engine.output_buffers[b][0].channel(0));
+      for (port, output) in outputs.iter_mut().enumerate() {
+        output.extend_from_slice(engine.output_buffers[logic][port].channel(0));
+      }
+    }
+    assert!(gate_a.iter().zip(&gate_b).any(|(&a, &b)| a > 0.5 && b <= 0.5));
+    assert!(gate_a.iter().zip(&gate_b).any(|(&a, &b)| a > 0.5 && b > 0.5));
+    let [and, or, xor, compare] = outputs;
+    for i in 0..gate_a.len() {
+      let (a, b) = (gate_a[i] > 0.5, gate_b[i] > 0.5);
+      let expected = [a && b, a || b, a != b, a].map(|high| if high { 1.0 } else { 0.0 });
+      assert_eq!([and[i], or[i], xor[i], compare[i]], expected, "frame {i}");
+    }
+  }
+
+  #[test]
+  fn peak_meter_cvs_can_be_tapped() {
+    let graph = GraphBuilder::new()
+      .add_module("osc-1", "oscillator", json!({ "type": "sine", "frequency": 440 }))
+      .add_module("meter-1", "peak-meter", json!({ "attack": 0.001, "release": 1.0, "rmsWindow": 100 }))
+      .connect("osc-1", "out", "meter-1", "in", "audio")
+      .tap("meter-1", "in")
+      .tap("meter-1", "peak-cv")
+      .tap("meter-1", "rms-cv")
+      .build();
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(&graph).unwrap();
+    assert_eq!(engine.tap_count(), 3);
+    let frames = 256;
+    let mut last = Vec::new();
+    for _ in 0..48000 / frames {
+      last = engine.render_planar(frames)[2 * frames..5 * frames].to_vec();
+    }
+    let input_peak = last[..frames].iter().fold(0.0f32, |peak, &x| peak.max(x.abs()));
+    let (peak, rms) = (last[2 * frames - 1], last[3 * frames - 1]);
+    assert!(input_peak > 0.1);
+    assert!((peak - input_peak).abs() < 0.02 * input_peak, "peak {peak} vs {input_peak}");
+    assert!((rms - input_peak / 2.0f32.sqrt()).abs() < 0.02 * input_peak, "rms {rms}");
+  }
+
+  #[test]
+  fn sample_hold_samples_noise_when_its_input_is_free() {
+    // Half a second of output, triggered by sixteenths at 300 BPM (ten triggers).
+    let run = |params: serde_json::Value| {
+      let graph = GraphBuilder::new()
+        .add_module("clock-1", "clock", json!({ "tempo": 300, "rate": 4 }))
+        .add_module("sh-1", "sample-hold", params)
+        .connect("clock-1", "clock", "sh-1", "trig", "gate")
+        .build();
+      let mut engine = GraphEngine::new(48000.0);
+      engine.set_graph_json(&graph).unwrap();
+      let sh = engine.module_map["sh-1"][0];
+      let mut out = Vec::new();
+      for _ in 0..24000 / 250 {
+        engine.render(250);
+        out.extend_from_slice(engine.output_buffers[sh][0].channel(0));
+      }
+      out
+    };
+    let max_jump = |out: &[Sample]| out.windows(2).map(|w| (w[1] - w[0]).abs()).fold(0.0, Sample::max);
+
+    let stepped = run(json!({ "noiseLevel": 0.25 }));
+    assert!(stepped.iter().all(|v| v.abs() <= 0.25));
+    let changes = stepped.windows(2).filter(|w| w[0] != w[1]).count();
+    assert!((9..=10).contains(&changes), "{changes} changes");
+    assert!(max_jump(&stepped) > 0.05);
+
+    let glided = run(json!({ "noiseLevel": 0.25, "glide": 0.01 }));
+    assert!(max_jump(&glided) < 0.01, "glide jumped {}", max_jump(&glided));
+  }
+
+  #[test]
+  fn render_reports_gate_step_and_bar_events() {
+    let graph = GraphBuilder::new()
+      .add_module("ctrl-1", "control", json!({ "voices": 2 }))
+      .add_module("seq-1", "step-sequencer", json!({ "tempo": 120, "rate": 4, "length": 2 }))
+      .add_module("clock-1", "clock", json!({ "tempo": 300 }))
+      .build();
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(&graph).unwrap();
+    let ctrl = hash_module_id("ctrl-1");
+    let seq = hash_module_id("seq-1");
+    let clock = hash_module_id("clock-1");
+    let events = |engine: &mut GraphEngine, frames: usize| {
+      engine.render(frames);
+      engine.drain_events().collect::<Vec<_>>()
+    };
+    let event = |module_hash, kind, value, frame| GraphEvent { module_hash, kind, value, frame };
+
+    // The sequencer reports its starting step on the first block.
+    assert_eq!(events(&mut engine, 256), [event(seq, GraphEventKind::Step, 0.0, 0)]);
+    // The retrigger holds the gate low for 8 samples first.
+    engine.trigger_control_voice_gate("ctrl-1", 1);
+    assert_eq!(events(&mut engine, 256), [event(ctrl, GraphEventKind::GateOn, 1.0, 8)]);
+    engine.set_control_voice_gate("ctrl-1", 1, 0.0);
+    assert_eq!(events(&mut engine, 256), [event(ctrl, GraphEventKind::GateOff, 1.0, 0)]);
+    assert!(engine.drain_events().next().is_none());
+
+    // One second: 8 sixteenth steps, and a bar every 0.8 s at 300 BPM.
+    let mut second = Vec::new();
+    for _ in 0..48000 / 256 {
+      second.extend(events(&mut engine, 256));
+    }
+    let steps: Vec<Sample> = second.iter().filter(|e| e.module_hash == seq).map(|e| e.value).collect();
+    assert_eq!(steps[..4], [1.0, 0.0, 1.0, 0.0]);
+    assert!(second.iter().any(|e| e.module_hash == clock && e.kind == GraphEventKind::Bar && e.value == 1.0));
+  }
+
+  #[test]
+  fn multiple_copies_its_input_to_every_output() {
+    let mut builder = GraphBuilder::new()
+      .add_module("in-1", "audio-in", json!({ "gain": 1 }))
+      .add_module("mult-1", "mult", json!({}))
+      .connect("in-1", "out", "mult-1", "in", "audio");
+    for k in 1..=4 {
+      let sink = format!("atten-{k}");
+      builder = builder
+        .add_module(&sink, "atten", json!({}))
+        .connect("mult-1", &format!("out-{k}"), &sink, "cv-in", "audio")
+        .tap(&sink, "cv-in");
+    }
+    let input = [0.5, -0.25, 1.0, 0.0];
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(&builder.build()).unwrap();
+    engine.set_external_input(&input);
+    let frames = input.len();
+    let rendered = engine.render(frames);
+    // [L][R] then one block per tap.
+    for tap in rendered[2 * frames..].chunks(frames) {
+      assert_eq!(tap, input);
+    }
+    assert_eq!(rendered.len(), 6 * frames);
+  }
+
+  #[test]
+  fn crossfader_cv_sweeps_with_equal_power() {
+    // Constant sources: A = 1, B = 0.5; the cv input alone sets the position.
+    let render_at = |position: f32| {
+      let graph = GraphBuilder::new()
+        .add_module("a-1", "atten", json!({ "offset": 1.0 }))
+        .add_module("b-1", "atten", json!({ "offset": 0.5 }))
+        .add_module("cv-1", "atten", json!({ "offset": position }))
+        .add_module("xfade-1", "xfade", json!({ "mix": 0, "curve": "equal-power" }))
+        .add_module("out-1", "output", json!({ "level": 1 }))
+        .connect("a-1", "cv-out", "xfade-1", "in-a", "audio")
+        .connect("b-1", "cv-out", "xfade-1", "in-b", "audio")
+        .connect("cv-1", "cv-out", "xfade-1", "cv", "cv")
+        .connect("xfade-1", "out", "out-1", "in", "audio")
+        .build();
+      let mut engine = GraphEngine::new(48000.0);
+      engine.set_graph_json(&graph).unwrap();
+      engine.render(64)[63]
+    };
+    assert!((render_at(0.0) - 1.0).abs() < 1e-6);
+    assert!((render_at(1.0) - 0.5).abs() < 1e-6);
+    // Centre: both sources at -3 dB.
+    let centre = std::f32::consts::FRAC_1_SQRT_2 * 1.5;
+    assert!((render_at(0.5) - centre).abs() < 1e-5, "{}", render_at(0.5));
+  }
+
+  #[test]
+  fn crossfader_fades_each_voice_separately() {
+    // Voice 0 of the Control sits at CV 0 (full A), voice 1 at CV 1 (full B).
+    let graph = GraphBuilder::new()
+      .add_module("ctrl-1", "control", json!({ "voices": 2 }))
+      .add_module("a-1", "atten", json!({ "offset": 1.0 }))
+      .add_module("b-1", "atten", json!({ "offset": 0.5 }))
+      .add_module("xfade-1", "crossfader", json!({ "mix": 0 }))
+      .connect("a-1", "cv-out", "xfade-1", "in-a", "audio")
+      .connect("b-1", "cv-out", "xfade-1", "in-b", "audio")
+      .connect("ctrl-1", "cv-out", "xfade-1", "fade", "cv")
+      .build();
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(&graph).unwrap();
+    engine.set_control_voice_cv("ctrl-1", 1, 1.0);
+    engine.render(16);
+    let voices: Vec<Sample> =
+      engine.module_map["xfade-1"].iter().map(|&index| engine.output_buffers[index][0].channel(0)[15]).collect();
+    assert_eq!(voices, [1.0, 0.5]);
+  }
+
+  #[test]
+  fn vco_sync_out_pulses_once_per_cycle() {
+    let mut engine = GraphEngine::new(48000.0);
+    engine
+      .set_graph_json(
+        r#"{
+          "modules": [
+            { "id": "osc-1", "type": "oscillator", "params": { "frequency": 100, "unison": 3, "detune": 30 } },
+            { "id": "osc-2", "type": "oscillator", "params": { "frequency": 150 } }
+          ],
+          "connections": [
+            { "from": { "moduleId": "osc-1", "portId": "sync-out" }, "to": { "moduleId": "osc-2", "portId": "sync" }, "kind": "sync" }
+          ]
+        }"#,
+      )
+      .unwrap();
+
+    let mut pulses = 0;
+    for _ in 0..375 {
+      engine.render(128);
+      let master = engine.module_map["osc-1"][0];
+      pulses += engine.output_buffers[master][2]
+        .channel(0)
+        .iter()
+        .filter(|&&s| s > 0.5)
+        .count();
+    }
+    // One second at 100 Hz.
+    assert!((99..=101).contains(&pulses), "got {pulses} sync pulses");
+  }
+
+  #[test]
+  fn v1_and_v2_fixtures_load_the_same_graph() {
+    let fixtures = [(include_str!("../fixtures/graph_v1.json"), 1), (include_str!("../fixtures/graph_v2.json"), 0)];
+    for (fixture, migrations) in fixtures {
+      let mut engine = GraphEngine::new(48000.0);
+      engine.set_graph_json(fixture).unwrap();
+      assert_eq!(engine.migrations().len(), migrations);
+      assert_eq!(engine.module_kind("mix-1"), Some(ModuleType::MixerWide));
+      assert_eq!(engine.get_param("mix-1", "levelA"), Some(0.8));
+      assert!(peak(&engine.render(256)[..256]) > 0.0);
+    }
+  }
+
+  #[test]
+  fn ui_v2_graph_round_trips_without_migration() {
+    // As the editor saves it: current schema, `mixer-wide`, layout fields.
+    let graph = r#"{
+      "schemaVersion": 2,
+      "modules": [
+        { "id": "osc-1", "type": "oscillator", "name": "VCO", "position": { "x": 0, "y": 0 }, "params": { "frequency": 220 } },
+        { "id": "mix-1", "type": "mixer-wide", "name": "Mixer 6ch", "position": { "x": 4, "y": 0 }, "params": { "levelA": 0.8 } },
+        { "id": "out-1", "type": "output", "name": "Main Out", "position": { "x": 8, "y": 0 }, "params": { "level": 0.8 } }
+      ],
+      "connections": [
+        { "from": { "moduleId": "osc-1", "portId": "out" }, "to": { "moduleId": "mix-1", "portId": "in-a" }, "kind": "audio" },
+        { "from": { "moduleId": "mix-1", "portId": "out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "audio" }
+      ]
+    }"#;
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(graph).unwrap();
+    assert!(engine.migrations().is_empty());
+    assert_eq!(engine.module_kind("mix-1"), Some(ModuleType::MixerWide));
+
+    let saved: serde_json::Value = serde_json::from_str(&engine.to_graph_json()).unwrap();
+    assert_eq!(saved["schemaVersion"], 2);
+    assert_eq!(saved["modules"][1]["type"], "mixer-wide");
+    let mut reloaded = GraphEngine::new(48000.0);
+    reloaded.set_graph_json(&engine.to_graph_json()).unwrap();
+    assert!(reloaded.migrations().is_empty());
+    assert!(peak(&reloaded.render(256)[..256]) > 0.0);
+  }
+
+  #[test]
+  fn newer_schema_version_is_rejected() {
+    let mut engine = GraphEngine::new(48000.0);
+    let err = engine
+      .set_graph_json(r#"{ "schemaVersion": 99, "modules": [], "connections": [] }"#)
+      .unwrap_err();
+    assert!(matches!(err, GraphError::ValidationFailed(_)));
+    assert!(err.to_string().contains("newer than supported"), "{err}");
+    assert!(matches!(engine.set_graph_json("{ modules"), Err(GraphError::JsonParse(_))));
+  }
+
+  #[test]
+  fn bypassed_distortion_passes_dry_input() {
+    let mut engine = GraphEngine::new(48000.0);
+    engine
+      .set_graph_json(
+        r#"{
+          "modules": [
+            { "id": "in-1", "type": "audio-in", "params": { "gain": 1 } },
+            { "id": "dist-1", "type": "distortion", "params": { "drive": 1, "mix": 1 } },
+            { "id": "out-1", "type": "output", "params": { "level": 1 } }
+          ],
+          "connections": [
+            { "from": { "moduleId": "in-1", "portId": "out" }, "to": { "moduleId": "dist-1", "portId": "in" }, "kind": "audio" },
+            { "from": { "moduleId": "dist-1", "portId": "out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "audio" }
+          ]
+        }"#,
+      )
+      .unwrap();
+    let input: Vec<Sample> = (0..256).map(|i| (i as f32 * 0.05).sin() * 0.5).collect();
+    engine.set_external_input(&input);
+
+    let wet = engine.render(256)[..256].to_vec();
+    assert!(wet.iter().zip(&input).any(|(w, d)| (w - d).abs() > 0.05));
+
+    engine.set_bypass("dist-1", true);
+    let dry = &engine.render(256)[..256];
+    for (out, expected) in dry.iter().zip(&input) {
+      assert!((out - expected).abs() < 1e-6);
+    }
+    assert!(engine.to_graph_json().contains(r#""bypass":true"#));
+  }
+
+  #[test]
+  fn to_graph_json_round_trips() {
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(include_str!("../fixtures/graph_v1.json")).unwrap();
+    engine.set_param("osc-1", "frequency", 330.0);
+
+    let exported = engine.to_graph_json();
+    let mut reloaded = GraphEngine::new(48000.0);
+    reloaded.set_graph_json(&exported).unwrap();
+
+    assert_eq!(reloaded.module_ids(), engine.module_ids());
+    assert_eq!(reloaded.modules.len(), engine.modules.len());
+    assert_eq!(reloaded.get_param("osc-1", "frequency"), Some(330.0));
+    assert_eq!(reloaded.get_param("mix-1", "levelA"), Some(0.8));
+    assert_eq!(reloaded.render(256), engine.render(256));
+  }
+
+  #[test]
+  fn get_param_reads_back_set_value() {
+    let mut engine = GraphEngine::new(48000.0);
+    engine
+      .set_graph_json(
+        r#"{
+          "modules": [
+            { "id": "osc-1", "type": "oscillator", "params": {} },
+            { "id": "vcf-1", "type": "vcf", "params": { "cutoff": 800 } },
+            { "id": "ring-1", "type": "ring-mod", "params": { "carrier-freq": 300, "carrier-shape": "square" } }
+          ],
+          "connections": []
+        }"#,
+      )
+      .unwrap();
+
+    assert_eq!(engine.get_param("vcf-1", "cutoff"), Some(800.0));
+    engine.set_param("vcf-1", "cutoff", 2400.0);
+    assert_eq!(engine.get_param("vcf-1", "cutoff"), Some(2400.0));
+    // Unset params read as their default, older spellings as their value
+    assert_eq!(engine.get_param("vcf-1", "resonance"), param_meta(ModuleType::Vcf, "resonance").map(|meta| meta.default));
+    assert_eq!(engine.get_param("ring-1", "carrierShape"), Some(3.0));
+    engine.set_param("ring-1", "carrierFreq", 500.0);
+    assert_eq!(engine.get_param("ring-1", "carrierFreq"), Some(500.0));
+    assert_eq!(engine.get_param("ring-1", "carrier-freq"), Some(500.0));
+    assert_eq!(engine.get_param("vcf-1", "nope"), None);
+    assert_eq!(engine.get_param("missing", "cutoff"), None);
+
+    assert_eq!(engine.module_ids(), vec!["osc-1", "ring-1", "vcf-1"]);
+    assert_eq!(engine.module_kind("vcf-1"), Some(ModuleType::Vcf));
+    assert_eq!(engine.module_kind("missing"), None);
+    assert!(engine.has_module("osc-1"));
+    assert!(!engine.has_module("ctrl-1"));
+  }
+
+  #[test]
+  fn render_interleaved_matches_planar() {
+    let mut planar_engine = GraphEngine::new(48000.0);
+    planar_engine.set_graph_json(REVERB_GRAPH).unwrap();
+    let mut interleaved_engine = GraphEngine::new(48000.0);
+    interleaved_engine.set_graph_json(REVERB_GRAPH).unwrap();
+
+    let frames = 64;
+    let planar = planar_engine.render_planar(frames).to_vec();
+    let interleaved = interleaved_engine.render_interleaved(frames);
+    assert_eq!(interleaved.len(), planar.len());
+    for frame in 0..frames {
+      assert_eq!(interleaved[2 * frame], planar[frame]);
+      assert_eq!(interleaved[2 * frame + 1], planar[frames + frame]);
+    }
+
+    // An empty block returns nothing rather than the previous block
+    assert!(interleaved_engine.render_interleaved(0).is_empty());
+    assert!(planar_engine.render_planar(0).is_empty());
+  }
+
+  const SINE_GRAPH: &str = r#"{
+    "modules": [
+      { "id": "osc-1", "type": "oscillator", "params": { "frequency": 440, "type": "sine" } },
+      { "id": "out-1", "type": "output", "params": { "level": 1 } }
+    ],
+    "connections": [
+      { "from": { "moduleId": "osc-1", "portId": "out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "audio" }
+    ]
+  }"#;
+
+  #[test]
+  fn random_block_sizes_keep_sine_continuous() {
+    let mut reference = GraphEngine::new(48000.0);
+    reference.set_graph_json(SINE_GRAPH).unwrap();
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(SINE_GRAPH).unwrap();
+
+    // Small LCG so the block sizes are random-looking but reproducible.
+    let mut seed = 0x1234_5678u32;
+    let mut expected = Vec::new();
+    let mut rendered = Vec::new();
+    for _ in 0..48 {
+      seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+      let frames = 1 + (seed >> 8) as usize % 4096;
+      rendered.extend_from_slice(&engine.render(frames)[..frames]);
+      while expected.len() < rendered.len() {
+        expected.extend_from_slice(&reference.render(64)[..64]);
+      }
+    }
+
+    assert!(peak(&rendered) > 0.1);
+    for (i, (got, want)) in rendered.iter().zip(&expected).enumerate() {
+      assert!((got - want).abs() < 1e-4, "discontinuity at sample {i}: {got} vs {want}");
+    }
+  }
+
+  #[test]
+  fn every_module_renders_tiny_and_huge_blocks() {
+    let kinds = [
+      "oscillator", "supersaw", "karplus", "nes-osc", "snes-osc", "noise", "mod-router", "atten", "sample-hold",
+      "slew", "env-follower", "quantizer", "ring-mod", "gain", "cv-vca", "output", "lab", "lfo", "adsr",
+      "vcf", "hpf", "dc-block", "mixer", "mixer-wide", "mixer-8", "crossfader", "mult", "pan", "stereo-width", "chorus", "ensemble",
+      "choir", "vocoder", "audio-in", "delay", "granular-delay", "tape-delay", "spring-reverb", "reverb",
+      "phaser", "distortion", "wavefolder", "bitcrush", "compressor", "noise-gate", "control", "scope", "mario", "cv-bank",
+      "arpeggiator", "step-sequencer", "tb-303", "909-kick", "909-snare", "909-hihat", "909-clap",
+      "909-tom", "909-rimshot", "808-kick", "808-snare", "808-hihat", "808-cowbell", "808-clap",
+      "808-tom", "drum-sequencer", "euclidean", "midi-file-sequencer", "fm-op", "fm-matrix", "shepard",
+      "pipe-organ", "spectral-swarm", "resonator", "wavetable", "granular", "particle-cloud",
+      "sample-player", "notes", "pitch-shifter", "clock", "clock-div", "chaos", "logic", "peak-meter", "turing-machine", "sid-player",
+      "ay-player",
+    ];
+    let modules: Vec<String> = kinds
+      .iter()
+      .enumerate()
+      .map(|(i, kind)| format!(r#"{{ "id": "m-{i}", "type": "{kind}", "params": {{}} }}"#))
+      .collect();
+    let graph = format!(r#"{{ "modules": [{}], "connections": [] }}"#, modules.join(","));
+
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(&graph).unwrap();
+    assert!(engine.render(0).is_empty());
+    for frames in [1, 2, 3, 4, MAX_BLOCK_FRAMES + 1, 4096, 5000, 1] {
+      assert_eq!(engine.render(frames).len(), 2 * frames);
+    }
+  }
+
+  #[test]
+  fn sample_player_plays_a_loaded_sample() {
+    let graph = r#"{
+      "modules": [
+        { "id": "player-1", "type": "sample-player", "params": {} },
+        { "id": "out-1", "type": "output", "params": { "level": 1 } }
+      ],
+      "connections": [
+        { "from": { "moduleId": "player-1", "portId": "out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "audio" }
+      ]
+    }"#;
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(graph).unwrap();
+    assert!(engine.render(128).iter().all(|&s| s == 0.0));
+
+    // Stereo at 24 kHz plays back at half speed per output sample.
+    let data: Vec<f32> = (0..2400).flat_map(|_| [0.5, -0.5]).collect();
+    assert_eq!(engine.load_sample("player-1", &data, 2, 24000.0), Ok(2400));
+    let out = engine.render(128);
+    assert!((out[64] - 0.5).abs() < 1e-4, "{}", out[64]);
+    assert!((out[128 + 64] + 0.5).abs() < 1e-4, "{}", out[128 + 64]);
+
+    assert!(engine.load_sample("out-1", &data, 2, 24000.0).is_err());
+    assert!(engine.load_sample("missing", &data, 2, 24000.0).is_err());
+  }
+
+  #[test]
+  fn midi_file_round_trips_into_the_sequencer() {
+    let file = dsp_core::parse_smf(include_bytes!("../fixtures/two_tracks.mid")).unwrap();
+    assert_eq!((file.ticks_per_beat, file.total_ticks, file.tempo), (96, 384, 120.0));
+    assert_eq!(file.tempo_map, [(0, 120.0), (384, 150.0)]);
+    assert!(file.warnings.is_empty());
+
+    let graph = r#"{
+      "modules": [
+        { "id": "midi-1", "type": "midi-file-sequencer", "params": { "voices": 1 } },
+        { "id": "out-1", "type": "output", "params": {} }
+      ],
+      "connections": []
+    }"#;
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(graph).unwrap();
+    engine.set_module_midi_data("midi-1", MidiData::from_file(file.clone())).unwrap();
+    assert!(engine.set_module_midi_data("out-1", MidiData::from_file(file)).is_err());
+
+    let expected: [&[(u32, u8, u8, u32)]; 2] = [
+      &[(0, 60, 100, 96), (96, 64, 90, 96), (192, 67, 80, 192)],
+      &[(0, 36, 110, 192), (192, 43, 100, 192)],
+    ];
+    let loaded = |engine: &GraphEngine| -> Vec<Vec<(u32, u8, u8, u32)>> {
+      let ModuleState::MidiFileSequencer(state) = &engine.modules[engine.module_map["midi-1"][0]].state else {
+        panic!("not a MIDI file sequencer");
+      };
+      assert_eq!(state.seq.total_ticks(), 384);
+      (0..3)
+        .map(|track| state.seq.track_notes(track).iter().map(|n| (n.tick, n.note, n.velocity, n.duration)).collect())
+        .collect()
+    };
+    assert_eq!(loaded(&engine), [expected[0].to_vec(), expected[1].to_vec(), Vec::new()]);
+
+    // The midiData param holds the same notes, so a rebuilt graph keeps them.
+    let spec = engine.live_spec();
+    let params = spec.modules[0].params.as_ref().unwrap();
+    let json: serde_json::Value = serde_json::from_str(params["midiData"].as_str().unwrap()).unwrap();
+    assert_eq!(json["tracks"][1]["name"], "Bass");
+    assert_eq!(json["tracks"][1]["channel"], 1);
+    let midi_data = params["midiData"].as_str().unwrap().to_string();
+    let mut rebuilt = GraphEngine::new(48000.0);
+    rebuilt.set_graph(spec);
+    assert_eq!(loaded(&rebuilt), loaded(&engine));
+
+    // So does the note list parsed back into a MidiData.
+    let mut reloaded = GraphEngine::new(48000.0);
+    reloaded.set_graph_json(graph).unwrap();
+    reloaded.set_module_midi_data("midi-1", MidiData::from_json(midi_data).unwrap()).unwrap();
+    assert_eq!(loaded(&reloaded), loaded(&engine));
+    assert!(MidiData::from_json("[]".to_string()).is_none());
+  }
+
+  #[test]
+  fn midi_file_param_embeds_the_file_and_tempo_scale_speeds_it_up() {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let base64: String = include_bytes!("../fixtures/two_tracks.mid")
+      .chunks(3)
+      .flat_map(|chunk| {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (i, &b)| bits | (b as u32) << (16 - 8 * i));
+        (0..4).map(move |i| if i <= chunk.len() { ALPHABET[(bits >> (18 - 6 * i) & 63) as usize] as char } else { '=' })
+      })
+      .collect();
+    let graph = format!(
+      r#"{{
+        "modules": [
+          {{ "id": "midi-1", "type": "midi-file-sequencer", "params": {{ "voices": 1, "midiFile": "{base64}" }} }},
+          {{ "id": "midi-2", "type": "midi-file-sequencer", "params": {{ "voices": 1, "midiFile": "{base64}", "tempo-scale": 2 }} }},
+          {{ "id": "out-1", "type": "output", "params": {{}} }}
+        ],
+        "connections": []
+      }}"#
+    );
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(&graph).unwrap();
+    assert_eq!(engine.get_midi_total_ticks("midi-1"), 384);
+    assert_eq!(engine.get_param("midi-2", "tempoScale"), Some(2.0));
+
+    // 120 BPM at 96 ticks per beat is 192 ticks per second.
+    engine.render(4800);
+    assert!((engine.get_sequencer_step("midi-1") - 19).abs() <= 1, "{}", engine.get_sequencer_step("midi-1"));
+    assert!((engine.get_sequencer_step("midi-2") - 38).abs() <= 1, "{}", engine.get_sequencer_step("midi-2"));
+
+    // Setting the note list replaces the embedded file for good.
+    engine.set_param_string("midi-1", "midiData", r#"{"ticksPerBeat": 96, "totalTicks": 96, "tracks": []}"#);
+    let spec = engine.live_spec();
+    assert!(!spec.modules[0].params.as_ref().unwrap().contains_key("midiFile"));
+    assert_eq!(engine.get_midi_total_ticks("midi-1"), 96);
+  }
+
+  #[test]
+  fn outputs_mix_at_their_levels_and_buses() {
+    let graph = |bus: u32| {
+      format!(
+        r#"{{
+          "modules": [
+            {{ "id": "in-1", "type": "audio-in", "params": {{ "gain": 1 }} }},
+            {{ "id": "out-1", "type": "output", "params": {{ "level": 1 }} }},
+            {{ "id": "out-2", "type": "output", "params": {{ "level": 0.25, "bus": {bus} }} }}
+          ],
+          "connections": [
+            {{ "from": {{ "moduleId": "in-1", "portId": "out" }}, "to": {{ "moduleId": "out-1", "portId": "in" }}, "kind": "audio" }},
+            {{ "from": {{ "moduleId": "in-1", "portId": "out" }}, "to": {{ "moduleId": "out-2", "portId": "in" }}, "kind": "audio" }}
+          ]
+        }}"#
+      )
+    };
+    let input = vec![0.5; 128];
+
+    // Same bus: the submixes sum at their own levels (1 + 0.25).
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(&graph(0)).unwrap();
+    engine.set_external_input(&input);
+    assert_eq!(engine.bus_count(), 1);
+    let data = engine.render(128);
+    assert!((data[127] - 0.625).abs() < 1e-4, "{}", data[127]);
+
+    // Bus 1 gets its own stereo pair after the main mix.
+    engine.set_graph_json(&graph(1)).unwrap();
+    engine.set_external_input(&input);
+    assert_eq!(engine.bus_count(), 2);
+    assert_eq!(engine.output_channels(), 4);
+    let data = engine.render(128);
+    let main = data[127];
+    let bus_1 = data[2 * 128 + 127];
+    assert!((main - 0.5).abs() < 1e-4, "{main}");
+    assert!((bus_1 / main - 0.25).abs() < 1e-3, "{bus_1} / {main}");
+    assert_eq!(data[3 * 128 + 127], bus_1);
+
+    // Moving an Output at runtime lays the buses out again.
+    engine.set_param("out-2", "bus", 2.0);
+    assert_eq!(engine.bus_count(), 3);
+    assert_eq!(engine.output_channels(), 6);
+    let data = engine.render(128);
+    assert_eq!(data[2 * 128 + 127], 0.0);
+    assert!((data[4 * 128 + 127] - bus_1).abs() < 1e-6, "{}", data[4 * 128 + 127]);
+
+    engine.set_param("out-2", "bus", 0.0);
+    assert_eq!(engine.bus_count(), 1);
+    assert_eq!(engine.output_channels(), 2);
+    let data = engine.render(128);
+    assert!((data[127] - 0.625).abs() < 1e-4, "{}", data[127]);
+  }
+
+  const GATED_SYNTH_GRAPH: &str = r#"{
+    "modules": [
+      { "id": "ctrl-1", "type": "control", "params": { "voices": 1 } },
+      { "id": "osc-1", "type": "oscillator", "params": { "frequency": 220 } },
+      { "id": "env-1", "type": "adsr", "params": { "attack": 0.001, "release": 0.05 } },
+      { "id": "vca-1", "type": "gain", "params": { "gain": 1 } },
+      { "id": "out-1", "type": "output", "params": { "level": 1 } }
+    ],
+    "connections": [
+      { "from": { "moduleId": "ctrl-1", "portId": "gate-out" }, "to": { "moduleId": "env-1", "portId": "gate" }, "kind": "gate" },
+      { "from": { "moduleId": "osc-1", "portId": "out" }, "to": { "moduleId": "vca-1", "portId": "in" }, "kind": "audio" },
+      { "from": { "moduleId": "env-1", "portId": "env" }, "to": { "moduleId": "vca-1", "portId": "cv" }, "kind": "cv" },
+      { "from": { "moduleId": "vca-1", "portId": "out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "audio" }
+    ]
+  }"#;
+
+  #[test]
+  fn latency_follows_the_slowest_path_and_ignores_feedback() {
+    // 20 ms and 40 ms grains at 48 kHz delay by half a grain: 480 + 960.
+    const GRAPH: &str = r#"{
+      "modules": [
+        { "id": "in-1", "type": "audio-in" },
+        { "id": "shift-1", "type": "pitch-shifter", "params": { "grain": 20 } },
+        { "id": "shift-2", "type": "pitch-shifter", "params": { "grain": 40 } },
+        { "id": "shift-3", "type": "pitch-shifter", "params": { "grain": 10 } },
+        { "id": "out-1", "type": "output" }
+      ],
+      "connections": [
+        { "from": { "moduleId": "in-1", "portId": "out" }, "to": { "moduleId": "shift-1", "portId": "in" }, "kind": "audio" },
+        { "from": { "moduleId": "shift-1", "portId": "out" }, "to": { "moduleId": "shift-2", "portId": "in" }, "kind": "audio" },
+        { "from": { "moduleId": "shift-2", "portId": "out" }, "to": { "moduleId": "shift-1", "portId": "in" }, "kind": "audio" },
+        { "from": { "moduleId": "in-1", "portId": "out" }, "to": { "moduleId": "shift-3", "portId": "in" }, "kind": "audio" },
+        { "from": { "moduleId": "shift-2", "portId": "out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "audio" },
+        { "from": { "moduleId": "shift-3", "portId": "out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "audio" }
+      ]
+    }"#;
+    let mut engine = GraphEngine::new(48000.0);
+    assert_eq!(engine.latency_samples(), 0);
+    engine.set_graph_json(GRAPH).unwrap();
+    assert_eq!(engine.latency_samples(), 1440);
+
+    // The parallel path takes over once it is the slower one.
+    engine.set_param("shift-3", "grain", 100.0);
+    assert_eq!(engine.latency_samples(), 2400);
+    engine.set_bypass("shift-3", true);
+    assert_eq!(engine.latency_samples(), 1440);
+    engine.set_sample_rate(96000.0);
+    assert_eq!(engine.latency_samples(), 2880);
+  }
+
+  #[test]
+  fn graph_diff_keeps_untouched_modules_running() {
+    const GRAPH: &str = r#"{
+      "modules": [
+        { "id": "in-1", "type": "audio-in", "params": { "gain": 1 } },
+        { "id": "rev-1", "type": "reverb", "params": { "mix": 1, "decay": 0.8 } },
+        { "id": "out-1", "type": "output", "params": { "level": 1 } }
+      ],
+      "connections": [
+        { "from": { "moduleId": "in-1", "portId": "out" }, "to": { "moduleId": "rev-1", "portId": "in" }, "kind": "audio" },
+        { "from": { "moduleId": "rev-1", "portId": "out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "audio" }
+      ]
+    }"#;
+    let mut edited = GraphEngine::new(48000.0);
+    let mut reference = GraphEngine::new(48000.0);
+    for engine in [&mut edited, &mut reference] {
+      engine.set_graph_json(GRAPH).unwrap();
+      let mut impulse = vec![0.0; 4096];
+      impulse[0] = 1.0;
+      engine.set_external_input(&impulse);
+      engine.render(4096);
+      engine.clear_external_input();
+    }
+
+    let lfo: ModuleSpecJson = serde_json::from_str(r#"{ "id": "lfo-1", "type": "lfo" }"#).unwrap();
+    let cable: ConnectionJson = serde_json::from_str(
+      r#"{ "from": { "moduleId": "lfo-1", "portId": "cv-out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "cv" }"#,
+    )
+    .unwrap();
+    edited.set_graph_diff(&[lfo], &[], &[], &[]).unwrap();
+    // The reverb tail carries on exactly as if nothing had changed.
+    let tail = edited.render(256)[..256].to_vec();
+    assert!(peak(&tail) > 1e-4, "reverb tail was lost");
+    assert_eq!(tail, &reference.render(256)[..256]);
+    assert!(edited.module_kind("lfo-1").is_some());
+
+    edited.set_graph_diff(&[], &["rev-1".to_string()], &[], &[]).unwrap();
+    assert!(edited.module_kind("rev-1").is_none());
+    assert!(edited.to_graph_json().contains("in-1") && !edited.to_graph_json().contains("rev-1"));
+    assert_eq!(peak(&edited.render(256)[..256]), 0.0);
+
+    assert!(edited.set_graph_diff(&[], &["nope".to_string()], &[], &[]).is_err());
+    assert!(edited.set_graph_diff(&[], &[], &[], &[cable]).is_err());
+  }
+
+  #[test]
+  fn pan_sweep_keeps_equal_power() {
+    let mut engine = GraphEngine::new(48000.0);
+    engine
+      .set_graph_json(
+        r#"{
+          "modules": [
+            { "id": "in-1", "type": "audio-in", "params": { "gain": 1 } },
+            { "id": "pan-1", "type": "pan", "params": { "pan": -1 } },
+            { "id": "out-1", "type": "output", "params": { "level": 1 } }
+          ],
+          "connections": [
+            { "from": { "moduleId": "in-1", "portId": "out" }, "to": { "moduleId": "pan-1", "portId": "in" }, "kind": "audio" },
+            { "from": { "moduleId": "pan-1", "portId": "out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "audio" }
+          ]
+        }"#,
+      )
+      .unwrap();
+    let input = vec![0.5; 64];
+    let mut previous_left = Sample::INFINITY;
+    for step in 0..=20 {
+      let pan = -1.0 + step as f32 * 0.1;
+      engine.set_param("pan-1", "pan", pan);
+      engine.set_external_input(&input);
+      let output = engine.render(64);
+      let (left, right) = (output[63], output[127]);
+      let power = left * left + right * right;
+      assert!((power - 0.25).abs() < 1e-4, "pan {pan}: power {power}");
+      assert!(left <= previous_left + 1e-6, "left rose at pan {pan}");
+      previous_left = left;
+      if step == 0 {
+        assert!(right.abs() < 1e-6);
+      }
+      if step == 10 {
+        // Center: -3 dB on each side.
+        assert!((left - right).abs() < 1e-6);
+        assert!((left - 0.5 * std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-4);
+      }
+    }
+  }
+
+  #[test]
+  fn output_voice_spread_pans_poly_voices_apart() {
+    // Per-voice noise seeds make the four voices uncorrelated.
+    let graph = GraphBuilder::new()
+      .add_module("ctrl-1", "control", json!({ "voices": 4 }))
+      .add_module("noise-1", "noise", json!({ "level": 0.5 }))
+      .add_module("out-1", "output", json!({ "level": 1, "voiceSpread": 1 }))
+      .connect("noise-1", "out", "out-1", "in", "audio")
+      .build();
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(&graph).unwrap();
+    let correlation = |engine: &mut GraphEngine| {
+      let frames = 4096;
+      let planar = engine.render_planar(frames);
+      let (left, right) = planar[..2 * frames].split_at(frames);
+      let dot = |a: &[Sample], b: &[Sample]| a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>();
+      let (left_power, right_power) = (dot(left, left), dot(right, right));
+      assert!(left_power > 0.0 && (left_power / right_power - 1.0).abs() < 0.2, "unbalanced: {left_power} vs {right_power}");
+      dot(left, right) / (left_power * right_power).sqrt()
+    };
+    let spread = correlation(&mut engine);
+    // Pans -1, -1/3, 1/3, 1 with an equal-power law: about 0.43.
+    assert!(spread < 0.6, "correlation {spread}");
+    engine.set_param("out-1", "voiceSpread", 0.0);
+    let summed = correlation(&mut engine);
+    assert!((summed - 1.0).abs() < 1e-4, "correlation {summed}");
+  }
+
+  #[test]
+  fn random_seed_makes_renders_reproducible() {
+    let graph = |seed: Option<u64>| {
+      let builder = GraphBuilder::new()
+        .add_module("ctrl-1", "control", json!({ "voices": 2 }))
+        .add_module("noise-1", "noise", json!({ "level": 0.5 }))
+        .add_module("noise-2", "noise", json!({ "level": 0.5 }));
+      match seed {
+        Some(seed) => builder.seed(seed).build(),
+        None => builder.build(),
+      }
+    };
+    // Voice 0 and voice 1 of both Noise modules.
+    let render = |engine: &mut GraphEngine| -> Vec<Vec<Sample>> {
+      engine.render(256);
+      ["noise-1", "noise-2"]
+        .iter()
+        .flat_map(|id| (0..2).map(move |voice| (*id, voice)))
+        .map(|(id, voice)| {
+          let index = engine.find_voice_instance(id, voice).unwrap();
+          engine.output_buffers[index][0].channel(0).to_vec()
+        })
+        .collect()
+    };
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(&graph(None)).unwrap();
+    let first = render(&mut engine);
+    assert_ne!(first[0], first[1], "voices of one module should differ");
+    assert_ne!(first[0], first[2], "modules should differ");
+    engine.set_graph_json(&graph(None)).unwrap();
+    assert_eq!(render(&mut engine), first, "same seed, same render");
+
+    engine.set_random_seed(7);
+    let reseeded = render(&mut engine);
+    assert_ne!(reseeded, first, "another seed, another render");
+    engine.set_random_seed(7);
+    assert_eq!(render(&mut engine), reseeded);
+    // A seed in the graph wins over the engine's.
+    engine.set_random_seed(DEFAULT_RANDOM_SEED);
+    engine.set_graph_json(&graph(Some(7))).unwrap();
+    assert_eq!(render(&mut engine), reseeded);
+  }
+
+  #[test]
+  fn stereo_cables_keep_the_chorus_image_through_a_chain() {
+    // The same chorus, straight into the Output or through a gain and a
+    // distortion on `stereo` cables: both keep distinct L and R.
+    let render = |chain: &[&str]| {
+      let mut builder = GraphBuilder::new()
+        .add_module("osc-1", "oscillator", json!({ "frequency": 220 }))
+        .add_module("fx-1", "chorus", json!({ "mix": 1, "spread": 1 }))
+        .add_module("out-1", "output", json!({ "level": 1 }))
+        .connect("osc-1", "out", "fx-1", "in", "audio");
+      let mut from = "fx-1";
+      for (index, kind) in chain.iter().enumerate() {
+        let id = ["stage-1", "stage-2"][index];
+        let params = if *kind == "gain" { json!({ "gain": 1 }) } else { json!({ "drive": 0, "mix": 0 }) };
+        builder = builder.add_module(id, kind, params).connect(from, "out", id, "in", "stereo");
+        from = id;
+      }
+      let graph = builder.connect(from, "out", "out-1", "in", "stereo").build();
+      let mut engine = GraphEngine::new(48000.0);
+      engine.set_graph_json(&graph).unwrap();
+      engine.render(4800);
+      let planar = engine.render_planar(1024);
+      let (left, right) = planar[..2048].split_at(1024);
+      (left.to_vec(), right.to_vec())
+    };
+    let (left, right) = render(&[]);
+    let difference = left.iter().zip(&right).fold(0.0f32, |peak, (l, r)| peak.max((l - r).abs()));
+    assert!(difference > 0.05, "the chorus should be stereo: {difference}");
+    assert_eq!(render(&["gain"]), (left.clone(), right.clone()), "a unity gain keeps the image");
+    let (shaped_left, shaped_right) = render(&["gain", "distortion"]);
+    let difference = shaped_left.iter().zip(&shaped_right).fold(0.0f32, |peak, (l, r)| peak.max((l - r).abs()));
+    assert!(difference > 0.05, "the distortion should shape each side: {difference}");
+  }
+
+  #[test]
+  fn wet_only_effects_drop_the_dry_signal() {
+    let render = |kind: &str, wet_only: f32| {
+      let graph = GraphBuilder::new()
+        .add_module("osc-1", "oscillator", json!({ "frequency": 220 }))
+        .add_module("fx-1", kind, json!({ "mix": 0, "wetOnly": wet_only }))
+        .add_module("out-1", "output", json!({ "level": 1 }))
+        .connect("osc-1", "out", "fx-1", "in", "audio")
+        .connect("fx-1", "out", "out-1", "in", "audio")
+        .build();
+      let mut engine = GraphEngine::new(48000.0);
+      engine.set_graph_json(&graph).unwrap();
+      engine.render(2048).iter().fold(0.0f32, |peak, &s| peak.max(s.abs()))
+    };
+    for kind in ["chorus", "ensemble", "delay", "reverb", "phaser"] {
+      assert!(render(kind, 0.0) > 0.1, "{kind}: mix 0 should pass the dry signal");
+      assert_eq!(render(kind, 1.0), 0.0, "{kind}: wet only with mix 0 should be silent");
+    }
+  }
+
+  #[test]
+  fn compressor_sidechain_pumps_under_a_kick() {
+    // Peaks of 10 ms windows over one second: steady without the key,
+    // dipping after every kick with it.
+    let render = |sidechain: f32| {
+      let graph = GraphBuilder::new()
+        .add_module("clock-1", "clock", json!({ "tempo": 120 }))
+        .add_module("kick", "909-kick", json!({}))
+        .add_module("pad", "oscillator", json!({ "frequency": 220 }))
+        .add_module("comp-1", "compressor", json!({ "threshold": -30, "ratio": 10, "attack": 1, "release": 100, "sidechain": sidechain }))
+        .add_module("out-1", "output", json!({ "level": 1 }))
+        .connect("clock-1", "clock", "kick", "trigger", "sync")
+        .connect("kick", "out", "comp-1", "sc", "audio")
+        .connect("pad", "out", "comp-1", "in", "audio")
+        .connect("comp-1", "out", "out-1", "in", "audio")
+        .build();
+      let mut engine = GraphEngine::new(48000.0);
+      engine.set_graph_json(&graph).unwrap();
+      engine.render(4800);
+      let peaks: Vec<f32> = (0..100)
+        .map(|_| engine.render(480).iter().step_by(2).fold(0.0f32, |peak, &s| peak.max(s.abs())))
+        .collect();
+      let loudest = peaks.iter().fold(0.0f32, |a, &b| a.max(b));
+      let quietest = peaks.iter().fold(f32::MAX, |a, &b| a.min(b));
+      (loudest, quietest)
+    };
+    let (loudest, quietest) = render(1.0);
+    assert!(quietest < loudest * 0.3, "the pad should duck under the kick: {quietest} vs {loudest}");
+    let (loudest, quietest) = render(0.0);
+    assert!(quietest > loudest * 0.9, "without the key the pad stays level: {quietest} vs {loudest}");
+  }
+
+  #[test]
+  fn env_follower_ducks_an_inverted_vca() {
+    const GRAPH: &str = r#"{
+      "modules": [
+        { "id": "kick", "type": "oscillator", "params": { "frequency": 60 } },
+        { "id": "pad", "type": "oscillator", "params": { "frequency": 220 } },
+        { "id": "env-1", "type": "env-follower", "params": { "attack": 0.001, "release": 0.5 } },
+        { "id": "vca-1", "type": "gain", "params": { "gain": 1, "invert": 1 } },
+        { "id": "out-1", "type": "output", "params": { "level": 1 } }
+      ],
+      "connections": [
+        { "from": { "moduleId": "kick", "portId": "out" }, "to": { "moduleId": "env-1", "portId": "in" }, "kind": "audio" },
+        { "from": { "moduleId": "env-1", "portId": "out" }, "to": { "moduleId": "vca-1", "portId": "cv" }, "kind": "cv" },
+        { "from": { "moduleId": "pad", "portId": "out" }, "to": { "moduleId": "vca-1", "portId": "in" }, "kind": "audio" },
+        { "from": { "moduleId": "vca-1", "portId": "out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "audio" }
+      ]
+    }"#;
+    let peak = |engine: &mut GraphEngine| {
+      engine.render(4800);
+      engine.render(4800).iter().fold(0.0f32, |acc, s| acc.max(s.abs()))
+    };
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(GRAPH).unwrap();
+    let ducked = peak(&mut engine);
+    engine.set_param("vca-1", "invert", 0.0);
+    let open = peak(&mut engine);
+    assert!(open > 0.5, "pad should pass with a high envelope: {open}");
+    assert!(ducked < open * 0.2, "pad should duck under the kick: {ducked} vs {open}");
+  }
+
+  #[test]
+  fn env_follower_tracks_an_amplitude_modulated_tone() {
+    // 1 kHz tone, amplitude swept by a 2 Hz LFO.
+    let graph = GraphBuilder::new()
+      .add_module("osc-1", "oscillator", json!({ "type": "sine", "frequency": 1000 }))
+      .add_module("lfo-1", "lfo", json!({ "rate": 2, "depth": 0.5, "offset": 0.5, "shape": "sine" }))
+      .add_module("vca-1", "gain", json!({ "gain": 1 }))
+      .add_module("env-1", "env-follower", json!({ "attack": 0.001, "release": 0.02 }))
+      .connect("osc-1", "out", "vca-1", "in", "audio")
+      .connect("lfo-1", "cv-out", "vca-1", "cv", "cv")
+      .connect("vca-1", "out", "env-1", "in", "audio")
+      .build();
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(&graph).unwrap();
+    let (vca, env) = (engine.module_map["vca-1"][0], engine.module_map["env-1"][0]);
+    let cv_out = output_port_index(ModuleType::EnvFollower, "cv-out").unwrap();
+    let (mut tone, mut envelope) = (Vec::new(), Vec::new());
+    for _ in 0..48000 / 256 {
+      engine.render(256);
+      tone.extend_from_slice(engine.output_buffers[vca][0].channel(0));
+      envelope.extend_from_slice(engine.output_buffers[env][cv_out].channel(0));
+    }
+    // Modulation envelope: peak of the tone over the last two cycles.
+    let loudest = tone.iter().fold(0.0f32, |peak, &x| peak.max(x.abs()));
+    assert!(loudest > 0.5);
+    for i in 4800..envelope.len() {
+      let expected = tone[i - 96..=i].iter().fold(0.0f32, |peak, &x| peak.max(x.abs()));
+      assert!((envelope[i] - expected).abs() < 0.1 * loudest, "frame {i}: {} vs {expected}", envelope[i]);
+    }
+    // Loud and quiet halves of the LFO cycle are told apart.
+    let (low, high) = envelope[4800..].iter().fold((1.0f32, 0.0f32), |(lo, hi), &x| (lo.min(x), hi.max(x)));
+    assert!(high > 0.5 && low < 0.1 * high, "envelope range {low}..{high}");
+  }
+
+  #[test]
+  fn cv_bank_channels_are_bounded() {
+    let bank_graph = |kind: &str, params: &str, port: &str| {
+      format!(
+        r#"{{
+          "modules": [
+            {{ "id": "bank", "type": "{kind}", "params": {params} }},
+            {{ "id": "out-1", "type": "output", "params": {{ "level": 1 }} }}
+          ],
+          "connections": [
+            {{ "from": {{ "moduleId": "bank", "portId": "{port}" }}, "to": {{ "moduleId": "out-1", "portId": "in" }}, "kind": "cv" }}
+          ]
+        }}"#
+      )
+    };
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(&bank_graph("cv-bank", r#"{ "channels": 3 }"#, "cv-3")).unwrap();
+    engine.set_bank_channel_cv("bank", 0, 0.5);
+    engine.set_bank_channel_cv("bank", 4, 0.75);
+    assert_eq!(engine.render(8)[0], 0.0, "channels 0 and 4 are out of range");
+    engine.set_bank_channel_cv("bank", 3, 0.25);
+    assert_eq!(engine.render(8)[0], 0.25);
+
+    // Ports past the channel count don't connect.
+    engine.set_graph_json(&bank_graph("cv-bank", r#"{ "channels": 3 }"#, "gate-4")).unwrap();
+    engine.set_bank_channel_gate("bank", 4, 1.0);
+    assert_eq!(engine.render(8)[0], 0.0);
+
+    // `mario` keeps its five channels and the old entry points.
+    engine.set_graph_json(&bank_graph("mario", "{}", "gate-5")).unwrap();
+    engine.set_mario_channel_gate("bank", 5, 1.0);
+    assert_eq!(engine.render(8)[0], 1.0);
+    engine.set_mario_channel_gate("bank", 6, 0.0);
+    assert_eq!(engine.render(8)[0], 1.0);
+  }
+
+  #[test]
+  fn legato_glide_only_between_overlapping_notes() {
+    const GRAPH: &str = r#"{
+      "modules": [
+        { "id": "ctrl-1", "type": "control", "params": { "voices": 1, "glide": 0.01, "glideMode": "legato" } },
+        { "id": "out-1", "type": "output", "params": { "level": 1 } }
+      ],
+      "connections": [
+        { "from": { "moduleId": "ctrl-1", "portId": "cv-out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "cv" }
+      ]
+    }"#;
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(GRAPH).unwrap();
+    let mut play = |cv: f32, gate: f32| {
+      engine.set_control_voice_cv("ctrl-1", 0, cv);
+      engine.set_control_voice_gate("ctrl-1", 0, gate);
+      engine.render(64)[0]
+    };
+
+    // Separated notes: the gate is low when the new pitch arrives.
+    play(0.0, 1.0);
+    play(0.0, 0.0);
+    assert_eq!(play(1.0, 1.0), 1.0, "a detached note should jump");
+    // Overlapping notes: the gate is still held, so the pitch glides.
+    let start = play(2.0, 1.0);
+    assert!(start > 1.0 && start < 1.01, "a legato note should glide: {start}");
+
+    engine.set_param("ctrl-1", "glideMode", 0.0);
+    engine.set_control_voice_gate("ctrl-1", 0, 0.0);
+    engine.render(960);
+    let mut play = |cv: f32| {
+      engine.set_control_voice_cv("ctrl-1", 0, cv);
+      engine.render(64)[0]
+    };
+    let start = play(0.0);
+    assert!(start < 2.0 && start > 1.99, "always mode glides detached notes: {start}");
+  }
+
+  #[test]
+  fn voice_glide_switches_portamento_per_voice() {
+    const GRAPH: &str = r#"{
+      "modules": [
+        { "id": "ctrl-1", "type": "control", "params": { "voices": 2, "glide": 0 } },
+        { "id": "out-1", "type": "output", "params": { "level": 1 } }
+      ],
+      "connections": [
+        { "from": { "moduleId": "ctrl-1", "portId": "cv-out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "cv" }
+      ]
+    }"#;
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(GRAPH).unwrap();
+    let mut play = |glide: f32, cv: f32| {
+      engine.set_control_voice_glide("ctrl-1", 0, glide);
+      engine.set_control_voice_cv("ctrl-1", 0, cv);
+      let first = engine.render(64)[0];
+      engine.render(960);
+      first
+    };
+
+    assert_eq!(play(0.0, 1.0), 1.0, "no glide should jump");
+    let start = play(0.01, 2.0);
+    assert!(start > 1.0 && start < 1.01, "glide on should ramp: {start}");
+    assert_eq!(play(0.0, 0.0), 0.0, "glide off should jump again");
+  }
+
+  #[test]
+  fn exponential_glide_leads_linear_and_lands_on_target() {
+    let glide = |curve: &str| {
+      let graph = format!(
+        r#"{{
+          "modules": [
+            {{ "id": "ctrl-1", "type": "control", "params": {{ "voices": 1, "glide": 0.01, "glideCurve": "{curve}" }} }},
+            {{ "id": "out-1", "type": "output", "params": {{ "level": 1 }} }}
+          ],
+          "connections": [
+            {{ "from": {{ "moduleId": "ctrl-1", "portId": "cv-out" }}, "to": {{ "moduleId": "out-1", "portId": "in" }}, "kind": "cv" }}
+          ]
+        }}"#
+      );
+      let mut engine = GraphEngine::new(48000.0);
+      engine.set_graph_json(&graph).unwrap();
+      engine.set_control_voice_cv("ctrl-1", 0, 2.0);
+      engine.render(600)[..600].to_vec()
+    };
+    let linear = glide("linear");
+    let exponential = glide("exponential");
+
+    // 480-sample glide: the linear ramp is halfway at 240 samples, the RC
+    // curve is already past 90% of the way.
+    assert!((linear[239] - 1.0).abs() < 0.01, "linear midpoint {}", linear[239]);
+    assert!(exponential[239] > 1.8, "exponential midpoint {}", exponential[239]);
+    assert!(exponential[59] > linear[59]);
+    assert!(exponential.windows(2).all(|pair| pair[1] >= pair[0]));
+    assert_eq!(linear[479], 2.0);
+    assert_eq!(exponential[479], 2.0);
+  }
+
+  #[test]
+  fn control_voices_get_fixed_detune_and_pan_offsets() {
+    const GRAPH: &str = r#"{
+      "modules": [
+        { "id": "ctrl-1", "type": "control", "params": { "voices": 8, "voiceDetune": 12, "voiceSpread": 1 } },
+        { "id": "out-1", "type": "output", "params": { "level": 1 } }
+      ],
+      "connections": [
+        { "from": { "moduleId": "ctrl-1", "portId": "pan-out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "cv" }
+      ]
+    }"#;
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(GRAPH).unwrap();
+    let voice_outputs = |engine: &mut GraphEngine| {
+      engine.render(16);
+      engine.module_map["ctrl-1"]
+        .clone()
+        .into_iter()
+        .map(|index| {
+          let outputs = &engine.output_buffers[index];
+          (outputs[0].channel(0)[15], outputs[4].channel(0)[15])
+        })
+        .collect::<Vec<_>>()
+    };
+
+    let before = voice_outputs(&mut engine);
+    assert_eq!(before.len(), 8);
+    for (voice, &(cv, pan)) in before.iter().enumerate() {
+      assert!(cv.abs() <= 0.01 && pan.abs() <= 1.0, "voice {voice}: cv {cv}, pan {pan}");
+      for &(other_cv, other_pan) in &before[..voice] {
+        assert!(cv != other_cv && pan != other_pan, "voice {voice} repeats an offset");
+      }
+    }
+
+    // Changing the amounts scales the same offsets; rebuilding keeps them.
+    engine.set_param("ctrl-1", "voiceDetune", 24.0);
+    engine.set_param("ctrl-1", "voiceSpread", 0.5);
+    let after = voice_outputs(&mut engine);
+    for (&(cv, pan), &(cv_after, pan_after)) in before.iter().zip(&after) {
+      assert!((cv_after - 2.0 * cv).abs() < 1e-6 && (pan_after - 0.5 * pan).abs() < 1e-6);
+    }
+    let mut rebuilt = GraphEngine::new(48000.0);
+    rebuilt.set_graph_json(GRAPH).unwrap();
+    assert_eq!(voice_outputs(&mut rebuilt), before);
+  }
+
+  /// Frequency of the sine the Control's note drives, from the first and
+  /// last rising zero crossings of a second of audio.
+  fn tuned_frequency(params: serde_json::Value, note: u8) -> f32 {
+    let graph = GraphBuilder::new()
+      .add_module("ctrl-1", "control", params)
+      .add_module("osc-1", "oscillator", json!({ "type": "sine", "frequency": 261.63 }))
+      .add_module("out-1", "output", json!({ "level": 1 }))
+      .connect("ctrl-1", "cv-out", "osc-1", "pitch", "cv")
+      .connect("osc-1", "out", "out-1", "in", "audio")
+      .build();
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(&graph).unwrap();
+    engine.set_control_voice_cv("ctrl-1", 0, engine.note_to_cv("ctrl-1", note));
+    let mut audio = Vec::new();
+    for _ in 0..48000 / 480 {
+      audio.extend_from_slice(&engine.render(480)[..480]);
+    }
+    let crossings: Vec<f32> = audio
+      .windows(2)
+      .enumerate()
+      .filter(|(_, pair)| pair[0] < 0.0 && pair[1] >= 0.0)
+      .map(|(i, pair)| i as f32 + pair[0] / (pair[0] - pair[1]))
+      .collect();
+    let (first, last) = (crossings[0], crossings[crossings.len() - 1]);
+    48000.0 * (crossings.len() - 1) as f32 / (last - first)
+  }
+
+  #[test]
+  fn control_tuning_follows_edo_and_reference() {
+    let edo19 = json!({ "voices": 1, "edo": 19 });
+    assert!((tuned_frequency(edo19.clone(), 60) - 261.63).abs() < 0.05);
+    let step = tuned_frequency(edo19.clone(), 61);
+    assert!((step - 261.63 * 2f32.powf(1.0 / 19.0)).abs() < 0.05, "{step}");
+    assert!((tuned_frequency(edo19, 79) - 523.26).abs() < 0.1);
+    // A = 432 Hz puts middle C at 256.87 Hz.
+    let low = tuned_frequency(json!({ "voices": 1, "refFreq": 256.87 }), 69);
+    assert!((low - 432.0).abs() < 0.1, "{low}");
+  }
+
+  #[test]
+  fn control_tuning_table_gives_just_intervals() {
+    // 7-note just major scale, Scala style: degrees 1..7, the last is the octave.
+    let just = [9.0f32 / 8.0, 5.0 / 4.0, 4.0 / 3.0, 3.0 / 2.0, 5.0 / 3.0, 15.0 / 8.0, 2.0];
+    let cents: Vec<f32> = just.iter().map(|ratio| 1200.0 * ratio.log2()).collect();
+    let params = json!({ "voices": 1, "tuning": cents });
+    // Notes count scale degrees: 64 is the fifth, 67 the next octave, 57 the
+    // fifth an octave down.
+    for (note, ratio) in [(62, 5.0 / 4.0), (64, 3.0 / 2.0), (67, 2.0), (69, 2.5), (57, 3.0 / 4.0)] {
+      let frequency = tuned_frequency(params.clone(), note);
+      assert!((frequency - 261.63 * ratio).abs() < 0.1, "note {note}: {frequency}");
+    }
+
+    // Live updates; a cleared table falls back to the EDO.
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(&GraphBuilder::new().add_module("ctrl-1", "control", json!({})).build()).unwrap();
+    assert_eq!(engine.note_to_cv("ctrl-1", 72), dsp_core::midi_note_to_cv(72));
+    engine.set_param_json("ctrl-1", "tuning", &json!(cents));
+    assert!((engine.note_to_cv("ctrl-1", 64) - 1.5f32.log2()).abs() < 1e-6);
+    engine.set_param_json("ctrl-1", "edo", &json!(24));
+    engine.set_param_json("ctrl-1", "tuning", &json!([]));
+    assert_eq!(engine.note_to_cv("ctrl-1", 66), 0.25);
+    assert!(engine.to_graph_json().contains("\"tuning\":[]"));
+  }
+
+  #[test]
+  fn velocity_retarget_mid_slew_stays_continuous() {
+    const GRAPH: &str = r#"{
+      "modules": [
+        { "id": "ctrl-1", "type": "control", "params": { "voices": 1, "velocity": 0.2 } },
+        { "id": "out-1", "type": "output", "params": { "level": 1 } }
+      ],
+      "connections": [
+        { "from": { "moduleId": "ctrl-1", "portId": "vel-out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "cv" }
+      ]
+    }"#;
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(GRAPH).unwrap();
+    let mut velocity = engine.render(64)[..64].to_vec();
+
+    // Two updates 64 frames apart, each with a 10 ms (480-frame) slew.
+    engine.set_control_voice_velocity("ctrl-1", 0, 1.0, 0.01);
+    velocity.extend_from_slice(&engine.render(64)[..64]);
+    engine.set_control_voice_velocity("ctrl-1", 0, 0.4, 0.01);
+    for _ in 0..10 {
+      velocity.extend_from_slice(&engine.render(64)[..64]);
+    }
+
+    let largest_step = velocity.windows(2).fold(0.0_f32, |acc, pair| acc.max((pair[1] - pair[0]).abs()));
+    assert!(largest_step < 0.01, "vel-out jumped by {largest_step}");
+    assert_eq!(velocity[0], 0.2);
+    assert!(velocity[127] > 0.3, "first slew should be under way: {}", velocity[127]);
+    assert_eq!(*velocity.last().unwrap(), 0.4);
+  }
+
+  #[test]
+  fn silent_graph_idles_and_wakes_on_gate() {
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(GATED_SYNTH_GRAPH).unwrap();
+    engine.set_idle_hold(0.1);
+
+    // 0.1 s = 4800 frames of silence with no gate held.
+    for _ in 0..40 {
+      engine.render(128);
+    }
+    assert!(engine.is_idle());
+    assert_eq!(peak(engine.render(128)), 0.0);
+
+    engine.set_control_voice_gate("ctrl-1", 0, 1.0);
+    assert!(!engine.is_idle());
+    let mut loudest: f32 = 0.0;
+    for _ in 0..8 {
+      loudest = loudest.max(peak(&engine.render(128)[..128]));
+    }
+    assert!(loudest > 0.1, "{loudest}");
+
+    // A held gate never idles, even if the output were quiet.
+    for _ in 0..80 {
+      engine.render(128);
+    }
+    assert!(!engine.is_idle());
+  }
+
+  #[test]
+  fn reverb_tail_keeps_engine_awake() {
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(REVERB_GRAPH).unwrap();
+    engine.set_idle_hold(0.05);
+
+    let mut impulse = vec![0.0; 128];
+    impulse[0] = 1.0;
+    engine.set_external_input(&impulse);
+    engine.render(128);
+    engine.clear_external_input();
+
+    // The tail is still well above -90 dBFS after 0.2 s.
+    for _ in 0..75 {
+      engine.render(128);
+    }
+    assert!(!engine.is_idle());
+    assert!(peak(&engine.render(128)[..128]) > 1e-4);
+  }
+
+  /// [`GATED_SYNTH_GRAPH`] plus `source` patched from `from` to `to`.
+  fn gated_synth_with(source: serde_json::Value, from: &str, to: (&str, &str), kind: &str) -> String {
+    let mut graph: serde_json::Value = serde_json::from_str(GATED_SYNTH_GRAPH).unwrap();
+    let source_id = source["id"].clone();
+    graph["modules"].as_array_mut().unwrap().push(source);
+    graph["connections"].as_array_mut().unwrap().push(json!({
+      "from": { "moduleId": source_id, "portId": from },
+      "to": { "moduleId": to.0, "portId": to.1 },
+      "kind": kind
+    }));
+    graph.to_string()
+  }
+
+  #[test]
+  fn sequenced_graph_never_idles() {
+    let clock = json!({ "id": "clk-1", "type": "clock", "params": {} });
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(&gated_synth_with(clock, "clock", ("env-1", "gate"), "sync")).unwrap();
+    engine.set_idle_hold(0.0);
+    engine.render(128);
+    assert!(!engine.is_idle());
+
+    // A clock that drives nothing audible doesn't keep the engine awake.
+    engine
+      .set_graph_json(
+        r#"{ "modules": [{ "id": "clk-1", "type": "clock", "params": {} }], "connections": [] }"#,
+      )
+      .unwrap();
+    engine.render(128);
+    assert!(engine.is_idle());
+  }
+
+  #[test]
+  fn lfo_on_a_silent_voice_idles() {
+    let lfo = json!({ "id": "lfo-1", "type": "lfo", "params": { "rate": 2 } });
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(&gated_synth_with(lfo, "cv-out", ("osc-1", "pitch"), "cv")).unwrap();
+    engine.set_idle_hold(0.1);
+
+    engine.set_control_voice_gate("ctrl-1", 0, 1.0);
+    for _ in 0..80 {
+      engine.render(128);
+    }
+    assert!(!engine.is_idle());
+
+    // Gate closed: once the release has faded the LFO has nothing to move.
+    engine.set_control_voice_gate("ctrl-1", 0, 0.0);
+    for _ in 0..80 {
+      engine.render(128);
+    }
+    assert!(engine.is_idle());
+  }
+
+  #[test]
+  fn lfo_on_a_vca_keeps_a_drone_awake() {
+    // A free-running oscillator whose level a 0.5 Hz square LFO switches off
+    // for a second at a time, far longer than the idle hold.
+    const GRAPH: &str = r#"{
+      "modules": [
+        { "id": "osc-1", "type": "oscillator", "params": { "frequency": 220 } },
+        { "id": "lfo-1", "type": "lfo", "params": { "rate": 0.5, "shape": 3, "depth": 1, "bipolar": 0 } },
+        { "id": "vca-1", "type": "gain", "params": { "gain": 1 } },
+        { "id": "out-1", "type": "output", "params": { "level": 1 } }
+      ],
+      "connections": [
+        { "from": { "moduleId": "osc-1", "portId": "out" }, "to": { "moduleId": "vca-1", "portId": "in" }, "kind": "audio" },
+        { "from": { "moduleId": "lfo-1", "portId": "cv-out" }, "to": { "moduleId": "vca-1", "portId": "cv" }, "kind": "cv" },
+        { "from": { "moduleId": "vca-1", "portId": "out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "audio" }
+      ]
+    }"#;
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(GRAPH).unwrap();
+    engine.set_idle_hold(0.1);
+
+    // Halfway through the trough.
+    for _ in 0..563 {
+      engine.render(128);
+    }
+    assert!(!engine.is_idle());
+    assert!(peak(&engine.render(128)[..128]) < 1e-4);
+
+    // The next swell plays instead of being skipped.
+    let mut loudest: f32 = 0.0;
+    for _ in 0..250 {
+      loudest = loudest.max(peak(&engine.render(128)[..128]));
+    }
+    assert!(loudest > 0.1, "{loudest}");
+  }
+
+  /// Magnitude of one frequency component (single-bin DFT).
+  fn magnitude(signal: &[Sample], freq: f32, sample_rate: f32) -> f32 {
+    let (mut re, mut im) = (0.0f32, 0.0f32);
+    for (n, &s) in signal.iter().enumerate() {
+      let angle = std::f32::consts::TAU * freq * n as f32 / sample_rate;
+      re += s * angle.cos();
+      im -= s * angle.sin();
+    }
+    (re * re + im * im).sqrt() / signal.len() as f32
+  }
+
+  #[test]
+  fn vco_fm_audio_input_adds_sidebands() {
+    let render_carrier = |patched: bool| {
+      let cable = if patched {
+        r#"{ "from": { "moduleId": "mod-1", "portId": "out" }, "to": { "moduleId": "car-1", "portId": "fm-audio" }, "kind": "audio" },"#
+      } else {
+        ""
+      };
+      let graph = format!(
+        r#"{{
+          "modules": [
+            {{ "id": "mod-1", "type": "oscillator", "params": {{ "frequency": 100, "type": "sine" }} }},
+            {{ "id": "car-1", "type": "oscillator", "params": {{ "frequency": 400, "type": "sine", "fmLin": 200 }} }},
+            {{ "id": "out-1", "type": "output", "params": {{ "level": 1 }} }}
+          ],
+          "connections": [
+            {cable}
+            {{ "from": {{ "moduleId": "car-1", "portId": "out" }}, "to": {{ "moduleId": "out-1", "portId": "in" }}, "kind": "audio" }}
+          ]
+        }}"#
+      );
+      let mut engine = GraphEngine::new(48000.0);
+      engine.set_graph_json(&graph).unwrap();
+      engine.render(4800);
+      engine.render(4800)[..4800].to_vec()
+    };
+
+    let dry = render_carrier(false);
+    let fm = render_carrier(true);
+    for sideband in [300.0, 500.0] {
+      let before = magnitude(&dry, sideband, 48000.0);
+      let after = magnitude(&fm, sideband, 48000.0);
+      assert!(before < 0.01, "{sideband} Hz present without FM: {before}");
+      assert!(after > 0.05, "{sideband} Hz sideband missing: {after}");
+    }
+  }
+
+  #[test]
+  fn vco_stereo_spread_pans_unison_voices() {
+    let render_vco = |spread: f32| {
+      let mut engine = GraphEngine::new(48000.0);
+      engine
+        .set_graph_json(&format!(
+          r#"{{ "modules": [{{ "id": "osc-1", "type": "oscillator", "params": {{ "frequency": 110, "unison": 4, "detune": 12, "stereoSpread": {spread} }} }}], "connections": [] }}"#
+        ))
+        .unwrap();
+      engine.render(512);
+      let osc = engine.module_map["osc-1"][0];
+      let out = &engine.output_buffers[osc][0];
+      (out.channel(0).to_vec(), out.channel(1).to_vec())
+    };
+
+    let (left, right) = render_vco(0.0);
+    assert_eq!(left, right, "no spread should stay mono");
+    assert!(peak(&left) > 0.1);
+
+    let (left, right) = render_vco(1.0);
+    let difference: Vec<f32> = left.iter().zip(&right).map(|(l, r)| l - r).collect();
+    assert!(peak(&difference) > 0.1, "full spread should decorrelate L/R");
+  }
+
+  #[test]
+  fn fm_matrix_algorithm_changes_the_spectrum() {
+    let render_fm = |params: &str| {
+      let mut engine = GraphEngine::new(48000.0);
+      engine
+        .set_graph_json(&format!(
+          r#"{{
+            "modules": [
+              {{ "id": "ctrl-1", "type": "control", "params": {{ "voices": 1 }} }},
+              {{ "id": "fm-1", "type": "fm-matrix", "params": {{ {params} }} }},
+              {{ "id": "out-1", "type": "output", "params": {{ "level": 1 }} }}
+            ],
+            "connections": [
+              {{ "from": {{ "moduleId": "ctrl-1", "portId": "gate-out" }}, "to": {{ "moduleId": "fm-1", "portId": "gate" }}, "kind": "gate" }},
+              {{ "from": {{ "moduleId": "fm-1", "portId": "out" }}, "to": {{ "moduleId": "out-1", "portId": "in" }}, "kind": "audio" }}
+            ]
+          }}"#
+        ))
+        .unwrap();
+      engine.set_control_voice_gate("ctrl-1", 0, 1.0);
+      // Skip the attack, then analyse 0.1 s of the held note.
+      engine.render(2400);
+      engine.render(4800)[..4800].to_vec()
+    };
+
+    // Algorithm 8 is all carriers: pure harmonics at ratios 1-4 of 220 Hz.
+    // The 4→3→2→1 stack instead spreads energy into FM sidebands.
+    let additive = render_fm(r#""algorithm": 7, "feedback": 0"#);
+    let stacked = render_fm(r#""algorithm": 0, "feedback": 0, "op2Level": 1, "op3Level": 1, "op4Level": 1"#);
+    let fundamental_a = magnitude(&additive, 220.0, 48000.0);
+    let fundamental_b = magnitude(&stacked, 220.0, 48000.0);
+    assert!(fundamental_a > 0.05, "additive fundamental missing: {fundamental_a}");
+    assert!(
+      (fundamental_a - fundamental_b).abs() > 0.02,
+      "algorithm change left the spectrum unchanged: {fundamental_a} vs {fundamental_b}"
+    );
+
+    // The free matrix and the nested form reach the same state.
+    let mut engine = GraphEngine::new(48000.0);
+    engine
+      .set_graph_json(
+        r#"{ "modules": [{ "id": "fm-1", "type": "fm-matrix", "params": {
+          "operators": [{ "ratio": 0.5 }, {}, {}, { "release": 42 }],
+          "matrix": [[0, 0.25, 0, 0]],
+          "op1Level": 0.6
+        } }], "connections": [] }"#,
+      )
+      .unwrap();
+    assert_eq!(engine.get_param("fm-1", "op1_ratio"), Some(0.5));
+    assert_eq!(engine.get_param("fm-1", "op4Release"), Some(42.0));
+    assert_eq!(engine.get_param("fm-1", "matrix12"), Some(0.25));
+    assert_eq!(engine.get_param("fm-1", "op1Level"), Some(0.6));
+    engine.set_param("fm-1", "matrix43", 0.5);
+    assert_eq!(engine.get_param("fm-1", "matrix43"), Some(0.5));
+    engine.set_param("fm-1", "op1Ratio", 2.0);
+    assert_eq!(engine.get_param("fm-1", "op1_ratio"), Some(2.0));
+    assert_eq!(engine.get_param("fm-1", "op2Ratio"), Some(2.0));
+    assert_eq!(engine.get_param("fm-1", "op5Ratio"), None);
+  }
+
+  #[test]
+  fn sends_sum_into_their_return_bus() {
+    let mut engine = GraphEngine::new(48000.0);
+    engine
+      .set_graph_json(
+        r#"{
+          "modules": [
+            { "id": "in-1", "type": "audio-in", "params": { "gain": 1 } },
+            { "id": "gain-1", "type": "gain", "params": { "gain": 1 } },
+            { "id": "gain-2", "type": "gain", "params": { "gain": 1 } },
+            { "id": "send-1", "type": "send", "params": { "bus": "reverbBus", "level": 0.3 } },
+            { "id": "send-2", "type": "send", "params": { "bus": "reverbBus", "level": 0.7 } },
+            { "id": "send-3", "type": "send", "params": { "bus": "otherBus", "level": 1 } },
+            { "id": "ret-1", "type": "return", "params": { "bus": "reverbBus", "level": 1 } },
+            { "id": "out-1", "type": "output", "params": { "level": 1 } }
+          ],
+          "connections": [
+            { "from": { "moduleId": "in-1", "portId": "out" }, "to": { "moduleId": "gain-1", "portId": "in" }, "kind": "audio" },
+            { "from": { "moduleId": "in-1", "portId": "out" }, "to": { "moduleId": "gain-2", "portId": "in" }, "kind": "audio" },
+            { "from": { "moduleId": "gain-1", "portId": "out" }, "to": { "moduleId": "send-1", "portId": "in" }, "kind": "audio" },
+            { "from": { "moduleId": "gain-2", "portId": "out" }, "to": { "moduleId": "send-2", "portId": "in" }, "kind": "audio" },
+            { "from": { "moduleId": "in-1", "portId": "out" }, "to": { "moduleId": "send-3", "portId": "in" }, "kind": "audio" },
+            { "from": { "moduleId": "ret-1", "portId": "out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "audio" }
+          ]
+        }"#,
+      )
+      .unwrap();
+    engine.set_external_input(&[0.5; 128]);
+    engine.render(128);
+
+    // 0.5 * 0.3 + 0.5 * 0.7 on reverbBus; otherBus doesn't leak in.
+    let ret = engine.module_map["ret-1"][0];
+    let bus = engine.output_buffers[ret][0].channel(0)[127];
+    assert!((bus - 0.5).abs() < 1e-4, "{bus}");
+    let send = engine.module_map["send-1"][0];
+    assert!((engine.output_buffers[send][0].channel(0)[127] - 0.5).abs() < 1e-4);
+    assert!((engine.output_buffers[send][1].channel(0)[127] - 0.15).abs() < 1e-4);
+
+    // Renaming the return's bus re-wires it without a graph reload.
+    engine.set_param_string("ret-1", "bus", "otherBus");
+    engine.render(128);
+    let bus = engine.output_buffers[ret][0].channel(0)[127];
+    assert!((bus - 0.5).abs() < 1e-4, "{bus}");
+    engine.set_param_string("ret-1", "bus", "nothing");
+    engine.render(128);
+    assert_eq!(engine.output_buffers[ret][0].channel(0)[127], 0.0);
+  }
+
+  const VOICE_BOUND_GRAPH: &str = r#"{
+    "modules": [
+      { "id": "ctrl-1", "type": "control", "params": { "voices": 4 } },
+      { "id": "gain-a", "type": "gain", "params": { "gain": 1 } },
+      { "id": "gain-b", "type": "gain", "params": { "gain": 1 } }
+    ],
+    "connections": [
+      { "from": { "moduleId": "ctrl-1", "portId": "cv-out", "voice": 0 }, "to": { "moduleId": "gain-a", "portId": "cv" }, "kind": "cv" },
+      { "from": { "moduleId": "ctrl-1", "portId": "cv-out", "voice": 3 }, "to": { "moduleId": "gain-b", "portId": "cv" }, "kind": "cv" }
+    ]
+  }"#;
+
+  /// CV seen on the `cv` input of every instance of `module_id`.
+  fn cv_inputs(engine: &GraphEngine, module_id: &str) -> Vec<Sample> {
+    engine.module_map[module_id]
+      .iter()
+      .map(|&index| engine.input_buffers[index][1].channel(0)[0])
+      .collect()
+  }
+
+  #[test]
+  fn voice_bound_connections_pick_one_voice() {
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(VOICE_BOUND_GRAPH).unwrap();
+    for voice in 0..4 {
+      engine.set_control_voice_cv("ctrl-1", voice, 0.25 * voice as f32);
+    }
+    engine.render(64);
+
+    assert_eq!(cv_inputs(&engine, "gain-a"), vec![0.0; 4]);
+    assert_eq!(cv_inputs(&engine, "gain-b"), vec![0.75; 4]);
+    assert!(engine.to_graph_json().contains(r#""voice":3"#));
+  }
+
+  #[test]
+  fn voice_bound_connections_follow_voice_count() {
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(VOICE_BOUND_GRAPH).unwrap();
+    let gain_b = engine.module_map["gain-b"][0];
+    assert_eq!(engine.modules[gain_b].connections[1].len(), 1);
+    assert!(engine.diagnostics().is_empty());
+
+    // Voice 3 no longer exists: its cable is dropped, voice 0 is kept.
+    engine.set_voice_count(2);
+    assert_eq!(engine.voice_count(), 2);
+    let gain_a = engine.module_map["gain-a"][0];
+    let gain_b = engine.module_map["gain-b"][0];
+    assert_eq!(engine.modules[gain_a].connections[1].len(), 1);
+    assert!(engine.modules[gain_b].connections[1].is_empty());
+    assert_eq!(engine.diagnostics().len(), 1);
+    assert!(engine.diagnostics()[0].contains("ctrl-1:cv-out -> gain-b:cv"), "{:?}", engine.diagnostics());
+
+    // Growing back re-resolves it from the stored graph.
+    engine.set_voice_count(4);
+    let gain_b = engine.module_map["gain-b"][0];
+    assert_eq!(engine.modules[gain_b].connections[1].len(), 1);
+    assert!(engine.diagnostics().is_empty());
+  }
+
+  fn delay_echo_offset(engine: &mut GraphEngine, frames: usize) -> usize {
+    let mut impulse = vec![0.0; frames];
+    impulse[0] = 1.0;
+    let mut rendered = Vec::new();
+    for block in 0..16 {
+      if block == 0 {
+        engine.set_external_input(&impulse);
+      } else if block == 1 {
+        engine.set_external_input(&vec![0.0; frames]);
+      }
+      rendered.extend_from_slice(&engine.render(frames)[..frames]);
+    }
+    rendered
+      .iter()
+      .enumerate()
+      .fold((0, 0.0), |best, (i, s)| if s.abs() > best.1 { (i, s.abs()) } else { best })
+      .0
+  }
+
+  #[test]
+  fn set_sample_rate_keeps_delay_time_in_ms() {
+    const DELAY_GRAPH: &str = r#"{
+      "modules": [
+        { "id": "in-1", "type": "audio-in", "params": { "gain": 1 } },
+        { "id": "delay-1", "type": "delay", "params": { "time": 100, "feedback": 0, "mix": 1, "tone": 1 } },
+        { "id": "out-1", "type": "output", "params": { "level": 1 } }
+      ],
+      "connections": [
+        { "from": { "moduleId": "in-1", "portId": "out" }, "to": { "moduleId": "delay-1", "portId": "in" }, "kind": "audio" },
+        { "from": { "moduleId": "delay-1", "portId": "out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "audio" }
+      ]
+    }"#;
+
+    let mut engine = GraphEngine::new(44100.0);
+    engine.set_graph_json(DELAY_GRAPH).unwrap();
+    let offset_44k = delay_echo_offset(&mut engine, 512);
+    let ms_44k = offset_44k as f32 / 44.1;
+
+    engine.set_sample_rate(48000.0);
+    assert_eq!(engine.module_map.len(), 3);
+    let offset_48k = delay_echo_offset(&mut engine, 512);
+    let ms_48k = offset_48k as f32 / 48.0;
+
+    assert!((ms_44k - 100.0).abs() < 0.5, "44.1k echo at {ms_44k} ms");
+    assert!((ms_48k - 100.0).abs() < 0.5, "48k echo at {ms_48k} ms");
+  }
+
+  #[test]
+  fn reset_silences_reverb_tail() {
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(REVERB_GRAPH).unwrap();
+
+    let burst: Vec<Sample> = (0..128).map(|i| (i as f32 * 0.05).sin() * 0.8).collect();
+    for _ in 0..32 {
+      engine.set_external_input(&burst);
+      engine.render(128);
+    }
+    engine.clear_external_input();
+    assert!(peak(engine.render(128)) > 1e-4, "reverb should ring after input stops");
+
+    engine.reset();
+    assert_eq!(peak(engine.render(128)), 0.0);
+
+    // Grains in flight stop too; the loaded buffer stays for the next ones.
+    engine
+      .set_graph_json(
+        r#"{
+          "modules": [
+            { "id": "gran-1", "type": "granular", "params": { "density": 20, "size": 200 } },
+            { "id": "out-1", "type": "output", "params": { "level": 1 } }
+          ],
+          "connections": [
+            { "from": { "moduleId": "gran-1", "portId": "out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "audio" }
+          ]
+        }"#,
+      )
+      .unwrap();
+    let sample: Vec<Sample> = (0..48000).map(|i| (i as f32 * 0.05).sin() * 0.8).collect();
+    engine.load_granular_buffer("gran-1", &sample);
+    for _ in 0..32 {
+      engine.render(128);
+    }
+    assert!(peak(engine.render(128)) > 1e-4, "grains should be playing");
+
+    engine.reset();
+    assert_eq!(peak(engine.render(128)), 0.0);
+    assert_eq!(engine.get_granular_buffer_length("gran-1"), sample.len());
+  }
+
+  #[test]
+  fn step_sequencer_slide_ties_tb303_notes() {
+    // Returns (env retriggers, sequencer gate) over the first two notes.
+    let run = |slide: bool| {
+      let mut engine = GraphEngine::new(48000.0);
+      engine
+        .set_graph_json(
+          r#"{
+            "modules": [
+              { "id": "seq-1", "type": "step-sequencer", "params": { "tempo": 120, "rate": 4, "length": 2 } },
+              { "id": "tb-1", "type": "tb303", "params": { "waveform": "saw" } }
+            ],
+            "connections": [
+              { "from": { "moduleId": "seq-1", "portId": "cv-out" }, "to": { "moduleId": "tb-1", "portId": "pitch" }, "kind": "cv" },
+              { "from": { "moduleId": "seq-1", "portId": "gate-out" }, "to": { "moduleId": "tb-1", "portId": "gate" }, "kind": "gate" },
+              { "from": { "moduleId": "seq-1", "portId": "slide-out" }, "to": { "moduleId": "tb-1", "portId": "slide" }, "kind": "gate" }
+            ]
+          }"#,
+        )
+        .unwrap();
+      // Steps play 1, 0, 1...: step 1 slides up an octave into step 0.
+      engine.set_param_string(
+        "seq-1",
+        "stepData",
+        &format!(r#"[{{"pitch":12,"gate":true}},{{"pitch":0,"gate":true,"slide":{slide}}}]"#),
+      );
+      let seq = engine.module_map["seq-1"][0];
+      let tb = engine.module_map["tb-1"][0];
+      let (mut env, mut gate) = (Vec::new(), Vec::new());
+      for _ in 0..17000 / 500 {
+        engine.render(500);
+        env.extend_from_slice(engine.output_buffers[tb][1].channel(0));
+        gate.extend_from_slice(engine.output_buffers[seq][1].channel(0));
+      }
+      let retriggers = env.windows(2).filter(|w| w[1] > w[0]).count();
+      (retriggers, gate)
+    };
+
+    let (retriggers, gate) = run(true);
+    assert_eq!(retriggers, 1, "tied step retriggered the envelope");
+    let start = gate.iter().position(|&g| g > 0.5).unwrap();
+    // 6000 frames per step: the gate holds across the step boundary.
+    assert!(gate[start..start + 6500].iter().all(|&g| g > 0.5), "slide step released its gate");
+
+    let (retriggers, _) = run(false);
+    assert_eq!(retriggers, 2);
+  }
+
+  #[test]
+  fn turing_machine_locks_into_a_seeded_loop() {
+    // CV after each clock: 24 clocks at probability 1, then 24 locked.
+    let run = |seed: u32| {
+      let mut engine = GraphEngine::new(48000.0);
+      engine
+        .set_graph_json(&format!(
+          r#"{{
+            "modules": [
+              {{ "id": "clock-1", "type": "clock", "params": {{ "tempo": 300, "rate": 4 }} }},
+              {{ "id": "turing-1", "type": "turing-machine", "params": {{ "probability": 1, "length": 5, "seed": {seed} }} }}
+            ],
+            "connections": [
+              {{ "from": {{ "moduleId": "clock-1", "portId": "clock" }}, "to": {{ "moduleId": "turing-1", "portId": "clock" }}, "kind": "gate" }}
+            ]
+          }}"#
+        ))
+        .unwrap();
+      let turing = engine.module_map["turing-1"][0];
+      let mut steps = Vec::new();
+      let mut last_pulse = 0.0;
+      while steps.len() < 48 {
+        engine.render(256);
+        let cv = engine.output_buffers[turing][0].channel(0);
+        let pulse = engine.output_buffers[turing][2].channel(0);
+        let before = steps.len();
+        for (&c, &p) in cv.iter().zip(pulse) {
+          if p > 0.5 && last_pulse <= 0.5 {
+            steps.push(c);
+          }
+          last_pulse = p;
+        }
+        if before < 24 && steps.len() >= 24 {
+          engine.set_param("turing-1", "probability", 0.0);
+        }
+      }
+      steps
+    };
+
+    let steps = run(7);
+    // The parameter lands on the next block, so allow one more random step.
+    let locked = &steps[26..];
+    assert!(locked.windows(6).all(|w| w[0] == w[5]), "{locked:?}");
+    assert_eq!(run(7), steps);
+    assert_ne!(run(8)[..24], steps[..24]);
+  }
+
+  #[test]
+  fn out_of_range_params_behave_like_their_bounds() {
+    const GRAPH: &str = r#"{
+      "modules": [
+        { "id": "noise-1", "type": "noise", "params": { "level": 0.5 } },
+        { "id": "vcf-1", "type": "vcf" },
+        { "id": "osc-1", "type": "oscillator" },
+        { "id": "out-1", "type": "output", "params": { "level": 1 } }
+      ],
+      "connections": [
+        { "from": { "moduleId": "noise-1", "portId": "out" }, "to": { "moduleId": "vcf-1", "portId": "in" }, "kind": "audio" },
+        { "from": { "moduleId": "vcf-1", "portId": "out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "audio" }
+      ]
+    }"#;
+    let render = |cutoff: f32, resonance: f32| {
+      let mut engine = GraphEngine::new(48000.0);
+      engine.set_graph_json(GRAPH).unwrap();
+      engine.set_param("vcf-1", "cutoff", cutoff);
+      engine.set_param("vcf-1", "resonance", resonance);
+      engine.render(512).to_vec()
+    };
+    assert_eq!(render(1.0e9, -3.0), render(12000.0, 0.0));
+    assert_eq!(render(f32::NAN, 0.4), render(800.0, 0.4));
+
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(GRAPH).unwrap();
+    engine.set_param("osc-1", "unison", 2.6);
+    assert_eq!(engine.get_param("osc-1", "unison"), Some(3.0));
+    engine.set_param("osc-1", "unison", 9.0);
+    assert_eq!(engine.get_param("osc-1", "unison"), Some(4.0));
+    // Parameters the table does not describe pass through.
+    engine.set_param("noise-1", "seed", 123456.0);
+    assert_eq!(engine.get_param("noise-1", "seed"), Some(123456.0));
+  }
+
+  #[test]
+  fn gliding_params_ramp_to_new_values() {
+    let mut buffer = ParamBuffer::new(0.0).with_glide(4);
+    buffer.set(1.0);
+    assert_eq!(buffer.value(), 1.0);
+    assert_eq!(buffer.slice(6), &[0.25, 0.5, 0.75, 1.0, 1.0, 1.0]);
+    assert_eq!(buffer.slice(2), &[1.0, 1.0]);
+
+    // A change mid-ramp starts from where the ramp got to.
+    buffer.set(0.0);
+    assert_eq!(buffer.slice(2), &[0.75, 0.5]);
+    buffer.set(1.0);
+    assert_eq!(buffer.slice(4), &[0.625, 0.75, 0.875, 1.0]);
+  }
+
+  #[test]
+  fn modulated_params_are_not_stored() {
+    const GRAPH: &str = r#"{
+      "modules": [
+        { "id": "vcf-1", "type": "vcf", "params": { "cutoff": 1000 } },
+        { "id": "out-1", "type": "output", "params": { "level": 1 } }
+      ],
+      "connections": []
+    }"#;
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(GRAPH).unwrap();
+
+    engine.modulate_param("vcf-1", "cutoff", 1.0e9);
+    assert_eq!(engine.get_param("vcf-1", "cutoff"), Some(1000.0));
+    let spec = engine.live_spec();
+    assert_eq!(spec.modules[0].params.as_ref().unwrap()["cutoff"], 1000.0);
+    assert_eq!(engine.snapshot_params().get("vcf-1", "cutoff"), Some(1000.0));
+  }
+
+  #[test]
+  fn loaded_params_are_constrained_like_set_param() {
+    let graph = |cutoff: &str, resonance: &str| {
+      format!(
+        r#"{{
+          "modules": [
+            {{ "id": "noise-1", "type": "noise", "params": {{ "level": 0.5 }} }},
+            {{ "id": "vcf-1", "type": "vcf", "params": {{ "cutoff": {cutoff}, "resonance": {resonance} }} }},
+            {{ "id": "osc-1", "type": "oscillator", "params": {{ "unison": 2.6 }} }},
+            {{ "id": "out-1", "type": "output", "params": {{ "level": 1 }} }}
+          ],
+          "connections": [
+            {{ "from": {{ "moduleId": "noise-1", "portId": "out" }}, "to": {{ "moduleId": "vcf-1", "portId": "in" }}, "kind": "audio" }},
+            {{ "from": {{ "moduleId": "vcf-1", "portId": "out" }}, "to": {{ "moduleId": "out-1", "portId": "in" }}, "kind": "audio" }}
+          ]
+        }}"#
+      )
+    };
+    let mut loaded = GraphEngine::new(48000.0);
+    loaded.set_graph_json(&graph("1e9", "-3")).unwrap();
+    assert_eq!(loaded.get_param("vcf-1", "cutoff"), Some(12000.0));
+    assert_eq!(loaded.get_param("vcf-1", "resonance"), Some(0.0));
+    assert_eq!(loaded.get_param("osc-1", "unison"), Some(3.0));
+
+    let mut bounded = GraphEngine::new(48000.0);
+    bounded.set_graph_json(&graph("12000", "0")).unwrap();
+    assert_eq!(loaded.render(512).to_vec(), bounded.render(512).to_vec());
+  }
+
+  #[test]
+  fn param_metadata_describes_ranges() {
+    let meta = GraphEngine::param_metadata("vcf");
+    let cutoff = meta.as_array().unwrap().iter().find(|p| p["id"] == "cutoff").unwrap();
+    assert_eq!(cutoff["min"], 40.0);
+    assert_eq!(cutoff["max"], 12000.0);
+    assert_eq!(cutoff["kind"], "log");
+
+    let meta = GraphEngine::param_metadata("lfo");
+    let shape = meta.as_array().unwrap().iter().find(|p| p["id"] == "shape").unwrap();
+    assert_eq!(shape["kind"], "enum");
+    assert_eq!(shape["labels"][3], "square");
+
+    assert_eq!(GraphEngine::param_metadata("not-a-module"), serde_json::json!([]));
+  }
+
+  #[test]
+  fn snapshots_recall_and_morph_params() {
+    const GRAPH: &str = r#"{
+      "modules": [
+        { "id": "lfo-1", "type": "lfo", "params": { "rate": 1, "depth": 0.2, "shape": 0 } },
+        { "id": "out-1", "type": "output", "params": { "level": 0.5 } }
+      ],
+      "connections": []
+    }"#;
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(GRAPH).unwrap();
+    engine.store_snapshot(0).unwrap();
+    assert_eq!(engine.snapshot_params().get("lfo-1", "depth"), Some(0.2));
+
+    engine.set_param("lfo-1", "depth", 0.8);
+    engine.set_param("lfo-1", "shape", 3.0);
+    engine.set_param("out-1", "level", 1.0);
+    engine.store_snapshot(1).unwrap();
+
+    engine.recall_snapshot(0).unwrap();
+    assert_eq!(engine.get_param("lfo-1", "depth"), Some(0.2));
+    assert_eq!(engine.get_param("lfo-1", "shape"), Some(0.0));
+    assert_eq!(engine.get_param("out-1", "level"), Some(0.5));
+
+    engine.morph_snapshots(0, 1, 0.25).unwrap();
+    assert!((engine.get_param("lfo-1", "depth").unwrap() - 0.35).abs() < 1e-6);
+    assert_eq!(engine.get_param("lfo-1", "shape"), Some(0.0));
+    engine.morph_snapshots(0, 1, 0.75).unwrap();
+    assert!((engine.get_param("out-1", "level").unwrap() - 0.875).abs() < 1e-6);
+    assert_eq!(engine.get_param("lfo-1", "shape"), Some(3.0));
+
+    assert!(engine.recall_snapshot(2).is_err());
+    assert!(engine.store_snapshot(SNAPSHOT_SLOTS).is_err());
+
+    // Loading a graph forgets every slot.
+    engine.set_graph_json(GRAPH).unwrap();
+    assert!(engine.recall_snapshot(0).is_err());
+    assert!(engine.morph_snapshots(0, 1, 0.5).is_err());
+  }
+
+  #[test]
+  fn stereo_taps_keep_left_and_right() {
+    const GRAPH: &str = r#"{
+      "modules": [
+        { "id": "noise-1", "type": "noise", "params": { "pan": -1 } },
+        { "id": "out-1", "type": "output", "params": { "level": 1, "bus": 1 } }
+      ],
+      "connections": [
+        { "from": { "moduleId": "noise-1", "portId": "out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "audio" }
+      ],
+      "taps": [{ "moduleId": "out-1", "portId": "in" }]
+    }"#;
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_graph_json(GRAPH).unwrap();
+    assert_eq!(engine.tap_channels(0), 1);
+    assert_eq!(engine.output_channels(), 5);
+
+    engine.set_stereo_taps(true);
+    assert_eq!(engine.tap_channels(0), 2);
+    assert_eq!(engine.output_channels(), 6);
+    let data = engine.render(128);
+    let (tap_l, tap_r) = (&data[2 * 128..3 * 128], &data[3 * 128..4 * 128]);
+    assert!(peak(tap_l) > 0.1, "{}", peak(tap_l));
+    assert!(peak(tap_r) < 1e-6, "{}", peak(tap_r));
+    // Bus 1 follows both tap channels.
+    assert_eq!(&data[4 * 128..5 * 128], tap_l);
+  }
+}
@@ -0,0 +1,472 @@
+//! MIDI channel routing for multi-timbral patches.
+//!
+//! A channel map sends each MIDI channel to a control module (splits and
+//! layers), with an optional transpose and note range. Voices are allocated
+//! per target control module, so `ctrl-1` and `ctrl-drums` each get their own
+//! pool (a [`VoiceAllocator`] with a shared steal policy). With no map every
+//! channel plays `ctrl-1`. A sustain pedal per channel keeps released notes
+//! on their voices until it is lifted.
+//!
+//! The note path doesn't allocate, so it can run on the audio thread: routed
+//! voices name their pool by index and are written into a caller-owned
+//! buffer, and a channel map is parsed into a [`ChannelMap`] elsewhere and
+//! swapped in with [`NoteRouter::install_map`].
+
+use dsp_core::{StealPolicy, VoiceAllocator};
+use serde::Deserialize;
+
+/// Control module that receives notes when no channel map is set.
+pub const DEFAULT_CONTROL_MODULE: &str = "ctrl-1";
+
+const MAX_VOICES: usize = 16;
+
+const MIDI_CHANNELS: usize = 16;
+
+/// Capacity that keeps a routed voice buffer from growing: every voice of
+/// sixteen pools.
+pub const ROUTED_VOICES_CAPACITY: usize = MAX_VOICES * MIDI_CHANNELS;
+
+/// One entry of the channel map JSON:
+/// `[{ "channel": 1, "moduleId": "ctrl-1", "transpose": 0, "noteLow": 0, "noteHigh": 127 }]`.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct ChannelRoute {
+  /// MIDI channel 1-16, or 0 for every channel.
+  pub channel: u8,
+  #[serde(rename = "moduleId")]
+  pub module_id: String,
+  /// Semitones added to the incoming note.
+  #[serde(default)]
+  pub transpose: i8,
+  /// Lowest incoming note accepted (before transpose).
+  #[serde(rename = "noteLow", default)]
+  pub note_low: u8,
+  /// Highest incoming note accepted (before transpose).
+  #[serde(rename = "noteHigh", default = "default_note_high")]
+  pub note_high: u8,
+}
+
+fn default_note_high() -> u8 {
+  127
+}
+
+impl ChannelRoute {
+  /// `channel` is zero-based, as carried by MIDI events.
+  fn accepts(&self, channel: u8, note: u8) -> bool {
+    (self.channel == 0 || self.channel == channel + 1) && (self.note_low..=self.note_high).contains(&note)
+  }
+
+  fn transposed(&self, note: u8) -> Option<u8> {
+    let note = note as i16 + self.transpose as i16;
+    u8::try_from(note).ok().filter(|&n| n <= 127)
+  }
+}
+
+/// A voice picked for a note on one control module.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RoutedVoice {
+  /// Voice pool of the control module, see [`NoteRouter::module_id`].
+  pub pool: usize,
+  pub voice: usize,
+  /// Note after transpose.
+  pub note: u8,
+}
+
+/// Voice allocator for one control module, keyed by `(channel, note)`
+/// before transpose.
+struct VoicePool {
+  module_id: String,
+  voices: VoiceAllocator<(u8, u8)>,
+}
+
+impl VoicePool {
+  fn new(module_id: &str, max_voices: usize, policy: StealPolicy) -> Self {
+    Self {
+      module_id: module_id.to_string(),
+      voices: VoiceAllocator::new(max_voices, policy),
+    }
+  }
+}
+
+/// A parsed channel map with its voice pools, built off the audio thread
+/// for [`NoteRouter::install_map`].
+pub struct ChannelMap {
+  routes: Vec<ChannelRoute>,
+  pools: Vec<VoicePool>,
+}
+
+impl ChannelMap {
+  /// Parse a channel map JSON. An empty array is the default map (every
+  /// channel to `ctrl-1`).
+  pub fn from_json(json: &str) -> Result<Self, String> {
+    let routes: Vec<ChannelRoute> =
+      serde_json::from_str(json).map_err(|err| format!("Invalid channel map: {err}"))?;
+    if let Some(route) = routes.iter().find(|route| route.channel > 16) {
+      return Err(format!("Invalid channel map: channel {} out of range", route.channel));
+    }
+    Ok(Self::new(routes))
+  }
+
+  fn new(routes: Vec<ChannelRoute>) -> Self {
+    let routes = if routes.is_empty() {
+      vec![ChannelRoute {
+        channel: 0,
+        module_id: DEFAULT_CONTROL_MODULE.to_string(),
+        transpose: 0,
+        note_low: 0,
+        note_high: 127,
+      }]
+    } else {
+      routes
+    };
+    // Pools are sized for the most voices, so installing never grows them.
+    let mut pools: Vec<VoicePool> = Vec::new();
+    for route in &routes {
+      if !pools.iter().any(|pool| pool.module_id == route.module_id) {
+        pools.push(VoicePool::new(&route.module_id, MAX_VOICES, StealPolicy::default()));
+      }
+    }
+    Self { routes, pools }
+  }
+
+  pub fn routes(&self) -> &[ChannelRoute] {
+    &self.routes
+  }
+}
+
+/// Routes channel-tagged notes to per-module voices.
+pub struct NoteRouter {
+  map: ChannelMap,
+  max_voices: usize,
+  policy: StealPolicy,
+  /// Sustain pedal down, per zero-based channel.
+  sustain: [bool; MIDI_CHANNELS],
+  /// `(channel, note)` released while the pedal was down.
+  sustained: Vec<(u8, u8)>,
+}
+
+impl NoteRouter {
+  pub fn new(max_voices: usize) -> Self {
+    let mut router = Self {
+      map: ChannelMap::new(Vec::new()),
+      max_voices: MAX_VOICES,
+      policy: StealPolicy::default(),
+      sustain: [false; MIDI_CHANNELS],
+      sustained: Vec::with_capacity(MIDI_CHANNELS * 128),
+    };
+    router.set_max_voices(max_voices);
+    router
+  }
+
+  /// Replace the channel map from JSON, see [`ChannelMap::from_json`].
+  pub fn set_map_json(&mut self, json: &str) -> Result<(), String> {
+    self.install_map(ChannelMap::from_json(json)?);
+    Ok(())
+  }
+
+  /// Swap in a parsed channel map, keeping the voice count and steal
+  /// policy. Voice state is cleared, so callers should release
+  /// [`active_voices`](Self::active_voices) first. Returns the previous
+  /// map so the caller can drop it off the audio thread.
+  pub fn install_map(&mut self, mut map: ChannelMap) -> ChannelMap {
+    for pool in &mut map.pools {
+      pool.voices.set_max_voices(self.max_voices);
+      pool.voices.set_policy(self.policy);
+    }
+    std::mem::swap(&mut self.map, &mut map);
+    map
+  }
+
+  pub fn routes(&self) -> &[ChannelRoute] {
+    &self.map.routes
+  }
+
+  /// Control module of a [`RoutedVoice::pool`].
+  pub fn module_id(&self, pool: usize) -> &str {
+    &self.map.pools[pool].module_id
+  }
+
+  pub fn set_max_voices(&mut self, max_voices: usize) {
+    self.max_voices = max_voices.clamp(1, MAX_VOICES);
+    for pool in &mut self.map.pools {
+      pool.voices.set_max_voices(self.max_voices);
+    }
+  }
+
+  /// Which voice a note steals once every voice of its module is busy.
+  pub fn set_steal_policy(&mut self, policy: StealPolicy) {
+    self.policy = policy;
+    for pool in &mut self.map.pools {
+      pool.voices.set_policy(policy);
+    }
+  }
+
+  /// Allocate a voice on every control module whose route accepts the note,
+  /// into `routed` (cleared first). `channel` is zero-based; `velocity`
+  /// (0-1) ranks voices for [`StealPolicy::LowestVelocity`].
+  pub fn note_on(&mut self, channel: u8, note: u8, velocity: f32, routed: &mut Vec<RoutedVoice>) {
+    routed.clear();
+    // Played again under the pedal: held by the key now, not the pedal.
+    self.sustained.retain(|&key| key != (channel, note));
+    for route in &self.map.routes {
+      if !route.accepts(channel, note) {
+        continue;
+      }
+      let Some(target_note) = route.transposed(note) else { continue };
+      let Some(pool) = self.map.pools.iter().position(|pool| pool.module_id == route.module_id) else {
+        continue;
+      };
+      let (voice, _) = self.map.pools[pool].voices.allocate((channel, note), velocity);
+      routed.push(RoutedVoice { pool, voice, note: target_note });
+    }
+  }
+
+  /// Release the voices a previous `note_on` allocated for this note, into
+  /// `routed` (cleared first). While the channel's sustain pedal is down
+  /// nothing is released yet: the voices come back from
+  /// [`set_sustain`](Self::set_sustain).
+  pub fn note_off(&mut self, channel: u8, note: u8, routed: &mut Vec<RoutedVoice>) {
+    routed.clear();
+    if self.sustain.get(channel as usize).copied().unwrap_or(false) {
+      if !self.sustained.contains(&(channel, note)) {
+        self.sustained.push((channel, note));
+      }
+      return;
+    }
+    self.map_voices(channel, note, true, routed);
+  }
+
+  /// Press or lift the sustain pedal (CC 64) of a zero-based channel.
+  /// Lifting it releases the notes let go meanwhile; their voices go into
+  /// `routed` (cleared first).
+  pub fn set_sustain(&mut self, channel: u8, down: bool, routed: &mut Vec<RoutedVoice>) {
+    routed.clear();
+    let Some(pedal) = self.sustain.get_mut(channel as usize) else {
+      return;
+    };
+    *pedal = down;
+    if down {
+      return;
+    }
+    for index in 0..self.sustained.len() {
+      let (key_channel, note) = self.sustained[index];
+      if key_channel == channel {
+        self.map_voices(key_channel, note, true, routed);
+      }
+    }
+    self.sustained.retain(|&(key_channel, _)| key_channel != channel);
+  }
+
+  /// Voices currently playing this note (e.g. for poly pressure), into
+  /// `routed` (cleared first).
+  pub fn voices_for(&mut self, channel: u8, note: u8, routed: &mut Vec<RoutedVoice>) {
+    routed.clear();
+    self.map_voices(channel, note, false, routed);
+  }
+
+  /// Voices currently playing a note of this zero-based channel, sustained
+  /// ones included (e.g. for channel pressure), into `routed` (cleared
+  /// first).
+  pub fn voices_on_channel(&self, channel: u8, routed: &mut Vec<RoutedVoice>) {
+    routed.clear();
+    self.collect_active(Some(channel), routed);
+  }
+
+  /// Appends the voices a `(channel, note)` holds, releasing them if asked.
+  fn map_voices(&mut self, channel: u8, note: u8, release: bool, routed: &mut Vec<RoutedVoice>) {
+    for route in &self.map.routes {
+      if !route.accepts(channel, note) {
+        continue;
+      }
+      let Some(target_note) = route.transposed(note) else { continue };
+      let Some(pool) = self.map.pools.iter().position(|pool| pool.module_id == route.module_id) else {
+        continue;
+      };
+      let key = (channel, note);
+      let voices = &mut self.map.pools[pool].voices;
+      let voice = if release { voices.release(key) } else { voices.find(key) };
+      if let Some(voice) = voice {
+        routed.push(RoutedVoice { pool, voice, note: target_note });
+      }
+    }
+  }
+
+  /// Every voice with a note held, into `routed` (cleared first).
+  pub fn active_voices(&self, routed: &mut Vec<RoutedVoice>) {
+    routed.clear();
+    self.collect_active(None, routed);
+  }
+
+  /// Appends the held voices, of one zero-based channel or all of them.
+  fn collect_active(&self, only_channel: Option<u8>, routed: &mut Vec<RoutedVoice>) {
+    for (pool_index, pool) in self.map.pools.iter().enumerate() {
+      for (voice, (channel, note)) in pool.voices.active_voices() {
+        if only_channel.is_some_and(|only| only != channel) {
+          continue;
+        }
+        let route = self
+          .map
+          .routes
+          .iter()
+          .find(|route| route.module_id == pool.module_id && route.accepts(channel, note));
+        let Some(note) = route.and_then(|route| route.transposed(note)) else { continue };
+        routed.push(RoutedVoice { pool: pool_index, voice, note });
+      }
+    }
+  }
+
+  /// Forget every held note and pedal (keeps the map).
+  pub fn reset(&mut self) {
+    for pool in &mut self.map.pools {
+      pool.voices.reset();
+    }
+    self.sustain = [false; MIDI_CHANNELS];
+    self.sustained.clear();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const SPLIT_MAP: &str = r#"[
+    { "channel": 1, "moduleId": "ctrl-1" },
+    { "channel": 2, "moduleId": "ctrl-drums", "noteLow": 36, "noteHigh": 51 },
+    { "channel": 2, "moduleId": "ctrl-bass", "transpose": -12, "noteHigh": 35 }
+  ]"#;
+
+  /// `(module id, voice, note)` of routed voices, for readable asserts.
+  fn named(router: &NoteRouter, routed: &[RoutedVoice]) -> Vec<(String, usize, u8)> {
+    routed.iter().map(|r| (router.module_id(r.pool).to_string(), r.voice, r.note)).collect()
+  }
+
+  fn note_on(router: &mut NoteRouter, channel: u8, note: u8, velocity: f32) -> Vec<(String, usize, u8)> {
+    let mut routed = Vec::new();
+    router.note_on(channel, note, velocity, &mut routed);
+    named(router, &routed)
+  }
+
+  fn note_off(router: &mut NoteRouter, channel: u8, note: u8) -> Vec<(String, usize, u8)> {
+    let mut routed = Vec::new();
+    router.note_off(channel, note, &mut routed);
+    named(router, &routed)
+  }
+
+  fn active_count(router: &NoteRouter) -> usize {
+    let mut routed = Vec::new();
+    router.active_voices(&mut routed);
+    routed.len()
+  }
+
+  #[test]
+  fn default_map_sends_every_channel_to_ctrl_1() {
+    let mut router = NoteRouter::new(8);
+    assert_eq!(note_on(&mut router, 9, 60, 1.0), vec![("ctrl-1".into(), 0, 60)]);
+    assert_eq!(note_off(&mut router, 9, 60).len(), 1);
+    assert_eq!(active_count(&router), 0);
+  }
+
+  #[test]
+  fn channels_split_and_allocate_independently() {
+    let mut router = NoteRouter::new(4);
+    router.set_map_json(SPLIT_MAP).unwrap();
+
+    assert_eq!(note_on(&mut router, 0, 60, 1.0), vec![("ctrl-1".into(), 0, 60)]);
+    assert_eq!(note_on(&mut router, 1, 36, 1.0), vec![("ctrl-drums".into(), 0, 36)]);
+    assert_eq!(note_on(&mut router, 1, 30, 1.0), vec![("ctrl-bass".into(), 0, 18)]);
+
+    // Out of every range on channel 2, and channel 3 is unmapped.
+    assert!(note_on(&mut router, 1, 80, 1.0).is_empty());
+    assert!(note_on(&mut router, 2, 60, 1.0).is_empty());
+
+    assert_eq!(note_on(&mut router, 0, 64, 1.0)[0].1, 1);
+    assert_eq!(note_off(&mut router, 1, 30)[0].0, "ctrl-bass");
+    assert_eq!(active_count(&router), 3);
+  }
+
+  #[test]
+  fn layered_routes_each_get_a_voice() {
+    let mut router = NoteRouter::new(8);
+    router
+      .set_map_json(r#"[{ "channel": 0, "moduleId": "ctrl-1" }, { "channel": 0, "moduleId": "ctrl-pad", "transpose": 12 }]"#)
+      .unwrap();
+    let voices = note_on(&mut router, 3, 60, 1.0);
+    assert_eq!(voices.len(), 2);
+    assert_eq!(voices[1].2, 72);
+    let mut routed = Vec::new();
+    router.voices_for(3, 60, &mut routed);
+    assert_eq!(routed.len(), 2);
+  }
+
+  #[test]
+  fn steal_policy_applies_to_every_pool() {
+    let mut router = NoteRouter::new(2);
+    router.set_steal_policy(StealPolicy::LowestVelocity);
+    note_on(&mut router, 0, 60, 0.3);
+    note_on(&mut router, 0, 62, 0.9);
+    assert_eq!(note_on(&mut router, 0, 64, 0.5)[0].1, 0);
+    // The stolen note no longer owns a voice.
+    assert!(note_off(&mut router, 0, 60).is_empty());
+    assert_eq!(note_off(&mut router, 0, 64)[0].1, 0);
+  }
+
+  #[test]
+  fn sustain_holds_released_notes_until_lifted() {
+    let mut router = NoteRouter::new(8);
+    let mut routed = Vec::new();
+    note_on(&mut router, 0, 60, 1.0);
+    note_on(&mut router, 0, 64, 1.0);
+    note_on(&mut router, 1, 67, 1.0);
+    router.set_sustain(0, true, &mut routed);
+
+    assert!(note_off(&mut router, 0, 60).is_empty());
+    assert!(note_off(&mut router, 0, 64).is_empty());
+    // Other channels are not held by this pedal.
+    assert_eq!(note_off(&mut router, 1, 67).len(), 1);
+    router.voices_on_channel(0, &mut routed);
+    assert_eq!(routed.len(), 2);
+
+    // 64 is played again before the pedal lifts, so it keeps sounding.
+    note_on(&mut router, 0, 64, 1.0);
+    router.set_sustain(0, false, &mut routed);
+    assert_eq!(named(&router, &routed), vec![("ctrl-1".into(), 0, 60)]);
+    router.voices_on_channel(0, &mut routed);
+    assert_eq!(routed[0].note, 64);
+    assert_eq!(note_off(&mut router, 0, 64).len(), 1);
+  }
+
+  #[test]
+  fn note_path_reuses_the_routed_buffer() {
+    let mut router = NoteRouter::new(8);
+    router.set_map_json(SPLIT_MAP).unwrap();
+    let mut routed = Vec::with_capacity(ROUTED_VOICES_CAPACITY);
+    let buffer = routed.as_ptr();
+    for note in 0..128 {
+      router.note_on(1, note, 1.0, &mut routed);
+      router.voices_on_channel(1, &mut routed);
+      router.note_off(1, note, &mut routed);
+    }
+    assert_eq!(routed.as_ptr(), buffer);
+    assert_eq!(routed.capacity(), ROUTED_VOICES_CAPACITY);
+  }
+
+  #[test]
+  fn parsed_map_installs_with_the_router_settings() {
+    let mut router = NoteRouter::new(2);
+    let map = ChannelMap::from_json(SPLIT_MAP).unwrap();
+    assert_eq!(map.routes().len(), 3);
+    let previous = router.install_map(map);
+    assert_eq!(previous.routes()[0].module_id, DEFAULT_CONTROL_MODULE);
+    note_on(&mut router, 0, 60, 1.0);
+    note_on(&mut router, 0, 62, 1.0);
+    // Two voices per pool, as before the swap.
+    assert_eq!(note_on(&mut router, 0, 64, 1.0)[0].1, 0);
+  }
+
+  #[test]
+  fn invalid_map_is_rejected() {
+    let mut router = NoteRouter::new(8);
+    assert!(router.set_map_json("{}").is_err());
+    assert!(router.set_map_json(r#"[{ "channel": 17, "moduleId": "ctrl-1" }]"#).is_err());
+    assert_eq!(router.routes()[0].module_id, DEFAULT_CONTROL_MODULE);
+  }
+}
@@ -0,0 +1,97 @@
+//! Graph JSON schema versioning.
+//!
+//! Patches carry an optional top-level `schemaVersion` (absent = 1). Before a
+//! payload is deserialized, every migration between its version and
+//! [`CURRENT_SCHEMA_VERSION`] is applied in order, so old saved patches keep
+//! loading after param or module renames.
+
+use serde_json::Value;
+
+/// Schema version written by this build.
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+type Migration = fn(Value) -> Value;
+
+/// `(from_version, description, migration)`; each step upgrades by one.
+const MIGRATIONS: &[(u32, &str, Migration)] = &[
+  (1, "v1 -> v2: rename module type mixer-1x2 to mixer-wide", migrate_v1_to_v2),
+];
+
+/// Upgrade a raw graph to [`CURRENT_SCHEMA_VERSION`].
+///
+/// Returns the migrated graph and the descriptions of the migrations that
+/// ran. Graphs from a newer schema are rejected.
+pub(crate) fn migrate(mut graph: Value) -> Result<(Value, Vec<&'static str>), String> {
+  let mut version = match graph.get("schemaVersion") {
+    None | Some(Value::Null) => 1,
+    Some(value) => value
+      .as_u64()
+      .and_then(|v| u32::try_from(v).ok())
+      .ok_or_else(|| format!("Invalid graph schemaVersion: {value}"))?,
+  };
+  if version > CURRENT_SCHEMA_VERSION {
+    return Err(format!(
+      "Graph schemaVersion {version} is newer than supported ({CURRENT_SCHEMA_VERSION})"
+    ));
+  }
+
+  let mut applied = Vec::new();
+  for &(from, description, migration) in MIGRATIONS {
+    if version == from {
+      graph = migration(graph);
+      version += 1;
+      applied.push(description);
+    }
+  }
+
+  if let Some(object) = graph.as_object_mut() {
+    object.insert("schemaVersion".to_string(), Value::from(CURRENT_SCHEMA_VERSION));
+  }
+  Ok((graph, applied))
+}
+
+/// v2 names the six-input stereo mixer after its module type.
+pub fn migrate_v1_to_v2(mut graph: Value) -> Value {
+  if let Some(modules) = graph.get_mut("modules").and_then(Value::as_array_mut) {
+    for module in modules {
+      if module.get("type").and_then(Value::as_str) == Some("mixer-1x2") {
+        module["type"] = Value::from("mixer-wide");
+      }
+    }
+  }
+  graph
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const V1_FIXTURE: &str = include_str!("../fixtures/graph_v1.json");
+  const V2_FIXTURE: &str = include_str!("../fixtures/graph_v2.json");
+
+  fn parse(json: &str) -> Value {
+    serde_json::from_str(json).unwrap()
+  }
+
+  #[test]
+  fn v1_fixture_migrates_to_v2_fixture() {
+    let (migrated, applied) = migrate(parse(V1_FIXTURE)).unwrap();
+    assert_eq!(migrated, parse(V2_FIXTURE));
+    assert_eq!(applied.len(), 1);
+  }
+
+  #[test]
+  fn current_fixture_is_unchanged() {
+    let (migrated, applied) = migrate(parse(V2_FIXTURE)).unwrap();
+    assert_eq!(migrated, parse(V2_FIXTURE));
+    assert!(applied.is_empty());
+  }
+
+  #[test]
+  fn newer_schema_is_rejected() {
+    let mut graph = parse(V2_FIXTURE);
+    graph["schemaVersion"] = Value::from(CURRENT_SCHEMA_VERSION + 1);
+    let err = migrate(graph).unwrap_err();
+    assert!(err.contains("newer than supported"), "{err}");
+  }
+}
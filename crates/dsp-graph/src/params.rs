@@ -0,0 +1,795 @@
+//! Parameter metadata for all module types.
+//!
+//! Ranges, defaults and step sizes of the numeric parameters each module
+//! accepts. Module states take their defaults from this table, both loading
+//! and `set_param` clamp and quantize values against it, and the UI can
+//! build its controls from [`param_table`] instead of hardcoding ranges.
+//! Parameters without an entry are passed through as-is.
+
+use crate::types::ModuleType;
+use dsp_core::CLOCK_DIV_LABELS;
+use serde::Serialize;
+
+/// How a parameter is best presented (knob curve or selector).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamKind {
+  Linear,
+  Log,
+  Enum,
+}
+
+/// Range and default of one numeric parameter.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct ParamMeta {
+  pub id: &'static str,
+  pub min: f32,
+  pub max: f32,
+  pub default: f32,
+  /// Quantization step from `min`; 0 means continuous.
+  pub step: f32,
+  pub kind: ParamKind,
+  /// Option names for `Enum` parameters, indexed by value.
+  #[serde(skip_serializing_if = "<[_]>::is_empty")]
+  pub labels: &'static [&'static str],
+}
+
+impl ParamMeta {
+  pub const fn linear(id: &'static str, min: f32, max: f32, default: f32) -> Self {
+    Self { id, min, max, default, step: 0.0, kind: ParamKind::Linear, labels: &[] }
+  }
+
+  pub const fn log(id: &'static str, min: f32, max: f32, default: f32) -> Self {
+    Self { id, min, max, default, step: 0.0, kind: ParamKind::Log, labels: &[] }
+  }
+
+  pub const fn stepped(id: &'static str, min: f32, max: f32, default: f32, step: f32) -> Self {
+    Self { id, min, max, default, step, kind: ParamKind::Linear, labels: &[] }
+  }
+
+  pub const fn choice(id: &'static str, labels: &'static [&'static str], default: f32) -> Self {
+    let max = labels.len().saturating_sub(1) as f32;
+    Self { id, min: 0.0, max, default, step: 1.0, kind: ParamKind::Enum, labels }
+  }
+
+  /// Clamp `value` into range and snap it to `step`. Non-finite values fall
+  /// back to the default.
+  pub fn constrain(&self, value: f32) -> f32 {
+    if !value.is_finite() {
+      return self.default;
+    }
+    let value = value.clamp(self.min, self.max);
+    if self.step > 0.0 {
+      (self.min + ((value - self.min) / self.step).round() * self.step).min(self.max)
+    } else {
+      value
+    }
+  }
+}
+
+/// Numeric parameters of a module type, in UI order.
+pub fn param_table(module_type: ModuleType) -> &'static [ParamMeta] {
+  match module_type {
+    ModuleType::Gain => GAIN,
+    ModuleType::CvVca => CV_VCA,
+    ModuleType::RingMod => RING_MOD,
+    ModuleType::Mixer => MIXER,
+    ModuleType::MixerWide => MIXER_WIDE,
+    ModuleType::Mixer8 => MIXER_8,
+    ModuleType::Crossfader => CROSSFADER,
+    ModuleType::Pan => PAN,
+    ModuleType::Width => WIDTH,
+    ModuleType::DcBlock => DC_BLOCK,
+    ModuleType::Chorus => CHORUS,
+    ModuleType::Ensemble => ENSEMBLE,
+    ModuleType::Choir => CHOIR,
+    ModuleType::Vocoder => VOCODER,
+    ModuleType::Delay => DELAY,
+    ModuleType::GranularDelay => GRANULAR_DELAY,
+    ModuleType::TapeDelay => TAPE_DELAY,
+    ModuleType::SpringReverb => SPRING_REVERB,
+    ModuleType::Reverb => REVERB,
+    ModuleType::Phaser => PHASER,
+    ModuleType::Distortion => DISTORTION,
+    ModuleType::Wavefolder => WAVEFOLDER,
+    ModuleType::BitCrusher => BIT_CRUSHER,
+    ModuleType::PitchShifter => PITCH_SHIFTER,
+    ModuleType::Compressor => COMPRESSOR,
+    ModuleType::Gate => GATE,
+    ModuleType::Vcf => VCF,
+    ModuleType::Hpf => HPF,
+    ModuleType::Granular => GRANULAR,
+    ModuleType::Adsr => ADSR,
+    ModuleType::Lfo => LFO,
+    ModuleType::ModRouter => MOD_ROUTER,
+    ModuleType::Attenuverter => ATTENUVERTER,
+    ModuleType::SampleHold => SAMPLE_HOLD,
+    ModuleType::Slew => SLEW,
+    ModuleType::EnvFollower => ENV_FOLLOWER,
+    ModuleType::Quantizer => QUANTIZER,
+    ModuleType::Chaos => CHAOS,
+    ModuleType::Logic => LOGIC,
+    ModuleType::PeakMeter => PEAK_METER,
+    ModuleType::AudioIn => AUDIO_IN,
+    ModuleType::Send => SEND,
+    ModuleType::Control => CONTROL,
+    ModuleType::Output => OUTPUT,
+    ModuleType::Arpeggiator => ARPEGGIATOR,
+    ModuleType::Clock => CLOCK,
+    ModuleType::ClockDiv => CLOCK_DIV,
+    ModuleType::DrumSequencer => DRUM_SEQUENCER,
+    ModuleType::Euclidean => EUCLIDEAN,
+    ModuleType::MidiFileSequencer => MIDI_FILE_SEQUENCER,
+    ModuleType::StepSequencer => STEP_SEQUENCER,
+    ModuleType::TuringMachine => TURING_MACHINE,
+    ModuleType::FmMatrix => FM_MATRIX,
+    ModuleType::FmOp => FM_OP,
+    ModuleType::Karplus => KARPLUS,
+    ModuleType::NesOsc => NES_OSC,
+    ModuleType::Noise => NOISE,
+    ModuleType::Oscillator => OSCILLATOR,
+    ModuleType::ParticleCloud => PARTICLE_CLOUD,
+    ModuleType::PipeOrgan => PIPE_ORGAN,
+    ModuleType::Resonator => RESONATOR,
+    ModuleType::SamplePlayer => SAMPLE_PLAYER,
+    ModuleType::Shepard => SHEPARD,
+    ModuleType::SnesOsc => SNES_OSC,
+    ModuleType::SpectralSwarm => SPECTRAL_SWARM,
+    ModuleType::Supersaw => SUPERSAW,
+    ModuleType::Tb303 => TB303,
+    ModuleType::Wavetable => WAVETABLE,
+    ModuleType::Kick808 => KICK_808,
+    ModuleType::Snare808 => SNARE_808,
+    ModuleType::HiHat808 => HIHAT_808,
+    ModuleType::Cowbell808 => COWBELL_808,
+    ModuleType::Clap808 => CLAP_808,
+    ModuleType::Tom808 => TOM_808,
+    ModuleType::Kick909 => KICK_909,
+    ModuleType::Snare909 => SNARE_909,
+    ModuleType::HiHat909 => HIHAT_909,
+    ModuleType::Clap909 => CLAP_909,
+    ModuleType::Tom909 => TOM_909,
+    ModuleType::Rimshot909 => RIMSHOT_909,
+    ModuleType::CvBank => CV_BANK,
+    _ => &[],
+  }
+}
+
+/// Metadata of one parameter, if the table describes it.
+pub fn param_meta(module_type: ModuleType, param: &str) -> Option<&'static ParamMeta> {
+  param_table(module_type).iter().find(|meta| meta.id == param)
+}
+
+const GAIN: &[ParamMeta] = &[
+  ParamMeta::linear("gain", 0.0, 1.0, 0.2),
+  ParamMeta::stepped("invert", 0.0, 1.0, 0.0, 1.0),
+];
+
+const CV_VCA: &[ParamMeta] = &[
+  ParamMeta::linear("gain", 0.0, 1.0, 1.0),
+  ParamMeta::stepped("invert", 0.0, 1.0, 0.0, 1.0),
+];
+
+const RING_MOD: &[ParamMeta] = &[
+  ParamMeta::linear("level", 0.0, 1.0, 0.9),
+  ParamMeta::linear("mix", 0.0, 1.0, 1.0),
+  ParamMeta::log("carrierFreq", 1.0, 20000.0, 440.0),
+  ParamMeta::choice("carrierShape", &["sine", "triangle", "saw", "square"], 0.0),
+  ParamMeta::stepped("internalCarrier", 0.0, 1.0, 0.0, 1.0),
+];
+
+const MIXER: &[ParamMeta] = &[
+  ParamMeta::linear("levelA", 0.0, 1.0, 0.6),
+  ParamMeta::linear("levelB", 0.0, 1.0, 0.6),
+];
+
+const MIXER_WIDE: &[ParamMeta] = &[
+  ParamMeta::linear("levelA", 0.0, 1.0, 0.6),
+  ParamMeta::linear("levelB", 0.0, 1.0, 0.6),
+  ParamMeta::linear("levelC", 0.0, 1.0, 0.6),
+  ParamMeta::linear("levelD", 0.0, 1.0, 0.6),
+  ParamMeta::linear("levelE", 0.0, 1.0, 0.6),
+  ParamMeta::linear("levelF", 0.0, 1.0, 0.6),
+];
+
+const MIXER_8: &[ParamMeta] = &[
+  ParamMeta::linear("level1", 0.0, 1.0, 0.6),
+  ParamMeta::linear("level2", 0.0, 1.0, 0.6),
+  ParamMeta::linear("level3", 0.0, 1.0, 0.6),
+  ParamMeta::linear("level4", 0.0, 1.0, 0.6),
+  ParamMeta::linear("level5", 0.0, 1.0, 0.6),
+  ParamMeta::linear("level6", 0.0, 1.0, 0.6),
+  ParamMeta::linear("level7", 0.0, 1.0, 0.6),
+  ParamMeta::linear("level8", 0.0, 1.0, 0.6),
+];
+
+const CROSSFADER: &[ParamMeta] = &[
+  ParamMeta::linear("mix", 0.0, 1.0, 0.5),
+  ParamMeta::choice("curve", &["linear", "equal-power"], 0.0),
+];
+
+const PAN: &[ParamMeta] = &[
+  ParamMeta::linear("pan", -1.0, 1.0, 0.0),
+];
+
+const DC_BLOCK: &[ParamMeta] = &[
+  ParamMeta::log("cutoff", 5.0, 200.0, 20.0),
+];
+
+const WIDTH: &[ParamMeta] = &[
+  ParamMeta::linear("width", 0.0, 2.0, 1.0),
+  ParamMeta::linear("monoBelow", 0.0, 500.0, 0.0),
+];
+
+const CHORUS: &[ParamMeta] = &[
+  ParamMeta::log("rate", 0.05, 4.0, 0.3),
+  ParamMeta::linear("depth", 1.0, 18.0, 8.0),
+  ParamMeta::linear("delay", 6.0, 25.0, 18.0),
+  ParamMeta::linear("mix", 0.0, 1.0, 0.45),
+  ParamMeta::linear("spread", 0.0, 1.0, 0.6),
+  ParamMeta::linear("feedback", 0.0, 0.4, 0.15),
+  ParamMeta::stepped("wetOnly", 0.0, 1.0, 0.0, 1.0),
+];
+
+const ENSEMBLE: &[ParamMeta] = &[
+  ParamMeta::log("rate", 0.05, 3.0, 0.25),
+  ParamMeta::linear("depth", 2.0, 25.0, 12.0),
+  ParamMeta::linear("delay", 6.0, 25.0, 12.0),
+  ParamMeta::linear("mix", 0.0, 1.0, 0.6),
+  ParamMeta::linear("spread", 0.0, 1.0, 0.7),
+  ParamMeta::stepped("wetOnly", 0.0, 1.0, 0.0, 1.0),
+];
+
+const CHOIR: &[ParamMeta] = &[
+  ParamMeta::log("rate", 0.05, 2.0, 0.25),
+  ParamMeta::linear("depth", 0.0, 1.0, 0.35),
+  ParamMeta::linear("mix", 0.0, 1.0, 0.5),
+];
+
+const VOCODER: &[ParamMeta] = &[
+  ParamMeta::log("attack", 2.0, 300.0, 25.0),
+  ParamMeta::log("release", 10.0, 1200.0, 140.0),
+  ParamMeta::log("low", 40.0, 2000.0, 120.0),
+  ParamMeta::log("high", 400.0, 12000.0, 5000.0),
+  ParamMeta::log("q", 0.4, 8.0, 2.5),
+  ParamMeta::stepped("formant", -12.0, 12.0, 0.0, 1.0),
+  ParamMeta::linear("emphasis", 0.0, 1.0, 0.4),
+  ParamMeta::linear("unvoiced", 0.0, 1.0, 0.0),
+  ParamMeta::linear("modGain", 0.0, 4.0, 1.0),
+  ParamMeta::linear("carGain", 0.0, 4.0, 1.0),
+  ParamMeta::linear("mix", 0.0, 1.0, 0.8),
+];
+
+const DELAY: &[ParamMeta] = &[
+  ParamMeta::log("time", 20.0, 1200.0, 360.0),
+  ParamMeta::linear("feedback", 0.0, 0.9, 0.35),
+  ParamMeta::linear("mix", 0.0, 1.0, 0.25),
+  ParamMeta::linear("tone", 0.0, 1.0, 0.55),
+  ParamMeta::stepped("wetOnly", 0.0, 1.0, 0.0, 1.0),
+];
+
+const GRANULAR_DELAY: &[ParamMeta] = &[
+  ParamMeta::log("time", 40.0, 1200.0, 420.0),
+  ParamMeta::log("size", 10.0, 500.0, 120.0),
+  ParamMeta::log("density", 0.2, 30.0, 6.0),
+  ParamMeta::linear("pitch", 0.25, 2.0, 1.0),
+  ParamMeta::linear("feedback", 0.0, 0.85, 0.35),
+  ParamMeta::linear("mix", 0.0, 1.0, 0.5),
+];
+
+const TAPE_DELAY: &[ParamMeta] = &[
+  ParamMeta::log("time", 60.0, 1200.0, 420.0),
+  ParamMeta::linear("feedback", 0.0, 0.9, 0.35),
+  ParamMeta::linear("mix", 0.0, 1.0, 0.35),
+  ParamMeta::linear("tone", 0.0, 1.0, 0.55),
+  ParamMeta::linear("wow", 0.0, 1.0, 0.2),
+  ParamMeta::linear("flutter", 0.0, 1.0, 0.2),
+  ParamMeta::linear("drive", 0.0, 1.0, 0.2),
+];
+
+const SPRING_REVERB: &[ParamMeta] = &[
+  ParamMeta::linear("decay", 0.0, 0.98, 0.6),
+  ParamMeta::linear("tone", 0.0, 1.0, 0.4),
+  ParamMeta::linear("mix", 0.0, 1.0, 0.4),
+  ParamMeta::linear("drive", 0.0, 1.0, 0.2),
+  ParamMeta::linear("diffusion", 0.0, 1.0, 0.5),
+  ParamMeta::stepped("spring-count", 1.0, 4.0, 3.0, 1.0),
+];
+
+const REVERB: &[ParamMeta] = &[
+  ParamMeta::linear("time", 0.1, 0.98, 0.62),
+  ParamMeta::linear("damp", 0.0, 1.0, 0.4),
+  ParamMeta::linear("preDelay", 0.0, 80.0, 18.0),
+  ParamMeta::linear("mix", 0.0, 1.0, 0.25),
+  ParamMeta::stepped("wetOnly", 0.0, 1.0, 0.0, 1.0),
+];
+
+const PHASER: &[ParamMeta] = &[
+  ParamMeta::log("rate", 0.05, 5.0, 0.5),
+  ParamMeta::linear("depth", 0.0, 1.0, 0.7),
+  ParamMeta::linear("feedback", 0.0, 0.9, 0.3),
+  ParamMeta::linear("mix", 0.0, 1.0, 0.5),
+  ParamMeta::stepped("stages", 2.0, 12.0, 4.0, 2.0),
+  ParamMeta::linear("stereo", 0.0, 0.5, 0.0),
+  ParamMeta::log("center", 100.0, 4000.0, 800.0),
+  ParamMeta::stepped("wetOnly", 0.0, 1.0, 0.0, 1.0),
+];
+
+const DISTORTION: &[ParamMeta] = &[
+  ParamMeta::linear("drive", 0.0, 1.0, 0.5),
+  ParamMeta::linear("tone", 0.0, 1.0, 0.5),
+  ParamMeta::linear("mix", 0.0, 1.0, 1.0),
+  ParamMeta::choice("mode", &["soft", "hard", "fold", "asymmetric", "tube"], 0.0),
+  ParamMeta::linear("bias", -1.0, 1.0, 0.0),
+];
+
+const WAVEFOLDER: &[ParamMeta] = &[
+  ParamMeta::linear("drive", 0.0, 1.0, 0.4),
+  ParamMeta::linear("fold", 0.0, 1.0, 0.5),
+  ParamMeta::linear("bias", -1.0, 1.0, 0.0),
+  ParamMeta::linear("mix", 0.0, 1.0, 0.8),
+];
+
+const BIT_CRUSHER: &[ParamMeta] = &[
+  ParamMeta::linear("bits", 1.0, 16.0, 8.0),
+  ParamMeta::log("downsample", 1.0, 64.0, 1.0),
+  ParamMeta::linear("mix", 0.0, 1.0, 1.0),
+  ParamMeta::stepped("antialias", 0.0, 1.0, 0.0, 1.0),
+  ParamMeta::stepped("dither", 0.0, 1.0, 0.0, 1.0),
+];
+
+const PITCH_SHIFTER: &[ParamMeta] = &[
+  ParamMeta::stepped("pitch", -24.0, 24.0, 0.0, 1.0),
+  ParamMeta::linear("fine", -100.0, 100.0, 0.0),
+  ParamMeta::linear("grain", 10.0, 100.0, 50.0),
+  ParamMeta::linear("mix", 0.0, 1.0, 1.0),
+];
+
+const COMPRESSOR: &[ParamMeta] = &[
+  ParamMeta::linear("threshold", -60.0, 0.0, -20.0),
+  ParamMeta::log("ratio", 1.0, 20.0, 4.0),
+  ParamMeta::log("attack", 0.5, 200.0, 10.0),
+  ParamMeta::log("release", 10.0, 2000.0, 100.0),
+  ParamMeta::linear("knee", 0.0, 24.0, 0.0),
+  ParamMeta::linear("makeup", -24.0, 24.0, 0.0),
+  ParamMeta::linear("mix", 0.0, 1.0, 1.0),
+  ParamMeta::stepped("detector", 0.0, 1.0, 0.0, 1.0),
+  ParamMeta::stepped("sidechain", 0.0, 1.0, 1.0, 1.0),
+];
+
+const GATE: &[ParamMeta] = &[
+  ParamMeta::linear("threshold", -80.0, 0.0, -40.0),
+  ParamMeta::log("attack", 0.1, 50.0, 1.0),
+  ParamMeta::linear("hold", 0.0, 500.0, 50.0),
+  ParamMeta::log("release", 5.0, 2000.0, 100.0),
+  ParamMeta::linear("floor", -80.0, 0.0, -80.0),
+];
+
+const VCF: &[ParamMeta] = &[
+  ParamMeta::log("cutoff", 40.0, 12000.0, 800.0),
+  ParamMeta::linear("resonance", 0.0, 1.0, 0.4),
+  ParamMeta::linear("drive", 0.0, 1.0, 0.2),
+  ParamMeta::linear("envAmount", -1.0, 1.0, 0.0),
+  ParamMeta::linear("modAmount", -1.0, 1.0, 0.0),
+  ParamMeta::linear("keyTrack", 0.0, 1.0, 0.0),
+];
+
+const HPF: &[ParamMeta] = &[
+  ParamMeta::log("cutoff", 40.0, 12000.0, 280.0),
+];
+
+const GRANULAR: &[ParamMeta] = &[
+  ParamMeta::linear("position", 0.0, 1.0, 0.5),
+  ParamMeta::log("size", 5.0, 500.0, 100.0),
+  ParamMeta::log("density", 1.0, 100.0, 8.0),
+  ParamMeta::linear("pitch", 0.25, 4.0, 1.0),
+  ParamMeta::linear("spray", 0.0, 1.0, 0.1),
+  ParamMeta::linear("scatter", 0.0, 24.0, 0.0),
+  ParamMeta::linear("panSpread", 0.0, 1.0, 0.5),
+  ParamMeta::linear("level", 0.0, 1.0, 0.8),
+  ParamMeta::choice("shape", &["triangle", "hann", "tukey", "gauss"], 1.0),
+];
+
+const ADSR: &[ParamMeta] = &[
+  ParamMeta::log("attack", 0.001, 5.0, 0.02),
+  ParamMeta::log("decay", 0.001, 5.0, 0.2),
+  ParamMeta::linear("sustain", 0.0, 1.0, 0.65),
+  ParamMeta::log("release", 0.001, 5.0, 0.4),
+];
+
+const LFO: &[ParamMeta] = &[
+  ParamMeta::log("rate", 0.05, 20.0, 2.0),
+  ParamMeta::linear("depth", 0.0, 1.0, 0.7),
+  ParamMeta::linear("offset", -1.0, 1.0, 0.0),
+  ParamMeta::choice("shape", &["sine", "triangle", "saw", "square"], 0.0),
+];
+
+const MOD_ROUTER: &[ParamMeta] = &[
+  ParamMeta::linear("depthPitch", -1.0, 1.0, 0.0),
+  ParamMeta::linear("depthPwm", -1.0, 1.0, 0.0),
+  ParamMeta::linear("depthVcf", -1.0, 1.0, 0.0),
+  ParamMeta::linear("depthVca", -1.0, 1.0, 0.0),
+  ParamMeta::linear("depthAux", -1.0, 1.0, 0.0),
+  ParamMeta::linear("offsetPitch", -1.0, 1.0, 0.0),
+  ParamMeta::linear("offsetPwm", -1.0, 1.0, 0.0),
+  ParamMeta::linear("offsetVcf", -1.0, 1.0, 0.0),
+  ParamMeta::linear("offsetVca", -1.0, 1.0, 0.0),
+  ParamMeta::linear("offsetAux", -1.0, 1.0, 0.0),
+];
+
+const ATTENUVERTER: &[ParamMeta] = &[
+  ParamMeta::linear("scale", -1.0, 1.0, 1.0),
+  ParamMeta::linear("offset", -5.0, 5.0, 0.0),
+];
+
+const SAMPLE_HOLD: &[ParamMeta] = &[
+  ParamMeta::choice("mode", &["track", "random"], 0.0),
+  ParamMeta::linear("glide", 0.0, 1.0, 0.0),
+  ParamMeta::linear("noiseLevel", 0.0, 1.0, 1.0),
+];
+
+const SLEW: &[ParamMeta] = &[
+  ParamMeta::linear("rise", 0.0, 1.0, 0.05),
+  ParamMeta::linear("fall", 0.0, 1.0, 0.05),
+];
+
+const ENV_FOLLOWER: &[ParamMeta] = &[
+  ParamMeta::log("attack", 0.001, 1.0, 0.01),
+  ParamMeta::log("release", 0.01, 2.0, 0.2),
+  ParamMeta::linear("gain", 0.0, 4.0, 1.0),
+];
+
+const QUANTIZER: &[ParamMeta] = &[
+  ParamMeta::stepped("root", 0.0, 11.0, 0.0, 1.0),
+];
+
+const CHAOS: &[ParamMeta] = &[
+  ParamMeta::log("speed", 0.01, 2.0, 0.5),
+  ParamMeta::linear("rho", 0.0, 50.0, 28.0),
+  ParamMeta::log("sigma", 1.0, 20.0, 10.0),
+  ParamMeta::log("beta", 0.1, 10.0, 8.0 / 3.0),
+  ParamMeta::stepped("root", 0.0, 11.0, 0.0, 1.0),
+];
+
+const LOGIC: &[ParamMeta] = &[
+  ParamMeta::linear("threshold", -1.0, 1.0, 0.0),
+];
+
+const PEAK_METER: &[ParamMeta] = &[
+  ParamMeta::log("attack", 0.001, 0.1, 0.005),
+  ParamMeta::log("release", 0.01, 5.0, 0.3),
+  ParamMeta::log("rmsWindow", 10.0, 1000.0, 300.0),
+];
+
+const AUDIO_IN: &[ParamMeta] = &[
+  ParamMeta::linear("gain", 0.0, 2.0, 1.0),
+];
+
+const SEND: &[ParamMeta] = &[
+  ParamMeta::linear("level", 0.0, 1.0, 0.5),
+];
+
+const CONTROL: &[ParamMeta] = &[
+  ParamMeta::linear("velocity", 0.0, 1.0, 1.0),
+  ParamMeta::linear("glide", 0.0, 0.5, 0.0),
+  ParamMeta::choice("glideMode", &["always", "legato"], 0.0),
+  ParamMeta::choice("glideCurve", &["linear", "exponential"], 0.0),
+  ParamMeta::linear("voiceDetune", 0.0, 50.0, 0.0),
+  ParamMeta::linear("voiceSpread", 0.0, 1.0, 0.0),
+  ParamMeta::log("refFreq", 220.0, 311.13, 261.63),
+  ParamMeta::stepped("edo", 5.0, 72.0, 12.0, 1.0),
+];
+
+const CV_BANK: &[ParamMeta] = &[
+  ParamMeta::stepped("channels", 1.0, 16.0, 5.0, 1.0),
+];
+
+const OUTPUT: &[ParamMeta] = &[
+  ParamMeta::linear("level", 0.0, 1.0, 0.8),
+  ParamMeta::linear("voiceSpread", 0.0, 1.0, 0.0),
+];
+
+const ARPEGGIATOR: &[ParamMeta] = &[
+  ParamMeta::linear("tempo", 40.0, 300.0, 120.0),
+  ParamMeta::linear("gate", 10.0, 100.0, 75.0),
+  ParamMeta::linear("swing", 0.0, 90.0, 0.0),
+  ParamMeta::linear("probability", 0.0, 100.0, 100.0),
+  ParamMeta::stepped("euclidSteps", 2.0, 16.0, 8.0, 1.0),
+];
+
+const CLOCK: &[ParamMeta] = &[
+  ParamMeta::linear("tempo", 40.0, 300.0, 120.0),
+  ParamMeta::linear("swing", 0.0, 90.0, 0.0),
+];
+
+const CLOCK_DIV: &[ParamMeta] = &[
+  ParamMeta::choice("ratio", &CLOCK_DIV_LABELS, 4.0),
+];
+
+const DRUM_SEQUENCER: &[ParamMeta] = &[
+  ParamMeta::linear("swing", 0.0, 90.0, 0.0),
+];
+
+const EUCLIDEAN: &[ParamMeta] = &[
+  ParamMeta::linear("tempo", 40.0, 300.0, 120.0),
+  ParamMeta::linear("gateLength", 10.0, 100.0, 50.0),
+  ParamMeta::linear("swing", 0.0, 90.0, 0.0),
+  ParamMeta::stepped("steps", 2.0, 32.0, 16.0, 1.0),
+];
+
+const MIDI_FILE_SEQUENCER: &[ParamMeta] = &[
+  ParamMeta::linear("tempo", 40.0, 300.0, 120.0),
+  ParamMeta::linear("tempoScale", 0.25, 4.0, 1.0),
+  ParamMeta::linear("gateLength", 10.0, 100.0, 90.0),
+];
+
+const STEP_SEQUENCER: &[ParamMeta] = &[
+  ParamMeta::linear("tempo", 40.0, 300.0, 120.0),
+  ParamMeta::linear("gateLength", 10.0, 100.0, 50.0),
+  ParamMeta::linear("swing", 0.0, 90.0, 0.0),
+  ParamMeta::log("slideTime", 10.0, 200.0, 50.0),
+];
+
+const TURING_MACHINE: &[ParamMeta] = &[
+  ParamMeta::linear("probability", 0.0, 1.0, 0.5),
+  ParamMeta::stepped("length", 2.0, 32.0, 8.0, 1.0),
+  ParamMeta::linear("range", 1.0, 5.0, 2.0),
+  ParamMeta::stepped("scale", 0.0, 8.0, 0.0, 1.0),
+  ParamMeta::stepped("root", 0.0, 11.0, 0.0, 1.0),
+  ParamMeta::stepped("write", 0.0, 1.0, 0.0, 1.0),
+];
+
+const FM_MATRIX: &[ParamMeta] = &[
+  ParamMeta::linear("feedback", 0.0, 1.0, 0.5),
+  ParamMeta::linear("brightness", 0.0, 1.0, 0.7),
+  ParamMeta::linear("master", 0.0, 1.0, 0.8),
+];
+
+const FM_OP: &[ParamMeta] = &[
+  ParamMeta::log("frequency", 20.0, 2000.0, 440.0),
+  ParamMeta::log("ratio", 0.1, 16.0, 1.0),
+  ParamMeta::linear("level", 0.0, 1.0, 1.0),
+  ParamMeta::linear("feedback", 0.0, 1.0, 0.0),
+  ParamMeta::log("attack", 0.1, 2000.0, 10.0),
+  ParamMeta::log("decay", 1.0, 3000.0, 200.0),
+  ParamMeta::linear("sustain", 0.0, 1.0, 0.7),
+  ParamMeta::log("release", 1.0, 5000.0, 300.0),
+];
+
+const KARPLUS: &[ParamMeta] = &[
+  ParamMeta::log("frequency", 40.0, 1200.0, 220.0),
+  ParamMeta::linear("damping", 0.0, 1.0, 0.3),
+  ParamMeta::linear("decay", 0.9, 0.999, 0.995),
+  ParamMeta::linear("brightness", 0.0, 1.0, 0.5),
+  ParamMeta::linear("pluckPos", 0.1, 0.9, 0.5),
+];
+
+const NES_OSC: &[ParamMeta] = &[
+  ParamMeta::log("frequency", 40.0, 2000.0, 220.0),
+  ParamMeta::linear("fine", -100.0, 100.0, 0.0),
+  ParamMeta::linear("volume", 0.0, 1.0, 1.0),
+  ParamMeta::linear("bitcrush", 0.0, 1.0, 1.0),
+];
+
+const NOISE: &[ParamMeta] = &[
+  ParamMeta::linear("level", 0.0, 1.0, 0.4),
+  ParamMeta::linear("stereo", 0.0, 1.0, 0.0),
+  ParamMeta::stepped("rateDivide", 1.0, 64.0, 1.0, 1.0),
+  ParamMeta::linear("pan", -1.0, 1.0, 0.0),
+];
+
+const OSCILLATOR: &[ParamMeta] = &[
+  ParamMeta::log("frequency", 40.0, 1200.0, 220.0),
+  ParamMeta::linear("detune", 0.0, 15.0, 0.0),
+  ParamMeta::linear("pwm", 0.05, 0.95, 0.5),
+  ParamMeta::linear("subMix", 0.0, 1.0, 0.0),
+  ParamMeta::linear("stereoSpread", 0.0, 1.0, 0.0),
+  ParamMeta::linear("fmLin", 0.0, 2000.0, 0.0),
+  ParamMeta::linear("fmExp", 0.0, 2.0, 0.0),
+  ParamMeta::stepped("unison", 1.0, 4.0, 1.0, 1.0),
+  ParamMeta::stepped("waveMorph", 0.0, 1.0, 0.0, 1.0),
+  ParamMeta::stepped("oversample", 0.0, 1.0, 0.0, 1.0),
+];
+
+const PARTICLE_CLOUD: &[ParamMeta] = &[
+  ParamMeta::stepped("count", 1.0, 32.0, 16.0, 1.0),
+  ParamMeta::log("grainSize", 10.0, 500.0, 100.0),
+  ParamMeta::linear("pitch", 0.25, 4.0, 1.0),
+  ParamMeta::linear("level", 0.0, 1.0, 0.8),
+  ParamMeta::linear("gravity", -1.0, 1.0, 0.0),
+  ParamMeta::linear("turbulence", 0.0, 1.0, 0.3),
+  ParamMeta::linear("friction", 0.0, 1.0, 0.1),
+  ParamMeta::linear("spread", 0.0, 1.0, 0.8),
+  ParamMeta::choice("mode", &["osc", "sample", "input"], 0.0),
+  ParamMeta::choice("oscShape", &["sine", "triangle", "saw", "square", "noise"], 0.0),
+];
+
+const PIPE_ORGAN: &[ParamMeta] = &[
+  ParamMeta::log("frequency", 40.0, 880.0, 220.0),
+  ParamMeta::linear("drawbar16", 0.0, 1.0, 0.5),
+  ParamMeta::linear("drawbar8", 0.0, 1.0, 0.8),
+  ParamMeta::linear("drawbar4", 0.0, 1.0, 0.6),
+  ParamMeta::linear("drawbar223", 0.0, 1.0, 0.0),
+  ParamMeta::linear("drawbar2", 0.0, 1.0, 0.4),
+  ParamMeta::linear("drawbar135", 0.0, 1.0, 0.0),
+  ParamMeta::linear("drawbar113", 0.0, 1.0, 0.0),
+  ParamMeta::linear("drawbar1", 0.0, 1.0, 0.2),
+  ParamMeta::linear("chiff", 0.0, 1.0, 0.3),
+  ParamMeta::linear("tremulant", 0.0, 1.0, 0.0),
+  ParamMeta::linear("tremRate", 4.0, 8.0, 6.0),
+  ParamMeta::linear("wind", 0.0, 1.0, 0.1),
+  ParamMeta::linear("brightness", 0.0, 1.0, 0.7),
+  ParamMeta::choice("voicing", &["diapason", "flute", "string"], 0.0),
+];
+
+const RESONATOR: &[ParamMeta] = &[
+  ParamMeta::log("frequency", 40.0, 2000.0, 220.0),
+  ParamMeta::linear("structure", 0.0, 1.0, 0.5),
+  ParamMeta::linear("brightness", 0.0, 1.0, 0.7),
+  ParamMeta::linear("damping", 0.0, 1.0, 0.7),
+  ParamMeta::linear("position", 0.0, 1.0, 0.5),
+  ParamMeta::linear("internalExc", 0.0, 1.0, 0.8),
+  ParamMeta::linear("chorus", 0.0, 1.0, 0.0),
+  ParamMeta::choice("mode", &["modal", "sympathetic", "inharmonic"], 0.0),
+  ParamMeta::stepped("polyphony", 1.0, 4.0, 1.0, 1.0),
+];
+
+const SAMPLE_PLAYER: &[ParamMeta] = &[
+  ParamMeta::stepped("loop", 0.0, 1.0, 0.0, 1.0),
+  ParamMeta::linear("start", 0.0, 1.0, 0.0),
+  ParamMeta::linear("end", 0.0, 1.0, 1.0),
+  ParamMeta::linear("gain", 0.0, 2.0, 1.0),
+];
+
+const SHEPARD: &[ParamMeta] = &[
+  ParamMeta::stepped("voices", 2.0, 12.0, 8.0, 1.0),
+  ParamMeta::linear("rate", -4.0, 4.0, 0.1),
+  ParamMeta::log("baseFreq", 55.0, 880.0, 220.0),
+  ParamMeta::linear("spread", 0.5, 2.0, 1.0),
+  ParamMeta::linear("stereo", 0.0, 1.0, 0.5),
+  ParamMeta::linear("detune", 0.0, 50.0, 0.0),
+  ParamMeta::linear("phaseSpread", 0.0, 1.0, 0.0),
+  ParamMeta::linear("tilt", -1.0, 1.0, 0.0),
+  ParamMeta::linear("feedback", 0.0, 0.9, 0.0),
+  ParamMeta::linear("vibrato", 0.0, 1.0, 0.0),
+  ParamMeta::linear("shimmer", 0.0, 1.0, 0.0),
+  ParamMeta::linear("mix", 0.0, 1.0, 1.0),
+  ParamMeta::choice("waveform", &["sine", "triangle", "saw", "square"], 0.0),
+  ParamMeta::choice("direction", &["up", "down", "alternate", "random"], 0.0),
+  ParamMeta::choice("interval", &["octave", "fifth", "fourth", "third"], 0.0),
+];
+
+const SNES_OSC: &[ParamMeta] = &[
+  ParamMeta::log("frequency", 40.0, 2000.0, 220.0),
+  ParamMeta::linear("fine", -100.0, 100.0, 0.0),
+  ParamMeta::linear("volume", 0.0, 1.0, 1.0),
+  ParamMeta::linear("gauss", 0.0, 1.0, 0.7),
+  ParamMeta::linear("color", 0.0, 1.0, 0.5),
+  ParamMeta::linear("lofi", 0.0, 1.0, 0.5),
+];
+
+const SPECTRAL_SWARM: &[ParamMeta] = &[
+  ParamMeta::log("frequency", 40.0, 880.0, 110.0),
+  ParamMeta::stepped("partials", 4.0, 32.0, 16.0, 1.0),
+  ParamMeta::linear("detune", 0.0, 100.0, 15.0),
+  ParamMeta::linear("drift", 0.0, 1.0, 0.3),
+  ParamMeta::linear("density", 0.0, 1.0, 0.8),
+  ParamMeta::log("evolution", 0.1, 10.0, 4.0),
+  ParamMeta::linear("inharmonic", -1.0, 1.0, 0.0),
+  ParamMeta::linear("tilt", -12.0, 12.0, -3.0),
+  ParamMeta::linear("oddEven", -1.0, 1.0, 0.0),
+  ParamMeta::linear("fundamentalMix", 0.0, 1.0, 0.5),
+  ParamMeta::linear("formantFreq", 0.0, 4000.0, 0.0),
+  ParamMeta::log("formantQ", 0.5, 20.0, 2.0),
+  ParamMeta::linear("spread", 0.0, 1.0, 0.7),
+  ParamMeta::linear("chorus", 0.0, 1.0, 0.0),
+  ParamMeta::linear("shimmer", -1.0, 1.0, 0.0),
+  ParamMeta::log("attack", 0.01, 10.0, 2.0),
+  ParamMeta::log("release", 0.01, 10.0, 3.0),
+  ParamMeta::log("attackLow", 0.1, 10.0, 1.0),
+  ParamMeta::log("attackHigh", 0.1, 10.0, 1.0),
+  ParamMeta::log("releaseLow", 0.1, 10.0, 1.0),
+  ParamMeta::log("releaseHigh", 0.1, 10.0, 1.0),
+  ParamMeta::choice("waveform", &["sine", "triangle", "saw", "square"], 0.0),
+];
+
+const SUPERSAW: &[ParamMeta] = &[
+  ParamMeta::log("frequency", 40.0, 1200.0, 220.0),
+  ParamMeta::linear("detune", 0.0, 100.0, 25.0),
+  ParamMeta::linear("mix", 0.0, 1.0, 1.0),
+  ParamMeta::linear("phaseSpread", 0.0, 1.0, 1.0),
+];
+
+const TB303: &[ParamMeta] = &[
+  ParamMeta::log("cutoff", 40.0, 12000.0, 800.0),
+  ParamMeta::linear("resonance", 0.0, 1.0, 0.3),
+  ParamMeta::log("decay", 0.01, 2.0, 0.3),
+  ParamMeta::linear("envmod", 0.0, 1.0, 0.5),
+  ParamMeta::linear("accent", 0.0, 1.0, 0.6),
+  ParamMeta::linear("glide", 0.0, 0.5, 0.02),
+];
+
+const WAVETABLE: &[ParamMeta] = &[
+  ParamMeta::log("frequency", 40.0, 2000.0, 220.0),
+  ParamMeta::linear("position", 0.0, 1.0, 0.0),
+  ParamMeta::linear("morphSpeed", 0.0, 10.0, 0.0),
+  ParamMeta::linear("detune", 0.0, 50.0, 15.0),
+  ParamMeta::linear("spread", 0.0, 1.0, 0.5),
+  ParamMeta::linear("subMix", 0.0, 1.0, 0.0),
+  ParamMeta::log("attack", 0.001, 2.0, 0.01),
+  ParamMeta::log("release", 0.001, 5.0, 0.3),
+  ParamMeta::choice("bank", &["basic", "vocal", "digital", "organic"], 0.0),
+  ParamMeta::stepped("unison", 1.0, 7.0, 1.0, 1.0),
+];
+
+const KICK_808: &[ParamMeta] = &[
+  ParamMeta::linear("tune", 20.0, 80.0, 45.0),
+  ParamMeta::log("decay", 0.1, 3.0, 1.5),
+  ParamMeta::linear("tone", 0.0, 1.0, 0.3),
+  ParamMeta::linear("click", 0.0, 1.0, 0.2),
+];
+
+const SNARE_808: &[ParamMeta] = &[
+  ParamMeta::linear("tune", 100.0, 300.0, 180.0),
+  ParamMeta::linear("tone", 0.0, 1.0, 0.5),
+  ParamMeta::linear("snappy", 0.0, 1.0, 0.6),
+  ParamMeta::linear("decay", 0.0, 1.0, 0.3),
+];
+
+const HIHAT_808: &[ParamMeta] = &[
+  ParamMeta::linear("tune", 0.5, 2.0, 1.0),
+  ParamMeta::log("decay", 0.02, 2.0, 0.15),
+  ParamMeta::linear("tone", 0.0, 1.0, 0.6),
+  ParamMeta::linear("snap", 0.0, 1.0, 0.5),
+];
+
+const COWBELL_808: &[ParamMeta] = &[
+  ParamMeta::linear("tune", 0.5, 2.0, 1.0),
+  ParamMeta::log("decay", 0.01, 0.5, 0.1),
+  ParamMeta::linear("tone", 0.0, 1.0, 0.6),
+];
+
+const CLAP_808: &[ParamMeta] = &[
+  ParamMeta::linear("tone", 0.0, 1.0, 0.5),
+  ParamMeta::linear("decay", 0.1, 0.8, 0.3),
+  ParamMeta::linear("spread", 0.0, 1.0, 0.5),
+];
+
+const TOM_808: &[ParamMeta] = &[
+  ParamMeta::linear("tune", 60.0, 400.0, 150.0),
+  ParamMeta::log("decay", 0.05, 1.0, 0.3),
+  ParamMeta::linear("pitch", 0.0, 1.0, 0.5),
+  ParamMeta::linear("tone", 0.0, 1.0, 0.4),
+];
+
+const KICK_909: &[ParamMeta] = &[
+  ParamMeta::linear("tune", 30.0, 100.0, 55.0),
+  ParamMeta::linear("attack", 0.0, 1.0, 0.5),
+  ParamMeta::linear("decay", 0.0, 1.0, 0.5),
+  ParamMeta::linear("drive", 0.0, 1.0, 0.3),
+];
+
+const SNARE_909: &[ParamMeta] = &[
+  ParamMeta::linear("tune", 100.0, 400.0, 200.0),
+  ParamMeta::linear("tone", 0.0, 1.0, 0.5),
+  ParamMeta::linear("snappy", 0.0, 1.0, 0.5),
+  ParamMeta::linear("decay", 0.0, 1.0, 0.3),
+];
+
+const HIHAT_909: &[ParamMeta] = &[
+  ParamMeta::linear("tone", 0.0, 1.0, 0.5),
+];
+
+const CLAP_909: &[ParamMeta] = &[
+  ParamMeta::linear("tone", 0.0, 1.0, 0.5),
+  ParamMeta::linear("decay", 0.0, 1.0, 0.4),
+];
+
+const TOM_909: &[ParamMeta] = &[
+  ParamMeta::linear("tune", 60.0, 300.0, 120.0),
+  ParamMeta::linear("decay", 0.0, 1.0, 0.4),
+];
+
+const RIMSHOT_909: &[ParamMeta] = &[
+  ParamMeta::linear("tune", 300.0, 800.0, 400.0),
+];
@@ -3,6 +3,11 @@
 //! This module defines the input and output ports for each module type,
 //! as well as mapping port IDs to indices.
 
+use std::collections::HashMap;
+
+use dsp_core::{MARIO_CHANNELS, MAX_BANK_CHANNELS};
+
+use crate::bank_channels;
 use crate::types::{ModuleType, PortInfo};
 
 /// Get the input ports for a given module type.
@@ -17,9 +22,14 @@ pub fn input_ports(module_type: ModuleType) -> Vec<PortInfo> {
       PortInfo { channels: 1 },
     ],
     ModuleType::Noise => vec![],
-    ModuleType::ModRouter => vec![PortInfo { channels: 1 }],
-    ModuleType::SampleHold => vec![PortInfo { channels: 1 }, PortInfo { channels: 1 }],
+    ModuleType::ModRouter | ModuleType::Attenuverter => vec![PortInfo { channels: 1 }],
+    ModuleType::SampleHold => vec![
+      PortInfo { channels: 1 },  // in
+      PortInfo { channels: 1 },  // trig
+      PortInfo { channels: 1 },  // noise
+    ],
     ModuleType::Slew => vec![PortInfo { channels: 1 }],
+    ModuleType::EnvFollower => vec![PortInfo { channels: 1 }],
     ModuleType::Quantizer => vec![PortInfo { channels: 1 }],
     ModuleType::RingMod => vec![PortInfo { channels: 1 }, PortInfo { channels: 1 }],
     ModuleType::Gain => vec![PortInfo { channels: 2 }, PortInfo { channels: 1 }],
@@ -60,23 +70,34 @@ pub fn input_ports(module_type: ModuleType) -> Vec<PortInfo> {
       PortInfo { channels: 2 },  // in-b (stereo)
       PortInfo { channels: 1 },  // mix CV
     ],
+    ModuleType::Multiple => vec![PortInfo { channels: 2 }],
+    ModuleType::Pan => vec![PortInfo { channels: 2 }, PortInfo { channels: 1 }],
+    ModuleType::Width | ModuleType::DcBlock => vec![PortInfo { channels: 2 }],
     ModuleType::Chorus
     | ModuleType::Ensemble
     | ModuleType::Delay
     | ModuleType::GranularDelay
     | ModuleType::TapeDelay
     | ModuleType::SpringReverb
-    | ModuleType::Reverb
-    | ModuleType::Phaser => {
+    | ModuleType::Reverb => {
       vec![PortInfo { channels: 2 }]
     },
+    ModuleType::Phaser => vec![
+      PortInfo { channels: 2 }, // audio in (stereo)
+      PortInfo { channels: 1 }, // sweep CV (replaces the LFO)
+    ],
     ModuleType::Choir => vec![
       PortInfo { channels: 2 }, // audio in (stereo)
       PortInfo { channels: 1 }, // vowel CV
     ],
-    ModuleType::Distortion => vec![PortInfo { channels: 1 }],
-    ModuleType::Wavefolder => vec![PortInfo { channels: 1 }],
-    ModuleType::Supersaw => vec![PortInfo { channels: 1 }],
+    // Stateless waveshapers: a stereo input is shaped per channel
+    ModuleType::Distortion => vec![PortInfo { channels: 2 }],
+    ModuleType::Wavefolder => vec![PortInfo { channels: 2 }],
+    ModuleType::BitCrusher => vec![PortInfo { channels: 1 }],
+    ModuleType::Supersaw => vec![
+      PortInfo { channels: 1 },  // pitch input
+      PortInfo { channels: 1 },  // gate input (re-scatters phases)
+    ],
     ModuleType::Karplus => vec![
       PortInfo { channels: 1 },  // pitch input
       PortInfo { channels: 1 },  // gate input
@@ -98,7 +119,7 @@ pub fn input_ports(module_type: ModuleType) -> Vec<PortInfo> {
       PortInfo { channels: 1 },
       PortInfo { channels: 1 },
     ],
-    ModuleType::Mario => vec![],
+    ModuleType::CvBank => vec![],
     ModuleType::Arpeggiator => vec![
       PortInfo { channels: 1 },  // cv-in
       PortInfo { channels: 1 },  // gate-in
@@ -114,6 +135,8 @@ pub fn input_ports(module_type: ModuleType) -> Vec<PortInfo> {
       PortInfo { channels: 1 },  // gate
       PortInfo { channels: 1 },  // velocity
       PortInfo { channels: 1 },  // cutoff-cv
+      PortInfo { channels: 1 },  // accent
+      PortInfo { channels: 1 },  // slide
     ],
     // TR-909 Drums - all have trigger + accent inputs
     ModuleType::Kick909 | ModuleType::Snare909 | ModuleType::HiHat909 |
@@ -154,6 +177,9 @@ pub fn input_ports(module_type: ModuleType) -> Vec<PortInfo> {
     ],
     // Notes - no inputs (UI only)
     ModuleType::Notes => vec![],
+    ModuleType::Send => vec![PortInfo { channels: 2 }],
+    // Return - bus sum, wired by set_graph (not patchable)
+    ModuleType::Return => vec![PortInfo { channels: 2 }],
     // Pitch Shifter - 2 inputs (audio, pitch CV)
     ModuleType::PitchShifter => vec![
       PortInfo { channels: 1 },  // audio input
@@ -165,6 +191,10 @@ pub fn input_ports(module_type: ModuleType) -> Vec<PortInfo> {
       PortInfo { channels: 1 },  // stop trigger
       PortInfo { channels: 1 },  // reset trigger
     ],
+    ModuleType::ClockDiv => vec![
+      PortInfo { channels: 1 },  // clock
+      PortInfo { channels: 1 },  // reset
+    ],
     // Shepard tone generator - 3 inputs (rate CV, pitch CV, sync)
     ModuleType::Shepard => vec![
       PortInfo { channels: 1 },  // rate CV
@@ -206,10 +236,16 @@ pub fn input_ports(module_type: ModuleType) -> Vec<PortInfo> {
     ModuleType::Chaos => vec![
       PortInfo { channels: 1 },  // speed
     ],
-    // Turing Machine - 2 inputs (clock, reset)
+    ModuleType::Logic => vec![
+      PortInfo { channels: 1 },  // in-a
+      PortInfo { channels: 1 },  // in-b
+    ],
+    ModuleType::PeakMeter => vec![PortInfo { channels: 1 }],
+    // Turing Machine - 3 inputs (clock, reset, probability CV)
     ModuleType::TuringMachine => vec![
       PortInfo { channels: 1 },  // clock
       PortInfo { channels: 1 },  // reset
+      PortInfo { channels: 1 },  // prob-cv
     ],
     // Granular - 4 inputs (audio, trigger, position CV, pitch CV)
     ModuleType::Granular => vec![
@@ -223,6 +259,11 @@ pub fn input_ports(module_type: ModuleType) -> Vec<PortInfo> {
       PortInfo { channels: 1 },  // audio in (for Input mode)
       PortInfo { channels: 1 },  // trigger
     ],
+    // SamplePlayer - 2 inputs (gate, pitch CV)
+    ModuleType::SamplePlayer => vec![
+      PortInfo { channels: 1 },  // gate (restart)
+      PortInfo { channels: 1 },  // pitch CV (V/oct)
+    ],
     // SidPlayer - 1 input (reset)
     ModuleType::SidPlayer => vec![
       PortInfo { channels: 1 },  // reset trigger
@@ -231,18 +272,38 @@ pub fn input_ports(module_type: ModuleType) -> Vec<PortInfo> {
     ModuleType::AyPlayer => vec![
       PortInfo { channels: 1 },  // reset trigger
     ],
-    // Compressor - 1 stereo input
+    // Compressor - 1 stereo input + sidechain
     ModuleType::Compressor => vec![
       PortInfo { channels: 2 },  // audio in (stereo)
+      PortInfo { channels: 1 },  // sidechain (mono)
+    ],
+    // Gate - 1 stereo input + key
+    ModuleType::Gate => vec![
+      PortInfo { channels: 2 },  // audio in (stereo)
+      PortInfo { channels: 1 },  // key (mono)
     ],
   }
 }
 
+/// Output ports of a module whose port count depends on its params
+/// (`cv-bank` channels); other types match `output_ports`.
+pub fn output_ports_for(module_type: ModuleType, params: &HashMap<String, serde_json::Value>) -> Vec<PortInfo> {
+  match module_type {
+    ModuleType::CvBank => cv_bank_ports(bank_channels(params)),
+    _ => output_ports(module_type),
+  }
+}
+
+/// A `cv-N` / `gate-N` output pair per channel.
+fn cv_bank_ports(channels: usize) -> Vec<PortInfo> {
+  vec![PortInfo { channels: 1 }; channels * 2]
+}
+
 /// Get the output ports for a given module type.
 pub fn output_ports(module_type: ModuleType) -> Vec<PortInfo> {
   match module_type {
     ModuleType::Oscillator => vec![
-      PortInfo { channels: 1 },
+      PortInfo { channels: 2 },
       PortInfo { channels: 1 },
       PortInfo { channels: 1 },
     ],
@@ -252,9 +313,12 @@ pub fn output_ports(module_type: ModuleType) -> Vec<PortInfo> {
       PortInfo { channels: 1 },
       PortInfo { channels: 1 },
       PortInfo { channels: 1 },
+      PortInfo { channels: 1 },
     ],
+    ModuleType::Attenuverter => vec![PortInfo { channels: 1 }],
     ModuleType::SampleHold => vec![PortInfo { channels: 1 }],
     ModuleType::Slew => vec![PortInfo { channels: 1 }],
+    ModuleType::EnvFollower => vec![PortInfo { channels: 1 }],
     ModuleType::Quantizer => vec![PortInfo { channels: 1 }],
     ModuleType::RingMod => vec![PortInfo { channels: 1 }],
     ModuleType::Gain => vec![PortInfo { channels: 2 }],
@@ -269,6 +333,10 @@ pub fn output_ports(module_type: ModuleType) -> Vec<PortInfo> {
     ModuleType::MixerWide => vec![PortInfo { channels: 2 }],  // stereo output
     ModuleType::Mixer8 => vec![PortInfo { channels: 2 }],     // stereo output
     ModuleType::Crossfader => vec![PortInfo { channels: 2 }], // stereo output
+    // Multiple - four copies of the input (stereo)
+    ModuleType::Multiple => vec![PortInfo { channels: 2 }; 4],
+    ModuleType::Pan => vec![PortInfo { channels: 2 }],
+    ModuleType::Width | ModuleType::DcBlock => vec![PortInfo { channels: 2 }],
     ModuleType::Chorus
     | ModuleType::Ensemble
     | ModuleType::Choir
@@ -280,8 +348,10 @@ pub fn output_ports(module_type: ModuleType) -> Vec<PortInfo> {
     | ModuleType::Phaser => {
       vec![PortInfo { channels: 2 }]
     },
-    ModuleType::Distortion => vec![PortInfo { channels: 1 }],
-    ModuleType::Wavefolder => vec![PortInfo { channels: 1 }],
+    // Stateless waveshapers: a stereo input is shaped per channel
+    ModuleType::Distortion => vec![PortInfo { channels: 2 }],
+    ModuleType::Wavefolder => vec![PortInfo { channels: 2 }],
+    ModuleType::BitCrusher => vec![PortInfo { channels: 1 }],
     ModuleType::Supersaw => vec![PortInfo { channels: 1 }],
     ModuleType::Karplus => vec![PortInfo { channels: 1 }],  // audio output
     ModuleType::NesOsc => vec![PortInfo { channels: 1 }],  // audio output
@@ -293,16 +363,10 @@ pub fn output_ports(module_type: ModuleType) -> Vec<PortInfo> {
       PortInfo { channels: 1 },
       PortInfo { channels: 1 },
       PortInfo { channels: 1 },
+      PortInfo { channels: 1 },  // pan-out
     ],
     ModuleType::Scope => vec![PortInfo { channels: 2 }, PortInfo { channels: 2 }],
-    ModuleType::Mario => {
-      let mut outputs = Vec::new();
-      for _ in 0..5 {
-        outputs.push(PortInfo { channels: 1 });
-        outputs.push(PortInfo { channels: 1 });
-      }
-      outputs
-    }
+    ModuleType::CvBank => cv_bank_ports(MARIO_CHANNELS),
     ModuleType::Arpeggiator => vec![
       PortInfo { channels: 1 },  // cv-out
       PortInfo { channels: 1 },  // gate-out
@@ -313,6 +377,7 @@ pub fn output_ports(module_type: ModuleType) -> Vec<PortInfo> {
       PortInfo { channels: 1 },  // gate-out
       PortInfo { channels: 1 },  // velocity-out
       PortInfo { channels: 1 },  // step-out
+      PortInfo { channels: 1 },  // slide-out
     ],
     ModuleType::Tb303 => vec![
       PortInfo { channels: 1 },  // out
@@ -366,6 +431,9 @@ pub fn output_ports(module_type: ModuleType) -> Vec<PortInfo> {
     ],
     // Notes - no outputs (UI only)
     ModuleType::Notes => vec![],
+    // Send - thru out, then the scaled bus feed (wired by set_graph, not patchable)
+    ModuleType::Send => vec![PortInfo { channels: 2 }, PortInfo { channels: 2 }],
+    ModuleType::Return => vec![PortInfo { channels: 2 }],
     // Clock - 4 outputs (clock, reset, run, bar)
     ModuleType::Clock => vec![
       PortInfo { channels: 1 },  // clock pulse
@@ -373,6 +441,7 @@ pub fn output_ports(module_type: ModuleType) -> Vec<PortInfo> {
       PortInfo { channels: 1 },  // run gate
       PortInfo { channels: 1 },  // bar pulse
     ],
+    ModuleType::ClockDiv => vec![PortInfo { channels: 1 }],
     // Shepard tone generator - 1 stereo output
     ModuleType::Shepard => vec![
       PortInfo { channels: 2 },  // stereo audio out
@@ -428,6 +497,18 @@ pub fn output_ports(module_type: ModuleType) -> Vec<PortInfo> {
       PortInfo { channels: 1 },  // z
       PortInfo { channels: 1 },  // gate
     ],
+    ModuleType::Logic => vec![
+      PortInfo { channels: 1 },  // and
+      PortInfo { channels: 1 },  // or
+      PortInfo { channels: 1 },  // xor
+      PortInfo { channels: 1 },  // compare
+    ],
+    // Peak Meter - 3 outputs (out, peak-cv, rms-cv)
+    ModuleType::PeakMeter => vec![
+      PortInfo { channels: 1 },  // out
+      PortInfo { channels: 1 },  // peak-cv
+      PortInfo { channels: 1 },  // rms-cv
+    ],
     // Turing Machine - 3 outputs (cv, gate, pulse)
     ModuleType::TuringMachine => vec![
       PortInfo { channels: 1 },  // cv
@@ -442,6 +523,10 @@ pub fn output_ports(module_type: ModuleType) -> Vec<PortInfo> {
     ModuleType::ParticleCloud => vec![
       PortInfo { channels: 2 },  // stereo out
     ],
+    // SamplePlayer - 1 stereo output
+    ModuleType::SamplePlayer => vec![
+      PortInfo { channels: 2 },  // stereo out
+    ],
     // SidPlayer - 1 stereo output + 3 gates + 3 CVs + 3 waveform CVs
     ModuleType::SidPlayer => vec![
       PortInfo { channels: 2 },  // stereo audio out
@@ -465,9 +550,14 @@ pub fn output_ports(module_type: ModuleType) -> Vec<PortInfo> {
       PortInfo { channels: 1 },  // cv-b
       PortInfo { channels: 1 },  // cv-c
     ],
-    // Compressor - 1 stereo output
+    // Compressor - stereo output + gain reduction
     ModuleType::Compressor => vec![
       PortInfo { channels: 2 },  // stereo audio out
+      PortInfo { channels: 1 },  // gain reduction CV (0 = none)
+    ],
+    // Gate - 1 stereo output
+    ModuleType::Gate => vec![
+      PortInfo { channels: 2 },  // stereo audio out
     ],
   }
 }
@@ -488,12 +578,17 @@ pub fn input_port_index(module_type: ModuleType, port_id: &str) -> Option<usize>
       "in" => Some(0),
       _ => None,
     },
+    ModuleType::Attenuverter => match port_id {
+      "cv-in" | "in" => Some(0),
+      _ => None,
+    },
     ModuleType::SampleHold => match port_id {
       "in" => Some(0),
       "trig" => Some(1),
+      "noise" => Some(2),
       _ => None,
     },
-    ModuleType::Slew => match port_id {
+    ModuleType::Slew | ModuleType::EnvFollower => match port_id {
       "in" => Some(0),
       _ => None,
     },
@@ -573,7 +668,20 @@ pub fn input_port_index(module_type: ModuleType, port_id: &str) -> Option<usize>
     ModuleType::Crossfader => match port_id {
       "in-a" | "a" => Some(0),
       "in-b" | "b" => Some(1),
-      "mix" | "cv" => Some(2),
+      "mix" | "cv" | "fade" => Some(2),
+      _ => None,
+    },
+    ModuleType::Multiple => match port_id {
+      "in" => Some(0),
+      _ => None,
+    },
+    ModuleType::Pan => match port_id {
+      "in" => Some(0),
+      "pan" | "cv" => Some(1),
+      _ => None,
+    },
+    ModuleType::Width | ModuleType::DcBlock => match port_id {
+      "in" => Some(0),
       _ => None,
     },
     ModuleType::Chorus
@@ -582,11 +690,15 @@ pub fn input_port_index(module_type: ModuleType, port_id: &str) -> Option<usize>
     | ModuleType::GranularDelay
     | ModuleType::TapeDelay
     | ModuleType::SpringReverb
-    | ModuleType::Reverb
-    | ModuleType::Phaser => match port_id {
+    | ModuleType::Reverb => match port_id {
       "in" => Some(0),
       _ => None,
     },
+    ModuleType::Phaser => match port_id {
+      "in" => Some(0),
+      "mod" | "sync" | "cv" => Some(1),
+      _ => None,
+    },
     ModuleType::Choir => match port_id {
       "in" => Some(0),
       "vowel" | "cv" => Some(1),
@@ -600,8 +712,13 @@ pub fn input_port_index(module_type: ModuleType, port_id: &str) -> Option<usize>
       "in" => Some(0),
       _ => None,
     },
+    ModuleType::BitCrusher => match port_id {
+      "in" => Some(0),
+      _ => None,
+    },
     ModuleType::Supersaw => match port_id {
       "pitch" => Some(0),
+      "gate" => Some(1),
       _ => None,
     },
     ModuleType::Karplus => match port_id {
@@ -648,6 +765,8 @@ pub fn input_port_index(module_type: ModuleType, port_id: &str) -> Option<usize>
       "gate" => Some(1),
       "velocity" | "vel" => Some(2),
       "cutoff-cv" | "cut" => Some(3),
+      "accent" | "acc" => Some(4),
+      "slide" => Some(5),
       _ => None,
     },
     // TR-909 Drums
@@ -701,6 +820,11 @@ pub fn input_port_index(module_type: ModuleType, port_id: &str) -> Option<usize>
     },
     // Notes - no inputs
     ModuleType::Notes => None,
+    ModuleType::Send => match port_id {
+      "in" => Some(0),
+      _ => None,
+    },
+    ModuleType::Return => None,
     // Clock - 3 inputs
     ModuleType::Clock => match port_id {
       "start" => Some(0),
@@ -708,6 +832,11 @@ pub fn input_port_index(module_type: ModuleType, port_id: &str) -> Option<usize>
       "rst-in" => Some(2),
       _ => None,
     },
+    ModuleType::ClockDiv => match port_id {
+      "clock" | "clk" => Some(0),
+      "reset" | "rst" => Some(1),
+      _ => None,
+    },
     // Shepard - 3 inputs
     ModuleType::Shepard => match port_id {
       "rate-cv" | "rate" => Some(0),
@@ -755,9 +884,19 @@ pub fn input_port_index(module_type: ModuleType, port_id: &str) -> Option<usize>
       "speed" => Some(0),
       _ => None,
     },
+    ModuleType::Logic => match port_id {
+      "in-a" | "a" => Some(0),
+      "in-b" | "b" => Some(1),
+      _ => None,
+    },
+    ModuleType::PeakMeter => match port_id {
+      "in" => Some(0),
+      _ => None,
+    },
     ModuleType::TuringMachine => match port_id {
       "clock" | "clk" => Some(0),
       "reset" | "rst" => Some(1),
+      "prob-cv" | "prob" => Some(2),
       _ => None,
     },
     // Granular - 4 inputs
@@ -774,6 +913,12 @@ pub fn input_port_index(module_type: ModuleType, port_id: &str) -> Option<usize>
       "trigger" | "trig" => Some(1),
       _ => None,
     },
+    // SamplePlayer - 2 inputs
+    ModuleType::SamplePlayer => match port_id {
+      "gate" | "trigger" | "trig" => Some(0),
+      "pitch" | "pitch-cv" => Some(1),
+      _ => None,
+    },
     // SidPlayer - 1 input
     ModuleType::SidPlayer => match port_id {
       "reset" | "rst" => Some(0),
@@ -784,9 +929,16 @@ pub fn input_port_index(module_type: ModuleType, port_id: &str) -> Option<usize>
       "reset" | "rst" => Some(0),
       _ => None,
     },
-    // Compressor - 1 input
+    // Compressor - 1 input + sidechain
     ModuleType::Compressor => match port_id {
       "in" | "input" | "audio" => Some(0),
+      "sidechain" | "sc" => Some(1),
+      _ => None,
+    },
+    // Gate - 1 input + key
+    ModuleType::Gate => match port_id {
+      "in" | "input" | "audio" => Some(0),
+      "key" | "sidechain" | "sc" => Some(1),
       _ => None,
     },
     _ => None,
@@ -811,6 +963,11 @@ pub fn output_port_index(module_type: ModuleType, port_id: &str) -> Option<usize
       "pwm" => Some(1),
       "vcf" => Some(2),
       "vca" => Some(3),
+      "aux" => Some(4),
+      _ => None,
+    },
+    ModuleType::Attenuverter => match port_id {
+      "cv-out" | "out" => Some(0),
       _ => None,
     },
     ModuleType::SampleHold => match port_id {
@@ -821,6 +978,10 @@ pub fn output_port_index(module_type: ModuleType, port_id: &str) -> Option<usize
       "out" => Some(0),
       _ => None,
     },
+    ModuleType::EnvFollower => match port_id {
+      "out" | "cv-out" => Some(0),
+      _ => None,
+    },
     ModuleType::Quantizer => match port_id {
       "out" => Some(0),
       _ => None,
@@ -874,10 +1035,17 @@ pub fn output_port_index(module_type: ModuleType, port_id: &str) -> Option<usize
       "out" => Some(0),
       _ => None,
     },
-    ModuleType::Crossfader => match port_id {
+    ModuleType::Crossfader | ModuleType::Pan | ModuleType::Width | ModuleType::DcBlock => match port_id {
       "out" => Some(0),
       _ => None,
     },
+    ModuleType::Multiple => match port_id {
+      "out-1" | "out" => Some(0),
+      "out-2" => Some(1),
+      "out-3" => Some(2),
+      "out-4" => Some(3),
+      _ => None,
+    },
     ModuleType::Chorus
     | ModuleType::Ensemble
     | ModuleType::Choir
@@ -898,6 +1066,10 @@ pub fn output_port_index(module_type: ModuleType, port_id: &str) -> Option<usize
       "out" => Some(0),
       _ => None,
     },
+    ModuleType::BitCrusher => match port_id {
+      "out" => Some(0),
+      _ => None,
+    },
     ModuleType::Supersaw => match port_id {
       "out" => Some(0),
       _ => None,
@@ -927,6 +1099,7 @@ pub fn output_port_index(module_type: ModuleType, port_id: &str) -> Option<usize
       "vel-out" => Some(1),
       "gate-out" => Some(2),
       "sync-out" => Some(3),
+      "pan-out" => Some(4),
       _ => None,
     },
     ModuleType::Scope => match port_id {
@@ -934,19 +1107,18 @@ pub fn output_port_index(module_type: ModuleType, port_id: &str) -> Option<usize
       "out-b" => Some(1),
       _ => None,
     },
-    ModuleType::Mario => match port_id {
-      "cv-1" => Some(0),
-      "gate-1" => Some(1),
-      "cv-2" => Some(2),
-      "gate-2" => Some(3),
-      "cv-3" => Some(4),
-      "gate-3" => Some(5),
-      "cv-4" => Some(6),
-      "gate-4" => Some(7),
-      "cv-5" => Some(8),
-      "gate-5" => Some(9),
-      _ => None,
-    },
+    ModuleType::CvBank => {
+      let (kind, channel) = port_id.split_once('-')?;
+      let channel: usize = channel.parse().ok()?;
+      if !(1..=MAX_BANK_CHANNELS).contains(&channel) {
+        return None;
+      }
+      match kind {
+        "cv" => Some((channel - 1) * 2),
+        "gate" => Some((channel - 1) * 2 + 1),
+        _ => None,
+      }
+    }
     ModuleType::Arpeggiator => match port_id {
       "cv-out" => Some(0),
       "gate-out" => Some(1),
@@ -958,6 +1130,7 @@ pub fn output_port_index(module_type: ModuleType, port_id: &str) -> Option<usize
       "gate-out" => Some(1),
       "velocity-out" => Some(2),
       "step-out" => Some(3),
+      "slide-out" => Some(4),
       _ => None,
     },
     ModuleType::Tb303 => match port_id {
@@ -1022,6 +1195,14 @@ pub fn output_port_index(module_type: ModuleType, port_id: &str) -> Option<usize
     },
     // Notes - no outputs
     ModuleType::Notes => None,
+    ModuleType::Send => match port_id {
+      "out" => Some(0),
+      _ => None,
+    },
+    ModuleType::Return => match port_id {
+      "out" => Some(0),
+      _ => None,
+    },
     // Clock - 4 outputs
     ModuleType::Clock => match port_id {
       "clock" | "clk" => Some(0),
@@ -1030,6 +1211,10 @@ pub fn output_port_index(module_type: ModuleType, port_id: &str) -> Option<usize
       "bar" => Some(3),
       _ => None,
     },
+    ModuleType::ClockDiv => match port_id {
+      "clock-out" | "out" => Some(0),
+      _ => None,
+    },
     // Shepard - 1 output
     ModuleType::Shepard => match port_id {
       "out" | "output" => Some(0),
@@ -1091,6 +1276,19 @@ pub fn output_port_index(module_type: ModuleType, port_id: &str) -> Option<usize
       "gate" => Some(3),
       _ => None,
     },
+    ModuleType::Logic => match port_id {
+      "and" => Some(0),
+      "or" => Some(1),
+      "xor" => Some(2),
+      "compare" | "cmp" => Some(3),
+      _ => None,
+    },
+    ModuleType::PeakMeter => match port_id {
+      "out" => Some(0),
+      "peak-cv" | "peak" => Some(1),
+      "rms-cv" | "rms" => Some(2),
+      _ => None,
+    },
     ModuleType::TuringMachine => match port_id {
       "cv" | "cv-out" => Some(0),
       "gate" | "gate-out" => Some(1),
@@ -1107,6 +1305,11 @@ pub fn output_port_index(module_type: ModuleType, port_id: &str) -> Option<usize
       "out" | "output" => Some(0),
       _ => None,
     },
+    // SamplePlayer - 1 stereo output
+    ModuleType::SamplePlayer => match port_id {
+      "out" | "output" => Some(0),
+      _ => None,
+    },
     // SidPlayer - 1 stereo output + 3 gates + 3 CVs + 3 waveform CVs
     ModuleType::SidPlayer => match port_id {
       "out" | "output" | "audio" => Some(0),
@@ -1132,8 +1335,14 @@ pub fn output_port_index(module_type: ModuleType, port_id: &str) -> Option<usize
       "cv-c" => Some(6),
       _ => None,
     },
-    // Compressor - 1 output
+    // Compressor - output + gain reduction
     ModuleType::Compressor => match port_id {
+      "out" | "output" => Some(0),
+      "gr" | "gain-reduction" => Some(1),
+      _ => None,
+    },
+    // Gate - 1 output
+    ModuleType::Gate => match port_id {
       "out" | "output" => Some(0),
       _ => None,
     },
@@ -0,0 +1,113 @@
+//! Preset files.
+//!
+//! A [`Preset`] wraps a graph payload with its metadata. On disk it is
+//! pretty-printed JSON with the [`PRESET_EXTENSION`] extension.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GraphEngine, GraphError};
+
+/// File extension of preset files (without the dot).
+pub const PRESET_EXTENSION: &str = "noobsynth";
+
+/// A saved patch: graph JSON plus metadata.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Preset {
+  pub name: String,
+  #[serde(default)]
+  pub description: String,
+  #[serde(default)]
+  pub author: String,
+  /// Graph payload in the `set_graph_json` shape.
+  pub graph_json: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub bpm: Option<f32>,
+  #[serde(default)]
+  pub tags: Vec<String>,
+}
+
+impl Preset {
+  /// Write the preset to `path` as JSON.
+  pub fn save(&self, path: &Path) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(self).map_err(|err| format!("Cannot serialize preset: {err}"))?;
+    std::fs::write(path, json).map_err(|err| format!("Cannot write {}: {err}", path.display()))
+  }
+
+  /// Read a preset from `path`. The graph is not checked; see [`validate`](Self::validate).
+  pub fn load(path: &Path) -> Result<Self, String> {
+    let json = std::fs::read_to_string(path).map_err(|err| format!("Cannot read {}: {err}", path.display()))?;
+    serde_json::from_str(&json).map_err(|err| format!("Invalid preset {}: {err}", path.display()))
+  }
+
+  /// Check the wrapped graph with [`GraphEngine::validate_graph_json`].
+  pub fn validate(&self) -> Result<(), GraphError> {
+    GraphEngine::validate_graph_json(&self.graph_json)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::GraphBuilder;
+  use serde_json::json;
+
+  fn graph() -> String {
+    GraphBuilder::new()
+      .add_module("osc-1", "oscillator", json!({ "type": "sawtooth" }))
+      .add_module("out-1", "output", json!({}))
+      .connect("osc-1", "out", "out-1", "in", "audio")
+      .build()
+  }
+
+  #[test]
+  fn preset_round_trips_through_a_file() {
+    let preset = Preset {
+      name: "Saw".to_string(),
+      description: "Plain saw".to_string(),
+      author: "test".to_string(),
+      graph_json: graph(),
+      bpm: Some(120.0),
+      tags: vec!["bass".to_string()],
+    };
+    let path = std::env::temp_dir().join(format!("dsp-graph-preset-{}.{PRESET_EXTENSION}", std::process::id()));
+    preset.save(&path).unwrap();
+    let loaded = Preset::load(&path);
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(loaded.unwrap(), preset);
+    assert!(preset.validate().is_ok());
+  }
+
+  #[test]
+  fn validate_rejects_broken_graphs() {
+    let with_graph = |graph_json: String| Preset { name: "Broken".to_string(), graph_json, ..Preset::default() };
+    let unknown_type = GraphBuilder::new().add_module("x-1", "theremin", json!({})).build();
+    let duplicate = GraphBuilder::new()
+      .add_module("osc-1", "oscillator", json!({}))
+      .add_module("osc-1", "noise", json!({}))
+      .build();
+    let dangling = GraphBuilder::new()
+      .add_module("osc-1", "oscillator", json!({}))
+      .connect("osc-1", "out", "out-1", "in", "audio")
+      .build();
+    assert!(matches!(with_graph("not json".to_string()).validate(), Err(GraphError::JsonParse(_))));
+    assert!(matches!(with_graph(unknown_type).validate(), Err(GraphError::UnknownModuleType(kind)) if kind == "theremin"));
+    match with_graph(duplicate).validate() {
+      Err(GraphError::ValidationFailed(problems)) => assert_eq!(problems, ["Duplicate module id osc-1"]),
+      other => panic!("{other:?}"),
+    }
+    match with_graph(dangling).validate() {
+      Err(GraphError::ValidationFailed(problems)) => assert_eq!(problems, ["Unknown module out-1"]),
+      other => panic!("{other:?}"),
+    }
+  }
+
+  #[test]
+  fn missing_metadata_defaults() {
+    let preset: Preset = serde_json::from_value(json!({ "name": "Bare", "graphJson": graph() })).unwrap();
+    assert_eq!(preset.bpm, None);
+    assert!(preset.tags.is_empty() && preset.author.is_empty());
+  }
+}
@@ -4,13 +4,15 @@
 //! to the appropriate DSP implementation based on module state.
 
 use dsp_core::{
+    input_at,
     AdsrInputs, AdsrParams, ArpeggiatorInputs, ArpeggiatorOutputs, ArpeggiatorParams,
+    BitCrusherParams,
     ChaosInputs, ChaosParams,
     ChoirInputs, ChoirParams, ChorusInputs, ChorusParams,
     Clap808Inputs, Clap808Params, Clap909Inputs, Clap909Params,
-    CompressorParams,
+    CompressorInputs, CompressorParams,
     Cowbell808Inputs, Cowbell808Params,
-    DelayInputs, DelayParams, Distortion, DistortionParams,
+    DcBlockerParams, DelayInputs, DelayParams, Distortion, DistortionParams,
     DrumSequencerInputs, DrumSequencerOutputs, DrumSequencerParams,
     EnsembleInputs, EnsembleParams, EuclideanInputs, EuclideanParams,
     FmMatrixParams, FmOperatorInputs, FmOperatorParams, OpParams,
@@ -22,22 +24,28 @@ use dsp_core::{
     Kick808Inputs, Kick808Params,
     Kick909Inputs, Kick909Params,
     LfoInputs, LfoParams,
+    Logic, LogicInputs, LogicOutputs, LogicParams,
+    PeakMeterInputs, PeakMeterOutputs, PeakMeterParams,
+    ClockDividerInputs, ClockDividerParams,
     MasterClockInputs, MasterClockOutputs, MasterClockParams,
     MidiFileSequencerInputs, MidiFileSequencerOutputs, MidiFileSequencerParams,
-    Mixer, Crossfader, NesOscInputs, NesOscParams, NoiseParams,
+    NoiseGateInputs, NoiseGateParams,
+    Mixer, Crossfader, CrossfaderParams, Pan, NesOscInputs, NesOscParams, NoiseParams,
     ParticleCloudInputs, ParticleCloudParams,
     PhaserInputs, PhaserParams, PipeOrganInputs, PipeOrganParams, PitchShifterInputs, PitchShifterParams,
     Quantizer, QuantizerInputs, QuantizerParams,
     ResonatorInputs, ResonatorParams,
-    ReverbInputs, ReverbParams, RingMod, RingModParams,
+    ReverbInputs, ReverbParams, RingModParams,
     Rimshot909Inputs, Rimshot909Params, Sample,
-    SampleHoldInputs, SampleHoldParams, ShepardInputs, ShepardParams,
+    SampleHoldInputs, SampleHoldParams, SamplePlayerInputs, SamplePlayerParams,
+    ShepardInputs, ShepardParams,
     SidPlayerInputs, SidPlayerOutputs, SidPlayerParams,
     AyPlayerInputs, AyPlayerOutputs, AyPlayerParams,
     SlewInputs, SlewParams,
+    EnvFollowerInputs, EnvFollowerParams,
     Snare808Inputs, Snare808Params,
     Snare909Inputs, Snare909Params, SnesOscInputs, SnesOscParams, SpectralSwarmInputs, SpectralSwarmParams,
-    SpringReverbInputs, SpringReverbParams,
+    SpringReverbInputs, SpringReverbParams, StereoWidthParams,
     StepSequencerInputs, StepSequencerOutputs, StepSequencerParams,
     SupersawInputs, SupersawParams,
     TapeDelayInputs, TapeDelayParams,
@@ -47,7 +55,6 @@ use dsp_core::{
     Vca, VcfInputs, VcfParams, VcoInputs, VcoParams,
     VocoderInputs, VocoderParams, Wavefolder, WavefolderParams,
     WavetableInputs, WavetableParams,
-    MARIO_CHANNELS,
 };
 
 use crate::buffer::{mix_buffers, Buffer};
@@ -77,13 +84,14 @@ pub(crate) fn process_module(
             let sync = inputs[4].channel(0);
             let fm_audio = inputs[5].channel(0);
             let (main_group, rest) = outputs.split_at_mut(1);
-            let out = main_group[0].channel_mut(0);
+            let (out_l, out_r) = main_group[0].channels_mut_2();
             let (sub_group, sync_group) = rest.split_at_mut(1);
             let sub_out = sub_group.get_mut(0).map(|buffer| buffer.channel_mut(0));
             let sync_out = sync_group.get_mut(0).map(|buffer| buffer.channel_mut(0));
             let params = VcoParams {
                 base_freq: state.base_freq.slice(frames),
                 waveform: state.waveform.slice(frames),
+                wave_morph: state.wave_morph.slice(frames),
                 pwm: state.pwm.slice(frames),
                 fm_lin_depth: state.fm_lin_depth.slice(frames),
                 fm_exp_depth: state.fm_exp_depth.slice(frames),
@@ -91,6 +99,8 @@ pub(crate) fn process_module(
                 detune: state.detune.slice(frames),
                 sub_mix: state.sub_mix.slice(frames),
                 sub_oct: state.sub_oct.slice(frames),
+                stereo_spread: state.stereo_spread.slice(frames),
+                oversample: state.oversample.slice(frames),
             };
             let vco_inputs = VcoInputs {
                 pitch: Some(pitch),
@@ -100,13 +110,14 @@ pub(crate) fn process_module(
                 pwm: Some(pwm_in),
                 sync: Some(sync),
             };
-            state.vco.process_block(out, sub_out, sync_out, vco_inputs, params);
+            state.vco.process_block_stereo(out_l, out_r, sub_out, sync_out, vco_inputs, params);
         }
         ModuleState::Noise(state) => {
             let (out_l, out_r) = outputs[0].channels_mut_2();
             let params = NoiseParams {
                 level: state.level.slice(frames),
                 noise_type: state.noise_type.slice(frames),
+                rate_divide: state.rate_divide.slice(frames),
                 stereo: state.stereo.slice(frames),
                 pan: state.pan.slice(frames),
             };
@@ -118,32 +129,33 @@ pub(crate) fn process_module(
             } else {
                 Some(inputs[0].channel(0))
             };
-            let (pitch_group, rest) = outputs.split_at_mut(1);
-            let (pwm_group, rest) = rest.split_at_mut(1);
-            let (vcf_group, vca_group) = rest.split_at_mut(1);
-            let out_pitch = pitch_group[0].channel_mut(0);
-            let out_pwm = pwm_group[0].channel_mut(0);
-            let out_vcf = vcf_group[0].channel_mut(0);
-            let out_vca = vca_group[0].channel_mut(0);
-            let depth_pitch = state.depth_pitch.slice(frames);
-            let depth_pwm = state.depth_pwm.slice(frames);
-            let depth_vcf = state.depth_vcf.slice(frames);
-            let depth_vca = state.depth_vca.slice(frames);
+            let routes = [
+                (&mut state.depth_pitch, &mut state.offset_pitch),
+                (&mut state.depth_pwm, &mut state.offset_pwm),
+                (&mut state.depth_vcf, &mut state.offset_vcf),
+                (&mut state.depth_vca, &mut state.offset_vca),
+                (&mut state.depth_aux, &mut state.offset_aux),
+            ];
+            for ((depth, offset), output) in routes.into_iter().zip(outputs.iter_mut()) {
+                let depth = depth.slice(frames);
+                let offset = offset.slice(frames);
+                let out = output.channel_mut(0);
+                for i in 0..frames {
+                    out[i] = input_at(input, i) * depth[i] + offset[i];
+                }
+            }
+        }
+        ModuleState::Attenuverter(state) => {
+            let input = if connections[0].is_empty() {
+                None
+            } else {
+                Some(inputs[0].channel(0))
+            };
+            let scale = state.scale.slice(frames);
+            let offset = state.offset.slice(frames);
+            let out = outputs[0].channel_mut(0);
             for i in 0..frames {
-                let source = match input {
-                    Some(values) => {
-                        if values.len() > 1 {
-                            values[i]
-                        } else {
-                            values[0]
-                        }
-                    }
-                    None => 0.0,
-                };
-                out_pitch[i] = source * depth_pitch[i];
-                out_pwm[i] = source * depth_pwm[i];
-                out_vcf[i] = source * depth_vcf[i];
-                out_vca[i] = source * depth_vca[i];
+                out[i] = input_at(input, i) * scale[i] + offset[i];
             }
         }
         ModuleState::SampleHold(state) => {
@@ -157,10 +169,17 @@ pub(crate) fn process_module(
             } else {
                 Some(inputs[1].channel(0))
             };
+            let noise = if connections[2].is_empty() {
+                None
+            } else {
+                Some(inputs[2].channel(0))
+            };
             let params = SampleHoldParams {
                 mode: state.mode.slice(frames),
+                glide: state.glide.slice(frames),
+                noise_level: state.noise_level.slice(frames),
             };
-            let sh_inputs = SampleHoldInputs { input, trigger };
+            let sh_inputs = SampleHoldInputs { input, trigger, noise };
             let output = outputs[0].channel_mut(0);
             state.sample_hold.process_block(output, sh_inputs, params);
         }
@@ -178,6 +197,21 @@ pub(crate) fn process_module(
             let output = outputs[0].channel_mut(0);
             state.slew.process_block(output, slew_inputs, params);
         }
+        ModuleState::EnvFollower(state) => {
+            let input = if connections[0].is_empty() {
+                None
+            } else {
+                Some(inputs[0].channel(0))
+            };
+            let params = EnvFollowerParams {
+                attack: state.attack.slice(frames),
+                release: state.release.slice(frames),
+                gain: state.gain.slice(frames),
+            };
+            let follower_inputs = EnvFollowerInputs { input };
+            let output = outputs[0].channel_mut(0);
+            state.follower.process_block(output, follower_inputs, params);
+        }
         ModuleState::Quantizer(state) => {
             let input = if connections[0].is_empty() {
                 None
@@ -192,6 +226,37 @@ pub(crate) fn process_module(
             let output = outputs[0].channel_mut(0);
             Quantizer::process_block(output, q_inputs, params);
         }
+        ModuleState::Logic(state) => {
+            let a = if connections[0].is_empty() { None } else { Some(inputs[0].channel(0)) };
+            let b = if connections[1].is_empty() { None } else { Some(inputs[1].channel(0)) };
+            let params = LogicParams { threshold: state.threshold.slice(frames) };
+            let (and_group, rest) = outputs.split_at_mut(1);
+            let (or_group, rest) = rest.split_at_mut(1);
+            let (xor_group, compare_group) = rest.split_at_mut(1);
+            let logic_outputs = LogicOutputs {
+                and: and_group[0].channel_mut(0),
+                or: or_group[0].channel_mut(0),
+                xor: xor_group[0].channel_mut(0),
+                compare: compare_group[0].channel_mut(0),
+            };
+            Logic::process_block(logic_outputs, LogicInputs { a, b }, params);
+        }
+        ModuleState::PeakMeter(state) => {
+            let input = if connections[0].is_empty() { None } else { Some(inputs[0].channel(0)) };
+            let params = PeakMeterParams {
+                attack: state.attack.slice(frames),
+                release: state.release.slice(frames),
+                rms_window: state.rms_window.slice(frames),
+            };
+            let (out_group, rest) = outputs.split_at_mut(1);
+            let (peak_group, rms_group) = rest.split_at_mut(1);
+            let meter_outputs = PeakMeterOutputs {
+                out: out_group[0].channel_mut(0),
+                peak: peak_group[0].channel_mut(0),
+                rms: rms_group[0].channel_mut(0),
+            };
+            state.meter.process_block(meter_outputs, PeakMeterInputs { input }, params);
+        }
         ModuleState::Chaos(state) => {
             let speed = if !connections[0].is_empty() {
                 Some(inputs[0].channel(0))
@@ -234,13 +299,18 @@ pub(crate) fn process_module(
             let output = outputs[0].channel_mut(0);
             let params = RingModParams {
                 level: state.level.slice(frames),
+                carrier_freq: state.carrier_freq.slice(frames),
+                carrier_shape: state.carrier_shape.slice(frames),
+                internal_carrier: state.internal_carrier.slice(frames),
+                mix: state.mix.slice(frames),
             };
-            RingMod::process_block(output, input_a, input_b, params);
+            state.ring.process_block(output, input_a, input_b, params);
         }
         ModuleState::Gain(state) => {
             let input_connected = !connections[0].is_empty();
             let cv_connected = !connections[1].is_empty();
             let gain = state.gain.slice(frames);
+            let invert = state.invert.slice(frames);
             let cv = if cv_connected { Some(inputs[1].channel(0)) } else { None };
             for channel in 0..2 {
                 let src = if input_connected {
@@ -254,13 +324,14 @@ pub(crate) fn process_module(
                 };
                 let output = outputs[0].channel_mut(channel);
                 let input = if input_connected { Some(src) } else { None };
-                Vca::process_block(output, input, cv, gain);
+                Vca::process_block(output, input, cv, gain, invert);
             }
         }
         ModuleState::CvVca(state) => {
             let input_connected = !connections[0].is_empty();
             let cv_connected = !connections[1].is_empty();
             let gain = state.gain.slice(frames);
+            let invert = state.invert.slice(frames);
             let input = if input_connected {
                 Some(inputs[0].channel(0))
             } else {
@@ -268,7 +339,7 @@ pub(crate) fn process_module(
             };
             let cv = if cv_connected { Some(inputs[1].channel(0)) } else { None };
             let output = outputs[0].channel_mut(0);
-            Vca::process_block(output, input, cv, gain);
+            Vca::process_block(output, input, cv, gain, invert);
         }
         ModuleState::Output(state) => {
             let input_connected = !connections[0].is_empty();
@@ -427,6 +498,18 @@ pub(crate) fn process_module(
             let output = outputs[0].channel_mut(0);
             state.hpf.process_block(output, hpf_inputs, params);
         }
+        ModuleState::DcBlock(state) => {
+            let (in_l, in_r) = if connections[0].is_empty() {
+                (None, None)
+            } else {
+                (Some(inputs[0].channel(0)), Some(inputs[0].channel(1)))
+            };
+            let params = DcBlockerParams {
+                cutoff: state.cutoff.slice(frames),
+            };
+            let (out_l, out_r) = outputs[0].channels_mut_2();
+            state.blocker.process_block(out_l, out_r, in_l, in_r, params);
+        }
         ModuleState::Mixer(state) => {
             // Stereo mixer: process L and R channels separately
             let a_conn = !connections[0].is_empty();
@@ -545,13 +628,52 @@ pub(crate) fn process_module(
             let in_a_l = if a_conn { Some(inputs[0].channel(0)) } else { None };
             let in_b_l = if b_conn { Some(inputs[1].channel(0)) } else { None };
             let out_l = outputs[0].channel_mut(0);
-            Crossfader::process_block(out_l, in_a_l, in_b_l, mix, mix_cv);
+            Crossfader::process_block(
+                out_l,
+                in_a_l,
+                in_b_l,
+                CrossfaderParams { mix, mix_cv, equal_power: state.equal_power },
+            );
 
             // Process right channel
             let in_a_r = if a_conn { Some(inputs[0].channel(1)) } else { None };
             let in_b_r = if b_conn { Some(inputs[1].channel(1)) } else { None };
             let out_r = outputs[0].channel_mut(1);
-            Crossfader::process_block(out_r, in_a_r, in_b_r, mix, mix_cv);
+            Crossfader::process_block(
+                out_r,
+                in_a_r,
+                in_b_r,
+                CrossfaderParams { mix, mix_cv, equal_power: state.equal_power },
+            );
+        }
+        ModuleState::Multiple => {
+            for output in outputs.iter_mut() {
+                mix_buffers(output, &inputs[0], 1.0);
+            }
+        }
+        ModuleState::Pan(state) => {
+            let input_connected = !connections[0].is_empty();
+            let pan_cv = if connections[1].is_empty() { None } else { Some(inputs[1].channel(0)) };
+            let (in_l, in_r) = if input_connected {
+                (Some(inputs[0].channel(0)), Some(inputs[0].channel(1)))
+            } else {
+                (None, None)
+            };
+            let (out_l, out_r) = outputs[0].channels_mut_2();
+            Pan::process_block(out_l, out_r, in_l, in_r, state.pan.slice(frames), pan_cv);
+        }
+        ModuleState::Width(state) => {
+            let (in_l, in_r) = if connections[0].is_empty() {
+                (None, None)
+            } else {
+                (Some(inputs[0].channel(0)), Some(inputs[0].channel(1)))
+            };
+            let params = StereoWidthParams {
+                width: state.amount.slice(frames),
+                mono_below: state.mono_below.slice(frames),
+            };
+            let (out_l, out_r) = outputs[0].channels_mut_2();
+            state.width.process_block(out_l, out_r, in_l, in_r, params);
         }
         ModuleState::Chorus(state) => {
             let input_connected = !connections[0].is_empty();
@@ -566,6 +688,7 @@ pub(crate) fn process_module(
                 depth_ms: state.depth.slice(frames),
                 delay_ms: state.delay.slice(frames),
                 mix: state.mix.slice(frames),
+                wet_only: state.wet_only.slice(frames),
                 feedback: state.feedback.slice(frames),
                 spread: state.spread.slice(frames),
             };
@@ -588,6 +711,7 @@ pub(crate) fn process_module(
                 depth_ms: state.depth.slice(frames),
                 delay_ms: state.delay.slice(frames),
                 mix: state.mix.slice(frames),
+                wet_only: state.wet_only.slice(frames),
                 spread: state.spread.slice(frames),
             };
             let ensemble_inputs = EnsembleInputs { input_l, input_r };
@@ -657,6 +781,7 @@ pub(crate) fn process_module(
                 time_ms: state.time.slice(frames),
                 feedback: state.feedback.slice(frames),
                 mix: state.mix.slice(frames),
+                wet_only: state.wet_only.slice(frames),
                 tone: state.tone.slice(frames),
                 ping_pong: state.ping_pong.slice(frames),
             };
@@ -724,6 +849,8 @@ pub(crate) fn process_module(
                 tone: state.tone.slice(frames),
                 mix: state.mix.slice(frames),
                 drive: state.drive.slice(frames),
+                diffusion: state.diffusion.slice(frames),
+                spring_count: state.spring_count.slice(frames),
             };
             let spring_inputs = SpringReverbInputs { input_l, input_r };
             let (left, right) = outputs[0].channels.split_at_mut(1);
@@ -744,6 +871,7 @@ pub(crate) fn process_module(
                 damp: state.damp.slice(frames),
                 pre_delay: state.pre_delay.slice(frames),
                 mix: state.mix.slice(frames),
+                wet_only: state.wet_only.slice(frames),
             };
             let reverb_inputs = ReverbInputs { input_l, input_r };
             let (left, right) = outputs[0].channels.split_at_mut(1);
@@ -764,8 +892,13 @@ pub(crate) fn process_module(
                 depth: state.depth.slice(frames),
                 feedback: state.feedback.slice(frames),
                 mix: state.mix.slice(frames),
+                wet_only: state.wet_only.slice(frames),
+                stages: state.stages.slice(frames),
+                stereo: state.stereo.slice(frames),
+                center: state.center.slice(frames),
             };
-            let phaser_inputs = PhaserInputs { input_l, input_r };
+            let modulation = if connections[1].is_empty() { None } else { Some(inputs[1].channel(0)) };
+            let phaser_inputs = PhaserInputs { input_l, input_r, modulation };
             let (left, right) = outputs[0].channels.split_at_mut(1);
             let out_l = &mut left[0];
             let out_r = &mut right[0];
@@ -773,36 +906,53 @@ pub(crate) fn process_module(
         }
         ModuleState::Distortion(state) => {
             let input_connected = !connections[0].is_empty();
-            let input = if input_connected { Some(inputs[0].channel(0)) } else { None };
-            let params = DistortionParams {
-                drive: state.drive.slice(frames),
-                tone: state.tone.slice(frames),
-                mix: state.mix.slice(frames),
-                mode: state.mode.slice(frames),
-            };
-            let output = outputs[0].channel_mut(0);
-            Distortion::process_block(output, input, params);
+            for channel in 0..2 {
+                let input = if input_connected { Some(inputs[0].channel(channel)) } else { None };
+                let params = DistortionParams {
+                    drive: state.drive.slice(frames),
+                    tone: state.tone.slice(frames),
+                    mix: state.mix.slice(frames),
+                    mode: state.mode.slice(frames),
+                    bias: state.bias.slice(frames),
+                };
+                Distortion::process_block(outputs[0].channel_mut(channel), input, params);
+            }
         }
         ModuleState::Wavefolder(state) => {
             let input_connected = !connections[0].is_empty();
-            let input = if input_connected { Some(inputs[0].channel(0)) } else { None };
-            let params = WavefolderParams {
-                drive: state.drive.slice(frames),
-                fold: state.fold.slice(frames),
-                bias: state.bias.slice(frames),
+            for channel in 0..2 {
+                let input = if input_connected { Some(inputs[0].channel(channel)) } else { None };
+                let params = WavefolderParams {
+                    drive: state.drive.slice(frames),
+                    fold: state.fold.slice(frames),
+                    bias: state.bias.slice(frames),
+                    mix: state.mix.slice(frames),
+                };
+                Wavefolder::process_block(outputs[0].channel_mut(channel), input, params);
+            }
+        }
+        ModuleState::BitCrusher(state) => {
+            let input = if connections[0].is_empty() { None } else { Some(inputs[0].channel(0)) };
+            let params = BitCrusherParams {
+                bits: state.bits.slice(frames),
+                downsample: state.downsample.slice(frames),
                 mix: state.mix.slice(frames),
+                antialias: state.antialias.slice(frames),
+                dither: state.dither.slice(frames),
             };
             let output = outputs[0].channel_mut(0);
-            Wavefolder::process_block(output, input, params);
+            state.crusher.process_block(output, input, params);
         }
         ModuleState::Supersaw(state) => {
             let pitch = if connections[0].is_empty() { None } else { Some(inputs[0].channel(0)) };
+            let gate = if connections[1].is_empty() { None } else { Some(inputs[1].channel(0)) };
             let params = SupersawParams {
                 base_freq: state.base_freq.slice(frames),
                 detune: state.detune.slice(frames),
                 mix: state.mix.slice(frames),
+                phase_spread: state.phase_spread.slice(frames),
             };
-            let supersaw_inputs = SupersawInputs { pitch };
+            let supersaw_inputs = SupersawInputs { pitch, gate };
             let output = outputs[0].channel_mut(0);
             state.supersaw.process_block(output, supersaw_inputs, params);
         }
@@ -856,20 +1006,28 @@ pub(crate) fn process_module(
             let (cv_group, rest) = outputs.split_at_mut(1);
             let (vel_group, rest) = rest.split_at_mut(1);
             let (gate_group, rest) = rest.split_at_mut(1);
+            let (sync_group, pan_group) = rest.split_at_mut(1);
             let cv_out = cv_group[0].channel_mut(0);
             let vel_out = vel_group[0].channel_mut(0);
             let gate_out = gate_group[0].channel_mut(0);
-            let sync_out = rest[0].channel_mut(0);
+            let sync_out = sync_group[0].channel_mut(0);
+            let pan_out = pan_group[0].channel_mut(0);
+            // Per-voice constants; detune in cents to V/oct.
+            let [detune_offset, pan_offset] = state.voice_offsets;
+            let detune = detune_offset * state.voice_detune.value() / 1200.0;
+            let pan = pan_offset * state.voice_spread.value().clamp(0.0, 1.0);
             for i in 0..frames {
-                if state.cv_remaining > 0 {
-                    state.cv += state.cv_step;
-                    state.cv_remaining -= 1;
-                }
+                state.step_cv();
                 if state.velocity_remaining > 0 {
-                    state.velocity += state.velocity_step;
                     state.velocity_remaining -= 1;
+                    state.velocity = if state.velocity_remaining == 0 {
+                        state.velocity_target
+                    } else {
+                        state.velocity + state.velocity_step
+                    };
                 }
-                cv_out[i] = state.cv;
+                cv_out[i] = state.cv + detune;
+                pan_out[i] = pan;
                 vel_out[i] = state.velocity;
                 if state.retrigger_samples > 0 {
                     gate_out[i] = 0.0;
@@ -902,19 +1060,12 @@ pub(crate) fn process_module(
                 mix_buffers(out_b, &inputs[1], 1.0);
             }
         }
-        ModuleState::Mario(state) => {
-            for channel in 0..MARIO_CHANNELS {
-                let cv_value = state.mario.cv(channel);
-                let gate_value = state.mario.gate(channel);
-                let cv_idx = channel * 2;
-                let gate_idx = channel * 2 + 1;
-                let (left, right) = outputs.split_at_mut(gate_idx);
-                let cv_out = left[cv_idx].channel_mut(0);
-                let gate_out = right[0].channel_mut(0);
-                for i in 0..frames {
-                    cv_out[i] = cv_value;
-                    gate_out[i] = gate_value;
-                }
+        ModuleState::CvBank(state) => {
+            for (channel, pair) in outputs.chunks_exact_mut(2).enumerate() {
+                let (cv_group, gate_group) = pair.split_at_mut(1);
+                let cv_out = cv_group[0].channel_mut(0);
+                let gate_out = gate_group[0].channel_mut(0);
+                state.bank.fill_channel(channel, &mut cv_out[..frames], &mut gate_out[..frames]);
             }
         }
         ModuleState::Arpeggiator(state) => {
@@ -956,11 +1107,13 @@ pub(crate) fn process_module(
             let cv_offset = if connections[2].is_empty() { None } else { Some(inputs[2].channel(0)) };
             let (cv_group, rest) = outputs.split_at_mut(1);
             let (gate_group, rest2) = rest.split_at_mut(1);
-            let (vel_group, step_group) = rest2.split_at_mut(1);
+            let (vel_group, rest3) = rest2.split_at_mut(1);
+            let (step_group, slide_group) = rest3.split_at_mut(1);
             let cv_out = cv_group[0].channel_mut(0);
             let gate_out = gate_group[0].channel_mut(0);
             let velocity_out = vel_group[0].channel_mut(0);
             let step_out = step_group[0].channel_mut(0);
+            let slide_out = slide_group[0].channel_mut(0);
             let seq_inputs = StepSequencerInputs { clock, reset, cv_offset };
             let params = StepSequencerParams {
                 enabled: state.enabled.slice(frames),
@@ -972,7 +1125,7 @@ pub(crate) fn process_module(
                 length: state.length.slice(frames),
                 direction: state.direction.slice(frames),
             };
-            let seq_outputs = StepSequencerOutputs { cv_out, gate_out, velocity_out, step_out };
+            let seq_outputs = StepSequencerOutputs { cv_out, gate_out, velocity_out, step_out, slide_out };
             state.seq.process_block(seq_outputs, seq_inputs, params);
         }
         ModuleState::Tb303(state) => {
@@ -980,10 +1133,12 @@ pub(crate) fn process_module(
             let gate = if connections[1].is_empty() { None } else { Some(inputs[1].channel(0)) };
             let velocity = if connections[2].is_empty() { None } else { Some(inputs[2].channel(0)) };
             let cutoff_cv = if connections[3].is_empty() { None } else { Some(inputs[3].channel(0)) };
+            let accent = if connections[4].is_empty() { None } else { Some(inputs[4].channel(0)) };
+            let slide = if connections[5].is_empty() { None } else { Some(inputs[5].channel(0)) };
             let (audio_group, env_group) = outputs.split_at_mut(1);
             let audio = audio_group[0].channel_mut(0);
             let env_out = env_group[0].channel_mut(0);
-            let tb_inputs = Tb303Inputs { pitch, gate, velocity, cutoff_cv };
+            let tb_inputs = Tb303Inputs { pitch, gate, velocity, accent, slide, cutoff_cv };
             let params = Tb303Params {
                 waveform: state.waveform.slice(frames),
                 cutoff: state.cutoff.slice(frames),
@@ -1254,6 +1409,7 @@ pub(crate) fn process_module(
             let seq_params = MidiFileSequencerParams {
                 enabled: state.enabled.slice(safe_frames),
                 tempo: state.tempo.slice(safe_frames),
+                tempo_scale: state.tempo_scale.slice(safe_frames),
                 gate_length: state.gate_length.slice(safe_frames),
                 loop_enabled: state.loop_enabled.slice(safe_frames),
                 mute: [
@@ -1381,6 +1537,12 @@ pub(crate) fn process_module(
             outputs[2].channel_mut(0)[..safe_frames].copy_from_slice(&buf_run[..safe_frames]);
             outputs[3].channel_mut(0)[..safe_frames].copy_from_slice(&buf_bar[..safe_frames]);
         }
+        ModuleState::ClockDiv(state) => {
+            let clock = if !connections[0].is_empty() { Some(inputs[0].channel(0)) } else { None };
+            let reset = if !connections[1].is_empty() { Some(inputs[1].channel(0)) } else { None };
+            let params = ClockDividerParams { ratio: state.ratio.slice(frames) };
+            state.divider.process_block(outputs[0].channel_mut(0), ClockDividerInputs { clock, reset }, params);
+        }
         ModuleState::Euclidean(state) => {
             let clock = if !connections[0].is_empty() { Some(inputs[0].channel(0)) } else { None };
             let reset = if connections.len() > 1 && !connections[1].is_empty() {
@@ -1472,44 +1634,29 @@ pub(crate) fn process_module(
                 None
             };
 
-            // Get param slices
-            let algorithm = state.algorithm.slice(frames);
-            let feedback = state.feedback.slice(frames);
-            let brightness = state.brightness.slice(frames);
-            let master = state.master.slice(frames);
-
-            // Operator params
-            let op1_ratio = state.op1_ratio.slice(frames);
-            let op1_level = state.op1_level.slice(frames);
-            let op1_detune = state.op1_detune.slice(frames);
-            let op1_attack = state.op1_attack.slice(frames);
-            let op1_decay = state.op1_decay.slice(frames);
-            let op1_sustain = state.op1_sustain.slice(frames);
-            let op1_release = state.op1_release.slice(frames);
-
-            let op2_ratio = state.op2_ratio.slice(frames);
-            let op2_level = state.op2_level.slice(frames);
-            let op2_detune = state.op2_detune.slice(frames);
-            let op2_attack = state.op2_attack.slice(frames);
-            let op2_decay = state.op2_decay.slice(frames);
-            let op2_sustain = state.op2_sustain.slice(frames);
-            let op2_release = state.op2_release.slice(frames);
-
-            let op3_ratio = state.op3_ratio.slice(frames);
-            let op3_level = state.op3_level.slice(frames);
-            let op3_detune = state.op3_detune.slice(frames);
-            let op3_attack = state.op3_attack.slice(frames);
-            let op3_decay = state.op3_decay.slice(frames);
-            let op3_sustain = state.op3_sustain.slice(frames);
-            let op3_release = state.op3_release.slice(frames);
-
-            let op4_ratio = state.op4_ratio.slice(frames);
-            let op4_level = state.op4_level.slice(frames);
-            let op4_detune = state.op4_detune.slice(frames);
-            let op4_attack = state.op4_attack.slice(frames);
-            let op4_decay = state.op4_decay.slice(frames);
-            let op4_sustain = state.op4_sustain.slice(frames);
-            let op4_release = state.op4_release.slice(frames);
+            // ParamBuffers hold one value per block, so build the core
+            // params once instead of per sample.
+            let params = FmMatrixParams {
+                algorithm: state.algorithm.value().max(0.0) as usize,
+                feedback: state.feedback.value(),
+                brightness: state.brightness.value(),
+                master: state.master.value(),
+                ops: std::array::from_fn(|op| {
+                    let op = &state.ops[op];
+                    OpParams {
+                        ratio: op.ratio.value(),
+                        level: op.level.value(),
+                        detune: op.detune.value(),
+                        attack_ms: op.attack.value(),
+                        decay_ms: op.decay.value(),
+                        sustain: op.sustain.value(),
+                        release_ms: op.release.value(),
+                    }
+                }),
+                matrix: std::array::from_fn(|src| {
+                    std::array::from_fn(|dst| state.routes[src][dst].value().clamp(0.0, 1.0))
+                }),
+            };
 
             // Split outputs to avoid borrow conflicts
             let (audio_out, mod_outputs) = outputs.split_at_mut(1);
@@ -1525,51 +1672,6 @@ pub(crate) fn process_module(
                 let velocity = velocity_cv.map(|v| v[i]).unwrap_or(1.0);
                 let fm_ext = fm_in.map(|f| f[i]).unwrap_or(0.0);
 
-                let params = FmMatrixParams {
-                    algorithm: algorithm[i] as usize,
-                    feedback: feedback[i],
-                    brightness: brightness[i],
-                    master: master[i],
-                    ops: [
-                        OpParams {
-                            ratio: op1_ratio[i],
-                            level: op1_level[i],
-                            detune: op1_detune[i],
-                            attack_ms: op1_attack[i],
-                            decay_ms: op1_decay[i],
-                            sustain: op1_sustain[i],
-                            release_ms: op1_release[i],
-                        },
-                        OpParams {
-                            ratio: op2_ratio[i],
-                            level: op2_level[i],
-                            detune: op2_detune[i],
-                            attack_ms: op2_attack[i],
-                            decay_ms: op2_decay[i],
-                            sustain: op2_sustain[i],
-                            release_ms: op2_release[i],
-                        },
-                        OpParams {
-                            ratio: op3_ratio[i],
-                            level: op3_level[i],
-                            detune: op3_detune[i],
-                            attack_ms: op3_attack[i],
-                            decay_ms: op3_decay[i],
-                            sustain: op3_sustain[i],
-                            release_ms: op3_release[i],
-                        },
-                        OpParams {
-                            ratio: op4_ratio[i],
-                            level: op4_level[i],
-                            detune: op4_detune[i],
-                            attack_ms: op4_attack[i],
-                            decay_ms: op4_decay[i],
-                            sustain: op4_sustain[i],
-                            release_ms: op4_release[i],
-                        },
-                    ],
-                };
-
                 let sample = state.matrix.process_sample(freq_hz, gate, velocity, fm_ext, &params);
                 audio_buf.channel_mut(0)[i] = sample;
                 audio_buf.channel_mut(1)[i] = sample;
@@ -1827,13 +1929,16 @@ pub(crate) fn process_module(
                 None
             };
 
-            let turing_inputs = TuringInputs { clock, reset };
+            let probability_cv = if connections[2].is_empty() { None } else { Some(inputs[2].channel(0)) };
+
+            let turing_inputs = TuringInputs { clock, reset, probability_cv };
             let params = TuringParams {
                 probability: state.probability.slice(frames),
                 length: state.length.slice(frames),
                 range: state.range.slice(frames),
                 scale: state.scale.slice(frames),
                 root: state.root.slice(frames),
+                write: state.write.slice(frames),
             };
 
             let (cv_group, rest) = outputs.split_at_mut(1);
@@ -1923,6 +2028,19 @@ pub(crate) fn process_module(
             let (out_l, out_r) = outputs[0].channels_mut_2();
             state.cloud.process_block(out_l, out_r, cloud_inputs, params);
         }
+        ModuleState::SamplePlayer(state) => {
+            // Input 0: gate (restart), Input 1: pitch CV
+            let gate = if connections[0].is_empty() { None } else { Some(inputs[0].channel(0)) };
+            let pitch = if connections[1].is_empty() { None } else { Some(inputs[1].channel(0)) };
+            let params = SamplePlayerParams {
+                looping: state.looping.slice(frames),
+                start: state.start.slice(frames),
+                end: state.end.slice(frames),
+                gain: state.gain.slice(frames),
+            };
+            let (out_l, out_r) = outputs[0].channels_mut_2();
+            state.player.process_block(out_l, out_r, SamplePlayerInputs { gate, pitch }, params);
+        }
         ModuleState::SidPlayer(state) => {
             // Input 0: reset trigger (optional)
             let reset = if !connections[0].is_empty() {
@@ -2047,19 +2165,68 @@ pub(crate) fn process_module(
             } else {
                 None
             };
+            let sidechain_enabled = state.sidechain.value() >= 0.5;
+            let sidechain =
+                if connections[1].is_empty() || !sidechain_enabled { None } else { Some(inputs[1].channel(0)) };
+            let compressor_inputs = CompressorInputs { input_l, input_r, sidechain };
             let params = CompressorParams {
                 threshold: state.threshold.slice(frames),
                 ratio: state.ratio.slice(frames),
                 attack: state.attack.slice(frames),
                 release: state.release.slice(frames),
+                knee: state.knee.slice(frames),
                 makeup: state.makeup.slice(frames),
                 mix: state.mix.slice(frames),
+                detector: state.detector.slice(frames),
+            };
+            let (audio_out, gr_out) = outputs.split_at_mut(1);
+            let (out_l, out_r) = audio_out[0].channels_mut_2();
+            let gain_reduction = Some(gr_out[0].channel_mut(0));
+            state.compressor.process_block_stereo(out_l, out_r, gain_reduction, compressor_inputs, params);
+        }
+        ModuleState::Gate(state) => {
+            let input_connected = !connections[0].is_empty();
+            let input_l = if input_connected { Some(inputs[0].channel(0)) } else { None };
+            let input_r = if input_connected {
+                Some(if inputs[0].channel_count() == 1 { inputs[0].channel(0) } else { inputs[0].channel(1) })
+            } else {
+                None
+            };
+            let key = if connections[1].is_empty() { None } else { Some(inputs[1].channel(0)) };
+            let params = NoiseGateParams {
+                threshold: state.threshold.slice(frames),
+                attack: state.attack.slice(frames),
+                hold: state.hold.slice(frames),
+                release: state.release.slice(frames),
+                floor: state.floor.slice(frames),
             };
             let (out_l, out_r) = outputs[0].channels_mut_2();
-            state.compressor.process_block_stereo(out_l, out_r, input_l, input_r, params);
+            state.gate.process_block(out_l, out_r, NoiseGateInputs { input_l, input_r, key }, params);
         }
         ModuleState::Notes => {
             // UI-only module, no audio processing
         }
+        ModuleState::Send(state) => {
+            let level = state.level.slice(frames);
+            let (thru_group, send_group) = outputs.split_at_mut(1);
+            mix_buffers(&mut thru_group[0], &inputs[0], 1.0);
+            let send = &mut send_group[0];
+            mix_buffers(send, &inputs[0], 1.0);
+            for channel in 0..2 {
+                for (sample, gain) in send.channel_mut(channel).iter_mut().zip(level) {
+                    *sample *= gain;
+                }
+            }
+        }
+        ModuleState::Return(state) => {
+            let level = state.level.slice(frames);
+            let output = &mut outputs[0];
+            mix_buffers(output, &inputs[0], 1.0);
+            for channel in 0..2 {
+                for (sample, gain) in output.channel_mut(channel).iter_mut().zip(level) {
+                    *sample *= gain;
+                }
+            }
+        }
     }
 }
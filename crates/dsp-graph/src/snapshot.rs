@@ -0,0 +1,50 @@
+//! Parameter snapshots for A/B comparison.
+//!
+//! A snapshot holds the numeric parameter values of every module, keyed by
+//! module id and param name. The engine keeps a few snapshot slots that can
+//! be recalled or morphed between without rebuilding the graph; loading a
+//! new graph clears them.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Number of snapshot slots kept by [`GraphEngine`](crate::GraphEngine).
+pub const SNAPSHOT_SLOTS: usize = 8;
+
+/// Numeric parameter values of a patch: module id -> param -> value.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParamSnapshot {
+  modules: BTreeMap<String, BTreeMap<String, f32>>,
+}
+
+impl ParamSnapshot {
+  pub fn get(&self, module_id: &str, param: &str) -> Option<f32> {
+    self.modules.get(module_id)?.get(param).copied()
+  }
+
+  pub fn insert(&mut self, module_id: &str, param: &str, value: f32) {
+    self
+      .modules
+      .entry(module_id.to_string())
+      .or_default()
+      .insert(param.to_string(), value);
+  }
+
+  /// Every `(module_id, param, value)`, ordered by module then param.
+  pub fn iter(&self) -> impl Iterator<Item = (&str, &str, f32)> {
+    self.modules.iter().flat_map(|(module_id, params)| {
+      params
+        .iter()
+        .map(move |(param, &value)| (module_id.as_str(), param.as_str(), value))
+    })
+  }
+
+  /// Number of stored values.
+  pub fn len(&self) -> usize {
+    self.modules.values().map(BTreeMap::len).sum()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+}
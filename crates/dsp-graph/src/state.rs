@@ -1,10 +1,10 @@
 //! Module state definitions for all DSP modules.
 
 use dsp_core::{
-    Adsr, Arpeggiator, AyPlayer, Chaos, Choir, Chorus, Clap808, Clap909, Compressor, Cowbell808, Delay, DrumSequencer, Ensemble,
+    Adsr, Arpeggiator, AyPlayer, BitCrusher, Chaos, Choir, Chorus, Clap808, Clap909, ClockDivider, Compressor, Cowbell808, CvBank, DcBlocker, Delay, DrumSequencer, Ensemble, EnvFollower,
     EuclideanSequencer, FmMatrix, FmOperator, Granular, GranularDelay, HiHat808, HiHat909, Hpf, KarplusStrong,
-    Kick808, Kick909, Lfo, Mario, MasterClock, MidiFileSequencer, NesOsc, Noise, ParticleCloud, Phaser, PipeOrgan, PitchShifter,
-    Resonator, Reverb, Rimshot909, SampleHold, Shepard, SidPlayer, SlewLimiter, Snare808, Snare909, SnesOsc, SpectralSwarm, SpringReverb,
+    Kick808, Kick909, Lfo, MasterClock, MidiFileSequencer, NesOsc, Noise, NoiseGate, ParticleCloud, PeakMeter, Phaser, PipeOrgan, PitchShifter,
+    Resonator, Reverb, RingMod, Rimshot909, SampleHold, SamplePlayer, Shepard, SidPlayer, SlewLimiter, Snare808, Snare909, SnesOsc, SpectralSwarm, SpringReverb, StereoWidth,
     StepSequencer, Supersaw, TapeDelay, Tb303, Tom808, Tom909, TuringMachine, Vcf, Vco, Vocoder, Wavetable,
 };
 
@@ -25,6 +25,11 @@ pub struct VcoState {
     pub detune: ParamBuffer,
     pub sub_mix: ParamBuffer,
     pub sub_oct: ParamBuffer,
+    pub stereo_spread: ParamBuffer,
+    /// Crossfade neighbouring waveforms on a fractional `type`.
+    pub wave_morph: ParamBuffer,
+    /// Render at 2x and decimate, for less aliasing on high notes.
+    pub oversample: ParamBuffer,
 }
 
 pub struct SupersawState {
@@ -32,6 +37,7 @@ pub struct SupersawState {
     pub base_freq: ParamBuffer,
     pub detune: ParamBuffer,
     pub mix: ParamBuffer,
+    pub phase_spread: ParamBuffer,
 }
 
 pub struct KarplusState {
@@ -67,8 +73,12 @@ pub struct SnesOscState {
 
 pub struct NoiseState {
     pub noise: Noise,
+    /// Base seed; each voice adds `voice_offset` so poly voices differ.
+    pub seed: ParamBuffer,
+    pub voice_offset: u32,
     pub level: ParamBuffer,
     pub noise_type: ParamBuffer,
+    pub rate_divide: ParamBuffer,
     pub stereo: ParamBuffer,
     pub pan: ParamBuffer,
 }
@@ -96,6 +106,17 @@ pub struct FmOpState {
     pub release: ParamBuffer,
 }
 
+/// One operator of an FM Matrix (times in ms, like the core `OpParams`).
+pub struct FmMatrixOpState {
+    pub ratio: ParamBuffer,
+    pub level: ParamBuffer,
+    pub detune: ParamBuffer,
+    pub attack: ParamBuffer,
+    pub decay: ParamBuffer,
+    pub sustain: ParamBuffer,
+    pub release: ParamBuffer,
+}
+
 pub struct FmMatrixState {
     pub matrix: FmMatrix,
     // Global params
@@ -103,38 +124,9 @@ pub struct FmMatrixState {
     pub feedback: ParamBuffer,
     pub brightness: ParamBuffer,
     pub master: ParamBuffer,
-    // Operator 1
-    pub op1_ratio: ParamBuffer,
-    pub op1_level: ParamBuffer,
-    pub op1_detune: ParamBuffer,
-    pub op1_attack: ParamBuffer,
-    pub op1_decay: ParamBuffer,
-    pub op1_sustain: ParamBuffer,
-    pub op1_release: ParamBuffer,
-    // Operator 2
-    pub op2_ratio: ParamBuffer,
-    pub op2_level: ParamBuffer,
-    pub op2_detune: ParamBuffer,
-    pub op2_attack: ParamBuffer,
-    pub op2_decay: ParamBuffer,
-    pub op2_sustain: ParamBuffer,
-    pub op2_release: ParamBuffer,
-    // Operator 3
-    pub op3_ratio: ParamBuffer,
-    pub op3_level: ParamBuffer,
-    pub op3_detune: ParamBuffer,
-    pub op3_attack: ParamBuffer,
-    pub op3_decay: ParamBuffer,
-    pub op3_sustain: ParamBuffer,
-    pub op3_release: ParamBuffer,
-    // Operator 4
-    pub op4_ratio: ParamBuffer,
-    pub op4_level: ParamBuffer,
-    pub op4_detune: ParamBuffer,
-    pub op4_attack: ParamBuffer,
-    pub op4_decay: ParamBuffer,
-    pub op4_sustain: ParamBuffer,
-    pub op4_release: ParamBuffer,
+    pub ops: [FmMatrixOpState; 4],
+    /// `routes[src][dst]`: extra modulation depth from operator src to dst.
+    pub routes: [[ParamBuffer; 4]; 4],
 }
 
 pub struct ShepardState {
@@ -258,6 +250,15 @@ pub struct ParticleCloudState {
     pub osc_shape: ParamBuffer,
 }
 
+/// Sample Player: the loaded sample lives in `player` and survives resets
+pub struct SamplePlayerState {
+    pub player: SamplePlayer,
+    pub looping: ParamBuffer,
+    pub start: ParamBuffer,
+    pub end: ParamBuffer,
+    pub gain: ParamBuffer,
+}
+
 // =============================================================================
 // Filter States
 // =============================================================================
@@ -280,12 +281,20 @@ pub struct HpfState {
     pub cutoff: ParamBuffer,
 }
 
+/// DC blocker: one-pole high-pass around 20 Hz
+pub struct DcBlockState {
+    pub blocker: DcBlocker,
+    pub cutoff: ParamBuffer,
+}
+
 // =============================================================================
 // Amplifier / Mixer States
 // =============================================================================
 
 pub struct GainState {
     pub gain: ParamBuffer,
+    /// CV drives the gain as `1 - cv` (ducking) when high.
+    pub invert: ParamBuffer,
 }
 
 pub struct MixerState {
@@ -316,10 +325,29 @@ pub struct Mixer8State {
 /// Crossfader: mix between two audio inputs (0 = 100% A, 1 = 100% B)
 pub struct CrossfaderState {
     pub mix: ParamBuffer,
+    /// Equal-power law instead of linear gains
+    pub equal_power: bool,
+}
+
+/// Pan: equal-power position (-1 = left, 1 = right)
+pub struct PanState {
+    pub pan: ParamBuffer,
+}
+
+/// Stereo width: mid/side side gain (0 = mono, 1 = unchanged, 2 = wide)
+pub struct WidthState {
+    pub width: StereoWidth,
+    pub amount: ParamBuffer,
+    pub mono_below: ParamBuffer,
 }
 
 pub struct RingModState {
+    pub ring: RingMod,
     pub level: ParamBuffer,
+    pub carrier_freq: ParamBuffer,
+    pub carrier_shape: ParamBuffer,
+    pub internal_carrier: ParamBuffer,
+    pub mix: ParamBuffer,
 }
 
 // =============================================================================
@@ -348,11 +376,25 @@ pub struct ModRouterState {
     pub depth_pwm: ParamBuffer,
     pub depth_vcf: ParamBuffer,
     pub depth_vca: ParamBuffer,
+    pub depth_aux: ParamBuffer,
+    pub offset_pitch: ParamBuffer,
+    pub offset_pwm: ParamBuffer,
+    pub offset_vcf: ParamBuffer,
+    pub offset_vca: ParamBuffer,
+    pub offset_aux: ParamBuffer,
+}
+
+/// Attenuverter: cv-out = cv-in * scale + offset
+pub struct AttenuverterState {
+    pub scale: ParamBuffer,
+    pub offset: ParamBuffer,
 }
 
 pub struct SampleHoldState {
     pub sample_hold: SampleHold,
     pub mode: ParamBuffer,
+    pub glide: ParamBuffer,
+    pub noise_level: ParamBuffer,
 }
 
 pub struct SlewState {
@@ -361,11 +403,29 @@ pub struct SlewState {
     pub fall: ParamBuffer,
 }
 
+pub struct EnvFollowerState {
+    pub follower: EnvFollower,
+    pub attack: ParamBuffer,
+    pub release: ParamBuffer,
+    pub gain: ParamBuffer,
+}
+
 pub struct QuantizerState {
     pub root: ParamBuffer,
     pub scale: ParamBuffer,
 }
 
+pub struct LogicState {
+    pub threshold: ParamBuffer,
+}
+
+pub struct PeakMeterState {
+    pub meter: PeakMeter,
+    pub attack: ParamBuffer,
+    pub release: ParamBuffer,
+    pub rms_window: ParamBuffer,
+}
+
 pub struct ChaosState {
     pub chaos: Chaos,
     pub speed: ParamBuffer,
@@ -388,6 +448,7 @@ pub struct ChorusState {
     pub mix: ParamBuffer,
     pub feedback: ParamBuffer,
     pub spread: ParamBuffer,
+    pub wet_only: ParamBuffer,
 }
 
 pub struct EnsembleState {
@@ -397,6 +458,7 @@ pub struct EnsembleState {
     pub delay: ParamBuffer,
     pub mix: ParamBuffer,
     pub spread: ParamBuffer,
+    pub wet_only: ParamBuffer,
 }
 
 pub struct ChoirState {
@@ -429,6 +491,7 @@ pub struct DelayState {
     pub mix: ParamBuffer,
     pub tone: ParamBuffer,
     pub ping_pong: ParamBuffer,
+    pub wet_only: ParamBuffer,
 }
 
 pub struct GranularDelayState {
@@ -458,6 +521,8 @@ pub struct SpringReverbState {
     pub tone: ParamBuffer,
     pub mix: ParamBuffer,
     pub drive: ParamBuffer,
+    pub diffusion: ParamBuffer,
+    pub spring_count: ParamBuffer,
 }
 
 pub struct ReverbState {
@@ -466,6 +531,7 @@ pub struct ReverbState {
     pub damp: ParamBuffer,
     pub pre_delay: ParamBuffer,
     pub mix: ParamBuffer,
+    pub wet_only: ParamBuffer,
 }
 
 pub struct PhaserState {
@@ -474,6 +540,10 @@ pub struct PhaserState {
     pub depth: ParamBuffer,
     pub feedback: ParamBuffer,
     pub mix: ParamBuffer,
+    pub stages: ParamBuffer,
+    pub stereo: ParamBuffer,
+    pub center: ParamBuffer,
+    pub wet_only: ParamBuffer,
 }
 
 pub struct DistortionState {
@@ -481,6 +551,7 @@ pub struct DistortionState {
     pub tone: ParamBuffer,
     pub mix: ParamBuffer,
     pub mode: ParamBuffer,
+    pub bias: ParamBuffer,
 }
 
 pub struct WavefolderState {
@@ -490,6 +561,15 @@ pub struct WavefolderState {
     pub mix: ParamBuffer,
 }
 
+pub struct BitCrusherState {
+    pub crusher: BitCrusher,
+    pub bits: ParamBuffer,
+    pub downsample: ParamBuffer,
+    pub mix: ParamBuffer,
+    pub antialias: ParamBuffer,
+    pub dither: ParamBuffer,
+}
+
 pub struct PitchShifterState {
     pub shifter: PitchShifter,
     pub pitch: ParamBuffer,
@@ -504,8 +584,22 @@ pub struct CompressorState {
     pub ratio: ParamBuffer,
     pub attack: ParamBuffer,
     pub release: ParamBuffer,
+    pub knee: ParamBuffer,
     pub makeup: ParamBuffer,
     pub mix: ParamBuffer,
+    pub detector: ParamBuffer,
+    /// Detect on the `sidechain` input when it is connected (1) or always on
+    /// `in` (0), to audition the patch without unplugging the key.
+    pub sidechain: ParamBuffer,
+}
+
+pub struct GateState {
+    pub gate: NoiseGate,
+    pub threshold: ParamBuffer,
+    pub attack: ParamBuffer,
+    pub hold: ParamBuffer,
+    pub release: ParamBuffer,
+    pub floor: ParamBuffer,
 }
 
 // =============================================================================
@@ -520,6 +614,12 @@ pub struct ClockState {
     pub swing: ParamBuffer,
 }
 
+pub struct ClockDivState {
+    pub divider: ClockDivider,
+    /// Index into `CLOCK_DIV_RATIOS`
+    pub ratio: ParamBuffer,
+}
+
 pub struct ArpeggiatorState {
     pub arp: Arpeggiator,
     pub enabled: ParamBuffer,
@@ -576,8 +676,8 @@ pub struct EuclideanState {
     pub swing: ParamBuffer,
 }
 
-pub struct MarioState {
-    pub mario: Mario,
+pub struct CvBankState {
+    pub bank: CvBank,
 }
 
 pub struct MidiFileSequencerState {
@@ -585,6 +685,7 @@ pub struct MidiFileSequencerState {
     pub voice_index: usize,
     pub enabled: ParamBuffer,
     pub tempo: ParamBuffer,
+    pub tempo_scale: ParamBuffer,
     pub gate_length: ParamBuffer,
     pub loop_enabled: ParamBuffer,
     pub mute1: ParamBuffer,
@@ -604,6 +705,8 @@ pub struct TuringState {
     pub range: ParamBuffer,
     pub scale: ParamBuffer,
     pub root: ParamBuffer,
+    pub write: ParamBuffer,
+    pub seed: ParamBuffer,
 }
 
 pub struct SidPlayerState {
@@ -720,6 +823,20 @@ pub struct Tom808State {
 
 pub struct OutputState {
     pub level: ParamBuffer,
+    /// Stereo output pair this Output feeds (0 = main L/R).
+    pub bus: usize,
+    /// How far poly voices are panned apart (0 = summed to the center).
+    pub voice_spread: f32,
+}
+
+/// Aux send: passes its input through and feeds `level` of it to a named bus.
+pub struct SendState {
+    pub level: ParamBuffer,
+}
+
+/// Aux return: plays the sum of every Send on its bus.
+pub struct ReturnState {
+    pub level: ParamBuffer,
 }
 
 pub struct LabState {
@@ -730,11 +847,55 @@ pub struct AudioInState {
     pub gain: ParamBuffer,
 }
 
+/// Frequency of MIDI note 60 at 0 V, the pitch VCOs are tuned against.
+pub const DEFAULT_REF_FREQ: f32 = 261.63;
+
+/// Note to V/Oct mapping of a Control module.
+///
+/// Notes are counted from MIDI note 60, which sits at `ref_freq`. Without a
+/// table the octave is split in `edo` equal steps; a table lists the cents
+/// of scale degrees 1..n like a Scala file, its last entry being the period
+/// (1200 for octave-repeating scales).
+pub struct Tuning {
+    /// Frequency of note 60 in Hz (`refFreq`).
+    pub ref_freq: f32,
+    /// Equal divisions of the octave (`edo`).
+    pub edo: f32,
+    /// Cents of degrees 1..n (`tuning`); overrides `edo` when not empty.
+    pub table: Vec<f32>,
+}
+
+impl Tuning {
+    /// V/Oct CV of a MIDI note. 12-EDO at the default reference matches
+    /// `dsp_core::midi_note_to_cv`.
+    pub fn note_to_cv(&self, note: u8) -> f32 {
+        let steps = note as i32 - 60;
+        let offset = (self.ref_freq / DEFAULT_REF_FREQ).log2();
+        match self.table.last() {
+            Some(&period) => {
+                let degrees = self.table.len() as i32;
+                let degree = steps.rem_euclid(degrees) as usize;
+                let cents = if degree == 0 { 0.0 } else { self.table[degree - 1] };
+                offset + (steps.div_euclid(degrees) as f32 * period + cents) / 1200.0
+            }
+            None => offset + steps as f32 / self.edo,
+        }
+    }
+}
+
+/// Time constants an exponential glide covers, so it lands within 1% of the
+/// target before the final snap.
+const GLIDE_EXP_TIME_CONSTANTS: f32 = 5.0;
+
 pub struct ControlState {
     pub cv: f32,
     pub cv_target: f32,
+    /// Per-sample increment (linear glide) or approach coefficient
+    /// (exponential glide).
     pub cv_step: f32,
     pub cv_remaining: usize,
+    /// Curve of the glide in flight.
+    pub cv_exponential: bool,
     pub velocity: f32,
     pub velocity_target: f32,
     pub velocity_step: f32,
@@ -744,9 +905,62 @@ pub struct ControlState {
     pub retrigger_samples: usize,
     pub sync_remaining: usize,
     pub glide_seconds: f32,
+    /// Glide only while the gate is held (fingered portamento); otherwise
+    /// every new CV glides.
+    pub glide_legato: bool,
+    /// Glide like an analog RC lag (fast start, slow landing) instead of a
+    /// linear ramp. CV is V/oct, so a linear ramp is already even in semitones.
+    pub glide_exponential: bool,
+    /// Max per-voice detune in cents, scaled by `voice_offsets[0]`.
+    pub voice_detune: ParamBuffer,
+    /// Stereo spread of the voices (0-1), scaled by `voice_offsets[1]` on `pan-out`.
+    pub voice_spread: ParamBuffer,
+    /// Detune and pan offsets of this voice in [-1, 1], fixed at creation
+    /// from the voice index.
+    pub voice_offsets: [f32; 2],
+    /// Used by `GraphEngine::note_to_cv`.
+    pub tuning: Tuning,
     pub sample_rate: f32,
 }
 
+impl ControlState {
+    /// Advance the glide in flight by one sample; the last step lands
+    /// exactly on the target.
+    pub fn step_cv(&mut self) {
+        if self.cv_remaining == 0 {
+            return;
+        }
+        self.cv_remaining -= 1;
+        self.cv = if self.cv_remaining == 0 {
+            self.cv_target
+        } else if self.cv_exponential {
+            self.cv + (self.cv_target - self.cv) * self.cv_step
+        } else {
+            self.cv + self.cv_step
+        };
+    }
+
+    /// Move the CV to `value`, ramping over the glide time when glide applies.
+    pub fn set_cv(&mut self, value: f32) {
+        let gliding = self.glide_seconds > 0.0 && (!self.glide_legato || self.gate > 0.5);
+        if gliding {
+            let total = (self.glide_seconds * self.sample_rate).max(1.0);
+            self.cv_target = value;
+            self.cv_remaining = total as usize;
+            self.cv_exponential = self.glide_exponential;
+            self.cv_step = if self.glide_exponential {
+                1.0 - (-GLIDE_EXP_TIME_CONSTANTS / total).exp()
+            } else {
+                (self.cv_target - self.cv) / total
+            };
+        } else {
+            self.cv = value;
+            self.cv_target = value;
+            self.cv_remaining = 0;
+        }
+    }
+}
+
 // =============================================================================
 // Module State Enum
 // =============================================================================
@@ -770,10 +984,12 @@ pub enum ModuleState {
     Wavetable(WavetableState),
     Granular(GranularState),
     ParticleCloud(ParticleCloudState),
+    SamplePlayer(SamplePlayerState),
 
     // Filters
     Vcf(VcfState),
     Hpf(HpfState),
+    DcBlock(DcBlockState),
 
     // Amplifiers / Mixers
     Gain(GainState),
@@ -782,16 +998,24 @@ pub enum ModuleState {
     MixerWide(MixerWideState),
     Mixer8(Mixer8State),
     Crossfader(CrossfaderState),
+    /// Buffered multiple: copies its input to four outputs
+    Multiple,
+    Pan(PanState),
+    Width(WidthState),
     RingMod(RingModState),
 
     // Modulators
     Lfo(LfoState),
     Adsr(AdsrState),
     ModRouter(ModRouterState),
+    Attenuverter(AttenuverterState),
     SampleHold(SampleHoldState),
     Slew(SlewState),
+    EnvFollower(EnvFollowerState),
     Quantizer(QuantizerState),
     Chaos(ChaosState),
+    Logic(LogicState),
+    PeakMeter(PeakMeterState),
 
     // Effects
     Chorus(ChorusState),
@@ -806,16 +1030,19 @@ pub enum ModuleState {
     Phaser(PhaserState),
     Distortion(DistortionState),
     Wavefolder(WavefolderState),
+    BitCrusher(BitCrusherState),
     PitchShifter(PitchShifterState),
     Compressor(CompressorState),
+    Gate(GateState),
 
     // Sequencers
     Clock(ClockState),
+    ClockDiv(ClockDivState),
     Arpeggiator(ArpeggiatorState),
     StepSequencer(StepSequencerState),
     DrumSequencer(DrumSequencerState),
     Euclidean(EuclideanState),
-    Mario(MarioState),
+    CvBank(CvBankState),
     MidiFileSequencer(MidiFileSequencerState),
     TuringMachine(TuringState),
     SidPlayer(SidPlayerState),
@@ -844,4 +1071,17 @@ pub enum ModuleState {
     Control(ControlState),
     Scope,
     Notes,
+    Send(SendState),
+    Return(ReturnState),
+}
+
+impl ModuleState {
+    /// Delay in samples this module adds between its input and output
+    /// (lookahead, grain buffers). Zero for most modules.
+    pub fn latency(&self) -> usize {
+        match self {
+            ModuleState::PitchShifter(state) => state.shifter.latency_samples(state.grain.value()),
+            _ => 0,
+        }
+    }
 }
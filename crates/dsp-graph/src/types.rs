@@ -22,10 +22,12 @@ pub enum ModuleType {
     Wavetable,
     Granular,
     ParticleCloud,
+    SamplePlayer,
 
     // Filters
     Vcf,
     Hpf,
+    DcBlock,
 
     // Amplifiers / Mixers
     Gain,
@@ -34,16 +36,23 @@ pub enum ModuleType {
     MixerWide,
     Mixer8,
     Crossfader,
+    Multiple,
+    Pan,
+    Width,
     RingMod,
 
     // Modulators
     Lfo,
     Adsr,
     ModRouter,
+    Attenuverter,
     SampleHold,
     Slew,
+    EnvFollower,
     Quantizer,
     Chaos,
+    Logic,
+    PeakMeter,
 
     // Effects
     Chorus,
@@ -58,16 +67,19 @@ pub enum ModuleType {
     Phaser,
     Distortion,
     Wavefolder,
+    BitCrusher,
     PitchShifter,
     Compressor,
+    Gate,
 
     // Sequencers
     Clock,
+    ClockDiv,
     Arpeggiator,
     StepSequencer,
     DrumSequencer,
     Euclidean,
-    Mario,
+    CvBank,
     MidiFileSequencer,
     TuringMachine,
     SidPlayer,
@@ -96,6 +108,8 @@ pub enum ModuleType {
     AudioIn,
     Scope,
     Notes,
+    Send,
+    Return,
 }
 
 /// Port channel configuration.
@@ -109,12 +123,21 @@ pub struct ConnectionEdge {
     pub source_module: usize,
     pub source_port: usize,
     pub gain: f32,
+    /// Stereo position (-1 to 1) of a poly voice summed into an Output,
+    /// scaled by the Output's `voiceSpread`; 0 for every other cable.
+    pub pan: f32,
 }
 
 /// A tap source for audio monitoring.
 pub struct TapSource {
     pub module_index: usize,
-    pub input_port: usize,
+    /// Index of the tapped port among the module's inputs, or among its
+    /// outputs when `output` is set.
+    pub port: usize,
+    pub output: bool,
+    /// Planar channels the tap occupies in the render output (1 = mono
+    /// downmix, 2 = L/R of a stereo port).
+    pub channels: usize,
 }
 
 /// Parameter buffer for smooth parameter updates.
@@ -122,6 +145,12 @@ pub struct ParamBuffer {
     value: f32,
     buffer: Vec<Sample>,
     dirty: bool,
+    /// Samples a change takes to reach `value`; 0 jumps straight to it.
+    glide: usize,
+    /// Value of the last sample handed out while gliding.
+    current: f32,
+    step: f32,
+    remaining: usize,
 }
 
 impl ParamBuffer {
@@ -131,20 +160,58 @@ impl ParamBuffer {
             value,
             buffer: Vec::new(),
             dirty: true,
+            glide: 0,
+            current: value,
+            step: 0.0,
+            remaining: 0,
         }
     }
 
+    /// Ramp later changes linearly over `samples` instead of jumping.
+    pub fn with_glide(mut self, samples: usize) -> Self {
+        self.glide = samples;
+        self
+    }
+
+    /// Change the ramp length, e.g. after a sample rate change.
+    pub fn set_glide(&mut self, samples: usize) {
+        self.glide = samples;
+    }
+
     /// Set a new value (marks buffer as dirty if changed).
     pub fn set(&mut self, value: f32) {
         if value != self.value {
             self.value = value;
             self.dirty = true;
+            if self.glide > 0 {
+                self.step = (value - self.current) / self.glide as f32;
+                self.remaining = self.glide;
+            } else {
+                self.current = value;
+                self.remaining = 0;
+            }
         }
     }
 
+    /// Current parameter value (the target while gliding).
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
     /// Get a slice of the parameter value for the given number of frames.
     pub fn slice(&mut self, frames: usize) -> &[Sample] {
-        if self.buffer.len() != frames || self.dirty {
+        if self.remaining > 0 {
+            self.buffer.resize(frames, self.value);
+            for sample in &mut self.buffer {
+                if self.remaining > 0 {
+                    self.remaining -= 1;
+                    self.current = if self.remaining == 0 { self.value } else { self.current + self.step };
+                }
+                *sample = self.current;
+            }
+            // Refill with the settled value next block
+            self.dirty = true;
+        } else if self.buffer.len() != frames || self.dirty {
             self.buffer.resize(frames, self.value);
             if frames > 0 {
                 self.buffer.fill(self.value);
@@ -22,8 +22,14 @@ fn shm_name(instance_id: Option<&str>) -> String {
 /// Magic number to verify shared memory is valid
 pub const MAGIC: u32 = 0x4E4F4F42; // "NOOB"
 
-/// Version of the IPC protocol
-pub const VERSION: u32 = 3;
+/// Version of the IPC protocol. Both sides must agree on it; bump it for
+/// any change that moves or resizes fields of [`SharedMemoryLayout`].
+pub const VERSION: u32 = 7;
+
+/// Minor version of the IPC protocol, for changes that keep the layout
+/// (new command types, new meanings for reserved fields). Peers with
+/// different minor versions still connect as long as the layout sizes match.
+pub const MINOR_VERSION: u32 = 1;
 
 /// Maximum voices supported
 pub const MAX_VOICES: usize = 16;
@@ -34,6 +40,27 @@ pub const CMD_RING_SIZE: usize = 256;
 /// Size of the graph JSON buffer
 pub const GRAPH_BUFFER_SIZE: usize = 64 * 1024; // 64KB for graph JSON
 
+/// Bytes of module data carried by one [`CommandType::SetModuleData`] chunk.
+/// A quarter of the string buffer, so the chunks still unread by the VST
+/// never get overwritten (see [`TauriBridge::set_module_data`]).
+pub const MODULE_DATA_CHUNK: usize = 1024;
+
+/// `CommandSlot::flags` bit of the first chunk of a module data transfer
+pub const MODULE_DATA_FIRST: u8 = 1;
+
+/// `CommandSlot::flags` bit of the last chunk of a module data transfer
+pub const MODULE_DATA_LAST: u8 = 2;
+
+/// How long [`TauriBridge::set_module_data`] waits for the VST to take each
+/// chunk before giving up
+const MODULE_DATA_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Size of the module fault report buffer
+pub const FAULT_BUFFER_SIZE: usize = 1024;
+
+/// Size of the engine event ring (VST -> UI)
+pub const EVENT_RING_SIZE: usize = 512;
+
 // ============================================================================
 // Shared Data Structures (raw repr(C) for memory mapping)
 // ============================================================================
@@ -45,7 +72,8 @@ pub struct SharedHeader {
     pub version: u32,
     /// Flags: bit 0 = VST connected, bit 1 = Tauri connected
     pub flags: AtomicU32,
-    pub _pad0: u32,
+    /// [`MINOR_VERSION`] of the side that created the segment
+    pub minor_version: u32,
     /// Monotonic counter incremented by Tauri when params change
     pub param_version: AtomicU64,
     /// Monotonic counter incremented by VST when params change
@@ -56,7 +84,9 @@ pub struct SharedHeader {
     pub vst_graph_version: AtomicU64,
     /// Sample rate set by VST
     pub sample_rate: AtomicU32,
-    pub _pad1: u32,
+    /// [`SHARED_MEM_SIZE`] of the side that created the segment (0 when
+    /// it predates minor versions)
+    pub layout_size: u32,
 }
 
 /// Synth parameters (shared between VST and Tauri)
@@ -82,6 +112,20 @@ pub struct VoiceState {
     pub _padding: [u8; 3],
 }
 
+impl VoiceState {
+    /// `note` value of a voice that has not played yet.
+    pub const NO_NOTE: u8 = 255;
+
+    /// A silent voice with no note.
+    pub const IDLE: VoiceState = VoiceState {
+        cv: 0.0,
+        gate: 0.0,
+        velocity: 0.0,
+        note: Self::NO_NOTE,
+        _padding: [0; 3],
+    };
+}
+
 /// Command types
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 #[repr(u8)]
@@ -103,6 +147,16 @@ pub enum CommandType {
     SetVoiceCv = 7,
     /// Set voice velocity
     SetVoiceVelocity = 8,
+    /// Clear all DSP state (tails, phases, envelopes) keeping the graph
+    Reset = 9,
+    /// MIDI channel map JSON in the string buffer (`extra` = packed offset/length)
+    SetChannelMap = 10,
+    /// Set the glide time of a voice in seconds (10 is taken by `SetChannelMap`)
+    SetGlide = 11,
+    /// One chunk of a string param too large for the string buffer
+    /// (`flags` = [`MODULE_DATA_FIRST`] / [`MODULE_DATA_LAST`], `extra` =
+    /// packed offset/length), reassembled by [`ModuleDataAssembler`]
+    SetModuleData = 12,
 }
 
 impl From<u8> for CommandType {
@@ -116,6 +170,10 @@ impl From<u8> for CommandType {
             6 => CommandType::ReleaseGate,
             7 => CommandType::SetVoiceCv,
             8 => CommandType::SetVoiceVelocity,
+            9 => CommandType::Reset,
+            10 => CommandType::SetChannelMap,
+            11 => CommandType::SetGlide,
+            12 => CommandType::SetModuleData,
             _ => CommandType::None,
         }
     }
@@ -141,6 +199,9 @@ pub struct CommandSlot {
     pub param_id: u32,
     /// Extra data
     pub extra: u32,
+    /// Sequence stamp, written by the ring itself (see [`slot_seq`]); even
+    /// while the slot is being written or once it has been consumed
+    pub seq: u32,
 }
 
 /// Command ring buffer header (positions stored separately for atomicity)
@@ -157,6 +218,9 @@ pub struct CommandRingHeader {
 pub struct SharedMemoryLayout {
     pub header: SharedHeader,
     pub params: SharedParams,
+    /// Per-macro counters incremented by Tauri after writing `params.macros[i]`,
+    /// so the VST only applies the macros the UI actually changed
+    pub macro_versions: [AtomicU32; 8],
     pub voices: [VoiceState; MAX_VOICES],
     pub ring_header: CommandRingHeader,
     pub ring_slots: [CommandSlot; CMD_RING_SIZE],
@@ -166,11 +230,118 @@ pub struct SharedMemoryLayout {
     pub string_buffer: [u8; 4096],
     /// String buffer write position
     pub string_pos: AtomicU32,
+    /// Module faults reported by the VST engine (null-terminated
+    /// `stage\tmodule_id\tmessage` lines)
+    pub fault_buffer: [u8; FAULT_BUFFER_SIZE],
+    /// Events written so far (VST increments); the UI keeps its own read position
+    pub event_write_pos: AtomicU64,
+    /// Engine events for UI animation, overwritten oldest first
+    pub event_slots: [EventSlot; EVENT_RING_SIZE],
+}
+
+/// One engine event (gate edge, sequencer step, clock bar) for the UI.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct EventSlot {
+    /// `hash_id` of the module id
+    pub module_hash: u32,
+    /// 0 = gate on, 1 = gate off (value = voice), 2 = step, 3 = bar
+    pub kind: u32,
+    pub value: f32,
+    /// Frame offset in the VST block that produced it
+    pub frame: u32,
+}
+
+/// A module that panicked in the VST engine, as published over IPC.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FaultReport {
+    /// `load` or `render`
+    pub stage: String,
+    pub module_id: String,
+    pub message: String,
 }
 
 // Calculate total size
 pub const SHARED_MEM_SIZE: usize = std::mem::size_of::<SharedMemoryLayout>();
 
+// ============================================================================
+// Errors and version negotiation
+// ============================================================================
+
+/// Why a bridge could not be created or opened.
+#[derive(Debug)]
+pub enum BridgeError {
+    /// The OS mapping itself failed
+    Shmem(ShmemError),
+    /// The segment does not start with [`MAGIC`] (not ours, or not yet
+    /// initialized by its creator)
+    BadMagic { found: u32 },
+    /// The segment was created by a build speaking another [`VERSION`]
+    VersionMismatch { found: u32, expected: u32 },
+    /// Same [`VERSION`] but a layout of another size
+    LayoutMismatch { found: u32, expected: u32 },
+}
+
+impl std::fmt::Display for BridgeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BridgeError::Shmem(err) => write!(f, "shared memory error: {err}"),
+            BridgeError::BadMagic { found } => {
+                write!(f, "shared memory is not a NoobSynth bridge (magic {found:#010x})")
+            }
+            BridgeError::VersionMismatch { found, expected } => write!(
+                f,
+                "NoobSynth plugin and app speak different IPC versions ({found} vs {expected}): please update the plugin and the app to the same release"
+            ),
+            BridgeError::LayoutMismatch { found, expected } => write!(
+                f,
+                "NoobSynth plugin and app disagree on the shared memory layout ({found} vs {expected} bytes): please update the plugin and the app to the same release"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BridgeError {}
+
+impl From<ShmemError> for BridgeError {
+    fn from(err: ShmemError) -> Self {
+        BridgeError::Shmem(err)
+    }
+}
+
+/// Check that a segment created by the other side can be used as is.
+/// Openers never reinitialize a segment: the creator owns its layout, and
+/// wiping it would drop the live session of the other process.
+fn check_header(header: &SharedHeader) -> Result<(), BridgeError> {
+    if header.magic != MAGIC {
+        return Err(BridgeError::BadMagic { found: header.magic });
+    }
+    if header.version != VERSION {
+        return Err(BridgeError::VersionMismatch { found: header.version, expected: VERSION });
+    }
+    if header.layout_size != 0 && header.layout_size as usize != SHARED_MEM_SIZE {
+        return Err(BridgeError::LayoutMismatch { found: header.layout_size, expected: SHARED_MEM_SIZE as u32 });
+    }
+    Ok(())
+}
+
+/// Zero a freshly created segment and stamp it with this build's versions.
+///
+/// # Safety
+/// `layout` must point to a mapping of at least [`SHARED_MEM_SIZE`] bytes
+/// that no other process uses yet.
+unsafe fn initialize_layout(layout: *mut SharedMemoryLayout) {
+    std::ptr::write_bytes(layout, 0, 1);
+    (*layout).header.magic = MAGIC;
+    (*layout).header.version = VERSION;
+    (*layout).header.minor_version = MINOR_VERSION;
+    (*layout).header.layout_size = SHARED_MEM_SIZE as u32;
+    (*layout).params = SharedParams {
+        macros: [0.0; 8],
+        _padding: [0.0; 8],
+    };
+}
+
 // ============================================================================
 // VST-side Bridge
 // ============================================================================
@@ -179,6 +350,7 @@ pub const SHARED_MEM_SIZE: usize = std::mem::size_of::<SharedMemoryLayout>();
 pub struct VstBridge {
     shmem: Shmem,
     last_param_version: u64,
+    last_macro_versions: [u32; 8],
     last_graph_version: u64,
 }
 
@@ -189,33 +361,26 @@ unsafe impl Send for VstBridge {}
 unsafe impl Sync for VstBridge {}
 
 impl VstBridge {
-    /// Create or open the shared memory segment
-    pub fn new() -> Result<Self, ShmemError> {
+    /// Create the shared memory segment
+    pub fn new() -> Result<Self, BridgeError> {
         Self::new_with_id(None)
     }
 
-    /// Create or open the shared memory segment for a specific instance
-    pub fn new_with_id(instance_id: Option<&str>) -> Result<Self, ShmemError> {
+    /// Create the shared memory segment for a specific instance
+    pub fn new_with_id(instance_id: Option<&str>) -> Result<Self, BridgeError> {
         let os_id = shm_name(instance_id);
         let shmem = ShmemConf::new()
             .size(SHARED_MEM_SIZE)
             .os_id(&os_id)
             .create()?;
 
-        // Initialize if we created it OR if magic is wrong (stale memory)
+        // The creator stamps the layout; a segment we don't own is checked
         unsafe {
             let ptr = shmem.as_ptr() as *mut SharedMemoryLayout;
-            if shmem.is_owner()
-                || (*ptr).header.magic != MAGIC
-                || (*ptr).header.version != VERSION
-            {
-                std::ptr::write_bytes(ptr, 0, 1);
-                (*ptr).header.magic = MAGIC;
-                (*ptr).header.version = VERSION;
-                (*ptr).params = SharedParams {
-                    macros: [0.0; 8],
-                    _padding: [0.0; 8],
-                };
+            if shmem.is_owner() {
+                initialize_layout(ptr);
+            } else {
+                check_header(&(*ptr).header)?;
             }
         }
 
@@ -229,35 +394,27 @@ impl VstBridge {
         Ok(Self {
             shmem,
             last_param_version: 0,
+            last_macro_versions: [0; 8],
             last_graph_version: 0,
         })
     }
 
     /// Open existing shared memory (created by Tauri)
-    pub fn open() -> Result<Self, ShmemError> {
+    pub fn open() -> Result<Self, BridgeError> {
         Self::open_with_id(None)
     }
 
-    /// Open existing shared memory (created by Tauri) for a specific instance
-    pub fn open_with_id(instance_id: Option<&str>) -> Result<Self, ShmemError> {
+    /// Open existing shared memory (created by Tauri) for a specific
+    /// instance. Fails with a [`BridgeError`] instead of reinitializing a
+    /// segment from an incompatible build.
+    pub fn open_with_id(instance_id: Option<&str>) -> Result<Self, BridgeError> {
         let os_id = shm_name(instance_id);
         let shmem = ShmemConf::new()
             .os_id(&os_id)
             .open()?;
 
-        // Verify magic/version, reinitialize if stale
         unsafe {
-            let layout = shmem.as_ptr() as *const SharedMemoryLayout;
-            if (*layout).header.magic != MAGIC || (*layout).header.version != VERSION {
-                let layout = shmem.as_ptr() as *mut SharedMemoryLayout;
-                std::ptr::write_bytes(layout, 0, 1);
-                (*layout).header.magic = MAGIC;
-                (*layout).header.version = VERSION;
-                (*layout).params = SharedParams {
-                    macros: [0.0; 8],
-                    _padding: [0.0; 8],
-                };
-            }
+            check_header(&(*(shmem.as_ptr() as *const SharedMemoryLayout)).header)?;
             let layout = shmem.as_ptr() as *mut SharedMemoryLayout;
             // Clear all flags and set only VST connected (removes stale Tauri flag)
             (*layout).header.flags.store(1, Ordering::SeqCst);
@@ -266,6 +423,7 @@ impl VstBridge {
         Ok(Self {
             shmem,
             last_param_version: 0,
+            last_macro_versions: [0; 8],
             last_graph_version: 0,
         })
     }
@@ -310,6 +468,21 @@ impl VstBridge {
         self.layout().params
     }
 
+    /// Macros the UI wrote since the last call, by index. Each version is
+    /// read before its value, so a value is never older than the version
+    /// recorded for it.
+    pub fn macro_updates(&mut self) -> [Option<f32>; 8] {
+        let mut updates = [None; 8];
+        for (index, update) in updates.iter_mut().enumerate() {
+            let version = self.layout().macro_versions[index].load(Ordering::Acquire);
+            if version != self.last_macro_versions[index] {
+                self.last_macro_versions[index] = version;
+                *update = Some(self.layout().params.macros[index]);
+            }
+        }
+        updates
+    }
+
     /// Write params from VST for the UI to read
     pub fn set_vst_params(&mut self, params: SharedParams) {
         let layout = self.layout_mut();
@@ -332,34 +505,30 @@ impl VstBridge {
 
     /// Pop next command from ring buffer
     pub fn pop_command(&mut self) -> Option<CommandSlot> {
-        let layout = self.layout_mut();
-        let write_pos = layout.ring_header.write_pos.load(Ordering::Acquire);
-        let read_pos = layout.ring_header.read_pos.load(Ordering::Relaxed);
-
-        if read_pos >= write_pos {
-            return None;
-        }
-
-        let index = (read_pos as usize) % CMD_RING_SIZE;
-        let cmd = layout.ring_slots[index];
-        layout.ring_header.read_pos.store(read_pos + 1, Ordering::Release);
-
-        if cmd.cmd_type == CommandType::None as u8 {
-            None
-        } else {
-            Some(cmd)
+        unsafe {
+            let layout = self.shmem.as_ptr() as *mut SharedMemoryLayout;
+            ring_pop(&(*layout).ring_header, std::ptr::addr_of_mut!((*layout).ring_slots).cast())
         }
     }
 
     /// Read a string from the string buffer at given offset
     pub fn read_string(&self, offset: u32, len: u32) -> Option<String> {
-        let layout = self.layout();
+        String::from_utf8(self.read_bytes(offset, len)?).ok()
+    }
+
+    /// Read raw bytes from the string buffer at given offset, e.g. a
+    /// [`CommandType::SetModuleData`] chunk that may split a UTF-8 character
+    pub fn read_bytes(&self, offset: u32, len: u32) -> Option<Vec<u8>> {
         let start = offset as usize;
         let end = start + len as usize;
-        if end <= layout.string_buffer.len() {
-            String::from_utf8(layout.string_buffer[start..end].to_vec()).ok()
-        } else {
-            None
+        self.layout().string_buffer.get(start..end).map(<[u8]>::to_vec)
+    }
+
+    /// Publish the state of one voice for the UI to read. Out-of-range
+    /// voices are ignored.
+    pub fn update_voice_state(&mut self, voice: usize, state: VoiceState) {
+        if let Some(slot) = self.layout_mut().voices.get_mut(voice) {
+            *slot = state;
         }
     }
 
@@ -372,6 +541,19 @@ impl VstBridge {
     pub fn is_ui_connected(&self) -> bool {
         self.layout().header.flags.load(Ordering::Relaxed) & 2 != 0
     }
+
+    /// Publish the engine's module faults as `(stage, module_id, message)`.
+    /// Writes in place without allocating, so it is safe on the audio thread.
+    pub fn set_faults<'a>(&mut self, faults: impl IntoIterator<Item = (&'a str, &'a str, &'a str)>) {
+        write_fault_lines(&mut self.layout_mut().fault_buffer, faults);
+    }
+
+    /// Append engine events to the event ring, overwriting the oldest ones
+    /// the UI has not read yet. Safe on the audio thread.
+    pub fn push_events(&mut self, events: impl IntoIterator<Item = EventSlot>) {
+        let layout = self.layout_mut();
+        write_events(&mut layout.event_slots, &layout.event_write_pos, events);
+    }
 }
 
 impl Drop for VstBridge {
@@ -391,6 +573,8 @@ impl Drop for VstBridge {
 /// Tauri-side of the IPC bridge
 pub struct TauriBridge {
     shmem: Shmem,
+    /// Next event ring position to read
+    event_read_pos: u64,
 }
 
 // SAFETY: Shmem is thread-safe by design - it's shared memory with atomic
@@ -399,14 +583,233 @@ pub struct TauriBridge {
 unsafe impl Send for TauriBridge {}
 unsafe impl Sync for TauriBridge {}
 
+/// Copy `bytes` into the string ring at `pos` without splitting it across the
+/// end of the buffer: a string that would wrap restarts at offset 0, so
+/// `VstBridge::read_string(offset, len)` always reads one contiguous slice.
+/// Returns `(offset, len, next_pos)`; strings longer than the buffer are
+/// truncated.
+fn write_contiguous(buffer: &mut [u8], pos: usize, bytes: &[u8]) -> (usize, usize, usize) {
+    let len = bytes.len().min(buffer.len());
+    let offset = if pos + len <= buffer.len() { pos } else { 0 };
+    buffer[offset..offset + len].copy_from_slice(&bytes[..len]);
+    (offset, len, (offset + len) % buffer.len())
+}
+
+/// Write fault lines into `buffer`, null-terminated. Tabs and newlines inside
+/// fields become spaces; what does not fit is cut off.
+fn write_fault_lines<'a>(buffer: &mut [u8], faults: impl IntoIterator<Item = (&'a str, &'a str, &'a str)>) {
+    let capacity = buffer.len() - 1;
+    let mut len = 0;
+    for (stage, module_id, message) in faults {
+        for (field, end) in [(stage, b'\t'), (module_id, b'\t'), (message, b'\n')] {
+            let bytes = field.bytes().map(|b| if b == b'\t' || b == b'\n' { b' ' } else { b });
+            for byte in bytes.chain(std::iter::once(end)) {
+                if len == capacity {
+                    break;
+                }
+                buffer[len] = byte;
+                len += 1;
+            }
+        }
+    }
+    buffer[len] = 0;
+}
+
+/// Inverse of [`write_fault_lines`]; a truncated last line is dropped.
+fn read_fault_lines(buffer: &[u8]) -> Vec<FaultReport> {
+    let end = buffer.iter().position(|&b| b == 0).unwrap_or(buffer.len());
+    String::from_utf8_lossy(&buffer[..end])
+        .split_terminator('\n')
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            Some(FaultReport {
+                stage: fields.next()?.to_string(),
+                module_id: fields.next()?.to_string(),
+                message: fields.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Sequence stamp of the command written at ring position `pos`. Always
+/// odd, so a zeroed or consumed slot (even) never passes for a command.
+fn slot_seq(pos: u64) -> u32 {
+    ((pos as u32) << 1) | 1
+}
+
+/// Reads of a slot retried when the writer changed it during the copy.
+const SLOT_READ_RETRIES: usize = 4;
+
+/// Append a command to the ring (single producer). The payload is written
+/// under an even stamp and published by the odd stamp, then by `write_pos`,
+/// both with Release ordering. Returns false when the ring is full.
+///
+/// # Safety
+/// `slots` must point to [`CMD_RING_SIZE`] slots that only this producer
+/// and one [`ring_pop`] consumer access.
+unsafe fn ring_push(header: &CommandRingHeader, slots: *mut CommandSlot, cmd: CommandSlot) -> bool {
+    let write_pos = header.write_pos.load(Ordering::Relaxed);
+    let read_pos = header.read_pos.load(Ordering::Acquire);
+
+    // Check if buffer is full
+    if write_pos.wrapping_sub(read_pos) >= CMD_RING_SIZE as u64 {
+        return false;
+    }
+
+    let slot = slots.add((write_pos as usize) % CMD_RING_SIZE);
+    let seq = AtomicU32::from_ptr(std::ptr::addr_of_mut!((*slot).seq));
+    seq.store(0, Ordering::Relaxed);
+    std::sync::atomic::fence(Ordering::Release);
+    std::ptr::write_volatile(slot, CommandSlot { seq: 0, ..cmd });
+    seq.store(slot_seq(write_pos), Ordering::Release);
+    header.write_pos.store(write_pos.wrapping_add(1), Ordering::Release);
+    true
+}
+
+/// Take the next command from the ring (single consumer), seqlock style:
+/// the stamp is checked before and after copying the slot, and a copy the
+/// writer changed meanwhile is retried. Consumed slots are cleared to
+/// [`CommandType::None`]; slots that hold no command are skipped.
+///
+/// # Safety
+/// Same contract as [`ring_push`].
+unsafe fn ring_pop(header: &CommandRingHeader, slots: *mut CommandSlot) -> Option<CommandSlot> {
+    loop {
+        let write_pos = header.write_pos.load(Ordering::Acquire);
+        let read_pos = header.read_pos.load(Ordering::Relaxed);
+        if read_pos >= write_pos {
+            return None;
+        }
+
+        let slot = slots.add((read_pos as usize) % CMD_RING_SIZE);
+        let seq = AtomicU32::from_ptr(std::ptr::addr_of_mut!((*slot).seq));
+        let expected = slot_seq(read_pos);
+        let mut cmd = None;
+        for _ in 0..SLOT_READ_RETRIES {
+            let before = seq.load(Ordering::Acquire);
+            if before != expected {
+                // Stale or lapped by the writer: nothing valid to read here
+                break;
+            }
+            let copy = std::ptr::read_volatile(slot);
+            std::sync::atomic::fence(Ordering::Acquire);
+            if seq.load(Ordering::Relaxed) == before {
+                cmd = Some(copy);
+                break;
+            }
+        }
+        if cmd.is_none() && seq.load(Ordering::Acquire) == expected {
+            // Still being rewritten after every retry: try again next call
+            return None;
+        }
+        if cmd.is_some() {
+            seq.store(0, Ordering::Relaxed);
+            std::ptr::write_volatile(std::ptr::addr_of_mut!((*slot).cmd_type), CommandType::None as u8);
+        }
+        header.read_pos.store(read_pos + 1, Ordering::Release);
+
+        match cmd {
+            Some(cmd) if cmd.cmd_type != CommandType::None as u8 => return Some(CommandSlot { seq: 0, ..cmd }),
+            _ => continue,
+        }
+    }
+}
+
+/// Append `events` to the ring, publishing each with a Release store of
+/// the write position.
+fn write_events(slots: &mut [EventSlot], write_pos: &AtomicU64, events: impl IntoIterator<Item = EventSlot>) {
+    for event in events {
+        let pos = write_pos.load(Ordering::Relaxed);
+        slots[pos as usize % slots.len()] = event;
+        write_pos.store(pos + 1, Ordering::Release);
+    }
+}
+
+/// Copy the events written since `read_pos` and advance it. When the writer
+/// lapped the reader only the newest events are kept, and slots overwritten
+/// during the copy are dropped.
+fn read_events(slots: &[EventSlot], write_pos: &AtomicU64, read_pos: &mut u64) -> Vec<EventSlot> {
+    let size = slots.len() as u64;
+    let end = write_pos.load(Ordering::Acquire);
+    let start = (*read_pos).max(end.saturating_sub(size)).min(end);
+    let events: Vec<EventSlot> = (start..end).map(|pos| slots[(pos % size) as usize]).collect();
+    // Anything before `valid` may have been overwritten while copying.
+    let valid = write_pos.load(Ordering::Acquire).saturating_sub(size);
+    *read_pos = end;
+    let skip = valid.saturating_sub(start).min(events.len() as u64) as usize;
+    events[skip..].to_vec()
+}
+
+/// Split module data into [`CommandType::SetModuleData`] chunks as
+/// `(flags, bytes)`. Empty data is still one (empty) chunk.
+pub fn module_data_chunks(data: &[u8]) -> impl Iterator<Item = (u8, &[u8])> {
+    let count = data.len().div_ceil(MODULE_DATA_CHUNK).max(1);
+    (0..count).map(move |index| {
+        let start = index * MODULE_DATA_CHUNK;
+        let end = (start + MODULE_DATA_CHUNK).min(data.len());
+        let mut flags = 0;
+        if index == 0 {
+            flags |= MODULE_DATA_FIRST;
+        }
+        if index + 1 == count {
+            flags |= MODULE_DATA_LAST;
+        }
+        (flags, &data[start..end])
+    })
+}
+
+/// Reassembles [`CommandType::SetModuleData`] chunks on the VST side.
+#[derive(Default)]
+pub struct ModuleDataAssembler {
+    /// `(module hash, param hash)` of the transfer in progress
+    target: Option<(u32, u32)>,
+    bytes: Vec<u8>,
+}
+
+impl ModuleDataAssembler {
+    /// Add a chunk; returns `(module hash, param hash, data)` once the last
+    /// chunk is in. A first chunk drops any unfinished transfer, and chunks
+    /// of another transfer than the current one are ignored.
+    pub fn push(&mut self, cmd: &CommandSlot, chunk: &[u8]) -> Option<(u32, u32, Vec<u8>)> {
+        let target = (cmd.module_id, cmd.param_id);
+        if cmd.flags & MODULE_DATA_FIRST != 0 {
+            self.target = Some(target);
+            self.bytes.clear();
+        }
+        if self.target != Some(target) {
+            return None;
+        }
+        self.bytes.extend_from_slice(chunk);
+        if cmd.flags & MODULE_DATA_LAST == 0 {
+            return None;
+        }
+        self.target = None;
+        Some((target.0, target.1, std::mem::take(&mut self.bytes)))
+    }
+}
+
+/// Pack a string buffer offset and length into `CommandSlot::extra`.
+///
+/// Both halves are 16 bits; the string buffer is smaller than 64 KiB so
+/// neither can overflow into the other.
+pub fn pack_string_ref(offset: u32, len: u32) -> u32 {
+    debug_assert!(offset <= 0xFFFF && len <= 0xFFFF);
+    ((offset & 0xFFFF) << 16) | (len & 0xFFFF)
+}
+
+/// Inverse of [`pack_string_ref`]: `(offset, len)`.
+pub fn unpack_string_ref(extra: u32) -> (u32, u32) {
+    (extra >> 16, extra & 0xFFFF)
+}
+
 impl TauriBridge {
     /// Create the shared memory segment
-    pub fn new() -> Result<Self, ShmemError> {
+    pub fn new() -> Result<Self, BridgeError> {
         Self::new_with_id(None)
     }
 
     /// Create the shared memory segment for a specific instance
-    pub fn new_with_id(instance_id: Option<&str>) -> Result<Self, ShmemError> {
+    pub fn new_with_id(instance_id: Option<&str>) -> Result<Self, BridgeError> {
         let os_id = shm_name(instance_id);
         let shmem = ShmemConf::new()
             .size(SHARED_MEM_SIZE)
@@ -416,50 +819,45 @@ impl TauriBridge {
         // Initialize
         unsafe {
             let ptr = shmem.as_ptr() as *mut SharedMemoryLayout;
-            std::ptr::write_bytes(ptr, 0, 1);
-            (*ptr).header.magic = MAGIC;
-            (*ptr).header.version = VERSION;
-            (*ptr).params = SharedParams {
-                macros: [0.0; 8],
-                _padding: [0.0; 8],
-            };
+            initialize_layout(ptr);
             // Mark Tauri as connected
             (*ptr).header.flags.store(2, Ordering::SeqCst);
         }
 
-        Ok(Self { shmem })
+        let event_read_pos = unsafe {
+            (*(shmem.as_ptr() as *const SharedMemoryLayout))
+                .event_write_pos
+                .load(Ordering::Acquire)
+        };
+        Ok(Self { shmem, event_read_pos })
     }
 
     /// Open existing shared memory
-    pub fn open() -> Result<Self, ShmemError> {
+    pub fn open() -> Result<Self, BridgeError> {
         Self::open_with_id(None)
     }
 
-    /// Open existing shared memory for a specific instance
-    pub fn open_with_id(instance_id: Option<&str>) -> Result<Self, ShmemError> {
+    /// Open existing shared memory for a specific instance. Fails with a
+    /// [`BridgeError`] instead of reinitializing a segment from an
+    /// incompatible build.
+    pub fn open_with_id(instance_id: Option<&str>) -> Result<Self, BridgeError> {
         let os_id = shm_name(instance_id);
         let shmem = ShmemConf::new()
             .os_id(&os_id)
             .open()?;
 
-        // Verify magic, reinitialize if wrong (stale from previous session)
         unsafe {
             let layout = shmem.as_ptr() as *mut SharedMemoryLayout;
-            if (*layout).header.magic != MAGIC || (*layout).header.version != VERSION {
-                // Stale shared memory - reinitialize it
-                eprintln!("[NoobSynth IPC] Reinitializing stale shared memory");
-                std::ptr::write_bytes(layout, 0, 1);
-                (*layout).header.magic = MAGIC;
-                (*layout).header.version = VERSION;
-                (*layout).params = SharedParams {
-                    macros: [0.0; 8],
-                    _padding: [0.0; 8],
-                };
-            }
+            check_header(&(*layout).header)?;
             (*layout).header.flags.fetch_or(2, Ordering::SeqCst);
         }
 
-        Ok(Self { shmem })
+        let event_read_pos = unsafe {
+            (*(shmem.as_ptr() as *const SharedMemoryLayout))
+                .event_write_pos
+                .load(Ordering::Acquire)
+        };
+        Ok(Self { shmem, event_read_pos })
     }
 
     fn layout_mut(&mut self) -> &mut SharedMemoryLayout {
@@ -472,41 +870,36 @@ impl TauriBridge {
 
     /// Push a command to the ring buffer
     fn push_command(&mut self, cmd: CommandSlot) -> bool {
-        let layout = self.layout_mut();
-        let write_pos = layout.ring_header.write_pos.load(Ordering::Relaxed);
-        let read_pos = layout.ring_header.read_pos.load(Ordering::Acquire);
-
-        // Check if buffer is full
-        if write_pos.wrapping_sub(read_pos) >= CMD_RING_SIZE as u64 {
-            return false;
+        unsafe {
+            let layout = self.shmem.as_ptr() as *mut SharedMemoryLayout;
+            ring_push(&(*layout).ring_header, std::ptr::addr_of_mut!((*layout).ring_slots).cast(), cmd)
         }
-
-        let index = (write_pos as usize) % CMD_RING_SIZE;
-        layout.ring_slots[index] = cmd;
-        layout.ring_header.write_pos.store(write_pos.wrapping_add(1), Ordering::Release);
-        true
     }
 
     /// Write a string to the string buffer, return offset and length
     fn write_string(&mut self, s: &str) -> (u32, u32) {
-        let layout = self.layout_mut();
-        let bytes = s.as_bytes();
-        let len = bytes.len().min(layout.string_buffer.len());
+        self.write_bytes(s.as_bytes())
+    }
 
+    fn write_bytes(&mut self, bytes: &[u8]) -> (u32, u32) {
+        let layout = self.layout_mut();
         let pos = layout.string_pos.load(Ordering::Relaxed) as usize;
-        let new_pos = (pos + len) % layout.string_buffer.len();
+        let (offset, len, new_pos) = write_contiguous(&mut layout.string_buffer, pos, bytes);
+        layout.string_pos.store(new_pos as u32, Ordering::Release);
+        (offset as u32, len as u32)
+    }
 
-        // Handle wraparound
-        if pos + len <= layout.string_buffer.len() {
-            layout.string_buffer[pos..pos + len].copy_from_slice(&bytes[..len]);
-        } else {
-            let first_part = layout.string_buffer.len() - pos;
-            layout.string_buffer[pos..].copy_from_slice(&bytes[..first_part]);
-            layout.string_buffer[..len - first_part].copy_from_slice(&bytes[first_part..len]);
+    /// Wait until the VST has popped every queued command.
+    fn wait_for_ring_drain(&self, timeout: std::time::Duration) -> bool {
+        let start = std::time::Instant::now();
+        let header = &self.layout().ring_header;
+        while header.read_pos.load(Ordering::Acquire) < header.write_pos.load(Ordering::Relaxed) {
+            if start.elapsed() > timeout {
+                return false;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
         }
-
-        layout.string_pos.store(new_pos as u32, Ordering::Release);
-        (pos as u32, len as u32)
+        true
     }
 
     /// Set a parameter by name
@@ -526,11 +919,13 @@ impl TauriBridge {
             value,
             module_id: module_hash,
             param_id: param_hash,
-            extra: (mod_off << 16) | mod_len, // Pack offset and length
+            extra: pack_string_ref(mod_off, mod_len),
+            seq: 0,
         });
     }
 
-    /// Send note on
+    /// Send note on. The plugin allocates the voice like a DAW note on
+    /// channel 1, so `voice` is only a hint it ignores.
     pub fn note_on(&mut self, voice: u8, note: u8, velocity: f32) {
         self.push_command(CommandSlot {
             cmd_type: CommandType::NoteOn as u8,
@@ -541,10 +936,11 @@ impl TauriBridge {
             module_id: 0,
             param_id: 0,
             extra: 0,
+            seq: 0,
         });
     }
 
-    /// Send note off
+    /// Send note off; releases whichever voice `note` was given.
     pub fn note_off(&mut self, voice: u8, note: u8) {
         self.push_command(CommandSlot {
             cmd_type: CommandType::NoteOff as u8,
@@ -555,6 +951,7 @@ impl TauriBridge {
             module_id: 0,
             param_id: 0,
             extra: 0,
+            seq: 0,
         });
     }
 
@@ -569,6 +966,7 @@ impl TauriBridge {
             module_id: 0,
             param_id: 0,
             extra: 0,
+            seq: 0,
         });
     }
 
@@ -583,6 +981,22 @@ impl TauriBridge {
             module_id: 0,
             param_id: 0,
             extra: 0,
+            seq: 0,
+        });
+    }
+
+    /// Set voice glide time in seconds
+    pub fn set_glide(&mut self, voice: u8, seconds: f32) {
+        self.push_command(CommandSlot {
+            cmd_type: CommandType::SetGlide as u8,
+            voice,
+            note: 0,
+            flags: 0,
+            value: seconds,
+            module_id: 0,
+            param_id: 0,
+            extra: 0,
+            seq: 0,
         });
     }
 
@@ -597,6 +1011,7 @@ impl TauriBridge {
             module_id: 0,
             param_id: 0,
             extra: 0,
+            seq: 0,
         });
     }
 
@@ -611,9 +1026,72 @@ impl TauriBridge {
             module_id: 0,
             param_id: 0,
             extra: 0,
+            seq: 0,
         });
     }
 
+    /// Reset all DSP state in the VST engine
+    pub fn reset(&mut self) {
+        self.push_command(CommandSlot {
+            cmd_type: CommandType::Reset as u8,
+            voice: 0,
+            note: 0,
+            flags: 0,
+            value: 0.0,
+            module_id: 0,
+            param_id: 0,
+            extra: 0,
+            seq: 0,
+        });
+    }
+
+    /// Set the MIDI channel map (JSON array of routes, see `dsp_graph::NoteRouter`)
+    pub fn set_channel_map(&mut self, json: &str) {
+        let (offset, len) = self.write_string(json);
+        self.push_command(CommandSlot {
+            cmd_type: CommandType::SetChannelMap as u8,
+            voice: 0,
+            note: 0,
+            flags: 0,
+            value: 0.0,
+            module_id: 0,
+            param_id: 0,
+            extra: pack_string_ref(offset, len),
+            seq: 0,
+        });
+    }
+
+    /// Send a string param of any size (the VST only takes a `midiData`
+    /// note list) in [`MODULE_DATA_CHUNK`] pieces. Each chunk waits for the ring to drain
+    /// first; the VST may still be reading the previous chunk then, but no
+    /// older one, and the string buffer holds four. Blocks while the VST
+    /// catches up, and returns false if it stops taking commands (e.g. the
+    /// host suspended it).
+    pub fn set_module_data(&mut self, module_id: &str, param_id: &str, data: &str) -> bool {
+        let (module_hash, param_hash) = (hash_id(module_id), hash_id(param_id));
+        for (flags, chunk) in module_data_chunks(data.as_bytes()) {
+            if !self.wait_for_ring_drain(MODULE_DATA_TIMEOUT) {
+                return false;
+            }
+            let (offset, len) = self.write_bytes(chunk);
+            let pushed = self.push_command(CommandSlot {
+                cmd_type: CommandType::SetModuleData as u8,
+                voice: 0,
+                note: 0,
+                flags,
+                value: 0.0,
+                module_id: module_hash,
+                param_id: param_hash,
+                extra: pack_string_ref(offset, len),
+                seq: 0,
+            });
+            if !pushed {
+                return false;
+            }
+        }
+        true
+    }
+
     /// Set graph JSON
     pub fn set_graph(&mut self, json: &str) {
         let layout = self.layout_mut();
@@ -633,6 +1111,7 @@ impl TauriBridge {
             module_id: 0,
             param_id: 0,
             extra: len as u32,
+            seq: 0,
         });
     }
 
@@ -671,10 +1150,34 @@ impl TauriBridge {
             .load(Ordering::Acquire)
     }
 
-    /// Update shared params
+    /// Copy out the voice states last published by the VST
+    pub fn voice_states(&self) -> [VoiceState; MAX_VOICES] {
+        self.layout().voices
+    }
+
+    /// Update shared params. Only the macros whose value differs from the
+    /// shared one get a new version, so the VST keeps its own values for
+    /// the others (e.g. DAW automation).
     pub fn set_params(&mut self, params: SharedParams) {
         let layout = self.layout_mut();
+        let previous = layout.params.macros;
         layout.params = params;
+        for (index, (&old, &new)) in previous.iter().zip(params.macros.iter()).enumerate() {
+            if old.to_bits() != new.to_bits() {
+                layout.macro_versions[index].fetch_add(1, Ordering::Release);
+            }
+        }
+        layout.header.param_version.fetch_add(1, Ordering::Release);
+    }
+
+    /// Update a single macro
+    pub fn set_macro(&mut self, index: usize, value: f32) {
+        let layout = self.layout_mut();
+        let Some(slot) = layout.params.macros.get_mut(index) else {
+            return;
+        };
+        *slot = value;
+        layout.macro_versions[index].fetch_add(1, Ordering::Release);
         layout.header.param_version.fetch_add(1, Ordering::Release);
     }
 
@@ -692,6 +1195,21 @@ impl TauriBridge {
     pub fn sample_rate(&self) -> u32 {
         self.layout().header.sample_rate.load(Ordering::Relaxed)
     }
+
+    /// Module faults last published by the VST
+    pub fn faults(&self) -> Vec<FaultReport> {
+        read_fault_lines(&self.layout().fault_buffer)
+    }
+
+    /// Engine events published by the VST since the last call, oldest
+    /// first. Events the VST overwrote before they were read are lost.
+    pub fn pop_events(&mut self) -> Vec<EventSlot> {
+        let mut read_pos = self.event_read_pos;
+        let layout = self.layout();
+        let events = read_events(&layout.event_slots, &layout.event_write_pos, &mut read_pos);
+        self.event_read_pos = read_pos;
+        events
+    }
 }
 
 impl Drop for TauriBridge {
@@ -966,6 +1484,263 @@ mod tests {
         assert!(SHARED_MEM_SIZE < 128 * 1024); // Should be under 128KB
     }
 
+    #[test]
+    fn test_write_string_never_wraps() {
+        let mut buffer = [0u8; 16];
+        let (offset, len, pos) = write_contiguous(&mut buffer, 0, b"osc-1-long");
+        assert_eq!((offset, len, pos), (0, 10, 10));
+
+        // Would straddle the end: restarts at 0 instead of splitting.
+        let (offset, len, pos) = write_contiguous(&mut buffer, pos, b"cutoff-x");
+        assert_eq!((offset, len, pos), (0, 8, 8));
+        assert_eq!(&buffer[offset..offset + len], b"cutoff-x");
+
+        // Exactly fills the tail.
+        let (offset, len, pos) = write_contiguous(&mut buffer, pos, b"12345678");
+        assert_eq!((offset, len, pos), (8, 8, 0));
+    }
+
+    #[test]
+    fn test_string_ref_packing() {
+        let extra = pack_string_ref(4095, 300);
+        assert_eq!(unpack_string_ref(extra), (4095, 300));
+    }
+
+    #[test]
+    fn test_fault_lines_round_trip() {
+        let mut buffer = [0xffu8; 64];
+        write_fault_lines(&mut buffer, [("render", "vcf-1", "index out\tof\nbounds"), ("load", "", "boom")]);
+        let faults = read_fault_lines(&buffer);
+        assert_eq!(faults.len(), 2);
+        assert_eq!(faults[0].module_id, "vcf-1");
+        assert_eq!(faults[0].message, "index out of bounds");
+        assert_eq!((faults[1].stage.as_str(), faults[1].module_id.as_str()), ("load", ""));
+
+        // Cut off mid-line: only whole lines come back.
+        let mut small = [0u8; 24];
+        write_fault_lines(&mut small, [("render", "osc-1", "a"), ("render", "osc-2", "b")]);
+        assert_eq!(read_fault_lines(&small).len(), 1);
+
+        write_fault_lines(&mut small, []);
+        assert!(read_fault_lines(&small).is_empty());
+    }
+
+    #[test]
+    fn test_event_ring_keeps_the_newest() {
+        let mut slots = [EventSlot::default(); 4];
+        let write_pos = AtomicU64::new(0);
+        let mut read_pos = 0;
+        let step = |value: f32| EventSlot { module_hash: 7, kind: 2, value, frame: 0 };
+
+        write_events(&mut slots, &write_pos, [step(0.0), step(1.0)]);
+        assert_eq!(read_events(&slots, &write_pos, &mut read_pos), [step(0.0), step(1.0)]);
+        assert!(read_events(&slots, &write_pos, &mut read_pos).is_empty());
+
+        // Six more into four slots: the reader gets the last four.
+        write_events(&mut slots, &write_pos, (2..8).map(|value| step(value as f32)));
+        let values: Vec<f32> = read_events(&slots, &write_pos, &mut read_pos).iter().map(|e| e.value).collect();
+        assert_eq!(values, [4.0, 5.0, 6.0, 7.0]);
+        assert_eq!(read_pos, 8);
+    }
+
+    #[test]
+    fn test_command_type_round_trip() {
+        for cmd in [CommandType::SetChannelMap, CommandType::SetGlide, CommandType::SetModuleData] {
+            assert_eq!(CommandType::from(cmd as u8), cmd);
+        }
+        assert_eq!(CommandType::from(200), CommandType::None);
+    }
+
+    /// A segment stamped by another build, kept alive for the test.
+    fn crafted_segment(name: &str, version: u32, minor_version: u32) -> (Shmem, String) {
+        let id = format!("{name}-{}", std::process::id());
+        let shmem = ShmemConf::new()
+            .size(SHARED_MEM_SIZE)
+            .os_id(shm_name(Some(&id)))
+            .create()
+            .unwrap();
+        unsafe {
+            let layout = shmem.as_ptr() as *mut SharedMemoryLayout;
+            initialize_layout(layout);
+            (*layout).header.version = version;
+            (*layout).header.minor_version = minor_version;
+            (*layout).params.macros[0] = 0.25;
+        }
+        (shmem, id)
+    }
+
+    fn first_macro(shmem: &Shmem) -> f32 {
+        unsafe { (*(shmem.as_ptr() as *const SharedMemoryLayout)).params.macros[0] }
+    }
+
+    #[test]
+    fn test_open_refuses_another_version() {
+        let (shmem, id) = crafted_segment("test-version", VERSION + 1, 0);
+        match VstBridge::open_with_id(Some(&id)) {
+            Err(BridgeError::VersionMismatch { found, expected }) => {
+                assert_eq!((found, expected), (VERSION + 1, VERSION));
+            }
+            other => panic!("expected a version mismatch, got {:?}", other.err()),
+        }
+        assert!(matches!(TauriBridge::open_with_id(Some(&id)), Err(BridgeError::VersionMismatch { .. })));
+        // The other side's segment is left as it was.
+        assert_eq!(first_macro(&shmem), 0.25);
+
+        let message = BridgeError::VersionMismatch { found: 4, expected: 5 }.to_string();
+        assert!(message.contains("please update"), "{message}");
+    }
+
+    #[test]
+    fn test_open_refuses_bad_magic() {
+        let (shmem, id) = crafted_segment("test-magic", VERSION, MINOR_VERSION);
+        unsafe { (*(shmem.as_ptr() as *mut SharedMemoryLayout)).header.magic = 0xdead_beef };
+        assert!(matches!(VstBridge::open_with_id(Some(&id)), Err(BridgeError::BadMagic { found: 0xdead_beef })));
+        assert_eq!(first_macro(&shmem), 0.25);
+    }
+
+    #[test]
+    fn test_open_accepts_another_minor_version() {
+        let (shmem, id) = crafted_segment("test-minor", VERSION, MINOR_VERSION + 1);
+        let bridge = TauriBridge::open_with_id(Some(&id)).unwrap();
+        assert_eq!(first_macro(&shmem), 0.25);
+        drop(bridge);
+
+        // A segment from before minor versions has no layout size.
+        unsafe { (*(shmem.as_ptr() as *mut SharedMemoryLayout)).header.layout_size = 0 };
+        assert!(VstBridge::open_with_id(Some(&id)).is_ok());
+        unsafe { (*(shmem.as_ptr() as *mut SharedMemoryLayout)).header.layout_size = 64 };
+        assert!(matches!(VstBridge::open_with_id(Some(&id)), Err(BridgeError::LayoutMismatch { found: 64, .. })));
+    }
+
+    #[test]
+    fn test_only_macros_written_by_the_ui_are_updates() {
+        let id = format!("test-macros-{}", std::process::id());
+        let mut tauri = TauriBridge::new_with_id(Some(&id)).unwrap();
+        let mut vst = VstBridge::open_with_id(Some(&id)).unwrap();
+        assert_eq!(vst.macro_updates(), [None; 8]);
+
+        tauri.set_macro(2, 0.5);
+        let mut expected = [None; 8];
+        expected[2] = Some(0.5);
+        assert_eq!(vst.macro_updates(), expected);
+        assert_eq!(vst.macro_updates(), [None; 8]);
+
+        // The VST publishing its own values (DAW automation) is not an update,
+        // and the UI echoing them back does not re-version them.
+        let mut macros = [0.0; 8];
+        macros[5] = 0.75;
+        vst.set_vst_params(SharedParams { macros, _padding: [0.0; 8] });
+        macros[1] = 0.25;
+        tauri.set_params(SharedParams { macros, _padding: [0.0; 8] });
+        let mut expected = [None; 8];
+        expected[1] = Some(0.25);
+        assert_eq!(vst.macro_updates(), expected);
+    }
+
+    #[test]
+    fn test_module_data_arrives_in_chunks() {
+        let id = format!("test-module-data-{}", std::process::id());
+        let mut tauri = TauriBridge::new_with_id(Some(&id)).unwrap();
+        let mut vst = VstBridge::open_with_id(Some(&id)).unwrap();
+        // Ten chunks, with two-byte characters straddling the chunk edges.
+        let data = "é".repeat(5 * MODULE_DATA_CHUNK);
+        let stop = std::sync::atomic::AtomicBool::new(false);
+
+        let received = std::thread::scope(|scope| {
+            let consumer = scope.spawn(|| {
+                let mut assembler = ModuleDataAssembler::default();
+                while !stop.load(Ordering::Relaxed) {
+                    let Some(cmd) = vst.pop_command() else {
+                        std::thread::yield_now();
+                        continue;
+                    };
+                    assert_eq!(CommandType::from(cmd.cmd_type), CommandType::SetModuleData);
+                    let (offset, len) = unpack_string_ref(cmd.extra);
+                    let chunk = vst.read_bytes(offset, len).unwrap();
+                    if let Some(done) = assembler.push(&cmd, &chunk) {
+                        return Some(done);
+                    }
+                }
+                None
+            });
+            let sent = tauri.set_module_data("midi-1", "midiData", &data);
+            stop.store(!sent, Ordering::Relaxed);
+            consumer.join().unwrap()
+        });
+        let (module_hash, param_hash, bytes) = received.unwrap();
+        assert_eq!((module_hash, param_hash), (hash_id("midi-1"), hash_id("midiData")));
+        assert_eq!(String::from_utf8(bytes).unwrap(), data);
+    }
+
+    #[test]
+    fn test_module_data_assembler_restarts_on_a_first_chunk() {
+        let chunk = |module_id: u32, flags: u8| CommandSlot { module_id, flags, ..Default::default() };
+        let mut assembler = ModuleDataAssembler::default();
+        assert!(assembler.push(&chunk(1, MODULE_DATA_FIRST), b"stale").is_none());
+        // A chunk of another module is not part of the transfer.
+        assert!(assembler.push(&chunk(2, MODULE_DATA_LAST), b"stray").is_none());
+        assert!(assembler.push(&chunk(1, MODULE_DATA_FIRST), b"ab").is_none());
+        assert!(assembler.push(&chunk(1, 0), b"cd").is_none());
+        assert_eq!(assembler.push(&chunk(1, MODULE_DATA_LAST), b"ef"), Some((1, 0, b"abcdef".to_vec())));
+
+        let chunks: Vec<(u8, &[u8])> = module_data_chunks(b"").collect();
+        assert_eq!(chunks, [(MODULE_DATA_FIRST | MODULE_DATA_LAST, &b""[..])]);
+        assert_eq!(module_data_chunks(&[0; MODULE_DATA_CHUNK + 1]).count(), 2);
+    }
+
+    #[test]
+    fn test_command_ring_survives_a_concurrent_stress() {
+        const COMMANDS: u32 = 1_000_000;
+        struct Ring(*mut SharedMemoryLayout);
+        unsafe impl Send for Ring {}
+        unsafe impl Sync for Ring {}
+        impl Ring {
+            fn parts(&self) -> (&CommandRingHeader, *mut CommandSlot) {
+                unsafe { (&(*self.0).ring_header, std::ptr::addr_of_mut!((*self.0).ring_slots).cast()) }
+            }
+        }
+        let checksum = |n: u32| n.wrapping_mul(0x9e37_79b9) ^ 0x5bd1_e995;
+
+        let layout = Box::into_raw(unsafe { Box::<SharedMemoryLayout>::new_zeroed().assume_init() });
+        let ring = Ring(layout);
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                let (header, slots) = ring.parts();
+                for n in 0..COMMANDS {
+                    let cmd = CommandSlot {
+                        cmd_type: CommandType::SetParam as u8,
+                        module_id: n,
+                        param_id: checksum(n),
+                        extra: !n,
+                        ..CommandSlot::default()
+                    };
+                    while !unsafe { ring_push(header, slots, cmd) } {
+                        std::thread::yield_now();
+                    }
+                }
+            });
+            scope.spawn(|| {
+                let (header, slots) = ring.parts();
+                let mut next = 0;
+                while next < COMMANDS {
+                    let Some(cmd) = (unsafe { ring_pop(header, slots) }) else {
+                        std::thread::yield_now();
+                        continue;
+                    };
+                    assert_eq!(cmd.module_id, next);
+                    assert_eq!((cmd.param_id, cmd.extra), (checksum(next), !next), "torn command {next}");
+                    next += 1;
+                }
+            });
+        });
+
+        // Everything consumed, and every slot cleared behind the reader.
+        let (header, slots) = ring.parts();
+        assert!(unsafe { ring_pop(header, slots) }.is_none());
+        let layout = unsafe { Box::from_raw(layout) };
+        assert!(layout.ring_slots.iter().all(|slot| slot.cmd_type == CommandType::None as u8));
+    }
+
     #[test]
     fn test_command_slot_size() {
         assert_eq!(std::mem::size_of::<CommandSlot>(), 24);
@@ -1,9 +1,17 @@
 use nih_plug::prelude::*;
 use nih_plug_egui::{create_egui_editor, egui, EguiState};
-use dsp_graph::GraphEngine;
-use dsp_ipc::{CommandType, SharedParams, VstBridge, hash_id, launcher};
+use dsp_graph::{
+    ChannelMap, CompiledGraph, FaultStage, GraphEngine, MidiData, NoteRouter, RoutedVoice, DEFAULT_CONTROL_MODULE,
+    ROUTED_VOICES_CAPACITY,
+};
+use dsp_ipc::{
+    BridgeError, CommandType, EventSlot, ModuleDataAssembler, SharedParams, VoiceState, VstBridge, hash_id, launcher,
+    unpack_string_ref, MAX_VOICES,
+};
 use serde::Deserialize;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
@@ -17,163 +25,7 @@ fn generate_instance_id() -> String {
 
 /// Default graph JSON for a simple synth patch
 /// VCO → VCF → VCA → Output with ADSR envelopes
-const DEFAULT_GRAPH_JSON: &str = r#"{
-  "modules": [
-    {
-      "id": "osc-1",
-      "type": "oscillator",
-      "name": "VCO",
-      "position": { "x": 0, "y": 0 },
-      "params": {
-        "frequency": 110,
-        "type": "sawtooth",
-        "pwm": 0.5,
-        "unison": 2,
-        "detune": 7,
-        "fmLin": 0,
-        "fmExp": 0,
-        "subMix": 0,
-        "subOct": 1
-      }
-    },
-    {
-      "id": "vcf-1",
-      "type": "vcf",
-      "name": "VCF",
-      "position": { "x": 0, "y": 0 },
-      "params": {
-        "cutoff": 1200,
-        "resonance": 0.2,
-        "drive": 0.1,
-        "envAmount": 0.4,
-        "modAmount": 0,
-        "keyTrack": 0.5,
-        "model": "svf",
-        "mode": "lp",
-        "slope": 12
-      }
-    },
-    {
-      "id": "gain-1",
-      "type": "gain",
-      "name": "VCA",
-      "position": { "x": 0, "y": 0 },
-      "params": { "gain": 0.8 }
-    },
-    {
-      "id": "chorus-1",
-      "type": "chorus",
-      "name": "Chorus",
-      "position": { "x": 0, "y": 0 },
-      "params": {
-        "rate": 0.3,
-        "depth": 12,
-        "delay": 18,
-        "mix": 0.4,
-        "spread": 0.7,
-        "feedback": 0.1
-      }
-    },
-    {
-      "id": "out-1",
-      "type": "output",
-      "name": "Output",
-      "position": { "x": 0, "y": 0 },
-      "params": { "level": 0.7 }
-    },
-    {
-      "id": "adsr-1",
-      "type": "adsr",
-      "name": "Amp Env",
-      "position": { "x": 0, "y": 0 },
-      "params": { "attack": 0.01, "decay": 0.3, "sustain": 0.7, "release": 0.5 }
-    },
-    {
-      "id": "adsr-2",
-      "type": "adsr",
-      "name": "Filter Env",
-      "position": { "x": 0, "y": 0 },
-      "params": { "attack": 0.01, "decay": 0.5, "sustain": 0.3, "release": 0.4 }
-    },
-    {
-      "id": "ctrl-1",
-      "type": "control",
-      "name": "Control",
-      "position": { "x": 0, "y": 0 },
-      "params": {
-        "cv": 0,
-        "cvMode": "unipolar",
-        "velocity": 1,
-        "midiVelocity": true,
-        "gate": 0,
-        "glide": 0.02,
-        "midiEnabled": false,
-        "midiChannel": 0,
-        "midiRoot": 60,
-        "midiInputId": "",
-        "midiVelSlew": 0.005,
-        "voices": 8,
-        "seqOn": false,
-        "seqTempo": 120,
-        "seqGate": 0.5
-      }
-    }
-  ],
-  "macros": [
-    {
-      "id": 1,
-      "name": "Cutoff",
-      "targets": [{ "moduleId": "vcf-1", "paramId": "cutoff", "min": 200, "max": 6000 }]
-    },
-    {
-      "id": 2,
-      "name": "Resonance",
-      "targets": [{ "moduleId": "vcf-1", "paramId": "resonance", "min": 0, "max": 0.8 }]
-    },
-    {
-      "id": 3,
-      "name": "Env Amount",
-      "targets": [{ "moduleId": "vcf-1", "paramId": "envAmount", "min": 0, "max": 0.9 }]
-    },
-    {
-      "id": 4,
-      "name": "Attack",
-      "targets": [{ "moduleId": "adsr-1", "paramId": "attack", "min": 0.01, "max": 2.0 }]
-    },
-    {
-      "id": 5,
-      "name": "Decay",
-      "targets": [{ "moduleId": "adsr-1", "paramId": "decay", "min": 0.05, "max": 2.5 }]
-    },
-    {
-      "id": 6,
-      "name": "Sustain",
-      "targets": [{ "moduleId": "adsr-1", "paramId": "sustain", "min": 0.0, "max": 1.0 }]
-    },
-    {
-      "id": 7,
-      "name": "Release",
-      "targets": [{ "moduleId": "adsr-1", "paramId": "release", "min": 0.05, "max": 3.0 }]
-    },
-    {
-      "id": 8,
-      "name": "Chorus",
-      "targets": [{ "moduleId": "chorus-1", "paramId": "mix", "min": 0.0, "max": 1.0 }]
-    }
-  ],
-  "connections": [
-    { "from": { "moduleId": "ctrl-1", "portId": "cv-out" }, "to": { "moduleId": "osc-1", "portId": "pitch" }, "kind": "cv" },
-    { "from": { "moduleId": "ctrl-1", "portId": "cv-out" }, "to": { "moduleId": "vcf-1", "portId": "key" }, "kind": "cv" },
-    { "from": { "moduleId": "ctrl-1", "portId": "gate-out" }, "to": { "moduleId": "adsr-1", "portId": "gate" }, "kind": "gate" },
-    { "from": { "moduleId": "ctrl-1", "portId": "gate-out" }, "to": { "moduleId": "adsr-2", "portId": "gate" }, "kind": "gate" },
-    { "from": { "moduleId": "osc-1", "portId": "out" }, "to": { "moduleId": "vcf-1", "portId": "in" }, "kind": "audio" },
-    { "from": { "moduleId": "adsr-2", "portId": "env" }, "to": { "moduleId": "vcf-1", "portId": "env" }, "kind": "cv" },
-    { "from": { "moduleId": "vcf-1", "portId": "out" }, "to": { "moduleId": "gain-1", "portId": "in" }, "kind": "audio" },
-    { "from": { "moduleId": "adsr-1", "portId": "env" }, "to": { "moduleId": "gain-1", "portId": "cv" }, "kind": "cv" },
-    { "from": { "moduleId": "gain-1", "portId": "out" }, "to": { "moduleId": "chorus-1", "portId": "in" }, "kind": "audio" },
-    { "from": { "moduleId": "chorus-1", "portId": "out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "audio" }
-  ]
-}"#;
+const DEFAULT_GRAPH_JSON: &str = include_str!("default_graph.json");
 
 /// Precomputed hashes for common module/param IDs
 mod hashes {
@@ -190,6 +42,38 @@ mod hashes {
     pub static GAIN_1: LazyLock<u32> = LazyLock::new(|| hash_id("gain-1"));
 }
 
+/// Cutoff rise at full channel pressure, in octaves
+const PRESSURE_CUTOFF_OCTAVES: f32 = 3.0;
+
+/// Where MIDI channel pressure (aftertouch) goes
+#[derive(Enum, Clone, Copy, Debug, PartialEq, Eq)]
+enum PressureDest {
+    /// Velocity of every voice playing on the channel
+    #[id = "velocity"]
+    #[name = "Velocity"]
+    Velocity,
+    /// Opens the graph's first VCF up to `PRESSURE_CUTOFF_OCTAVES` above
+    /// its cutoff
+    #[id = "vcf-cutoff"]
+    #[name = "VCF Cutoff"]
+    VcfCutoff,
+    /// Pushes the first VCF's envelope amount towards 1
+    #[id = "vcf-env-amount"]
+    #[name = "VCF Env Amount"]
+    VcfEnvAmount,
+}
+
+impl PressureDest {
+    /// VCF param this destination modulates, if any
+    fn vcf_param(self) -> Option<&'static str> {
+        match self {
+            PressureDest::Velocity => None,
+            PressureDest::VcfCutoff => Some("cutoff"),
+            PressureDest::VcfEnvAmount => Some("envAmount"),
+        }
+    }
+}
+
 #[derive(Clone)]
 struct MacroTarget {
     module_id: String,
@@ -235,19 +119,67 @@ struct GraphIndexPayload {
 #[derive(Deserialize)]
 struct GraphIndexModule {
     id: String,
+    #[serde(rename = "type", default)]
+    kind: String,
     params: Option<HashMap<String, serde_json::Value>>,
 }
 
+/// Work for the plugin's background thread
+pub enum PluginTask {
+    /// Build a graph off the audio thread and save it in the plugin state.
+    /// `from_ui` graphs are not published back to the UI that sent them.
+    CompileGraph { graph_json: String, from_ui: bool },
+    /// Parse a `midiData` note list received from the UI and save it in
+    /// the plugin state
+    LoadModuleData { module_id: String, param_id: String, data: Vec<u8> },
+    /// Parse a MIDI channel map received from the UI
+    ParseChannelMap(String),
+    /// Free a replaced graph off the audio thread
+    Retire(PendingGraph),
+    /// Free the graph JSON replaced by loaded module data
+    RetireModuleData(Vec<PendingModuleData>),
+    /// Free a replaced channel map off the audio thread
+    RetireChannelMap(ChannelMap),
+}
+
+/// A graph compiled in the background, with everything derived from its
+/// JSON, waiting for the audio thread to swap it in
+pub struct PendingGraph {
+    graph_json: String,
+    compiled: CompiledGraph,
+    module_hash_map: HashMap<u32, String>,
+    param_hash_map: HashMap<u32, String>,
+    macro_specs: Vec<MacroSpec>,
+    /// VCF that channel pressure modulates
+    pressure_vcf: Option<String>,
+    /// Copy the graph into the UI's shared graph buffer once installed
+    publish: bool,
+}
+
+/// MIDI notes parsed in the background, waiting for the audio thread to
+/// load them into their sequencer
+pub struct PendingModuleData {
+    module_id: String,
+    /// Taken by the audio thread when loaded
+    midi_data: Option<MidiData>,
+    /// Saved graph with the notes in the module's params; holds the
+    /// replaced graph once loaded
+    graph_json: String,
+}
+
 /// NoobSynth VST3/CLAP Plugin
 pub struct NoobSynth {
     params: Arc<NoobSynthParams>,
     engine: GraphEngine,
     /// Current graph state as JSON (for state persistence)
     graph_json: String,
-    /// Voice allocation: maps voice_id to MIDI note
-    voice_notes: [Option<u8>; 16],
-    /// Next voice to allocate (round-robin)
-    next_voice: usize,
+    /// Routes DAW MIDI channels to control modules and allocates their voices
+    note_router: NoteRouter,
+    /// Voices picked by `note_router` for the current event, reused so the
+    /// note path doesn't allocate
+    routed: Vec<RoutedVoice>,
+    /// Channel map parsed by the background task, installed at the next block
+    pending_channel_map: Arc<Mutex<Option<ChannelMap>>>,
     /// Maximum voices
     max_voices: usize,
     /// Unique instance identifier for IPC
@@ -265,6 +197,30 @@ pub struct NoobSynth {
     last_published_macros: [f32; 8],
     last_ui_connected: bool,
     ui_macro_override: bool,
+    /// Latency last reported to the host, in samples
+    reported_latency: u32,
+    /// `ctrl-1` voice states mirrored to the UI through the shared layout
+    voice_states: [VoiceState; MAX_VOICES],
+    /// Number of engine faults last published to the UI; `None` forces a publish
+    published_faults: Option<usize>,
+    /// Graph compiled by the background task, installed at the next block
+    pending_graph: Arc<Mutex<Option<PendingGraph>>>,
+    /// Module data parsed by the background task, loaded at the next block
+    pending_module_data: Arc<Mutex<Vec<PendingModuleData>>>,
+    /// `CompileGraph` and `LoadModuleData` tasks not yet in `pending_graph`
+    /// or `pending_module_data`; the saved graph may already be ahead of the
+    /// installed one while this is non-zero
+    graph_tasks: Arc<AtomicU32>,
+    /// Hash of the last saved graph sent to compile, so one that fails to
+    /// load is not retried every block
+    synced_graph: Option<u64>,
+    /// First VCF of the installed graph, the target of channel pressure
+    pressure_vcf: Option<String>,
+    /// Stored value of the param channel pressure moves, put back once the
+    /// pressure goes back to zero
+    pressure_base: Option<(PressureDest, f32)>,
+    /// Chunks of a large string param (a MIDI file) still being received
+    module_data: ModuleDataAssembler,
 }
 
 /// Plugin parameters exposed to the DAW
@@ -277,6 +233,10 @@ struct NoobSynthParams {
     #[persist = "graph-json"]
     graph_json: Mutex<String>,
 
+    /// Channel pressure destination
+    #[id = "channel-pressure-dest"]
+    pub channel_pressure_dest: EnumParam<PressureDest>,
+
     /// Macro 1
     #[id = "macro_1"]
     pub macro_1: FloatParam,
@@ -316,6 +276,8 @@ impl Default for NoobSynthParams {
             editor_state: EguiState::from_size(360, 200),
             graph_json: Mutex::new(DEFAULT_GRAPH_JSON.to_string()),
 
+            channel_pressure_dest: EnumParam::new("Pressure Dest", PressureDest::Velocity),
+
             macro_1: FloatParam::new(
                 "Macro 1",
                 0.0,
@@ -404,8 +366,9 @@ impl Default for NoobSynth {
             params,
             engine: GraphEngine::new(44100.0),
             graph_json: DEFAULT_GRAPH_JSON.to_string(),
-            voice_notes: [None; 16],
-            next_voice: 0,
+            note_router: NoteRouter::new(8),
+            routed: Vec::with_capacity(ROUTED_VOICES_CAPACITY),
+            pending_channel_map: Arc::new(Mutex::new(None)),
             max_voices: 8,
             instance_id,
             ipc_bridge: None,
@@ -420,6 +383,16 @@ impl Default for NoobSynth {
             last_published_macros,
             last_ui_connected: false,
             ui_macro_override: false,
+            reported_latency: 0,
+            voice_states: [VoiceState::IDLE; MAX_VOICES],
+            published_faults: None,
+            pending_graph: Arc::new(Mutex::new(None)),
+            pending_module_data: Arc::new(Mutex::new(Vec::new())),
+            graph_tasks: Arc::new(AtomicU32::new(0)),
+            synced_graph: None,
+            pressure_vcf: None,
+            pressure_base: None,
+            module_data: ModuleDataAssembler::default(),
         }
     }
 }
@@ -431,23 +404,23 @@ impl NoobSynth {
         let Some(bridge) = &mut self.ipc_bridge else {
             return;
         };
-        if !bridge.params_changed() {
-            return;
-        }
-        let mut values = bridge.params().macros;
-        for value in &mut values {
-            *value = value.clamp(0.0, 1.0);
-        }
+        // Only the macros the UI wrote: the others may hold values we
+        // published ourselves and must not be applied back
+        let updates = bridge.macro_updates();
         let mut changed = false;
-        for (index, value) in values.iter().enumerate() {
-            if (self.last_macro_values[index] - *value).abs() > 1e-6 {
-                self.apply_macro_value(index, *value);
+        for (index, update) in updates.into_iter().enumerate() {
+            let Some(value) = update else {
+                continue;
+            };
+            let value = value.clamp(0.0, 1.0);
+            if (self.last_macro_values[index] - value).abs() > 1e-6 {
+                self.apply_macro_value(index, value);
+                self.last_macro_values[index] = value;
+                self.last_published_macros[index] = value;
                 changed = true;
             }
         }
         if changed {
-            self.last_macro_values = values;
-            self.last_published_macros = values;
             self.ui_macro_override = true;
         }
     }
@@ -494,28 +467,74 @@ impl NoobSynth {
         self.persist_graph_json();
     }
 
-    fn apply_graph_json(&mut self, graph_json: String) {
-        if let Err(e) = self.engine.set_graph_json(&graph_json) {
-            nih_error!("Failed to load graph: {}", e);
-            return;
+    /// Push the host voice count to `ctrl-1`. Effect-only graphs have no
+    /// control module, in which case MIDI notes have nothing to drive.
+    fn apply_voice_count(&mut self) {
+        if self.engine.has_module("ctrl-1") {
+            self.engine.set_param("ctrl-1", "voices", self.max_voices as f32);
+        } else {
+            nih_log!("Graph has no ctrl-1 module; MIDI notes will be ignored");
         }
-        self.set_graph_json(graph_json);
-        self.engine.set_param("ctrl-1", "voices", self.max_voices as f32);
-        self.refresh_hash_maps();
-        self.macro_specs = parse_macro_specs(&self.graph_json);
-        self.apply_all_macros();
-        self.publish_graph_to_ui();
     }
 
-    fn sync_graph_from_params(&mut self) {
-        let stored = match self.params.graph_json.try_lock() {
-            Ok(guard) => guard.clone(),
-            Err(_) => return,
+    /// Build `graph_json` on the background thread; `install_pending_graph`
+    /// swaps it in once ready.
+    fn compile_graph(&mut self, graph_json: String, from_ui: bool, context: &mut impl ProcessContext<Self>) {
+        self.graph_tasks.fetch_add(1, Ordering::AcqRel);
+        context.execute_background(PluginTask::CompileGraph { graph_json, from_ui });
+    }
+
+    /// Swap in a graph compiled by the background task, if one is ready.
+    /// The background task already saved it in the plugin state; the
+    /// replaced graph and lookup tables go back to it to be freed.
+    fn install_pending_graph(&mut self, context: &mut impl ProcessContext<Self>) {
+        let pending = match self.pending_graph.try_lock() {
+            Ok(mut slot) => slot.take(),
+            Err(_) => None,
         };
-        if stored.trim().is_empty() || stored == self.graph_json {
+        let Some(pending) = pending else {
             return;
+        };
+        self.published_faults = None;
+        let retired = PendingGraph {
+            compiled: self.engine.install_compiled(pending.compiled),
+            graph_json: std::mem::replace(&mut self.graph_json, pending.graph_json),
+            module_hash_map: std::mem::replace(&mut self.module_hash_map, pending.module_hash_map),
+            param_hash_map: std::mem::replace(&mut self.param_hash_map, pending.param_hash_map),
+            macro_specs: std::mem::replace(&mut self.macro_specs, pending.macro_specs),
+            pressure_vcf: std::mem::replace(&mut self.pressure_vcf, pending.pressure_vcf),
+            publish: false,
+        };
+        context.execute_background(PluginTask::Retire(retired));
+        self.pressure_base = None;
+        self.apply_voice_count();
+        self.apply_all_macros();
+        if pending.publish {
+            self.publish_graph_to_ui();
         }
-        self.apply_graph_json(stored);
+    }
+
+    /// Pick up a graph the host restored into the saved state. Graphs
+    /// being compiled are saved before they are installed, so the check
+    /// waits until none is in flight.
+    fn sync_graph_from_params(&mut self, context: &mut impl ProcessContext<Self>) {
+        if self.graph_tasks.load(Ordering::Acquire) > 0 {
+            return;
+        }
+        let stored = match self.params.graph_json.try_lock() {
+            Ok(guard) if !guard.trim().is_empty() && *guard != self.graph_json => {
+                let mut hasher = DefaultHasher::new();
+                guard.hash(&mut hasher);
+                let hash = hasher.finish();
+                if self.synced_graph == Some(hash) {
+                    return;
+                }
+                self.synced_graph = Some(hash);
+                guard.clone()
+            }
+            _ => return,
+        };
+        self.compile_graph(stored, false, context);
     }
 
     fn refresh_hash_maps(&mut self) {
@@ -524,12 +543,84 @@ impl NoobSynth {
         self.param_hash_map = param_map;
     }
 
+    /// Forward the engine events of the block just rendered (gate edges,
+    /// sequencer steps, clock bars) to the UI's event ring.
+    fn publish_events(&mut self) {
+        let Some(bridge) = &mut self.ipc_bridge else {
+            return;
+        };
+        bridge.push_events(self.engine.drain_events().map(|event| EventSlot {
+            module_hash: event.module_hash,
+            kind: event.kind as u32,
+            value: event.value,
+            frame: event.frame,
+        }));
+    }
+
+    /// Mirror the engine's module faults (caught panics) to the UI when
+    /// the list changed. Runs on the audio thread; writes without allocating.
+    fn publish_faults(&mut self) {
+        let count = self.engine.faults().len();
+        if self.published_faults == Some(count) {
+            return;
+        }
+        if let Some(bridge) = &mut self.ipc_bridge {
+            bridge.set_faults(self.engine.faults().iter().map(|fault| {
+                let stage = match fault.stage {
+                    FaultStage::Load => "load",
+                    FaultStage::Render => "render",
+                };
+                (stage, fault.module_id.as_str(), fault.message.as_str())
+            }));
+            self.published_faults = Some(count);
+        }
+    }
+
     fn publish_graph_to_ui(&mut self) {
         if let Some(bridge) = &mut self.ipc_bridge {
             bridge.set_vst_graph(&self.graph_json);
         }
     }
 
+    /// Apply a string param received in chunks (see `TauriBridge::set_module_data`)
+    /// and keep it in the saved graph. It is parsed on the background thread;
+    /// `install_pending_module_data` loads the result. The UI already holds
+    /// the value, and the graph with it may not fit the shared graph buffer,
+    /// so it is not published back.
+    fn apply_module_data(
+        &mut self,
+        module_hash: u32,
+        param_hash: u32,
+        data: Vec<u8>,
+        context: &mut impl ProcessContext<Self>,
+    ) {
+        let module_id = self.lookup_module_id(module_hash).map(str::to_string);
+        let param_id = self.lookup_param_id(param_hash).map(str::to_string);
+        let (Some(module_id), Some(param_id)) = (module_id, param_id) else {
+            return;
+        };
+        self.graph_tasks.fetch_add(1, Ordering::AcqRel);
+        context.execute_background(PluginTask::LoadModuleData { module_id, param_id, data });
+    }
+
+    /// Load the MIDI notes parsed by the background task, if any are ready,
+    /// along with the saved graph that holds them.
+    fn install_pending_module_data(&mut self, context: &mut impl ProcessContext<Self>) {
+        let mut pending = match self.pending_module_data.try_lock() {
+            Ok(mut slot) if !slot.is_empty() => std::mem::take(&mut *slot),
+            _ => return,
+        };
+        for data in &mut pending {
+            if let Some(midi_data) = data.midi_data.take() {
+                if let Err(e) = self.engine.set_module_midi_data(&data.module_id, midi_data) {
+                    nih_error!("Failed to load module data: {}", e);
+                }
+            }
+            std::mem::swap(&mut self.graph_json, &mut data.graph_json);
+        }
+        context.execute_background(PluginTask::RetireModuleData(pending));
+    }
+
     fn lookup_module_id(&self, hash: u32) -> Option<&str> {
         if let Some(value) = self.module_hash_map.get(&hash) {
             return Some(value.as_str());
@@ -544,39 +635,133 @@ impl NoobSynth {
         hash_to_param_id(hash)
     }
 
-    /// Allocate a voice for a new note (round-robin with voice stealing)
-    fn allocate_voice(&mut self, note: u8) -> usize {
-        // First, check if this note is already playing
-        for (i, n) in self.voice_notes.iter().enumerate() {
-            if *n == Some(note) {
-                return i;
-            }
+    /// Update the shared-memory copy of a `ctrl-1` voice. Voices of other
+    /// control modules have no slot in the layout and are skipped.
+    fn publish_voice_state(&mut self, module_id: &str, voice: usize, update: impl FnOnce(&mut VoiceState)) {
+        if module_id != DEFAULT_CONTROL_MODULE || voice >= MAX_VOICES {
+            return;
+        }
+        update(&mut self.voice_states[voice]);
+        if let Some(bridge) = &mut self.ipc_bridge {
+            bridge.update_voice_state(voice, self.voice_states[voice]);
         }
+    }
 
-        // Find a free voice
-        for (i, n) in self.voice_notes.iter().enumerate() {
-            if i < self.max_voices && n.is_none() {
-                self.voice_notes[i] = Some(note);
-                return i;
-            }
+    /// Mark every voice idle, e.g. after a reset.
+    fn clear_voice_states(&mut self) {
+        for voice in 0..MAX_VOICES {
+            self.publish_voice_state("ctrl-1", voice, |state| *state = VoiceState::IDLE);
+        }
+    }
+
+    /// `publish_voice_state` for a voice picked by the note router
+    fn publish_routed_state(&mut self, routed: RoutedVoice, update: impl FnOnce(&mut VoiceState)) {
+        if self.note_router.module_id(routed.pool) == DEFAULT_CONTROL_MODULE {
+            self.publish_voice_state(DEFAULT_CONTROL_MODULE, routed.voice, update);
         }
+    }
+
+    fn note_on_voice(&mut self, routed: RoutedVoice, velocity: f32) {
+        let RoutedVoice { pool, voice, note } = routed;
+        let module_id = self.note_router.module_id(pool);
+        let cv = self.engine.note_to_cv(module_id, note);
+        self.engine.set_control_voice_cv(module_id, voice, cv);
+        self.engine.set_control_voice_velocity(module_id, voice, velocity, 0.005);
+        self.engine.trigger_control_voice_gate(module_id, voice);
+        self.publish_routed_state(routed, |state| {
+            *state = VoiceState { cv, gate: 1.0, velocity, note, _padding: [0; 3] };
+        });
+    }
 
-        // No free voice, steal the next one (round-robin)
-        let voice = self.next_voice % self.max_voices;
-        self.voice_notes[voice] = Some(note);
-        self.next_voice = (self.next_voice + 1) % self.max_voices;
-        voice
+    fn note_off_voice(&mut self, routed: RoutedVoice) {
+        self.engine.set_control_voice_gate(self.note_router.module_id(routed.pool), routed.voice, 0.0);
+        self.publish_routed_state(routed, |state| state.gate = 0.0);
     }
 
-    /// Release a voice by note
-    fn release_voice(&mut self, note: u8) -> Option<usize> {
-        for (i, n) in self.voice_notes.iter_mut().enumerate() {
-            if *n == Some(note) {
-                *n = None;
-                return Some(i);
+    /// Velocity of a routed voice, e.g. from aftertouch
+    fn set_voice_pressure(&mut self, routed: RoutedVoice, pressure: f32, slew: f32) {
+        self.engine.set_control_voice_velocity(self.note_router.module_id(routed.pool), routed.voice, pressure, slew);
+        self.publish_routed_state(routed, |state| state.velocity = pressure);
+    }
+
+    /// Apply MIDI channel pressure (0-1) to the destination picked by the
+    /// `channel-pressure-dest` param. The VCF destinations go through
+    /// `modulate_param`, so the pressed value glides in and is never saved
+    /// with the graph.
+    fn apply_channel_pressure(&mut self, channel: u8, pressure: f32) {
+        let dest = self.params.channel_pressure_dest.value();
+        let Some(param) = dest.vcf_param() else {
+            self.release_pressure();
+            let mut routed = std::mem::take(&mut self.routed);
+            self.note_router.voices_on_channel(channel, &mut routed);
+            for &voice in &routed {
+                self.set_voice_pressure(voice, pressure, 0.005);
             }
+            self.routed = routed;
+            return;
+        };
+        // Destination changed while pressed: put the old param back first
+        if self.pressure_base.is_some_and(|(previous, _)| previous != dest) {
+            self.release_pressure();
         }
-        None
+        let Some(vcf) = self.pressure_vcf.as_deref() else {
+            return;
+        };
+        let base = match self.pressure_base {
+            Some((_, base)) => base,
+            None => {
+                let Some(base) = self.engine.get_param(vcf, param) else {
+                    return;
+                };
+                self.pressure_base = Some((dest, base));
+                base
+            }
+        };
+        if pressure <= 0.0 {
+            self.release_pressure();
+            return;
+        }
+        let value = match dest {
+            PressureDest::VcfCutoff => base * (pressure * PRESSURE_CUTOFF_OCTAVES).exp2(),
+            _ => base + (1.0 - base) * pressure,
+        };
+        self.engine.modulate_param(vcf, param, value);
+    }
+
+    /// Glide the param channel pressure moved back to its stored value.
+    fn release_pressure(&mut self) {
+        let Some((dest, base)) = self.pressure_base.take() else {
+            return;
+        };
+        if let (Some(vcf), Some(param)) = (self.pressure_vcf.as_deref(), dest.vcf_param()) {
+            self.engine.modulate_param(vcf, param, base);
+        }
+    }
+
+    /// Close the gates of every voice the router holds, e.g. before the
+    /// channel map changes under them.
+    fn release_routed_voices(&mut self) {
+        let mut routed = std::mem::take(&mut self.routed);
+        self.note_router.active_voices(&mut routed);
+        for &voice in &routed {
+            self.note_off_voice(voice);
+        }
+        self.routed = routed;
+        self.note_router.reset();
+    }
+
+    /// Swap in a channel map parsed by the background task, if one is ready.
+    fn install_pending_channel_map(&mut self, context: &mut impl ProcessContext<Self>) {
+        let map = match self.pending_channel_map.try_lock() {
+            Ok(mut slot) => slot.take(),
+            Err(_) => None,
+        };
+        let Some(map) = map else {
+            return;
+        };
+        self.release_routed_voices();
+        let retired = self.note_router.install_map(map);
+        context.execute_background(PluginTask::RetireChannelMap(retired));
     }
 
     fn apply_macro_value(&mut self, macro_index: usize, value: f32) {
@@ -601,6 +786,9 @@ impl NoobSynth {
         self.last_macro_values = values;
     }
 
+    /// Push DAW macro values to the engine. Only macros that moved since the
+    /// last block are re-applied, so an idle automation lane costs a single
+    /// array compare per block and no `set_param` lookups.
     fn sync_macros_to_engine(&mut self) {
         let values = self.params.macro_values();
         if values == self.last_daw_macro_values {
@@ -638,7 +826,7 @@ impl NoobSynth {
                 self.publish_macros_to_ui();
             }
             Err(e) => {
-                nih_log!("VstBridge::new() failed: {:?}, trying open()...", e);
+                nih_log!("VstBridge::new() failed: {}, trying open()...", e);
                 // Try to open existing (maybe Tauri created it first)
                 match VstBridge::open_with_id(Some(self.instance_id.as_str())) {
                     Ok(mut bridge) => {
@@ -650,7 +838,8 @@ impl NoobSynth {
                         self.publish_macros_to_ui();
                     }
                     Err(e2) => {
-                        nih_log!("VstBridge::open() also failed: {:?}", e2);
+                        // A version mismatch says which side to update
+                        nih_log!("VstBridge::open() also failed: {}", e2);
                         nih_log!("IPC bridge unavailable - UI control will not work");
                     }
                 }
@@ -663,6 +852,7 @@ impl NoobSynth {
 
     fn reconnect_ipc(&mut self) {
         self.ipc_bridge = None;
+        self.published_faults = None;
         let sample_rate = self.ui_sample_rate.load(Ordering::Relaxed);
         match VstBridge::open_with_id(Some(self.instance_id.as_str())) {
             Ok(mut bridge) => {
@@ -674,7 +864,13 @@ impl NoobSynth {
                 self.publish_macros_to_ui();
                 nih_log!("IPC bridge reconnected (open)");
             }
-            Err(_) => match VstBridge::new_with_id(Some(self.instance_id.as_str())) {
+            Err(err @ (BridgeError::BadMagic { .. }
+            | BridgeError::VersionMismatch { .. }
+            | BridgeError::LayoutMismatch { .. })) => {
+                // The app's segment is incompatible: recreating it would not help
+                nih_log!("IPC bridge reconnect failed: {}", err);
+            }
+            Err(BridgeError::Shmem(_)) => match VstBridge::new_with_id(Some(self.instance_id.as_str())) {
                 Ok(mut bridge) => {
                     if sample_rate > 0 {
                         bridge.set_sample_rate(sample_rate);
@@ -685,14 +881,14 @@ impl NoobSynth {
                     nih_log!("IPC bridge reconnected (new)");
                 }
                 Err(err) => {
-                    nih_log!("IPC bridge reconnect failed: {:?}", err);
+                    nih_log!("IPC bridge reconnect failed: {}", err);
                 }
             },
         }
     }
 
     /// Process IPC commands from Tauri UI
-    fn process_ipc_commands(&mut self) {
+    fn process_ipc_commands(&mut self, context: &mut impl ProcessContext<Self>) {
         let graph_json = {
             let Some(bridge) = &mut self.ipc_bridge else {
                 return;
@@ -700,10 +896,11 @@ impl NoobSynth {
             bridge.graph_changed()
         };
 
-        // Check for graph changes
+        // Check for graph changes; the graph is built on the background
+        // thread and swapped in by install_pending_graph
         if let Some(graph_json) = graph_json {
             nih_log!("Received new graph from UI ({} bytes)", graph_json.len());
-            self.apply_graph_json(graph_json);
+            self.compile_graph(graph_json, true, context);
         }
 
         // Process commands from ring buffer
@@ -724,64 +921,95 @@ impl NoobSynth {
                     let param_id = self.lookup_param_id(cmd.param_id).map(str::to_string);
                     if let (Some(module_id), Some(param_id)) = (module_id, param_id) {
                         self.engine.set_param(&module_id, &param_id, cmd.value);
-                        if let Some(updated) = update_graph_param_json(
-                            &self.graph_json,
-                            &module_id,
-                            &param_id,
-                            cmd.value,
-                        ) {
+                        let value = serde_json::Number::from_f64(cmd.value as f64).map(serde_json::Value::Number);
+                        let updated = value.and_then(|value| {
+                            update_graph_param_json(&self.graph_json, &module_id, &param_id, value)
+                        });
+                        if let Some(updated) = updated {
                             self.set_graph_json(updated);
                             self.publish_graph_to_ui();
                         }
                     }
                 }
+                // UI notes share the router with DAW notes (as channel 1),
+                // so neither steals a voice the other holds behind its back
                 CommandType::NoteOn => {
-                    let voice = cmd.voice as usize;
-                    let note = cmd.note;
-                    let velocity = cmd.value;
-
-                    if voice < self.max_voices {
-                        self.voice_notes[voice] = Some(note);
-                        let cv = (note as f32 - 60.0) / 12.0;
-                        self.engine.set_control_voice_cv("ctrl-1", voice, cv);
-                        self.engine.set_control_voice_velocity("ctrl-1", voice, velocity, 0.005);
-                        self.engine.trigger_control_voice_gate("ctrl-1", voice);
+                    let mut routed = std::mem::take(&mut self.routed);
+                    self.note_router.note_on(0, cmd.note, cmd.value, &mut routed);
+                    for &voice in &routed {
+                        self.note_on_voice(voice, cmd.value);
                     }
+                    self.routed = routed;
                 }
                 CommandType::NoteOff => {
+                    let mut routed = std::mem::take(&mut self.routed);
+                    self.note_router.note_off(0, cmd.note, &mut routed);
+                    for &voice in &routed {
+                        self.note_off_voice(voice);
+                    }
+                    self.routed = routed;
+                }
+                CommandType::ReleaseGate => {
                     let voice = cmd.voice as usize;
                     if voice < self.max_voices {
-                        self.voice_notes[voice] = None;
                         self.engine.set_control_voice_gate("ctrl-1", voice, 0.0);
+                        self.publish_voice_state("ctrl-1", voice, |state| state.gate = 0.0);
                     }
                 }
                 CommandType::SetVoiceCv => {
                     let voice = cmd.voice as usize;
                     if voice < self.max_voices {
                         self.engine.set_control_voice_cv("ctrl-1", voice, cmd.value);
+                        self.publish_voice_state("ctrl-1", voice, |state| state.cv = cmd.value);
                     }
                 }
-                CommandType::SetVoiceVelocity => {
+                CommandType::SetGlide => {
                     let voice = cmd.voice as usize;
                     if voice < self.max_voices {
-                        self.engine.set_control_voice_velocity("ctrl-1", voice, cmd.value, 0.005);
+                        self.engine.set_control_voice_glide("ctrl-1", voice, cmd.value);
                     }
                 }
-                CommandType::TriggerGate => {
+                CommandType::SetVoiceVelocity => {
                     let voice = cmd.voice as usize;
                     if voice < self.max_voices {
-                        self.engine.trigger_control_voice_gate("ctrl-1", voice);
+                        self.engine.set_control_voice_velocity("ctrl-1", voice, cmd.value, 0.005);
+                        self.publish_voice_state("ctrl-1", voice, |state| state.velocity = cmd.value);
                     }
                 }
-                CommandType::ReleaseGate => {
+                CommandType::TriggerGate => {
                     let voice = cmd.voice as usize;
                     if voice < self.max_voices {
-                        self.engine.set_control_voice_gate("ctrl-1", voice, 0.0);
+                        self.engine.trigger_control_voice_gate("ctrl-1", voice);
+                        self.publish_voice_state("ctrl-1", voice, |state| state.gate = 1.0);
                     }
                 }
                 CommandType::SetGraph => {
                     // Graph was already handled above via graph_changed()
                 }
+                CommandType::SetChannelMap => {
+                    let (offset, len) = unpack_string_ref(cmd.extra);
+                    let map_json = self
+                        .ipc_bridge
+                        .as_ref()
+                        .and_then(|bridge| bridge.read_string(offset, len));
+                    if let Some(map_json) = map_json {
+                        context.execute_background(PluginTask::ParseChannelMap(map_json));
+                    }
+                }
+                CommandType::SetModuleData => {
+                    let (offset, len) = unpack_string_ref(cmd.extra);
+                    let chunk = self.ipc_bridge.as_ref().and_then(|bridge| bridge.read_bytes(offset, len));
+                    if let Some((module_hash, param_hash, data)) =
+                        chunk.and_then(|chunk| self.module_data.push(&cmd, &chunk))
+                    {
+                        self.apply_module_data(module_hash, param_hash, data, context);
+                    }
+                }
+                CommandType::Reset => {
+                    self.note_router.reset();
+                    self.engine.reset();
+                    self.clear_voice_states();
+                }
                 CommandType::None => {}
             }
         }
@@ -808,7 +1036,7 @@ fn hash_to_param_id(hash: u32) -> Option<&'static str> {
         "level", "cutoff", "resonance", "envAmount", "attack", "decay",
         "sustain", "release", "mix", "frequency", "type", "drive",
         "rate", "depth", "delay", "spread", "feedback", "gain",
-        "cv", "gate", "velocity", "voices", "glide",
+        "cv", "gate", "velocity", "voices", "glide", "midiData", "midiFile",
     ];
 
     for param in common_params {
@@ -837,6 +1065,8 @@ fn hash_to_param_id(hash: u32) -> Option<&'static str> {
                 "velocity" => "velocity",
                 "voices" => "voices",
                 "glide" => "glide",
+                "midiData" => "midiData",
+                "midiFile" => "midiFile",
                 _ => return None,
             });
         }
@@ -914,11 +1144,42 @@ fn build_hash_maps(payload: &str) -> (HashMap<u32, String>, HashMap<u32, String>
     (module_map, param_map)
 }
 
+/// Id of the first VCF in the graph, which channel pressure modulates.
+fn find_pressure_vcf(payload: &str) -> Option<String> {
+    let parsed: GraphIndexPayload = serde_json::from_str(payload).ok()?;
+    parsed.modules.into_iter().find(|module| module.kind == "vcf").map(|module| module.id)
+}
+
+/// Parse a `midiData` note list and save it in the stored graph, on the
+/// background thread.
+fn load_module_data(
+    params: &NoobSynthParams,
+    module_id: &str,
+    param_id: &str,
+    data: Vec<u8>,
+) -> Result<PendingModuleData, String> {
+    if param_id != "midiData" {
+        return Err("only midiData is sent as module data".to_string());
+    }
+    let value = String::from_utf8(data).map_err(|_| "not UTF-8")?;
+    let midi_data = MidiData::from_json(value).ok_or("not a note list")?;
+    let mut stored = params.graph_json.lock().map_err(|_| "graph state unavailable")?;
+    let value = serde_json::Value::String(midi_data.json().to_string());
+    let graph_json = update_graph_param_json(&stored, module_id, param_id, value).ok_or("unknown module")?;
+    // Saved before the load, as for compiled graphs
+    *stored = graph_json.clone();
+    Ok(PendingModuleData {
+        module_id: module_id.to_string(),
+        midi_data: Some(midi_data),
+        graph_json,
+    })
+}
+
 fn update_graph_param_json(
     graph_json: &str,
     module_id: &str,
     param_id: &str,
-    value: f32,
+    value: serde_json::Value,
 ) -> Option<String> {
     let mut root: serde_json::Value = serde_json::from_str(graph_json).ok()?;
     let modules = root.get_mut("modules")?.as_array_mut()?;
@@ -935,8 +1196,7 @@ fn update_graph_param_json(
             *params_entry = serde_json::Value::Object(serde_json::Map::new());
         }
         let params_obj = params_entry.as_object_mut()?;
-        let number = serde_json::Number::from_f64(value as f64)?;
-        params_obj.insert(param_id.to_string(), serde_json::Value::Number(number));
+        params_obj.insert(param_id.to_string(), value);
         return serde_json::to_string(&root).ok();
     }
     None
@@ -957,17 +1217,88 @@ impl Plugin for NoobSynth {
         names: PortNames::const_default(),
     }];
 
-    const MIDI_INPUT: MidiConfig = MidiConfig::Basic;
+    // CCs for the sustain pedal and channel pressure
+    const MIDI_INPUT: MidiConfig = MidiConfig::MidiCCs;
     const MIDI_OUTPUT: MidiConfig = MidiConfig::None;
     const SAMPLE_ACCURATE_AUTOMATION: bool = true;
 
     type SysExMessage = ();
-    type BackgroundTask = ();
+    type BackgroundTask = PluginTask;
 
     fn params(&self) -> Arc<dyn Params> {
         self.params.clone()
     }
 
+    fn task_executor(&mut self) -> TaskExecutor<Self> {
+        let pending_graph = self.pending_graph.clone();
+        let pending_module_data = self.pending_module_data.clone();
+        let pending_channel_map = self.pending_channel_map.clone();
+        let graph_tasks = self.graph_tasks.clone();
+        let params = self.params.clone();
+        let sample_rate = self.ui_sample_rate.clone();
+        Box::new(move |task| match task {
+            PluginTask::CompileGraph { graph_json, from_ui } => {
+                let sample_rate = sample_rate.load(Ordering::Relaxed) as f32;
+                match GraphEngine::compile_graph_json(&graph_json, sample_rate, false) {
+                    Ok(compiled) => {
+                        for migration in compiled.migrations() {
+                            nih_log!("Graph migrated: {}", migration);
+                        }
+                        for diagnostic in compiled.diagnostics() {
+                            nih_log!("{}", diagnostic);
+                        }
+                        let (module_hash_map, param_hash_map) = build_hash_maps(&graph_json);
+                        let macro_specs = parse_macro_specs(&graph_json);
+                        let pressure_vcf = find_pressure_vcf(&graph_json);
+                        // Saved before the swap: the audio thread only
+                        // moves the installed copy
+                        if let Ok(mut stored) = params.graph_json.lock() {
+                            if *stored != graph_json {
+                                *stored = graph_json.clone();
+                            }
+                        }
+                        if let Ok(mut slot) = pending_graph.lock() {
+                            *slot = Some(PendingGraph {
+                                graph_json,
+                                compiled,
+                                module_hash_map,
+                                param_hash_map,
+                                macro_specs,
+                                pressure_vcf,
+                                publish: !from_ui,
+                            });
+                        }
+                    }
+                    Err(e) => nih_error!("Failed to load graph: {}", e),
+                }
+                graph_tasks.fetch_sub(1, Ordering::AcqRel);
+            }
+            PluginTask::LoadModuleData { module_id, param_id, data } => {
+                match load_module_data(&params, &module_id, &param_id, data) {
+                    Ok(loaded) => {
+                        if let Ok(mut slot) = pending_module_data.lock() {
+                            slot.push(loaded);
+                        }
+                    }
+                    Err(e) => nih_error!("Failed to load {}.{}: {}", module_id, param_id, e),
+                }
+                graph_tasks.fetch_sub(1, Ordering::AcqRel);
+            }
+            PluginTask::Retire(retired) => drop(retired),
+            PluginTask::ParseChannelMap(map_json) => match ChannelMap::from_json(&map_json) {
+                Ok(map) => {
+                    nih_log!("Channel map set ({} routes)", map.routes().len());
+                    if let Ok(mut slot) = pending_channel_map.lock() {
+                        *slot = Some(map);
+                    }
+                }
+                Err(err) => nih_log!("{}", err),
+            },
+            PluginTask::RetireModuleData(retired) => drop(retired),
+            PluginTask::RetireChannelMap(retired) => drop(retired),
+        })
+    }
+
     fn editor(&mut self, _async_executor: AsyncExecutor<Self>) -> Option<Box<dyn Editor>> {
         let ui_auto_launch = Arc::new(AtomicBool::new(false));
         let ui_connected = self.ui_connected.clone();
@@ -1009,7 +1340,7 @@ impl Plugin for NoobSynth {
         &mut self,
         _audio_io_layout: &AudioIOLayout,
         buffer_config: &BufferConfig,
-        _context: &mut impl InitContext<Self>,
+        context: &mut impl InitContext<Self>,
     ) -> bool {
         // Initialize the graph engine with the correct sample rate
         self.engine = GraphEngine::new(buffer_config.sample_rate);
@@ -1028,9 +1359,12 @@ impl Plugin for NoobSynth {
         self.macro_specs = parse_macro_specs(&self.graph_json);
 
         // Set initial voice count
-        self.engine.set_param("ctrl-1", "voices", self.max_voices as f32);
+        self.apply_voice_count();
         self.apply_all_macros();
 
+        self.reported_latency = self.engine.latency_samples() as u32;
+        context.set_latency_samples(self.reported_latency);
+
         // Initialize IPC bridge (will also try to launch Tauri)
         self.init_ipc(buffer_config.sample_rate);
 
@@ -1040,8 +1374,10 @@ impl Plugin for NoobSynth {
 
     fn reset(&mut self) {
         // Reset all voices
-        self.voice_notes = [None; 16];
-        self.next_voice = 0;
+        self.note_router.reset();
+        self.engine.reset();
+        self.clear_voice_states();
+        self.release_pressure();
     }
 
     fn process(
@@ -1056,10 +1392,13 @@ impl Plugin for NoobSynth {
         }
 
         self.sync_macros_from_ui();
-        self.sync_graph_from_params();
+        self.install_pending_graph(context);
+        self.install_pending_module_data(context);
+        self.install_pending_channel_map(context);
+        self.sync_graph_from_params(context);
 
         // Process IPC commands from Tauri UI
-        self.process_ipc_commands();
+        self.process_ipc_commands(context);
 
         let connected = self
             .ipc_bridge
@@ -1075,38 +1414,61 @@ impl Plugin for NoobSynth {
 
         // Apply macro updates from DAW (only when changed)
         self.sync_macros_to_engine();
+
+        // Graph edits, params and macros can all change the path latency
+        let latency = self.engine.latency_samples() as u32;
+        if latency != self.reported_latency {
+            self.reported_latency = latency;
+            context.set_latency_samples(latency);
+        }
         self.publish_macros_to_ui();
 
         // Process MIDI events from DAW
         while let Some(event) = context.next_event() {
             match event {
-                NoteEvent::NoteOn { note, velocity, .. } => {
-                    let voice = self.allocate_voice(note);
-                    let cv = (note as f32 - 60.0) / 12.0;
-
-                    self.engine.set_control_voice_cv("ctrl-1", voice, cv);
-                    self.engine.set_control_voice_velocity("ctrl-1", voice, velocity, 0.005);
-                    self.engine.trigger_control_voice_gate("ctrl-1", voice);
+                NoteEvent::NoteOn { channel, note, velocity, .. } => {
+                    let mut routed = std::mem::take(&mut self.routed);
+                    self.note_router.note_on(channel, note, velocity, &mut routed);
+                    for &voice in &routed {
+                        self.note_on_voice(voice, velocity);
+                    }
+                    self.routed = routed;
                 }
-                NoteEvent::NoteOff { note, .. } => {
-                    if let Some(voice) = self.release_voice(note) {
-                        self.engine.set_control_voice_gate("ctrl-1", voice, 0.0);
+                NoteEvent::NoteOff { channel, note, .. } => {
+                    let mut routed = std::mem::take(&mut self.routed);
+                    self.note_router.note_off(channel, note, &mut routed);
+                    for &voice in &routed {
+                        self.note_off_voice(voice);
                     }
+                    self.routed = routed;
                 }
-                NoteEvent::PolyPressure { note, pressure, .. } => {
-                    // Find the voice playing this note and update velocity
-                    for (i, n) in self.voice_notes.iter().enumerate() {
-                        if *n == Some(note) {
-                            self.engine.set_control_voice_velocity("ctrl-1", i, pressure, 0.01);
-                            break;
-                        }
+                NoteEvent::PolyPressure { channel, note, pressure, .. } => {
+                    // Update velocity on every voice playing this note
+                    let mut routed = std::mem::take(&mut self.routed);
+                    self.note_router.voices_for(channel, note, &mut routed);
+                    for &voice in &routed {
+                        self.set_voice_pressure(voice, pressure, 0.01);
                     }
+                    self.routed = routed;
+                }
+                NoteEvent::MidiChannelPressure { channel, pressure, .. } => {
+                    self.apply_channel_pressure(channel, pressure);
+                }
+                NoteEvent::MidiCC { channel, cc: 64, value, .. } => {
+                    // Sustain pedal: notes let go meanwhile close on lift
+                    let mut routed = std::mem::take(&mut self.routed);
+                    self.note_router.set_sustain(channel, value >= 0.5, &mut routed);
+                    for &voice in &routed {
+                        self.note_off_voice(voice);
+                    }
+                    self.routed = routed;
                 }
                 _ => {}
             }
         }
 
-        // Render audio
+        // Render audio. The engine chunks internally (MAX_BLOCK_FRAMES), so
+        // hosts that exceed their declared max buffer size are still fine.
         let num_samples = buffer.samples();
         let output = self.engine.render(num_samples);
 
@@ -1127,7 +1489,17 @@ impl Plugin for NoobSynth {
             }
         }
 
-        ProcessStatus::Normal
+        self.publish_faults();
+        self.publish_events();
+
+        // Let the host suspend us once the engine has gone idle (all outputs
+        // silent past the tail hold, no gates held); otherwise keep running
+        // so tails and sequencers aren't cut.
+        if self.engine.is_idle() {
+            ProcessStatus::Normal
+        } else {
+            ProcessStatus::KeepAlive
+        }
     }
 }
 
@@ -1,10 +1,14 @@
+mod scope;
+
 use dsp_graph::GraphEngine;
 use js_sys::{Float32Array, Uint8Array};
+use scope::{ScopeRing, SCOPE_FRAMES};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
 pub struct WasmGraphEngine {
   engine: GraphEngine,
+  scope: ScopeRing,
 }
 
 #[wasm_bindgen]
@@ -13,13 +17,26 @@ impl WasmGraphEngine {
   pub fn new(sample_rate: f32) -> WasmGraphEngine {
     WasmGraphEngine {
       engine: GraphEngine::new(sample_rate),
+      scope: ScopeRing::new(SCOPE_FRAMES),
     }
   }
 
   pub fn set_graph(&mut self, graph_json: &str) -> Result<(), JsValue> {
+    self.scope.clear();
     self.engine
       .set_graph_json(graph_json)
-      .map_err(|err| JsValue::from_str(&err))
+      .map_err(|err| JsValue::from_str(&err.to_string()))
+  }
+
+  /// Live graph (current param values) as JSON, for preset saving
+  pub fn to_graph_json(&self) -> String {
+    self.engine.to_graph_json()
+  }
+
+  /// Clear all DSP state (tails, phases, envelopes) without rebuilding the graph
+  pub fn reset(&mut self) {
+    self.engine.reset();
+    self.scope.clear();
   }
 
   pub fn set_param(&mut self, module_id: &str, param_id: &str, value: f32) {
@@ -30,6 +47,40 @@ impl WasmGraphEngine {
     self.engine.set_param_string(module_id, param_id, value);
   }
 
+  pub fn set_bypass(&mut self, module_id: &str, bypass: bool) {
+    self.engine.set_bypass(module_id, bypass);
+  }
+
+  /// Current value of a numeric param (undefined if unknown)
+  pub fn get_param(&self, module_id: &str, param_id: &str) -> Option<f32> {
+    self.engine.get_param(module_id, param_id)
+  }
+
+  /// Range/default/step metadata of a module type's params, as a JSON array
+  pub fn param_metadata(module_type: &str) -> String {
+    GraphEngine::param_metadata(module_type).to_string()
+  }
+
+  /// Soft-clip ceiling of the output buses; `undefined` disables the limiter.
+  pub fn set_output_ceiling(&mut self, ceiling: Option<f32>) {
+    self.engine.set_output_ceiling(ceiling);
+  }
+
+  pub fn store_snapshot(&mut self, slot: usize) -> Result<(), JsValue> {
+    self.engine.store_snapshot(slot).map_err(|err| JsValue::from_str(&err))
+  }
+
+  pub fn recall_snapshot(&mut self, slot: usize) -> Result<(), JsValue> {
+    self.engine.recall_snapshot(slot).map_err(|err| JsValue::from_str(&err))
+  }
+
+  pub fn morph_snapshots(&mut self, slot_a: usize, slot_b: usize, t: f32) -> Result<(), JsValue> {
+    self
+      .engine
+      .morph_snapshots(slot_a, slot_b, t)
+      .map_err(|err| JsValue::from_str(&err))
+  }
+
   pub fn set_control_voice_cv(&mut self, module_id: &str, voice: usize, value: f32) {
     self.engine.set_control_voice_cv(module_id, voice, value);
   }
@@ -57,12 +108,22 @@ impl WasmGraphEngine {
       .set_control_voice_velocity(module_id, voice, value, slew_seconds);
   }
 
+  /// Set a `cv-bank` channel CV (1-based channel)
+  pub fn set_bank_channel_cv(&mut self, module_id: &str, channel: usize, value: f32) {
+    self.engine.set_bank_channel_cv(module_id, channel, value);
+  }
+
+  /// Set a `cv-bank` channel gate (1-based channel)
+  pub fn set_bank_channel_gate(&mut self, module_id: &str, channel: usize, value: f32) {
+    self.engine.set_bank_channel_gate(module_id, channel, value);
+  }
+
   pub fn set_mario_channel_cv(&mut self, module_id: &str, channel: usize, value: f32) {
-    self.engine.set_mario_channel_cv(module_id, channel, value);
+    self.engine.set_bank_channel_cv(module_id, channel, value);
   }
 
   pub fn set_mario_channel_gate(&mut self, module_id: &str, channel: usize, value: f32) {
-    self.engine.set_mario_channel_gate(module_id, channel, value);
+    self.engine.set_bank_channel_gate(module_id, channel, value);
   }
 
   pub fn set_external_input(&mut self, input: &[f32]) {
@@ -73,11 +134,44 @@ impl WasmGraphEngine {
     self.engine.clear_external_input();
   }
 
+  /// Render a block. The result is planar: `[L][R][tap 0][tap 1]...`, each
+  /// `frames` long, so tap `n` starts at `(2 + n) * frames` and channel `c`
+  /// is `subarray(c * frames, (c + 1) * frames)`, ready to copy into the
+  /// worklet's `outputs[0][c]` like `AudioBuffer.getChannelData(c)`. Extra
+  /// output buses follow the taps.
   pub fn render(&mut self, frames: usize) -> Float32Array {
-    let data = self.engine.render(frames);
+    let scope_channels = 2 + self.engine.tap_count();
+    let data = self.engine.render_planar(frames);
+    self.scope.push_planar(&data[..scope_channels * frames], frames);
     unsafe { Float32Array::view(data) }
   }
 
+  /// Render a block and return a pointer to the planar output in wasm linear memory.
+  /// JS reads it with `new Float32Array(memory.buffer, ptr, frames * output_channels())`;
+  /// the pointer stays valid until the next render with a different size or graph.
+  pub fn render_ptr(&mut self, frames: usize) -> *const f32 {
+    let scope_channels = 2 + self.engine.tap_count();
+    let data = self.engine.render(frames);
+    self.scope.push_planar(&data[..scope_channels * frames], frames);
+    data.as_ptr()
+  }
+
+  /// Number of planar channels written by `render` / `render_ptr`.
+  pub fn output_channels(&self) -> usize {
+    self.engine.output_channels()
+  }
+
+  /// Number of graph taps (scope inputs) in the current graph
+  pub fn tap_count(&self) -> usize {
+    self.engine.tap_count()
+  }
+
+  /// Get the most recent `frames` samples of a tap (max 2048), oldest first
+  pub fn get_tap(&self, tap_index: usize, frames: usize) -> Float32Array {
+    let data = self.scope.export(tap_index, frames);
+    Float32Array::from(&data[..])
+  }
+
   /// Get current step position for a sequencer module
   /// Returns -1 if module not found or not a sequencer
   pub fn get_sequencer_step(&self, module_id: &str) -> i32 {
@@ -141,6 +235,21 @@ impl WasmGraphEngine {
     self.engine.load_particle_buffer(module_id, data);
   }
 
+  /// Load decoded audio (interleaved, 1 or 2 channels) into a SamplePlayer
+  /// module. Returns the number of frames loaded.
+  pub fn load_sample(
+    &mut self,
+    module_id: &str,
+    data: &[f32],
+    channels: usize,
+    sample_rate: f32,
+  ) -> Result<usize, JsValue> {
+    self
+      .engine
+      .load_sample(module_id, data, channels, sample_rate)
+      .map_err(|err| JsValue::from_str(&err))
+  }
+
   /// Load a SID file into a SidPlayer module
   pub fn load_sid_file(&mut self, module_id: &str, data: &[u8]) {
     self.engine.load_sid_file(module_id, data);
@@ -167,3 +276,21 @@ impl WasmGraphEngine {
     self.engine.get_ay_elapsed(module_id)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn render_ptr_is_stable_for_same_block_size() {
+    let mut engine = WasmGraphEngine::new(48000.0);
+    engine
+      .set_graph(r#"{"modules":[{"id":"osc","type":"oscillator"},{"id":"out","type":"output"}],"connections":[{"from":{"moduleId":"osc","portId":"out"},"to":{"moduleId":"out","portId":"in"},"kind":"audio"}]}"#)
+      .unwrap();
+    let first = engine.render_ptr(128);
+    for _ in 0..8 {
+      assert_eq!(engine.render_ptr(128), first);
+    }
+    assert_eq!(engine.output_channels(), 2);
+  }
+}
@@ -0,0 +1,126 @@
+//! Rolling scope buffer for graph taps.
+//!
+//! Mirrors the native app's `ScopeSnapshot`: keeps the last `frames` samples of
+//! every tap so the browser oscilloscope can pull an ordered window on demand
+//! instead of accumulating render blocks in JS.
+
+pub const SCOPE_FRAMES: usize = 2048;
+
+pub struct ScopeRing {
+  frames: usize,
+  data: Vec<Vec<f32>>,
+  write_index: usize,
+  filled: bool,
+}
+
+impl ScopeRing {
+  pub fn new(frames: usize) -> Self {
+    Self {
+      frames,
+      data: Vec::new(),
+      write_index: 0,
+      filled: false,
+    }
+  }
+
+  fn ensure_taps(&mut self, tap_count: usize) {
+    if self.data.len() == tap_count {
+      return;
+    }
+    self.data = (0..tap_count).map(|_| vec![0.0; self.frames]).collect();
+    self.write_index = 0;
+    self.filled = false;
+  }
+
+  /// Push one rendered block in the engine's planar layout
+  /// (`[L][R][tap 0][tap 1]...`, each `frames` long).
+  pub fn push_planar(&mut self, planar: &[f32], frames: usize) {
+    if frames == 0 {
+      return;
+    }
+    let channels = planar.len() / frames;
+    let tap_count = channels.saturating_sub(2);
+    self.ensure_taps(tap_count);
+    if tap_count == 0 {
+      return;
+    }
+
+    if frames >= self.frames {
+      let start = frames - self.frames;
+      for (tap_index, tap) in self.data.iter_mut().enumerate() {
+        let offset = (2 + tap_index) * frames;
+        tap.copy_from_slice(&planar[offset + start..offset + frames]);
+      }
+      self.write_index = 0;
+      self.filled = true;
+      return;
+    }
+
+    for (tap_index, tap) in self.data.iter_mut().enumerate() {
+      let offset = (2 + tap_index) * frames;
+      let block = &planar[offset..offset + frames];
+      let first = (self.frames - self.write_index).min(frames);
+      tap[self.write_index..self.write_index + first].copy_from_slice(&block[..first]);
+      tap[..frames - first].copy_from_slice(&block[first..]);
+    }
+
+    let end_index = self.write_index + frames;
+    if end_index >= self.frames {
+      self.filled = true;
+    }
+    self.write_index = end_index % self.frames;
+  }
+
+  /// Most recent `frames` samples of a tap, oldest first.
+  pub fn export(&self, tap_index: usize, frames: usize) -> Vec<f32> {
+    let Some(tap) = self.data.get(tap_index) else {
+      return Vec::new();
+    };
+    let available = if self.filled { self.frames } else { self.write_index };
+    let count = frames.min(available);
+    let mut ordered = Vec::with_capacity(count);
+    let start = (self.write_index + self.frames - count) % self.frames;
+    for i in 0..count {
+      ordered.push(tap[(start + i) % self.frames]);
+    }
+    ordered
+  }
+
+  pub fn clear(&mut self) {
+    self.data.clear();
+    self.write_index = 0;
+    self.filled = false;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn planar_block(frames: usize, start: f32) -> Vec<f32> {
+    // L, R silent; one tap carrying a ramp.
+    let mut block = vec![0.0; frames * 3];
+    for i in 0..frames {
+      block[2 * frames + i] = start + i as f32;
+    }
+    block
+  }
+
+  #[test]
+  fn export_is_ordered_after_wraparound() {
+    let mut ring = ScopeRing::new(8);
+    ring.push_planar(&planar_block(3, 0.0), 3);
+    ring.push_planar(&planar_block(3, 3.0), 3);
+    ring.push_planar(&planar_block(3, 6.0), 3);
+    assert_eq!(ring.export(0, 8), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+    assert_eq!(ring.export(0, 2), vec![7.0, 8.0]);
+  }
+
+  #[test]
+  fn export_before_filled_returns_written_frames() {
+    let mut ring = ScopeRing::new(8);
+    ring.push_planar(&planar_block(3, 0.0), 3);
+    assert_eq!(ring.export(0, 8), vec![0.0, 1.0, 2.0]);
+    assert!(ring.export(1, 8).is_empty());
+  }
+}
@@ -0,0 +1,37 @@
+#!/usr/bin/env python3
+"""Fail if any Criterion benchmark regressed beyond a threshold.
+
+Usage: check-bench-regression.py <criterion-dir> [threshold]
+
+Reads `change/estimates.json` written by `cargo bench -- --baseline <name>`
+and exits non-zero when the mean time increased more than `threshold`
+(a fraction, default 0.10 = 10%).
+"""
+
+import json
+import pathlib
+import sys
+
+
+def main() -> int:
+    root = pathlib.Path(sys.argv[1] if len(sys.argv) > 1 else "target/criterion")
+    threshold = float(sys.argv[2]) if len(sys.argv) > 2 else 0.10
+
+    regressions = []
+    for estimates in sorted(root.glob("**/change/estimates.json")):
+        bench = estimates.parent.parent.relative_to(root)
+        change = json.loads(estimates.read_text())["mean"]["point_estimate"]
+        print(f"{bench}: {change * 100:+.1f}%")
+        if change > threshold:
+            regressions.append((bench, change))
+
+    if regressions:
+        print(f"\n{len(regressions)} benchmark(s) regressed more than {threshold * 100:.0f}%:")
+        for bench, change in regressions:
+            print(f"  {bench}: {change * 100:+.1f}%")
+        return 1
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
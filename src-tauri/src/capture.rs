@@ -0,0 +1,259 @@
+//! Rolling capture of the master output.
+//!
+//! The audio callback writes bus 0 (L/R) into a preallocated ring of atomics,
+//! so capturing never locks or allocates on the audio thread. Exports copy
+//! the ring from another thread; frames the callback overwrites during the
+//! copy are dropped from the front of the snapshot.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+
+/// Shortest capture length the UI can ask for.
+pub const MIN_CAPTURE_SECONDS: f32 = 5.0;
+/// Longest capture length (about 23 MB at 48 kHz).
+pub const MAX_CAPTURE_SECONDS: f32 = 60.0;
+
+/// Stereo ring holding the last `capacity` frames of the master output.
+pub struct CaptureRing {
+  sample_rate: u32,
+  /// Interleaved L/R samples stored as `f32` bits.
+  samples: Box<[AtomicU32]>,
+  /// Frames written since the ring was created (single writer).
+  written: AtomicUsize,
+}
+
+impl CaptureRing {
+  pub fn new(seconds: f32, sample_rate: u32) -> Self {
+    let seconds = seconds.clamp(MIN_CAPTURE_SECONDS, MAX_CAPTURE_SECONDS);
+    let frames = ((seconds * sample_rate as f32) as usize).max(1);
+    Self {
+      sample_rate,
+      samples: (0..frames * 2).map(|_| AtomicU32::new(0)).collect(),
+      written: AtomicUsize::new(0),
+    }
+  }
+
+  pub fn sample_rate(&self) -> u32 {
+    self.sample_rate
+  }
+
+  fn capacity(&self) -> usize {
+    self.samples.len() / 2
+  }
+
+  /// Append one block. Called from the audio callback only.
+  pub fn push(&self, left: &[f32], right: &[f32]) {
+    let capacity = self.capacity();
+    let start = self.written.load(Ordering::Relaxed);
+    for (offset, (l, r)) in left.iter().zip(right).enumerate() {
+      let slot = (start + offset) % capacity * 2;
+      self.samples[slot].store(l.to_bits(), Ordering::Relaxed);
+      self.samples[slot + 1].store(r.to_bits(), Ordering::Relaxed);
+    }
+    self.written.store(start + left.len().min(right.len()), Ordering::Release);
+  }
+
+  /// Frames currently held (grows until the ring is full).
+  pub fn available_frames(&self) -> usize {
+    self.written.load(Ordering::Acquire).min(self.capacity())
+  }
+
+  pub fn available_seconds(&self) -> f32 {
+    self.available_frames() as f32 / self.sample_rate.max(1) as f32
+  }
+
+  /// Interleaved copy of the held frames, oldest first.
+  pub fn snapshot(&self) -> Vec<f32> {
+    let capacity = self.capacity();
+    let end = self.written.load(Ordering::Acquire);
+    let len = end.min(capacity);
+    let mut interleaved = Vec::with_capacity(len * 2);
+    for frame in end - len..end {
+      let slot = frame % capacity * 2;
+      interleaved.push(f32::from_bits(self.samples[slot].load(Ordering::Relaxed)));
+      interleaved.push(f32::from_bits(self.samples[slot + 1].load(Ordering::Relaxed)));
+    }
+    // Frames written meanwhile landed on the oldest slots we copied.
+    let overwritten = (self.written.load(Ordering::Acquire) - end).min(len);
+    interleaved.drain(..overwritten * 2);
+    interleaved
+  }
+}
+
+/// Audio-callback end of the capture: the ring it writes to, replaced
+/// whenever the command thread reconfigures the capture.
+pub struct CaptureTap {
+  ring: Option<Arc<CaptureRing>>,
+  updates: mpsc::Receiver<Option<Arc<CaptureRing>>>,
+}
+
+impl CaptureTap {
+  pub fn push(&mut self, left: &[f32], right: &[f32]) {
+    while let Ok(ring) = self.updates.try_recv() {
+      self.ring = ring;
+    }
+    if let Some(ring) = &self.ring {
+      ring.push(left, right);
+    }
+  }
+}
+
+/// Command-thread end of the capture.
+#[derive(Default)]
+pub struct CaptureControl {
+  /// Requested length; 0 when capture is off.
+  seconds: f32,
+  ring: Option<Arc<CaptureRing>>,
+  /// Rings the callback may still hold; freed here once it lets go, so the
+  /// audio thread never deallocates.
+  retired: Vec<Arc<CaptureRing>>,
+  tap: Option<mpsc::Sender<Option<Arc<CaptureRing>>>>,
+}
+
+impl CaptureControl {
+  /// Turn the capture on or off. A running stream starts filling a fresh
+  /// ring immediately; otherwise the ring is allocated with the next stream.
+  pub fn configure(&mut self, enabled: bool, seconds: f32, sample_rate: u32) {
+    self.seconds = if enabled {
+      seconds.clamp(MIN_CAPTURE_SECONDS, MAX_CAPTURE_SECONDS)
+    } else {
+      0.0
+    };
+    let ring = (self.seconds > 0.0 && sample_rate > 0 && self.tap.is_some())
+      .then(|| Arc::new(CaptureRing::new(self.seconds, sample_rate)));
+    self.replace(ring.clone());
+    if let Some(tap) = &self.tap {
+      let _ = tap.send(ring);
+    }
+  }
+
+  /// Callback end for a new output stream at `sample_rate`.
+  pub fn tap(&mut self, sample_rate: u32) -> CaptureTap {
+    let (tx, rx) = mpsc::channel();
+    self.tap = Some(tx);
+    let ring = (self.seconds > 0.0).then(|| Arc::new(CaptureRing::new(self.seconds, sample_rate)));
+    self.replace(ring.clone());
+    CaptureTap { ring, updates: rx }
+  }
+
+  /// The stream is gone; the last ring stays available for export.
+  pub fn detach(&mut self) {
+    self.tap = None;
+  }
+
+  fn replace(&mut self, ring: Option<Arc<CaptureRing>>) {
+    self.retired.retain(|old| Arc::strong_count(old) > 1);
+    if let Some(old) = std::mem::replace(&mut self.ring, ring) {
+      self.retired.push(old);
+    }
+  }
+
+  pub fn ring(&self) -> Option<Arc<CaptureRing>> {
+    self.ring.clone()
+  }
+
+  pub fn seconds(&self) -> f32 {
+    self.seconds
+  }
+
+  pub fn available_seconds(&self) -> f32 {
+    self.ring.as_ref().map_or(0.0, |ring| ring.available_seconds())
+  }
+}
+
+/// Write interleaved stereo samples as a 32-bit float WAV file.
+pub fn write_wav(path: &Path, sample_rate: u32, interleaved: &[f32]) -> io::Result<()> {
+  let mut file = BufWriter::new(File::create(path)?);
+  write_wav_to(&mut file, sample_rate, interleaved)?;
+  file.flush()
+}
+
+fn write_wav_to(out: &mut impl Write, sample_rate: u32, interleaved: &[f32]) -> io::Result<()> {
+  const CHANNELS: u16 = 2;
+  const BYTES_PER_SAMPLE: u16 = 4;
+  const FORMAT_IEEE_FLOAT: u16 = 3;
+  let data_len = u32::try_from(interleaved.len() * BYTES_PER_SAMPLE as usize)
+    .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "capture too long for WAV"))?;
+  let block_align = CHANNELS * BYTES_PER_SAMPLE;
+
+  out.write_all(b"RIFF")?;
+  out.write_all(&(36 + data_len).to_le_bytes())?;
+  out.write_all(b"WAVEfmt ")?;
+  out.write_all(&16_u32.to_le_bytes())?;
+  out.write_all(&FORMAT_IEEE_FLOAT.to_le_bytes())?;
+  out.write_all(&CHANNELS.to_le_bytes())?;
+  out.write_all(&sample_rate.to_le_bytes())?;
+  out.write_all(&(sample_rate * block_align as u32).to_le_bytes())?;
+  out.write_all(&block_align.to_le_bytes())?;
+  out.write_all(&(BYTES_PER_SAMPLE * 8).to_le_bytes())?;
+  out.write_all(b"data")?;
+  out.write_all(&data_len.to_le_bytes())?;
+  for sample in interleaved {
+    out.write_all(&sample.to_le_bytes())?;
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn ring_keeps_the_latest_frames_in_order() {
+    let ring = CaptureRing::new(MIN_CAPTURE_SECONDS, 10);
+    assert_eq!(ring.available_frames(), 0);
+    // 50 frames of capacity; write 120 in uneven blocks.
+    let frames: Vec<f32> = (0..120).map(|n| n as f32).collect();
+    for block in frames.chunks(7) {
+      let right: Vec<f32> = block.iter().map(|s| -s).collect();
+      ring.push(block, &right);
+    }
+
+    assert_eq!(ring.available_frames(), 50);
+    assert_eq!(ring.available_seconds(), MIN_CAPTURE_SECONDS);
+    let snapshot = ring.snapshot();
+    assert_eq!(snapshot.len(), 100);
+    for (index, frame) in snapshot.chunks(2).enumerate() {
+      let expected = (70 + index) as f32;
+      assert_eq!(frame, [expected, -expected]);
+    }
+  }
+
+  #[test]
+  fn wav_header_describes_stereo_float() {
+    let mut bytes = Vec::new();
+    write_wav_to(&mut bytes, 48000, &[0.5, -0.5, 0.25, -0.25]).unwrap();
+    assert_eq!(bytes.len(), 44 + 16);
+    assert_eq!(&bytes[0..4], b"RIFF");
+    assert_eq!(u32::from_le_bytes(bytes[4..8].try_into().unwrap()), 36 + 16);
+    assert_eq!(u16::from_le_bytes([bytes[20], bytes[21]]), 3);
+    assert_eq!(u16::from_le_bytes([bytes[22], bytes[23]]), 2);
+    assert_eq!(u32::from_le_bytes(bytes[24..28].try_into().unwrap()), 48000);
+    assert_eq!(&bytes[36..40], b"data");
+    assert_eq!(f32::from_le_bytes(bytes[44..48].try_into().unwrap()), 0.5);
+  }
+
+  #[test]
+  fn control_hands_new_rings_to_the_tap() {
+    let mut control = CaptureControl::default();
+    let mut tap = control.tap(100);
+    assert!(control.ring().is_none());
+
+    control.configure(true, 30.0, 100);
+    tap.push(&[1.0; 10], &[1.0; 10]);
+    assert_eq!(control.available_seconds(), 0.1);
+
+    // Reconfiguring swaps in an empty ring; the old one is retired, not
+    // dropped, while the tap still holds it.
+    control.configure(true, 10.0, 100);
+    assert_eq!(control.available_seconds(), 0.0);
+    assert_eq!(control.retired.len(), 1);
+    tap.push(&[1.0; 10], &[1.0; 10]);
+    control.configure(false, 10.0, 100);
+    assert_eq!(control.seconds(), 0.0);
+    assert!(control.ring().is_none());
+  }
+}
@@ -0,0 +1,43 @@
+//! Engine events (gate edges, sequencer steps, clock bars) for UI animation.
+//!
+//! The audio callback appends the events of each rendered block; the UI
+//! takes them through `native_get_events`. Frames are relative to the block
+//! that produced each event.
+
+use std::collections::VecDeque;
+
+use dsp_graph::GraphEvent;
+
+/// Events kept between two UI polls; the oldest are dropped past this.
+const EVENT_LOG_CAPACITY: usize = 2048;
+
+pub struct EventLog {
+  events: VecDeque<GraphEvent>,
+}
+
+impl Default for EventLog {
+  fn default() -> Self {
+    Self { events: VecDeque::with_capacity(EVENT_LOG_CAPACITY) }
+  }
+}
+
+impl EventLog {
+  /// Append events without growing past the capacity allocated up front.
+  pub fn push(&mut self, events: impl IntoIterator<Item = GraphEvent>) {
+    for event in events {
+      if self.events.len() == EVENT_LOG_CAPACITY {
+        self.events.pop_front();
+      }
+      self.events.push_back(event);
+    }
+  }
+
+  /// Events since the last call, oldest first.
+  pub fn take(&mut self) -> Vec<GraphEvent> {
+    self.events.drain(..).collect()
+  }
+
+  pub fn reset(&mut self) {
+    self.events.clear();
+  }
+}
@@ -1,15 +1,30 @@
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{FromSample, Sample, SampleFormat, StreamConfig};
-use dsp_core::{Node, SineOsc};
-use dsp_graph::GraphEngine;
-use dsp_ipc::{SharedParams, TauriBridge};
+use dsp_core::{parse_smf, Node, SineOsc, SmfFile};
+use dsp_graph::{
+  midi_data_json, ChannelMap, GraphEngine, GraphEvent, GraphEventKind, MidiData, ModuleFault, NoteRouter, Preset,
+  PRESET_EXTENSION,
+};
+use dsp_ipc::{BridgeError, EventSlot, FaultReport, SharedParams, TauriBridge, VoiceState};
 use midir::MidiInput;
 use serde::Serialize;
 use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
 use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
 use tauri::{Manager, State};
 
+mod capture;
+mod events;
+mod meter;
+mod sample_file;
+mod spectrum;
+
+use capture::{CaptureControl, CaptureRing, CaptureTap};
+use events::EventLog;
+use meter::{MeterPacket, OutputMeters};
+use sample_file::DecodedSample;
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct NativeStatus {
@@ -21,6 +36,12 @@ struct NativeStatus {
   input_sample_rate: u32,
   input_channels: u16,
   input_error: Option<String>,
+  /// Rolling capture length in seconds, 0 when off.
+  capture_seconds: f32,
+  /// Seconds of master output currently held by the capture.
+  capture_available_seconds: f32,
+  /// Modules of the running graph that panicked (and were silenced).
+  faults: Vec<ModuleFault>,
 }
 
 enum AudioCommand {
@@ -78,21 +99,71 @@ enum AudioCommand {
     slew: f32,
     reply: mpsc::Sender<Result<NativeStatus, String>>,
   },
-  SetMarioChannelCv {
+  SetBankChannelCv {
     module_id: String,
     channel: usize,
     value: f32,
     reply: mpsc::Sender<Result<NativeStatus, String>>,
   },
-  SetMarioChannelGate {
+  SetBankChannelGate {
     module_id: String,
     channel: usize,
     value: f32,
     reply: mpsc::Sender<Result<NativeStatus, String>>,
   },
+  // Multi-timbral MIDI routing
+  SetChannelMap {
+    map_json: String,
+    reply: mpsc::Sender<Result<NativeStatus, String>>,
+  },
+  /// Channel-tagged MIDI note routed through the channel map; velocity 0 is
+  /// a note off.
+  MidiNote {
+    channel: u8,
+    note: u8,
+    velocity: f32,
+    reply: mpsc::Sender<Result<NativeStatus, String>>,
+  },
+  SetScopeTrigger {
+    trigger: ScopeTrigger,
+    reply: mpsc::Sender<Result<NativeStatus, String>>,
+  },
+  SetOutputCeiling {
+    ceiling: Option<f32>,
+    reply: mpsc::Sender<Result<NativeStatus, String>>,
+  },
+  SetCapture {
+    enabled: bool,
+    seconds: f32,
+    reply: mpsc::Sender<Result<NativeStatus, String>>,
+  },
+  /// Hand out the capture ring so the export can read it off this thread.
+  ExportCapture {
+    reply: mpsc::Sender<Result<Arc<CaptureRing>, String>>,
+  },
+  StoreSnapshot {
+    slot: usize,
+    reply: mpsc::Sender<Result<NativeStatus, String>>,
+  },
+  RecallSnapshot {
+    slot: usize,
+    reply: mpsc::Sender<Result<NativeStatus, String>>,
+  },
+  MorphSnapshots {
+    slot_a: usize,
+    slot_b: usize,
+    t: f32,
+    reply: mpsc::Sender<Result<NativeStatus, String>>,
+  },
+  GetGraphJson {
+    reply: mpsc::Sender<Result<String, String>>,
+  },
   Status {
     reply: mpsc::Sender<Result<NativeStatus, String>>,
   },
+  Reset {
+    reply: mpsc::Sender<Result<NativeStatus, String>>,
+  },
   // SID/AY Player commands
   LoadSidFile {
     module_id: String,
@@ -140,58 +211,122 @@ enum AudioCommand {
     data: Vec<f32>,
     reply: mpsc::Sender<Result<usize, String>>,
   },
+  // Sample Player commands
+  LoadSample {
+    module_id: String,
+    sample: DecodedSample,
+    reply: mpsc::Sender<Result<usize, String>>,
+  },
+  // MIDI File Sequencer commands
+  LoadMidiFile {
+    module_id: String,
+    data: MidiData,
+    reply: mpsc::Sender<Result<(), String>>,
+  },
 }
 
 const SCOPE_FRAMES: usize = 2048;
 
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum ScopeTriggerMode {
+  #[default]
+  Free,
+  Rising,
+  Falling,
+}
+
+impl ScopeTriggerMode {
+  fn parse(mode: &str) -> Result<Self, String> {
+    match mode {
+      "free" => Ok(Self::Free),
+      "rising" => Ok(Self::Rising),
+      "falling" => Ok(Self::Falling),
+      other => Err(format!("unknown scope trigger mode: {other}")),
+    }
+  }
+}
+
+/// Oscilloscope-style trigger: with an edge mode, `export` starts the window
+/// at the latest crossing of `threshold` on tap `channel` (its left side for
+/// a stereo tap).
+#[derive(Clone, Copy, Debug, Default)]
+struct ScopeTrigger {
+  mode: ScopeTriggerMode,
+  threshold: f32,
+  channel: usize,
+}
+
+/// Rolling history of the graph taps. `data` holds one ring per planar
+/// channel, so a stereo tap owns two consecutive rings (L then R).
 #[derive(Default)]
 struct ScopeSnapshot {
   frames: usize,
-  tap_count: usize,
+  /// Channels per tap (1 = mono, 2 = stereo).
+  tap_channels: Vec<usize>,
   sample_rate: u32,
   data: Vec<Vec<f32>>,
   write_index: usize,
   filled: bool,
+  trigger: ScopeTrigger,
 }
 
 impl ScopeSnapshot {
   fn new(frames: usize) -> Self {
     Self {
       frames,
-      tap_count: 0,
+      tap_channels: Vec::new(),
       sample_rate: 0,
       data: Vec::new(),
       write_index: 0,
       filled: false,
+      trigger: ScopeTrigger::default(),
     }
   }
 
+  fn set_trigger(&mut self, trigger: ScopeTrigger) {
+    self.trigger = trigger;
+  }
+
+  /// Start of the latest trigger crossing in `ordered` that still leaves
+  /// `window` samples after it. `None` in free mode or without a crossing.
+  fn trigger_start(&self, ordered: &[f32], window: usize) -> Option<usize> {
+    let threshold = self.trigger.threshold;
+    let crossed = |prev: f32, cur: f32| match self.trigger.mode {
+      ScopeTriggerMode::Free => false,
+      ScopeTriggerMode::Rising => prev < threshold && cur >= threshold,
+      ScopeTriggerMode::Falling => prev > threshold && cur <= threshold,
+    };
+    let last = ordered.len().checked_sub(window)?;
+    (1..=last).rev().find(|&i| crossed(ordered[i - 1], ordered[i]))
+  }
+
   fn reset(&mut self) {
-    self.tap_count = 0;
+    self.tap_channels.clear();
     self.data.clear();
     self.write_index = 0;
     self.filled = false;
   }
 
-  fn ensure_taps(&mut self, tap_count: usize) {
-    if self.tap_count == tap_count && !self.data.is_empty() {
+  fn ensure_taps(&mut self, tap_channels: &[usize]) {
+    if self.tap_channels == tap_channels && !self.data.is_empty() {
       return;
     }
-    self.tap_count = tap_count;
-    self.data = (0..tap_count)
+    self.tap_channels = tap_channels.to_vec();
+    self.data = (0..tap_channels.iter().sum::<usize>())
       .map(|_| vec![0.0; self.frames])
       .collect();
     self.write_index = 0;
     self.filled = false;
   }
 
-  fn push(&mut self, tap_slices: &[&[f32]], sample_rate: u32) {
-    let tap_count = tap_slices.len();
-    if tap_count == 0 {
+  /// Append one block. `tap_slices` has one slice per planar tap channel,
+  /// grouped by `tap_channels`.
+  fn push(&mut self, tap_slices: &[&[f32]], tap_channels: &[usize], sample_rate: u32) {
+    if tap_slices.is_empty() || tap_slices.len() != tap_channels.iter().sum::<usize>() {
       return;
     }
     self.sample_rate = sample_rate;
-    self.ensure_taps(tap_count);
+    self.ensure_taps(tap_channels);
     let block_frames = tap_slices[0].len();
     if block_frames == 0 {
       return;
@@ -221,28 +356,82 @@ impl ScopeSnapshot {
     self.write_index = end_index % self.frames;
   }
 
+  /// A ring unrolled oldest first.
+  fn ordered(&self, ring: &[f32]) -> Vec<f32> {
+    let mut ordered = vec![0.0; self.frames];
+    if self.filled {
+      let head = &ring[self.write_index..];
+      let tail = &ring[..self.write_index];
+      ordered[..head.len()].copy_from_slice(head);
+      ordered[head.len()..].copy_from_slice(tail);
+    } else {
+      ordered.copy_from_slice(ring);
+    }
+    ordered
+  }
+
+  /// Recorded history of every tap, oldest first, with stereo taps summed
+  /// to mono. Ignores the trigger.
+  fn mono_history(&self) -> Option<Vec<Vec<f32>>> {
+    if self.tap_channels.is_empty() {
+      return None;
+    }
+    let available = if self.filled { self.frames } else { self.write_index };
+    let mut rings = self.data.iter();
+    let mut taps = Vec::with_capacity(self.tap_channels.len());
+    for &channels in &self.tap_channels {
+      let mut mono = self.ordered(rings.next()?);
+      if channels == 2 {
+        let right = self.ordered(rings.next()?);
+        for (left, right) in mono.iter_mut().zip(right) {
+          *left = 0.5 * (*left + right);
+        }
+      }
+      mono.drain(..self.frames - available);
+      taps.push(mono);
+    }
+    Some(taps)
+  }
+
   fn export(&self) -> Option<ScopePacket> {
-    if self.tap_count == 0 {
+    let tap_count = self.tap_channels.len();
+    if tap_count == 0 {
       return None;
     }
-    let mut data = Vec::with_capacity(self.tap_count);
-    for tap in 0..self.tap_count {
-      let mut ordered = vec![0.0; self.frames];
-      if self.filled {
-        let head = &self.data[tap][self.write_index..];
-        let tail = &self.data[tap][..self.write_index];
-        ordered[..head.len()].copy_from_slice(head);
-        ordered[head.len()..].copy_from_slice(tail);
-      } else {
-        ordered.copy_from_slice(&self.data[tap]);
+    let mut data: Vec<Vec<f32>> = self.data.iter().map(|ring| self.ordered(ring)).collect();
+
+    // Triggered modes show half the history, starting at the edge, so
+    // there is room to search for it. Without an edge (e.g. silence) the
+    // most recent half is shown, like a scope in auto mode.
+    let mut frames = self.frames;
+    if self.trigger.mode != ScopeTriggerMode::Free {
+      let window = self.frames / 2;
+      let tap = self.trigger.channel.min(tap_count - 1);
+      let channel = self.tap_channels[..tap].iter().sum::<usize>();
+      let start = self
+        .trigger_start(&data[channel], window)
+        .unwrap_or(self.frames - window);
+      for tap in &mut data {
+        tap.copy_within(start..start + window, 0);
+        tap.truncate(window);
       }
-      data.push(ordered);
+      frames = window;
+    }
+
+    let mut rings = data.into_iter();
+    let mut left = Vec::with_capacity(tap_count);
+    let mut right = Vec::with_capacity(tap_count);
+    for &channels in &self.tap_channels {
+      left.push(rings.next().unwrap_or_default());
+      right.push(if channels == 2 { rings.next().unwrap_or_default() } else { Vec::new() });
     }
     Some(ScopePacket {
       sample_rate: self.sample_rate,
-      frames: self.frames,
-      tap_count: self.tap_count,
-      data,
+      frames,
+      tap_count,
+      tap_channels: self.tap_channels.clone(),
+      data: left,
+      data_right: right,
     })
   }
 }
@@ -296,13 +485,45 @@ struct ScopePacket {
   sample_rate: u32,
   frames: usize,
   tap_count: usize,
+  /// Channels per tap (1 = mono, 2 = stereo).
+  tap_channels: Vec<usize>,
+  /// Per tap: the mono signal, or the left channel of a stereo tap.
   data: Vec<Vec<f32>>,
+  /// Per tap: the right channel of a stereo tap, empty for mono taps.
+  data_right: Vec<Vec<f32>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpectrumPacket {
+  sample_rate: u32,
+  fft_size: usize,
+  /// Frequency step between bins; bin `k` is `k * bin_hz` Hz.
+  bin_hz: f32,
+  tap_count: usize,
+  /// Per tap: `fft_size / 2 + 1` magnitudes from DC to Nyquist.
+  bins: Vec<Vec<f32>>,
+}
+
+impl SpectrumPacket {
+  fn from_history(sample_rate: u32, taps: &[Vec<f32>]) -> Self {
+    Self {
+      sample_rate,
+      fft_size: spectrum::SPECTRUM_SIZE,
+      bin_hz: spectrum::bin_hz(sample_rate, spectrum::SPECTRUM_SIZE),
+      tap_count: taps.len(),
+      bins: taps.iter().map(|tap| spectrum::magnitude_spectrum(tap)).collect(),
+    }
+  }
 }
 
 struct AudioThreadState {
   stream: Option<cpal::Stream>,
   input_stream: Option<cpal::Stream>,
   graph: Option<Arc<Mutex<GraphEngine>>>,
+  /// Engine kept from the last stop so a restart (e.g. device switch) with
+  /// the same graph only re-rates modules instead of reparsing the JSON.
+  idle_graph: Option<GraphEngine>,
   graph_json: Option<String>,
   device_name: Option<String>,
   sample_rate: u32,
@@ -313,14 +534,30 @@ struct AudioThreadState {
   input_error: Option<String>,
   input_buffer: Arc<Mutex<InputRing>>,
   scope: Arc<Mutex<ScopeSnapshot>>,
+  meters: Arc<Mutex<OutputMeters>>,
+  events: Arc<Mutex<EventLog>>,
+  /// MIDI channel -> control module routing and per-module voice pools.
+  note_router: NoteRouter,
+  /// Output limiter ceiling, kept across engine rebuilds.
+  output_ceiling: Option<f32>,
+  /// Rolling capture of the master output, kept across stream restarts.
+  capture: CaptureControl,
 }
 
+/// Device output is soft-clipped at 0 dBFS unless the UI changes it.
+const DEFAULT_OUTPUT_CEILING: f32 = 1.0;
+
 impl AudioThreadState {
-  fn new(scope: Arc<Mutex<ScopeSnapshot>>) -> Self {
+  fn new(
+    scope: Arc<Mutex<ScopeSnapshot>>,
+    meters: Arc<Mutex<OutputMeters>>,
+    events: Arc<Mutex<EventLog>>,
+  ) -> Self {
     Self {
       stream: None,
       input_stream: None,
       graph: None,
+      idle_graph: None,
       graph_json: None,
       device_name: None,
       sample_rate: 0,
@@ -331,6 +568,11 @@ impl AudioThreadState {
       input_error: None,
       input_buffer: Arc::new(Mutex::new(InputRing::new(0))),
       scope,
+      meters,
+      events,
+      note_router: NoteRouter::new(8),
+      output_ceiling: Some(DEFAULT_OUTPUT_CEILING),
+      capture: CaptureControl::default(),
     }
   }
 }
@@ -346,6 +588,13 @@ impl AudioThreadState {
       input_sample_rate: self.input_sample_rate,
       input_channels: self.input_channels,
       input_error: self.input_error.clone(),
+      capture_seconds: self.capture.seconds(),
+      capture_available_seconds: self.capture.available_seconds(),
+      faults: self
+        .graph
+        .as_ref()
+        .and_then(|graph| graph.lock().ok().map(|engine| engine.faults().to_vec()))
+        .unwrap_or_default(),
     }
   }
 }
@@ -353,15 +602,21 @@ impl AudioThreadState {
 struct NativeAudioState {
   tx: mpsc::Sender<AudioCommand>,
   scope: Arc<Mutex<ScopeSnapshot>>,
+  meters: Arc<Mutex<OutputMeters>>,
+  events: Arc<Mutex<EventLog>>,
 }
 
 impl NativeAudioState {
   fn new() -> Self {
     let (tx, rx) = mpsc::channel();
     let scope = Arc::new(Mutex::new(ScopeSnapshot::new(SCOPE_FRAMES)));
+    let meters = Arc::new(Mutex::new(OutputMeters::default()));
+    let events = Arc::new(Mutex::new(EventLog::default()));
     let thread_scope = Arc::clone(&scope);
-    thread::spawn(move || audio_thread(rx, thread_scope));
-    Self { tx, scope }
+    let thread_meters = Arc::clone(&meters);
+    let thread_events = Arc::clone(&events);
+    thread::spawn(move || audio_thread(rx, thread_scope, thread_meters, thread_events));
+    Self { tx, scope, meters, events }
   }
 }
 
@@ -383,8 +638,13 @@ where
     .map_err(|_| "native audio thread unavailable".to_string())?
 }
 
-fn audio_thread(rx: mpsc::Receiver<AudioCommand>, scope: Arc<Mutex<ScopeSnapshot>>) {
-  let mut state = AudioThreadState::new(scope);
+fn audio_thread(
+  rx: mpsc::Receiver<AudioCommand>,
+  scope: Arc<Mutex<ScopeSnapshot>>,
+  meters: Arc<Mutex<OutputMeters>>,
+  events: Arc<Mutex<EventLog>>,
+) {
+  let mut state = AudioThreadState::new(scope, meters, events);
   while let Ok(command) = rx.recv() {
     match command {
       AudioCommand::Start {
@@ -480,31 +740,100 @@ fn audio_thread(rx: mpsc::Receiver<AudioCommand>, scope: Arc<Mutex<ScopeSnapshot
         });
         let _ = reply.send(result.map(|_| state.status()));
       }
-      AudioCommand::SetMarioChannelCv {
+      AudioCommand::SetBankChannelCv {
         module_id,
         channel,
         value,
         reply,
       } => {
         let result = with_graph_mut(&mut state, |engine| {
-          engine.set_mario_channel_cv(&module_id, channel, value);
+          engine.set_bank_channel_cv(&module_id, channel, value);
         });
         let _ = reply.send(result.map(|_| state.status()));
       }
-      AudioCommand::SetMarioChannelGate {
+      AudioCommand::SetBankChannelGate {
         module_id,
         channel,
         value,
         reply,
       } => {
         let result = with_graph_mut(&mut state, |engine| {
-          engine.set_mario_channel_gate(&module_id, channel, value);
+          engine.set_bank_channel_gate(&module_id, channel, value);
         });
         let _ = reply.send(result.map(|_| state.status()));
       }
+      AudioCommand::SetChannelMap { map_json, reply } => {
+        let result = set_channel_map(&mut state, &map_json);
+        let _ = reply.send(result);
+      }
+      AudioCommand::MidiNote {
+        channel,
+        note,
+        velocity,
+        reply,
+      } => {
+        let result = route_midi_note(&mut state, channel, note, velocity);
+        let _ = reply.send(result.map(|_| state.status()));
+      }
+      AudioCommand::SetScopeTrigger { trigger, reply } => {
+        let result = state
+          .scope
+          .lock()
+          .map(|mut scope| scope.set_trigger(trigger))
+          .map_err(|_| "scope unavailable".to_string());
+        let _ = reply.send(result.map(|_| state.status()));
+      }
+      AudioCommand::SetOutputCeiling { ceiling, reply } => {
+        state.output_ceiling = ceiling;
+        let result = with_graph_mut(&mut state, |engine| engine.set_output_ceiling(ceiling));
+        let _ = reply.send(result.map(|_| state.status()));
+      }
+      AudioCommand::SetCapture { enabled, seconds, reply } => {
+        let sample_rate = state.sample_rate;
+        state.capture.configure(enabled, seconds, sample_rate);
+        let _ = reply.send(Ok(state.status()));
+      }
+      AudioCommand::ExportCapture { reply } => {
+        let _ = reply.send(capture_ring(&state));
+      }
+      AudioCommand::StoreSnapshot { slot, reply } => {
+        let result = with_snapshots(&mut state, |engine| engine.store_snapshot(slot));
+        let _ = reply.send(result.map(|_| state.status()));
+      }
+      AudioCommand::RecallSnapshot { slot, reply } => {
+        let result = with_snapshots(&mut state, |engine| engine.recall_snapshot(slot));
+        let _ = reply.send(result.map(|_| state.status()));
+      }
+      AudioCommand::MorphSnapshots {
+        slot_a,
+        slot_b,
+        t,
+        reply,
+      } => {
+        let result = with_snapshots(&mut state, |engine| engine.morph_snapshots(slot_a, slot_b, t));
+        let _ = reply.send(result.map(|_| state.status()));
+      }
+      AudioCommand::GetGraphJson { reply } => {
+        // Live params when an engine runs, the last graph sent otherwise.
+        let result = match &state.graph {
+          Some(graph) => match graph.lock() {
+            Ok(engine) => Ok(engine.to_graph_json()),
+            Err(_) => Err("graph engine unavailable".to_string()),
+          },
+          None => state.graph_json.clone().ok_or_else(|| "no graph loaded".to_string()),
+        };
+        let _ = reply.send(result);
+      }
       AudioCommand::Status { reply } => {
         let _ = reply.send(Ok(state.status()));
       }
+      AudioCommand::Reset { reply } => {
+        state.note_router.reset();
+        let result = with_graph_mut(&mut state, |engine| {
+          engine.reset();
+        });
+        let _ = reply.send(result.map(|_| state.status()));
+      }
       // SID/AY Player commands
       AudioCommand::LoadSidFile {
         module_id,
@@ -614,6 +943,28 @@ fn audio_thread(rx: mpsc::Receiver<AudioCommand>, scope: Arc<Mutex<ScopeSnapshot
         };
         let _ = reply.send(result);
       }
+      AudioCommand::LoadSample { module_id, sample, reply } => {
+        let result = match &state.graph {
+          Some(graph) => match graph.lock() {
+            Ok(mut engine) => {
+              engine.load_sample(&module_id, &sample.data, sample.channels, sample.sample_rate)
+            }
+            Err(_) => Err("graph engine unavailable".to_string()),
+          },
+          None => Err("no graph".to_string()),
+        };
+        let _ = reply.send(result);
+      }
+      AudioCommand::LoadMidiFile { module_id, data, reply } => {
+        let result = match &state.graph {
+          Some(graph) => match graph.lock() {
+            Ok(mut engine) => engine.set_module_midi_data(&module_id, data),
+            Err(_) => Err("graph engine unavailable".to_string()),
+          },
+          None => Err("no graph".to_string()),
+        };
+        let _ = reply.send(result);
+      }
     }
   }
 }
@@ -628,6 +979,10 @@ fn start_audio(
     return Ok(state.status());
   }
 
+  let graph_unchanged = match &graph_json {
+    Some(payload) => state.graph_json.as_deref() == Some(payload.as_str()),
+    None => true,
+  };
   if let Some(payload) = graph_json {
     state.graph_json = Some(payload);
   }
@@ -715,10 +1070,25 @@ fn start_audio(
     }
   }
 
-  let mut engine = GraphEngine::new(sample_rate as f32);
-  engine.set_graph_json(&graph_payload)?;
+  let engine = match state.idle_graph.take() {
+    Some(mut engine) if graph_unchanged => {
+      engine.set_sample_rate(sample_rate as f32);
+      engine.set_output_ceiling(state.output_ceiling);
+      engine
+    }
+    _ => {
+      let mut engine = GraphEngine::new(sample_rate as f32);
+      engine.set_stereo_taps(true);
+      engine.set_output_ceiling(state.output_ceiling);
+      engine.set_graph_json(&graph_payload).map_err(|err| err.to_string())?;
+      engine
+    }
+  };
   let graph = Arc::new(Mutex::new(engine));
   let scope = Arc::clone(&state.scope);
+  let meters = Arc::clone(&state.meters);
+  let events = Arc::clone(&state.events);
+  let capture = state.capture.tap(sample_rate);
   let stream = match output_config.sample_format() {
     SampleFormat::F32 => {
       build_graph_stream::<f32>(
@@ -726,6 +1096,9 @@ fn start_audio(
         &stream_config,
         graph.clone(),
         scope,
+        meters,
+        events,
+        capture,
         sample_rate,
         input_buffer.clone(),
       )?
@@ -736,6 +1109,9 @@ fn start_audio(
         &stream_config,
         graph.clone(),
         scope,
+        meters,
+        events,
+        capture,
         sample_rate,
         input_buffer.clone(),
       )?
@@ -746,6 +1122,9 @@ fn start_audio(
         &stream_config,
         graph.clone(),
         scope,
+        meters,
+        events,
+        capture,
         sample_rate,
         input_buffer.clone(),
       )?
@@ -775,7 +1154,13 @@ fn start_audio(
 fn stop_audio(state: &mut AudioThreadState) -> Result<NativeStatus, String> {
   state.stream = None;
   state.input_stream = None;
-  state.graph = None;
+  state.capture.detach();
+  // The stream owned the other handle, so the engine can be parked for reuse.
+  state.idle_graph = state
+    .graph
+    .take()
+    .and_then(|graph| Arc::try_unwrap(graph).ok())
+    .and_then(|graph| graph.into_inner().ok());
   state.input_device_name = None;
   state.input_sample_rate = 0;
   state.input_channels = 0;
@@ -786,9 +1171,24 @@ fn stop_audio(state: &mut AudioThreadState) -> Result<NativeStatus, String> {
   if let Ok(mut scope) = state.scope.lock() {
     scope.reset();
   }
+  if let Ok(mut meters) = state.meters.lock() {
+    meters.reset();
+  }
+  if let Ok(mut events) = state.events.lock() {
+    events.reset();
+  }
   Ok(state.status())
 }
 
+/// The capture ring, if it holds anything to export.
+fn capture_ring(state: &AudioThreadState) -> Result<Arc<CaptureRing>, String> {
+  let ring = state.capture.ring().ok_or("capture is off")?;
+  if ring.available_frames() == 0 {
+    return Err("capture is empty".to_string());
+  }
+  Ok(ring)
+}
+
 fn with_graph_mut<F>(state: &mut AudioThreadState, f: F) -> Result<(), String>
 where
   F: FnOnce(&mut GraphEngine),
@@ -800,11 +1200,79 @@ where
   Ok(())
 }
 
+/// Run a snapshot operation; snapshots belong to the loaded graph, so this
+/// fails when there is none.
+fn with_snapshots<F>(state: &mut AudioThreadState, f: F) -> Result<(), String>
+where
+  F: FnOnce(&mut GraphEngine) -> Result<(), String>,
+{
+  let graph = state.graph.as_ref().ok_or("no graph loaded")?;
+  let mut engine = graph.lock().map_err(|_| "graph engine unavailable")?;
+  f(&mut engine)
+}
+
+/// Replace the channel map, closing the gates of notes routed by the old one.
+fn set_channel_map(state: &mut AudioThreadState, map_json: &str) -> Result<NativeStatus, String> {
+  let map = ChannelMap::from_json(map_json)?;
+  let mut held = Vec::new();
+  state.note_router.active_voices(&mut held);
+  if let Some(graph) = &state.graph {
+    let mut engine = graph.lock().map_err(|_| "graph engine unavailable")?;
+    for routed in &held {
+      engine.set_control_voice_gate(state.note_router.module_id(routed.pool), routed.voice, 0.0);
+    }
+  }
+  state.note_router.reset();
+  state.note_router.install_map(map);
+  Ok(state.status())
+}
+
+fn route_midi_note(
+  state: &mut AudioThreadState,
+  channel: u8,
+  note: u8,
+  velocity: f32,
+) -> Result<(), String> {
+  let mut routed = Vec::new();
+  if velocity <= 0.0 {
+    state.note_router.note_off(channel, note, &mut routed);
+  } else {
+    state.note_router.note_on(channel, note, velocity, &mut routed);
+  }
+  let Some(graph) = &state.graph else {
+    return Ok(());
+  };
+  let mut engine = graph.lock().map_err(|_| "graph engine unavailable")?;
+  for routed in &routed {
+    let module_id = state.note_router.module_id(routed.pool);
+    if velocity <= 0.0 {
+      engine.set_control_voice_gate(module_id, routed.voice, 0.0);
+      continue;
+    }
+    let cv = engine.note_to_cv(module_id, routed.note);
+    engine.set_control_voice_cv(module_id, routed.voice, cv);
+    engine.set_control_voice_velocity(module_id, routed.voice, velocity, 0.005);
+    engine.trigger_control_voice_gate(module_id, routed.voice);
+  }
+  Ok(())
+}
+
 fn set_graph(state: &mut AudioThreadState, graph_json: String) -> Result<NativeStatus, String> {
   state.graph_json = Some(graph_json.clone());
+  state.idle_graph = None;
   if let Some(graph) = &state.graph {
-    let mut engine = graph.lock().map_err(|_| "graph engine unavailable")?;
-    engine.set_graph_json(&graph_json)?;
+    // Build the new graph without the engine lock so the audio callback
+    // keeps rendering; the lock is only held for the swap.
+    let compiled = GraphEngine::compile_graph_json(&graph_json, state.sample_rate as f32, true)
+      .map_err(|err| err.to_string())?;
+    for migration in compiled.migrations() {
+      eprintln!("Graph migrated: {migration}");
+    }
+    for diagnostic in compiled.diagnostics() {
+      eprintln!("{diagnostic}");
+    }
+    let retired = graph.lock().map_err(|_| "graph engine unavailable")?.install_compiled(compiled);
+    drop(retired);
   }
   Ok(state.status())
 }
@@ -913,11 +1381,15 @@ where
   }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn write_graph_output<T>(
   output: &mut [T],
   channels: usize,
   graph: &Arc<Mutex<GraphEngine>>,
   scope: &Arc<Mutex<ScopeSnapshot>>,
+  meters: &Arc<Mutex<OutputMeters>>,
+  events: &Arc<Mutex<EventLog>>,
+  capture: &mut CaptureTap,
   sample_rate: u32,
   input_buffer: &Arc<Mutex<InputRing>>,
 ) where
@@ -944,36 +1416,59 @@ fn write_graph_output<T>(
     } else if locked {
       engine.clear_external_input();
     }
+    let taps = engine.tap_count();
+    let tap_channels: Vec<usize> = (0..taps).map(|tap| engine.tap_channels(tap)).collect();
+    let tap_channel_total = engine.tap_channel_total();
+    let bus_count = engine.bus_count();
     let data = engine.render(frames);
-    let left = &data[0..frames];
-    let right = if data.len() >= frames * 2 {
-      &data[frames..frames * 2]
-    } else {
-      left
+    capture.push(&data[..frames], &data[frames..2 * frames]);
+    // Device channel pair `b` plays output bus `b`. With a single bus the
+    // extra channels duplicate the left side as before; otherwise channels
+    // past the last bus stay silent.
+    let device_source = |channel_index: usize| -> Option<usize> {
+      let bus = channel_index / 2;
+      let side = channel_index % 2;
+      if bus == 0 {
+        Some(side)
+      } else if bus < bus_count {
+        Some(tap_channel_total + 2 * bus + side)
+      } else if bus_count == 1 {
+        Some(0)
+      } else {
+        None
+      }
     };
 
     for (frame_index, frame) in output.chunks_mut(channels).enumerate() {
-      let l = left[frame_index];
-      let r = right[frame_index];
       for (channel_index, sample) in frame.iter_mut().enumerate() {
-        let value = if channel_index == 0 { l } else if channel_index == 1 { r } else { l };
+        let value = device_source(channel_index)
+          .map(|channel| data[channel * frames + frame_index])
+          .unwrap_or(0.0);
         *sample = T::from_sample(value);
       }
     }
 
-    let tap_count = data.len() / frames;
-    if tap_count > 2 {
-      let taps = tap_count - 2;
-      let mut tap_slices = Vec::with_capacity(taps);
-      for tap_index in 0..taps {
-        let start = (2 + tap_index) * frames;
-        let end = start + frames;
-        tap_slices.push(&data[start..end]);
-      }
+    if let Ok(mut meters) = meters.try_lock() {
+      let channel_slices: Vec<Option<&[f32]>> = (0..channels)
+        .map(|channel_index| {
+          device_source(channel_index).map(|channel| &data[channel * frames..(channel + 1) * frames])
+        })
+        .collect();
+      meters.push(&channel_slices, frames, sample_rate);
+    }
+
+    if taps > 0 {
+      let tap_slices: Vec<&[f32]> = (2..2 + tap_channel_total)
+        .map(|channel| &data[channel * frames..(channel + 1) * frames])
+        .collect();
       if let Ok(mut snapshot) = scope.try_lock() {
-        snapshot.push(&tap_slices, sample_rate);
+        snapshot.push(&tap_slices, &tap_channels, sample_rate);
       }
     }
+
+    if let Ok(mut events) = events.try_lock() {
+      events.push(engine.drain_events());
+    }
   } else {
     for sample in output.iter_mut() {
       *sample = T::EQUILIBRIUM;
@@ -981,11 +1476,15 @@ fn write_graph_output<T>(
   }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn build_graph_stream<T: Sample + FromSample<f32> + cpal::SizedSample>(
   device: &cpal::Device,
   config: &StreamConfig,
   graph: Arc<Mutex<GraphEngine>>,
   scope: Arc<Mutex<ScopeSnapshot>>,
+  meters: Arc<Mutex<OutputMeters>>,
+  events: Arc<Mutex<EventLog>>,
+  mut capture: CaptureTap,
   sample_rate: u32,
   input_buffer: Arc<Mutex<InputRing>>,
 ) -> Result<cpal::Stream, String> {
@@ -995,7 +1494,7 @@ fn build_graph_stream<T: Sample + FromSample<f32> + cpal::SizedSample>(
     .build_output_stream(
       config,
       move |data: &mut [T], _| {
-        write_graph_output(data, channels, &graph, &scope, sample_rate, &input_buffer)
+        write_graph_output(data, channels, &graph, &scope, &meters, &events, &mut capture, sample_rate, &input_buffer)
       },
       err_fn,
       None,
@@ -1184,13 +1683,35 @@ fn native_set_control_voice_velocity(
 }
 
 #[tauri::command]
-fn native_set_mario_channel_cv(
+fn native_set_channel_map(state: State<NativeAudioState>, map_json: String) -> Result<(), String> {
+  send_audio_command(&state, |reply| AudioCommand::SetChannelMap { map_json, reply }).map(|_| ())
+}
+
+#[tauri::command]
+fn native_midi_note(
+  state: State<NativeAudioState>,
+  channel: u8,
+  note: u8,
+  velocity: f32,
+) -> Result<(), String> {
+  send_audio_command(&state, |reply| AudioCommand::MidiNote {
+    channel,
+    note,
+    velocity,
+    reply,
+  })
+  .map(|_| ())
+}
+
+/// Set a `cv-bank` channel CV (1-based channel)
+#[tauri::command]
+fn native_set_bank_channel_cv(
   state: State<NativeAudioState>,
   module_id: String,
   channel: usize,
   value: f32,
 ) -> Result<(), String> {
-  send_audio_command(&state, |reply| AudioCommand::SetMarioChannelCv {
+  send_audio_command(&state, |reply| AudioCommand::SetBankChannelCv {
     module_id,
     channel,
     value,
@@ -1199,14 +1720,15 @@ fn native_set_mario_channel_cv(
   .map(|_| ())
 }
 
+/// Set a `cv-bank` channel gate (1-based channel)
 #[tauri::command]
-fn native_set_mario_channel_gate(
+fn native_set_bank_channel_gate(
   state: State<NativeAudioState>,
   module_id: String,
   channel: usize,
   value: f32,
 ) -> Result<(), String> {
-  send_audio_command(&state, |reply| AudioCommand::SetMarioChannelGate {
+  send_audio_command(&state, |reply| AudioCommand::SetBankChannelGate {
     module_id,
     channel,
     value,
@@ -1215,6 +1737,26 @@ fn native_set_mario_channel_gate(
   .map(|_| ())
 }
 
+#[tauri::command]
+fn native_set_mario_channel_cv(
+  state: State<NativeAudioState>,
+  module_id: String,
+  channel: usize,
+  value: f32,
+) -> Result<(), String> {
+  native_set_bank_channel_cv(state, module_id, channel, value)
+}
+
+#[tauri::command]
+fn native_set_mario_channel_gate(
+  state: State<NativeAudioState>,
+  module_id: String,
+  channel: usize,
+  value: f32,
+) -> Result<(), String> {
+  native_set_bank_channel_gate(state, module_id, channel, value)
+}
+
 #[tauri::command]
 fn native_start_graph(
   state: State<NativeAudioState>,
@@ -1240,12 +1782,176 @@ fn native_status(state: State<NativeAudioState>) -> Result<NativeStatus, String>
   send_audio_command(&state, |reply| AudioCommand::Status { reply })
 }
 
+#[tauri::command]
+fn native_reset(state: State<NativeAudioState>) -> Result<NativeStatus, String> {
+  send_audio_command(&state, |reply| AudioCommand::Reset { reply })
+}
+
 #[tauri::command]
 fn native_get_scope(state: State<NativeAudioState>) -> Result<ScopePacket, String> {
   let scope = state.scope.lock().map_err(|_| "scope unavailable")?;
   scope.export().ok_or_else(|| "scope not ready".to_string())
 }
 
+/// FFT magnitudes of the scope taps, computed here rather than in the audio
+/// callback.
+#[tauri::command]
+fn native_get_spectrum(state: State<NativeAudioState>) -> Result<SpectrumPacket, String> {
+  // Copy the history out so the audio thread is not locked out of the
+  // scope during the FFT.
+  let (sample_rate, taps) = {
+    let scope = state.scope.lock().map_err(|_| "scope unavailable")?;
+    (scope.sample_rate, scope.mono_history().ok_or("scope not ready")?)
+  };
+  Ok(SpectrumPacket::from_history(sample_rate, &taps))
+}
+
+/// Range, default and step of every numeric param of a module type, so the
+/// UI can build its controls from the engine's table.
+#[tauri::command]
+fn native_get_param_metadata(module_type: String) -> serde_json::Value {
+  GraphEngine::param_metadata(&module_type)
+}
+
+/// Peak (with hold) and RMS of each device output channel, in dBFS, and
+/// whether each clipped since the previous call.
+#[tauri::command]
+fn native_get_meters(state: State<NativeAudioState>) -> Result<MeterPacket, String> {
+  let mut meters = state.meters.lock().map_err(|_| "meters unavailable")?;
+  meters.export().ok_or_else(|| "meters not ready".to_string())
+}
+
+/// Gate edges, sequencer steps and clock bars rendered since the last call,
+/// for animating the UI in time with the audio.
+#[tauri::command]
+fn native_get_events(state: State<NativeAudioState>) -> Result<Vec<GraphEvent>, String> {
+  let mut events = state.events.lock().map_err(|_| "events unavailable")?;
+  Ok(events.take())
+}
+
+/// Soft-clip ceiling of the device output (linear, 1.0 = 0 dBFS); `null`
+/// disables the limiter. NaN/Inf samples are always muted.
+#[tauri::command]
+fn native_set_output_ceiling(state: State<NativeAudioState>, ceiling: Option<f32>) -> Result<(), String> {
+  send_audio_command(&state, |reply| AudioCommand::SetOutputCeiling { ceiling, reply }).map(|_| ())
+}
+
+/// Keep the last `seconds` (5-60) of the master output in a rolling buffer.
+/// Reconfiguring starts a fresh buffer; `NativeStatus.captureAvailableSeconds`
+/// reports how much is filled.
+#[tauri::command]
+fn native_set_capture(state: State<NativeAudioState>, enabled: bool, seconds: f32) -> Result<NativeStatus, String> {
+  send_audio_command(&state, |reply| AudioCommand::SetCapture { enabled, seconds, reply })
+}
+
+/// Write the captured master output to `path` as a 32-bit float WAV.
+/// Resolves once the file is written, or with the write error. The snapshot
+/// and the disk write run on a blocking worker, not the audio command thread.
+#[tauri::command]
+async fn native_export_capture(state: State<'_, NativeAudioState>, path: String) -> Result<(), String> {
+  let tx = state.tx.clone();
+  tauri::async_runtime::spawn_blocking(move || {
+    let (reply_tx, reply_rx) = mpsc::channel();
+    tx.send(AudioCommand::ExportCapture { reply: reply_tx })
+      .map_err(|_| "native audio thread unavailable".to_string())?;
+    let ring = reply_rx
+      .recv()
+      .map_err(|_| "native audio thread unavailable".to_string())??;
+    let samples = ring.snapshot();
+    capture::write_wav(Path::new(&path), ring.sample_rate(), &samples)
+      .map_err(|err| format!("capture export to {path} failed: {err}"))
+  })
+  .await
+  .map_err(|err| err.to_string())?
+}
+
+/// Store the current param values in snapshot `slot` (0-7).
+#[tauri::command]
+fn native_store_snapshot(state: State<NativeAudioState>, slot: usize) -> Result<(), String> {
+  send_audio_command(&state, |reply| AudioCommand::StoreSnapshot { slot, reply }).map(|_| ())
+}
+
+/// Restore the param values stored in snapshot `slot`.
+#[tauri::command]
+fn native_recall_snapshot(state: State<NativeAudioState>, slot: usize) -> Result<(), String> {
+  send_audio_command(&state, |reply| AudioCommand::RecallSnapshot { slot, reply }).map(|_| ())
+}
+
+/// Interpolate params between two stored snapshots (`t` from 0 to 1).
+#[tauri::command]
+fn native_morph_snapshots(
+  state: State<NativeAudioState>,
+  slot_a: usize,
+  slot_b: usize,
+  t: f32,
+) -> Result<(), String> {
+  send_audio_command(&state, |reply| AudioCommand::MorphSnapshots {
+    slot_a,
+    slot_b,
+    t,
+    reply,
+  })
+  .map(|_| ())
+}
+
+/// Save the current graph (with its live param values) as a preset file.
+/// The `.noobsynth` extension is added when `path` has none.
+#[tauri::command]
+fn native_save_preset(
+  state: State<NativeAudioState>,
+  name: String,
+  description: String,
+  path: String,
+) -> Result<(), String> {
+  let (reply_tx, reply_rx) = mpsc::channel();
+  state
+    .tx
+    .send(AudioCommand::GetGraphJson { reply: reply_tx })
+    .map_err(|_| "native audio thread unavailable".to_string())?;
+  let graph_json = reply_rx
+    .recv()
+    .map_err(|_| "native audio thread unavailable".to_string())??;
+  let preset = Preset {
+    name,
+    description,
+    graph_json,
+    ..Preset::default()
+  };
+  let mut path = PathBuf::from(path);
+  if path.extension().is_none() {
+    path.set_extension(PRESET_EXTENSION);
+  }
+  preset.save(&path)
+}
+
+/// Load a preset file, validate its graph and apply it. Returns the preset
+/// so the UI can rebuild its patch from `graphJson`.
+#[tauri::command]
+fn native_load_preset(state: State<NativeAudioState>, path: String) -> Result<Preset, String> {
+  let preset = Preset::load(Path::new(&path))?;
+  preset.validate().map_err(|err| err.to_string())?;
+  let graph_json = preset.graph_json.clone();
+  send_audio_command(&state, |reply| AudioCommand::SetGraph { graph_json, reply })?;
+  Ok(preset)
+}
+
+/// Set the scope trigger: `mode` is "free", "rising" or "falling";
+/// `channel` is the tap index the edge is detected on.
+#[tauri::command]
+fn native_set_scope_trigger(
+  state: State<NativeAudioState>,
+  mode: String,
+  threshold: f32,
+  channel: usize,
+) -> Result<(), String> {
+  let trigger = ScopeTrigger {
+    mode: ScopeTriggerMode::parse(&mode)?,
+    threshold,
+    channel,
+  };
+  send_audio_command(&state, |reply| AudioCommand::SetScopeTrigger { trigger, reply }).map(|_| ())
+}
+
 // ============================================================================
 // SID/AY Player Support
 // ============================================================================
@@ -1438,6 +2144,70 @@ fn native_load_granular_buffer(
     .map_err(|_| "native audio thread unavailable".to_string())?
 }
 
+/// Decode a WAV or FLAC file and load it into a Sample Player module.
+/// Returns the number of frames loaded.
+#[tauri::command]
+fn native_load_sample(
+  state: State<NativeAudioState>,
+  module_id: String,
+  data: Vec<u8>,
+) -> Result<usize, String> {
+  let sample = sample_file::decode(&data)?;
+  let (reply_tx, reply_rx) = mpsc::channel();
+  state
+    .tx
+    .send(AudioCommand::LoadSample {
+      module_id,
+      sample,
+      reply: reply_tx,
+    })
+    .map_err(|_| "native audio thread unavailable".to_string())?;
+  reply_rx
+    .recv()
+    .map_err(|_| "native audio thread unavailable".to_string())?
+}
+
+/// A `.mid` file loaded into a MIDI File Sequencer: the `midiData` param
+/// for the UI to keep in the patch, and what was dropped to fit.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MidiFileLoad {
+  midi_data: String,
+  warnings: Vec<String>,
+}
+
+fn read_midi_file(path: &str) -> Result<SmfFile, String> {
+  let data = std::fs::read(path).map_err(|err| format!("{path}: {err}"))?;
+  parse_smf(&data).map_err(|err| format!("{path}: {err}"))
+}
+
+#[tauri::command]
+fn native_load_midi_file(
+  state: State<NativeAudioState>,
+  module_id: String,
+  path: String,
+) -> Result<MidiFileLoad, String> {
+  // Serialized here so the engine lock is only held to load the notes.
+  let data = MidiData::from_file(read_midi_file(&path)?);
+  let load = MidiFileLoad {
+    midi_data: data.json().to_string(),
+    warnings: data.file().warnings.clone(),
+  };
+  let (reply_tx, reply_rx) = mpsc::channel();
+  state
+    .tx
+    .send(AudioCommand::LoadMidiFile {
+      module_id,
+      data,
+      reply: reply_tx,
+    })
+    .map_err(|_| "native audio thread unavailable".to_string())?;
+  reply_rx
+    .recv()
+    .map_err(|_| "native audio thread unavailable".to_string())??;
+  Ok(load)
+}
+
 // ============================================================================
 // VST Mode Support
 // ============================================================================
@@ -1467,6 +2237,30 @@ struct VstStatus {
   connected: bool,
   vst_connected: bool,
   sample_rate: u32,
+  /// Modules of the plugin's graph that panicked (and were silenced).
+  faults: Vec<VstFault>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VstFault {
+  stage: String,
+  module_id: String,
+  message: String,
+}
+
+impl From<FaultReport> for VstFault {
+  fn from(report: FaultReport) -> Self {
+    Self {
+      stage: report.stage,
+      module_id: report.module_id,
+      message: report.message,
+    }
+  }
+}
+
+fn vst_faults(bridge: &TauriBridge) -> Vec<VstFault> {
+  bridge.faults().into_iter().map(VstFault::from).collect()
 }
 
 /// Try to connect to VST shared memory
@@ -1481,6 +2275,7 @@ fn vst_connect(state: State<VstBridgeState>) -> Result<VstStatus, String> {
       connected: true,
       vst_connected: bridge.is_vst_connected(),
       sample_rate: bridge.sample_rate(),
+      faults: vst_faults(bridge),
     });
   }
 
@@ -1490,6 +2285,7 @@ fn vst_connect(state: State<VstBridgeState>) -> Result<VstStatus, String> {
       eprintln!("[NoobSynth] VST IPC bridge opened successfully");
       let sample_rate = bridge.sample_rate();
       let vst_connected = bridge.is_vst_connected();
+      let faults = vst_faults(&bridge);
       *bridge_lock = Some(bridge);
       if let Ok(mut last) = state.last_vst_graph_version.lock() {
         *last = 0;
@@ -1501,16 +2297,26 @@ fn vst_connect(state: State<VstBridgeState>) -> Result<VstStatus, String> {
         connected: true,
         vst_connected,
         sample_rate,
+        faults,
       })
     }
-    Err(open_err) => {
-      eprintln!("[NoobSynth] TauriBridge::open failed: {:?}", open_err);
+    // The plugin's segment is from another build: report it rather than
+    // creating a second one the plugin would never see
+    Err(open_err @ (BridgeError::BadMagic { .. }
+    | BridgeError::VersionMismatch { .. }
+    | BridgeError::LayoutMismatch { .. })) => {
+      eprintln!("[NoobSynth] TauriBridge::open failed: {}", open_err);
+      Err(open_err.to_string())
+    }
+    Err(open_err @ BridgeError::Shmem(_)) => {
+      eprintln!("[NoobSynth] TauriBridge::open failed: {}", open_err);
       // Try to create it (we might be starting before VST)
       match TauriBridge::new_with_id(instance_id) {
         Ok(bridge) => {
           eprintln!("[NoobSynth] VST IPC bridge created successfully");
           let sample_rate = bridge.sample_rate();
           let vst_connected = bridge.is_vst_connected();
+          let faults = vst_faults(&bridge);
           *bridge_lock = Some(bridge);
           if let Ok(mut last) = state.last_vst_graph_version.lock() {
             *last = 0;
@@ -1522,11 +2328,12 @@ fn vst_connect(state: State<VstBridgeState>) -> Result<VstStatus, String> {
             connected: true,
             vst_connected,
             sample_rate,
+            faults,
           })
         }
         Err(create_err) => {
-          eprintln!("[NoobSynth] TauriBridge::new failed: {:?}", create_err);
-          Err(format!("VST IPC failed - open: {:?}, create: {:?}", open_err, create_err))
+          eprintln!("[NoobSynth] TauriBridge::new failed: {}", create_err);
+          Err(format!("VST IPC failed - open: {}, create: {}", open_err, create_err))
         }
       }
     }
@@ -1556,11 +2363,13 @@ fn vst_status(state: State<VstBridgeState>) -> Result<VstStatus, String> {
       connected: true,
       vst_connected: bridge.is_vst_connected(),
       sample_rate: bridge.sample_rate(),
+      faults: vst_faults(bridge),
     }),
     None => Ok(VstStatus {
       connected: false,
       vst_connected: false,
       sample_rate: 0,
+      faults: Vec::new(),
     }),
   }
 }
@@ -1574,6 +2383,15 @@ fn vst_set_graph(state: State<VstBridgeState>, graph_json: String) -> Result<(),
   Ok(())
 }
 
+/// Set the plugin's MIDI channel map via VST
+#[tauri::command]
+fn vst_set_channel_map(state: State<VstBridgeState>, map_json: String) -> Result<(), String> {
+  let mut bridge_lock = state.bridge.lock().map_err(|_| "lock error")?;
+  let bridge = bridge_lock.as_mut().ok_or("VST not connected")?;
+  bridge.set_channel_map(&map_json);
+  Ok(())
+}
+
 /// Set parameter via VST
 #[tauri::command]
 fn vst_set_param(
@@ -1588,6 +2406,27 @@ fn vst_set_param(
   Ok(())
 }
 
+/// Load a `.mid` file into a MIDI File Sequencer of the plugin. The note
+/// list goes over in chunks, as it rarely fits the IPC string buffer.
+#[tauri::command]
+fn vst_load_midi_file(
+  state: State<VstBridgeState>,
+  module_id: String,
+  path: String,
+) -> Result<MidiFileLoad, String> {
+  let file = read_midi_file(&path)?;
+  let load = MidiFileLoad {
+    midi_data: midi_data_json(&file),
+    warnings: file.warnings,
+  };
+  let mut bridge_lock = state.bridge.lock().map_err(|_| "lock error")?;
+  let bridge = bridge_lock.as_mut().ok_or("VST not connected")?;
+  if !bridge.set_module_data(&module_id, "midiData", &load.midi_data) {
+    return Err("the plugin stopped taking commands; is it processing audio?".to_string());
+  }
+  Ok(load)
+}
+
 /// Fetch the current graph from the VST plugin (if available)
 #[tauri::command]
 fn vst_pull_graph(state: State<VstBridgeState>) -> Result<Option<String>, String> {
@@ -1649,6 +2488,54 @@ fn vst_pull_macros(state: State<VstBridgeState>) -> Result<Option<Vec<f32>>, Str
   Ok(Some(params.macros.to_vec()))
 }
 
+/// A VST voice as seen by the UI
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoiceStateDto {
+  pub cv: f32,
+  pub gate: f32,
+  pub velocity: f32,
+  /// `None` until the voice has played a note
+  pub note: Option<u8>,
+}
+
+impl From<VoiceState> for VoiceStateDto {
+  fn from(state: VoiceState) -> Self {
+    Self {
+      cv: state.cv,
+      gate: state.gate,
+      velocity: state.velocity,
+      note: (state.note != VoiceState::NO_NOTE).then_some(state.note),
+    }
+  }
+}
+
+/// Voice CV/gate/velocity last published by the VST
+#[tauri::command]
+fn vst_get_voice_states(state: State<VstBridgeState>) -> Result<Vec<VoiceStateDto>, String> {
+  let bridge_lock = state.bridge.lock().map_err(|_| "lock error")?;
+  let bridge = bridge_lock.as_ref().ok_or("VST not connected")?;
+  Ok(bridge.voice_states().into_iter().map(VoiceStateDto::from).collect())
+}
+
+/// Engine events published by the VST since the last call, in the
+/// `native_get_events` shape.
+#[tauri::command]
+fn vst_get_events(state: State<VstBridgeState>) -> Result<Vec<GraphEvent>, String> {
+  let mut bridge_lock = state.bridge.lock().map_err(|_| "lock error")?;
+  let bridge = bridge_lock.as_mut().ok_or("VST not connected")?;
+  Ok(bridge.pop_events().into_iter().filter_map(graph_event).collect())
+}
+
+fn graph_event(slot: EventSlot) -> Option<GraphEvent> {
+  Some(GraphEvent {
+    module_hash: slot.module_hash,
+    kind: GraphEventKind::from_code(slot.kind)?,
+    value: slot.value,
+    frame: slot.frame,
+  })
+}
+
 /// Set control voice CV via VST
 #[tauri::command]
 fn vst_set_control_voice_cv(
@@ -1676,6 +2563,15 @@ fn vst_trigger_control_voice_gate(
   Ok(())
 }
 
+/// Reset all DSP state via VST
+#[tauri::command]
+fn vst_reset(state: State<VstBridgeState>) -> Result<(), String> {
+  let mut bridge_lock = state.bridge.lock().map_err(|_| "lock error")?;
+  let bridge = bridge_lock.as_mut().ok_or("VST not connected")?;
+  bridge.reset();
+  Ok(())
+}
+
 /// Release gate via VST
 #[tauri::command]
 fn vst_release_control_voice_gate(
@@ -1791,12 +2687,30 @@ pub fn run() {
       native_trigger_control_voice_gate,
       native_trigger_control_voice_sync,
       native_set_control_voice_velocity,
+      native_set_bank_channel_cv,
+      native_set_bank_channel_gate,
       native_set_mario_channel_cv,
       native_set_mario_channel_gate,
+      native_set_channel_map,
+      native_midi_note,
       native_start_graph,
       native_stop_graph,
       native_status,
+      native_reset,
       native_get_scope,
+      native_get_spectrum,
+      native_get_meters,
+      native_get_events,
+      native_set_capture,
+      native_export_capture,
+      native_get_param_metadata,
+      native_set_output_ceiling,
+      native_store_snapshot,
+      native_recall_snapshot,
+      native_morph_snapshots,
+      native_save_preset,
+      native_load_preset,
+      native_set_scope_trigger,
       // SID/AY Player commands
       native_load_sid_file,
       native_load_ym_file,
@@ -1807,22 +2721,29 @@ pub fn run() {
       // Sequencer commands
       native_get_sequencer_step,
       native_seek_midi_sequencer,
+      native_load_midi_file,
       // Granular commands
       native_get_granular_position,
       native_load_granular_buffer,
+      native_load_sample,
       // VST mode commands
       is_vst_mode,
       vst_connect,
       vst_disconnect,
       vst_status,
       vst_set_graph,
+      vst_set_channel_map,
       vst_set_param,
+      vst_load_midi_file,
       vst_pull_graph,
       vst_set_macros,
       vst_pull_macros,
+      vst_get_voice_states,
+      vst_get_events,
       vst_set_control_voice_cv,
       vst_trigger_control_voice_gate,
       vst_release_control_voice_gate,
+      vst_reset,
       vst_set_control_voice_velocity,
       vst_note_on,
       vst_note_off
@@ -1874,3 +2795,90 @@ pub fn run() {
     .run(tauri::generate_context!())
     .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rising_trigger_starts_window_at_zero_crossing() {
+    let sample_rate = 48000;
+    let mut scope = ScopeSnapshot::new(SCOPE_FRAMES);
+    scope.set_trigger(ScopeTrigger {
+      mode: ScopeTriggerMode::Rising,
+      threshold: 0.0,
+      channel: 0,
+    });
+
+    // 441 Hz does not divide the block size, so the raw window drifts.
+    let step = std::f32::consts::TAU * 441.0 / sample_rate as f32;
+    let mut phase = 0.3_f32;
+    for _ in 0..20 {
+      let block: Vec<f32> = (0..128)
+        .map(|_| {
+          phase += step;
+          phase.sin()
+        })
+        .collect();
+      scope.push(&[&block], &[1], sample_rate);
+    }
+
+    let packet = scope.export().unwrap();
+    assert_eq!(packet.frames, SCOPE_FRAMES / 2);
+    let samples = &packet.data[0];
+    assert_eq!(samples.len(), SCOPE_FRAMES / 2);
+    assert!(samples[0] >= 0.0 && samples[0] < step, "starts at {}", samples[0]);
+    assert!(samples[1] > samples[0], "window must start on a rising edge");
+  }
+
+  #[test]
+  fn stereo_tap_keeps_left_and_right_in_packet() {
+    let graph = r#"{
+      "modules": [
+        { "id": "noise-1", "type": "noise", "params": { "pan": -1 } },
+        { "id": "out-1", "type": "output", "params": { "level": 1 } }
+      ],
+      "connections": [
+        { "from": { "moduleId": "noise-1", "portId": "out" }, "to": { "moduleId": "out-1", "portId": "in" }, "kind": "audio" }
+      ],
+      "taps": [{ "moduleId": "out-1", "portId": "in" }]
+    }"#;
+    let mut engine = GraphEngine::new(48000.0);
+    engine.set_stereo_taps(true);
+    engine.set_graph_json(graph).unwrap();
+    let tap_channels = [engine.tap_channels(0)];
+    assert_eq!(tap_channels, [2]);
+
+    let mut scope = ScopeSnapshot::new(SCOPE_FRAMES);
+    let frames = 128;
+    for _ in 0..SCOPE_FRAMES / frames {
+      let data = engine.render(frames);
+      let slices = [&data[2 * frames..3 * frames], &data[3 * frames..4 * frames]];
+      scope.push(&slices, &tap_channels, 48000);
+    }
+
+    let packet = scope.export().unwrap();
+    assert_eq!(packet.tap_channels, vec![2]);
+    let (left, right) = (&packet.data[0], &packet.data_right[0]);
+    assert_eq!(left.len(), right.len());
+    assert!(left.iter().any(|s| s.abs() > 0.1), "left channel is silent");
+    assert!(right.iter().all(|s| s.abs() < 1e-6), "hard-left tap leaked into the right channel");
+  }
+
+  #[test]
+  fn spectrum_packet_reports_tone_bin() {
+    let sample_rate = 48000;
+    let mut scope = ScopeSnapshot::new(SCOPE_FRAMES);
+    let step = std::f32::consts::TAU * 3000.0 / sample_rate as f32;
+    let block: Vec<f32> = (0..SCOPE_FRAMES).map(|n| (step * n as f32).sin()).collect();
+    scope.push(&[&block], &[1], sample_rate);
+
+    let packet = SpectrumPacket::from_history(sample_rate, &scope.mono_history().unwrap());
+    assert_eq!(packet.tap_count, 1);
+    assert_eq!(packet.bins[0].len(), packet.fft_size / 2 + 1);
+    let peak_bin = (0..packet.bins[0].len())
+      .max_by(|&a, &b| packet.bins[0][a].total_cmp(&packet.bins[0][b]))
+      .unwrap();
+    assert_eq!(peak_bin as f32 * packet.bin_hz, 3000.0);
+  }
+}
@@ -0,0 +1,184 @@
+//! Peak/RMS metering of the device output.
+//!
+//! Updated from the audio callback after each block is written; the UI polls
+//! the levels in dBFS through `native_get_meters`. Each poll also reports
+//! which channels clipped since the previous one, for clip LEDs.
+
+use serde::Serialize;
+
+/// How long a new peak is held before it starts to fall.
+const PEAK_HOLD_SECONDS: f32 = 1.0;
+/// Fall rate of the held peak once the hold expires.
+const PEAK_DECAY_DB_PER_SECOND: f32 = 20.0;
+/// Length of the RMS window.
+const RMS_WINDOW_SECONDS: f32 = 0.3;
+/// Level reported for silence.
+const METER_FLOOR_DB: f32 = -120.0;
+
+fn to_db(level: f32) -> f32 {
+  if level <= 0.0 {
+    METER_FLOOR_DB
+  } else {
+    (20.0 * level.log10()).max(METER_FLOOR_DB)
+  }
+}
+
+#[derive(Default)]
+struct ChannelMeter {
+  peak: f32,
+  /// Samples left before the held peak decays.
+  hold: usize,
+  /// Squared samples of the RMS window (ring).
+  squares: Vec<f32>,
+  sum: f64,
+  index: usize,
+  /// Samples in the window so far, until it first fills.
+  count: usize,
+  /// A sample went past full scale since the last export.
+  clipped: bool,
+}
+
+impl ChannelMeter {
+  fn new(window: usize) -> Self {
+    Self {
+      squares: vec![0.0; window.max(1)],
+      ..Self::default()
+    }
+  }
+
+  fn push(&mut self, samples: impl Iterator<Item = f32>, hold: usize, decay: f32) {
+    for sample in samples {
+      let level = sample.abs();
+      self.clipped |= level > 1.0;
+      if level >= self.peak {
+        self.peak = level;
+        self.hold = hold;
+      } else if self.hold > 0 {
+        self.hold -= 1;
+      } else {
+        self.peak *= decay;
+      }
+
+      let square = sample * sample;
+      self.sum += square as f64 - self.squares[self.index] as f64;
+      self.squares[self.index] = square;
+      self.index = (self.index + 1) % self.squares.len();
+      self.count = (self.count + 1).min(self.squares.len());
+    }
+  }
+
+  fn rms(&self) -> f32 {
+    if self.count == 0 {
+      return 0.0;
+    }
+    (self.sum.max(0.0) / self.count as f64).sqrt() as f32
+  }
+}
+
+/// Per-channel peak hold and windowed RMS of what the device plays.
+#[derive(Default)]
+pub struct OutputMeters {
+  sample_rate: u32,
+  channels: Vec<ChannelMeter>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MeterPacket {
+  sample_rate: u32,
+  /// Per device channel, in dBFS (floored at `METER_FLOOR_DB`).
+  peak_db: Vec<f32>,
+  rms_db: Vec<f32>,
+  /// Per device channel, whether a sample exceeded ±1.0 since the last poll.
+  clipped: Vec<bool>,
+}
+
+impl OutputMeters {
+  /// Meter one block of `frames` samples. `channels` holds one slice per
+  /// device channel, `None` for channels that play silence.
+  pub fn push(&mut self, channels: &[Option<&[f32]>], frames: usize, sample_rate: u32) {
+    if self.sample_rate != sample_rate || self.channels.len() != channels.len() {
+      let window = (RMS_WINDOW_SECONDS * sample_rate as f32) as usize;
+      self.sample_rate = sample_rate;
+      self.channels = (0..channels.len()).map(|_| ChannelMeter::new(window)).collect();
+    }
+    let rate = sample_rate.max(1) as f32;
+    let hold = (PEAK_HOLD_SECONDS * rate) as usize;
+    let decay = 10.0_f32.powf(-PEAK_DECAY_DB_PER_SECOND / (20.0 * rate));
+    for (meter, samples) in self.channels.iter_mut().zip(channels) {
+      match samples {
+        Some(samples) => meter.push(samples.iter().copied(), hold, decay),
+        None => meter.push(std::iter::repeat(0.0).take(frames), hold, decay),
+      }
+    }
+  }
+
+  pub fn reset(&mut self) {
+    self.sample_rate = 0;
+    self.channels.clear();
+  }
+
+  /// Current levels. Clears the clip flags, so each clip is reported once.
+  pub fn export(&mut self) -> Option<MeterPacket> {
+    if self.channels.is_empty() {
+      return None;
+    }
+    Some(MeterPacket {
+      sample_rate: self.sample_rate,
+      peak_db: self.channels.iter().map(|meter| to_db(meter.peak)).collect(),
+      rms_db: self.channels.iter().map(|meter| to_db(meter.rms())).collect(),
+      clipped: self.channels.iter_mut().map(|meter| std::mem::take(&mut meter.clipped)).collect(),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::f32::consts::TAU;
+
+  #[test]
+  fn half_scale_sine_reads_minus_six_peak_and_minus_nine_rms() {
+    let sample_rate = 48000;
+    let mut meters = OutputMeters::default();
+    let step = TAU * 1000.0 / sample_rate as f32;
+    for block in 0..200 {
+      let samples: Vec<f32> = (0..256)
+        .map(|n| 0.5 * (step * (block * 256 + n) as f32).sin())
+        .collect();
+      meters.push(&[Some(&samples), None], samples.len(), sample_rate);
+    }
+
+    let packet = meters.export().unwrap();
+    assert!((packet.peak_db[0] + 6.02).abs() < 0.05, "peak {}", packet.peak_db[0]);
+    assert!((packet.rms_db[0] + 9.03).abs() < 0.05, "rms {}", packet.rms_db[0]);
+    assert_eq!(packet.peak_db[1], METER_FLOOR_DB);
+
+    // Silence: the peak holds, then falls at the decay rate.
+    let silence = vec![0.0; sample_rate as usize];
+    meters.push(&[Some(&silence), None], silence.len(), sample_rate);
+    let held = meters.export().unwrap().peak_db[0];
+    assert!((held + 6.02).abs() < 0.05, "peak should still be held: {held}");
+    meters.push(&[Some(&silence), None], silence.len(), sample_rate);
+    let packet = meters.export().unwrap();
+    assert!((packet.peak_db[0] - held + PEAK_DECAY_DB_PER_SECOND).abs() < 0.1, "{}", packet.peak_db[0]);
+    assert_eq!(packet.rms_db[0], METER_FLOOR_DB);
+
+    meters.reset();
+    assert!(meters.export().is_none());
+  }
+
+  #[test]
+  fn clip_flag_latches_until_the_next_poll() {
+    let sample_rate = 48000;
+    let mut meters = OutputMeters::default();
+    let mut samples = vec![0.5; 256];
+    samples[100] = -1.2;
+    meters.push(&[Some(&samples), Some(&[1.0; 256])], samples.len(), sample_rate);
+    // Later blocks below full scale do not clear it.
+    meters.push(&[Some(&[0.1; 256]), None], 256, sample_rate);
+
+    assert_eq!(meters.export().unwrap().clipped, [true, false]);
+    assert_eq!(meters.export().unwrap().clipped, [false, false]);
+  }
+}
@@ -0,0 +1,146 @@
+//! Audio file decoding for the Sample Player module.
+//!
+//! Files are decoded here, off the audio thread, so the DSP crates never
+//! link a codec: the engine only receives interleaved `f32` frames.
+
+use std::io::Cursor;
+
+use dsp_core::MAX_SAMPLE_FRAMES;
+
+/// Decoded audio, interleaved.
+pub struct DecodedSample {
+  pub data: Vec<f32>,
+  pub channels: usize,
+  pub sample_rate: f32,
+}
+
+/// Decode a WAV (PCM or float) or FLAC file, detected from its header.
+pub fn decode(bytes: &[u8]) -> Result<DecodedSample, String> {
+  if bytes.starts_with(b"fLaC") {
+    decode_flac(bytes)
+  } else if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WAVE" {
+    decode_wav(bytes)
+  } else {
+    Err("Unsupported audio file (expected WAV or FLAC)".to_string())
+  }
+}
+
+fn check_length(frames: u64) -> Result<(), String> {
+  if frames > MAX_SAMPLE_FRAMES as u64 {
+    return Err(format!(
+      "Sample too long: {frames} frames (max {MAX_SAMPLE_FRAMES}, 60 s at 48 kHz)"
+    ));
+  }
+  Ok(())
+}
+
+/// Scale factor from a signed integer sample of `bits` bits to [-1, 1).
+fn int_scale(bits: u32) -> f32 {
+  1.0 / (1_u64 << bits.clamp(1, 32).saturating_sub(1)) as f32
+}
+
+fn decode_wav(bytes: &[u8]) -> Result<DecodedSample, String> {
+  let mut reader =
+    hound::WavReader::new(Cursor::new(bytes)).map_err(|err| format!("Invalid WAV file: {err}"))?;
+  let spec = reader.spec();
+  check_length(reader.duration() as u64)?;
+  let data = match spec.sample_format {
+    hound::SampleFormat::Float => reader.samples::<f32>().collect::<Result<Vec<_>, _>>(),
+    hound::SampleFormat::Int => {
+      let scale = int_scale(spec.bits_per_sample as u32);
+      reader
+        .samples::<i32>()
+        .map(|sample| sample.map(|value| value as f32 * scale))
+        .collect()
+    }
+  }
+  .map_err(|err| format!("Invalid WAV file: {err}"))?;
+  Ok(DecodedSample {
+    data,
+    channels: spec.channels as usize,
+    sample_rate: spec.sample_rate as f32,
+  })
+}
+
+fn decode_flac(bytes: &[u8]) -> Result<DecodedSample, String> {
+  let mut reader =
+    claxon::FlacReader::new(Cursor::new(bytes)).map_err(|err| format!("Invalid FLAC file: {err}"))?;
+  let info = reader.streaminfo();
+  if let Some(frames) = info.samples {
+    check_length(frames)?;
+  }
+  let scale = int_scale(info.bits_per_sample);
+  let data = reader
+    .samples()
+    .map(|sample| sample.map(|value| value as f32 * scale))
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|err| format!("Invalid FLAC file: {err}"))?;
+  Ok(DecodedSample {
+    data,
+    channels: info.channels as usize,
+    sample_rate: info.sample_rate as f32,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn wav_bytes(spec: hound::WavSpec, write: impl FnOnce(&mut hound::WavWriter<&mut Cursor<Vec<u8>>>)) -> Vec<u8> {
+    let mut cursor = Cursor::new(Vec::new());
+    let mut writer = hound::WavWriter::new(&mut cursor, spec).unwrap();
+    write(&mut writer);
+    writer.finalize().unwrap();
+    cursor.into_inner()
+  }
+
+  #[test]
+  fn decodes_pcm_and_float_wav() {
+    let pcm = wav_bytes(
+      hound::WavSpec {
+        channels: 2,
+        sample_rate: 44100,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+      },
+      |writer| {
+        for sample in [16384_i16, -32768, 0, 32767] {
+          writer.write_sample(sample).unwrap();
+        }
+      },
+    );
+    let decoded = decode(&pcm).unwrap();
+    assert_eq!(decoded.channels, 2);
+    assert_eq!(decoded.sample_rate, 44100.0);
+    assert_eq!(&decoded.data[..3], &[0.5, -1.0, 0.0]);
+
+    let float = wav_bytes(
+      hound::WavSpec {
+        channels: 1,
+        sample_rate: 48000,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+      },
+      |writer| writer.write_sample(0.25_f32).unwrap(),
+    );
+    assert_eq!(decode(&float).unwrap().data, vec![0.25]);
+  }
+
+  #[test]
+  fn rejects_unknown_and_oversized_files() {
+    assert!(decode(b"OggS not a wav").is_err());
+    let spec = hound::WavSpec {
+      channels: 1,
+      sample_rate: 48000,
+      bits_per_sample: 8,
+      sample_format: hound::SampleFormat::Int,
+    };
+    let long = wav_bytes(spec, |writer| {
+      for _ in 0..=MAX_SAMPLE_FRAMES {
+        writer.write_sample(0_i8).unwrap();
+      }
+    });
+    let err = decode(&long).err().unwrap();
+    assert!(err.contains("too long"), "{err}");
+  }
+}
@@ -0,0 +1,105 @@
+//! Magnitude spectrum of scope taps.
+//!
+//! Runs on the command thread from the stored scope history, never in the
+//! audio callback.
+
+use std::f32::consts::TAU;
+
+/// FFT length; `SPECTRUM_SIZE / 2 + 1` bins from DC to Nyquist.
+pub const SPECTRUM_SIZE: usize = 1024;
+
+/// Width of one bin in Hz.
+pub fn bin_hz(sample_rate: u32, size: usize) -> f32 {
+  sample_rate as f32 / size as f32
+}
+
+/// Hann-windowed magnitude spectrum of the last `SPECTRUM_SIZE` samples
+/// (zero-padded in front when fewer are available). Scaled so a full-scale
+/// sine centred on a bin reads 1.0.
+pub fn magnitude_spectrum(samples: &[f32]) -> Vec<f32> {
+  let size = SPECTRUM_SIZE;
+  let tail = &samples[samples.len().saturating_sub(size)..];
+  let offset = size - tail.len();
+
+  let mut re = vec![0.0_f32; size];
+  let mut im = vec![0.0_f32; size];
+  let mut window_sum = 0.0;
+  for n in 0..size {
+    let window = 0.5 - 0.5 * (TAU * n as f32 / size as f32).cos();
+    window_sum += window;
+    if n >= offset {
+      re[n] = tail[n - offset] * window;
+    }
+  }
+
+  fft_in_place(&mut re, &mut im);
+
+  let scale = 2.0 / window_sum;
+  (0..=size / 2)
+    .map(|bin| (re[bin] * re[bin] + im[bin] * im[bin]).sqrt() * scale)
+    .collect()
+}
+
+/// Iterative radix-2 FFT; `re.len()` must be a power of two.
+fn fft_in_place(re: &mut [f32], im: &mut [f32]) {
+  let n = re.len();
+  let mut j = 0;
+  for i in 1..n {
+    let mut bit = n >> 1;
+    while j & bit != 0 {
+      j ^= bit;
+      bit >>= 1;
+    }
+    j |= bit;
+    if i < j {
+      re.swap(i, j);
+      im.swap(i, j);
+    }
+  }
+
+  let mut len = 2;
+  while len <= n {
+    let angle = -TAU / len as f32;
+    for start in (0..n).step_by(len) {
+      for k in 0..len / 2 {
+        let (sin, cos) = (angle * k as f32).sin_cos();
+        let a = start + k;
+        let b = a + len / 2;
+        let tr = re[b] * cos - im[b] * sin;
+        let ti = re[b] * sin + im[b] * cos;
+        re[b] = re[a] - tr;
+        im[b] = im[a] - ti;
+        re[a] += tr;
+        im[a] += ti;
+      }
+    }
+    len <<= 1;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn pure_tone_peaks_at_its_bin() {
+    let sample_rate = 48000;
+    let freq = 1000.0;
+    let tone: Vec<f32> = (0..2048)
+      .map(|n| 0.5 * (TAU * freq * n as f32 / sample_rate as f32).sin())
+      .collect();
+
+    let bins = magnitude_spectrum(&tone);
+    assert_eq!(bins.len(), SPECTRUM_SIZE / 2 + 1);
+    let (peak_bin, &peak) = bins
+      .iter()
+      .enumerate()
+      .max_by(|a, b| a.1.total_cmp(b.1))
+      .unwrap();
+    let expected = (freq / bin_hz(sample_rate, SPECTRUM_SIZE)).round() as usize;
+    assert_eq!(peak_bin, expected);
+    // 1000 Hz sits between bins, so the Hann main lobe loses a little.
+    assert!(peak > 0.35 && peak < 0.55, "{peak}");
+    assert!(bins[expected * 3] < 0.01, "energy far from the tone: {}", bins[expected * 3]);
+  }
+}
@@ -21,7 +21,7 @@ import {
   hasSameModuleShape,
   isRecord,
 } from './state/graphUtils'
-import { clampMidiNote, clampVoiceCount } from './state/midiUtils'
+import { clampMidiNote, clampVoiceCount, readTuning } from './state/midiUtils'
 import {
   DEFAULT_GRID_METRICS,
   type GridMetrics,
@@ -36,6 +36,7 @@ import {
   readGridMetrics,
 } from './state/gridLayout'
 import { buildModuleSpec, moduleSizes } from './state/moduleRegistry'
+import { GRAPH_SCHEMA_VERSION, migrateGraph } from './shared/graph'
 import type { GraphState, MacroSpec, MacroTarget, ModuleSpec, ModuleType } from './shared/graph'
 import { PatchLayer } from './ui/PatchLayer'
 import { RackView } from './ui/RackView'
@@ -53,7 +54,11 @@ type NativeScopePacket = {
   sampleRate: number
   frames: number
   tapCount: number
+  tapChannels: number[]
+  // Mono taps, or the left side of stereo taps
   data: number[][]
+  // Right side of stereo taps (empty for mono taps)
+  dataRight: number[][]
 }
 
 type NativeScopeSnapshot = {
@@ -121,6 +126,12 @@ const getVstInstanceId = () => {
 const buildScopeTaps = (modules: ModuleSpec[]): NativeTap[] => {
   const taps: NativeTap[] = []
   modules.forEach((module) => {
+    if (module.type === 'peak-meter') {
+      // Output taps: the meter's CVs are read back like scope channels.
+      taps.push({ moduleId: module.id, portId: 'peak-cv' })
+      taps.push({ moduleId: module.id, portId: 'rms-cv' })
+      return
+    }
     if (module.type !== 'scope') {
       return
     }
@@ -149,6 +160,9 @@ const buildGraphSignature = (graph: GraphState): string => {
   return `${moduleSignature}::${connectionSignature}::${macroSignature}`
 }
 
+/** Distortion `mode` names, indexed by the engine's numeric mode. */
+const DISTORTION_MODES = ['soft', 'hard', 'fold', 'asymmetric', 'tube']
+
 const normalizeNativeParamValue = (paramId: string, value: number | string | boolean): number => {
   if (typeof value === 'number') {
     if (paramId === 'slope') {
@@ -163,7 +177,7 @@ const normalizeNativeParamValue = (paramId: string, value: number | string | boo
     return value ? 1 : 0
   }
   const text = value.toLowerCase()
-  if (paramId === 'type' || paramId === 'shape') {
+  if (paramId === 'type' || paramId === 'shape' || paramId === 'carrierShape') {
     if (text === 'triangle') return 1
     if (text === 'saw' || text === 'sawtooth') return 2
     if (text === 'square') return 3
@@ -172,17 +186,28 @@ const normalizeNativeParamValue = (paramId: string, value: number | string | boo
   if (paramId === 'noiseType') {
     if (text === 'pink') return 1
     if (text === 'brown') return 2
+    if (text === 'blue') return 3
+    if (text === 'violet') return 4
+    if (text === 'digital') return 5
+    if (text === 'digital-short') return 6
     return 0
   }
   if (paramId === 'mode') {
     if (text === 'hp') return 1
     if (text === 'bp') return 2
     if (text === 'notch') return 3
-    return 0
+    const distortionMode = DISTORTION_MODES.indexOf(text)
+    return distortionMode > 0 ? distortionMode : 0
   }
   if (paramId === 'model') {
     return text === 'ladder' ? 1 : 0
   }
+  if (paramId === 'glideMode') {
+    return text === 'legato' ? 1 : 0
+  }
+  if (paramId === 'glideCurve') {
+    return text === 'exponential' ? 1 : 0
+  }
   return Number.NaN
 }
 
@@ -198,6 +223,8 @@ const BOOLEAN_PARAMS = new Set([
   'chB',
   'chC',
   'chD',
+  'internalCarrier',
+  'invert',
 ])
 
 const denormalizeNativeParamValue = (
@@ -208,7 +235,7 @@ const denormalizeNativeParamValue = (
     if (paramId === 'slope' && value <= 1) {
       return value >= 1 ? 24 : 12
     }
-    if (paramId === 'type' || paramId === 'shape') {
+    if (paramId === 'type' || paramId === 'shape' || paramId === 'carrierShape') {
       if (value === 1) return 'triangle'
       if (value === 2) return 'sawtooth'
       if (value === 3) return 'square'
@@ -217,6 +244,10 @@ const denormalizeNativeParamValue = (
     if (paramId === 'noiseType') {
       if (value === 1) return 'pink'
       if (value === 2) return 'brown'
+      if (value === 3) return 'blue'
+      if (value === 4) return 'violet'
+      if (value === 5) return 'digital'
+      if (value === 6) return 'digital-short'
       return 'white'
     }
     if (paramId === 'mode') {
@@ -231,6 +262,12 @@ const denormalizeNativeParamValue = (
     if (paramId === 'cvMode') {
       return value >= 1 ? 'unipolar' : 'bipolar'
     }
+    if (paramId === 'glideMode') {
+      return value >= 1 ? 'legato' : 'always'
+    }
+    if (paramId === 'glideCurve') {
+      return value >= 1 ? 'exponential' : 'linear'
+    }
   }
   if (typeof value === 'number' && BOOLEAN_PARAMS.has(paramId)) {
     return value >= 0.5
@@ -245,7 +282,9 @@ const normalizeGraphFromVst = (graph: GraphState): GraphState => ({
     params: Object.fromEntries(
       Object.entries(module.params).map(([paramId, value]) => [
         paramId,
-        denormalizeNativeParamValue(paramId, value),
+        module.type === 'distortion' && paramId === 'mode' && typeof value === 'number'
+          ? (DISTORTION_MODES[Math.round(value)] ?? 'soft')
+          : denormalizeNativeParamValue(paramId, value),
       ]),
     ),
   })),
@@ -426,6 +465,7 @@ function App() {
     const taps = buildScopeTaps(nextGraph.modules)
     nativeScopeTapsRef.current = taps
     return JSON.stringify({
+      schemaVersion: GRAPH_SCHEMA_VERSION,
       modules: nextGraph.modules,
       connections: nextGraph.connections,
       taps,
@@ -473,6 +513,7 @@ function App() {
   const buildVstGraphJson = useCallback(
     (nextGraph: GraphState) =>
       JSON.stringify({
+        schemaVersion: GRAPH_SCHEMA_VERSION,
         modules: nextGraph.modules,
         connections: nextGraph.connections,
         macros: nextGraph.macros ?? [],
@@ -869,6 +910,7 @@ function App() {
     typeof controlModule?.params.midiInputId === 'string' ? controlModule.params.midiInputId : ''
   const voiceCount = clampVoiceCount(Number(controlModule?.params.voices ?? 1))
   const manualVelocity = Math.max(0, Math.min(1, Number(controlModule?.params.velocity ?? 1)))
+  const tuning = useMemo(() => readTuning(controlModule?.params), [controlModule?.params])
 
   useEffect(() => {
     if (status !== 'running') {
@@ -927,8 +969,8 @@ function App() {
       }
 
       if (status === 'running' && !options?.skipEngine) {
-        // String params like stepData/drumData/midiData go through setParamString
-        if (typeof value === 'string' && (paramId === 'stepData' || paramId === 'drumData' || paramId === 'midiData')) {
+        // String params like stepData/drumData/midiData/bus go through setParamString
+        if (typeof value === 'string' && (paramId === 'stepData' || paramId === 'drumData' || paramId === 'midiData' || paramId === 'bus')) {
           engine.setParamString(moduleId, paramId, value)
         } else {
           engine.setParam(moduleId, paramId, value)
@@ -936,7 +978,7 @@ function App() {
       }
       if (isTauri && tauriNativeRunning && !options?.skipEngine) {
         // String params like stepData/drumData/midiData need special handling
-        if (typeof value === 'string' && (paramId === 'stepData' || paramId === 'drumData' || paramId === 'midiData')) {
+        if (typeof value === 'string' && (paramId === 'stepData' || paramId === 'drumData' || paramId === 'midiData' || paramId === 'bus')) {
           void invokeTauri('native_set_param_string', { moduleId, paramId, value })
         } else {
           const numeric = normalizeNativeParamValue(paramId, value)
@@ -966,6 +1008,7 @@ function App() {
       setGraph(nextGraph)
       if (isVst && vstConnected) {
         const graphJson = JSON.stringify({
+          schemaVersion: GRAPH_SCHEMA_VERSION,
           modules: nextGraph.modules,
           connections: nextGraph.connections,
           macros: nextMacros,
@@ -1144,6 +1187,13 @@ function App() {
         })
         return result
       },
+      loadSample: async (moduleId: string, data: Uint8Array): Promise<number> => {
+        const result = await invokeTauri<number>('native_load_sample', {
+          moduleId,
+          data: Array.from(data),
+        })
+        return result
+      },
     }
   }, [isTauri, tauriNativeRunning])
 
@@ -1277,6 +1327,7 @@ function App() {
         console.error('Failed to pull graph from VST:', error)
       }
       const fallbackJson = JSON.stringify({
+        schemaVersion: GRAPH_SCHEMA_VERSION,
         modules: graphRef.current.modules,
         connections: graphRef.current.connections,
         macros: graphRef.current.macros ?? [],
@@ -1507,6 +1558,7 @@ function App() {
     seqGateRatio,
     seqOn,
     seqTempo,
+    tuning,
     updateParam,
     voiceCount,
   })
@@ -1602,7 +1654,7 @@ function App() {
   }
 
   const applyPreset = (nextGraph: GraphState, options?: { skipVstSync?: boolean; presetId?: string }) => {
-    const cloned = cloneGraph(nextGraph)
+    const cloned = cloneGraph(migrateGraph(nextGraph))
     // Force sequencer OFF when loading presets (prevents auto-start from preset data)
     const controlModule = cloned.modules.find((m) => m.type === 'control')
     if (controlModule && typeof controlModule.params.seqOn !== 'undefined') {
@@ -1661,7 +1713,7 @@ function App() {
   }, [urlGraph, urlPresetId])
 
   const handleExportPreset = useCallback(() => {
-    const payload = { version: 1, graph: graphRef.current }
+    const payload = { version: 1, graph: { schemaVersion: GRAPH_SCHEMA_VERSION, ...graphRef.current } }
     const json = JSON.stringify(payload, null, 2)
     const blob = new Blob([json], { type: 'application/json' })
     const url = URL.createObjectURL(blob)
@@ -1806,6 +1858,7 @@ function App() {
       const taps = buildScopeTaps(graphRef.current.modules)
       nativeScopeTapsRef.current = taps
       const graphJson = JSON.stringify({
+        schemaVersion: GRAPH_SCHEMA_VERSION,
         modules: graphRef.current.modules,
         connections: graphRef.current.connections,
         taps,
@@ -1829,6 +1882,7 @@ function App() {
       const taps = buildScopeTaps(graphRef.current.modules)
       nativeScopeTapsRef.current = taps
       const graphJson = JSON.stringify({
+        schemaVersion: GRAPH_SCHEMA_VERSION,
         modules: graphRef.current.modules,
         connections: graphRef.current.connections,
         taps,
@@ -2023,6 +2077,7 @@ function App() {
       // In VST mode, just sync the graph - audio is handled by DAW
       if (vstConnected) {
         const graphJson = JSON.stringify({
+          schemaVersion: GRAPH_SCHEMA_VERSION,
           modules: graphRef.current.modules,
           connections: graphRef.current.connections,
           macros: graphRef.current.macros ?? [],
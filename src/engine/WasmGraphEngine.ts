@@ -1,3 +1,4 @@
+import { GRAPH_SCHEMA_VERSION } from '../shared/graph'
 import type { Connection, GraphState, ModuleSpec } from '../shared/graph'
 import wasmGraphProcessorUrl from './worklets/wasm-graph-processor.ts?worker&url'
 
@@ -24,6 +25,7 @@ export class AudioEngine {
   private watchedMidiSeq: string | null = null
   private granularLoadCallbacks: Map<string, (length: number) => void> = new Map()
   private granularPositionCallbacks: Map<string, (position: number) => void> = new Map()
+  private sampleLoadCallbacks: Map<string, (result: { frames?: number; error?: string }) => void> = new Map()
   private watchedGranulars: Set<string> = new Set()
   private sidVoiceCallbacks: Map<string, (voices: Array<{freq: number, gate: boolean, waveform: number}>, elapsed: number) => void> = new Map()
   private watchedSids: Set<string> = new Set()
@@ -404,6 +406,27 @@ export class AudioEngine {
     })
   }
 
+  /** Load decoded audio (interleaved) into a sample-player; resolves to the frame count. */
+  loadSample(moduleId: string, data: Float32Array, channels: number, sampleRate: number): Promise<number> {
+    return new Promise((resolve, reject) => {
+      if (!this.graphNode) {
+        reject(new Error('Audio engine not initialized'))
+        return
+      }
+      this.sampleLoadCallbacks.set(moduleId, (result) => {
+        if (result.error !== undefined) {
+          reject(new Error(result.error))
+        } else {
+          resolve(result.frames ?? 0)
+        }
+      })
+      this.graphNode.port.postMessage(
+        { type: 'loadSample', moduleId, data, channels, sampleRate },
+        [data.buffer]
+      )
+    })
+  }
+
   loadSidFile(moduleId: string, data: Uint8Array): void {
     if (!this.graphNode) {
       console.error('Audio engine not initialized')
@@ -549,6 +572,13 @@ export class AudioEngine {
           callback(length)
           this.granularLoadCallbacks.delete(moduleId)
         }
+      } else if (data.type === 'sampleLoaded') {
+        const { moduleId, frames, error } = data as { type: string; moduleId: string; frames?: number; error?: string }
+        const callback = this.sampleLoadCallbacks.get(moduleId)
+        if (callback) {
+          callback({ frames, error })
+          this.sampleLoadCallbacks.delete(moduleId)
+        }
       } else if (data.type === 'granularPositions' && data.positions) {
         for (const [moduleId, position] of Object.entries(data.positions)) {
           const callback = this.granularPositionCallbacks.get(moduleId)
@@ -675,6 +705,7 @@ export class AudioEngine {
       return
     }
     const payload = {
+      schemaVersion: GRAPH_SCHEMA_VERSION,
       modules: this.currentGraph.modules,
       connections: this.currentGraph.connections,
       taps: this.tapOutputs,
@@ -699,7 +730,7 @@ export class AudioEngine {
       return value ? 1 : 0
     }
     const text = value.toLowerCase()
-    if (paramId === 'type' || paramId === 'shape') {
+    if (paramId === 'type' || paramId === 'shape' || paramId === 'carrierShape') {
       if (text === 'triangle') return 1
       if (text === 'saw' || text === 'sawtooth') return 2
       if (text === 'square') return 3
@@ -710,17 +741,30 @@ export class AudioEngine {
       if (text === 'brown') return 2
       if (text === 'blue') return 3
       if (text === 'violet') return 4
+      if (text === 'digital') return 5
+      if (text === 'digital-short') return 6
       return 0
     }
     if (paramId === 'mode') {
       if (text === 'hp') return 1
       if (text === 'bp') return 2
       if (text === 'notch') return 3
+      // Distortion modes
+      if (text === 'hard') return 1
+      if (text === 'fold') return 2
+      if (text === 'asymmetric') return 3
+      if (text === 'tube') return 4
       return 0
     }
     if (paramId === 'model') {
       return text === 'ladder' ? 1 : 0
     }
+    if (paramId === 'glideMode') {
+      return text === 'legato' ? 1 : 0
+    }
+    if (paramId === 'glideCurve') {
+      return text === 'exponential' ? 1 : 0
+    }
     return Number.NaN
   }
 }
@@ -62,6 +62,7 @@ type GraphMessage =
   | { type: 'seekMidiSeq'; moduleId: string; tick: number }
   | { type: 'loadGranularBuffer'; moduleId: string; data: Float32Array }
   | { type: 'watchGranulars'; moduleIds: string[] }
+  | { type: 'loadSample'; moduleId: string; data: Float32Array; channels: number; sampleRate: number }
   | { type: 'loadSidFile'; moduleId: string; data: Uint8Array }
   | { type: 'watchSids'; moduleIds: string[] }
   | { type: 'loadYmFile'; moduleId: string; data: Uint8Array }
@@ -74,6 +75,8 @@ class WasmGraphProcessor extends AudioWorkletProcessor {
   private ready = false
   private pendingGraph: string | null = null
   private inputScratch: Float32Array | null = null
+  private memory: WebAssembly.Memory | null = null
+  private outputView: Float32Array | null = null
   private watchedSequencers: string[] = []
   private lastSteps: Map<string, number> = new Map()
   private stepPollCounter = 0
@@ -140,7 +143,8 @@ class WasmGraphProcessor extends AudioWorkletProcessor {
   private async initWasm() {
     try {
       const bytes = decodeWasmDataUrl(wasmDataUrl)
-      await init({ module_or_path: bytes })
+      const exports = await init({ module_or_path: bytes })
+      this.memory = exports.memory
       if (WasmGraphEngine) {
         this.engine = new WasmGraphEngine(sampleRate)
         this.ready = true
@@ -161,6 +165,27 @@ class WasmGraphProcessor extends AudioWorkletProcessor {
     }
   }
 
+  // Render straight out of wasm linear memory. The view is only rebuilt when the
+  // output pointer/size changes or memory grows (which detaches the old buffer).
+  private renderBlock(frames: number): Float32Array {
+    const engine = this.engine!
+    if (!this.memory) {
+      return engine.render(frames)
+    }
+    const ptr = engine.render_ptr(frames)
+    const length = engine.output_channels() * frames
+    const view = this.outputView
+    if (
+      !view ||
+      view.buffer !== this.memory.buffer ||
+      view.byteOffset !== ptr ||
+      view.length !== length
+    ) {
+      this.outputView = new Float32Array(this.memory.buffer, ptr, length)
+    }
+    return this.outputView!
+  }
+
   private processQueuedMessage(message: GraphMessage) {
     // Only called from process() - engine is guaranteed to exist and not be borrowed
     switch (message.type) {
@@ -207,6 +232,19 @@ class WasmGraphProcessor extends AudioWorkletProcessor {
           length: message.data.length,
         })
         break
+      case 'loadSample':
+        try {
+          const frames = this.engine!.load_sample(
+            message.moduleId,
+            message.data,
+            message.channels,
+            message.sampleRate,
+          )
+          this.port.postMessage({ type: 'sampleLoaded', moduleId: message.moduleId, frames })
+        } catch (error) {
+          this.port.postMessage({ type: 'sampleLoaded', moduleId: message.moduleId, error: String(error) })
+        }
+        break
       case 'loadSidFile':
         this.engine!.load_sid_file(message.moduleId, message.data)
         break
@@ -264,7 +302,7 @@ class WasmGraphProcessor extends AudioWorkletProcessor {
       this.engine.clear_external_input()
     }
 
-    const data = this.engine.render(frames)
+    const data = this.renderBlock(frames)
     const channelCount = outputs.length + 1
     const expected = channelCount * frames
     if (data.length < expected) {
@@ -4,7 +4,11 @@ import init, * as wasm from './dsp_wasm.js'
 type WasmGraphEngineCtor = new (sampleRate: number) => {
   set_graph(graphJson: string): void
   set_param(moduleId: string, paramId: string, value: number): void
+  reset(): void
+  to_graph_json(): string
   set_param_string(moduleId: string, paramId: string, value: string): void
+  get_param(moduleId: string, paramId: string): number | undefined
+  set_bypass(moduleId: string, bypass: boolean): void
   set_control_voice_cv(moduleId: string, voice: number, value: number): void
   set_control_voice_gate(moduleId: string, voice: number, value: number): void
   trigger_control_voice_gate(moduleId: string, voice: number): void
@@ -20,6 +24,10 @@ type WasmGraphEngineCtor = new (sampleRate: number) => {
   set_external_input(input: Float32Array): void
   clear_external_input(): void
   render(frames: number): Float32Array
+  render_ptr(frames: number): number
+  output_channels(): number
+  tap_count(): number
+  get_tap(tapIndex: number, frames: number): Float32Array
   get_sequencer_step(moduleId: string): number
   get_midi_total_ticks(moduleId: string): number
   seek_midi_sequencer(moduleId: string, tick: number): void
@@ -28,6 +36,7 @@ type WasmGraphEngineCtor = new (sampleRate: number) => {
   get_granular_buffer_length(moduleId: string): number
   get_granular_position(moduleId: string): number
   get_granular_waveform(moduleId: string, maxPoints: number): Float32Array
+  load_sample(moduleId: string, data: Float32Array, channels: number, sampleRate: number): number
   load_sid_file(moduleId: string, data: Uint8Array): void
   get_sid_voice_states(moduleId: string): Uint16Array
   get_sid_elapsed(moduleId: string): number
@@ -1,6 +1,7 @@
 import { useCallback, useEffect, useRef, useState } from 'react'
 import type { AudioEngine } from '../engine/WasmGraphEngine'
 import { DEFAULT_SEQUENCER_PATTERN } from '../state/sequencerPattern'
+import { noteToCv, type Tuning } from '../state/midiUtils'
 
 type ControlBridge = Pick<
   AudioEngine,
@@ -23,6 +24,7 @@ type UseControlVoicesParams = {
   controlModuleId: string | null
   voiceCount: number
   midiRoot: number
+  tuning: Tuning
   seqOn: boolean
   seqTempo: number
   seqGateRatio: number
@@ -41,6 +43,7 @@ export const useControlVoices = ({
   controlModuleId,
   voiceCount,
   midiRoot,
+  tuning,
   seqOn,
   seqTempo,
   seqGateRatio,
@@ -146,7 +149,7 @@ export const useControlVoices = ({
       const clampedVelocity = Math.max(0, Math.min(1, velocity))
       const voiceIndex = allocateVoice(note, clampedVelocity)
       // Use fixed reference (MIDI 60 = C4) so octave changes affect pitch
-      const cv = (note - 60) / 12
+      const cv = noteToCv(note, tuning)
       updateParam(controlModuleId, 'cv', cv, { skipEngine: true })
       if (useVelocity) {
         updateParam(controlModuleId, 'velocity', clampedVelocity, { skipEngine: true })
@@ -172,7 +175,7 @@ export const useControlVoices = ({
       nativeControl?.triggerControlVoiceGate(controlModuleId, voiceIndex)
       nativeControl?.triggerControlVoiceSync(controlModuleId, voiceIndex)
     },
-    [allocateVoice, controlModuleId, engine, nativeControl, updateParam],
+    [allocateVoice, controlModuleId, engine, nativeControl, tuning, updateParam],
   )
 
   const releaseVoiceNote = useCallback(
@@ -14,11 +14,16 @@ export type ModuleType =
   | 'wavetable'
   | 'granular'
   | 'particle-cloud'
+  | 'sample-player'
   | 'mod-router'
+  | 'atten'
   | 'sample-hold'
   | 'slew'
+  | 'env-follower'
   | 'quantizer'
   | 'chaos'
+  | 'logic'
+  | 'peak-meter'
   | 'ring-mod'
   | 'gain'
   | 'cv-vca'
@@ -30,10 +35,14 @@ export type ModuleType =
   | 'scope'
   | 'vcf'
   | 'hpf'
+  | 'dc-block'
   | 'mixer'
-  | 'mixer-1x2'
+  | 'mixer-wide'
   | 'mixer-8'
   | 'crossfader'
+  | 'mult'
+  | 'pan'
+  | 'stereo-width'
   | 'chorus'
   | 'ensemble'
   | 'choir'
@@ -78,15 +87,21 @@ export type ModuleType =
   | 'notes'
   // Effects
   | 'pitch-shifter'
+  | 'bitcrush'
   | 'compressor'
+  | 'noise-gate'
   // Master Clock
   | 'clock'
+  | 'clock-div'
   // Turing Machine
   | 'turing-machine'
   // SID Player
   | 'sid-player'
   // AY Player (ZX Spectrum, Amstrad CPC, MSX, Atari ST)
   | 'ay-player'
+  // Aux buses
+  | 'send'
+  | 'return'
 
 export interface ModuleSpec {
   id: string
@@ -112,6 +127,8 @@ export type MacroSpec = {
 export interface PortRef {
   moduleId: string
   portId: string
+  /** Poly source only: bind to this voice instead of the default reduction. */
+  voice?: number
 }
 
 export interface Connection {
@@ -125,3 +142,20 @@ export interface GraphState {
   connections: Connection[]
   macros?: MacroSpec[]
 }
+
+/** Graph JSON schema version written for the engines; matches `CURRENT_SCHEMA_VERSION` in dsp-graph. */
+export const GRAPH_SCHEMA_VERSION = 2
+
+/** Module types renamed by a schema version, as in dsp-graph's `migrate.rs` (v1 -> v2). */
+const RENAMED_MODULE_TYPES: Record<string, ModuleType> = {
+  'mixer-1x2': 'mixer-wide',
+}
+
+/** Bring a graph saved by an older version (presets, files, shared URLs) to the current module names. */
+export const migrateGraph = (graph: GraphState): GraphState => ({
+  ...graph,
+  modules: graph.modules.map((module) => {
+    const renamed = RENAMED_MODULE_TYPES[module.type as string]
+    return renamed ? { ...module, type: renamed } : module
+  }),
+})
@@ -10,3 +10,41 @@ export const formatMidiNote = (note: number) => {
   const octave = Math.floor(clamped / 12) - 1
   return `${name}${octave}`
 }
+
+/** Frequency of MIDI note 60 at 0 V (the engine's `DEFAULT_REF_FREQ`). */
+export const DEFAULT_REF_FREQ = 261.63
+
+/** Note to V/Oct mapping of a Control module. */
+export type Tuning = {
+  refFreq: number
+  edo: number
+  /** Cents of scale degrees 1..n (Scala style, the last is the period). */
+  table: number[]
+}
+
+/** Read `refFreq`, `edo` and the `tuning` table from Control params. */
+export const readTuning = (params?: Record<string, unknown>): Tuning => {
+  const table = Array.isArray(params?.tuning)
+    ? params.tuning.filter((cents): cents is number => typeof cents === 'number')
+    : []
+  return {
+    refFreq: Math.max(1, Number(params?.refFreq ?? DEFAULT_REF_FREQ)),
+    edo: Math.max(1, Math.round(Number(params?.edo ?? 12))),
+    table: table.length > 0 && table[table.length - 1] > 0 ? table : [],
+  }
+}
+
+/** V/Oct CV of a MIDI note; mirrors `GraphEngine::note_to_cv`. */
+export const noteToCv = (note: number, tuning: Tuning) => {
+  const steps = note - 60
+  const offset = Math.log2(tuning.refFreq / DEFAULT_REF_FREQ)
+  const { table } = tuning
+  if (table.length === 0) {
+    return offset + steps / tuning.edo
+  }
+  const degrees = table.length
+  const degree = ((steps % degrees) + degrees) % degrees
+  const periods = Math.floor(steps / degrees)
+  const cents = degree === 0 ? 0 : table[degree - 1]
+  return offset + (periods * table[degrees - 1] + cents) / 1200
+}
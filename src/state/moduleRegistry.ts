@@ -14,14 +14,20 @@ export const moduleSizes: Record<ModuleType, string> = {
   'wavetable': '2x3',
   'granular': '3x4',
   'particle-cloud': '2x3',
-  'mod-router': '2x2',
-  'sample-hold': '2x1',
+  'sample-player': '2x2',
+  'mod-router': '2x3',
+  atten: '1x1',
+  'sample-hold': '2x2',
   slew: '1x2',
+  'env-follower': '1x2',
   quantizer: '2x2',
   chaos: '2x2',
-  'ring-mod': '1x1',
+  logic: '2x2',
+  'peak-meter': '1x2',
+  'ring-mod': '2x1',
   vcf: '2x2',
   hpf: '1x1',
+  'dc-block': '1x1',
   control: '3x6',
   scope: '2x3',
   adsr: '1x2',
@@ -32,13 +38,17 @@ export const moduleSizes: Record<ModuleType, string> = {
   'tape-delay': '2x2',
   'spring-reverb': '2x1',
   reverb: '2x1',
-  phaser: '2x1',
+  phaser: '2x2',
   distortion: '2x2',
   wavefolder: '2x2',
+  bitcrush: '2x2',
   mixer: '1x1',
-  'mixer-1x2': '1x2',
+  'mixer-wide': '1x2',
   'mixer-8': '1x3',
   crossfader: '1x1',
+  mult: '1x1',
+  pan: '1x1',
+  'stereo-width': '1x1',
   gain: '1x1',
   'cv-vca': '1x1',
   output: '1x1',
@@ -77,8 +87,10 @@ export const moduleSizes: Record<ModuleType, string> = {
   // Effects
   'pitch-shifter': '2x2',
   compressor: '2x2',
+  'noise-gate': '2x2',
   // Master Clock
   clock: '2x2',
+  'clock-div': '2x1',
   // MIDI File Sequencer
   'midi-file-sequencer': '2x5',
   // Turing Machine
@@ -87,6 +99,9 @@ export const moduleSizes: Record<ModuleType, string> = {
   'sid-player': '3x5',
   // AY Player
   'ay-player': '3x5',
+  // Aux buses
+  send: '1x2',
+  return: '1x2',
 }
 
 export const modulePortLayouts: Partial<Record<ModuleType, 'stacked' | 'strip'>> = {
@@ -101,6 +116,7 @@ export const modulePortLayouts: Partial<Record<ModuleType, 'stacked' | 'strip'>>
   'wavetable': 'strip',
   'granular': 'strip',
   'particle-cloud': 'strip',
+  'sample-player': 'strip',
   vcf: 'strip',
   control: 'strip',
   lab: 'strip',
@@ -111,7 +127,7 @@ export const modulePortLayouts: Partial<Record<ModuleType, 'stacked' | 'strip'>>
   chaos: 'strip',
   vocoder: 'strip',
   mario: 'strip',
-  'mixer-1x2': 'strip',
+  'mixer-wide': 'strip',
   'mixer-8': 'strip',
   arpeggiator: 'strip',
   'step-sequencer': 'strip',
@@ -200,16 +216,21 @@ export const moduleCatalog: { type: ModuleType; label: string; category: ModuleC
   { type: 'wavetable', label: 'Wavetable', category: 'sources' },
   { type: 'granular', label: 'Granular', category: 'sources' },
   { type: 'particle-cloud', label: 'Particle Cloud', category: 'sources' },
+  { type: 'sample-player', label: 'Sample Player', category: 'sources' },
   // Filters
   { type: 'vcf', label: 'VCF', category: 'filters' },
   { type: 'hpf', label: 'HPF', category: 'filters' },
+  { type: 'dc-block', label: 'DC Blocker', category: 'filters' },
   // Amplifiers
   { type: 'gain', label: 'VCA', category: 'amplifiers' },
   { type: 'cv-vca', label: 'Mod VCA', category: 'amplifiers' },
   { type: 'mixer', label: 'Mixer 1x1', category: 'amplifiers' },
-  { type: 'mixer-1x2', label: 'Mixer 6ch', category: 'amplifiers' },
+  { type: 'mixer-wide', label: 'Mixer 6ch', category: 'amplifiers' },
   { type: 'mixer-8', label: 'Mixer 8ch', category: 'amplifiers' },
   { type: 'crossfader', label: 'Crossfader', category: 'amplifiers' },
+  { type: 'mult', label: 'Multiple', category: 'amplifiers' },
+  { type: 'pan', label: 'Pan', category: 'amplifiers' },
+  { type: 'stereo-width', label: 'Stereo Width', category: 'amplifiers' },
   // Effects
   { type: 'chorus', label: 'Chorus', category: 'effects' },
   { type: 'ensemble', label: 'Ensemble', category: 'effects' },
@@ -223,19 +244,26 @@ export const moduleCatalog: { type: ModuleType; label: string; category: ModuleC
   { type: 'phaser', label: 'Phaser', category: 'effects' },
   { type: 'distortion', label: 'Distortion', category: 'effects' },
   { type: 'wavefolder', label: 'Wavefolder', category: 'effects' },
+  { type: 'bitcrush', label: 'Bit Crusher', category: 'effects' },
   { type: 'ring-mod', label: 'Ring Mod', category: 'effects' },
   { type: 'pitch-shifter', label: 'Pitch Shifter', category: 'effects' },
   { type: 'compressor', label: 'Compressor', category: 'effects' },
+  { type: 'noise-gate', label: 'Noise Gate', category: 'effects' },
   // Modulators
   { type: 'adsr', label: 'ADSR', category: 'modulators' },
   { type: 'lfo', label: 'LFO', category: 'modulators' },
   { type: 'mod-router', label: 'Mod Router', category: 'modulators' },
+  { type: 'atten', label: 'Attenuverter', category: 'modulators' },
   { type: 'sample-hold', label: 'S&H', category: 'modulators' },
   { type: 'slew', label: 'Slew', category: 'modulators' },
+  { type: 'env-follower', label: 'Env Follower', category: 'modulators' },
   { type: 'quantizer', label: 'Quantizer', category: 'modulators' },
   { type: 'chaos', label: 'Chaos Engine', category: 'modulators' },
+  { type: 'logic', label: 'Logic', category: 'modulators' },
+  { type: 'peak-meter', label: 'Peak Meter', category: 'modulators' },
   // Sequencers
   { type: 'clock', label: 'Clock', category: 'sequencers' },
+  { type: 'clock-div', label: 'Clock Div', category: 'sequencers' },
   { type: 'arpeggiator', label: 'Arpeggiator', category: 'sequencers' },
   { type: 'step-sequencer', label: 'Step Seq', category: 'sequencers' },
   { type: 'euclidean', label: 'Euclidean', category: 'sequencers' },
@@ -266,6 +294,8 @@ export const moduleCatalog: { type: ModuleType; label: string; category: ModuleC
   { type: 'scope', label: 'Scope', category: 'io' },
   { type: 'lab', label: 'Lab', category: 'io' },
   { type: 'notes', label: 'Notes', category: 'io' },
+  { type: 'send', label: 'Send', category: 'io' },
+  { type: 'return', label: 'Return', category: 'io' },
 ]
 
 export const modulePrefixes: Record<ModuleType, string> = {
@@ -282,20 +312,29 @@ export const modulePrefixes: Record<ModuleType, string> = {
   'wavetable': 'wt',
   'granular': 'gran',
   'particle-cloud': 'pcloud',
+  'sample-player': 'smp',
   'mod-router': 'modr',
+  atten: 'atten',
   'sample-hold': 'sh',
   slew: 'slew',
+  'env-follower': 'envf',
   quantizer: 'quant',
   chaos: 'chaos',
+  logic: 'logic',
+  'peak-meter': 'meter',
   'ring-mod': 'ring',
   vcf: 'vcf',
   hpf: 'hpf',
+  'dc-block': 'dc',
   gain: 'gain',
   'cv-vca': 'mod',
   mixer: 'mix',
-  'mixer-1x2': 'mix6',
+  'mixer-wide': 'mix6',
   'mixer-8': 'mix8',
   crossfader: 'xfade',
+  mult: 'mult',
+  pan: 'pan',
+  'stereo-width': 'width',
   chorus: 'chorus',
   ensemble: 'ens',
   choir: 'choir',
@@ -309,8 +348,10 @@ export const modulePrefixes: Record<ModuleType, string> = {
   phaser: 'phaser',
   distortion: 'dist',
   wavefolder: 'fold',
+  bitcrush: 'crush',
   'pitch-shifter': 'pitch',
   compressor: 'comp',
+  'noise-gate': 'gate',
   adsr: 'adsr',
   lfo: 'lfo',
   scope: 'scope',
@@ -346,12 +387,15 @@ export const modulePrefixes: Record<ModuleType, string> = {
   notes: 'notes',
   // Master Clock
   clock: 'clock',
+  'clock-div': 'cdiv',
   // MIDI File Sequencer
   'midi-file-sequencer': 'midiseq',
   // Turing Machine
   'turing-machine': 'turing',
   'sid-player': 'sid',
   'ay-player': 'ay',
+  send: 'send',
+  return: 'ret',
 }
 
 export const moduleLabels: Record<ModuleType, string> = {
@@ -368,20 +412,29 @@ export const moduleLabels: Record<ModuleType, string> = {
   'wavetable': 'Wavetable',
   'granular': 'Granular',
   'particle-cloud': 'Particle Cloud',
+  'sample-player': 'Sample Player',
   'mod-router': 'Mod Router',
+  atten: 'Attenuverter',
   'sample-hold': 'S&H',
   slew: 'Slew',
+  'env-follower': 'Env Follower',
   quantizer: 'Quantizer',
   chaos: 'Chaos Engine',
+  logic: 'Logic',
+  'peak-meter': 'Peak Meter',
   'ring-mod': 'Ring Mod',
   vcf: 'VCF',
   hpf: 'HPF',
+  'dc-block': 'DC Blocker',
   gain: 'VCA',
   'cv-vca': 'Mod VCA',
   mixer: 'Mixer 2ch',
-  'mixer-1x2': 'Mixer 6ch',
+  'mixer-wide': 'Mixer 6ch',
   'mixer-8': 'Mixer 8ch',
   crossfader: 'Crossfader',
+  mult: 'Multiple',
+  pan: 'Pan',
+  'stereo-width': 'Stereo Width',
   chorus: 'Chorus',
   ensemble: 'Ensemble',
   choir: 'Choir',
@@ -395,8 +448,10 @@ export const moduleLabels: Record<ModuleType, string> = {
   phaser: 'Phaser',
   distortion: 'Distortion',
   wavefolder: 'Wavefolder',
+  bitcrush: 'Bit Crusher',
   'pitch-shifter': 'Pitch Shifter',
   compressor: 'Compressor',
+  'noise-gate': 'Noise Gate',
   adsr: 'ADSR',
   lfo: 'LFO',
   scope: 'Scope',
@@ -432,12 +487,15 @@ export const moduleLabels: Record<ModuleType, string> = {
   notes: 'Notes',
   // Master Clock
   clock: 'Master Clock',
+  'clock-div': 'Clock Div',
   // MIDI File Sequencer
   'midi-file-sequencer': 'MIDI File Seq',
   // Turing Machine
   'turing-machine': 'Turing Machine',
   'sid-player': 'SID Player',
   'ay-player': 'AY Player',
+  send: 'Aux Send',
+  return: 'Aux Return',
 }
 
 export const moduleDefaults: Record<ModuleType, Record<string, number | string | boolean>> = {
@@ -451,8 +509,11 @@ export const moduleDefaults: Record<ModuleType, Record<string, number | string |
     fmExp: 0,
     subMix: 0,
     subOct: 1,
+    stereoSpread: 0.5,
+    waveMorph: 0,
+    oversample: 0,
   },
-  noise: { level: 0.4, noiseType: 'white', stereo: 1, pan: 0 },
+  noise: { level: 0.4, noiseType: 'white', rateDivide: 1, stereo: 1, pan: 0 },
   shepard: {
     voices: 8,        // Number of octave-spaced voices (2-12)
     rate: 0.1,        // Climb rate (-4 to 4, cycles/second)
@@ -561,14 +622,35 @@ export const moduleDefaults: Record<ModuleType, Record<string, number | string |
     mode: 0,              // 0=Osc, 1=Sample, 2=Input
     oscShape: 0,          // 0=Sine, 1=Tri, 2=Saw, 3=Square, 4=Noise
   },
-  'mod-router': { depthPitch: 0, depthPwm: 0, depthVcf: 0, depthVca: 0 },
-  'sample-hold': { mode: 0 },
+  'sample-player': {
+    loop: 0,              // 0=One-shot, 1=Loop
+    start: 0,             // Start point (0-1)
+    end: 1,               // End point (0-1)
+    gain: 1,              // Output gain (0-2)
+  },
+  'mod-router': {
+    depthPitch: 0,
+    depthPwm: 0,
+    depthVcf: 0,
+    depthVca: 0,
+    depthAux: 0,
+    offsetPitch: 0,
+    offsetPwm: 0,
+    offsetVcf: 0,
+    offsetVca: 0,
+    offsetAux: 0,
+  },
+  atten: { scale: 1, offset: 0 },
+  'sample-hold': { mode: 0, glide: 0, noiseLevel: 1 },
   slew: { rise: 0.05, fall: 0.05 },
+  'env-follower': { attack: 0.01, release: 0.2, gain: 1 },
   quantizer: { root: 0, scale: 0 },
   chaos: { speed: 0.5, rho: 28, sigma: 10, beta: 2.66, scale: 0, root: 0 },
-  'ring-mod': { level: 0.9 },
-  gain: { gain: 0.7 },
-  'cv-vca': { gain: 1 },
+  logic: { threshold: 0 },
+  'peak-meter': { attack: 0.005, release: 0.3, rmsWindow: 300 },
+  'ring-mod': { level: 0.9, mix: 1, carrierFreq: 440, carrierShape: 'sine', internalCarrier: false },
+  gain: { gain: 0.7, invert: false },
+  'cv-vca': { gain: 1, invert: false },
   vcf: {
     cutoff: 800,
     resonance: 0.2,
@@ -583,8 +665,9 @@ export const moduleDefaults: Record<ModuleType, Record<string, number | string |
   hpf: {
     cutoff: 280,
   },
+  'dc-block': { cutoff: 20 },
   mixer: { levelA: 0.6, levelB: 0.6 },
-  'mixer-1x2': {
+  'mixer-wide': {
     levelA: 0.6,
     levelB: 0.6,
     levelC: 0.6,
@@ -602,7 +685,10 @@ export const moduleDefaults: Record<ModuleType, Record<string, number | string |
     level7: 0.6,
     level8: 0.6,
   },
-  crossfader: { mix: 0.5 },
+  crossfader: { mix: 0.5, curve: 'linear' },
+  mult: {},
+  pan: { pan: 0 },
+  'stereo-width': { width: 1, monoBelow: 0 },
   chorus: { rate: 0.3, depth: 8, delay: 18, mix: 0.4, spread: 0.6, feedback: 0.1 },
   ensemble: { rate: 0.25, depth: 12, delay: 12, mix: 0.6, spread: 0.7 },
   choir: { vowel: 0, rate: 0.25, depth: 0.35, mix: 0.5 },
@@ -643,14 +729,18 @@ export const moduleDefaults: Record<ModuleType, Record<string, number | string |
     tone: 0.4,
     mix: 0.4,
     drive: 0.2,
+    diffusion: 0.5,
+    'spring-count': 3,
   },
   reverb: { time: 0.6, damp: 0.4, preDelay: 18, mix: 0.2 },
-  phaser: { rate: 0.5, depth: 0.7, feedback: 0.3, mix: 0.5 },
-  distortion: { drive: 0.5, tone: 0.5, mix: 1.0, mode: 'soft' },
+  phaser: { rate: 0.5, depth: 0.7, feedback: 0.3, mix: 0.5, stages: 4, stereo: 0, center: 800 },
+  distortion: { drive: 0.5, tone: 0.5, mix: 1.0, mode: 'soft', bias: 0 },
   wavefolder: { drive: 0.4, fold: 0.5, bias: 0, mix: 0.8 },
+  bitcrush: { bits: 8, downsample: 1, mix: 1, antialias: false, dither: false },
   'pitch-shifter': { pitch: 0, fine: 0, grain: 50, mix: 1.0 },
-  compressor: { threshold: -20, ratio: 4, attack: 10, release: 100, makeup: 0, mix: 1.0 },
-  supersaw: { frequency: 220, detune: 25, mix: 1.0 },
+  compressor: { threshold: -20, ratio: 4, attack: 10, release: 100, knee: 0, makeup: 0, mix: 1.0, detector: 0, sidechain: 1 },
+  'noise-gate': { threshold: -40, attack: 1, hold: 50, release: 100, floor: -80 },
+  supersaw: { frequency: 220, detune: 25, mix: 1.0, phaseSpread: 1.0 },
   karplus: {
     frequency: 220,
     damping: 0.3,
@@ -686,6 +776,12 @@ export const moduleDefaults: Record<ModuleType, Record<string, number | string |
     midiVelocity: true,
     gate: 0,
     glide: 0.02,
+    glideMode: 'always',
+    glideCurve: 'linear',
+    voiceDetune: 0,
+    voiceSpread: 0,
+    refFreq: 261.63,
+    edo: 12,
     midiEnabled: false,
     midiChannel: 0,
     midiRoot: 60,
@@ -698,6 +794,8 @@ export const moduleDefaults: Record<ModuleType, Record<string, number | string |
   },
   output: { level: 1.0 },
   lab: { level: 0.5, drive: 0.3, bias: 0, shape: 'triangle' },
+  send: { level: 0.5, bus: 'A' },
+  return: { level: 1.0, bus: 'A' },
   mario: { running: false, tempo: 180, song: 'smb' },
   arpeggiator: {
     enabled: true,
@@ -927,10 +1025,12 @@ export const moduleDefaults: Record<ModuleType, Record<string, number | string |
     rate: 4,            // 1/16 note (same as sequencers)
     swing: 0,
   },
+  'clock-div': { ratio: 4 },   // /2
   // MIDI File Sequencer
   'midi-file-sequencer': {
     enabled: true,
     tempo: 120,
+    tempoScale: 1,      // Playback speed multiplier
     gateLength: 90,     // 90% gate
     loop: true,
     voices: 4,          // Polyphony per track
@@ -1,4 +1,4 @@
-import type { GraphState } from '../shared/graph'
+import { migrateGraph, type GraphState } from '../shared/graph'
 import { defaultGraph } from './defaultGraph'
 
 export type PresetSpec = {
@@ -101,7 +101,7 @@ const buildPresetFromPatch = (
 
   // If a complete graph is provided, use it directly
   if (patch.graph) {
-    return { id, name, description, group, graph: cloneGraph(patch.graph) }
+    return { id, name, description, group, graph: migrateGraph(cloneGraph(patch.graph)) }
   }
 
   // Otherwise, patch the defaultGraph
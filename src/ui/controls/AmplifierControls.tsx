@@ -1,60 +1,118 @@
 /**
  * Amplifier and mixer module controls
  *
- * Modules: gain, cv-vca, mixer, mixer-1x2, mixer-8, crossfader, ring-mod
+ * Modules: gain, cv-vca, mixer, mixer-wide, mixer-8, crossfader, pan, stereo-width, ring-mod
  */
 
 import type React from 'react'
 import type { ControlProps } from './types'
 import { RotaryKnob } from '../RotaryKnob'
-import { formatDecimal2 } from '../formatters'
+import { ControlBox } from '../ControlBox'
+import { ControlButtons } from '../ControlButtons'
+import { ToggleButton, ToggleGroup } from '../ToggleButton'
+import { formatDecimal2, formatFreq, formatPercent } from '../formatters'
 
 export function renderAmplifierControls(props: ControlProps): React.ReactElement | null {
   const { module, updateParam } = props
 
   if (module.type === 'gain') {
     return (
-      <RotaryKnob
-        label="Gain"
-        min={0}
-        max={1}
-        step={0.01}
-        value={Number(module.params.gain ?? 0.2)}
-        onChange={(value) => updateParam(module.id, 'gain', value)}
-        format={formatDecimal2}
-      />
+      <>
+        <RotaryKnob
+          label="Gain"
+          min={0}
+          max={1}
+          step={0.01}
+          value={Number(module.params.gain ?? 0.2)}
+          onChange={(value) => updateParam(module.id, 'gain', value)}
+          format={formatDecimal2}
+        />
+        <ToggleButton
+          label="Invert CV"
+          value={Boolean(module.params.invert)}
+          onChange={(value) => updateParam(module.id, 'invert', value)}
+        />
+      </>
     )
   }
 
   if (module.type === 'cv-vca') {
     return (
-      <RotaryKnob
-        label="Depth"
-        min={0}
-        max={1}
-        step={0.01}
-        value={Number(module.params.gain ?? 1)}
-        onChange={(value) => updateParam(module.id, 'gain', value)}
-        format={formatDecimal2}
-      />
+      <>
+        <RotaryKnob
+          label="Depth"
+          min={0}
+          max={1}
+          step={0.01}
+          value={Number(module.params.gain ?? 1)}
+          onChange={(value) => updateParam(module.id, 'gain', value)}
+          format={formatDecimal2}
+        />
+        <ToggleButton
+          label="Invert CV"
+          value={Boolean(module.params.invert)}
+          onChange={(value) => updateParam(module.id, 'invert', value)}
+        />
+      </>
     )
   }
 
   if (module.type === 'ring-mod') {
     return (
-      <RotaryKnob
-        label="Level"
-        min={0}
-        max={1}
-        step={0.01}
-        value={Number(module.params.level ?? 0.9)}
-        onChange={(value) => updateParam(module.id, 'level', value)}
-        format={formatDecimal2}
-      />
+      <>
+        <ControlBox horizontal>
+          <RotaryKnob
+            label="Level"
+            min={0}
+            max={1}
+            step={0.01}
+            value={Number(module.params.level ?? 0.9)}
+            onChange={(value) => updateParam(module.id, 'level', value)}
+            format={formatDecimal2}
+          />
+          <RotaryKnob
+            label="Mix"
+            min={0}
+            max={1}
+            step={0.01}
+            value={Number(module.params.mix ?? 1)}
+            onChange={(value) => updateParam(module.id, 'mix', value)}
+            format={formatPercent}
+          />
+          <RotaryKnob
+            label="Carrier"
+            min={1}
+            max={20000}
+            step={1}
+            value={Number(module.params.carrierFreq ?? 440)}
+            onChange={(value) => updateParam(module.id, 'carrierFreq', value)}
+            format={formatFreq}
+          />
+        </ControlBox>
+        <ControlBox label="Carrier" compact>
+          <ControlButtons
+            options={[
+              { id: 'sine', label: 'SIN' },
+              { id: 'triangle', label: 'TRI' },
+              { id: 'sawtooth', label: 'SAW' },
+              { id: 'square', label: 'SQR' },
+            ]}
+            value={String(module.params.carrierShape ?? 'sine')}
+            onChange={(value) => updateParam(module.id, 'carrierShape', value)}
+          />
+        </ControlBox>
+        <ToggleGroup>
+          <ToggleButton
+            label="Internal"
+            value={Boolean(module.params.internalCarrier)}
+            onChange={(value) => updateParam(module.id, 'internalCarrier', value)}
+          />
+        </ToggleGroup>
+      </>
     )
   }
 
-  if (module.type === 'mixer' || module.type === 'mixer-1x2') {
+  if (module.type === 'mixer' || module.type === 'mixer-wide') {
     const levels = module.type === 'mixer' ? ['A', 'B'] : ['A', 'B', 'C', 'D', 'E', 'F']
     return (
       <>
@@ -95,18 +153,70 @@ export function renderAmplifierControls(props: ControlProps): React.ReactElement
   }
 
   if (module.type === 'crossfader') {
+    return (
+      <>
+        <RotaryKnob
+          label="A ↔ B"
+          min={0}
+          max={1}
+          step={0.01}
+          value={Number(module.params.mix ?? 0.5)}
+          onChange={(value) => updateParam(module.id, 'mix', value)}
+          format={formatDecimal2}
+        />
+        <ControlBox label="Curve" compact>
+          <ControlButtons
+            options={[
+              { id: 'linear', label: 'Lin' },
+              { id: 'equal-power', label: 'EqP' },
+            ]}
+            value={String(module.params.curve ?? 'linear')}
+            onChange={(value) => updateParam(module.id, 'curve', value)}
+          />
+        </ControlBox>
+      </>
+    )
+  }
+
+  if (module.type === 'pan') {
     return (
       <RotaryKnob
-        label="A ↔ B"
-        min={0}
+        label="L ↔ R"
+        min={-1}
         max={1}
         step={0.01}
-        value={Number(module.params.mix ?? 0.5)}
-        onChange={(value) => updateParam(module.id, 'mix', value)}
+        value={Number(module.params.pan ?? 0)}
+        onChange={(value) => updateParam(module.id, 'pan', value)}
         format={formatDecimal2}
       />
     )
   }
 
+  if (module.type === 'stereo-width') {
+    return (
+      <>
+        <RotaryKnob
+          label="Width"
+          min={0}
+          max={2}
+          step={0.01}
+          value={Number(module.params.width ?? 1)}
+          onChange={(value) => updateParam(module.id, 'width', value)}
+          format={formatPercent}
+        />
+        <RotaryKnob
+          label="Mono <"
+          min={0}
+          max={500}
+          step={5}
+          unit="Hz"
+          value={Number(module.params.monoBelow ?? 0)}
+          onChange={(value) => updateParam(module.id, 'monoBelow', value)}
+          format={(value) => (value <= 0 ? 'Off' : `${Math.round(value)}`)}
+        />
+      </>
+    )
+  }
+
   return null
 }
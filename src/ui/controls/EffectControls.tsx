@@ -2,7 +2,8 @@
  * Effect module controls
  *
  * Modules: chorus, ensemble, choir, vocoder, delay, granular-delay, tape-delay,
- *          spring-reverb, reverb, phaser, distortion, wavefolder, pitch-shifter, compressor
+ *          spring-reverb, reverb, phaser, distortion, wavefolder, bitcrush, pitch-shifter, compressor,
+ *          noise-gate
  */
 
 import type React from 'react'
@@ -16,6 +17,15 @@ import { formatDecimal1, formatDecimal2, formatInt, formatPercent } from '../for
 export function renderEffectControls(props: ControlProps): React.ReactElement | null {
   const { module, updateParam } = props
 
+  // On a send bus the dry signal is already in the mix: drop it here.
+  const wetOnlyToggle = (
+    <ToggleButton
+      label="Wet Only"
+      value={Boolean(module.params.wetOnly)}
+      onChange={(value) => updateParam(module.id, 'wetOnly', value)}
+    />
+  )
+
   if (module.type === 'chorus') {
     return (
       <>
@@ -76,6 +86,7 @@ export function renderEffectControls(props: ControlProps): React.ReactElement |
           onChange={(value) => updateParam(module.id, 'feedback', value)}
           format={formatDecimal2}
         />
+        <ToggleGroup>{wetOnlyToggle}</ToggleGroup>
       </>
     )
   }
@@ -131,6 +142,7 @@ export function renderEffectControls(props: ControlProps): React.ReactElement |
           onChange={(value) => updateParam(module.id, 'spread', value)}
           format={formatDecimal2}
         />
+        <ToggleGroup>{wetOnlyToggle}</ToggleGroup>
       </>
     )
   }
@@ -341,6 +353,7 @@ export function renderEffectControls(props: ControlProps): React.ReactElement |
             value={pingPong}
             onChange={(value) => updateParam(module.id, 'pingPong', value)}
           />
+          {wetOnlyToggle}
         </ToggleGroup>
       </>
     )
@@ -520,6 +533,27 @@ export function renderEffectControls(props: ControlProps): React.ReactElement |
           onChange={(value) => updateParam(module.id, 'drive', value)}
           format={(value) => `${Math.round(value * 100)}%`}
         />
+        <RotaryKnob
+          label="Diffuse"
+          min={0}
+          max={1}
+          step={0.01}
+          value={Number(module.params.diffusion ?? 0.5)}
+          onChange={(value) => updateParam(module.id, 'diffusion', value)}
+          format={(value) => `${Math.round(value * 100)}%`}
+        />
+        <ControlBox label="Springs" compact>
+          <ControlButtons
+            options={[
+              { id: 1, label: '1' },
+              { id: 2, label: '2' },
+              { id: 3, label: '3' },
+              { id: 4, label: '4' },
+            ]}
+            value={Number(module.params['spring-count'] ?? 3)}
+            onChange={(value) => updateParam(module.id, 'spring-count', value)}
+          />
+        </ControlBox>
       </>
     )
   }
@@ -564,6 +598,7 @@ export function renderEffectControls(props: ControlProps): React.ReactElement |
           onChange={(value) => updateParam(module.id, 'mix', value)}
           format={formatDecimal2}
         />
+        <ToggleGroup>{wetOnlyToggle}</ToggleGroup>
       </>
     )
   }
@@ -608,6 +643,34 @@ export function renderEffectControls(props: ControlProps): React.ReactElement |
           onChange={(value) => updateParam(module.id, 'mix', value)}
           format={formatDecimal2}
         />
+        <RotaryKnob
+          label="Center"
+          min={100}
+          max={4000}
+          step={1}
+          unit="Hz"
+          value={Number(module.params.center ?? 800)}
+          onChange={(value) => updateParam(module.id, 'center', value)}
+          format={formatInt}
+        />
+        <RotaryKnob
+          label="Stereo"
+          min={0}
+          max={0.5}
+          step={0.01}
+          value={Number(module.params.stereo ?? 0)}
+          onChange={(value) => updateParam(module.id, 'stereo', value)}
+          format={(value) => `${Math.round(value * 360)}°`}
+        />
+        <ControlBox label="Stages" compact>
+          <ControlButtons
+            options={[2, 4, 6, 8, 10, 12].map((stages) => ({ id: stages, label: String(stages) }))}
+            value={Number(module.params.stages ?? 4)}
+            onChange={(value) => updateParam(module.id, 'stages', value)}
+            columns={6}
+          />
+        </ControlBox>
+        <ToggleGroup>{wetOnlyToggle}</ToggleGroup>
       </>
     )
   }
@@ -648,11 +711,24 @@ export function renderEffectControls(props: ControlProps): React.ReactElement |
               { id: 'soft', label: 'SOFT' },
               { id: 'hard', label: 'HARD' },
               { id: 'fold', label: 'FOLD' },
+              { id: 'asymmetric', label: 'ASYM' },
+              { id: 'tube', label: 'TUBE' },
             ]}
             value={String(module.params.mode ?? 'soft')}
             onChange={(value) => updateParam(module.id, 'mode', value)}
           />
         </ControlBox>
+        {(module.params.mode === 'asymmetric' || module.params.mode === 'tube') && (
+          <RotaryKnob
+            label="Bias"
+            min={-1}
+            max={1}
+            step={0.01}
+            value={Number(module.params.bias ?? 0)}
+            onChange={(value) => updateParam(module.id, 'bias', value)}
+            format={formatDecimal2}
+          />
+        )}
       </>
     )
   }
@@ -700,6 +776,53 @@ export function renderEffectControls(props: ControlProps): React.ReactElement |
     )
   }
 
+  if (module.type === 'bitcrush') {
+    return (
+      <>
+        <RotaryKnob
+          label="Bits"
+          min={1}
+          max={16}
+          step={0.1}
+          value={Number(module.params.bits ?? 8)}
+          onChange={(value) => updateParam(module.id, 'bits', value)}
+          format={formatDecimal1}
+        />
+        <RotaryKnob
+          label="Down"
+          min={1}
+          max={64}
+          step={0.5}
+          unit="x"
+          value={Number(module.params.downsample ?? 1)}
+          onChange={(value) => updateParam(module.id, 'downsample', value)}
+          format={formatDecimal1}
+        />
+        <RotaryKnob
+          label="Mix"
+          min={0}
+          max={1}
+          step={0.01}
+          value={Number(module.params.mix ?? 1)}
+          onChange={(value) => updateParam(module.id, 'mix', value)}
+          format={formatPercent}
+        />
+        <ToggleGroup>
+          <ToggleButton
+            label="AA"
+            value={Boolean(module.params.antialias)}
+            onChange={(value) => updateParam(module.id, 'antialias', value)}
+          />
+          <ToggleButton
+            label="Dither"
+            value={Boolean(module.params.dither)}
+            onChange={(value) => updateParam(module.id, 'dither', value)}
+          />
+        </ToggleGroup>
+      </>
+    )
+  }
+
   if (module.type === 'pitch-shifter') {
     return (
       <>
@@ -795,6 +918,16 @@ export function renderEffectControls(props: ControlProps): React.ReactElement |
           onChange={(value) => updateParam(module.id, 'release', value)}
           format={formatInt}
         />
+        <RotaryKnob
+          label="Knee"
+          min={0}
+          max={24}
+          step={0.5}
+          unit="dB"
+          value={Number(module.params.knee ?? 0)}
+          onChange={(value) => updateParam(module.id, 'knee', value)}
+          format={formatDecimal1}
+        />
         <RotaryKnob
           label="Makeup"
           min={-24}
@@ -817,6 +950,80 @@ export function renderEffectControls(props: ControlProps): React.ReactElement |
           onChange={(value) => updateParam(module.id, 'mix', value)}
           format={formatPercent}
         />
+        <ControlBox label="Detect" compact>
+          <ControlButtons
+            options={[
+              { id: 0, label: 'Peak' },
+              { id: 1, label: 'RMS' },
+            ]}
+            value={Number(module.params.detector ?? 0)}
+            onChange={(value) => updateParam(module.id, 'detector', value)}
+          />
+        </ControlBox>
+        <ToggleGroup>
+          <ToggleButton
+            label="Sidechain"
+            value={Boolean(module.params.sidechain ?? 1)}
+            onChange={(value) => updateParam(module.id, 'sidechain', value)}
+          />
+        </ToggleGroup>
+      </>
+    )
+  }
+
+  if (module.type === 'noise-gate') {
+    return (
+      <>
+        <RotaryKnob
+          label="Thresh"
+          min={-80}
+          max={0}
+          step={0.5}
+          unit="dB"
+          value={Number(module.params.threshold ?? -40)}
+          onChange={(value) => updateParam(module.id, 'threshold', value)}
+          format={formatDecimal1}
+        />
+        <RotaryKnob
+          label="Attack"
+          min={0.1}
+          max={50}
+          step={0.1}
+          unit="ms"
+          value={Number(module.params.attack ?? 1)}
+          onChange={(value) => updateParam(module.id, 'attack', value)}
+          format={formatDecimal1}
+        />
+        <RotaryKnob
+          label="Hold"
+          min={0}
+          max={500}
+          step={1}
+          unit="ms"
+          value={Number(module.params.hold ?? 50)}
+          onChange={(value) => updateParam(module.id, 'hold', value)}
+          format={formatInt}
+        />
+        <RotaryKnob
+          label="Release"
+          min={5}
+          max={2000}
+          step={5}
+          unit="ms"
+          value={Number(module.params.release ?? 100)}
+          onChange={(value) => updateParam(module.id, 'release', value)}
+          format={formatInt}
+        />
+        <RotaryKnob
+          label="Floor"
+          min={-80}
+          max={0}
+          step={1}
+          unit="dB"
+          value={Number(module.params.floor ?? -80)}
+          onChange={(value) => updateParam(module.id, 'floor', value)}
+          format={formatInt}
+        />
       </>
     )
   }
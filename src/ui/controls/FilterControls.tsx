@@ -1,7 +1,7 @@
 /**
  * Filter module controls
  *
- * Modules: vcf, hpf
+ * Modules: vcf, hpf, dc-block
  */
 
 import type React from 'react'
@@ -158,5 +158,20 @@ export function renderFilterControls(props: ControlProps): React.ReactElement |
     )
   }
 
+  if (module.type === 'dc-block') {
+    return (
+      <RotaryKnob
+        label="Cutoff"
+        min={5}
+        max={200}
+        step={1}
+        unit="Hz"
+        value={Number(module.params.cutoff ?? 20)}
+        onChange={(value) => updateParam(module.id, 'cutoff', value)}
+        format={formatInt}
+      />
+    )
+  }
+
   return null
 }
@@ -1,7 +1,7 @@
 /**
  * Modulator module controls
  *
- * Modules: adsr, lfo, mod-router, sample-hold, slew, quantizer
+ * Modules: adsr, lfo, mod-router, atten, sample-hold, slew, env-follower, quantizer, logic, peak-meter, chaos
  */
 
 import type React from 'react'
@@ -10,7 +10,7 @@ import { RotaryKnob } from '../RotaryKnob'
 import { WaveformSelector } from '../WaveformSelector'
 import { ControlBox } from '../ControlBox'
 import { ControlButtons } from '../ControlButtons'
-import { formatDecimal2 } from '../formatters'
+import { formatDecimal2, formatInt, formatMs } from '../formatters'
 
 export function renderModulatorControls(props: ControlProps): React.ReactElement | null {
   const { module, updateParam } = props
@@ -112,65 +112,169 @@ export function renderModulatorControls(props: ControlProps): React.ReactElement
     )
   }
 
-  if (module.type === 'mod-router') {
+  if (module.type === 'atten') {
     return (
       <>
         <RotaryKnob
-          label="Pitch"
+          label="Scale"
           min={-1}
           max={1}
           step={0.01}
-          value={Number(module.params.depthPitch ?? 0)}
-          onChange={(value) => updateParam(module.id, 'depthPitch', value)}
+          value={Number(module.params.scale ?? 1)}
+          onChange={(value) => updateParam(module.id, 'scale', value)}
           format={formatDecimal2}
         />
         <RotaryKnob
-          label="PWM"
-          min={-1}
-          max={1}
+          label="Offset"
+          min={-5}
+          max={5}
           step={0.01}
-          value={Number(module.params.depthPwm ?? 0)}
-          onChange={(value) => updateParam(module.id, 'depthPwm', value)}
+          value={Number(module.params.offset ?? 0)}
+          onChange={(value) => updateParam(module.id, 'offset', value)}
           format={formatDecimal2}
         />
+      </>
+    )
+  }
+
+  if (module.type === 'mod-router') {
+    return (
+      <>
+        <ControlBox label="Depth" horizontal>
+          <RotaryKnob
+            label="Pitch"
+            min={-1}
+            max={1}
+            step={0.01}
+            value={Number(module.params.depthPitch ?? 0)}
+            onChange={(value) => updateParam(module.id, 'depthPitch', value)}
+            format={formatDecimal2}
+          />
+          <RotaryKnob
+            label="PWM"
+            min={-1}
+            max={1}
+            step={0.01}
+            value={Number(module.params.depthPwm ?? 0)}
+            onChange={(value) => updateParam(module.id, 'depthPwm', value)}
+            format={formatDecimal2}
+          />
+          <RotaryKnob
+            label="VCF"
+            min={-1}
+            max={1}
+            step={0.01}
+            value={Number(module.params.depthVcf ?? 0)}
+            onChange={(value) => updateParam(module.id, 'depthVcf', value)}
+            format={formatDecimal2}
+          />
+          <RotaryKnob
+            label="VCA"
+            min={-1}
+            max={1}
+            step={0.01}
+            value={Number(module.params.depthVca ?? 0)}
+            onChange={(value) => updateParam(module.id, 'depthVca', value)}
+            format={formatDecimal2}
+          />
+          <RotaryKnob
+            label="Aux"
+            min={-1}
+            max={1}
+            step={0.01}
+            value={Number(module.params.depthAux ?? 0)}
+            onChange={(value) => updateParam(module.id, 'depthAux', value)}
+            format={formatDecimal2}
+          />
+        </ControlBox>
+        <ControlBox label="Offset" horizontal>
+          <RotaryKnob
+            label="Pitch"
+            min={-1}
+            max={1}
+            step={0.01}
+            value={Number(module.params.offsetPitch ?? 0)}
+            onChange={(value) => updateParam(module.id, 'offsetPitch', value)}
+            format={formatDecimal2}
+          />
+          <RotaryKnob
+            label="PWM"
+            min={-1}
+            max={1}
+            step={0.01}
+            value={Number(module.params.offsetPwm ?? 0)}
+            onChange={(value) => updateParam(module.id, 'offsetPwm', value)}
+            format={formatDecimal2}
+          />
+          <RotaryKnob
+            label="VCF"
+            min={-1}
+            max={1}
+            step={0.01}
+            value={Number(module.params.offsetVcf ?? 0)}
+            onChange={(value) => updateParam(module.id, 'offsetVcf', value)}
+            format={formatDecimal2}
+          />
+          <RotaryKnob
+            label="VCA"
+            min={-1}
+            max={1}
+            step={0.01}
+            value={Number(module.params.offsetVca ?? 0)}
+            onChange={(value) => updateParam(module.id, 'offsetVca', value)}
+            format={formatDecimal2}
+          />
+          <RotaryKnob
+            label="Aux"
+            min={-1}
+            max={1}
+            step={0.01}
+            value={Number(module.params.offsetAux ?? 0)}
+            onChange={(value) => updateParam(module.id, 'offsetAux', value)}
+            format={formatDecimal2}
+          />
+        </ControlBox>
+      </>
+    )
+  }
+
+  if (module.type === 'sample-hold') {
+    const mode = Number(module.params.mode ?? 0) < 0.5 ? 0 : 1
+    return (
+      <>
         <RotaryKnob
-          label="VCF"
-          min={-1}
+          label="Glide"
+          min={0}
           max={1}
           step={0.01}
-          value={Number(module.params.depthVcf ?? 0)}
-          onChange={(value) => updateParam(module.id, 'depthVcf', value)}
+          unit="s"
+          value={Number(module.params.glide ?? 0)}
+          onChange={(value) => updateParam(module.id, 'glide', value)}
           format={formatDecimal2}
         />
         <RotaryKnob
-          label="VCA"
-          min={-1}
+          label="Noise"
+          min={0}
           max={1}
           step={0.01}
-          value={Number(module.params.depthVca ?? 0)}
-          onChange={(value) => updateParam(module.id, 'depthVca', value)}
+          value={Number(module.params.noiseLevel ?? 1)}
+          onChange={(value) => updateParam(module.id, 'noiseLevel', value)}
           format={formatDecimal2}
         />
+        <ControlBox label="Mode">
+          <ControlButtons
+            options={[
+              { id: 0, label: 'Sample' },
+              { id: 1, label: 'Random' },
+            ]}
+            value={mode}
+            onChange={(value) => updateParam(module.id, 'mode', value)}
+          />
+        </ControlBox>
       </>
     )
   }
 
-  if (module.type === 'sample-hold') {
-    const mode = Number(module.params.mode ?? 0) < 0.5 ? 0 : 1
-    return (
-      <ControlBox label="Mode">
-        <ControlButtons
-          options={[
-            { id: 0, label: 'Sample' },
-            { id: 1, label: 'Random' },
-          ]}
-          value={mode}
-          onChange={(value) => updateParam(module.id, 'mode', value)}
-        />
-      </ControlBox>
-    )
-  }
-
   if (module.type === 'slew') {
     return (
       <>
@@ -198,6 +302,42 @@ export function renderModulatorControls(props: ControlProps): React.ReactElement
     )
   }
 
+  if (module.type === 'env-follower') {
+    return (
+      <>
+        <RotaryKnob
+          label="Attack"
+          min={0.001}
+          max={1}
+          step={0.001}
+          unit="s"
+          value={Number(module.params.attack ?? 0.01)}
+          onChange={(value) => updateParam(module.id, 'attack', value)}
+          format={formatDecimal2}
+        />
+        <RotaryKnob
+          label="Release"
+          min={0.01}
+          max={2}
+          step={0.01}
+          unit="s"
+          value={Number(module.params.release ?? 0.2)}
+          onChange={(value) => updateParam(module.id, 'release', value)}
+          format={formatDecimal2}
+        />
+        <RotaryKnob
+          label="Gain"
+          min={0}
+          max={4}
+          step={0.01}
+          value={Number(module.params.gain ?? 1)}
+          onChange={(value) => updateParam(module.id, 'gain', value)}
+          format={formatDecimal2}
+        />
+      </>
+    )
+  }
+
   if (module.type === 'quantizer') {
     const notes = ['C', 'C#', 'D', 'D#', 'E', 'F', 'F#', 'G', 'G#', 'A', 'A#', 'B']
     return (
@@ -232,6 +372,57 @@ export function renderModulatorControls(props: ControlProps): React.ReactElement
     )
   }
 
+  if (module.type === 'logic') {
+    return (
+      <RotaryKnob
+        label="Thresh"
+        min={-1}
+        max={1}
+        step={0.01}
+        value={Number(module.params.threshold ?? 0)}
+        onChange={(value) => updateParam(module.id, 'threshold', value)}
+        format={formatDecimal2}
+      />
+    )
+  }
+
+  if (module.type === 'peak-meter') {
+    return (
+      <>
+        <RotaryKnob
+          label="Attack"
+          min={0.001}
+          max={0.1}
+          step={0.001}
+          unit="ms"
+          value={Number(module.params.attack ?? 0.005)}
+          onChange={(value) => updateParam(module.id, 'attack', value)}
+          format={formatMs}
+        />
+        <RotaryKnob
+          label="Release"
+          min={0.01}
+          max={5}
+          step={0.01}
+          unit="s"
+          value={Number(module.params.release ?? 0.3)}
+          onChange={(value) => updateParam(module.id, 'release', value)}
+          format={formatDecimal2}
+        />
+        <RotaryKnob
+          label="RMS"
+          min={10}
+          max={1000}
+          step={1}
+          unit="ms"
+          value={Number(module.params.rmsWindow ?? 300)}
+          onChange={(value) => updateParam(module.id, 'rmsWindow', value)}
+          format={formatInt}
+        />
+      </>
+    )
+  }
+
   if (module.type === 'chaos') {
     const notes = ['C', 'C#', 'D', 'D#', 'E', 'F', 'F#', 'G', 'G#', 'A', 'A#', 'B']
     return (
@@ -0,0 +1,40 @@
+/**
+ * Send / Return Module Controls
+ *
+ * Aux send level and bus selection; a Return plays every Send on its bus.
+ */
+
+import type { ControlProps } from '../types'
+import { RotaryKnob } from '../../RotaryKnob'
+import { ControlBox } from '../../ControlBox'
+import { ControlButtons } from '../../ControlButtons'
+import { formatDecimal2 } from '../../formatters'
+
+const busOptions = [
+  { id: 'A', label: 'A' },
+  { id: 'B', label: 'B' },
+  { id: 'C', label: 'C' },
+  { id: 'D', label: 'D' },
+]
+
+export function BusControls({ module, updateParam }: ControlProps) {
+  const isSend = module.type === 'send'
+  const bus = String(module.params.bus ?? 'A')
+
+  return (
+    <>
+      <RotaryKnob
+        label={isSend ? 'Send' : 'Level'}
+        min={0}
+        max={1}
+        step={0.01}
+        value={Number(module.params.level ?? (isSend ? 0.5 : 1.0))}
+        onChange={(value) => updateParam(module.id, 'level', value)}
+        format={formatDecimal2}
+      />
+      <ControlBox label="Bus">
+        <ControlButtons options={busOptions} value={bus} onChange={(value) => updateParam(module.id, 'bus', value)} />
+      </ControlBox>
+    </>
+  )
+}
@@ -51,6 +51,12 @@ export function ControlModuleControls({
   const midiInputId = typeof module.params.midiInputId === 'string' ? module.params.midiInputId : ''
   const keyboardEnabled = Boolean(module.params.keyboardEnabled)
   const glideTime = Number(module.params.glide ?? 0)
+  const glideMode = String(module.params.glideMode ?? 'always')
+  const glideCurve = String(module.params.glideCurve ?? 'linear')
+  const voiceDetune = Number(module.params.voiceDetune ?? 0)
+  const voiceSpread = Number(module.params.voiceSpread ?? 0)
+  const refFreq = Number(module.params.refFreq ?? 261.63)
+  const edo = Number(module.params.edo ?? 12)
 
   // Calculate current octave from midiRoot (C4 = 60 -> octave 4)
   const currentOctave = Math.floor(midiRoot / 12) - 1
@@ -108,6 +114,44 @@ export function ControlModuleControls({
           onChange={(value) => updateParam(module.id, 'glide', value)}
           format={formatDecimal2}
         />
+        <RotaryKnob
+          label="Detune"
+          min={0}
+          max={50}
+          step={1}
+          unit="ct"
+          value={voiceDetune}
+          onChange={(value) => updateParam(module.id, 'voiceDetune', value)}
+          format={formatInt}
+        />
+        <RotaryKnob
+          label="Spread"
+          min={0}
+          max={1}
+          step={0.01}
+          value={voiceSpread}
+          onChange={(value) => updateParam(module.id, 'voiceSpread', value)}
+          format={formatDecimal2}
+        />
+        <RotaryKnob
+          label="C4"
+          min={220}
+          max={311.13}
+          step={0.01}
+          unit="Hz"
+          value={refFreq}
+          onChange={(value) => updateParam(module.id, 'refFreq', value)}
+          format={formatDecimal2}
+        />
+        <RotaryKnob
+          label="EDO"
+          min={5}
+          max={72}
+          step={1}
+          value={edo}
+          onChange={(value) => updateParam(module.id, 'edo', Math.round(value))}
+          format={formatInt}
+        />
       </ControlBox>
 
       <ControlBoxRow>
@@ -121,6 +165,24 @@ export function ControlModuleControls({
             onChange={(value) => updateParam(module.id, 'cvMode', value)}
           />
         </ControlBox>
+        <ControlBox label="Glide" compact>
+          <ControlButtons
+            options={[
+              { id: 'always', label: 'Always' },
+              { id: 'legato', label: 'Legato' },
+            ]}
+            value={glideMode}
+            onChange={(value) => updateParam(module.id, 'glideMode', value)}
+          />
+          <ControlButtons
+            options={[
+              { id: 'linear', label: 'Lin' },
+              { id: 'exponential', label: 'Exp' },
+            ]}
+            value={glideCurve}
+            onChange={(value) => updateParam(module.id, 'glideCurve', value)}
+          />
+        </ControlBox>
         <ControlBox label="Voices" compact>
           <ControlButtons
             options={[
@@ -1,7 +1,7 @@
 /**
  * Output Module Controls
  *
- * Simple output level control.
+ * Output level, and how far poly voices are panned apart.
  */
 
 import type { ControlProps } from '../types'
@@ -10,14 +10,25 @@ import { formatDecimal2 } from '../../formatters'
 
 export function OutputControls({ module, updateParam }: ControlProps) {
   return (
-    <RotaryKnob
-      label="Level"
-      min={0}
-      max={1}
-      step={0.01}
-      value={Number(module.params.level ?? 0.8)}
-      onChange={(value) => updateParam(module.id, 'level', value)}
-      format={formatDecimal2}
-    />
+    <>
+      <RotaryKnob
+        label="Level"
+        min={0}
+        max={1}
+        step={0.01}
+        value={Number(module.params.level ?? 0.8)}
+        onChange={(value) => updateParam(module.id, 'level', value)}
+        format={formatDecimal2}
+      />
+      <RotaryKnob
+        label="Spread"
+        min={0}
+        max={1}
+        step={0.01}
+        value={Number(module.params.voiceSpread ?? 0)}
+        onChange={(value) => updateParam(module.id, 'voiceSpread', value)}
+        format={formatDecimal2}
+      />
+    </>
   )
 }
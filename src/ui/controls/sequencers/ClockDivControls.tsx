@@ -0,0 +1,29 @@
+/**
+ * Clock Divider Controls
+ *
+ * Divides or multiplies an incoming clock.
+ */
+
+import type { ControlProps } from '../types'
+import { ControlBox } from '../../ControlBox'
+import { ControlButtons } from '../../ControlButtons'
+
+const ratioOptions = ['/16', '/8', '/4', '/3', '/2', 'x1', 'x2', 'x3', 'x4', 'x8'].map((label, id) => ({
+  id,
+  label,
+}))
+
+export function ClockDivControls({ module, updateParam }: ControlProps) {
+  const ratio = Number(module.params.ratio ?? 4)
+
+  return (
+    <ControlBox label="Ratio">
+      <ControlButtons
+        options={ratioOptions}
+        value={ratio}
+        onChange={(value) => updateParam(module.id, 'ratio', value)}
+        columns={5}
+      />
+    </ControlBox>
+  )
+}
@@ -9,13 +9,14 @@ import type { ControlProps } from '../types'
 import { RotaryKnob } from '../../RotaryKnob'
 import { ToggleButton, ToggleGroup } from '../../ToggleButton'
 import { ControlBox, ControlBoxRow } from '../../ControlBox'
-import { formatInt } from '../../formatters'
+import { formatDecimal2, formatInt } from '../../formatters'
 import { useMidiTrackInfo, useMidiTotalTicks } from './shared/midiHelpers'
 
 export function MidiFileSequencerControls({ module, engine, status, audioMode, nativeSequencer, updateParam }: ControlProps) {
   const enabled = module.params.enabled !== false
   const loopEnabled = module.params.loop !== false
   const tempo = Number(module.params.tempo ?? 120)
+  const tempoScale = Number(module.params.tempoScale ?? 1)
   const gateLength = Number(module.params.gateLength ?? 90)
   const voices = Number(module.params.voices ?? 4)
   const selectedFile = String(module.params.selectedFile ?? '')
@@ -153,6 +154,16 @@ export function MidiFileSequencerControls({ module, engine, status, audioMode, n
             onChange={(value) => updateParam(module.id, 'tempo', value)}
             format={formatInt}
           />
+          <RotaryKnob
+            label="Speed"
+            min={0.25}
+            max={4}
+            step={0.05}
+            unit="x"
+            value={tempoScale}
+            onChange={(value) => updateParam(module.id, 'tempoScale', value)}
+            format={formatDecimal2}
+          />
           <RotaryKnob
             label="Gate"
             min={10}
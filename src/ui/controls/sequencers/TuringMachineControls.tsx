@@ -16,6 +16,7 @@ export function TuringMachineControls({ module, updateParam }: ControlProps) {
   const range = Number(module.params.range ?? 2)
   const scale = Number(module.params.scale ?? 0)
   const root = Number(module.params.root ?? 0)
+  const write = Number(module.params.write ?? 0)
 
   const scaleOptions = [
     { id: 0, label: 'Off' },
@@ -56,9 +57,19 @@ export function TuringMachineControls({ module, updateParam }: ControlProps) {
             format={(v) => `${Math.round(v * 100)}%`}
           />
           <span className="control-hint">
-            {probability < 0.1 ? 'Locked' : probability > 0.9 ? 'Random' : 'Evolving'}
+            {write > 0.5 ? 'Writing' : probability < 0.1 ? 'Locked' : probability > 0.9 ? 'Random' : 'Evolving'}
           </span>
         </ControlBox>
+        <ControlBox label="Write">
+          <ControlButtons
+            options={[
+              { id: 0, label: 'Off' },
+              { id: 1, label: 'On' },
+            ]}
+            value={write > 0.5 ? 1 : 0}
+            onChange={(value) => updateParam(module.id, 'write', value)}
+          />
+        </ControlBox>
       </ControlBoxRow>
 
       <ControlBoxRow>
@@ -66,7 +77,7 @@ export function TuringMachineControls({ module, updateParam }: ControlProps) {
           <RotaryKnob
             label="Length"
             min={2}
-            max={16}
+            max={32}
             step={1}
             value={length}
             onChange={(value) => updateParam(module.id, 'length', Math.round(value))}
@@ -12,6 +12,7 @@ import { StepSequencerControls } from './StepSequencerControls'
 import { DrumSequencerControls } from './DrumSequencerControls'
 import { EuclideanControls } from './EuclideanControls'
 import { ClockControls } from './ClockControls'
+import { ClockDivControls } from './ClockDivControls'
 import { MarioControls } from './MarioControls'
 import { MidiFileSequencerControls } from './MidiFileSequencerControls'
 import { TuringMachineControls } from './TuringMachineControls'
@@ -32,6 +33,8 @@ export function renderSequencerControls(props: ControlProps): React.ReactElement
       return <EuclideanControls {...props} />
     case 'clock':
       return <ClockControls {...props} />
+    case 'clock-div':
+      return <ClockDivControls {...props} />
     case 'mario':
       return <MarioControls {...props} />
     case 'midi-file-sequencer':
@@ -1,7 +1,7 @@
 /**
  * Noise Module Controls
  *
- * Parameters: level, noiseType, stereo, pan
+ * Parameters: level, noiseType, rateDivide, stereo, pan
  */
 
 import type { ControlProps } from '../types'
@@ -32,6 +32,15 @@ export function NoiseControls({ module, updateParam }: ControlProps) {
           onChange={(value) => updateParam(module.id, 'stereo', value)}
           format={formatPercent}
         />
+        <RotaryKnob
+          label="Rate"
+          min={1}
+          max={64}
+          step={1}
+          value={Number(module.params.rateDivide ?? 1)}
+          onChange={(value) => updateParam(module.id, 'rateDivide', value)}
+          format={(v) => `/${Math.round(v)}`}
+        />
         <RotaryKnob
           label="Pan"
           min={-1}
@@ -50,6 +59,8 @@ export function NoiseControls({ module, updateParam }: ControlProps) {
             { id: 'brown', label: 'BRN' },
             { id: 'blue', label: 'BLU' },
             { id: 'violet', label: 'VIO' },
+            { id: 'digital', label: 'DIG' },
+            { id: 'digital-short', label: 'MTL' },
           ]}
           value={String(module.params.noiseType ?? 'white')}
           onChange={(value) => updateParam(module.id, 'noiseType', value)}
@@ -2,7 +2,7 @@
  * VCO (Voltage Controlled Oscillator) Module Controls
  *
  * Main oscillator with sub-oscillator, unison, and FM.
- * Parameters: frequency, detune, pwm, subMix, subOct, unison, fmLin, fmExp, type
+ * Parameters: frequency, detune, pwm, subMix, subOct, unison, stereoSpread, fmLin, fmExp, type, waveMorph, oversample
  */
 
 import type { ControlProps } from '../types'
@@ -10,10 +10,19 @@ import { RotaryKnob } from '../../RotaryKnob'
 import { WaveformSelector } from '../../WaveformSelector'
 import { ControlBox, ControlBoxRow } from '../../ControlBox'
 import { ControlButtons } from '../../ControlButtons'
+import { ToggleButton } from '../../ToggleButton'
 import { formatInt, formatDecimal1, formatDecimal2 } from '../../formatters'
 
+/** Position of each named waveform on the morph scale. */
+const WAVE_POSITIONS: Record<string, number> = { sine: 0, triangle: 1, sawtooth: 2, saw: 2, square: 3 }
+const WAVE_NAMES = ['sine', 'triangle', 'sawtooth', 'square']
+
 export function OscillatorControls({ module, updateParam }: ControlProps) {
   const subOct = Number(module.params.subOct ?? 1)
+  const waveMorph = Boolean(module.params.waveMorph)
+  const oversample = Boolean(module.params.oversample)
+  const waveType = module.params.type ?? 'sawtooth'
+  const wavePosition = typeof waveType === 'number' ? waveType : (WAVE_POSITIONS[String(waveType)] ?? 2)
 
   return (
     <>
@@ -55,6 +64,15 @@ export function OscillatorControls({ module, updateParam }: ControlProps) {
         onChange={(value) => updateParam(module.id, 'subMix', value)}
         format={formatDecimal2}
       />
+      <RotaryKnob
+        label="Spread"
+        min={0}
+        max={1}
+        step={0.01}
+        value={Number(module.params.stereoSpread ?? 0)}
+        onChange={(value) => updateParam(module.id, 'stereoSpread', value)}
+        format={formatDecimal2}
+      />
       <RotaryKnob
         label="FM Lin"
         min={0}
@@ -75,10 +93,32 @@ export function OscillatorControls({ module, updateParam }: ControlProps) {
         onChange={(value) => updateParam(module.id, 'fmExp', value)}
         format={formatDecimal2}
       />
-      <WaveformSelector
-        label="Wave"
-        value={String(module.params.type ?? 'sawtooth')}
-        onChange={(value) => updateParam(module.id, 'type', value)}
+      {waveMorph ? (
+        <RotaryKnob
+          label="Morph"
+          min={0}
+          max={3}
+          step={0.01}
+          value={wavePosition}
+          onChange={(value) => updateParam(module.id, 'type', value)}
+          format={formatDecimal2}
+        />
+      ) : (
+        <WaveformSelector
+          label="Wave"
+          value={typeof waveType === 'number' ? WAVE_NAMES[Math.min(3, Math.max(0, Math.round(waveType)))] : String(waveType)}
+          onChange={(value) => updateParam(module.id, 'type', value)}
+        />
+      )}
+      <ToggleButton
+        label="Morph"
+        value={waveMorph}
+        onChange={(value) => updateParam(module.id, 'waveMorph', value ? 1 : 0)}
+      />
+      <ToggleButton
+        label="2x"
+        value={oversample}
+        onChange={(value) => updateParam(module.id, 'oversample', value ? 1 : 0)}
       />
       <ControlBoxRow>
         <ControlBox label="Sub Oct" compact>
@@ -0,0 +1,128 @@
+/**
+ * Sample Player Module Controls
+ *
+ * Plays a WAV/FLAC file (up to 60 s at 48 kHz) with loop and start/end points.
+ * Parameters: loop, start, end, gain
+ */
+
+import { useCallback, useRef, useState } from 'react'
+import type React from 'react'
+import type { ControlProps } from '../types'
+import { RotaryKnob } from '../../RotaryKnob'
+import { ControlBox } from '../../ControlBox'
+import { ControlButtons } from '../../ControlButtons'
+import { formatDecimal2, formatPercent } from '../../formatters'
+
+type SamplePlayerControlsProps = Pick<ControlProps, 'module' | 'engine' | 'audioMode' | 'nativeGranular' | 'updateParam'>
+
+export function SamplePlayerControls({ module, engine, audioMode, nativeGranular, updateParam }: SamplePlayerControlsProps) {
+  const [fileName, setFileName] = useState<string | null>(null)
+  const [error, setError] = useState<string | null>(null)
+  const [isLoading, setIsLoading] = useState(false)
+  const fileInputRef = useRef<HTMLInputElement>(null)
+  const audioContextRef = useRef<AudioContext | null>(null)
+
+  const isNativeMode = audioMode === 'native' && nativeGranular?.isActive
+
+  const loop = Number(module.params.loop ?? 0)
+  const start = Number(module.params.start ?? 0)
+  const end = Number(module.params.end ?? 1)
+  const gain = Number(module.params.gain ?? 1)
+
+  const handleFileChange = useCallback(async (event: React.ChangeEvent<HTMLInputElement>) => {
+    const file = event.target.files?.[0]
+    if (!file) return
+
+    setIsLoading(true)
+    setError(null)
+    try {
+      const arrayBuffer = await file.arrayBuffer()
+      if (isNativeMode && nativeGranular) {
+        // Native mode: the backend decodes WAV/FLAC itself
+        await nativeGranular.loadSample(module.id, new Uint8Array(arrayBuffer))
+      } else {
+        if (!audioContextRef.current) {
+          audioContextRef.current = new AudioContext()
+        }
+        const audioBuffer = await audioContextRef.current.decodeAudioData(arrayBuffer)
+        const channels = Math.min(audioBuffer.numberOfChannels, 2)
+        const interleaved = new Float32Array(audioBuffer.length * channels)
+        for (let c = 0; c < channels; c++) {
+          const data = audioBuffer.getChannelData(c)
+          for (let i = 0; i < data.length; i++) {
+            interleaved[i * channels + c] = data[i]
+          }
+        }
+        await engine.loadSample(module.id, interleaved, channels, audioBuffer.sampleRate)
+      }
+      setFileName(file.name)
+    } catch (err) {
+      console.error('Failed to load sample:', err)
+      setError(err instanceof Error ? err.message : String(err))
+    } finally {
+      setIsLoading(false)
+      if (fileInputRef.current) {
+        fileInputRef.current.value = ''
+      }
+    }
+  }, [engine, isNativeMode, module.id, nativeGranular])
+
+  return (
+    <>
+      <div className="granular-load-row">
+        <input
+          ref={fileInputRef}
+          type="file"
+          accept=".wav,.flac,audio/wav,audio/flac"
+          style={{ display: 'none' }}
+          onChange={handleFileChange}
+        />
+        <button
+          className="granular-load-btn"
+          onClick={() => fileInputRef.current?.click()}
+          disabled={isLoading}
+          title={error ?? fileName ?? undefined}
+        >
+          {isLoading ? 'Loading...' : error ? 'Error' : fileName ?? 'Load Sample'}
+        </button>
+      </div>
+      <ControlBox label="Mode" compact>
+        <ControlButtons
+          options={[
+            { id: 0, label: '1-SHOT' },
+            { id: 1, label: 'LOOP' },
+          ]}
+          value={loop}
+          onChange={(value) => updateParam(module.id, 'loop', value)}
+        />
+      </ControlBox>
+      <RotaryKnob
+        label="Start"
+        min={0}
+        max={1}
+        step={0.001}
+        value={start}
+        onChange={(value) => updateParam(module.id, 'start', value)}
+        format={formatPercent}
+      />
+      <RotaryKnob
+        label="End"
+        min={0}
+        max={1}
+        step={0.001}
+        value={end}
+        onChange={(value) => updateParam(module.id, 'end', value)}
+        format={formatPercent}
+      />
+      <RotaryKnob
+        label="Gain"
+        min={0}
+        max={2}
+        step={0.01}
+        value={gain}
+        onChange={(value) => updateParam(module.id, 'gain', value)}
+        format={formatDecimal2}
+      />
+    </>
+  )
+}
@@ -1,7 +1,7 @@
 /**
  * Supersaw Module Controls
  *
- * Parameters: frequency, detune, mix
+ * Parameters: frequency, detune, mix, phaseSpread
  */
 
 import type { ControlProps } from '../types'
@@ -40,6 +40,15 @@ export function SupersawControls({ module, updateParam }: ControlProps) {
         onChange={(value) => updateParam(module.id, 'mix', value)}
         format={formatDecimal2}
       />
+      <RotaryKnob
+        label="Phase"
+        min={0}
+        max={1}
+        step={0.01}
+        value={Number(module.params.phaseSpread ?? 1)}
+        onChange={(value) => updateParam(module.id, 'phaseSpread', value)}
+        format={formatDecimal2}
+      />
     </>
   )
 }
@@ -24,6 +24,7 @@ import { SpectralSwarmControls } from './SpectralSwarmControls'
 import { ResonatorControls } from './ResonatorControls'
 import { WavetableControls } from './WavetableControls'
 import { ParticleCloudControls } from './ParticleCloudControls'
+import { SamplePlayerControls } from './SamplePlayerControls'
 
 // Granular is already extracted to its own file
 import { GranularControls } from '../GranularControls'
@@ -101,6 +102,17 @@ export function renderSourceControls(props: ControlProps): React.ReactElement |
         />
       )
 
+    case 'sample-player':
+      return (
+        <SamplePlayerControls
+          module={module}
+          engine={props.engine}
+          audioMode={props.audioMode}
+          nativeGranular={props.nativeGranular}
+          updateParam={props.updateParam}
+        />
+      )
+
     default:
       return null
   }
@@ -123,4 +135,5 @@ export {
   ResonatorControls,
   WavetableControls,
   ParticleCloudControls,
+  SamplePlayerControls,
 }
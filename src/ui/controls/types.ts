@@ -41,12 +41,14 @@ export type NativeSequencerBridge = {
 }
 
 /**
- * Bridge for native granular operations (Tauri mode)
+ * Bridge for native granular and sample-player operations (Tauri mode)
  */
 export type NativeGranularBridge = {
   isActive: boolean
   getGranularPosition: (moduleId: string) => Promise<number>
   loadGranularBuffer: (moduleId: string, data: Float32Array) => Promise<number>
+  /** Load a WAV/FLAC file into a sample-player (decoded natively); resolves to the frame count */
+  loadSample: (moduleId: string, data: Uint8Array) => Promise<number>
 }
 
 /**
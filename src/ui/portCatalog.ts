@@ -35,12 +35,6 @@ const simpleCvProcessor = (): ModulePorts => ({
   outputs: [{ id: 'out', label: 'Out', kind: 'cv', direction: 'out' }],
 })
 
-// Helper for simple oscillators (pitch in -> audio out)
-const pitchToAudio = (): ModulePorts => ({
-  inputs: [{ id: 'pitch', label: 'Pitch', kind: 'cv', direction: 'in' }],
-  outputs: [{ id: 'out', label: 'Out', kind: 'audio', direction: 'out' }],
-})
-
 export const modulePorts: Record<ModuleType, ModulePorts> = {
   oscillator: {
     inputs: [
@@ -68,16 +62,26 @@ export const modulePorts: Record<ModuleType, ModulePorts> = {
       { id: 'pwm', label: 'PWM', kind: 'cv', direction: 'out' },
       { id: 'vcf', label: 'VCF', kind: 'cv', direction: 'out' },
       { id: 'vca', label: 'VCA', kind: 'cv', direction: 'out' },
+      { id: 'aux', label: 'AUX', kind: 'cv', direction: 'out' },
     ],
   },
+  atten: {
+    inputs: [{ id: 'cv-in', label: 'In', kind: 'cv', direction: 'in' }],
+    outputs: [{ id: 'cv-out', label: 'Out', kind: 'cv', direction: 'out' }],
+  },
   'sample-hold': {
     inputs: [
       { id: 'in', label: 'In', kind: 'cv', direction: 'in' },
       { id: 'trig', label: 'Trig', kind: 'sync', direction: 'in' },
+      { id: 'noise', label: 'Noise', kind: 'audio', direction: 'in' },
     ],
     outputs: [{ id: 'out', label: 'Out', kind: 'cv', direction: 'out' }],
   },
   slew: simpleCvProcessor(),
+  'env-follower': {
+    inputs: [{ id: 'in', label: 'In', kind: 'audio', direction: 'in' }],
+    outputs: [{ id: 'out', label: 'Env', kind: 'cv', direction: 'out' }],
+  },
   quantizer: simpleCvProcessor(),
   chaos: {
     inputs: [{ id: 'speed', label: 'Speed', kind: 'cv', direction: 'in' }],
@@ -88,6 +92,26 @@ export const modulePorts: Record<ModuleType, ModulePorts> = {
       { id: 'gate', label: 'Gate', kind: 'gate', direction: 'out' },
     ],
   },
+  'peak-meter': {
+    inputs: [{ id: 'in', label: 'In', kind: 'audio', direction: 'in' }],
+    outputs: [
+      { id: 'out', label: 'Out', kind: 'audio', direction: 'out' },
+      { id: 'peak-cv', label: 'Peak', kind: 'cv', direction: 'out' },
+      { id: 'rms-cv', label: 'RMS', kind: 'cv', direction: 'out' },
+    ],
+  },
+  logic: {
+    inputs: [
+      { id: 'in-a', label: 'A', kind: 'gate', direction: 'in' },
+      { id: 'in-b', label: 'B', kind: 'gate', direction: 'in' },
+    ],
+    outputs: [
+      { id: 'and', label: 'AND', kind: 'gate', direction: 'out' },
+      { id: 'or', label: 'OR', kind: 'gate', direction: 'out' },
+      { id: 'xor', label: 'XOR', kind: 'gate', direction: 'out' },
+      { id: 'compare', label: 'A>T', kind: 'gate', direction: 'out' },
+    ],
+  },
   'ring-mod': {
     inputs: [
       { id: 'in-a', label: 'In A', kind: 'audio', direction: 'in' },
@@ -123,6 +147,7 @@ export const modulePorts: Record<ModuleType, ModulePorts> = {
     outputs: [{ id: 'out', label: 'Out', kind: 'audio', direction: 'out' }],
   },
   hpf: simpleAudioEffect(),
+  'dc-block': simpleAudioEffect(),
   mixer: {
     inputs: [
       { id: 'in-a', label: 'In A', kind: 'audio', direction: 'in' },
@@ -130,7 +155,7 @@ export const modulePorts: Record<ModuleType, ModulePorts> = {
     ],
     outputs: [{ id: 'out', label: 'Out', kind: 'audio', direction: 'out' }],
   },
-  'mixer-1x2': {
+  'mixer-wide': {
     inputs: [
       { id: 'in-a', label: 'In A', kind: 'audio', direction: 'in' },
       { id: 'in-b', label: 'In B', kind: 'audio', direction: 'in' },
@@ -162,6 +187,24 @@ export const modulePorts: Record<ModuleType, ModulePorts> = {
     ],
     outputs: [{ id: 'out', label: 'Out', kind: 'audio', direction: 'out' }],
   },
+  mult: {
+    inputs: [{ id: 'in', label: 'In', kind: 'audio', direction: 'in' }],
+    outputs: [
+      { id: 'out-1', label: '1', kind: 'audio', direction: 'out' },
+      { id: 'out-2', label: '2', kind: 'audio', direction: 'out' },
+      { id: 'out-3', label: '3', kind: 'audio', direction: 'out' },
+      { id: 'out-4', label: '4', kind: 'audio', direction: 'out' },
+    ],
+  },
+  pan: {
+    inputs: [
+      { id: 'in', label: 'In', kind: 'audio', direction: 'in' },
+      { id: 'pan', label: 'Pan', kind: 'cv', direction: 'in' },
+    ],
+    outputs: [{ id: 'out', label: 'Out', kind: 'audio', direction: 'out' }],
+  },
+  'stereo-width': simpleAudioEffect(),
+
   chorus: simpleAudioEffect(),
   ensemble: simpleAudioEffect(),
   choir: {
@@ -187,11 +230,40 @@ export const modulePorts: Record<ModuleType, ModulePorts> = {
   'tape-delay': simpleAudioEffect(),
   'spring-reverb': simpleAudioEffect(),
   reverb: simpleAudioEffect(),
-  phaser: simpleAudioEffect(),
+  phaser: {
+    inputs: [
+      { id: 'in', label: 'In', kind: 'audio', direction: 'in' },
+      { id: 'mod', label: 'Mod', kind: 'cv', direction: 'in' },
+    ],
+    outputs: [{ id: 'out', label: 'Out', kind: 'audio', direction: 'out' }],
+  },
   distortion: simpleAudioEffect(),
   wavefolder: simpleAudioEffect(),
-  compressor: simpleAudioEffect(),
-  supersaw: pitchToAudio(),
+  bitcrush: simpleAudioEffect(),
+  compressor: {
+    inputs: [
+      { id: 'in', label: 'In', kind: 'audio', direction: 'in' },
+      { id: 'sidechain', label: 'SC', kind: 'audio', direction: 'in' },
+    ],
+    outputs: [
+      { id: 'out', label: 'Out', kind: 'audio', direction: 'out' },
+      { id: 'gr', label: 'GR', kind: 'cv', direction: 'out' },
+    ],
+  },
+  'noise-gate': {
+    inputs: [
+      { id: 'in', label: 'In', kind: 'audio', direction: 'in' },
+      { id: 'key', label: 'Key', kind: 'audio', direction: 'in' },
+    ],
+    outputs: [{ id: 'out', label: 'Out', kind: 'audio', direction: 'out' }],
+  },
+  supersaw: {
+    inputs: [
+      { id: 'pitch', label: 'Pitch', kind: 'cv', direction: 'in' },
+      { id: 'gate', label: 'Gate', kind: 'gate', direction: 'in' },
+    ],
+    outputs: [{ id: 'out', label: 'Out', kind: 'audio', direction: 'out' }],
+  },
   karplus: {
     inputs: [
       { id: 'pitch', label: 'Pitch', kind: 'cv', direction: 'in' },
@@ -227,6 +299,7 @@ export const modulePorts: Record<ModuleType, ModulePorts> = {
       { id: 'vel-out', label: 'Vel', kind: 'cv', direction: 'out' },
       { id: 'gate-out', label: 'Gate', kind: 'gate', direction: 'out' },
       { id: 'sync-out', label: 'Sync', kind: 'sync', direction: 'out' },
+      { id: 'pan-out', label: 'Pan', kind: 'cv', direction: 'out' },
     ],
   },
   adsr: {
@@ -304,6 +377,7 @@ export const modulePorts: Record<ModuleType, ModulePorts> = {
       { id: 'gate-out', label: 'Gate', kind: 'gate', direction: 'out' },
       { id: 'velocity-out', label: 'Vel', kind: 'cv', direction: 'out' },
       { id: 'step-out', label: 'Step', kind: 'cv', direction: 'out' },
+      { id: 'slide-out', label: 'Slide', kind: 'gate', direction: 'out' },
     ],
   },
   'tb-303': {
@@ -312,6 +386,8 @@ export const modulePorts: Record<ModuleType, ModulePorts> = {
       { id: 'gate', label: 'Gate', kind: 'gate', direction: 'in' },
       { id: 'velocity', label: 'Vel', kind: 'cv', direction: 'in' },
       { id: 'cutoff-cv', label: 'Cut', kind: 'cv', direction: 'in' },
+      { id: 'accent', label: 'Acc', kind: 'gate', direction: 'in' },
+      { id: 'slide', label: 'Slide', kind: 'gate', direction: 'in' },
     ],
     outputs: [
       { id: 'out', label: 'Out', kind: 'audio', direction: 'out' },
@@ -476,6 +552,15 @@ export const modulePorts: Record<ModuleType, ModulePorts> = {
       { id: 'out', label: 'Out', kind: 'audio', direction: 'out' },
     ],
   },
+  'sample-player': {
+    inputs: [
+      { id: 'gate', label: 'Gate', kind: 'gate', direction: 'in' },
+      { id: 'pitch', label: 'Pitch', kind: 'cv', direction: 'in' },
+    ],
+    outputs: [
+      { id: 'out', label: 'Out', kind: 'audio', direction: 'out' },
+    ],
+  },
   notes: {
     inputs: [],
     outputs: [],
@@ -493,6 +578,13 @@ export const modulePorts: Record<ModuleType, ModulePorts> = {
       { id: 'bar', label: 'Bar', kind: 'sync', direction: 'out' },
     ],
   },
+  'clock-div': {
+    inputs: [
+      { id: 'clock', label: 'Clk', kind: 'sync', direction: 'in' },
+      { id: 'reset', label: 'Rst', kind: 'sync', direction: 'in' },
+    ],
+    outputs: [{ id: 'clock-out', label: 'Out', kind: 'sync', direction: 'out' }],
+  },
   'midi-file-sequencer': {
     inputs: [
       { id: 'clock', label: 'Clk', kind: 'sync', direction: 'in' },
@@ -534,6 +626,7 @@ export const modulePorts: Record<ModuleType, ModulePorts> = {
     inputs: [
       { id: 'clock', label: 'Clk', kind: 'sync', direction: 'in' },
       { id: 'reset', label: 'Rst', kind: 'sync', direction: 'in' },
+      { id: 'prob-cv', label: 'Prob', kind: 'cv', direction: 'in' },
     ],
     outputs: [
       { id: 'cv', label: 'CV', kind: 'cv', direction: 'out' },
@@ -572,4 +665,13 @@ export const modulePorts: Record<ModuleType, ModulePorts> = {
       { id: 'cv-c', label: 'CVC', kind: 'cv', direction: 'out' },
     ],
   },
+  // Aux buses: Sends feed every Return on the same bus (no cable needed)
+  send: {
+    inputs: [{ id: 'in', label: 'In', kind: 'audio', direction: 'in' }],
+    outputs: [{ id: 'out', label: 'Out', kind: 'audio', direction: 'out' }],
+  },
+  return: {
+    inputs: [],
+    outputs: [{ id: 'out', label: 'Out', kind: 'audio', direction: 'out' }],
+  },
 }